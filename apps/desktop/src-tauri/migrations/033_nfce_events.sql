@@ -0,0 +1,28 @@
+-- Migration: 033_nfce_events
+-- Description: Vínculo NFC-e ↔ venda e eventos fiscais (cancelamento 110111 e inutilização)
+-- Created: 2026-10-18
+ALTER TABLE sales ADD COLUMN nfce_access_key TEXT;
+ALTER TABLE sales ADD COLUMN nfce_protocol TEXT;
+ALTER TABLE sales ADD COLUMN nfce_authorized_at TEXT;
+ALTER TABLE sales ADD COLUMN nfce_cancel_protocol TEXT;
+CREATE TABLE IF NOT EXISTS fiscal_events (
+  id TEXT PRIMARY KEY NOT NULL,
+  sale_id TEXT,
+  access_key TEXT,
+  event_type TEXT NOT NULL,
+  -- CANCELLATION, INUTILIZATION
+  sequence INTEGER NOT NULL DEFAULT 1,
+  serie INTEGER,
+  number_start INTEGER,
+  number_end INTEGER,
+  justification TEXT NOT NULL,
+  status_code TEXT,
+  status_message TEXT,
+  protocol TEXT,
+  xml TEXT,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  FOREIGN KEY (sale_id) REFERENCES sales (id) ON DELETE SET NULL
+);
+CREATE INDEX idx_fiscal_events_sale ON fiscal_events(sale_id);
+CREATE INDEX idx_fiscal_events_access_key ON fiscal_events(access_key);
+CREATE INDEX idx_sales_nfce_access_key ON sales(nfce_access_key);
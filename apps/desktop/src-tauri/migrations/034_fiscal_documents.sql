@@ -0,0 +1,28 @@
+-- Migration: 034_fiscal_documents
+-- Description: Armazenamento dos documentos fiscais emitidos (XML autorizado nfeProc)
+-- Created: 2026-10-18
+CREATE TABLE IF NOT EXISTS fiscal_documents (
+  id TEXT PRIMARY KEY NOT NULL,
+  sale_id TEXT,
+  access_key TEXT NOT NULL UNIQUE,
+  model INTEGER NOT NULL DEFAULT 65,
+  serie INTEGER NOT NULL,
+  number INTEGER NOT NULL,
+  emission_type INTEGER NOT NULL DEFAULT 1,
+  -- 1=Normal, 9=Contingência offline
+  environment INTEGER NOT NULL,
+  status TEXT NOT NULL,
+  -- AUTHORIZED, CONTINGENCY, CANCELED, REJECTED
+  protocol TEXT,
+  total REAL NOT NULL DEFAULT 0,
+  xml TEXT NOT NULL,
+  -- nfeProc quando autorizada; NFe assinada enquanto pendente
+  issued_at TEXT NOT NULL,
+  authorized_at TEXT,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  FOREIGN KEY (sale_id) REFERENCES sales (id) ON DELETE SET NULL
+);
+CREATE INDEX idx_fiscal_documents_sale ON fiscal_documents(sale_id);
+CREATE INDEX idx_fiscal_documents_issued ON fiscal_documents(issued_at);
+CREATE INDEX idx_fiscal_documents_status ON fiscal_documents(status);
@@ -0,0 +1,49 @@
+-- Migration: 035_tax_rules
+-- Description: Regras tributárias (ICMS/PIS/COFINS) por NCM/CFOP/regime, vínculo com produtos/categorias e tabela IBPT
+-- Created: 2026-10-18
+ALTER TABLE fiscal_settings ADD COLUMN crt INTEGER NOT NULL DEFAULT 1;
+-- 1=Simples Nacional, 3=Regime Normal
+
+CREATE TABLE IF NOT EXISTS tax_rules (
+  id TEXT PRIMARY KEY NOT NULL,
+  name TEXT NOT NULL,
+  crt INTEGER NOT NULL DEFAULT 1,
+  -- Regime do emitente ao qual a regra se aplica
+  ncm TEXT,
+  -- Prefixo de NCM (NULL = qualquer)
+  cfop TEXT,
+  -- NULL = qualquer
+  icms_origin INTEGER NOT NULL DEFAULT 0,
+  icms_code TEXT NOT NULL,
+  -- CSOSN (102, 500, ...) no Simples; CST (00, 10, 60, ...) no Regime Normal
+  icms_rate REAL NOT NULL DEFAULT 0,
+  icms_base_reduction REAL NOT NULL DEFAULT 0,
+  st_mva REAL NOT NULL DEFAULT 0,
+  st_rate REAL NOT NULL DEFAULT 0,
+  fcp_rate REAL NOT NULL DEFAULT 0,
+  pis_cst TEXT NOT NULL DEFAULT '07',
+  pis_rate REAL NOT NULL DEFAULT 0,
+  cofins_cst TEXT NOT NULL DEFAULT '07',
+  cofins_rate REAL NOT NULL DEFAULT 0,
+  is_active BOOLEAN NOT NULL DEFAULT 1,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX idx_tax_rules_ncm ON tax_rules(ncm);
+
+ALTER TABLE products ADD COLUMN tax_rule_id TEXT REFERENCES tax_rules (id) ON DELETE SET NULL;
+ALTER TABLE categories ADD COLUMN tax_rule_id TEXT REFERENCES tax_rules (id) ON DELETE SET NULL;
+
+-- Alíquotas aproximadas (Lei 12.741/2012 - De Olho no Imposto)
+CREATE TABLE IF NOT EXISTS ibpt_rates (
+  ncm TEXT PRIMARY KEY NOT NULL,
+  description TEXT,
+  federal_rate REAL NOT NULL DEFAULT 0,
+  import_rate REAL NOT NULL DEFAULT 0,
+  state_rate REAL NOT NULL DEFAULT 0,
+  municipal_rate REAL NOT NULL DEFAULT 0,
+  valid_until TEXT,
+  version TEXT,
+  source TEXT,
+  updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,21 @@
+-- Migration: 036_nfe_model55
+-- Description: NF-e modelo 55 (numeração própria, dados fiscais do destinatário e NCM dos produtos)
+-- Created: 2026-10-18
+-- Numeração da NF-e (independente da série da NFC-e)
+ALTER TABLE fiscal_settings ADD COLUMN nfe_serie INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE fiscal_settings ADD COLUMN nfe_next_number INTEGER NOT NULL DEFAULT 1;
+
+-- Destinatário pessoa jurídica (grupo dest)
+ALTER TABLE customers ADD COLUMN cnpj TEXT;
+ALTER TABLE customers ADD COLUMN state_registration TEXT;
+-- IE ou "ISENTO"
+ALTER TABLE customers ADD COLUMN city_code TEXT;
+-- Código IBGE do município (7 dígitos)
+CREATE INDEX IF NOT EXISTS idx_customers_cnpj ON customers(cnpj);
+
+-- NCM do produto (obrigatório no item da NF-e)
+ALTER TABLE products ADD COLUMN ncm TEXT;
+
+-- Documento fiscal emitido a partir de uma ordem de serviço
+ALTER TABLE fiscal_documents ADD COLUMN service_order_id TEXT REFERENCES service_orders(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_fiscal_documents_service_order ON fiscal_documents(service_order_id);
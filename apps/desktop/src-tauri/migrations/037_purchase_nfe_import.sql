@@ -0,0 +1,38 @@
+-- Migration: 037_purchase_nfe_import
+-- Description: Importação de NF-e de compra (código do fornecedor por produto, notas importadas e histórico de custo)
+-- Created: 2026-10-18
+-- Código do produto no cadastro do fornecedor (cProd da NF-e / catálogo)
+CREATE TABLE IF NOT EXISTS product_suppliers (
+  product_id TEXT NOT NULL,
+  supplier_id TEXT NOT NULL,
+  supplier_code TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+  PRIMARY KEY (product_id, supplier_id),
+  FOREIGN KEY (product_id) REFERENCES products (id) ON DELETE CASCADE,
+  FOREIGN KEY (supplier_id) REFERENCES suppliers (id) ON DELETE CASCADE
+);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_product_suppliers_code ON product_suppliers(supplier_id, supplier_code);
+
+-- Notas de compra já lançadas no estoque (impede importação duplicada)
+CREATE TABLE IF NOT EXISTS purchase_invoices (
+  id TEXT PRIMARY KEY NOT NULL,
+  access_key TEXT NOT NULL UNIQUE,
+  supplier_id TEXT NOT NULL,
+  number INTEGER NOT NULL,
+  serie INTEGER NOT NULL,
+  issued_at TEXT NOT NULL,
+  total REAL NOT NULL,
+  items_imported INTEGER NOT NULL DEFAULT 0,
+  items_skipped INTEGER NOT NULL DEFAULT 0,
+  xml TEXT NOT NULL,
+  employee_id TEXT,
+  created_at TEXT NOT NULL DEFAULT (datetime('now')),
+  FOREIGN KEY (supplier_id) REFERENCES suppliers (id),
+  FOREIGN KEY (employee_id) REFERENCES employees (id) ON DELETE SET NULL
+);
+CREATE INDEX IF NOT EXISTS idx_purchase_invoices_supplier ON purchase_invoices(supplier_id);
+
+-- Histórico de preços passa a distinguir venda e custo
+ALTER TABLE price_history ADD COLUMN price_type TEXT NOT NULL DEFAULT 'SALE';
+-- SALE | COST
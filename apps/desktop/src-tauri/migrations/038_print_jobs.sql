@@ -0,0 +1,24 @@
+-- Migration: 038_print_jobs
+-- Description: Fila persistente de impressão (spool) com retentativas para a impressora térmica
+-- Created: 2026-10-18
+CREATE TABLE IF NOT EXISTS print_jobs (
+  id TEXT PRIMARY KEY NOT NULL,
+  kind TEXT NOT NULL,
+  -- RECEIPT, SERVICE_ORDER, ATTENDANT_ORDER, DANFE, TEST
+  reference TEXT,
+  -- Identificação exibida na fila: "Venda #12", "OS #40" ou chave de acesso
+  payload BLOB NOT NULL,
+  -- Bytes ESC/POS prontos para envio
+  status TEXT NOT NULL DEFAULT 'PENDING',
+  -- PENDING, PRINTING, DONE, FAILED, CANCELED
+  attempts INTEGER NOT NULL DEFAULT 0,
+  max_attempts INTEGER NOT NULL DEFAULT 8,
+  last_error TEXT,
+  next_attempt_at TEXT NOT NULL,
+  printed_at TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_print_jobs_status ON print_jobs(status, created_at);
+CREATE INDEX IF NOT EXISTS idx_print_jobs_reference ON print_jobs(reference);
@@ -0,0 +1,57 @@
+-- Migration: 039_pricing_rules
+-- Description: Regras de preço/promoções avaliadas na venda (leve X pague Y, combo, happy hour, atacado...)
+-- Created: 2026-10-18
+CREATE TABLE IF NOT EXISTS pricing_rules (
+  id TEXT PRIMARY KEY NOT NULL,
+  name TEXT NOT NULL,
+  -- Nome exibido no cupom como motivo do desconto
+  kind TEXT NOT NULL,
+  -- BUY_X_PAY_Y, NTH_ITEM_PERCENT, COMBO_PRICE, HAPPY_HOUR, CATEGORY_PERCENT, CUSTOMER_GROUP_PRICE, WHOLESALE
+  product_id TEXT,
+  category_id TEXT,
+  -- Escopo da regra (ambos nulos = todos os produtos)
+  customer_group TEXT,
+  -- Grupo de preço do cliente (CUSTOMER_GROUP_PRICE)
+  quantity REAL NOT NULL DEFAULT 0,
+  -- X do "leve X", N do "N-ésimo item" ou quantidade mínima do atacado
+  pay_quantity REAL NOT NULL DEFAULT 0,
+  -- Y do "pague Y"
+  percent REAL NOT NULL DEFAULT 0,
+  price REAL,
+  -- Preço fixo: do combo (por conjunto) ou unitário (grupo de cliente/atacado)
+  start_time TEXT,
+  end_time TEXT,
+  -- Janela de horário HH:MM (happy hour)
+  weekdays TEXT,
+  -- Dias da semana separados por vírgula (0 = domingo); nulo = todos
+  valid_from TEXT,
+  valid_until TEXT,
+  -- Vigência YYYY-MM-DD (inclusiva)
+  priority INTEGER NOT NULL DEFAULT 0,
+  is_active INTEGER NOT NULL DEFAULT 1,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  FOREIGN KEY (product_id) REFERENCES products (id) ON DELETE CASCADE,
+  FOREIGN KEY (category_id) REFERENCES categories (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_pricing_rules_active ON pricing_rules(is_active, priority);
+
+-- Composição dos combos/kits
+CREATE TABLE IF NOT EXISTS pricing_rule_items (
+  rule_id TEXT NOT NULL,
+  product_id TEXT NOT NULL,
+  quantity REAL NOT NULL DEFAULT 1,
+  PRIMARY KEY (rule_id, product_id),
+  FOREIGN KEY (rule_id) REFERENCES pricing_rules (id) ON DELETE CASCADE,
+  FOREIGN KEY (product_id) REFERENCES products (id) ON DELETE CASCADE
+);
+
+-- Regra aplicada em cada item (relatórios e cupom)
+ALTER TABLE sale_items ADD COLUMN pricing_rule_id TEXT;
+ALTER TABLE sale_items ADD COLUMN discount_reason TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_sale_items_pricing_rule ON sale_items(pricing_rule_id);
+
+-- Grupo de preço do cliente (ex.: ATACADO, FUNCIONARIO)
+ALTER TABLE customers ADD COLUMN price_group TEXT;
@@ -0,0 +1,74 @@
+-- Migration: 040_sale_returns
+-- Description: Devoluções parciais e trocas vinculadas aos itens da venda original, com vale-crédito
+-- Created: 2026-10-18
+CREATE TABLE IF NOT EXISTS sale_returns (
+  id TEXT PRIMARY KEY NOT NULL,
+  sale_id TEXT NOT NULL,
+  customer_id TEXT,
+  employee_id TEXT NOT NULL,
+  cash_session_id TEXT,
+  -- Sessão de caixa em que o reembolso foi feito
+  total REAL NOT NULL,
+  -- Valor dos itens devolvidos (líquido dos descontos da venda)
+  refund_type TEXT NOT NULL,
+  -- PAYMENT (estorno na forma de pagamento), STORE_CREDIT (vale-crédito)
+  refund_method TEXT,
+  -- CASH, PIX, CREDIT, DEBIT, VOUCHER, OTHER (quando PAYMENT)
+  refund_amount REAL NOT NULL DEFAULT 0,
+  -- Valor efetivamente devolvido ao cliente (total menos o abatido na troca)
+  exchange_sale_id TEXT,
+  -- Venda nova da troca, paga em parte com o valor devolvido
+  store_credit_id TEXT,
+  reason TEXT,
+  fiscal_access_key TEXT,
+  -- Chave da NF-e de devolução, quando emitida
+  created_at TEXT NOT NULL,
+  FOREIGN KEY (sale_id) REFERENCES sales (id),
+  FOREIGN KEY (customer_id) REFERENCES customers (id) ON DELETE SET NULL,
+  FOREIGN KEY (employee_id) REFERENCES employees (id),
+  FOREIGN KEY (cash_session_id) REFERENCES cash_sessions (id),
+  FOREIGN KEY (exchange_sale_id) REFERENCES sales (id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sale_returns_sale ON sale_returns(sale_id);
+CREATE INDEX IF NOT EXISTS idx_sale_returns_session ON sale_returns(cash_session_id);
+
+CREATE TABLE IF NOT EXISTS sale_return_items (
+  id TEXT PRIMARY KEY NOT NULL,
+  return_id TEXT NOT NULL,
+  sale_item_id TEXT NOT NULL,
+  product_id TEXT NOT NULL,
+  product_name TEXT NOT NULL,
+  lot_id TEXT,
+  -- Lote em que o item voltou ao estoque
+  quantity REAL NOT NULL,
+  unit_price REAL NOT NULL,
+  -- Preço unitário líquido pago na venda
+  total REAL NOT NULL,
+  restocked INTEGER NOT NULL DEFAULT 1,
+  -- 0 = item avariado, não volta ao estoque
+  created_at TEXT NOT NULL,
+  FOREIGN KEY (return_id) REFERENCES sale_returns (id) ON DELETE CASCADE,
+  FOREIGN KEY (sale_item_id) REFERENCES sale_items (id),
+  FOREIGN KEY (product_id) REFERENCES products (id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sale_return_items_return ON sale_return_items(return_id);
+CREATE INDEX IF NOT EXISTS idx_sale_return_items_sale_item ON sale_return_items(sale_item_id);
+
+-- Vale-crédito gerado por devolução
+CREATE TABLE IF NOT EXISTS store_credits (
+  id TEXT PRIMARY KEY NOT NULL,
+  code TEXT NOT NULL UNIQUE,
+  -- EAN-13 com prefixo GS1 980 (recibos de reembolso)
+  customer_id TEXT,
+  return_id TEXT,
+  amount REAL NOT NULL,
+  balance REAL NOT NULL,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  FOREIGN KEY (customer_id) REFERENCES customers (id) ON DELETE SET NULL,
+  FOREIGN KEY (return_id) REFERENCES sale_returns (id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_store_credits_customer ON store_credits(customer_id);
@@ -0,0 +1,70 @@
+-- Migration: 041_customer_credit
+-- Description: Crediário (fiado): limite por cliente, parcelas a receber com juros/multa e recebimentos no caixa
+-- Created: 2026-10-18
+CREATE TABLE IF NOT EXISTS customer_credit_accounts (
+  customer_id TEXT PRIMARY KEY NOT NULL,
+  credit_limit REAL NOT NULL DEFAULT 0,
+  interest_rate REAL NOT NULL DEFAULT 0,
+  -- Juros de mora em % ao mês (pro rata dia)
+  fine_percent REAL NOT NULL DEFAULT 0,
+  -- Multa em % sobre a parcela em atraso (cobrada uma vez)
+  grace_days INTEGER NOT NULL DEFAULT 0,
+  -- Carência: pago até N dias após o vencimento não tem encargos
+  default_installments INTEGER NOT NULL DEFAULT 1,
+  installment_interval_days INTEGER NOT NULL DEFAULT 30,
+  is_blocked INTEGER NOT NULL DEFAULT 0,
+  notes TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  FOREIGN KEY (customer_id) REFERENCES customers (id) ON DELETE CASCADE
+);
+
+-- Parcelas a receber (uma linha por parcela)
+CREATE TABLE IF NOT EXISTS receivables (
+  id TEXT PRIMARY KEY NOT NULL,
+  customer_id TEXT NOT NULL,
+  sale_id TEXT,
+  installment_number INTEGER NOT NULL DEFAULT 1,
+  installment_count INTEGER NOT NULL DEFAULT 1,
+  amount REAL NOT NULL,
+  paid_amount REAL NOT NULL DEFAULT 0,
+  -- Principal já pago
+  due_date TEXT NOT NULL,
+  -- YYYY-MM-DD
+  status TEXT NOT NULL DEFAULT 'OPEN',
+  -- OPEN, PAID, CANCELED
+  interest_paid_until TEXT,
+  -- Juros quitados até esta data (recebimento parcial)
+  fine_charged INTEGER NOT NULL DEFAULT 0,
+  paid_at TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  FOREIGN KEY (customer_id) REFERENCES customers (id),
+  FOREIGN KEY (sale_id) REFERENCES sales (id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_receivables_customer ON receivables(customer_id, status);
+CREATE INDEX IF NOT EXISTS idx_receivables_due ON receivables(status, due_date);
+CREATE INDEX IF NOT EXISTS idx_receivables_sale ON receivables(sale_id);
+
+-- Recebimentos de parcelas (entram na sessão de caixa)
+CREATE TABLE IF NOT EXISTS receivable_payments (
+  id TEXT PRIMARY KEY NOT NULL,
+  customer_id TEXT NOT NULL,
+  receivable_id TEXT NOT NULL,
+  amount REAL NOT NULL,
+  -- Principal abatido
+  interest REAL NOT NULL DEFAULT 0,
+  fine REAL NOT NULL DEFAULT 0,
+  total REAL NOT NULL,
+  method TEXT NOT NULL,
+  cash_session_id TEXT,
+  employee_id TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  FOREIGN KEY (customer_id) REFERENCES customers (id),
+  FOREIGN KEY (receivable_id) REFERENCES receivables (id),
+  FOREIGN KEY (cash_session_id) REFERENCES cash_sessions (id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_receivable_payments_customer ON receivable_payments(customer_id);
+CREATE INDEX IF NOT EXISTS idx_receivable_payments_session ON receivable_payments(cash_session_id);
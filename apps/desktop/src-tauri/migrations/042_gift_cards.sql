@@ -0,0 +1,38 @@
+-- Migration: 042_gift_cards
+-- Description: Vale-presente e vale-crédito como forma de pagamento, com histórico de movimentações
+-- Created: 2026-10-18
+ALTER TABLE store_credits ADD COLUMN kind TEXT NOT NULL DEFAULT 'STORE_CREDIT';
+-- STORE_CREDIT (devolução), GIFT_CARD (vale-presente vendido)
+ALTER TABLE store_credits ADD COLUMN is_active INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE store_credits ADD COLUMN expires_at TEXT;
+-- YYYY-MM-DD (NULL = sem validade)
+
+-- Movimentações do saldo (crédito positivo, débito negativo)
+CREATE TABLE IF NOT EXISTS store_credit_transactions (
+  id TEXT PRIMARY KEY NOT NULL,
+  store_credit_id TEXT NOT NULL,
+  type TEXT NOT NULL,
+  -- ISSUE, REDEEM, REVERSAL
+  amount REAL NOT NULL,
+  balance_after REAL NOT NULL,
+  sale_id TEXT,
+  return_id TEXT,
+  payment_method TEXT,
+  -- Forma de pagamento da venda do vale-presente
+  cash_session_id TEXT,
+  employee_id TEXT,
+  created_at TEXT NOT NULL,
+  FOREIGN KEY (store_credit_id) REFERENCES store_credits (id),
+  FOREIGN KEY (sale_id) REFERENCES sales (id),
+  FOREIGN KEY (cash_session_id) REFERENCES cash_sessions (id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_store_credit_tx_credit ON store_credit_transactions(store_credit_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_store_credit_tx_sale ON store_credit_transactions(sale_id);
+CREATE INDEX IF NOT EXISTS idx_store_credit_tx_session ON store_credit_transactions(cash_session_id);
+
+-- Emissões anteriores (vales de devolução)
+INSERT INTO store_credit_transactions (id, store_credit_id, type, amount, balance_after, return_id, cash_session_id, employee_id, created_at)
+SELECT sc.id || '-issue', sc.id, 'ISSUE', sc.amount, sc.amount, sc.return_id, sr.cash_session_id, sr.employee_id, sc.created_at
+FROM store_credits sc
+LEFT JOIN sale_returns sr ON sr.id = sc.return_id;
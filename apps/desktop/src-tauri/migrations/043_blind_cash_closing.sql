@@ -0,0 +1,40 @@
+-- Migration: 043_blind_cash_closing
+-- Description: Fechamento cego do caixa: contagem por cédula/moeda e por forma de pagamento, aprovação do gerente acima da tolerância
+-- Created: 2026-10-18
+
+-- Sessões com diferença acima da tolerância ficam em PENDING_APPROVAL
+ALTER TABLE cash_sessions ADD COLUMN approved_by_id TEXT REFERENCES employees (id);
+ALTER TABLE cash_sessions ADD COLUMN approved_at TEXT;
+
+-- Contagem por forma de pagamento (esperado calculado no momento do fechamento)
+CREATE TABLE IF NOT EXISTS cash_closing_counts (
+  id TEXT PRIMARY KEY NOT NULL,
+  session_id TEXT NOT NULL,
+  method TEXT NOT NULL,
+  -- CASH, PIX, DEBIT, CREDIT, VOUCHER, OTHER
+  expected_amount REAL NOT NULL DEFAULT 0,
+  counted_amount REAL NOT NULL DEFAULT 0,
+  difference REAL NOT NULL DEFAULT 0,
+  created_at TEXT NOT NULL,
+  FOREIGN KEY (session_id) REFERENCES cash_sessions (id) ON DELETE CASCADE,
+  UNIQUE (session_id, method)
+);
+
+-- Cédulas e moedas contadas na gaveta
+CREATE TABLE IF NOT EXISTS cash_closing_denominations (
+  id TEXT PRIMARY KEY NOT NULL,
+  session_id TEXT NOT NULL,
+  denomination REAL NOT NULL,
+  quantity INTEGER NOT NULL DEFAULT 0,
+  amount REAL NOT NULL DEFAULT 0,
+  FOREIGN KEY (session_id) REFERENCES cash_sessions (id) ON DELETE CASCADE,
+  UNIQUE (session_id, denomination)
+);
+
+CREATE INDEX IF NOT EXISTS idx_cash_closing_counts_session ON cash_closing_counts(session_id);
+CREATE INDEX IF NOT EXISTS idx_cash_closing_denominations_session ON cash_closing_denominations(session_id);
+
+INSERT OR IGNORE INTO settings (id, key, value, type, group_name, description, created_at, updated_at)
+VALUES
+    (lower(hex(randomblob(16))), 'cash.blind_closing', 'false', 'BOOLEAN', 'cash', 'Fechamento cego: operador não vê o valor esperado', datetime('now'), datetime('now')),
+    (lower(hex(randomblob(16))), 'cash.closing_tolerance', '5.00', 'NUMBER', 'cash', 'Diferença máxima por forma de pagamento sem aprovação do gerente (R$)', datetime('now'), datetime('now'));
@@ -0,0 +1,8 @@
+-- Migration: 044_sale_payment_card
+-- Description: Dados do cartão por pagamento (TEF/POS) para o grupo card do detPag da NFC-e
+-- Created: 2026-10-18
+
+ALTER TABLE sale_payments ADD COLUMN card_integration INTEGER; -- 1=TEF, 2=POS
+ALTER TABLE sale_payments ADD COLUMN acquirer_cnpj TEXT;
+ALTER TABLE sale_payments ADD COLUMN card_brand TEXT; -- tBand
+ALTER TABLE sale_payments ADD COLUMN authorization_code TEXT; -- cAut
@@ -0,0 +1,7 @@
+-- Migration: 045_nfce_cancel_window
+-- Description: Prazo de cancelamento da NFC-e configurável (varia por UF)
+-- Created: 2026-10-18
+
+INSERT OR IGNORE INTO settings (id, key, value, type, group_name, description, created_at, updated_at)
+VALUES
+    (lower(hex(randomblob(16))), 'fiscal.nfce_cancel_window_minutes', '30', 'NUMBER', 'fiscal', 'Prazo para cancelamento da NFC-e após a autorização (minutos, conforme a UF)', datetime('now'), datetime('now'));
@@ -11,6 +11,7 @@ mod tests {
             commands::get_products,
             commands::get_product_by_id,
             commands::get_product_by_barcode,
+            commands::scan_product_barcode,
             commands::search_products,
             commands::get_low_stock_products,
             commands::create_product,
@@ -67,6 +68,10 @@ mod tests {
             commands::close_cash_session,
             commands::add_cash_movement,
             commands::get_cash_session_summary,
+            commands::close_cash_session_blind,
+            commands::approve_cash_closing,
+            commands::get_pending_cash_closings,
+            commands::get_cash_closing_report,
             // Stock
             commands::get_recent_stock_movements,
             commands::get_product_stock_movements,
@@ -74,6 +79,9 @@ mod tests {
             commands::get_product_lots,
             commands::get_expiring_lots,
             commands::get_expired_lots,
+            commands::preview_purchase_nfe,
+            commands::import_purchase_nfe,
+            commands::get_purchase_invoices,
             // Suppliers
             commands::get_suppliers,
             commands::get_supplier_by_id,
@@ -148,6 +156,30 @@ mod tests {
             commands::get_price_history_by_product,
             commands::get_recent_price_history,
             commands::get_price_history_by_id,
+            // Promoções
+            commands::get_pricing_rules,
+            commands::save_pricing_rule,
+            commands::delete_pricing_rule,
+            commands::preview_sale_pricing,
+            commands::get_customer_price_group,
+            commands::set_customer_price_group,
+            // Devoluções e Trocas
+            commands::get_returnable_sale_items,
+            commands::get_sale_returns,
+            commands::create_sale_return,
+            // Vale-Crédito e Vale-Presente
+            commands::get_store_credit,
+            commands::get_store_credit_by_code,
+            commands::get_customer_store_credits,
+            commands::get_store_credit_transactions,
+            commands::issue_gift_card,
+            commands::set_store_credit_active,
+            // Crediário (Fiado)
+            commands::get_credit_account,
+            commands::save_credit_account,
+            commands::get_customer_statement,
+            commands::get_overdue_receivables,
+            commands::receive_account_payment,
             // System
             commands::get_app_data_path,
             commands::get_database_path,
@@ -197,10 +229,21 @@ mod tests {
             commands::print_sale_by_id,
             commands::print_service_order,
             commands::print_attendant_order,
+            commands::print_customer_statement,
+            commands::print_store_credit,
+            commands::print_cash_closing_report,
+            commands::list_print_jobs,
+            commands::reprint_print_job,
+            commands::cancel_print_job,
             commands::test_printer,
             commands::test_printer_connection,
             commands::print_test_documents,
             commands::get_printer_config,
+            commands::get_printer_sensors,
+            commands::get_receipt_template,
+            commands::save_receipt_template,
+            commands::preview_receipt_template,
+            commands::set_printer_logo,
             commands::configure_scale,
             commands::load_hardware_configs,
             commands::hardware_health_check,
@@ -220,6 +263,24 @@ mod tests {
             commands::get_scanner_server_info,
             commands::generate_pairing_qr,
             commands::generate_qr_svg,
+            commands::parse_barcode,
+            commands::start_scale_stream,
+            commands::stop_scale_stream,
+            commands::set_scale_unit_price,
+            commands::get_weighted_barcode_config,
+            commands::save_weighted_barcode_config,
+            commands::get_scale_catalog_config,
+            commands::save_scale_catalog_config,
+            commands::export_scale_catalog,
+            commands::start_demo_hardware,
+            commands::stop_demo_hardware,
+            commands::get_demo_hardware,
+            commands::set_demo_scale_weight,
+            commands::configure_label_printer,
+            commands::get_label_printer_config,
+            commands::test_label_printer,
+            commands::print_product_labels,
+            commands::print_price_change_labels,
             // Backups
             commands::backup::create_backup,
             commands::backup::restore_backup,
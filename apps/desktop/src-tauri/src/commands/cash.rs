@@ -1,13 +1,14 @@
 //! Comandos Tauri para Caixa
 
 use crate::audit_log;
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
 use crate::middleware::audit::{AuditAction, AuditService};
-use crate::middleware::Permission;
+use crate::middleware::{check_permission, Permission};
 use crate::models::{
-    CashMovement, CashSession, CashSessionSummary, CreateCashMovement, CreateCashSession,
+    BlindCashClosing, CashClosingReport, CashMovement, CashSession, CashSessionSummary,
+    CreateCashMovement, CreateCashSession,
 };
-use crate::repositories::CashRepository;
+use crate::repositories::{CashRepository, SettingsRepository};
 use crate::require_permission;
 use crate::AppState;
 use tauri::State;
@@ -97,6 +98,17 @@ pub async fn close_cash_session(
     let info = state.session.require_authenticated()?;
     let employee_id = info.employee_id;
     let employee = require_permission!(state.pool(), &employee_id, Permission::CloseCash);
+
+    // Com o fechamento cego ativo a contagem passa pela conferência e aprovação
+    if SettingsRepository::new(state.pool())
+        .get_bool("cash.blind_closing")
+        .await?
+    {
+        return Err(AppError::BusinessRule(
+            "Fechamento cego ativo: feche o caixa pela contagem".into(),
+        ));
+    }
+
     let repo = CashRepository::new(state.pool());
     let result = repo.close_session(&id, actual_balance, notes).await?;
 
@@ -155,6 +167,153 @@ pub async fn get_cash_session_summary(
     session_id: String,
     state: State<'_, AppState>,
 ) -> AppResult<CashSessionSummary> {
+    let info = state.session.require_authenticated()?;
+    require_permission!(
+        state.pool(),
+        &info.employee_id,
+        Permission::ViewCashMovements
+    );
     let repo = CashRepository::new(state.pool());
-    repo.get_session_summary(&session_id).await
+    let mut summary = repo.get_session_summary(&session_id).await?;
+
+    // No fechamento cego o operador não vê o esperado enquanto o caixa está aberto
+    let blind = SettingsRepository::new(state.pool())
+        .get_bool("cash.blind_closing")
+        .await?;
+    if blind
+        && summary.session.status == "OPEN"
+        && check_permission(
+            state.pool(),
+            &info.employee_id,
+            Permission::ApproveCashClosing,
+        )
+        .await
+        .is_err()
+    {
+        summary.hide_expected();
+    }
+
+    Ok(summary)
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// FECHAMENTO CEGO
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Fecha o caixa pela contagem de cédulas/moedas e das formas eletrônicas.
+/// Diferença acima de `cash.closing_tolerance` fica aguardando o gerente.
+#[tauri::command]
+#[specta::specta]
+pub async fn close_cash_session_blind(
+    input: BlindCashClosing,
+    state: State<'_, AppState>,
+) -> AppResult<CashSession> {
+    let info = state.session.require_authenticated()?;
+    let employee = require_permission!(state.pool(), &info.employee_id, Permission::CloseCash);
+    let tolerance = SettingsRepository::new(state.pool())
+        .get_number("cash.closing_tolerance")
+        .await?
+        .unwrap_or(0.0);
+
+    let repo = CashRepository::new(state.pool());
+    let session_id = input.session_id.clone();
+    let result = repo.close_session_blind(input, tolerance).await?;
+
+    let audit_service = AuditService::new(state.pool().clone());
+    audit_log!(
+        audit_service,
+        AuditAction::CashSessionClosed,
+        &employee.id,
+        &employee.name,
+        "CashSession",
+        &session_id,
+        format!(
+            "Fechamento cego: Contado {:.2}, Diferença {:.2}, Status {}",
+            result.actual_balance.unwrap_or(0.0),
+            result.difference.unwrap_or(0.0),
+            result.status
+        )
+    );
+
+    Ok(result)
+}
+
+/// Gerente aprova a diferença de um fechamento pendente
+#[tauri::command]
+#[specta::specta]
+pub async fn approve_cash_closing(
+    id: String,
+    notes: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<CashSession> {
+    let info = state.session.require_authenticated()?;
+    let employee = require_permission!(
+        state.pool(),
+        &info.employee_id,
+        Permission::ApproveCashClosing
+    );
+    let repo = CashRepository::new(state.pool());
+    let result = repo.approve_closing(&id, &employee.id, notes).await?;
+
+    let audit_service = AuditService::new(state.pool().clone());
+    audit_log!(
+        audit_service,
+        AuditAction::CashClosingApproved,
+        &employee.id,
+        &employee.name,
+        "CashSession",
+        &id,
+        format!(
+            "Diferença aprovada: {:.2}",
+            result.difference.unwrap_or(0.0)
+        )
+    );
+
+    Ok(result)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_pending_cash_closings(state: State<'_, AppState>) -> AppResult<Vec<CashSession>> {
+    let info = state.session.require_authenticated()?;
+    require_permission!(
+        state.pool(),
+        &info.employee_id,
+        Permission::ApproveCashClosing
+    );
+    let repo = CashRepository::new(state.pool());
+    repo.find_pending_approval().await
+}
+
+/// Relatório Z do fechamento (ou leitura X da sessão aberta)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_cash_closing_report(
+    session_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<CashClosingReport> {
+    let info = state.session.require_authenticated()?;
+    load_closing_report(state.pool(), &info.employee_id, &session_id).await
+}
+
+/// No fechamento cego o esperado só é exibido após a contagem (ou ao gerente)
+pub(crate) async fn load_closing_report(
+    pool: &sqlx::SqlitePool,
+    employee_id: &str,
+    session_id: &str,
+) -> AppResult<CashClosingReport> {
+    let settings = SettingsRepository::new(pool);
+    let tolerance = settings
+        .get_number("cash.closing_tolerance")
+        .await?
+        .unwrap_or(0.0);
+    let report = CashRepository::new(pool)
+        .get_closing_report(session_id, tolerance)
+        .await?;
+
+    if report.summary.session.status == "OPEN" && settings.get_bool("cash.blind_closing").await? {
+        require_permission!(pool, employee_id, Permission::ApproveCashClosing);
+    }
+
+    Ok(report)
 }
@@ -0,0 +1,115 @@
+//! Comandos Tauri para Crediário (Fiado)
+
+use crate::audit_log;
+use crate::error::AppResult;
+use crate::middleware::audit::{AuditAction, AuditService};
+use crate::middleware::Permission;
+use crate::models::{
+    CustomerCreditAccount, CustomerStatement, Receivable, ReceivablePayment, ReceiveAccountPayment,
+    SaveCreditAccount,
+};
+use crate::repositories::{CashRepository, CreditAccountRepository};
+use crate::require_permission;
+use crate::AppState;
+use tauri::State;
+
+/// Conta de crediário do cliente (limite e regras de cobrança)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_credit_account(
+    customer_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Option<CustomerCreditAccount>> {
+    state.session.require_authenticated()?;
+    let repo = CreditAccountRepository::new(state.pool());
+    repo.find_account(&customer_id).await
+}
+
+/// Cria ou atualiza o crediário do cliente
+#[tauri::command]
+#[specta::specta]
+pub async fn save_credit_account(
+    input: SaveCreditAccount,
+    state: State<'_, AppState>,
+) -> AppResult<CustomerCreditAccount> {
+    let info = state.session.require_authenticated()?;
+    let employee =
+        require_permission!(state.pool(), &info.employee_id, Permission::ManageCustomers);
+    let repo = CreditAccountRepository::new(state.pool());
+    let result = repo.save_account(input).await?;
+
+    let audit_service = AuditService::new(state.pool().clone());
+    audit_log!(
+        audit_service,
+        AuditAction::CreditAccountUpdated,
+        &employee.id,
+        &employee.name,
+        "Customer",
+        &result.customer_id,
+        format!(
+            "Limite: {}, Juros: {}% a.m., Multa: {}%, Bloqueado: {}",
+            result.credit_limit, result.interest_rate, result.fine_percent, result.is_blocked
+        )
+    );
+
+    Ok(result)
+}
+
+/// Extrato do crediário: parcelas em aberto com encargos do dia e últimos recebimentos
+#[tauri::command]
+#[specta::specta]
+pub async fn get_customer_statement(
+    customer_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<CustomerStatement> {
+    state.session.require_authenticated()?;
+    let repo = CreditAccountRepository::new(state.pool());
+    repo.get_statement(&customer_id).await
+}
+
+/// Parcelas vencidas além da carência (todos os clientes)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_overdue_receivables(state: State<'_, AppState>) -> AppResult<Vec<Receivable>> {
+    state.session.require_authenticated()?;
+    let repo = CreditAccountRepository::new(state.pool());
+    repo.find_overdue().await
+}
+
+/// Recebe pagamento do crediário no caixa aberto do operador
+#[tauri::command]
+#[specta::specta]
+pub async fn receive_account_payment(
+    mut input: ReceiveAccountPayment,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<ReceivablePayment>> {
+    let info = state.session.require_authenticated()?;
+    let employee = require_permission!(state.pool(), &info.employee_id, Permission::CreateSales);
+    input.employee_id = info.employee_id.clone();
+    input.cash_session_id = CashRepository::new(state.pool())
+        .find_session_by_employee(&info.employee_id)
+        .await?
+        .map(|s| s.id);
+
+    let repo = CreditAccountRepository::new(state.pool());
+    let customer_id = input.customer_id.clone();
+    let result = repo.receive_payment(input).await?;
+
+    let total: f64 = result.iter().map(|p| p.total).sum();
+    let audit_service = AuditService::new(state.pool().clone());
+    audit_log!(
+        audit_service,
+        AuditAction::AccountPaymentReceived,
+        &employee.id,
+        &employee.name,
+        "Customer",
+        &customer_id,
+        format!(
+            "Recebimento fiado: {:.2} em {} parcela(s)",
+            total,
+            result.len()
+        )
+    );
+
+    Ok(result)
+}
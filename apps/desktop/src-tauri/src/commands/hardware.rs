@@ -2,20 +2,32 @@
 //!
 //! Expõe funcionalidades de hardware para o frontend React:
 //! - Impressora térmica
+//! - Impressora de etiquetas (ZPL/EPL/PPLA)
 //! - Balança
 //! - Scanner de código de barras
 //! - Gaveta de dinheiro
+//! - Fila de impressão persistente (cupons, OS, pedidos e DANFE)
 
 use crate::error::AppResult;
 use crate::hardware::device::HardwareDevice;
+use crate::hardware::HardwareManager;
 use crate::hardware::{
     self,
     drawer::{CashDrawer, DrawerConfig},
+    label::{LabelPrinter, LabelPrinterConfig, ProductLabel},
+    print_queue::PrintJobKind,
     printer::{PrinterConfig, Receipt, ThermalPrinter},
-    scale::{Scale, ScaleConfig, ScaleReading},
+    raster::{LogoCache, MonoBitmap, PrinterLogoInfo},
+    receipt_template::{ReceiptTemplate, ReceiptTemplateSetting, TemplateDocument},
+    scale::{Scale, ScaleConfig, ScaleReading, WeightedBarcodeConfig, WEIGHTED_BARCODE_SETTING},
+    scale_catalog::{
+        ScaleCatalogConfig, ScaleCatalogExport, ScaleCatalogItem, SCALE_CATALOG_SETTING,
+    },
     scanner::{MobileDevice, MobileScannerConfig, ScannerServerState},
+    simulator::{DemoHardware, DemoHardwareInfo, SimulatedWeight},
     HardwareError,
 };
+use crate::models::PrintJob;
 use crate::services::mobile_server::MobileServer;
 use crate::AppState;
 use qrcode::render::svg;
@@ -34,6 +46,7 @@ pub struct HardwareState {
     pub printer_config: RwLock<PrinterConfig>,
     pub scale_config: RwLock<ScaleConfig>,
     pub drawer_config: RwLock<DrawerConfig>,
+    pub label_printer_config: RwLock<LabelPrinterConfig>,
     pub scanner_server: RwLock<Option<Arc<ScannerServerState>>>,
     // Handle do task do servidor de scanner (para permitir parada limpa)
     pub scanner_task: RwLock<Option<tokio::task::JoinHandle<()>>>,
@@ -41,6 +54,19 @@ pub struct HardwareState {
     pub scanner_task_id: RwLock<Option<String>>,
     pub scanner_task_started_at: RwLock<Option<f64>>,
     pub mobile_server: RwLock<Option<Arc<MobileServer>>>,
+    // Fila de impressão e status dos dispositivos
+    pub manager: Arc<HardwareManager>,
+    // Logo da loja já convertido para bitmap
+    pub logo_cache: LogoCache,
+    // Simuladores do modo de demonstração
+    pub demo_hardware: RwLock<Option<DemoHardwareSession>>,
+}
+
+/// Modo de demonstração ativo e as configurações que ele substituiu
+pub struct DemoHardwareSession {
+    hardware: DemoHardware,
+    printer_config: PrinterConfig,
+    scale_config: ScaleConfig,
 }
 
 impl Default for HardwareState {
@@ -49,11 +75,15 @@ impl Default for HardwareState {
             printer_config: RwLock::new(PrinterConfig::default()),
             scale_config: RwLock::new(ScaleConfig::default()),
             drawer_config: RwLock::new(DrawerConfig::default()),
+            label_printer_config: RwLock::new(LabelPrinterConfig::default()),
             scanner_server: RwLock::new(None),
             scanner_task: RwLock::new(None),
             scanner_task_id: RwLock::new(None),
             scanner_task_started_at: RwLock::new(None),
             mobile_server: RwLock::new(None),
+            manager: HardwareManager::new(),
+            logo_cache: LogoCache::default(),
+            demo_hardware: RwLock::new(None),
         }
     }
 }
@@ -172,6 +202,7 @@ pub async fn configure_printer(
     state.session.require_authenticated()?;
     let mut printer_config = hw_state.printer_config.write().await;
     *printer_config = config.clone();
+    hw_state.manager.set_printer_config(config.clone()).await;
 
     // Persist to database
     let repo = crate::repositories::SettingsRepository::new(state.pool());
@@ -196,40 +227,22 @@ pub async fn print_receipt(
     app_state: State<'_, AppState>,
 ) -> AppResult<()> {
     app_state.session.require_authenticated()?;
-    let config = {
-        let guard = state.printer_config.read().await;
-        (*guard).clone()
-    };
-
-    if !config.enabled {
-        return Err(HardwareError::NotConfigured("Impressora não habilitada".into()).into());
-    }
-
-    let mut printer = ThermalPrinter::new(config.clone());
-    printer.print_receipt(&receipt);
-    // Clone config to owned value for the thread
-    let printer_config = config.clone();
-
-    if printer_config.connection == crate::hardware::printer::PrinterConnection::Network {
-        printer.print_network().await?;
-    } else {
-        // Run blocking IO in a separate thread
-        tokio::task::spawn_blocking(move || -> AppResult<()> {
-            match printer_config.connection {
-                crate::hardware::printer::PrinterConnection::Usb => {
-                    printer.print_usb()?;
-                }
-                crate::hardware::printer::PrinterConnection::Serial => {
-                    printer.print_serial()?;
-                }
-                _ => {}
+    let template = load_receipt_template(app_state.pool(), TemplateDocument::Receipt).await?;
+    enqueue_printer_job(
+        &state,
+        app_state.pool(),
+        PrintJobKind::Receipt,
+        Some(&format!("Venda #{}", receipt.sale_number)),
+        |printer| match template {
+            Some(template) => {
+                printer.print_template(&template, &receipt);
             }
-            Ok(())
-        })
-        .await
-        .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))??;
-    }
-
+            None => {
+                printer.print_receipt(&receipt);
+            }
+        },
+    )
+    .await?;
     Ok(())
 }
 
@@ -265,8 +278,17 @@ pub async fn print_sale_by_id(
     let company_cnpj = settings_repo.get_value("company.cnpj").await?;
     let company_phone = settings_repo.get_value("company.phone").await?;
 
-    // 3. Construir Recibo
-    // 3. Construir Recibo
+    // 3. Cliente identificado na venda
+    let customer = match sale.sale.customer_id.as_deref() {
+        Some(customer_id) => {
+            crate::repositories::CustomerRepository::new(state.pool())
+                .find_by_id(customer_id)
+                .await?
+        }
+        None => None,
+    };
+
+    // 4. Construir Recibo
     let items = sale
         .items
         .iter()
@@ -277,6 +299,8 @@ pub async fn print_sale_by_id(
             unit: item.product_unit.clone(),
             unit_price: item.unit_price,
             total: item.total,
+            discount: item.discount,
+            discount_reason: item.discount_reason.clone(),
         })
         .collect();
 
@@ -291,6 +315,8 @@ pub async fn print_sale_by_id(
             .clone()
             .unwrap_or_else(|| "Operador".into()),
         date_time: sale.sale.created_at.clone(),
+        customer_name: customer.as_ref().map(|c| c.name.clone()),
+        customer_document: customer.and_then(|c| c.cpf),
         items,
         subtotal: sale.sale.subtotal,
         discount: sale.sale.discount_value,
@@ -300,7 +326,7 @@ pub async fn print_sale_by_id(
         change: sale.sale.change,
     };
 
-    // 4. Imprimir
+    // 5. Imprimir
     print_receipt(receipt, hw_state, state).await
 }
 
@@ -313,83 +339,534 @@ pub async fn print_service_order(
     app_state: State<'_, AppState>,
 ) -> AppResult<()> {
     app_state.session.require_authenticated()?;
-    let config = {
-        let guard = state.printer_config.read().await;
-        (*guard).clone()
+    let template = load_receipt_template(app_state.pool(), TemplateDocument::ServiceOrder).await?;
+    enqueue_printer_job(
+        &state,
+        app_state.pool(),
+        PrintJobKind::ServiceOrder,
+        Some(&format!("OS #{}", os.order_number)),
+        |printer| match template {
+            Some(template) => {
+                printer.print_template(&template, &os);
+            }
+            None => {
+                printer.print_service_order(&os);
+            }
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+/// Imprime pedido do atendente (para cliente apresentar no caixa)
+#[tauri::command]
+#[specta::specta]
+pub async fn print_attendant_order(
+    order: crate::hardware::printer::AttendantOrderReceipt,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<()> {
+    app_state.session.require_authenticated()?;
+    let template =
+        load_receipt_template(app_state.pool(), TemplateDocument::AttendantOrder).await?;
+    enqueue_printer_job(
+        &state,
+        app_state.pool(),
+        PrintJobKind::AttendantOrder,
+        Some(&format!("Pedido {}", order.order_number)),
+        |printer| match template {
+            Some(template) => {
+                printer.print_template(&template, &order);
+            }
+            None => {
+                printer.print_attendant_order(&order);
+            }
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+/// Imprime extrato do crediário (fiado) do cliente
+#[tauri::command]
+#[specta::specta]
+pub async fn print_customer_statement(
+    customer_id: String,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<()> {
+    app_state.session.require_authenticated()?;
+    let statement = crate::repositories::CreditAccountRepository::new(app_state.pool())
+        .get_statement(&customer_id)
+        .await?;
+
+    let settings_repo = crate::repositories::SettingsRepository::new(app_state.pool());
+    let company_name = settings_repo
+        .get_value("company.name")
+        .await?
+        .unwrap_or_else(|| "Minha Empresa".into());
+    let company_address = settings_repo
+        .get_value("company.address")
+        .await?
+        .unwrap_or_else(|| "".into());
+    let company_phone = settings_repo.get_value("company.phone").await?;
+
+    let installments = statement
+        .open_receivables
+        .iter()
+        .map(|r| crate::hardware::printer::StatementInstallment {
+            description: format!("Parcela {}/{}", r.installment_number, r.installment_count),
+            due_date: chrono::NaiveDate::parse_from_str(&r.due_date, "%Y-%m-%d")
+                .map(|d| d.format("%d/%m/%Y").to_string())
+                .unwrap_or_else(|_| r.due_date.clone()),
+            amount_due: r.amount_due,
+            overdue: r.days_overdue > 0,
+        })
+        .collect();
+
+    let receipt = crate::hardware::printer::CustomerStatementReceipt {
+        company_name,
+        company_address,
+        company_phone,
+        customer_name: statement.customer_name.clone(),
+        date_time: chrono::Local::now().format("%d/%m/%Y %H:%M").to_string(),
+        installments,
+        balance: statement.balance,
+        charges: statement.charges,
+        total_due: statement.total_due,
+        credit_limit: statement
+            .account
+            .as_ref()
+            .map(|a| a.credit_limit)
+            .unwrap_or(0.0),
+        available_credit: statement.available_credit,
     };
 
-    if !config.enabled {
-        return Err(HardwareError::NotConfigured("Impressora não habilitada".into()).into());
-    }
+    enqueue_printer_job(
+        &state,
+        app_state.pool(),
+        PrintJobKind::Statement,
+        Some(&format!("Extrato {}", statement.customer_name)),
+        |printer| {
+            printer.print_customer_statement(&receipt);
+        },
+    )
+    .await?;
+    Ok(())
+}
 
-    let mut printer = ThermalPrinter::new(config.clone());
-    printer.print_service_order(&os);
-    let printer_config = config.clone();
+/// Imprime vale-troca / vale-presente com o saldo atual
+#[tauri::command]
+#[specta::specta]
+pub async fn print_store_credit(
+    id: String,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<()> {
+    app_state.session.require_authenticated()?;
+    let credit = crate::repositories::StoreCreditRepository::new(app_state.pool())
+        .find_by_id(&id)
+        .await?
+        .ok_or_else(|| crate::error::AppError::NotFound {
+            entity: "StoreCredit".into(),
+            id: id.clone(),
+        })?;
 
-    if printer_config.connection == crate::hardware::printer::PrinterConnection::Network {
-        printer.print_network().await?;
+    let customer_name = match credit.customer_id.as_deref() {
+        Some(customer_id) => crate::repositories::CustomerRepository::new(app_state.pool())
+            .find_by_id(customer_id)
+            .await?
+            .map(|c| c.name),
+        None => None,
+    };
+
+    let settings_repo = crate::repositories::SettingsRepository::new(app_state.pool());
+    let company_name = settings_repo
+        .get_value("company.name")
+        .await?
+        .unwrap_or_else(|| "Minha Empresa".into());
+    let company_address = settings_repo
+        .get_value("company.address")
+        .await?
+        .unwrap_or_else(|| "".into());
+    let company_phone = settings_repo.get_value("company.phone").await?;
+
+    let title = if credit.kind == "GIFT_CARD" {
+        "VALE-PRESENTE"
     } else {
-        tokio::task::spawn_blocking(move || -> AppResult<()> {
-            match printer_config.connection {
-                crate::hardware::printer::PrinterConnection::Usb => {
-                    printer.print_usb()?;
-                }
-                crate::hardware::printer::PrinterConnection::Serial => {
-                    printer.print_serial()?;
-                }
-                _ => {}
-            }
-            Ok(())
-        })
-        .await
-        .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))??;
-    }
+        "VALE-TROCA"
+    };
+    let receipt = crate::hardware::printer::StoreCreditReceipt {
+        company_name,
+        company_address,
+        company_phone,
+        title: title.into(),
+        code: credit.code.clone(),
+        amount: credit.amount,
+        balance: credit.balance,
+        customer_name,
+        expires_at: credit.expires_at.as_ref().map(|d| {
+            chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                .map(|d| d.format("%d/%m/%Y").to_string())
+                .unwrap_or_else(|_| d.clone())
+        }),
+        date_time: chrono::Local::now().format("%d/%m/%Y %H:%M").to_string(),
+    };
 
+    enqueue_printer_job(
+        &state,
+        app_state.pool(),
+        PrintJobKind::StoreCredit,
+        Some(&format!("{} {}", title, credit.code)),
+        |printer| {
+            printer.print_store_credit(&receipt);
+        },
+    )
+    .await?;
     Ok(())
 }
 
-/// Imprime pedido do atendente (para cliente apresentar no caixa)
+/// Imprime o relatório de fechamento (Z) ou a leitura parcial (X) do caixa
 #[tauri::command]
 #[specta::specta]
-pub async fn print_attendant_order(
-    order: crate::hardware::printer::AttendantOrderReceipt,
+pub async fn print_cash_closing_report(
+    session_id: String,
     state: State<'_, HardwareState>,
     app_state: State<'_, AppState>,
 ) -> AppResult<()> {
+    let info = app_state.session.require_authenticated()?;
+    let report = crate::commands::cash::load_closing_report(
+        app_state.pool(),
+        &info.employee_id,
+        &session_id,
+    )
+    .await?;
+
+    let settings_repo = crate::repositories::SettingsRepository::new(app_state.pool());
+    let company_name = settings_repo
+        .get_value("company.name")
+        .await?
+        .unwrap_or_else(|| "Minha Empresa".into());
+    let company_address = settings_repo
+        .get_value("company.address")
+        .await?
+        .unwrap_or_else(|| "".into());
+    let company_phone = settings_repo.get_value("company.phone").await?;
+
+    let format_date = |value: &str| {
+        chrono::DateTime::parse_from_rfc3339(value)
+            .map(|d| {
+                d.with_timezone(&chrono::Local)
+                    .format("%d/%m/%Y %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|_| value.to_string())
+    };
+    let method_label = |code: &str| {
+        crate::models::PaymentMethod::from_code(code)
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| code.to_string())
+    };
+
+    let summary = &report.summary;
+    let session = &summary.session;
+    let methods = summary
+        .by_method
+        .iter()
+        .map(|m| {
+            let count = report.counts.iter().find(|c| c.method == m.method);
+            crate::hardware::printer::ClosingMethodLine {
+                label: method_label(&m.method),
+                sales: m.sales,
+                receipts: m.receipts,
+                refunds: m.refunds,
+                expected: count.map(|c| c.expected_amount).unwrap_or(m.expected),
+                counted: count.map(|c| c.counted_amount),
+                difference: count.map(|c| c.difference),
+            }
+        })
+        .collect();
+    let denominations = report
+        .denominations
+        .iter()
+        .map(|d| crate::hardware::printer::ClosingDenominationLine {
+            denomination: d.denomination,
+            quantity: d.quantity.max(0) as u32,
+            amount: d.amount,
+        })
+        .collect();
+
+    let is_open = session.status == "OPEN";
+    let receipt = crate::hardware::printer::CashClosingReceipt {
+        company_name,
+        company_address,
+        company_phone,
+        title: if is_open {
+            "LEITURA PARCIAL (X)".into()
+        } else {
+            "FECHAMENTO DE CAIXA (Z)".into()
+        },
+        operator_name: report.employee_name.clone(),
+        opened_at: format_date(&session.opened_at),
+        closed_at: session.closed_at.as_deref().map(format_date),
+        approved_by: report.approved_by_name.clone(),
+        pending_approval: session.status == "PENDING_APPROVAL",
+        opening_balance: session.opening_balance,
+        total_supplies: summary.total_supplies,
+        total_withdrawals: summary.total_withdrawals,
+        total_sales: summary.total_sales,
+        total_canceled: summary.total_canceled,
+        total_refunds: summary.total_refunds,
+        total_account_receipts: summary.total_account_receipts,
+        total_gift_card_sales: summary.total_gift_card_sales,
+        methods,
+        denominations,
+        total_difference: if report.counts.is_empty() {
+            session.difference
+        } else {
+            Some(report.counts.iter().map(|c| c.difference).sum())
+        },
+        date_time: chrono::Local::now().format("%d/%m/%Y %H:%M").to_string(),
+    };
+
+    enqueue_printer_job(
+        &state,
+        app_state.pool(),
+        PrintJobKind::CashClosing,
+        Some(&format!("Caixa {}", report.employee_name)),
+        |printer| {
+            printer.print_cash_closing(&receipt);
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// MODELOS DE CUPOM
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Modelo personalizado do documento; `None` mantém o layout padrão. Um
+/// modelo corrompido nas configurações não impede a impressão.
+async fn load_receipt_template(
+    pool: &sqlx::SqlitePool,
+    document: TemplateDocument,
+) -> AppResult<Option<ReceiptTemplate>> {
+    let repo = crate::repositories::SettingsRepository::new(pool);
+    let Some(json) = repo.get_value(document.setting_key()).await? else {
+        return Ok(None);
+    };
+    match serde_json::from_str(&json) {
+        Ok(template) => Ok(Some(template)),
+        Err(e) => {
+            tracing::warn!(
+                "Modelo de {} inválido, usando layout padrão: {}",
+                document.label(),
+                e
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Modelo vigente do documento (personalizado ou o padrão embutido)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_receipt_template(
+    document: TemplateDocument,
+    app_state: State<'_, AppState>,
+) -> AppResult<ReceiptTemplateSetting> {
     app_state.session.require_authenticated()?;
-    let config = {
-        let guard = state.printer_config.read().await;
-        (*guard).clone()
+    let custom = load_receipt_template(app_state.pool(), document).await?;
+    Ok(ReceiptTemplateSetting {
+        document,
+        custom: custom.is_some(),
+        template: custom.unwrap_or_else(|| document.default_template()),
+    })
+}
+
+/// Grava o modelo do documento; `None` volta ao layout padrão
+#[tauri::command]
+#[specta::specta]
+pub async fn save_receipt_template(
+    document: TemplateDocument,
+    template: Option<ReceiptTemplate>,
+    app_state: State<'_, AppState>,
+) -> AppResult<ReceiptTemplateSetting> {
+    app_state.session.require_authenticated()?;
+    let repo = crate::repositories::SettingsRepository::new(app_state.pool());
+
+    let Some(template) = template else {
+        repo.delete(document.setting_key()).await?;
+        return Ok(ReceiptTemplateSetting {
+            document,
+            custom: false,
+            template: document.default_template(),
+        });
     };
 
+    template
+        .validate()
+        .map_err(crate::error::AppError::Validation)?;
+    repo.set(crate::models::SetSetting {
+        key: document.setting_key().into(),
+        value: serde_json::to_string(&template).unwrap_or_default(),
+        value_type: Some("JSON".into()),
+        group_name: Some("hardware".into()),
+        description: Some(format!("Modelo de impressão do {}", document.label())),
+    })
+    .await?;
+
+    Ok(ReceiptTemplateSetting {
+        document,
+        custom: true,
+        template,
+    })
+}
+
+/// Pré-visualização em texto do modelo com dados de exemplo
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_receipt_template(
+    document: TemplateDocument,
+    template: ReceiptTemplate,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<Vec<String>> {
+    app_state.session.require_authenticated()?;
+    template
+        .validate()
+        .map_err(crate::error::AppError::Validation)?;
+    let width = state.printer_config.read().await.paper_width as usize;
+    Ok(template.preview(&document.sample_data(), width))
+}
+
+/// Grava o documento na fila de impressão persistente; o envio e as
+/// retentativas ficam com o `HardwareManager`
+async fn enqueue_printer_job(
+    state: &HardwareState,
+    pool: &sqlx::SqlitePool,
+    kind: PrintJobKind,
+    reference: Option<&str>,
+    build: impl FnOnce(&mut ThermalPrinter),
+) -> AppResult<PrintJob> {
+    let config = state.printer_config.read().await.clone();
     if !config.enabled {
         return Err(HardwareError::NotConfigured("Impressora não habilitada".into()).into());
     }
 
-    let mut printer = ThermalPrinter::new(config.clone());
-    printer.print_attendant_order(&order);
-    let printer_config = config.clone();
-
-    if printer_config.connection == crate::hardware::printer::PrinterConnection::Network {
-        printer.print_network().await?;
-    } else {
-        tokio::task::spawn_blocking(move || -> AppResult<()> {
-            match printer_config.connection {
-                crate::hardware::printer::PrinterConnection::Usb => {
-                    printer.print_usb()?;
-                }
-                crate::hardware::printer::PrinterConnection::Serial => {
-                    printer.print_serial()?;
-                }
-                _ => {}
-            }
-            Ok(())
-        })
+    let logo = load_printer_logo(state, pool, &config).await?;
+    let mut printer = ThermalPrinter::new(config);
+    printer.set_logo(logo);
+    build(&mut printer);
+    state
+        .manager
+        .enqueue_print(kind, reference, printer.get_buffer())
         .await
-        .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))??;
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// LOGO
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Logo da loja convertido para a largura da bobina. A conversão fica em
+/// cache; um logo inválido nas configurações não impede a impressão.
+async fn load_printer_logo(
+    state: &HardwareState,
+    pool: &sqlx::SqlitePool,
+    config: &PrinterConfig,
+) -> AppResult<Option<Arc<MonoBitmap>>> {
+    let repo = crate::repositories::SettingsRepository::new(pool);
+    let Some(source) = repo.get_value(hardware::LOGO_SETTING_KEY).await? else {
+        return Ok(None);
+    };
+    match state
+        .logo_cache
+        .get_or_convert(&source, hardware::paper_width_dots(config))
+    {
+        Ok(bitmap) => Ok(Some(bitmap)),
+        Err(e) => {
+            tracing::warn!("Logo da impressora ignorado: {}", e);
+            Ok(None)
+        }
     }
+}
 
-    Ok(())
+/// Define o logo impresso no topo dos cupons (PNG em base64); `None` remove
+#[tauri::command]
+#[specta::specta]
+pub async fn set_printer_logo(
+    logo: Option<String>,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<Option<PrinterLogoInfo>> {
+    app_state.session.require_authenticated()?;
+    let repo = crate::repositories::SettingsRepository::new(app_state.pool());
+    state.logo_cache.clear();
+
+    let Some(logo) = logo else {
+        repo.delete(hardware::LOGO_SETTING_KEY).await?;
+        return Ok(None);
+    };
+
+    let config = state.printer_config.read().await.clone();
+    let bitmap = state
+        .logo_cache
+        .get_or_convert(&logo, hardware::paper_width_dots(&config))
+        .map_err(crate::error::AppError::Validation)?;
+    repo.set(crate::models::SetSetting {
+        key: hardware::LOGO_SETTING_KEY.into(),
+        value: logo,
+        value_type: Some("STRING".into()),
+        group_name: Some("hardware".into()),
+        description: Some("Logo da loja impresso nos cupons (PNG em base64)".into()),
+    })
+    .await?;
+
+    Ok(Some(PrinterLogoInfo {
+        width_dots: bitmap.width,
+        height_dots: bitmap.height,
+    }))
+}
+
+/// Lista os trabalhos da fila de impressão (mais recentes primeiro)
+#[tauri::command]
+#[specta::specta]
+pub async fn list_print_jobs(
+    status: Option<String>,
+    limit: Option<i32>,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<Vec<PrintJob>> {
+    app_state.session.require_authenticated()?;
+    state
+        .manager
+        .list_print_jobs(status.as_deref(), limit.unwrap_or(50))
+        .await
+}
+
+/// Reimprime um trabalho da fila (falho, cancelado ou já impresso)
+#[tauri::command]
+#[specta::specta]
+pub async fn reprint_print_job(
+    id: String,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<PrintJob> {
+    app_state.session.require_authenticated()?;
+    state.manager.reprint_job(&id).await
+}
+
+/// Cancela um trabalho que ainda aguarda na fila
+#[tauri::command]
+#[specta::specta]
+pub async fn cancel_print_job(
+    id: String,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<PrintJob> {
+    app_state.session.require_authenticated()?;
+    state.manager.cancel_job(&id).await
 }
 
 /// Testa impressão
@@ -488,6 +965,14 @@ pub async fn print_test_documents(
     printer.line("Barcode (EAN-13):");
     printer.barcode_ean13("7894900011517");
     printer.feed(1);
+    printer.line("Code128 / ITF-14 / GS1-128:");
+    printer.barcode(hardware::BarcodeSymbology::Code128, "MRC-00001");
+    printer.barcode(hardware::BarcodeSymbology::Itf, "17891234567892");
+    printer.barcode(
+        hardware::BarcodeSymbology::Gs1128,
+        "(01)07891234567895(17)261231(10)LOTE42",
+    );
+    printer.feed(1);
     printer.align(crate::hardware::printer::TextAlign::Center);
     printer.line("QR (TESTE):");
     printer.qrcode(&format!("TESTE-NOTA:{}", test_id));
@@ -569,7 +1054,27 @@ pub async fn print_test_documents(
         .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))??;
     }
 
-    Ok(())
+    Ok(())
+}
+
+/// Lê os sensores da impressora (papel, tampa, erro e gaveta) via DLE EOT / GS r
+#[tauri::command]
+#[specta::specta]
+pub async fn get_printer_sensors(
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<hardware::PrinterSensorStatus> {
+    app_state.session.require_authenticated()?;
+    let config = state.printer_config.read().await.clone();
+
+    if !config.enabled {
+        return Err(HardwareError::NotConfigured("Impressora não habilitada".into()).into());
+    }
+
+    let sensors = tokio::task::spawn_blocking(move || hardware::query_printer_sensors(&config))
+        .await
+        .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))??;
+    Ok(sensors)
 }
 
 /// Retorna configuração atual da impressora
@@ -584,6 +1089,131 @@ pub async fn get_printer_config(
     Ok(config.clone())
 }
 
+// ════════════════════════════════════════════════════════════════════════════
+// COMANDOS DE IMPRESSORA DE ETIQUETAS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Configura a impressora de etiquetas
+#[tauri::command]
+#[specta::specta]
+pub async fn configure_label_printer(
+    config: LabelPrinterConfig,
+    state: State<'_, AppState>,
+    hw_state: State<'_, HardwareState>,
+) -> AppResult<()> {
+    state.session.require_authenticated()?;
+    *hw_state.label_printer_config.write().await = config.clone();
+    hw_state
+        .manager
+        .set_label_printer_config(config.clone())
+        .await;
+
+    let repo = crate::repositories::SettingsRepository::new(state.pool());
+    repo.set(crate::models::SetSetting {
+        key: "hardware.label_printer.config".into(),
+        value: serde_json::to_string(&config).unwrap_or_default(),
+        value_type: Some("JSON".into()),
+        group_name: Some("hardware".into()),
+        description: Some("Configuração da impressora de etiquetas".into()),
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Retorna configuração atual da impressora de etiquetas
+#[tauri::command]
+#[specta::specta]
+pub async fn get_label_printer_config(
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<LabelPrinterConfig> {
+    app_state.session.require_authenticated()?;
+    Ok(state.label_printer_config.read().await.clone())
+}
+
+/// Imprime uma etiqueta de exemplo
+#[tauri::command]
+#[specta::specta]
+pub async fn test_label_printer(
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<()> {
+    app_state.session.require_authenticated()?;
+    let config = state.label_printer_config.read().await.clone();
+    let sample = ProductLabel {
+        name: "TESTE DE ETIQUETA GIRO".into(),
+        code: "000001".into(),
+        price: 12.9,
+        unit: "kg".into(),
+        barcode: Some("7891234567895".into()),
+        unit_price: Some(12.9),
+        unit_price_measure: Some("kg".into()),
+    };
+    LabelPrinter::new(config).print_labels(&[sample], 1).await?;
+    Ok(())
+}
+
+/// Imprime etiquetas dos produtos informados (`copies` de cada).
+/// Retorna quantos produtos foram impressos.
+#[tauri::command]
+#[specta::specta]
+pub async fn print_product_labels(
+    product_ids: Vec<String>,
+    copies: u16,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<u32> {
+    app_state.session.require_authenticated()?;
+    print_labels_for(&state, app_state.pool(), &product_ids, copies).await
+}
+
+/// Imprime etiquetas de todos os produtos ativos cujo preço de venda mudou
+/// a partir de `since` (AAAA-MM-DD ou data/hora ISO), conforme o histórico
+/// de preços. Retorna quantos produtos foram impressos.
+#[tauri::command]
+#[specta::specta]
+pub async fn print_price_change_labels(
+    since: String,
+    copies: u16,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<u32> {
+    app_state.session.require_authenticated()?;
+    let pool = app_state.pool();
+    let product_ids = crate::repositories::PriceHistoryRepository::new(pool)
+        .find_changed_product_ids(&since)
+        .await?;
+    print_labels_for(&state, pool, &product_ids, copies).await
+}
+
+async fn print_labels_for(
+    state: &HardwareState,
+    pool: &sqlx::SqlitePool,
+    product_ids: &[String],
+    copies: u16,
+) -> AppResult<u32> {
+    let config = state.label_printer_config.read().await.clone();
+    if !config.enabled {
+        return Err(
+            HardwareError::NotConfigured("Impressora de etiquetas não habilitada".into()).into(),
+        );
+    }
+
+    let repo = crate::repositories::ProductRepository::new(pool);
+    let mut labels = Vec::with_capacity(product_ids.len());
+    for id in product_ids {
+        if let Some(product) = repo.find_by_id(id).await? {
+            labels.push(ProductLabel::from_product(&product));
+        }
+    }
+
+    LabelPrinter::new(config)
+        .print_labels(&labels, copies)
+        .await?;
+    Ok(labels.len() as u32)
+}
+
 // ════════════════════════════════════════════════════════════════════════════
 // COMANDOS DE BALANÇA
 // ════════════════════════════════════════════════════════════════════════════
@@ -610,6 +1240,16 @@ pub async fn configure_scale(
         description: Some("Configuração da balança serial".into()),
     })
     .await?;
+    drop(scale_config);
+
+    // Leitura contínua em andamento passa a usar a nova configuração
+    if hw_state.manager.is_scale_streaming() {
+        if config.enabled || config.mock_mode {
+            hw_state.manager.start_scale_stream(config).await?;
+        } else {
+            hw_state.manager.stop_scale_stream().await;
+        }
+    }
 
     Ok(())
 }
@@ -631,6 +1271,14 @@ pub async fn read_weight(
         return Err(HardwareError::NotConfigured("Balança não habilitada".into()).into());
     }
 
+    // Com a leitura contínua ativa a porta está ocupada: usa o último peso
+    if state.manager.is_scale_streaming() {
+        return state
+            .manager
+            .latest_scale_reading()
+            .ok_or_else(|| HardwareError::Timeout.into());
+    }
+
     let scale = Scale::new(config.clone())?;
 
     // Scale read is blocking, wrap in spawn_blocking
@@ -667,6 +1315,211 @@ pub async fn test_scale_connection(
     }
 }
 
+/// Inicia a leitura contínua da balança (pesos chegam via `hardware:event`)
+#[tauri::command]
+#[specta::specta]
+pub async fn start_scale_stream(
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<()> {
+    app_state.session.require_authenticated()?;
+    let config = state.scale_config.read().await.clone();
+    state.manager.start_scale_stream(config).await?;
+    Ok(())
+}
+
+/// Encerra a leitura contínua da balança
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_scale_stream(
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<()> {
+    app_state.session.require_authenticated()?;
+    state.manager.stop_scale_stream().await;
+    Ok(())
+}
+
+/// Envia o preço por kg à balança computadora
+#[tauri::command]
+#[specta::specta]
+pub async fn set_scale_unit_price(
+    unit_price: f64,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<()> {
+    app_state.session.require_authenticated()?;
+    if !(unit_price.is_finite() && unit_price >= 0.0) {
+        return Err(crate::error::AppError::Validation(
+            "Preço por kg inválido".into(),
+        ));
+    }
+    let config = state.scale_config.read().await.clone();
+    state
+        .manager
+        .send_scale_unit_price(&config, unit_price)
+        .await?;
+    Ok(())
+}
+
+/// Layouts de código pesado da loja (padrão se nunca configurados)
+pub(crate) async fn load_weighted_barcode_config(
+    pool: &sqlx::SqlitePool,
+) -> AppResult<WeightedBarcodeConfig> {
+    let repo = crate::repositories::SettingsRepository::new(pool);
+    let Some(json) = repo.get_value(WEIGHTED_BARCODE_SETTING).await? else {
+        return Ok(WeightedBarcodeConfig::default());
+    };
+    match serde_json::from_str(&json) {
+        Ok(config) => Ok(config),
+        Err(e) => {
+            tracing::warn!("Layouts de código pesado inválidos, usando padrão: {}", e);
+            Ok(WeightedBarcodeConfig::default())
+        }
+    }
+}
+
+/// Layouts de código pesado (EAN-13 iniciado em 2)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_weighted_barcode_config(
+    app_state: State<'_, AppState>,
+) -> AppResult<WeightedBarcodeConfig> {
+    app_state.session.require_authenticated()?;
+    load_weighted_barcode_config(app_state.pool()).await
+}
+
+/// Grava os layouts de código pesado da loja
+#[tauri::command]
+#[specta::specta]
+pub async fn save_weighted_barcode_config(
+    config: WeightedBarcodeConfig,
+    app_state: State<'_, AppState>,
+) -> AppResult<WeightedBarcodeConfig> {
+    app_state.session.require_authenticated()?;
+    config
+        .validate()
+        .map_err(crate::error::AppError::Validation)?;
+
+    let repo = crate::repositories::SettingsRepository::new(app_state.pool());
+    repo.set(crate::models::SetSetting {
+        key: WEIGHTED_BARCODE_SETTING.into(),
+        value: serde_json::to_string(&config).unwrap_or_default(),
+        value_type: Some("JSON".into()),
+        group_name: Some("pdv".into()),
+        description: Some("Layouts de código de barras pesado (prefixo 2)".into()),
+    })
+    .await?;
+
+    Ok(config)
+}
+
+/// Configuração do cadastro de itens das balanças de balcão
+#[tauri::command]
+#[specta::specta]
+pub async fn get_scale_catalog_config(
+    app_state: State<'_, AppState>,
+) -> AppResult<ScaleCatalogConfig> {
+    app_state.session.require_authenticated()?;
+    load_scale_catalog_config(app_state.pool()).await
+}
+
+async fn load_scale_catalog_config(pool: &sqlx::SqlitePool) -> AppResult<ScaleCatalogConfig> {
+    let repo = crate::repositories::SettingsRepository::new(pool);
+    let config = match repo.get_value(SCALE_CATALOG_SETTING).await? {
+        Some(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            tracing::warn!("Configuração do cadastro das balanças inválida: {}", e);
+            ScaleCatalogConfig::default()
+        }),
+        None => ScaleCatalogConfig::default(),
+    };
+    Ok(config)
+}
+
+/// Grava a configuração do cadastro de itens das balanças
+#[tauri::command]
+#[specta::specta]
+pub async fn save_scale_catalog_config(
+    config: ScaleCatalogConfig,
+    app_state: State<'_, AppState>,
+) -> AppResult<ScaleCatalogConfig> {
+    app_state.session.require_authenticated()?;
+    config
+        .validate()
+        .map_err(crate::error::AppError::Validation)?;
+
+    let repo = crate::repositories::SettingsRepository::new(app_state.pool());
+    repo.set(crate::models::SetSetting {
+        key: SCALE_CATALOG_SETTING.into(),
+        value: serde_json::to_string(&config).unwrap_or_default(),
+        value_type: Some("JSON".into()),
+        group_name: Some("hardware".into()),
+        description: Some("Exportação de itens para balanças Toledo/Filizola".into()),
+    })
+    .await?;
+
+    Ok(config)
+}
+
+/// Exporta os produtos por kg para as balanças de balcão: gera o arquivo do
+/// formato configurado, grava na pasta e/ou envia pela rede
+#[tauri::command]
+#[specta::specta]
+pub async fn export_scale_catalog(app_state: State<'_, AppState>) -> AppResult<ScaleCatalogExport> {
+    app_state.session.require_authenticated()?;
+    let config = load_scale_catalog_config(app_state.pool()).await?;
+
+    let products = crate::repositories::ProductRepository::new(app_state.pool())
+        .find_active_by_unit(crate::models::ProductUnit::Kilogram)
+        .await?;
+    let mut items = Vec::with_capacity(products.len());
+    let mut skipped = Vec::new();
+    for product in &products {
+        match ScaleCatalogItem::from_product(product, config.format) {
+            Ok(item) => items.push(item),
+            Err(reason) => skipped.push(format!("{}: {}", product.name, reason)),
+        }
+    }
+    // Arquivo vazio apagaria o cadastro da balança
+    if items.is_empty() {
+        return Err(crate::error::AppError::Validation(
+            "Nenhum produto por kg com código interno numérico (PLU) para exportar".into(),
+        ));
+    }
+
+    let bytes = hardware::render_catalog(&items, &config);
+    let written_path = match config
+        .output_dir
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        Some(dir) => Some(hardware::write_catalog(dir, config.format, &bytes)?),
+        None => None,
+    };
+    let address = config
+        .network_address
+        .as_deref()
+        .filter(|a| !a.trim().is_empty());
+    if let Some(address) = address {
+        hardware::push_catalog(address, &bytes).await?;
+    }
+
+    tracing::info!(
+        "Cadastro das balanças exportado: {} itens, {} ignorados",
+        items.len(),
+        skipped.len()
+    );
+    let (content, _, _) = encoding_rs::WINDOWS_1252.decode(&bytes);
+    Ok(ScaleCatalogExport {
+        file_name: config.format.file_name().to_string(),
+        content: content.into_owned(),
+        exported: items.len() as u32,
+        skipped,
+        written_path,
+        pushed: address.is_some(),
+    })
+}
+
 /// Detecta automaticamente a balança
 #[tauri::command]
 #[specta::specta]
@@ -861,6 +1714,15 @@ pub async fn open_drawer(
     .await
     .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))??;
 
+    // Com sensor disponível (impressora serial/rede), avisa quando a gaveta fechar
+    let printer = state.printer_config.read().await.clone();
+    if !config.mock_mode
+        && printer.enabled
+        && printer.connection != crate::hardware::printer::PrinterConnection::Usb
+    {
+        state.manager.clone().watch_drawer_close(config, printer);
+    }
+
     Ok(())
 }
 
@@ -1166,6 +2028,14 @@ pub async fn start_serial_scanner(
     }
 }
 
+/// Interpreta um código lido pela leitora USB (teclado): formato, produto e,
+/// em códigos GS1, lote e validade
+#[tauri::command]
+#[specta::specta]
+pub fn parse_barcode(code: String) -> AppResult<hardware::ParsedBarcode> {
+    Ok(hardware::ParsedBarcode::parse(&code))
+}
+
 /// Gera QR Code em SVG para exibir no frontend (teste de leitura)
 #[tauri::command]
 #[specta::specta]
@@ -1245,6 +2115,7 @@ pub async fn get_hardware_overview(
     let printer_cfg = hw_state.printer_config.read().await;
     let scale_cfg = hw_state.scale_config.read().await;
     let drawer_cfg = hw_state.drawer_config.read().await;
+    let label_cfg = hw_state.label_printer_config.read().await;
     let scanner_server = hw_state.scanner_server.read().await;
 
     let printer_status = if printer_cfg.mock_mode {
@@ -1271,6 +2142,14 @@ pub async fn get_hardware_overview(
         "not_configured"
     };
 
+    let label_status = if label_cfg.mock_mode {
+        "mock"
+    } else if label_cfg.enabled {
+        "configured"
+    } else {
+        "not_configured"
+    };
+
     let scanner_status = if scanner_server.is_some() {
         "running"
     } else {
@@ -1318,6 +2197,17 @@ pub async fn get_hardware_overview(
             enabled: true,
             mock_mode: false,
         },
+        label_printer: DeviceOverview {
+            status: label_status.to_string(),
+            port: if label_cfg.port.is_empty() {
+                None
+            } else {
+                Some(label_cfg.port.clone())
+            },
+            model: Some(format!("{:?}", label_cfg.language).to_uppercase()),
+            enabled: label_cfg.enabled,
+            mock_mode: label_cfg.mock_mode,
+        },
         available_ports: hardware::list_serial_ports(),
     })
 }
@@ -1330,6 +2220,7 @@ pub struct HardwareOverviewResponse {
     pub scale: DeviceOverview,
     pub drawer: DeviceOverview,
     pub scanner: DeviceOverview,
+    pub label_printer: DeviceOverview,
     pub available_ports: Vec<String>,
 }
 
@@ -1404,6 +2295,8 @@ pub async fn apply_detected_device(
                 parity: "none".to_string(),
                 stop_bits: 1,
                 mock_mode: false,
+                continuous: false,
+                price_computing: false,
             };
 
             configure_scale(config, app_state.clone(), hw_state.clone()).await?;
@@ -1415,6 +2308,7 @@ pub async fn apply_detected_device(
                 pin: hardware::DrawerPin::Pin2,
                 pulse_duration: 200,
                 mock_mode: false,
+                sensor_inverted: false,
             };
 
             configure_drawer(config, app_state.clone(), hw_state.clone()).await?;
@@ -1422,8 +2316,134 @@ pub async fn apply_detected_device(
         hardware::DeviceType::Scanner => {
             // Scanner doesn't need port configuration, it's WebSocket based
         }
+        hardware::DeviceType::Label => {
+            // Mantém linguagem e tamanho da etiqueta já configurados
+            let current = hw_state.label_printer_config.read().await.clone();
+            let connection = if device.port.contains(':') {
+                hardware::PrinterConnection::Network
+            } else if device.port.starts_with("/dev/usb/lp") || device.port.starts_with("/dev/lp") {
+                hardware::PrinterConnection::Usb
+            } else {
+                hardware::PrinterConnection::Serial
+            };
+            let config = LabelPrinterConfig {
+                enabled: true,
+                connection,
+                port: device.port,
+                mock_mode: false,
+                ..current
+            };
+
+            configure_label_printer(config, app_state.clone(), hw_state.clone()).await?;
+        }
+    }
+
+    Ok(())
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// COMANDOS DE MODO DE DEMONSTRAÇÃO
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Troca as configurações em uso sem persistir (a leitura contínua da
+/// balança, se ativa, passa para a nova porta)
+async fn swap_hardware_configs(
+    hw_state: &HardwareState,
+    printer: PrinterConfig,
+    scale: ScaleConfig,
+) -> AppResult<()> {
+    hw_state.manager.set_printer_config(printer.clone()).await;
+    *hw_state.printer_config.write().await = printer;
+    *hw_state.scale_config.write().await = scale.clone();
+
+    if hw_state.manager.is_scale_streaming() {
+        if scale.enabled || scale.mock_mode {
+            hw_state.manager.start_scale_stream(scale).await?;
+        } else {
+            hw_state.manager.stop_scale_stream().await;
+        }
+    }
+    Ok(())
+}
+
+/// Liga o modo de demonstração: balança e impressora simuladas no lugar das
+/// configuradas, sem alterar as configurações salvas
+#[tauri::command]
+#[specta::specta]
+pub async fn start_demo_hardware(
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<DemoHardwareInfo> {
+    app_state.session.require_authenticated()?;
+    let mut demo = state.demo_hardware.write().await;
+    if let Some(session) = demo.as_ref() {
+        return Ok(session.hardware.info());
     }
 
+    let hardware = DemoHardware::start().await?;
+    let printer_config = state.printer_config.read().await.clone();
+    let scale_config = state.scale_config.read().await.clone();
+    swap_hardware_configs(
+        &state,
+        hardware.printer.printer_config(&printer_config),
+        hardware.scale_config(),
+    )
+    .await?;
+
+    let info = hardware.info();
+    *demo = Some(DemoHardwareSession {
+        hardware,
+        printer_config,
+        scale_config,
+    });
+    Ok(info)
+}
+
+/// Desliga o modo de demonstração e volta às configurações anteriores
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_demo_hardware(
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<()> {
+    app_state.session.require_authenticated()?;
+    let Some(session) = state.demo_hardware.write().await.take() else {
+        return Ok(());
+    };
+    swap_hardware_configs(&state, session.printer_config, session.scale_config).await
+}
+
+/// Situação do modo de demonstração (None quando desligado)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_demo_hardware(
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<Option<DemoHardwareInfo>> {
+    app_state.session.require_authenticated()?;
+    let demo = state.demo_hardware.read().await;
+    Ok(demo.as_ref().map(|session| session.hardware.info()))
+}
+
+/// Coloca um peso no prato da balança simulada
+#[tauri::command]
+#[specta::specta]
+pub async fn set_demo_scale_weight(
+    weight_grams: u32,
+    state: State<'_, HardwareState>,
+    app_state: State<'_, AppState>,
+) -> AppResult<()> {
+    app_state.session.require_authenticated()?;
+    let demo = state.demo_hardware.read().await;
+    let scale = demo
+        .as_ref()
+        .and_then(|session| session.hardware.scale.as_ref())
+        .ok_or_else(|| {
+            crate::error::AppError::BusinessRule(
+                "Balança simulada não está ativa (modo de demonstração)".into(),
+            )
+        })?;
+    scale.set_weight(SimulatedWeight::grams(weight_grams));
     Ok(())
 }
 
@@ -1497,6 +2517,7 @@ pub async fn load_hardware_configs_internal(
     // Printer
     if let Ok(Some(val)) = repo.get_value("hardware.printer.config").await {
         if let Ok(config) = serde_json::from_str::<PrinterConfig>(&val) {
+            hw_state.manager.set_printer_config(config.clone()).await;
             let mut printer_config = hw_state.printer_config.write().await;
             *printer_config = config;
         }
@@ -1510,6 +2531,17 @@ pub async fn load_hardware_configs_internal(
         }
     }
 
+    // Label printer
+    if let Ok(Some(val)) = repo.get_value("hardware.label_printer.config").await {
+        if let Ok(config) = serde_json::from_str::<LabelPrinterConfig>(&val) {
+            hw_state
+                .manager
+                .set_label_printer_config(config.clone())
+                .await;
+            *hw_state.label_printer_config.write().await = config;
+        }
+    }
+
     // Drawer
     if let Ok(Some(val)) = repo.get_value("hardware.drawer.config").await {
         if let Ok(config) = serde_json::from_str::<DrawerConfig>(&val) {
@@ -1520,3 +2552,39 @@ pub async fn load_hardware_configs_internal(
 
     Ok(())
 }
+
+/// Inicia a fila de impressão (retomando trabalhos pendentes de execuções
+/// anteriores) e repassa os eventos de hardware ao frontend (`hardware:event`)
+pub async fn start_print_queue(
+    app_handle: tauri::AppHandle,
+    state: &AppState,
+    hw_state: &HardwareState,
+) -> AppResult<()> {
+    use tauri::Emitter;
+
+    let mut events = hw_state.manager.subscribe();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    let _ = app_handle.emit("hardware:event", event);
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(_) => break,
+            }
+        }
+    });
+
+    let recovered = hw_state
+        .manager
+        .attach_print_spool(state.pool().clone())
+        .await?;
+    if recovered > 0 {
+        tracing::info!(
+            "Fila de impressão: {} trabalho(s) interrompido(s) retomado(s)",
+            recovered
+        );
+    }
+    hw_state.manager.clone().start_print_queue_loop();
+    Ok(())
+}
@@ -5,6 +5,7 @@ pub mod audit;
 pub mod backup;
 pub mod cash;
 pub mod categories;
+pub mod credit_accounts;
 pub mod customers;
 pub mod dispatcher;
 pub mod employees;
@@ -18,14 +19,18 @@ pub mod network;
 #[cfg(test)]
 pub mod network_test;
 pub mod price_history;
+pub mod pricing_rules;
 pub mod products;
+pub mod purchase_import;
 pub mod reports;
+pub mod sale_returns;
 pub mod sales;
 #[cfg(debug_assertions)]
 pub mod seed;
 pub mod service_orders;
 pub mod settings;
 pub mod stock;
+pub mod store_credits;
 pub mod suppliers;
 pub mod sync;
 pub mod system;
@@ -51,6 +56,7 @@ pub use audit::*;
 pub use backup::*;
 pub use cash::*;
 pub use categories::*;
+pub use credit_accounts::*;
 pub use customers::*;
 pub use dispatcher::*;
 pub use employees::*;
@@ -60,12 +66,16 @@ pub use license::*;
 pub use mobile::*;
 pub use network::*;
 pub use price_history::*;
+pub use pricing_rules::*;
 pub use products::*;
+pub use purchase_import::*;
 pub use reports::*;
+pub use sale_returns::*;
 pub use sales::*;
 pub use service_orders::*;
 pub use settings::*;
 pub use stock::*;
+pub use store_credits::*;
 pub use suppliers::*;
 pub use sync::*;
 pub use system::*;
@@ -0,0 +1,81 @@
+//! Comandos Tauri para Promoções e Regras de Preço
+
+use crate::error::AppResult;
+use crate::middleware::Permission;
+use crate::models::{CreateSaleItem, PricingRule, SalePricing, SavePricingRule};
+use crate::repositories::PricingRuleRepository;
+use crate::require_permission;
+use crate::AppState;
+use tauri::State;
+
+/// Lista as regras de preço ativas (maior prioridade primeiro)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_pricing_rules(state: State<'_, AppState>) -> AppResult<Vec<PricingRule>> {
+    state.session.require_authenticated()?;
+    let repo = PricingRuleRepository::new(state.pool());
+    repo.find_all().await
+}
+
+/// Cria ou atualiza uma regra de preço
+#[tauri::command]
+#[specta::specta]
+pub async fn save_pricing_rule(
+    input: SavePricingRule,
+    state: State<'_, AppState>,
+) -> AppResult<PricingRule> {
+    let info = state.session.require_authenticated()?;
+    require_permission!(state.pool(), &info.employee_id, Permission::UpdateProducts);
+    let repo = PricingRuleRepository::new(state.pool());
+    repo.save(input).await
+}
+
+/// Desativa uma regra de preço
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_pricing_rule(id: String, state: State<'_, AppState>) -> AppResult<()> {
+    let info = state.session.require_authenticated()?;
+    require_permission!(state.pool(), &info.employee_id, Permission::UpdateProducts);
+    let repo = PricingRuleRepository::new(state.pool());
+    repo.delete(&id).await
+}
+
+/// Calcula as promoções do carrinho, como serão aplicadas ao finalizar a venda
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_sale_pricing(
+    items: Vec<CreateSaleItem>,
+    customer_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<SalePricing> {
+    state.session.require_authenticated()?;
+    let repo = PricingRuleRepository::new(state.pool());
+    repo.evaluate(&items, customer_id.as_deref()).await
+}
+
+/// Busca o grupo de preço do cliente
+#[tauri::command]
+#[specta::specta]
+pub async fn get_customer_price_group(
+    customer_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Option<String>> {
+    state.session.require_authenticated()?;
+    let repo = PricingRuleRepository::new(state.pool());
+    repo.find_customer_price_group(&customer_id).await
+}
+
+/// Define (ou remove) o grupo de preço do cliente
+#[tauri::command]
+#[specta::specta]
+pub async fn set_customer_price_group(
+    customer_id: String,
+    price_group: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    let info = state.session.require_authenticated()?;
+    require_permission!(state.pool(), &info.employee_id, Permission::ManageCustomers);
+    let repo = PricingRuleRepository::new(state.pool());
+    repo.set_customer_price_group(&customer_id, price_group.as_deref())
+        .await
+}
@@ -2,10 +2,11 @@
 
 use crate::audit_log;
 use crate::commands::network::NetworkState;
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
+use crate::hardware::WeightedBarcode;
 use crate::middleware::audit::{AuditAction, AuditService};
 use crate::middleware::Permission;
-use crate::models::{CreateProduct, Product, UpdateProduct};
+use crate::models::{CreateProduct, Product, ScannedProduct, UpdateProduct};
 use crate::repositories::ProductRepository;
 use crate::require_permission;
 use crate::AppState;
@@ -61,6 +62,49 @@ pub async fn get_product_by_barcode(
     repo.find_by_barcode(&barcode).await
 }
 
+/// Localiza o produto do código lido no PDV. Códigos cadastrados têm
+/// prioridade; senão, códigos pesados (prefixo 2) são decodificados pelos
+/// layouts da loja e o produto é buscado pelo código da balança, com a
+/// quantidade vinda do peso ou do preço embutido
+#[tauri::command]
+#[specta::specta]
+pub async fn scan_product_barcode(
+    barcode: String,
+    state: State<'_, AppState>,
+) -> AppResult<Option<ScannedProduct>> {
+    let repo = ProductRepository::with_events(state.pool(), &state.event_service);
+    let barcode = barcode.trim();
+    if let Some(product) = repo.find_by_barcode(barcode).await? {
+        return Ok(Some(ScannedProduct {
+            product,
+            quantity: 1.0,
+            total_price: None,
+            weighted: None,
+        }));
+    }
+
+    let config = crate::commands::hardware::load_weighted_barcode_config(state.pool()).await?;
+    let Some(weighted) = WeightedBarcode::decode(barcode, &config) else {
+        return Ok(None);
+    };
+    let Some(product) = repo.find_by_scale_code(&weighted.product_code).await? else {
+        return Ok(None);
+    };
+
+    let quantity = weighted.quantity(product.sale_price).ok_or_else(|| {
+        AppError::Validation(format!(
+            "Produto '{}' sem preço de venda: não é possível calcular o peso",
+            product.name
+        ))
+    })?;
+    Ok(Some(ScannedProduct {
+        product,
+        quantity,
+        total_price: weighted.total_price(),
+        weighted: Some(weighted),
+    }))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn search_products(query: String, state: State<'_, AppState>) -> AppResult<Vec<Product>> {
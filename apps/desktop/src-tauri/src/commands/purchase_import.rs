@@ -0,0 +1,251 @@
+//! Comandos Tauri para Entrada de Mercadoria por NF-e de Compra
+//!
+//! Fluxo: prévia (vínculo automático por código do fornecedor ou EAN) →
+//! conferência dos vínculos pelo usuário → importação (lotes, entradas e custo).
+
+use crate::audit_log;
+use crate::error::{AppError, AppResult};
+use crate::middleware::audit::{AuditAction, AuditService};
+use crate::middleware::Permission;
+use crate::models::{
+    CreatePurchaseInvoice, CreateSupplier, ImportPurchaseNfe, PurchaseEntry, PurchaseImportPreview,
+    PurchaseImportResult, PurchaseInvoice, PurchaseLotInfo, PurchasePreviewItem, Supplier,
+};
+use crate::nfce::purchase::{parse_purchase_nfe, PurchaseItem, PurchaseNfe};
+use crate::repositories::{PurchaseRepository, SupplierRepository};
+use crate::require_permission;
+use crate::AppState;
+use std::collections::HashMap;
+use tauri::State;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// COMANDOS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Lê a NF-e e sugere o produto de cada item (não salva no banco)
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_purchase_nfe(
+    file_path: String,
+    state: State<'_, AppState>,
+) -> AppResult<PurchaseImportPreview> {
+    state.session.require_authenticated()?;
+    let (_, nfe) = read_nfe(&file_path)?;
+
+    let supplier = find_supplier(state.pool(), &nfe.supplier.cnpj).await?;
+    let repo = PurchaseRepository::new(state.pool());
+    let already_imported = repo.find_by_access_key(&nfe.access_key).await?.is_some();
+
+    let mut items = Vec::with_capacity(nfe.items.len());
+    for item in &nfe.items {
+        let mut matched = None;
+        if let Some(supplier) = &supplier {
+            matched = repo
+                .find_product_by_supplier_code(&supplier.id, &item.supplier_code)
+                .await?
+                .map(|product| (product, "SUPPLIER_CODE"));
+        }
+        if matched.is_none() {
+            if let Some(ean) = &item.ean {
+                matched = repo
+                    .find_product_by_barcode(ean)
+                    .await?
+                    .map(|product| (product, "EAN"));
+            }
+        }
+
+        let (product, source) = match matched {
+            Some(((id, name), source)) => (Some((id, name)), Some(source.to_string())),
+            None => (None, None),
+        };
+        items.push(PurchasePreviewItem {
+            item_number: item.number as i32,
+            supplier_code: item.supplier_code.clone(),
+            ean: item.ean.clone(),
+            description: item.description.clone(),
+            ncm: item.ncm.clone(),
+            unit: item.unit.clone(),
+            quantity: item.quantity,
+            unit_cost: item.unit_cost(),
+            total_cost: item.total_cost,
+            lots: lots_of(item),
+            product_id: product.as_ref().map(|(id, _)| id.clone()),
+            product_name: product.map(|(_, name)| name),
+            match_source: source,
+        });
+    }
+
+    Ok(PurchaseImportPreview {
+        access_key: nfe.access_key,
+        number: nfe.number as i32,
+        serie: nfe.serie as i32,
+        issued_at: nfe.issued_at,
+        total: nfe.total,
+        supplier_cnpj: nfe.supplier.cnpj,
+        supplier_name: nfe.supplier.name,
+        supplier_id: supplier.map(|s| s.id),
+        already_imported,
+        items,
+    })
+}
+
+/// Dá entrada da NF-e no estoque com os vínculos conferidos pelo usuário
+#[tauri::command]
+#[specta::specta]
+pub async fn import_purchase_nfe(
+    input: ImportPurchaseNfe,
+    state: State<'_, AppState>,
+) -> AppResult<PurchaseImportResult> {
+    let info = state.session.require_authenticated()?;
+    let employee = require_permission!(state.pool(), &info.employee_id, Permission::ManageStock);
+    let (xml, nfe) = read_nfe(&input.file_path)?;
+
+    let repo = PurchaseRepository::new(state.pool());
+    if repo.find_by_access_key(&nfe.access_key).await?.is_some() {
+        return Err(AppError::Duplicate(format!(
+            "NF-e {} já foi importada",
+            nfe.access_key
+        )));
+    }
+
+    let matches: HashMap<i32, String> = input
+        .matches
+        .iter()
+        .filter_map(|m| Some((m.item_number, m.product_id.clone()?)))
+        .collect();
+
+    let entries: Vec<PurchaseEntry> = nfe
+        .items
+        .iter()
+        .filter_map(|item| {
+            let product_id = matches.get(&(item.number as i32))?;
+            Some(PurchaseEntry {
+                product_id: product_id.clone(),
+                supplier_code: item.supplier_code.clone(),
+                quantity: item.quantity,
+                unit_cost: item.unit_cost(),
+                lots: lots_of(item),
+            })
+        })
+        .collect();
+    if entries.is_empty() {
+        return Err(AppError::Validation(
+            "Vincule ao menos um item da nota a um produto".into(),
+        ));
+    }
+
+    // Fornecedor desconhecido é cadastrado a partir do emitente da nota
+    let (supplier, supplier_created) = match find_supplier(state.pool(), &nfe.supplier.cnpj).await?
+    {
+        Some(supplier) => (supplier, false),
+        None => {
+            let repo = SupplierRepository::with_events(state.pool(), &state.event_service);
+            let created = repo
+                .create(CreateSupplier {
+                    name: nfe.supplier.name.clone(),
+                    trade_name: nfe.supplier.trade_name.clone(),
+                    cnpj: Some(nfe.supplier.cnpj.clone()),
+                    phone: nfe.supplier.phone.clone(),
+                    email: None,
+                    address: nfe.supplier.address.clone(),
+                    city: nfe.supplier.city.clone(),
+                    state: nfe.supplier.uf.clone(),
+                    notes: Some(format!("Cadastrado pela NF-e {}", nfe.access_key)),
+                })
+                .await?;
+            (created, true)
+        }
+    };
+
+    let items_skipped = (nfe.items.len() - entries.len()) as i32;
+    let mut result = repo
+        .import(CreatePurchaseInvoice {
+            access_key: nfe.access_key.clone(),
+            supplier_id: supplier.id.clone(),
+            number: nfe.number as i32,
+            serie: nfe.serie as i32,
+            issued_at: nfe.issued_at.clone(),
+            total: nfe.total,
+            xml,
+            employee_id: Some(employee.id.clone()),
+            entries,
+            items_skipped,
+            remember_codes: input.remember_codes,
+        })
+        .await?;
+    result.supplier_created = supplier_created;
+
+    let audit_service = AuditService::new(state.pool().clone());
+    audit_log!(
+        audit_service,
+        AuditAction::StockEntry,
+        &employee.id,
+        &employee.name,
+        "PurchaseInvoice",
+        &result.invoice_id,
+        format!(
+            "NF-e {} de {} | Itens: {} (ignorados: {}) | Lotes: {}",
+            nfe.number,
+            supplier.name,
+            result.items_imported,
+            result.items_skipped,
+            result.lots_created
+        )
+    );
+
+    Ok(result)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_purchase_invoices(
+    limit: Option<i32>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<PurchaseInvoice>> {
+    state.session.require_authenticated()?;
+    let repo = PurchaseRepository::new(state.pool());
+    repo.find_recent(limit.unwrap_or(50)).await
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// AUXILIARES
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn read_nfe(file_path: &str) -> AppResult<(String, PurchaseNfe)> {
+    let xml = std::fs::read_to_string(file_path)
+        .map_err(|e| AppError::Validation(format!("Erro ao ler XML {}: {}", file_path, e)))?;
+    let nfe = parse_purchase_nfe(&xml).map_err(AppError::Validation)?;
+    Ok((xml, nfe))
+}
+
+/// Busca o fornecedor pelo CNPJ (cadastros com ou sem máscara)
+async fn find_supplier(pool: &sqlx::SqlitePool, cnpj: &str) -> AppResult<Option<Supplier>> {
+    let repo = SupplierRepository::new(pool);
+    if let Some(supplier) = repo.find_by_cnpj(cnpj).await? {
+        return Ok(Some(supplier));
+    }
+    if cnpj.len() == 14 {
+        let masked = format!(
+            "{}.{}.{}/{}-{}",
+            &cnpj[0..2],
+            &cnpj[2..5],
+            &cnpj[5..8],
+            &cnpj[8..12],
+            &cnpj[12..14]
+        );
+        return repo.find_by_cnpj(&masked).await;
+    }
+    Ok(None)
+}
+
+fn lots_of(item: &PurchaseItem) -> Vec<PurchaseLotInfo> {
+    item.lots
+        .iter()
+        .map(|lot| PurchaseLotInfo {
+            lot_number: Some(lot.number.clone()),
+            quantity: lot.quantity,
+            manufacturing_date: lot.manufacturing_date.clone(),
+            expiration_date: lot.expiration_date.clone(),
+        })
+        .collect()
+}
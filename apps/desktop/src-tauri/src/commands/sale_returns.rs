@@ -0,0 +1,79 @@
+//! Comandos Tauri para Devoluções e Trocas
+
+use crate::audit_log;
+use crate::error::AppResult;
+use crate::middleware::audit::{AuditAction, AuditService};
+use crate::middleware::Permission;
+use crate::models::{CreateSaleReturn, ReturnableSaleItem, SaleReturn};
+use crate::repositories::{CashRepository, SaleReturnRepository};
+use crate::require_permission;
+use crate::AppState;
+use tauri::State;
+
+/// Itens da venda com a quantidade que ainda pode ser devolvida
+#[tauri::command]
+#[specta::specta]
+pub async fn get_returnable_sale_items(
+    sale_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<ReturnableSaleItem>> {
+    state.session.require_authenticated()?;
+    let repo = SaleReturnRepository::new(state.pool());
+    repo.find_returnable_items(&sale_id).await
+}
+
+/// Devoluções já registradas para a venda
+#[tauri::command]
+#[specta::specta]
+pub async fn get_sale_returns(
+    sale_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<SaleReturn>> {
+    state.session.require_authenticated()?;
+    let repo = SaleReturnRepository::new(state.pool());
+    repo.find_by_sale(&sale_id).await
+}
+
+/// Registra devolução parcial ou troca (estorno no caixa aberto do operador)
+#[tauri::command]
+#[specta::specta]
+pub async fn create_sale_return(
+    mut input: CreateSaleReturn,
+    state: State<'_, AppState>,
+) -> AppResult<SaleReturn> {
+    let info = state.session.require_authenticated()?;
+    let employee = require_permission!(state.pool(), &info.employee_id, Permission::CancelSales);
+    input.employee_id = info.employee_id.clone();
+    if let Some(exchange) = input.exchange.as_mut() {
+        exchange.employee_id = info.employee_id.clone();
+    }
+
+    let session = CashRepository::new(state.pool())
+        .find_session_by_employee(&info.employee_id)
+        .await?;
+    input.cash_session_id = session.map(|s| s.id);
+    if let (Some(exchange), Some(session_id)) =
+        (input.exchange.as_mut(), input.cash_session_id.as_ref())
+    {
+        exchange.cash_session_id = session_id.clone();
+    }
+
+    let repo = SaleReturnRepository::new(state.pool());
+    let result = repo.create(input).await?;
+
+    let audit_service = AuditService::new(state.pool().clone());
+    audit_log!(
+        audit_service,
+        AuditAction::SaleReturned,
+        &employee.id,
+        &employee.name,
+        "Sale",
+        &result.sale_id,
+        format!(
+            "Devolução: {}, Valor: {}, Reembolso: {} {}",
+            result.id, result.total, result.refund_type, result.refund_amount
+        )
+    );
+
+    Ok(result)
+}
@@ -1,6 +1,6 @@
 //! Comandos Tauri para Vendas
 
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
 use crate::models::{
     CreateSale, DailySalesSummary, MonthlySalesSummary, PaginatedResult, Sale, SaleFilters,
     SaleWithDetails,
@@ -94,6 +94,33 @@ pub async fn cancel_sale(
     let canceled_by = info.employee_id;
     let employee = require_permission!(state.pool(), &canceled_by, Permission::CancelSales);
     let repo = SaleRepository::with_events(state.pool(), &state.event_service);
+    let sale = repo
+        .find_by_id(&id)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "Sale".into(),
+            id: id.clone(),
+        })?;
+    if sale.status == "CANCELED" {
+        return Err(AppError::SaleCannotBeCanceled(
+            "venda já cancelada".to_string(),
+        ));
+    }
+
+    // Cancelamento fiscal (evento 110111) antes de mexer no banco: se a SEFAZ
+    // rejeitar, o prazo tiver expirado ou a justificativa for inválida, a venda
+    // continua ativa e o erro volta para o operador.
+    let nfce_canceled = crate::repositories::FiscalRepository::new(state.pool())
+        .find_sale_link(&id)
+        .await?
+        .is_some_and(|link| link.nfce_cancel_protocol.is_some());
+    if !nfce_canceled {
+        let justification = format!("Cancelamento da venda: {}", reason);
+        crate::nfce::commands::cancel_sale_nfce(state.pool(), &id, &justification)
+            .await
+            .map_err(AppError::SaleCannotBeCanceled)?;
+    }
+
     let result = repo.cancel(&id, &canceled_by, &reason).await?;
 
     // Audit Log
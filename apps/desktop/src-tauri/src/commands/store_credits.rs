@@ -0,0 +1,123 @@
+//! Comandos Tauri para Vale-Crédito e Vale-Presente
+
+use crate::audit_log;
+use crate::error::AppResult;
+use crate::middleware::audit::{AuditAction, AuditService};
+use crate::middleware::Permission;
+use crate::models::{IssueGiftCard, StoreCredit, StoreCreditTransaction};
+use crate::repositories::{CashRepository, StoreCreditRepository};
+use crate::require_permission;
+use crate::AppState;
+use tauri::State;
+
+/// Vale pelo id (ex.: emitido por uma devolução)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_store_credit(
+    id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Option<StoreCredit>> {
+    state.session.require_authenticated()?;
+    let repo = StoreCreditRepository::new(state.pool());
+    repo.find_by_id(&id).await
+}
+
+/// Consulta o vale pelo código lido no recibo (saldo antes de usar como pagamento)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_store_credit_by_code(
+    code: String,
+    state: State<'_, AppState>,
+) -> AppResult<Option<StoreCredit>> {
+    state.session.require_authenticated()?;
+    let repo = StoreCreditRepository::new(state.pool());
+    repo.find_by_code(&code).await
+}
+
+/// Vales do cliente (devoluções e vale-presentes)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_customer_store_credits(
+    customer_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<StoreCredit>> {
+    state.session.require_authenticated()?;
+    let repo = StoreCreditRepository::new(state.pool());
+    repo.find_by_customer(&customer_id).await
+}
+
+/// Histórico de movimentações do vale
+#[tauri::command]
+#[specta::specta]
+pub async fn get_store_credit_transactions(
+    store_credit_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<StoreCreditTransaction>> {
+    state.session.require_authenticated()?;
+    let repo = StoreCreditRepository::new(state.pool());
+    repo.find_transactions(&store_credit_id).await
+}
+
+/// Vende um vale-presente no caixa aberto do operador
+#[tauri::command]
+#[specta::specta]
+pub async fn issue_gift_card(
+    mut input: IssueGiftCard,
+    state: State<'_, AppState>,
+) -> AppResult<StoreCredit> {
+    let info = state.session.require_authenticated()?;
+    let employee = require_permission!(state.pool(), &info.employee_id, Permission::CreateSales);
+    input.employee_id = info.employee_id.clone();
+    input.cash_session_id = CashRepository::new(state.pool())
+        .find_session_by_employee(&info.employee_id)
+        .await?
+        .map(|s| s.id);
+
+    let repo = StoreCreditRepository::new(state.pool());
+    let result = repo.issue_gift_card(input).await?;
+
+    let audit_service = AuditService::new(state.pool().clone());
+    audit_log!(
+        audit_service,
+        AuditAction::GiftCardIssued,
+        &employee.id,
+        &employee.name,
+        "StoreCredit",
+        &result.id,
+        format!("Vale-presente {}: {:.2}", result.code, result.amount)
+    );
+
+    Ok(result)
+}
+
+/// Bloqueia (extravio) ou libera o vale
+#[tauri::command]
+#[specta::specta]
+pub async fn set_store_credit_active(
+    id: String,
+    is_active: bool,
+    state: State<'_, AppState>,
+) -> AppResult<StoreCredit> {
+    let info = state.session.require_authenticated()?;
+    let employee = require_permission!(state.pool(), &info.employee_id, Permission::CancelSales);
+    let repo = StoreCreditRepository::new(state.pool());
+    let result = repo.set_active(&id, is_active).await?;
+
+    let audit_service = AuditService::new(state.pool().clone());
+    audit_log!(
+        audit_service,
+        AuditAction::StoreCreditBlocked,
+        &employee.id,
+        &employee.name,
+        "StoreCredit",
+        &result.id,
+        format!(
+            "Vale {} {} (saldo {:.2})",
+            result.code,
+            if is_active { "liberado" } else { "bloqueado" },
+            result.balance
+        )
+    );
+
+    Ok(result)
+}
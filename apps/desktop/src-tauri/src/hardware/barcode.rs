@@ -0,0 +1,589 @@
+//! Códigos de Barras - Simbologias e GS1
+//!
+//! - Impressão ESC/POS (`GS k`, função B) de EAN-13, EAN-8, Code128 com
+//!   subconjuntos A/B/C, GS1-128, ITF e Code39
+//! - Dígito verificador GS1 (módulo 10), comum a EAN-8/13, UPC e ITF-14
+//! - Leitura dos Identificadores de Aplicação GS1 (01 GTIN, 10 lote,
+//!   17 validade), para preencher lote e validade ao escanear caixas
+
+use super::printer::{escpos, ThermalPrinter};
+use super::raster::paper_width_dots;
+use serde::{Deserialize, Serialize};
+
+/// Separador de campos GS1 (FNC1 transmitido pela leitora)
+pub const GS1_SEPARATOR: char = '\u{1D}';
+
+// Códigos `m` do comando GS k (função B)
+const GS_K_EAN8: u8 = 68;
+const GS_K_CODE39: u8 = 69;
+const GS_K_ITF: u8 = 70;
+const GS_K_CODE128: u8 = 73;
+
+// ════════════════════════════════════════════════════════════════════════════
+// TIPOS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Simbologia para impressão
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BarcodeSymbology {
+    #[default]
+    Ean13,
+    Ean8,
+    Code128,
+    #[serde(rename = "GS1_128")]
+    Gs1128,
+    Itf,
+    Code39,
+}
+
+impl BarcodeSymbology {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ean13 => "EAN-13",
+            Self::Ean8 => "EAN-8",
+            Self::Code128 => "CODE-128",
+            Self::Gs1128 => "GS1-128",
+            Self::Itf => "ITF",
+            Self::Code39 => "CODE-39",
+        }
+    }
+}
+
+/// Subconjunto do Code128
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Code128Subset {
+    /// Maiúsculas, dígitos e caracteres de controle
+    A,
+    /// ASCII imprimível
+    B,
+    /// Pares de dígitos (mais compacto para números)
+    C,
+    /// C para números de tamanho par, B nos demais casos
+    Auto,
+}
+
+/// Elemento GS1 (Identificador de Aplicação e valor)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Gs1Element {
+    pub ai: String,
+    pub value: String,
+}
+
+/// Dados GS1 lidos do código
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Gs1Data {
+    /// AI 01 (GTIN-14)
+    pub gtin: Option<String>,
+    /// AI 10
+    pub lot: Option<String>,
+    /// AI 17 (AAAA-MM-DD)
+    pub expiration_date: Option<String>,
+    /// AI 21
+    pub serial: Option<String>,
+    pub elements: Vec<Gs1Element>,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// DÍGITO VERIFICADOR
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Dígito verificador GS1 (pesos 3 e 1 a partir da direita)
+pub fn gs1_check_digit(body: &str) -> Option<u32> {
+    let mut sum = 0;
+    for (i, c) in body.chars().rev().enumerate() {
+        let digit = c.to_digit(10)?;
+        sum += if i % 2 == 0 { digit * 3 } else { digit };
+    }
+    Some((10 - sum % 10) % 10)
+}
+
+/// Confere o último dígito como verificador GS1
+pub fn validate_gs1_check_digit(code: &str) -> bool {
+    if code.len() < 2 || !code.is_ascii() {
+        return false;
+    }
+    let (body, check) = code.split_at(code.len() - 1);
+    match (
+        gs1_check_digit(body),
+        check.chars().next().and_then(|c| c.to_digit(10)),
+    ) {
+        (Some(expected), Some(check)) => expected == check,
+        _ => false,
+    }
+}
+
+/// Código do produto a partir do GTIN-14. Indicador 0 é o próprio EAN-13 (ou
+/// EAN-8 com zeros à esquerda); caixas (indicador 1-8, ITF-14/DUN-14) apontam
+/// para o EAN-13 da unidade, que tem o mesmo corpo
+pub fn gtin_to_product_code(gtin: &str) -> Option<String> {
+    if gtin.len() != 14 || !gtin.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if let Some(ean8) = gtin.strip_prefix("000000") {
+        return Some(ean8.to_string());
+    }
+    let body = &gtin[1..13];
+    Some(format!("{}{}", body, gs1_check_digit(body)?))
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// GS1
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Tamanho do AI e tamanho fixo do dado (None = variável, termina no FNC1),
+/// pela tabela de prefixos da especificação GS1
+fn ai_format(data: &str) -> Option<(usize, Option<usize>)> {
+    let prefix = data.get(..2)?;
+    if !prefix.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(match prefix {
+        "00" => (2, Some(18)),
+        "01" | "02" | "03" => (2, Some(14)),
+        "11" | "12" | "13" | "15" | "16" | "17" => (2, Some(6)),
+        "20" => (2, Some(2)),
+        "10" | "21" | "22" | "30" | "37" => (2, None),
+        "41" => (3, Some(13)),
+        "24" | "25" | "40" | "42" | "71" => (3, None),
+        "31" | "32" | "33" | "34" | "35" | "36" => (4, Some(6)),
+        "39" | "43" | "70" | "80" | "81" | "82" => (4, None),
+        _ if prefix.starts_with('9') => (2, None),
+        _ => return None,
+    })
+}
+
+/// Remove o identificador de simbologia AIM (`]C1`, `]d2`, `]E0`...)
+/// enviado por algumas leitoras antes do código
+pub fn strip_symbology_id(code: &str) -> (Option<&str>, &str) {
+    if code.starts_with(']') && code.len() >= 3 && code.is_char_boundary(3) {
+        (Some(&code[1..3]), &code[3..])
+    } else {
+        (None, code)
+    }
+}
+
+/// Lê os elementos GS1 do código, em formato transmitido (com FNC1/GS entre
+/// campos variáveis) ou legível (`(01)07891234567895(10)L123`). Retorna
+/// `None` se o código não for GS1.
+pub fn parse_gs1(code: &str) -> Option<Gs1Data> {
+    let (symbology, data) = strip_symbology_id(code.trim());
+    let elements = if data.starts_with('(') {
+        parse_gs1_human_readable(data)?
+    } else {
+        let declared = matches!(symbology, Some("C1" | "d2" | "Q3" | "e0" | "J1"));
+        // Sem identificador, só aceita estrutura GS1 inequívoca: separador
+        // FNC1 ou GTIN seguido de outros dados
+        let structured =
+            data.contains(GS1_SEPARATOR) || (data.starts_with("01") && data.len() > 16);
+        if !declared && !structured {
+            return None;
+        }
+        parse_gs1_elements(data)?
+    };
+    if elements.is_empty() {
+        return None;
+    }
+
+    let mut gs1 = Gs1Data::default();
+    let mut best_before = None;
+    for element in &elements {
+        match element.ai.as_str() {
+            "01" => {
+                if !validate_gs1_check_digit(&element.value) {
+                    return None;
+                }
+                gs1.gtin = Some(element.value.clone());
+            }
+            "10" => gs1.lot = Some(element.value.clone()),
+            "15" => best_before = gs1_date(&element.value),
+            "17" => gs1.expiration_date = gs1_date(&element.value),
+            "21" => gs1.serial = Some(element.value.clone()),
+            _ => {}
+        }
+    }
+    if gs1.expiration_date.is_none() {
+        gs1.expiration_date = best_before;
+    }
+    gs1.elements = elements;
+    Some(gs1)
+}
+
+fn parse_gs1_elements(data: &str) -> Option<Vec<Gs1Element>> {
+    let mut elements = Vec::new();
+    let mut rest = data.trim_start_matches(GS1_SEPARATOR);
+    while !rest.is_empty() {
+        let (ai_len, fixed) = ai_format(rest)?;
+        let ai = rest.get(..ai_len)?;
+        if !ai.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        rest = &rest[ai_len..];
+
+        let value_len = match fixed {
+            Some(len) => len,
+            None => rest.find(GS1_SEPARATOR).unwrap_or(rest.len()),
+        };
+        let value = rest.get(..value_len)?;
+        if value.is_empty() || value.contains(GS1_SEPARATOR) {
+            return None;
+        }
+        elements.push(Gs1Element {
+            ai: ai.to_string(),
+            value: value.to_string(),
+        });
+        rest = rest[value_len..].trim_start_matches(GS1_SEPARATOR);
+    }
+    Some(elements)
+}
+
+fn parse_gs1_human_readable(data: &str) -> Option<Vec<Gs1Element>> {
+    let mut elements = Vec::new();
+    let mut rest = data;
+    while let Some(after) = rest.strip_prefix('(') {
+        let close = after.find(')')?;
+        let ai = &after[..close];
+        let (ai_len, fixed) = ai_format(ai)?;
+        if ai.len() != ai_len {
+            return None;
+        }
+        let after = &after[close + 1..];
+        let value_len = after.find('(').unwrap_or(after.len());
+        let value = after[..value_len].trim();
+        if value.is_empty() || fixed.is_some_and(|len| value.len() != len) {
+            return None;
+        }
+        elements.push(Gs1Element {
+            ai: ai.to_string(),
+            value: value.to_string(),
+        });
+        rest = &after[value_len..];
+    }
+    rest.is_empty().then_some(elements)
+}
+
+/// Data GS1 `AAMMDD` em `AAAA-MM-DD`; dia 00 é o último dia do mês
+fn gs1_date(value: &str) -> Option<String> {
+    if value.len() != 6 || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let year = 2000 + value[..2].parse::<i32>().ok()?;
+    let month = value[2..4].parse::<u32>().ok()?;
+    let day = value[4..6].parse::<u32>().ok()?;
+    let date = if day == 0 {
+        let (next_year, next_month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+        chrono::NaiveDate::from_ymd_opt(next_year, next_month, 1)?.pred_opt()?
+    } else {
+        chrono::NaiveDate::from_ymd_opt(year, month, day)?
+    };
+    Some(date.format("%Y-%m-%d").to_string())
+}
+
+/// Converte o formato legível `(01)...(10)...` para o transmitido, com GS
+/// após cada campo variável que não seja o último
+fn gs1_element_string(data: &str) -> Option<String> {
+    let elements = if data.starts_with('(') {
+        parse_gs1_human_readable(data)?
+    } else {
+        parse_gs1_elements(data)?
+    };
+    let mut out = String::new();
+    for (i, element) in elements.iter().enumerate() {
+        out.push_str(&element.ai);
+        out.push_str(&element.value);
+        let variable = ai_format(&element.ai).is_some_and(|(_, fixed)| fixed.is_none());
+        if variable && i + 1 < elements.len() {
+            out.push(GS1_SEPARATOR);
+        }
+    }
+    Some(out)
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// CODIFICAÇÃO ESC/POS (GS k, função B)
+// ════════════════════════════════════════════════════════════════════════════
+
+fn encode_ean8(data: &str) -> Option<Vec<u8>> {
+    if !data.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    match data.len() {
+        7 => Some(format!("{}{}", data, gs1_check_digit(data)?).into_bytes()),
+        8 if validate_gs1_check_digit(data) => Some(data.as_bytes().to_vec()),
+        _ => None,
+    }
+}
+
+/// ITF codifica dígitos aos pares; ITF-14 tem o dígito verificador conferido
+fn encode_itf(data: &str) -> Option<Vec<u8>> {
+    if data.is_empty() || data.len() % 2 != 0 || !data.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if data.len() == 14 && !validate_gs1_check_digit(data) {
+        return None;
+    }
+    Some(data.as_bytes().to_vec())
+}
+
+fn encode_code39(data: &str) -> Option<Vec<u8>> {
+    let data = data.trim_matches('*').to_uppercase();
+    let valid = !data.is_empty()
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase() || b" -.$/+%".contains(&b));
+    valid.then(|| data.into_bytes())
+}
+
+fn encode_code128(data: &str, subset: Code128Subset) -> Option<Vec<u8>> {
+    if data.is_empty() {
+        return None;
+    }
+    let subset = match subset {
+        Code128Subset::Auto => {
+            if data.len() % 2 == 0 && data.bytes().all(|b| b.is_ascii_digit()) {
+                Code128Subset::C
+            } else if data.bytes().all(|b| (0x20..=0x7F).contains(&b)) {
+                Code128Subset::B
+            } else {
+                Code128Subset::A
+            }
+        }
+        other => other,
+    };
+
+    let mut out = Vec::with_capacity(data.len() + 2);
+    match subset {
+        Code128Subset::A => {
+            if !data.bytes().all(|b| b <= 0x5F) {
+                return None;
+            }
+            out.extend_from_slice(b"{A");
+            out.extend_from_slice(data.as_bytes());
+        }
+        Code128Subset::B => {
+            if !data.bytes().all(|b| (0x20..=0x7F).contains(&b)) {
+                return None;
+            }
+            out.extend_from_slice(b"{B");
+            for b in data.bytes() {
+                // '{' inicia comandos de controle; o caractere literal é "{{"
+                if b == b'{' {
+                    out.push(b'{');
+                }
+                out.push(b);
+            }
+        }
+        Code128Subset::C => {
+            if data.len() % 2 != 0 || !data.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            out.extend_from_slice(b"{C");
+            for pair in data.as_bytes().chunks(2) {
+                out.push((pair[0] - b'0') * 10 + (pair[1] - b'0'));
+            }
+        }
+        Code128Subset::Auto => unreachable!(),
+    }
+    Some(out)
+}
+
+/// GS1-128: Code128 iniciado por FNC1, com FNC1 separando campos variáveis
+fn encode_gs1_128(data: &str) -> Option<Vec<u8>> {
+    let elements = gs1_element_string(data)?;
+    if !elements.is_ascii() {
+        return None;
+    }
+    let mut out = Vec::with_capacity(elements.len() + 4);
+    if elements.len() % 2 == 0 && elements.bytes().all(|b| b.is_ascii_digit()) {
+        out.extend_from_slice(b"{C{1");
+        for pair in elements.as_bytes().chunks(2) {
+            out.push((pair[0] - b'0') * 10 + (pair[1] - b'0'));
+        }
+    } else {
+        out.extend_from_slice(b"{B{1");
+        for c in elements.chars() {
+            if c == GS1_SEPARATOR {
+                out.extend_from_slice(b"{1");
+            } else {
+                out.push(c as u8);
+            }
+        }
+    }
+    Some(out)
+}
+
+/// Módulos aproximados do símbolo, para escolher a largura da barra
+fn symbol_modules(m: u8, payload: &[u8]) -> u32 {
+    let n = payload.len() as u32;
+    match m {
+        GS_K_EAN8 => 67,
+        GS_K_ITF => n * 9 + 9,
+        GS_K_CODE39 => (n + 2) * 16,
+        // Símbolos + início, verificador e parada (prefixos "{X" não contam)
+        _ => (n.saturating_sub(2) + 3) * 11 + 2,
+    }
+}
+
+impl ThermalPrinter {
+    /// Imprime código de barras na simbologia informada; dados inválidos para
+    /// a simbologia são ignorados, como em `barcode_ean13`
+    pub fn barcode(&mut self, symbology: BarcodeSymbology, data: &str) -> &mut Self {
+        let encoded = match symbology {
+            BarcodeSymbology::Ean13 => return self.barcode_ean13(data),
+            BarcodeSymbology::Ean8 => encode_ean8(data).map(|d| (GS_K_EAN8, d)),
+            BarcodeSymbology::Code128 => {
+                encode_code128(data, Code128Subset::Auto).map(|d| (GS_K_CODE128, d))
+            }
+            BarcodeSymbology::Gs1128 => encode_gs1_128(data).map(|d| (GS_K_CODE128, d)),
+            BarcodeSymbology::Itf => encode_itf(data).map(|d| (GS_K_ITF, d)),
+            BarcodeSymbology::Code39 => encode_code39(data).map(|d| (GS_K_CODE39, d)),
+        };
+        match encoded {
+            Some((m, payload)) => self.barcode_function_b(m, &payload),
+            None => self,
+        }
+    }
+
+    /// Code128 em um subconjunto específico
+    pub fn barcode_code128(&mut self, data: &str, subset: Code128Subset) -> &mut Self {
+        match encode_code128(data, subset) {
+            Some(payload) => self.barcode_function_b(GS_K_CODE128, &payload),
+            None => self,
+        }
+    }
+
+    fn barcode_function_b(&mut self, m: u8, payload: &[u8]) -> &mut Self {
+        if payload.len() > 255 {
+            return self;
+        }
+        // Barra de 2 pontos quando cabe na bobina, senão a mais fina
+        let fits = symbol_modules(m, payload) * 2 <= paper_width_dots(self.config());
+        let width = if fits { 2 } else { 1 };
+
+        self.raw(&escpos::BARCODE_HEIGHT);
+        self.raw(&[escpos::GS, b'w', width]);
+        self.raw(&escpos::BARCODE_HRI_BELOW);
+        self.raw(&[escpos::GS, b'k', m, payload.len() as u8]);
+        self.raw(payload);
+        self.raw(&[escpos::LF]);
+        self
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TESTES
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::printer::PrinterConfig;
+
+    #[test]
+    fn test_check_digit() {
+        assert_eq!(gs1_check_digit("789123456789"), Some(5));
+        assert!(validate_gs1_check_digit("7891234567895"));
+        assert!(validate_gs1_check_digit("12345670"));
+        assert!(validate_gs1_check_digit("17891234567892"));
+        assert!(!validate_gs1_check_digit("7891234567890"));
+        assert!(!validate_gs1_check_digit("78912345A7895"));
+    }
+
+    #[test]
+    fn test_gtin_to_product_code() {
+        assert_eq!(
+            gtin_to_product_code("07891234567895").as_deref(),
+            Some("7891234567895")
+        );
+        // Caixa com indicador 1: mesmo corpo, verificador recalculado
+        assert_eq!(
+            gtin_to_product_code("17891234567892").as_deref(),
+            Some("7891234567895")
+        );
+        assert_eq!(
+            gtin_to_product_code("00000012345670").as_deref(),
+            Some("12345670")
+        );
+    }
+
+    #[test]
+    fn test_parse_gs1_transmitted() {
+        let gs1 = parse_gs1("]C101078912345678951726123110LOTE42\u{1D}21SN9").unwrap();
+        assert_eq!(gs1.gtin.as_deref(), Some("07891234567895"));
+        assert_eq!(gs1.expiration_date.as_deref(), Some("2026-12-31"));
+        assert_eq!(gs1.lot.as_deref(), Some("LOTE42"));
+        assert_eq!(gs1.serial.as_deref(), Some("SN9"));
+        assert_eq!(gs1.elements.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_gs1_human_readable_and_day_zero() {
+        let gs1 = parse_gs1("(01)07891234567895(17)270200(10)A1").unwrap();
+        assert_eq!(gs1.expiration_date.as_deref(), Some("2027-02-28"));
+        assert_eq!(gs1.lot.as_deref(), Some("A1"));
+    }
+
+    #[test]
+    fn test_parse_gs1_rejects_plain_codes() {
+        assert!(parse_gs1("7891234567895").is_none());
+        assert!(parse_gs1("17891234567892").is_none());
+        // GTIN com verificador errado
+        assert!(parse_gs1("(01)07891234567890").is_none());
+    }
+
+    #[test]
+    fn test_code128_subsets() {
+        assert_eq!(
+            encode_code128("123456", Code128Subset::Auto).unwrap(),
+            vec![b'{', b'C', 12, 34, 56]
+        );
+        assert_eq!(
+            encode_code128("AB{1", Code128Subset::Auto).unwrap(),
+            b"{BAB{{1".to_vec()
+        );
+        assert!(encode_code128("abc", Code128Subset::A).is_none());
+        assert!(encode_code128("123", Code128Subset::C).is_none());
+    }
+
+    #[test]
+    fn test_gs1_128_inserts_fnc1() {
+        assert_eq!(
+            encode_gs1_128("(10)L1(17)261231").unwrap(),
+            b"{B{110L1{117261231".to_vec()
+        );
+        assert_eq!(
+            encode_gs1_128("(01)07891234567895").unwrap()[..6],
+            [b'{', b'C', b'{', b'1', 1, 7]
+        );
+    }
+
+    #[test]
+    fn test_other_symbologies() {
+        assert_eq!(encode_ean8("1234567").unwrap(), b"12345670".to_vec());
+        assert!(encode_ean8("12345671").is_none());
+        assert!(encode_itf("123").is_none());
+        assert!(encode_itf("17891234567890").is_none());
+        assert!(encode_itf("17891234567892").is_some());
+        assert_eq!(encode_code39("*abc-1*").unwrap(), b"ABC-1".to_vec());
+        assert!(encode_code39("a_b").is_none());
+    }
+
+    #[test]
+    fn test_printer_barcode_command() {
+        let mut printer = ThermalPrinter::new(PrinterConfig::default());
+        printer.barcode(BarcodeSymbology::Itf, "17891234567892");
+        let buffer = printer.get_buffer();
+        let header = [escpos::GS, b'k', GS_K_ITF, 14];
+        assert!(buffer.windows(4).any(|w| w == header));
+
+        let mut printer = ThermalPrinter::new(PrinterConfig::default());
+        printer.barcode(BarcodeSymbology::Ean8, "invalido");
+        assert!(printer.get_buffer().is_empty());
+    }
+}
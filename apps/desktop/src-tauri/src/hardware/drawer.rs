@@ -1,11 +1,14 @@
 //! Módulo de Gaveta de Dinheiro
 //!
-//! Controle de gaveta via impressora térmica (pulso RJ11)
+//! Controle de gaveta via impressora térmica (pulso RJ11), com leitura do
+//! sensor de gaveta aberta pelo status em tempo real da impressora
 
-use super::{HardwareError, HardwareResult};
+use super::{
+    printer::PrinterConfig, printer_status::query_printer_sensors, HardwareError, HardwareResult,
+};
 use serde::{Deserialize, Serialize};
 use std::io::Write;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 // ════════════════════════════════════════════════════════════════════════════
 // TIPOS
@@ -22,6 +25,9 @@ pub struct DrawerConfig {
     /// Duração do pulso em ms
     pub pulse_duration: u16,
     pub mock_mode: bool,
+    /// Sensor com lógica invertida (pino 3 em nível alto = gaveta aberta)
+    #[serde(default)]
+    pub sensor_inverted: bool,
 }
 
 /// Pino de acionamento da gaveta
@@ -40,6 +46,7 @@ impl Default for DrawerConfig {
             pin: DrawerPin::Pin2,
             pulse_duration: 200,
             mock_mode: false,
+            sensor_inverted: false,
         }
     }
 }
@@ -53,6 +60,22 @@ pub struct DrawerStatus {
     pub open_count_today: u32,
 }
 
+/// Resultado do acompanhamento da gaveta após a abertura
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrawerWatchOutcome {
+    /// Gaveta abriu e foi fechada
+    Closed,
+    /// Continua aberta ao fim do tempo limite
+    StillOpen,
+    /// O sensor nunca indicou a gaveta aberta (sem sensor ou sem pulso)
+    NotOpened,
+}
+
+/// Tempo para o sensor indicar a abertura após o pulso
+const OPEN_GRACE: Duration = Duration::from_secs(2);
+/// Intervalo de leitura do sensor
+const SENSOR_POLL: Duration = Duration::from_millis(300);
+
 // ════════════════════════════════════════════════════════════════════════════
 // COMANDOS ESC/POS PARA GAVETA
 // ════════════════════════════════════════════════════════════════════════════
@@ -181,6 +204,46 @@ impl CashDrawer {
 
         Ok(())
     }
+
+    /// Lê o sensor de gaveta aberta pela impressora (bloqueante)
+    pub fn sensor_open(&self, printer: &PrinterConfig) -> HardwareResult<bool> {
+        let sensors = query_printer_sensors(printer)?;
+        Ok(sensors.drawer_open(self.config.sensor_inverted))
+    }
+
+    /// Aguarda o fechamento da gaveta após `open` (bloqueante)
+    pub fn wait_closed(
+        &self,
+        printer: &PrinterConfig,
+        timeout: Duration,
+    ) -> HardwareResult<DrawerWatchOutcome> {
+        watch_until_closed(|| self.sensor_open(printer), timeout, SENSOR_POLL)
+    }
+}
+
+/// Acompanha o sensor: espera a gaveta abrir (até `OPEN_GRACE`) e depois fechar
+fn watch_until_closed(
+    mut read_open: impl FnMut() -> HardwareResult<bool>,
+    timeout: Duration,
+    poll: Duration,
+) -> HardwareResult<DrawerWatchOutcome> {
+    let start = Instant::now();
+    let mut opened = false;
+
+    loop {
+        if read_open()? {
+            opened = true;
+        } else if opened {
+            return Ok(DrawerWatchOutcome::Closed);
+        } else if start.elapsed() >= OPEN_GRACE {
+            return Ok(DrawerWatchOutcome::NotOpened);
+        }
+
+        if start.elapsed() >= timeout {
+            return Ok(DrawerWatchOutcome::StillOpen);
+        }
+        std::thread::sleep(poll);
+    }
 }
 
 // ════════════════════════════════════════════════════════════════════════════
@@ -215,4 +278,37 @@ mod tests {
         assert!(!drawer.is_enabled());
         assert!(drawer.open().is_ok()); // Não deve falhar quando desabilitado
     }
+
+    #[test]
+    fn test_watch_until_closed() {
+        let mut readings = vec![false, true, true, false].into_iter();
+        let outcome = watch_until_closed(
+            || Ok(readings.next().unwrap_or(false)),
+            Duration::from_secs(5),
+            Duration::ZERO,
+        )
+        .unwrap();
+        assert_eq!(outcome, DrawerWatchOutcome::Closed);
+    }
+
+    #[test]
+    fn test_watch_still_open() {
+        let outcome = watch_until_closed(
+            || Ok(true),
+            Duration::from_millis(20),
+            Duration::from_millis(5),
+        )
+        .unwrap();
+        assert_eq!(outcome, DrawerWatchOutcome::StillOpen);
+    }
+
+    #[test]
+    fn test_watch_sensor_error() {
+        let result = watch_until_closed(
+            || Err(HardwareError::Timeout),
+            Duration::from_secs(1),
+            Duration::ZERO,
+        );
+        assert!(result.is_err());
+    }
 }
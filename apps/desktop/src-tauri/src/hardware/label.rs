@@ -0,0 +1,741 @@
+//! Impressoras de Etiquetas - ZPL, EPL e PPLA
+//!
+//! Suporta:
+//! - Zebra (ZPL II e EPL2)
+//! - Argox e Elgin (PPLA)
+//!
+//! A etiqueta de gôndola/produto (nome, preço, preço por kg ou litro e
+//! código de barras) é montada uma vez em décimos de milímetro e depois
+//! convertida para a linguagem da impressora. O envio usa o mesmo transporte
+//! das impressoras térmicas (USB, serial ou rede).
+
+use super::barcode::validate_gs1_check_digit;
+use super::printer::{PrinterConfig, PrinterConnection, PrinterModel, ThermalPrinter};
+use super::{HardwareError, HardwareResult};
+use crate::models::{Product, ProductUnit};
+use serde::{Deserialize, Serialize};
+
+/// Início de comando PPLA
+const STX: u8 = 0x02;
+
+/// Margem interna da etiqueta (décimos de mm)
+const MARGIN: u32 = 20;
+
+/// Altura mínima útil do código de barras (décimos de mm)
+const MIN_BARCODE_HEIGHT: u32 = 50;
+
+/// Altura reservada para os números abaixo das barras (décimos de mm)
+const HRI_HEIGHT: u32 = 30;
+
+/// Máximo de cópias por etiqueta num único comando
+pub const MAX_LABEL_COPIES: u16 = 999;
+
+// ════════════════════════════════════════════════════════════════════════════
+// TIPOS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Linguagem de comandos da impressora de etiquetas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelLanguage {
+    /// Zebra ZPL II
+    #[default]
+    Zpl,
+    /// Zebra EPL2 (linha LP/TLP)
+    Epl,
+    /// Argox/Elgin PPLA
+    Ppla,
+}
+
+/// Configuração da impressora de etiquetas
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelPrinterConfig {
+    pub enabled: bool,
+    pub language: LabelLanguage,
+    pub connection: PrinterConnection,
+    pub port: String,
+    pub baud_rate: u32,
+    /// Resolução do cabeçote (203 ou 300 dpi)
+    pub dpi: u16,
+    /// Largura da etiqueta em mm
+    pub label_width_mm: u16,
+    /// Altura da etiqueta em mm
+    pub label_height_mm: u16,
+    /// Espaço entre etiquetas em mm
+    pub gap_mm: u16,
+    /// Temperatura de impressão (0-30)
+    pub darkness: u8,
+    #[specta(type = i32)]
+    pub timeout_ms: u64,
+    pub mock_mode: bool,
+}
+
+impl Default for LabelPrinterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            language: LabelLanguage::Zpl,
+            connection: PrinterConnection::Usb,
+            port: String::new(),
+            baud_rate: 9600,
+            dpi: 203,
+            label_width_mm: 60,
+            label_height_mm: 40,
+            gap_mm: 3,
+            darkness: 15,
+            timeout_ms: 3000,
+            mock_mode: false,
+        }
+    }
+}
+
+impl LabelPrinterConfig {
+    /// Configuração de transporte equivalente (USB, serial ou rede)
+    pub(super) fn transport(&self) -> PrinterConfig {
+        PrinterConfig {
+            enabled: self.enabled,
+            model: PrinterModel::Generic,
+            connection: self.connection.clone(),
+            port: self.port.clone(),
+            auto_cut: false,
+            open_drawer_on_sale: false,
+            baud_rate: self.baud_rate,
+            timeout_ms: self.timeout_ms,
+            mock_mode: self.mock_mode,
+            ..PrinterConfig::default()
+        }
+    }
+
+    /// Décimos de milímetro para pontos do cabeçote
+    fn dots(&self, tenths_mm: u32) -> u32 {
+        (tenths_mm * u32::from(self.dpi.max(1)) + 127) / 254
+    }
+}
+
+/// Dados de uma etiqueta de produto
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductLabel {
+    pub name: String,
+    /// Código interno (usado nas barras quando o produto não tem EAN)
+    pub code: String,
+    pub price: f64,
+    /// Unidade de venda ("un", "kg", "L"...)
+    pub unit: String,
+    pub barcode: Option<String>,
+    /// Preço por kg ou litro dos produtos fracionados
+    pub unit_price: Option<f64>,
+    /// Medida do preço unitário ("kg" ou "L")
+    pub unit_price_measure: Option<String>,
+}
+
+impl ProductLabel {
+    pub fn from_product(product: &Product) -> Self {
+        let unit = ProductUnit::from_db_str(&product.unit).unwrap_or_default();
+        let price = product.sale_price;
+        let unit_price = match unit {
+            ProductUnit::Kilogram => Some((price, "kg")),
+            ProductUnit::Gram => Some((price * 1000.0, "kg")),
+            ProductUnit::Liter => Some((price, "L")),
+            ProductUnit::Milliliter => Some((price * 1000.0, "L")),
+            _ if product.is_weighted => Some((price, "kg")),
+            _ => None,
+        };
+
+        Self {
+            name: product.name.clone(),
+            code: product.internal_code.clone(),
+            price,
+            unit: unit.to_string(),
+            barcode: product
+                .barcode
+                .as_ref()
+                .map(|b| b.trim().to_string())
+                .filter(|b| !b.is_empty()),
+            unit_price: unit_price.map(|(value, _)| value),
+            unit_price_measure: unit_price.map(|(_, measure)| measure.to_string()),
+        }
+    }
+
+    /// Preço de venda (com a unidade quando não é por peça)
+    fn price_text(&self) -> String {
+        let price = format!("R$ {}", format_money(self.price));
+        match self.unit.as_str() {
+            "un" | "" => price,
+            unit => format!("{}/{}", price, unit),
+        }
+    }
+
+    /// Linha do preço por kg/litro
+    fn unit_price_text(&self) -> Option<String> {
+        let value = self.unit_price?;
+        let measure = self.unit_price_measure.as_deref().unwrap_or("kg");
+        Some(format!("Preço por {}: R$ {}", measure, format_money(value)))
+    }
+
+    /// Simbologia e conteúdo das barras: EAN quando válido, senão Code128
+    fn barcode_data(&self) -> Option<(LabelBarcode, String)> {
+        let code = self
+            .barcode
+            .clone()
+            .unwrap_or_else(|| self.code.trim().to_string());
+        if code.is_empty() {
+            return None;
+        }
+        let digits = code.chars().all(|c| c.is_ascii_digit());
+        let kind = match code.len() {
+            13 if digits && validate_gs1_check_digit(&code) => LabelBarcode::Ean13,
+            8 if digits && validate_gs1_check_digit(&code) => LabelBarcode::Ean8,
+            _ => LabelBarcode::Code128,
+        };
+        Some((kind, code))
+    }
+}
+
+/// Simbologia usada na etiqueta
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelBarcode {
+    Ean13,
+    Ean8,
+    Code128,
+}
+
+/// Elemento posicionado na etiqueta (medidas em décimos de mm, origem no
+/// canto superior esquerdo)
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelElement {
+    Text {
+        x: u32,
+        y: u32,
+        height: u32,
+        text: String,
+    },
+    Barcode {
+        x: u32,
+        y: u32,
+        height: u32,
+        kind: LabelBarcode,
+        data: String,
+    },
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// LAYOUT
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Distribui nome (até 2 linhas), preço, preço por kg e código de barras
+/// no tamanho de etiqueta configurado
+pub fn layout_label(label: &ProductLabel, config: &LabelPrinterConfig) -> Vec<LabelElement> {
+    let width = u32::from(config.label_width_mm) * 10;
+    let height = u32::from(config.label_height_mm) * 10;
+    let inner_width = width.saturating_sub(2 * MARGIN);
+    let mut elements = Vec::new();
+    let mut y = MARGIN;
+
+    let name_height = 30;
+    let max_chars = (inner_width * 10 / (name_height * 6)).max(8) as usize;
+    for line in wrap_text(&label.name, max_chars, 2) {
+        elements.push(LabelElement::Text {
+            x: MARGIN,
+            y,
+            height: name_height,
+            text: line,
+        });
+        y += name_height + 5;
+    }
+
+    let price_height = (height / 4).clamp(40, 80);
+    elements.push(LabelElement::Text {
+        x: MARGIN,
+        y: y + 5,
+        height: price_height,
+        text: label.price_text(),
+    });
+    y += price_height + 15;
+
+    if let Some(text) = label.unit_price_text() {
+        let unit_height = 25;
+        elements.push(LabelElement::Text {
+            x: MARGIN,
+            y,
+            height: unit_height,
+            text,
+        });
+        y += unit_height + 5;
+    }
+
+    // Barras no espaço restante (omitidas se a etiqueta for pequena demais)
+    if let Some((kind, data)) = label.barcode_data() {
+        let available = height.saturating_sub(y + MARGIN + HRI_HEIGHT);
+        if available >= MIN_BARCODE_HEIGHT {
+            elements.push(LabelElement::Barcode {
+                x: MARGIN,
+                y: y + 5,
+                height: available.min(150) - 5,
+                kind,
+                data,
+            });
+        }
+    }
+
+    elements
+}
+
+/// Quebra o texto em até `max_lines` linhas de `max_chars`
+fn wrap_text(text: &str, max_chars: usize, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let needed =
+            current.chars().count() + word.chars().count() + usize::from(!current.is_empty());
+        if needed > max_chars && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines.truncate(max_lines);
+    lines
+        .into_iter()
+        .map(|l| l.chars().take(max_chars).collect())
+        .collect()
+}
+
+fn format_money(value: f64) -> String {
+    format!("{:.2}", value).replace('.', ",")
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// GERADORES
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Gera os comandos de todas as etiquetas (`copies` de cada) na linguagem
+/// configurada
+pub fn render_labels(config: &LabelPrinterConfig, labels: &[ProductLabel], copies: u16) -> Vec<u8> {
+    let copies = copies.clamp(1, MAX_LABEL_COPIES);
+    let mut out = Vec::new();
+    if config.language == LabelLanguage::Ppla {
+        // Unidades métricas (décimos de mm) para todas as etiquetas
+        out.extend_from_slice(&[STX, b'm', b'\r']);
+    }
+    for label in labels {
+        let elements = layout_label(label, config);
+        let bytes = match config.language {
+            LabelLanguage::Zpl => render_zpl(config, &elements, copies),
+            LabelLanguage::Epl => render_epl(config, &elements, copies),
+            LabelLanguage::Ppla => render_ppla(config, &elements, copies),
+        };
+        out.extend_from_slice(&bytes);
+    }
+    out
+}
+
+/// ZPL II: `^XA ... ^XZ`, textos em UTF-8 (`^CI28`) com a fonte escalável 0
+fn render_zpl(config: &LabelPrinterConfig, elements: &[LabelElement], copies: u16) -> Vec<u8> {
+    let mut zpl = String::from("^XA\n^CI28\n");
+    zpl.push_str(&format!(
+        "^PW{}\n^LL{}\n~SD{:02}\n",
+        config.dots(u32::from(config.label_width_mm) * 10),
+        config.dots(u32::from(config.label_height_mm) * 10),
+        config.darkness.min(30)
+    ));
+
+    for element in elements {
+        match element {
+            LabelElement::Text { x, y, height, text } => {
+                zpl.push_str(&format!(
+                    "^FO{},{}^A0N,{}^FD{}^FS\n",
+                    config.dots(*x),
+                    config.dots(*y),
+                    config.dots(*height),
+                    zpl_escape(text)
+                ));
+            }
+            LabelElement::Barcode {
+                x,
+                y,
+                height,
+                kind,
+                data,
+            } => {
+                let (command, data) = match kind {
+                    // O dígito verificador é calculado pela impressora
+                    LabelBarcode::Ean13 => ("^BEN", data[..12].to_string()),
+                    LabelBarcode::Ean8 => ("^B8N", data[..7].to_string()),
+                    // '>' inicia códigos de controle no Code128
+                    LabelBarcode::Code128 => ("^BCN", zpl_escape(data).replace('>', "><")),
+                };
+                zpl.push_str(&format!(
+                    "^FO{},{}^BY2{},{},Y,N^FD{}^FS\n",
+                    config.dots(*x),
+                    config.dots(*y),
+                    command,
+                    config.dots(*height),
+                    data
+                ));
+            }
+        }
+    }
+
+    zpl.push_str(&format!("^PQ{}\n^XZ\n", copies));
+    zpl.into_bytes()
+}
+
+fn zpl_escape(text: &str) -> String {
+    text.replace(['^', '~'], " ")
+}
+
+/// EPL2: formulário `N ... P`, textos em Windows-1252 (`I8,A`)
+fn render_epl(config: &LabelPrinterConfig, elements: &[LabelElement], copies: u16) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(
+        format!(
+            "\nN\nI8,A,001\nq{}\nQ{},{}\nD{}\n",
+            config.dots(u32::from(config.label_width_mm) * 10),
+            config.dots(u32::from(config.label_height_mm) * 10),
+            config.dots(u32::from(config.gap_mm) * 10),
+            (config.darkness / 2).min(15)
+        )
+        .as_bytes(),
+    );
+
+    for element in elements {
+        let line = match element {
+            LabelElement::Text { x, y, height, text } => {
+                // Fontes 1-5 (altura em pontos a 203 dpi) ampliadas pelo multiplicador
+                let (font, multiplier) = pick_font(
+                    config,
+                    config.dots(*height),
+                    &[(1, 12), (2, 16), (3, 20), (4, 24), (5, 48)],
+                );
+                format!(
+                    "A{},{},0,{},{},{},N,\"{}\"\n",
+                    config.dots(*x),
+                    config.dots(*y),
+                    font,
+                    multiplier,
+                    multiplier,
+                    text.replace('\\', "\\\\").replace('"', "\\\"")
+                )
+            }
+            LabelElement::Barcode {
+                x,
+                y,
+                height,
+                kind,
+                data,
+            } => {
+                let (symbology, data) = match kind {
+                    LabelBarcode::Ean13 => ("E30", &data[..12]),
+                    LabelBarcode::Ean8 => ("E80", &data[..7]),
+                    LabelBarcode::Code128 => ("1", data.as_str()),
+                };
+                format!(
+                    "B{},{},0,{},2,4,{},B,\"{}\"\n",
+                    config.dots(*x),
+                    config.dots(*y),
+                    symbology,
+                    config.dots(*height),
+                    data.replace('"', "")
+                )
+            }
+        };
+        out.extend_from_slice(&encode_1252(&line));
+    }
+
+    out.extend_from_slice(format!("P{}\n", copies).as_bytes());
+    out
+}
+
+/// PPLA: `<STX>L ... E`, um registro por linha; a linha (row) é medida a
+/// partir da base da etiqueta, em décimos de mm (modo métrico)
+fn render_ppla(config: &LabelPrinterConfig, elements: &[LabelElement], copies: u16) -> Vec<u8> {
+    let label_height = u32::from(config.label_height_mm) * 10;
+    let mut out = vec![STX, b'L', b'\r'];
+    out.extend_from_slice(format!("D11\rH{:02}\r", config.darkness.min(30)).as_bytes());
+
+    for element in elements {
+        let record = match element {
+            LabelElement::Text { x, y, height, text } => {
+                // Fontes internas 1-6 (altura em pontos a 203 dpi)
+                let (font, multiplier) = pick_font(
+                    config,
+                    config.dots(*height),
+                    &[(1, 13), (2, 18), (3, 27), (4, 36), (5, 52), (6, 64)],
+                );
+                let row = label_height.saturating_sub(y + height);
+                format!(
+                    "1{}{}{}000{:04}{:04}{}\r",
+                    font, multiplier, multiplier, row, x, text
+                )
+            }
+            LabelElement::Barcode {
+                x,
+                y,
+                height,
+                kind,
+                data,
+            } => {
+                let (symbology, data) = match kind {
+                    LabelBarcode::Ean13 => ('F', &data[..12]),
+                    LabelBarcode::Ean8 => ('G', &data[..7]),
+                    LabelBarcode::Code128 => ('E', data.as_str()),
+                };
+                let row = label_height.saturating_sub(y + height);
+                format!(
+                    "1{}42{:03}{:04}{:04}{}\r",
+                    symbology,
+                    (*height).min(999),
+                    row,
+                    x,
+                    data
+                )
+            }
+        };
+        out.extend_from_slice(&encode_1252(&record));
+    }
+
+    out.extend_from_slice(format!("Q{:04}\rE\r", copies).as_bytes());
+    out
+}
+
+/// Fonte interna cuja altura (ajustada à resolução) mais se aproxima da
+/// pedida, com o multiplicador (1-9) para as alturas maiores que a base
+fn pick_font(config: &LabelPrinterConfig, height_dots: u32, fonts: &[(u8, u32)]) -> (u8, u32) {
+    let scale = |base: u32| base * u32::from(config.dpi.max(1)) / 203;
+    let (font, base) = fonts
+        .iter()
+        .rev()
+        .find(|(_, base)| scale(*base) <= height_dots)
+        .copied()
+        .unwrap_or(fonts[0]);
+    let multiplier = (height_dots / scale(base).max(1)).clamp(1, 9);
+    (font, multiplier)
+}
+
+fn encode_1252(text: &str) -> Vec<u8> {
+    let (bytes, _, _) = encoding_rs::WINDOWS_1252.encode(text);
+    bytes.into_owned()
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// IMPRESSORA
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Impressora de etiquetas
+pub struct LabelPrinter {
+    config: LabelPrinterConfig,
+}
+
+impl LabelPrinter {
+    pub fn new(config: LabelPrinterConfig) -> Self {
+        Self { config }
+    }
+
+    /// Imprime `copies` etiquetas de cada produto
+    pub async fn print_labels(&self, labels: &[ProductLabel], copies: u16) -> HardwareResult<()> {
+        if labels.is_empty() {
+            return Ok(());
+        }
+        self.send(&render_labels(&self.config, labels, copies))
+            .await
+    }
+
+    /// Envia comandos já gerados pela conexão configurada
+    pub async fn send(&self, bytes: &[u8]) -> HardwareResult<()> {
+        if !self.config.enabled {
+            return Err(HardwareError::NotConfigured(
+                "Impressora de etiquetas não habilitada".into(),
+            ));
+        }
+        if self.config.mock_mode {
+            tracing::info!(
+                "[LabelPrinter] MOCK PRINT ({:?}, {} bytes)",
+                self.config.language,
+                bytes.len()
+            );
+            return Ok(());
+        }
+
+        let mut transport = ThermalPrinter::new(self.config.transport());
+        transport.raw(bytes);
+        match self.config.connection {
+            PrinterConnection::Network => transport.print_network().await,
+            PrinterConnection::Usb => tokio::task::spawn_blocking(move || transport.print_usb())
+                .await
+                .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))?,
+            PrinterConnection::Serial => {
+                tokio::task::spawn_blocking(move || transport.print_serial())
+                    .await
+                    .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))?
+            }
+        }
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TESTES
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(unit: &str, barcode: Option<&str>) -> ProductLabel {
+        let product = Product {
+            id: "p1".into(),
+            barcode: barcode.map(String::from),
+            internal_code: "000123".into(),
+            name: "Queijo Mussarela Fatiado Tipo Exportação Premium".into(),
+            description: None,
+            notes: None,
+            unit: unit.into(),
+            is_weighted: false,
+            sale_price: 4.99,
+            cost_price: 3.0,
+            current_stock: 10.0,
+            min_stock: 0.0,
+            max_stock: None,
+            is_active: true,
+            category_id: "c1".into(),
+            oem_code: None,
+            aftermarket_code: None,
+            part_brand: None,
+            application: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        ProductLabel::from_product(&product)
+    }
+
+    #[test]
+    fn test_unit_price_per_kg() {
+        let label = sample("GRAM", None);
+        assert_eq!(label.unit_price, Some(4990.0));
+        assert_eq!(label.unit_price_measure.as_deref(), Some("kg"));
+        assert_eq!(
+            label.unit_price_text().as_deref(),
+            Some("Preço por kg: R$ 4990,00")
+        );
+
+        let label = sample("KILOGRAM", None);
+        assert_eq!(label.price_text(), "R$ 4,99/kg");
+        assert!(sample("UNIT", None).unit_price.is_none());
+        assert_eq!(sample("UNIT", None).price_text(), "R$ 4,99");
+    }
+
+    #[test]
+    fn test_barcode_choice() {
+        let ean = sample("UNIT", Some("7891234567895"));
+        assert_eq!(
+            ean.barcode_data(),
+            Some((LabelBarcode::Ean13, "7891234567895".into()))
+        );
+        // EAN com dígito errado e produto sem EAN usam Code128
+        let bad = sample("UNIT", Some("7891234567890"));
+        assert_eq!(bad.barcode_data().unwrap().0, LabelBarcode::Code128);
+        let internal = sample("UNIT", None);
+        assert_eq!(
+            internal.barcode_data(),
+            Some((LabelBarcode::Code128, "000123".into()))
+        );
+    }
+
+    #[test]
+    fn test_layout_fits_label() {
+        let config = LabelPrinterConfig::default();
+        let elements = layout_label(&sample("GRAM", Some("7891234567895")), &config);
+        let texts = elements
+            .iter()
+            .filter(|e| matches!(e, LabelElement::Text { .. }))
+            .count();
+        // Nome em 2 linhas, preço e preço por kg
+        assert_eq!(texts, 4);
+        let Some(LabelElement::Barcode { y, height, .. }) = elements.last() else {
+            panic!("etiqueta sem código de barras");
+        };
+        assert!(y + height + HRI_HEIGHT <= 400);
+
+        // Etiqueta pequena demais para as barras
+        let small = LabelPrinterConfig {
+            label_height_mm: 15,
+            ..config
+        };
+        let elements = layout_label(&sample("UNIT", Some("7891234567895")), &small);
+        assert!(!elements
+            .iter()
+            .any(|e| matches!(e, LabelElement::Barcode { .. })));
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(
+            wrap_text("Arroz Branco Tipo 1 Pacote 5kg", 12, 2),
+            vec!["Arroz Branco", "Tipo 1"]
+        );
+        assert_eq!(wrap_text("Supercalifragilistico", 8, 2), vec!["Supercal"]);
+    }
+
+    #[test]
+    fn test_render_zpl() {
+        let config = LabelPrinterConfig::default();
+        let out = render_labels(&config, &[sample("UNIT", Some("7891234567895"))], 3);
+        let zpl = String::from_utf8(out).unwrap();
+        assert!(zpl.starts_with("^XA\n^CI28\n^PW480\n^LL320\n"));
+        assert!(zpl.contains("^FDR$ 4,99^FS"));
+        assert!(zpl.contains("^BY2^BEN,"));
+        assert!(zpl.contains("^FD789123456789^FS"));
+        assert!(zpl.ends_with("^PQ3\n^XZ\n"));
+    }
+
+    #[test]
+    fn test_render_epl() {
+        let config = LabelPrinterConfig {
+            language: LabelLanguage::Epl,
+            ..Default::default()
+        };
+        let out = render_labels(&config, &[sample("GRAM", None)], 1);
+        assert!(out.starts_with(b"\nN\nI8,A,001\nq480\nQ320,24\n"));
+        // "ç" em Windows-1252
+        assert!(out.windows(2).any(|w| w == [0xE7, b'o']));
+        let text = String::from_utf8_lossy(&out);
+        assert!(text.contains(",1,2,4,"));
+        assert!(text.contains("B,\"000123\""));
+        assert!(text.ends_with("P1\n"));
+    }
+
+    #[test]
+    fn test_render_ppla() {
+        let config = LabelPrinterConfig {
+            language: LabelLanguage::Ppla,
+            ..Default::default()
+        };
+        let labels = [
+            sample("UNIT", Some("7891234567895")),
+            sample("UNIT", Some("7891234567895")),
+        ];
+        let out = render_labels(&config, &labels, 2);
+        assert!(out.starts_with(&[STX, b'm', b'\r', STX, b'L', b'\r']));
+        let text = String::from_utf8_lossy(&out);
+        assert_eq!(text.matches("Q0002\rE\r").count(), 2);
+        // Barras EAN-13 com a linha medida da base da etiqueta
+        assert!(text.contains("1F42"));
+        assert!(text.contains("789123456789\r"));
+    }
+
+    #[test]
+    fn test_pick_font() {
+        let config = LabelPrinterConfig::default();
+        let fonts = [(1, 12), (2, 16), (3, 20), (4, 24), (5, 48)];
+        assert_eq!(pick_font(&config, 24, &fonts), (4, 1));
+        assert_eq!(pick_font(&config, 64, &fonts), (5, 1));
+        assert_eq!(pick_font(&config, 100, &fonts), (5, 2));
+        assert_eq!(pick_font(&config, 5, &fonts), (1, 1));
+    }
+}
@@ -2,22 +2,32 @@
 //!
 //! Este módulo fornece:
 //! - Detecção automática de dispositivos
-//! - Health checks contínuos
+//! - Health checks contínuos (com sensores da impressora via DLE EOT / GS r)
 //! - Reconexão automática com backoff
 //! - Eventos de status para o frontend
+//! - Fila de impressão persistente (ver `print_queue.rs`)
+//! - Leitura contínua da balança (ver `scale_stream.rs`)
 
 use super::{
-    drawer::DrawerConfig,
+    drawer::{CashDrawer, DrawerConfig, DrawerWatchOutcome},
+    label::LabelPrinterConfig,
     printer::{PrinterConfig, PrinterConnection, PrinterModel},
-    scale::{Scale, ScaleConfig, ScaleProtocol},
+    printer_status::{query_printer_sensors, PrinterSensorStatus},
+    scale::{Scale, ScaleConfig, ScaleProtocol, ScaleReading},
+    scale_stream::ScaleStreamHandle,
     HardwareError, HardwareResult,
 };
+use crate::models::PrintJob;
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, Notify, RwLock};
 use tokio::time::interval;
 
+/// Tempo máximo de acompanhamento da gaveta aberta antes do alerta
+const DRAWER_WATCH_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
 // ════════════════════════════════════════════════════════════════════════════
 // TIPOS
 // ════════════════════════════════════════════════════════════════════════════
@@ -66,6 +76,8 @@ pub enum DeviceType {
     Scale,
     Drawer,
     Scanner,
+    /// Impressora de etiquetas (ZPL/EPL/PPLA)
+    Label,
 }
 
 /// Status consolidado de todo hardware
@@ -76,6 +88,7 @@ pub struct HardwareOverview {
     pub scale: DeviceStatusInfo,
     pub drawer: DeviceStatusInfo,
     pub scanner: DeviceStatusInfo,
+    pub label_printer: DeviceStatusInfo,
     pub last_check: String,
 }
 
@@ -89,6 +102,10 @@ pub struct DeviceStatusInfo {
     pub last_error: Option<String>,
     pub reconnect_attempts: u32,
     pub uptime_seconds: Option<u64>,
+    /// Sensores da impressora (status em tempo real, serial/rede)
+    pub sensors: Option<PrinterSensorStatus>,
+    /// Sensor da gaveta, quando a impressora informa
+    pub drawer_open: Option<bool>,
 }
 
 impl Default for DeviceStatusInfo {
@@ -100,6 +117,8 @@ impl Default for DeviceStatusInfo {
             last_error: None,
             reconnect_attempts: 0,
             uptime_seconds: None,
+            sensors: None,
+            drawer_open: None,
         }
     }
 }
@@ -113,6 +132,10 @@ pub struct HardwareEvent {
     pub new_status: DeviceStatus,
     pub message: Option<String>,
     pub timestamp: i64,
+    /// Trabalho da fila de impressão que originou o evento
+    pub print_job: Option<PrintJob>,
+    /// Peso publicado pela leitura contínua da balança
+    pub scale_reading: Option<ScaleReading>,
 }
 
 /// Resultado de auto-detecção
@@ -131,22 +154,32 @@ pub struct AutoDetectResult {
 /// Gerenciador centralizado de hardware
 pub struct HardwareManager {
     /// Status dos dispositivos
-    printer_status: RwLock<DeviceStatusInfo>,
-    scale_status: RwLock<DeviceStatusInfo>,
+    pub(super) printer_status: RwLock<DeviceStatusInfo>,
+    pub(super) scale_status: RwLock<DeviceStatusInfo>,
     drawer_status: RwLock<DeviceStatusInfo>,
     scanner_status: RwLock<DeviceStatusInfo>,
+    label_status: RwLock<DeviceStatusInfo>,
 
     /// Configurações atuais
-    printer_config: RwLock<Option<PrinterConfig>>,
+    pub(super) printer_config: RwLock<Option<PrinterConfig>>,
     scale_config: RwLock<Option<ScaleConfig>>,
     drawer_config: RwLock<Option<DrawerConfig>>,
+    label_config: RwLock<Option<LabelPrinterConfig>>,
 
     /// Canal de eventos
-    event_tx: broadcast::Sender<HardwareEvent>,
+    pub(super) event_tx: broadcast::Sender<HardwareEvent>,
 
     /// Flags de controle
     health_check_running: RwLock<bool>,
     last_health_check: RwLock<Option<chrono::DateTime<chrono::Utc>>>,
+
+    /// Spool da fila de impressão (definido ao anexar o banco)
+    pub(super) print_pool: RwLock<Option<SqlitePool>>,
+    pub(super) print_wake: Notify,
+
+    /// Leitura contínua da balança (ver `scale_stream.rs`)
+    pub(super) scale_stream: std::sync::Mutex<Option<ScaleStreamHandle>>,
+    pub(super) scale_reading: std::sync::Mutex<Option<ScaleReading>>,
 }
 
 impl HardwareManager {
@@ -159,12 +192,18 @@ impl HardwareManager {
             scale_status: RwLock::new(DeviceStatusInfo::default()),
             drawer_status: RwLock::new(DeviceStatusInfo::default()),
             scanner_status: RwLock::new(DeviceStatusInfo::default()),
+            label_status: RwLock::new(DeviceStatusInfo::default()),
             printer_config: RwLock::new(None),
             scale_config: RwLock::new(None),
             drawer_config: RwLock::new(None),
+            label_config: RwLock::new(None),
             event_tx,
             health_check_running: RwLock::new(false),
             last_health_check: RwLock::new(None),
+            print_pool: RwLock::new(None),
+            print_wake: Notify::new(),
+            scale_stream: std::sync::Mutex::new(None),
+            scale_reading: std::sync::Mutex::new(None),
         })
     }
 
@@ -174,7 +213,7 @@ impl HardwareManager {
     }
 
     /// Emite evento de mudança de status
-    fn emit_event(
+    pub(super) fn emit_event(
         &self,
         device_type: DeviceType,
         old: DeviceStatus,
@@ -188,11 +227,26 @@ impl HardwareManager {
                 new_status: new,
                 message: msg,
                 timestamp: chrono::Utc::now().timestamp_millis(),
+                print_job: None,
+                scale_reading: None,
             };
             let _ = self.event_tx.send(event);
         }
     }
 
+    /// Emite aviso sem mudança de status (ex.: papel acabando, gaveta fechada)
+    fn emit_notice(&self, device_type: DeviceType, status: DeviceStatus, msg: Option<String>) {
+        let _ = self.event_tx.send(HardwareEvent {
+            device_type,
+            old_status: status.clone(),
+            new_status: status,
+            message: msg,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            print_job: None,
+            scale_reading: None,
+        });
+    }
+
     // ────────────────────────────────────────────────────────────────────────
     // CONFIGURAÇÃO
     // ────────────────────────────────────────────────────────────────────────
@@ -259,6 +313,27 @@ impl HardwareManager {
         self.emit_event(DeviceType::Drawer, old, status.status.clone(), None);
     }
 
+    /// Configura impressora de etiquetas
+    pub async fn set_label_printer_config(&self, config: LabelPrinterConfig) {
+        let mut cfg = self.label_config.write().await;
+        *cfg = Some(config.clone());
+
+        let mut status = self.label_status.write().await;
+        let old = status.status.clone();
+
+        if config.mock_mode {
+            status.status = DeviceStatus::MockMode;
+        } else if !config.enabled {
+            status.status = DeviceStatus::NotConfigured;
+        } else {
+            status.status = DeviceStatus::Connecting;
+            status.port = Some(config.port.clone());
+            status.model = Some(format!("{:?}", config.language).to_uppercase());
+        }
+
+        self.emit_event(DeviceType::Label, old, status.status.clone(), None);
+    }
+
     // ────────────────────────────────────────────────────────────────────────
     // STATUS
     // ────────────────────────────────────────────────────────────────────────
@@ -269,6 +344,7 @@ impl HardwareManager {
         let scale = self.scale_status.read().await.clone();
         let drawer = self.drawer_status.read().await.clone();
         let scanner = self.scanner_status.read().await.clone();
+        let label_printer = self.label_status.read().await.clone();
 
         let last_check = self
             .last_health_check
@@ -282,6 +358,7 @@ impl HardwareManager {
             scale,
             drawer,
             scanner,
+            label_printer,
             last_check,
         }
     }
@@ -307,6 +384,11 @@ impl HardwareManager {
             self.check_drawer(&config).await;
         }
 
+        // Check label printer
+        if let Some(config) = self.label_config.read().await.clone() {
+            self.check_label_printer(&config).await;
+        }
+
         // Update last check time
         *self.last_health_check.write().await = Some(chrono::Utc::now());
 
@@ -314,6 +396,9 @@ impl HardwareManager {
     }
 
     /// Health check da impressora
+    ///
+    /// Em serial e rede lê os sensores (papel, tampa, erro, gaveta); sem
+    /// status em tempo real (USB ou modelo que não responde) só testa a conexão.
     async fn check_printer(&self, config: &PrinterConfig) {
         if !config.enabled || config.mock_mode {
             return;
@@ -321,23 +406,38 @@ impl HardwareManager {
 
         let result = tokio::task::spawn_blocking({
             let config = config.clone();
-            move || check_printer_connection(&config)
+            move || match query_printer_sensors(&config) {
+                Ok(sensors) => Ok(Some(sensors)),
+                Err(HardwareError::NotConfigured(_) | HardwareError::Timeout) => {
+                    check_printer_connection(&config).map(|_| None)
+                }
+                Err(e) => Err(e),
+            }
         })
         .await;
 
         let mut status = self.printer_status.write().await;
         let old = status.status.clone();
+        let old_sensors = status.sensors.clone();
 
         match result {
-            Ok(Ok(())) => {
-                status.status = DeviceStatus::Connected;
-                status.last_error = None;
+            Ok(Ok(sensors)) => {
+                let problem = sensors.as_ref().and_then(|s| s.blocking_problem());
+                let warning = sensors.as_ref().and_then(|s| s.warning());
+                status.status = if problem.is_some() {
+                    DeviceStatus::Error
+                } else {
+                    DeviceStatus::Connected
+                };
+                status.last_error = problem.or(warning).map(str::to_string);
                 status.reconnect_attempts = 0;
+                status.sensors = sensors;
             }
             Ok(Err(e)) => {
                 status.status = DeviceStatus::Disconnected;
                 status.last_error = Some(e.to_string());
                 status.reconnect_attempts += 1;
+                status.sensors = None;
             }
             Err(e) => {
                 status.status = DeviceStatus::Error;
@@ -345,17 +445,26 @@ impl HardwareManager {
             }
         }
 
-        self.emit_event(
-            DeviceType::Printer,
-            old,
-            status.status.clone(),
-            status.last_error.clone(),
-        );
+        if old == status.status && old_sensors != status.sensors {
+            self.emit_notice(
+                DeviceType::Printer,
+                status.status.clone(),
+                status.last_error.clone(),
+            );
+        } else {
+            self.emit_event(
+                DeviceType::Printer,
+                old,
+                status.status.clone(),
+                status.last_error.clone(),
+            );
+        }
     }
 
     /// Health check da balança
     async fn check_scale(&self, config: &ScaleConfig) {
-        if !config.enabled || config.mock_mode {
+        // Com a leitura contínua ativa a porta é dela, que já mantém o status
+        if !config.enabled || config.mock_mode || self.is_scale_streaming() {
             return;
         }
 
@@ -407,8 +516,12 @@ impl HardwareManager {
         .await
         .unwrap_or(false);
 
+        // Sensor lido no health check da impressora
+        let sensors = self.printer_status.read().await.sensors.clone();
+
         let mut status = self.drawer_status.write().await;
         let old = status.status.clone();
+        let old_open = status.drawer_open;
 
         if exists {
             status.status = DeviceStatus::Connected;
@@ -417,15 +530,105 @@ impl HardwareManager {
             status.status = DeviceStatus::Disconnected;
             status.last_error = Some("Porta da impressora não encontrada".to_string());
         }
+        status.drawer_open = sensors.map(|s| s.drawer_open(config.sensor_inverted));
+
+        match status.drawer_open {
+            Some(open) if old == status.status && old_open != status.drawer_open => {
+                let msg = if open {
+                    "Gaveta aberta"
+                } else {
+                    "Gaveta fechada"
+                };
+                self.emit_notice(DeviceType::Drawer, status.status.clone(), Some(msg.into()));
+            }
+            _ => self.emit_event(
+                DeviceType::Drawer,
+                old,
+                status.status.clone(),
+                status.last_error.clone(),
+            ),
+        }
+    }
+
+    /// Health check da impressora de etiquetas (só testa a conexão)
+    async fn check_label_printer(&self, config: &LabelPrinterConfig) {
+        if !config.enabled || config.mock_mode {
+            return;
+        }
+
+        let result = tokio::task::spawn_blocking({
+            let config = config.transport();
+            move || check_printer_connection(&config)
+        })
+        .await;
+
+        let mut status = self.label_status.write().await;
+        let old = status.status.clone();
+
+        match result {
+            Ok(Ok(())) => {
+                status.status = DeviceStatus::Connected;
+                status.last_error = None;
+                status.reconnect_attempts = 0;
+            }
+            Ok(Err(e)) => {
+                status.status = DeviceStatus::Disconnected;
+                status.last_error = Some(e.to_string());
+                status.reconnect_attempts += 1;
+            }
+            Err(e) => {
+                status.status = DeviceStatus::Error;
+                status.last_error = Some(format!("Task panic: {}", e));
+            }
+        }
 
         self.emit_event(
-            DeviceType::Drawer,
+            DeviceType::Label,
             old,
             status.status.clone(),
             status.last_error.clone(),
         );
     }
 
+    /// Acompanha a gaveta pelo sensor da impressora após a abertura e avisa
+    /// quando ela for fechada (ou se continuar aberta além do limite)
+    pub fn watch_drawer_close(
+        self: Arc<Self>,
+        drawer: DrawerConfig,
+        printer: PrinterConfig,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                CashDrawer::new(drawer).wait_closed(&printer, DRAWER_WATCH_TIMEOUT)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(DrawerWatchOutcome::Closed)) => {
+                    self.set_drawer_open(false, "Gaveta fechada".into()).await;
+                }
+                Ok(Ok(DrawerWatchOutcome::StillOpen)) => {
+                    let msg = format!(
+                        "Gaveta aberta há mais de {} minutos",
+                        DRAWER_WATCH_TIMEOUT.as_secs() / 60
+                    );
+                    self.set_drawer_open(true, msg).await;
+                }
+                Ok(Ok(DrawerWatchOutcome::NotOpened)) => {
+                    tracing::warn!("[CashDrawer] Sensor não indicou abertura da gaveta");
+                }
+                Ok(Err(e)) => tracing::warn!("[CashDrawer] Erro lendo sensor da gaveta: {}", e),
+                Err(e) => tracing::error!("[CashDrawer] Task panic: {}", e),
+            }
+        })
+    }
+
+    async fn set_drawer_open(&self, open: bool, msg: String) {
+        let mut status = self.drawer_status.write().await;
+        status.drawer_open = Some(open);
+        self.emit_notice(DeviceType::Drawer, status.status.clone(), Some(msg));
+    }
+
     /// Inicia loop de health checks em background
     pub fn start_health_check_loop(
         self: Arc<Self>,
@@ -669,6 +872,8 @@ impl HardwareManager {
             parity: "none".to_string(),
             stop_bits: 1,
             mock_mode: false,
+            continuous: false,
+            price_computing: false,
         };
 
         self.set_scale_config(config).await;
@@ -686,12 +891,18 @@ impl Default for HardwareManager {
                 scale_status: RwLock::new(DeviceStatusInfo::default()),
                 drawer_status: RwLock::new(DeviceStatusInfo::default()),
                 scanner_status: RwLock::new(DeviceStatusInfo::default()),
+                label_status: RwLock::new(DeviceStatusInfo::default()),
                 printer_config: RwLock::new(None),
                 scale_config: RwLock::new(None),
                 drawer_config: RwLock::new(None),
+                label_config: RwLock::new(None),
                 event_tx,
                 health_check_running: RwLock::new(false),
                 last_health_check: RwLock::new(None),
+                print_pool: RwLock::new(None),
+                print_wake: Notify::new(),
+                scale_stream: std::sync::Mutex::new(None),
+                scale_reading: std::sync::Mutex::new(None),
             }
         })
     }
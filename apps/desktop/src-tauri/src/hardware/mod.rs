@@ -2,6 +2,7 @@
 //!
 //! Integração com equipamentos:
 //! - Impressoras térmicas (ESC/POS)
+//! - Impressoras de etiquetas (ZPL, EPL, PPLA)
 //! - Balanças (Toledo, Filizola)
 //! - Scanner de código de barras (USB HID + Mobile WebSocket)
 //! - Gaveta de dinheiro
@@ -11,24 +12,50 @@
 //! O módulo é organizado em:
 //! - `manager.rs` - Gerenciador centralizado com autoconfig e health checks
 //! - `printer.rs` - Interface ESC/POS para impressoras térmicas
+//! - `barcode.rs` - Simbologias de código de barras e dados GS1
+//! - `label.rs` - Etiquetas de gôndola em ZPL, EPL e PPLA
+//! - `print_queue.rs` - Fila de impressão persistente com retentativas
+//! - `printer_status.rs` - Sensores da impressora (DLE EOT / GS r)
+//! - `raster.rs` - Logo em bitmap (GS v 0 / ESC *) com cache
+//! - `receipt_template.rs` - Modelos de cupom configuráveis por loja
 //! - `scale.rs` - Protocolos Toledo, Filizola, Elgin, Urano
+//! - `scale_stream.rs` - Leitura contínua da balança com reconexão
+//! - `scale_catalog.rs` - Cadastro de itens das balanças (Toledo MGV, Filizola)
 //! - `scanner.rs` - WebSocket para scanner mobile
+//! - `simulator.rs` - Balança e impressora simuladas (testes e demonstração)
 //! - `drawer.rs` - Controle de gaveta via impressora
 //! - `device.rs` - Trait comum para dispositivos
 
+pub mod barcode;
 pub mod device;
 pub mod drawer;
+pub mod label;
 pub mod manager;
+pub mod print_queue;
 pub mod printer;
+pub mod printer_status;
+pub mod raster;
+pub mod receipt_template;
 pub mod scale;
+pub mod scale_catalog;
+pub mod scale_stream;
 pub mod scanner;
+pub mod simulator;
 
+pub use barcode::*;
 pub use device::*;
 pub use drawer::*;
+pub use label::*;
 pub use manager::*;
+pub use print_queue::*;
 pub use printer::*;
+pub use printer_status::*;
+pub use raster::*;
+pub use receipt_template::*;
 pub use scale::*;
+pub use scale_catalog::*;
 pub use scanner::*;
+pub use simulator::*;
 
 use thiserror::Error;
 
@@ -0,0 +1,509 @@
+//! Fila de Impressão Persistente
+//!
+//! Todo trabalho é gravado no SQLite (`print_jobs`) antes do envio e só deixa
+//! a fila quando a impressora aceita os bytes. Falhas (sem papel, cabo USB
+//! solto, impressora de rede offline) são retentadas com backoff exponencial,
+//! e trabalhos interrompidos por um fechamento do app voltam para a fila na
+//! próxima inicialização.
+//!
+//! A fila é FIFO: enquanto o trabalho mais antigo não sai, os seguintes
+//! aguardam, mantendo a ordem dos cupons. Cada mudança de estado é publicada
+//! como `HardwareEvent` com o campo `print_job` preenchido.
+
+use super::{
+    manager::{DeviceStatus, DeviceType, HardwareEvent, HardwareManager},
+    printer::{PrinterConnection, ThermalPrinter},
+    printer_status::printer_port_lock,
+    HardwareError, HardwareResult,
+};
+use crate::error::{AppError, AppResult};
+use crate::models::PrintJob;
+use crate::repositories::print_job_repository::spool_timestamp;
+use crate::repositories::PrintJobRepository;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+
+// ════════════════════════════════════════════════════════════════════════════
+// CONFIGURAÇÃO
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Tentativas antes de o trabalho ir para FAILED (~10 min com o backoff)
+pub const PRINT_MAX_ATTEMPTS: i32 = 8;
+
+/// Intervalo base do backoff, dobrado a cada falha
+const BACKOFF_BASE_SECS: u64 = 5;
+const BACKOFF_MAX_SECS: u64 = 300;
+
+/// Verificação periódica da fila (além do aviso imediato ao enfileirar)
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Limite para conectar e enviar a uma impressora de rede
+const NETWORK_SEND_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Trabalhos concluídos/cancelados ficam no spool para reimpressão
+const SPOOL_RETENTION_DAYS: i64 = 7;
+
+// ════════════════════════════════════════════════════════════════════════════
+// TIPOS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Origem do trabalho de impressão
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PrintJobKind {
+    Receipt,
+    ServiceOrder,
+    AttendantOrder,
+    Danfe,
+    Statement,
+    StoreCredit,
+    CashClosing,
+    Test,
+}
+
+impl PrintJobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Receipt => "RECEIPT",
+            Self::ServiceOrder => "SERVICE_ORDER",
+            Self::AttendantOrder => "ATTENDANT_ORDER",
+            Self::Danfe => "DANFE",
+            Self::Statement => "STATEMENT",
+            Self::StoreCredit => "STORE_CREDIT",
+            Self::CashClosing => "CASH_CLOSING",
+            Self::Test => "TEST",
+        }
+    }
+}
+
+/// Espera antes da próxima tentativa após `failures` falhas seguidas
+pub fn print_backoff(failures: i32) -> Duration {
+    let exponent = failures.saturating_sub(1).clamp(0, 16) as u32;
+    let secs = BACKOFF_BASE_SECS.saturating_mul(1u64 << exponent);
+    Duration::from_secs(secs.min(BACKOFF_MAX_SECS))
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// FILA NO HARDWARE MANAGER
+// ════════════════════════════════════════════════════════════════════════════
+
+impl HardwareManager {
+    /// Anexa o banco como spool: devolve à fila os trabalhos interrompidos e
+    /// descarta os concluídos antigos. Retorna quantos foram recuperados.
+    pub async fn attach_print_spool(&self, pool: SqlitePool) -> AppResult<u64> {
+        let repo = PrintJobRepository::new(&pool);
+        let recovered = repo.recover_interrupted().await?;
+
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(SPOOL_RETENTION_DAYS);
+        repo.purge_finished(&spool_timestamp(cutoff)).await?;
+
+        *self.print_pool.write().await = Some(pool);
+        self.print_wake.notify_one();
+        Ok(recovered)
+    }
+
+    /// Inicia o envio da fila em background
+    pub fn start_print_queue_loop(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = self.process_print_queue().await {
+                    tracing::error!("Erro na fila de impressão: {}", e);
+                }
+
+                tokio::select! {
+                    _ = self.print_wake.notified() => {}
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                }
+            }
+        })
+    }
+
+    /// Grava o trabalho no spool e acorda a fila
+    pub async fn enqueue_print(
+        &self,
+        kind: PrintJobKind,
+        reference: Option<&str>,
+        payload: &[u8],
+    ) -> AppResult<PrintJob> {
+        let pool = self.print_spool().await?;
+        let job = PrintJobRepository::new(&pool)
+            .create(kind.as_str(), reference, payload, PRINT_MAX_ATTEMPTS)
+            .await?;
+
+        self.emit_job_event(&job, None).await;
+        self.print_wake.notify_one();
+        Ok(job)
+    }
+
+    /// Trabalhos mais recentes do spool
+    pub async fn list_print_jobs(
+        &self,
+        status: Option<&str>,
+        limit: i32,
+    ) -> AppResult<Vec<PrintJob>> {
+        let pool = self.print_spool().await?;
+        PrintJobRepository::new(&pool).list(status, limit).await
+    }
+
+    /// Reimprime um trabalho: FAILED/CANCELED voltam para a fila; DONE gera
+    /// uma nova cópia com os mesmos bytes
+    pub async fn reprint_job(&self, id: &str) -> AppResult<PrintJob> {
+        let pool = self.print_spool().await?;
+        let repo = PrintJobRepository::new(&pool);
+        let job = find_job(&repo, id).await?;
+
+        let job = match job.status.as_str() {
+            "DONE" => {
+                let payload = repo.find_payload(id).await?.unwrap_or_default();
+                repo.create(
+                    &job.kind,
+                    job.reference.as_deref(),
+                    &payload,
+                    PRINT_MAX_ATTEMPTS,
+                )
+                .await?
+            }
+            "FAILED" | "CANCELED" if repo.requeue(id).await? => find_job(&repo, id).await?,
+            _ => {
+                return Err(AppError::BusinessRule(
+                    "Trabalho de impressão ainda está na fila".into(),
+                ))
+            }
+        };
+
+        self.emit_job_event(&job, None).await;
+        self.print_wake.notify_one();
+        Ok(job)
+    }
+
+    /// Cancela um trabalho pendente ou que falhou
+    pub async fn cancel_job(&self, id: &str) -> AppResult<PrintJob> {
+        let pool = self.print_spool().await?;
+        let repo = PrintJobRepository::new(&pool);
+
+        if !repo.cancel(id).await? {
+            find_job(&repo, id).await?;
+            return Err(AppError::BusinessRule(
+                "Trabalho de impressão já enviado não pode ser cancelado".into(),
+            ));
+        }
+
+        let job = find_job(&repo, id).await?;
+        self.emit_job_event(&job, None).await;
+        Ok(job)
+    }
+
+    /// Envia os trabalhos vencidos em ordem, parando na primeira falha.
+    /// Retorna quantos foram impressos.
+    pub async fn process_print_queue(&self) -> AppResult<usize> {
+        let Some(pool) = self.print_pool.read().await.clone() else {
+            return Ok(0);
+        };
+        let repo = PrintJobRepository::new(&pool);
+        let mut printed = 0;
+
+        while let Some(job) = repo.find_next_pending().await? {
+            if job.next_attempt_at > spool_timestamp(chrono::Utc::now()) {
+                break;
+            }
+            if !repo.mark_printing(&job.id).await? {
+                continue;
+            }
+
+            let payload = repo.find_payload(&job.id).await?.unwrap_or_default();
+            match self.send_to_printer(payload).await {
+                Ok(()) => {
+                    let job = repo.mark_done(&job.id).await?;
+                    self.record_printer_result(None).await;
+                    self.emit_job_event(&job, None).await;
+                    printed += 1;
+                }
+                Err(e) => {
+                    let error = e.to_string();
+                    let next_attempt = chrono::Utc::now()
+                        + chrono::Duration::from_std(print_backoff(job.attempts + 1))
+                            .unwrap_or_else(|_| chrono::Duration::seconds(BACKOFF_MAX_SECS as i64));
+                    let job = repo
+                        .record_failure(&job.id, &error, &spool_timestamp(next_attempt))
+                        .await?;
+
+                    let message = if job.status == "FAILED" {
+                        format!(
+                            "Impressão não concluída após {} tentativas: {}",
+                            job.attempts, error
+                        )
+                    } else {
+                        format!(
+                            "Falha na impressão (tentativa {}/{}), nova tentativa às {}: {}",
+                            job.attempts,
+                            job.max_attempts,
+                            next_attempt
+                                .with_timezone(&chrono::Local)
+                                .format("%H:%M:%S"),
+                            error
+                        )
+                    };
+                    tracing::warn!("[PrintQueue] {} ({})", message, job.id);
+
+                    self.record_printer_result(Some(error)).await;
+                    self.emit_job_event(&job, Some(message)).await;
+                    break;
+                }
+            }
+        }
+
+        Ok(printed)
+    }
+
+    async fn print_spool(&self) -> AppResult<SqlitePool> {
+        self.print_pool.read().await.clone().ok_or_else(|| {
+            HardwareError::NotConfigured("Fila de impressão não inicializada".into()).into()
+        })
+    }
+
+    /// Envia os bytes usando a configuração atual da impressora
+    async fn send_to_printer(&self, payload: Vec<u8>) -> HardwareResult<()> {
+        let config = self
+            .printer_config
+            .read()
+            .await
+            .clone()
+            .filter(|config| config.enabled)
+            .ok_or_else(|| HardwareError::NotConfigured("Impressora não habilitada".into()))?;
+
+        let mut printer = ThermalPrinter::new(config.clone());
+        printer.raw(&payload);
+
+        if config.connection == PrinterConnection::Network {
+            tokio::time::timeout(NETWORK_SEND_TIMEOUT, printer.print_network())
+                .await
+                .map_err(|_| HardwareError::Timeout)?
+        } else {
+            tokio::task::spawn_blocking(move || {
+                let _guard = printer_port_lock();
+                match config.connection {
+                    PrinterConnection::Usb => printer.print_usb(),
+                    PrinterConnection::Serial => printer.print_serial(),
+                    PrinterConnection::Network => Ok(()),
+                }
+            })
+            .await
+            .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))?
+        }
+    }
+
+    /// Reflete o resultado do envio no status da impressora
+    async fn record_printer_result(&self, error: Option<String>) {
+        let mut status = self.printer_status.write().await;
+        if status.status == DeviceStatus::MockMode {
+            return;
+        }
+        let old = status.status.clone();
+
+        match error {
+            None => {
+                status.status = DeviceStatus::Connected;
+                status.last_error = None;
+                status.reconnect_attempts = 0;
+            }
+            Some(e) => {
+                status.status = DeviceStatus::Disconnected;
+                status.last_error = Some(e);
+                status.reconnect_attempts += 1;
+            }
+        }
+
+        self.emit_event(
+            DeviceType::Printer,
+            old,
+            status.status.clone(),
+            status.last_error.clone(),
+        );
+    }
+
+    /// Publica o estado do trabalho (sempre, mesmo sem mudança da impressora)
+    async fn emit_job_event(&self, job: &PrintJob, message: Option<String>) {
+        let status = self.printer_status.read().await.status.clone();
+        let _ = self.event_tx.send(HardwareEvent {
+            device_type: DeviceType::Printer,
+            old_status: status.clone(),
+            new_status: status,
+            message,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            print_job: Some(job.clone()),
+            scale_reading: None,
+        });
+    }
+}
+
+async fn find_job(repo: &PrintJobRepository<'_>, id: &str) -> AppResult<PrintJob> {
+    repo.find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            entity: "PrintJob".into(),
+            id: id.to_string(),
+        })
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TESTES
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::printer::PrinterConfig;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_manager(config: PrinterConfig) -> (Arc<HardwareManager>, SqlitePool) {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let manager = HardwareManager::new();
+        manager.set_printer_config(config).await;
+        manager.attach_print_spool(pool.clone()).await.unwrap();
+        (manager, pool)
+    }
+
+    fn mock_printer() -> PrinterConfig {
+        PrinterConfig {
+            enabled: true,
+            mock_mode: true,
+            ..Default::default()
+        }
+    }
+
+    fn offline_printer() -> PrinterConfig {
+        PrinterConfig {
+            enabled: true,
+            connection: PrinterConnection::Network,
+            // Porta fechada: conexão recusada de imediato
+            port: "127.0.0.1:9".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_until_cap() {
+        assert_eq!(print_backoff(1), Duration::from_secs(5));
+        assert_eq!(print_backoff(2), Duration::from_secs(10));
+        assert_eq!(print_backoff(4), Duration::from_secs(40));
+        assert_eq!(print_backoff(7), Duration::from_secs(300));
+        assert_eq!(print_backoff(100), Duration::from_secs(300));
+    }
+
+    #[tokio::test]
+    async fn test_job_printed_and_reported() {
+        let (manager, _pool) = setup_manager(mock_printer()).await;
+        let mut events = manager.subscribe();
+
+        let job = manager
+            .enqueue_print(PrintJobKind::Receipt, Some("sale-1"), b"cupom")
+            .await
+            .unwrap();
+        assert_eq!(manager.process_print_queue().await.unwrap(), 1);
+
+        let queued = events.recv().await.unwrap();
+        assert_eq!(queued.print_job.unwrap().status, "PENDING");
+        let done = events.recv().await.unwrap().print_job.unwrap();
+        assert_eq!(done.id, job.id);
+        assert_eq!(done.status, "DONE");
+        assert!(done.printed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_offline_printer_keeps_order_and_retries() {
+        let (manager, pool) = setup_manager(offline_printer()).await;
+
+        let first = manager
+            .enqueue_print(PrintJobKind::Receipt, None, b"1")
+            .await
+            .unwrap();
+        let second = manager
+            .enqueue_print(PrintJobKind::Danfe, None, b"2")
+            .await
+            .unwrap();
+
+        assert_eq!(manager.process_print_queue().await.unwrap(), 0);
+        let repo = PrintJobRepository::new(&pool);
+        let failed = repo.find_by_id(&first.id).await.unwrap().unwrap();
+        assert_eq!(failed.status, "PENDING");
+        assert_eq!(failed.attempts, 1);
+        assert!(failed.last_error.is_some());
+        // O segundo aguarda atrás do primeiro
+        let waiting = repo.find_by_id(&second.id).await.unwrap().unwrap();
+        assert_eq!(waiting.attempts, 0);
+
+        // Ainda dentro do backoff: nada é tentado
+        assert_eq!(manager.process_print_queue().await.unwrap(), 0);
+        assert_eq!(
+            repo.find_by_id(&first.id).await.unwrap().unwrap().attempts,
+            1
+        );
+
+        // Impressora volta e o backoff vence
+        manager.set_printer_config(mock_printer()).await;
+        sqlx::query("UPDATE print_jobs SET next_attempt_at = '2000-01-01T00:00:00.000Z'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(manager.process_print_queue().await.unwrap(), 2);
+        assert_eq!(
+            repo.find_by_id(&second.id).await.unwrap().unwrap().status,
+            "DONE"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_reprint() {
+        let (manager, pool) = setup_manager(mock_printer()).await;
+
+        let job = manager
+            .enqueue_print(PrintJobKind::Receipt, Some("sale-1"), b"cupom")
+            .await
+            .unwrap();
+        let canceled = manager.cancel_job(&job.id).await.unwrap();
+        assert_eq!(canceled.status, "CANCELED");
+        assert_eq!(manager.process_print_queue().await.unwrap(), 0);
+
+        let requeued = manager.reprint_job(&job.id).await.unwrap();
+        assert_eq!(requeued.id, job.id);
+        assert_eq!(manager.process_print_queue().await.unwrap(), 1);
+        assert!(manager.cancel_job(&job.id).await.is_err());
+
+        // Reimpressão de trabalho concluído gera uma cópia
+        let copy = manager.reprint_job(&job.id).await.unwrap();
+        assert_ne!(copy.id, job.id);
+        assert_eq!(copy.reference.as_deref(), Some("sale-1"));
+        let payload = PrintJobRepository::new(&pool)
+            .find_payload(&copy.id)
+            .await
+            .unwrap();
+        assert_eq!(payload.as_deref(), Some(&b"cupom"[..]));
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_job_survives_restart() {
+        let (manager, pool) = setup_manager(mock_printer()).await;
+        let job = manager
+            .enqueue_print(PrintJobKind::Receipt, None, b"cupom")
+            .await
+            .unwrap();
+        PrintJobRepository::new(&pool)
+            .mark_printing(&job.id)
+            .await
+            .unwrap();
+
+        // Novo manager sobre o mesmo banco, como após reiniciar o app
+        let restarted = HardwareManager::new();
+        restarted.set_printer_config(mock_printer()).await;
+        assert_eq!(restarted.attach_print_spool(pool).await.unwrap(), 1);
+        assert_eq!(restarted.process_print_queue().await.unwrap(), 1);
+    }
+}
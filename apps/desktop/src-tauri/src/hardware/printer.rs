@@ -7,6 +7,7 @@
 //! - Daruma DR800
 //! - Genéricas 58mm/80mm
 
+use super::raster::MonoBitmap;
 use super::{HardwareError, HardwareResult};
 use serde::{Deserialize, Serialize};
 #[cfg(any(target_os = "linux", target_os = "windows"))]
@@ -14,6 +15,7 @@ use std::fs::OpenOptions;
 use std::io::Write;
 #[cfg(target_os = "linux")]
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
 // ════════════════════════════════════════════════════════════════════════════
@@ -25,6 +27,7 @@ pub mod escpos {
     // Caracteres de controle
     pub const ESC: u8 = 0x1B;
     pub const GS: u8 = 0x1D;
+    pub const FS: u8 = 0x1C;
     pub const LF: u8 = 0x0A;
     pub const CR: u8 = 0x0D;
     pub const HT: u8 = 0x09;
@@ -66,7 +69,7 @@ pub mod escpos {
     pub const BARCODE_EAN13: [u8; 3] = [GS, b'k', 67];
 
     // QR Code
-    pub const QRCODE_MODEL: [u8; 8] = [GS, b'(', b'k', 4, 0, 49, 65, 50];
+    pub const QRCODE_MODEL: [u8; 9] = [GS, b'(', b'k', 4, 0, 49, 65, 50, 0]; // Modelo 2
     pub const QRCODE_SIZE: [u8; 8] = [GS, b'(', b'k', 3, 0, 49, 67, 6];
     pub const QRCODE_ERROR: [u8; 8] = [GS, b'(', b'k', 3, 0, 49, 69, 48];
     pub const QRCODE_PRINT: [u8; 8] = [GS, b'(', b'k', 3, 0, 49, 81, 48];
@@ -270,6 +273,7 @@ pub struct TextStyle {
 pub struct ThermalPrinter {
     config: PrinterConfig,
     buffer: Vec<u8>,
+    logo: Option<Arc<MonoBitmap>>,
 }
 
 impl ThermalPrinter {
@@ -278,9 +282,26 @@ impl ThermalPrinter {
         Self {
             config,
             buffer: Vec::with_capacity(4096),
+            logo: None,
         }
     }
 
+    /// Configuração em uso
+    pub fn config(&self) -> &PrinterConfig {
+        &self.config
+    }
+
+    /// Define o logo da loja impresso no topo dos cupons
+    pub fn set_logo(&mut self, logo: Option<Arc<MonoBitmap>>) -> &mut Self {
+        self.logo = logo;
+        self
+    }
+
+    /// Logo da loja configurado
+    pub fn logo(&self) -> Option<Arc<MonoBitmap>> {
+        self.logo.clone()
+    }
+
     /// Verifica se o nome da impressora sugere ser uma impressora térmica
     pub fn is_thermal_candidate(name: &str) -> bool {
         let name_lower = name.to_lowercase();
@@ -352,6 +373,12 @@ impl ThermalPrinter {
         self
     }
 
+    /// Acrescenta comandos ESC/POS já montados (ex: DANFE NFC-e)
+    pub fn raw(&mut self, bytes: &[u8]) -> &mut Self {
+        self.buffer.extend_from_slice(bytes);
+        self
+    }
+
     /// Linha vazia
     pub fn feed(&mut self, lines: u8) -> &mut Self {
         for _ in 0..lines {
@@ -1008,6 +1035,12 @@ pub struct ReceiptItem {
     pub unit: String,
     pub unit_price: f64,
     pub total: f64,
+    /// Desconto do item (manual ou promoção), já abatido do total
+    #[serde(default)]
+    pub discount: f64,
+    /// Motivo impresso abaixo do item (nome da promoção)
+    #[serde(default)]
+    pub discount_reason: Option<String>,
 }
 
 /// Dados do cupom
@@ -1023,6 +1056,12 @@ pub struct Receipt {
     pub operator_name: String,
     pub date_time: String,
 
+    #[serde(default)]
+    pub customer_name: Option<String>,
+    /// CPF/CNPJ do cliente identificado na venda
+    #[serde(default)]
+    pub customer_document: Option<String>,
+
     pub items: Vec<ReceiptItem>,
     pub subtotal: f64,
     pub discount: f64,
@@ -1087,10 +1126,112 @@ pub struct AttendantOrderReceipt {
     pub notes: Option<String>,
 }
 
+/// Parcela listada no extrato do crediário
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementInstallment {
+    pub description: String,
+    pub due_date: String,
+    pub amount_due: f64,
+    pub overdue: bool,
+}
+
+/// Dados para impressão do Extrato de Crediário (Fiado)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerStatementReceipt {
+    pub company_name: String,
+    pub company_address: String,
+    pub company_phone: Option<String>,
+
+    pub customer_name: String,
+    pub date_time: String,
+
+    pub installments: Vec<StatementInstallment>,
+    pub balance: f64,
+    pub charges: f64,
+    pub total_due: f64,
+    pub credit_limit: f64,
+    pub available_credit: f64,
+}
+
+/// Dados para impressão de Vale-Troca / Vale-Presente
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreCreditReceipt {
+    pub company_name: String,
+    pub company_address: String,
+    pub company_phone: Option<String>,
+
+    /// "VALE-PRESENTE" ou "VALE-TROCA"
+    pub title: String,
+    pub code: String,
+    pub amount: f64,
+    pub balance: f64,
+    pub customer_name: Option<String>,
+    pub expires_at: Option<String>,
+    pub date_time: String,
+}
+
+/// Linha do relatório de fechamento por forma de pagamento
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosingMethodLine {
+    pub label: String,
+    pub sales: f64,
+    pub receipts: f64,
+    pub refunds: f64,
+    pub expected: f64,
+    /// Só nas formas conferidas no fechamento
+    pub counted: Option<f64>,
+    pub difference: Option<f64>,
+}
+
+/// Cédula/moeda contada no fechamento
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosingDenominationLine {
+    pub denomination: f64,
+    pub quantity: u32,
+    pub amount: f64,
+}
+
+/// Dados para impressão do Relatório de Fechamento de Caixa (Z)
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CashClosingReceipt {
+    pub company_name: String,
+    pub company_address: String,
+    pub company_phone: Option<String>,
+
+    /// "FECHAMENTO DE CAIXA" ou "LEITURA PARCIAL" (sessão aberta)
+    pub title: String,
+    pub operator_name: String,
+    pub opened_at: String,
+    pub closed_at: Option<String>,
+    pub approved_by: Option<String>,
+    pub pending_approval: bool,
+
+    pub opening_balance: f64,
+    pub total_supplies: f64,
+    pub total_withdrawals: f64,
+    pub total_sales: f64,
+    pub total_canceled: f64,
+    pub total_refunds: f64,
+    pub total_account_receipts: f64,
+    pub total_gift_card_sales: f64,
+
+    pub methods: Vec<ClosingMethodLine>,
+    pub denominations: Vec<ClosingDenominationLine>,
+    pub total_difference: Option<f64>,
+    pub date_time: String,
+}
+
 impl ThermalPrinter {
     /// Imprime cupom de venda completo
     pub fn print_receipt(&mut self, receipt: &Receipt) -> &mut Self {
         self.init();
+        self.print_logo();
 
         // Cabeçalho
         self.align(TextAlign::Center);
@@ -1126,6 +1267,12 @@ impl ThermalPrinter {
         self.line(&format!("VENDA: #{:06}", receipt.sale_number));
         self.line(&format!("DATA:  {}", receipt.date_time));
         self.line(&format!("OPER:  {}", receipt.operator_name));
+        if let Some(ref customer) = receipt.customer_name {
+            self.line(&format!("CLIENTE: {}", customer));
+        }
+        if let Some(ref document) = receipt.customer_document {
+            self.line(&format!("CPF/CNPJ: {}", document));
+        }
         self.separator('-');
 
         // Itens - Cabeçalho
@@ -1161,6 +1308,27 @@ impl ThermalPrinter {
                 total_str,
                 width = spaces + total_str.len()
             ));
+
+            if item.discount > 0.0 {
+                let reason: String = item
+                    .discount_reason
+                    .as_deref()
+                    .unwrap_or("DESCONTO")
+                    .chars()
+                    .take(24)
+                    .collect();
+                let reason = format!("      {}", reason);
+                let discount_str = format!("-R$ {:.2}", item.discount);
+                let spaces = (self.config.paper_width as usize)
+                    .saturating_sub(reason.chars().count() + discount_str.len());
+
+                self.line(&format!(
+                    "{}{:>width$}",
+                    reason,
+                    discount_str,
+                    width = spaces + discount_str.len()
+                ));
+            }
         }
 
         self.separator('-');
@@ -1227,6 +1395,7 @@ impl ThermalPrinter {
     /// Imprime Ordem de Serviço completa
     pub fn print_service_order(&mut self, os: &ServiceOrderReceipt) -> &mut Self {
         self.init();
+        self.print_logo();
         let width = self.config.paper_width as usize;
 
         // Cabeçalho
@@ -1413,6 +1582,7 @@ impl ThermalPrinter {
     /// Imprime Pedido do Atendente (para cliente levar ao caixa)
     pub fn print_attendant_order(&mut self, order: &AttendantOrderReceipt) -> &mut Self {
         self.init();
+        self.print_logo();
         let width = self.config.paper_width as usize;
 
         // Cabeçalho
@@ -1557,6 +1727,329 @@ impl ThermalPrinter {
 
         self
     }
+
+    /// Imprime extrato do crediário (fiado) do cliente
+    pub fn print_customer_statement(&mut self, statement: &CustomerStatementReceipt) -> &mut Self {
+        self.init();
+        self.print_logo();
+        let width = self.config.paper_width as usize;
+
+        // Cabeçalho
+        self.align(TextAlign::Center);
+        self.style(TextStyle {
+            bold: true,
+            double_height: true,
+            ..Default::default()
+        });
+        self.line(&statement.company_name);
+
+        self.style(TextStyle::default());
+        self.line(&statement.company_address);
+        if let Some(ref phone) = statement.company_phone {
+            self.line(&format!("TEL: {}", phone));
+        }
+
+        self.feed(1);
+        self.separator('=');
+        self.style(TextStyle {
+            bold: true,
+            ..Default::default()
+        });
+        self.line("EXTRATO DE CREDIARIO");
+        self.style(TextStyle::default());
+        self.separator('=');
+
+        self.align(TextAlign::Left);
+        self.line(&format!("CLIENTE: {}", statement.customer_name));
+        self.line(&statement.date_time);
+        self.separator('-');
+
+        // Parcelas em aberto
+        if statement.installments.is_empty() {
+            self.align(TextAlign::Center);
+            self.line("Nenhuma parcela em aberto");
+            self.align(TextAlign::Left);
+        }
+        for installment in &statement.installments {
+            self.line(&installment.description);
+
+            let detail = format!(
+                "    Venc. {}{}",
+                installment.due_date,
+                if installment.overdue { " *ATRASO*" } else { "" }
+            );
+            let total_str = format!("R$ {:.2}", installment.amount_due);
+            let spaces = width.saturating_sub(detail.len() + total_str.len());
+
+            self.line(&format!(
+                "{}{:>width$}",
+                detail,
+                total_str,
+                width = spaces + total_str.len()
+            ));
+        }
+
+        self.separator('-');
+
+        // Totais
+        self.align(TextAlign::Right);
+        self.line(&format!("SALDO: R$ {:.2}", statement.balance));
+        if statement.charges > 0.0 {
+            self.line(&format!("MULTA/JUROS: R$ {:.2}", statement.charges));
+        }
+        self.style(TextStyle {
+            bold: true,
+            double_height: true,
+            ..Default::default()
+        });
+        self.line(&format!("TOTAL DEVIDO: R$ {:.2}", statement.total_due));
+        self.style(TextStyle::default());
+
+        self.line(&format!("LIMITE: R$ {:.2}", statement.credit_limit));
+        self.line(&format!("DISPONIVEL: R$ {:.2}", statement.available_credit));
+        self.separator('=');
+
+        self.align(TextAlign::Center);
+        self.feed(1);
+        self.line("Valores calculados para a data de emissao");
+
+        // Corte
+        if self.config.auto_cut {
+            self.cut(true);
+        } else {
+            self.feed(4);
+        }
+
+        self
+    }
+
+    /// Imprime vale-troca / vale-presente com código de barras e QR Code
+    pub fn print_store_credit(&mut self, credit: &StoreCreditReceipt) -> &mut Self {
+        self.init();
+        self.print_logo();
+
+        // Cabeçalho
+        self.align(TextAlign::Center);
+        self.style(TextStyle {
+            bold: true,
+            double_height: true,
+            ..Default::default()
+        });
+        self.line(&credit.company_name);
+
+        self.style(TextStyle::default());
+        self.line(&credit.company_address);
+        if let Some(ref phone) = credit.company_phone {
+            self.line(&format!("TEL: {}", phone));
+        }
+
+        self.feed(1);
+        self.separator('=');
+        self.style(TextStyle {
+            bold: true,
+            double_width: true,
+            ..Default::default()
+        });
+        self.line(&credit.title);
+        self.style(TextStyle::default());
+        self.separator('=');
+
+        // Valor
+        self.style(TextStyle {
+            bold: true,
+            double_height: true,
+            ..Default::default()
+        });
+        self.line(&format!("R$ {:.2}", credit.balance));
+        self.style(TextStyle::default());
+        if (credit.amount - credit.balance).abs() > 0.005 {
+            self.line(&format!("Valor original: R$ {:.2}", credit.amount));
+        }
+        self.feed(1);
+
+        self.align(TextAlign::Left);
+        self.line(&format!("EMISSAO: {}", credit.date_time));
+        if let Some(ref customer) = credit.customer_name {
+            self.line(&format!("CLIENTE: {}", customer));
+        }
+        match credit.expires_at {
+            Some(ref expires_at) => self.line(&format!("VALIDADE: {}", expires_at)),
+            None => self.line("VALIDADE: INDETERMINADA"),
+        };
+        self.separator('-');
+
+        // Código para resgate no caixa
+        self.align(TextAlign::Center);
+        self.barcode_ean13(&credit.code);
+        self.feed(1);
+        self.qrcode(&credit.code);
+        self.feed(1);
+        self.line("Apresente este vale no caixa.");
+        self.line("Nao e trocado por dinheiro.");
+
+        // Corte
+        if self.config.auto_cut {
+            self.cut(true);
+        } else {
+            self.feed(4);
+        }
+
+        self
+    }
+
+    /// Imprime relatório de fechamento de caixa (Z) por forma de pagamento
+    pub fn print_cash_closing(&mut self, report: &CashClosingReceipt) -> &mut Self {
+        self.init();
+
+        // Cabeçalho
+        self.align(TextAlign::Center);
+        self.style(TextStyle {
+            bold: true,
+            ..Default::default()
+        });
+        self.line(&report.company_name);
+        self.style(TextStyle::default());
+        self.line(&report.company_address);
+        if let Some(ref phone) = report.company_phone {
+            self.line(&format!("TEL: {}", phone));
+        }
+
+        self.separator('=');
+        self.style(TextStyle {
+            bold: true,
+            double_height: true,
+            ..Default::default()
+        });
+        self.line(&report.title);
+        self.style(TextStyle::default());
+        self.separator('=');
+
+        self.align(TextAlign::Left);
+        self.line(&format!("OPERADOR: {}", report.operator_name));
+        self.line(&format!("ABERTURA:   {}", report.opened_at));
+        if let Some(ref closed_at) = report.closed_at {
+            self.line(&format!("FECHAMENTO: {}", closed_at));
+        }
+        self.separator('-');
+
+        // Movimento da sessão
+        self.labeled_line("Fundo de troco", report.opening_balance);
+        self.labeled_line("Suprimentos", report.total_supplies);
+        self.labeled_line("Sangrias", -report.total_withdrawals);
+        self.labeled_line("Vendas", report.total_sales);
+        if report.total_canceled > 0.0 {
+            self.labeled_line("Canceladas", report.total_canceled);
+        }
+        if report.total_refunds > 0.0 {
+            self.labeled_line("Estornos", -report.total_refunds);
+        }
+        if report.total_account_receipts > 0.0 {
+            self.labeled_line("Receb. fiado", report.total_account_receipts);
+        }
+        if report.total_gift_card_sales > 0.0 {
+            self.labeled_line("Vale-presentes", report.total_gift_card_sales);
+        }
+        self.separator('-');
+
+        // Por forma de pagamento
+        self.style(TextStyle {
+            bold: true,
+            ..Default::default()
+        });
+        self.line("POR FORMA DE PAGAMENTO");
+        self.style(TextStyle::default());
+        for method in &report.methods {
+            self.style(TextStyle {
+                bold: true,
+                ..Default::default()
+            });
+            self.line(&method.label.to_uppercase());
+            self.style(TextStyle::default());
+            if method.sales != 0.0 {
+                self.labeled_line("  Vendas", method.sales);
+            }
+            if method.receipts != 0.0 {
+                self.labeled_line("  Recebimentos", method.receipts);
+            }
+            if method.refunds != 0.0 {
+                self.labeled_line("  Estornos", -method.refunds);
+            }
+            self.labeled_line("  Esperado", method.expected);
+            if let Some(counted) = method.counted {
+                self.labeled_line("  Contado", counted);
+            }
+            if let Some(difference) = method.difference {
+                self.labeled_line("  Diferenca", difference);
+            }
+        }
+
+        // Contagem da gaveta
+        if !report.denominations.is_empty() {
+            self.separator('-');
+            self.style(TextStyle {
+                bold: true,
+                ..Default::default()
+            });
+            self.line("CONTAGEM DE CEDULAS E MOEDAS");
+            self.style(TextStyle::default());
+            for d in &report.denominations {
+                self.labeled_line(
+                    &format!("  {:>4} x R$ {:.2}", d.quantity, d.denomination),
+                    d.amount,
+                );
+            }
+        }
+
+        self.separator('=');
+        if let Some(difference) = report.total_difference {
+            self.style(TextStyle {
+                bold: true,
+                ..Default::default()
+            });
+            self.labeled_line("DIFERENCA TOTAL", difference);
+            self.style(TextStyle::default());
+        }
+        if report.pending_approval {
+            self.align(TextAlign::Center);
+            self.line("*** AGUARDANDO APROVACAO DO GERENTE ***");
+            self.align(TextAlign::Left);
+        }
+        if let Some(ref approved_by) = report.approved_by {
+            self.line(&format!("APROVADO POR: {}", approved_by));
+        }
+
+        self.feed(2);
+        self.align(TextAlign::Center);
+        self.line("________________________________");
+        self.line("Operador");
+        self.feed(1);
+        self.line("________________________________");
+        self.line("Gerente");
+        self.feed(1);
+        self.line(&report.date_time);
+
+        // Corte
+        if self.config.auto_cut {
+            self.cut(true);
+        } else {
+            self.feed(4);
+        }
+
+        self
+    }
+
+    /// Descrição à esquerda e valor alinhado à direita
+    fn labeled_line(&mut self, label: &str, value: f64) -> &mut Self {
+        let value_str = format!("R$ {:.2}", value);
+        let spaces = (self.config.paper_width as usize)
+            .saturating_sub(label.chars().count() + value_str.len());
+        self.line(&format!(
+            "{}{:>width$}",
+            label,
+            value_str,
+            width = spaces + value_str.len()
+        ))
+    }
 }
 
 // ════════════════════════════════════════════════════════════════════════════
@@ -1593,6 +2086,8 @@ mod tests {
             sale_number: 1,
             operator_name: "Admin".to_string(),
             date_time: "07/01/2026 12:00".to_string(),
+            customer_name: None,
+            customer_document: None,
             items: vec![ReceiptItem {
                 code: "MRC-00001".to_string(),
                 name: "Refrigerante Cola 2L".to_string(),
@@ -1600,6 +2095,8 @@ mod tests {
                 unit: "UN".to_string(),
                 unit_price: 9.99,
                 total: 19.98,
+                discount: 0.0,
+                discount_reason: None,
             }],
             subtotal: 19.98,
             discount: 0.0,
@@ -0,0 +1,400 @@
+//! Status em Tempo Real da Impressora Térmica
+//!
+//! Consulta os sensores da impressora pelos comandos de status em tempo real:
+//! - `DLE EOT n` (Epson, Elgin, Daruma, C3Tech e genéricas ESC/POS)
+//! - `GS r n` (Bematech, que não responde ao DLE EOT no modo ESC/POS)
+//!
+//! As respostas trazem papel no fim/acabando, tampa aberta, erro de
+//! guilhotina e o nível do pino 3 do conector RJ11, usado como sensor da
+//! gaveta. Disponível apenas em conexões serial e rede (a porta USB raw não
+//! tem leitura com timeout).
+
+use super::{
+    printer::{PrinterConfig, PrinterConnection, PrinterModel},
+    HardwareError, HardwareResult,
+};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+// ════════════════════════════════════════════════════════════════════════════
+// COMANDOS
+// ════════════════════════════════════════════════════════════════════════════
+
+const DLE: u8 = 0x10;
+const EOT: u8 = 0x04;
+const GS: u8 = 0x1D;
+
+/// DLE EOT 1: status da impressora (gaveta, offline)
+const DLE_EOT_PRINTER: [u8; 3] = [DLE, EOT, 1];
+/// DLE EOT 2: causa do offline (tampa, fim de papel, erro)
+const DLE_EOT_OFFLINE: [u8; 3] = [DLE, EOT, 2];
+/// DLE EOT 3: causa do erro (guilhotina, irrecuperável)
+const DLE_EOT_ERROR: [u8; 3] = [DLE, EOT, 3];
+/// DLE EOT 4: sensor do rolo de papel
+const DLE_EOT_PAPER: [u8; 3] = [DLE, EOT, 4];
+/// GS r 1: sensor de papel
+const GS_R_PAPER: [u8; 3] = [GS, b'r', 1];
+/// GS r 2: conector da gaveta
+const GS_R_DRAWER: [u8; 3] = [GS, b'r', 2];
+
+// ════════════════════════════════════════════════════════════════════════════
+// TIPOS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Família de comandos de status suportada pelo modelo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusProtocol {
+    DleEot,
+    GsR,
+}
+
+impl StatusProtocol {
+    pub fn for_model(model: &PrinterModel) -> Self {
+        match model {
+            PrinterModel::Bematech => Self::GsR,
+            _ => Self::DleEot,
+        }
+    }
+}
+
+/// Sensores lidos da impressora
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PrinterSensorStatus {
+    pub online: bool,
+    pub paper_near_end: bool,
+    pub paper_out: bool,
+    pub cover_open: bool,
+    /// Erro de guilhotina ou do mecanismo
+    pub error: bool,
+    /// Nível do pino 3 do conector da gaveta (alto = true)
+    pub drawer_pin_high: bool,
+}
+
+impl PrinterSensorStatus {
+    /// Sensor da gaveta; por padrão pino em nível baixo = gaveta aberta
+    pub fn drawer_open(&self, inverted: bool) -> bool {
+        self.drawer_pin_high == inverted
+    }
+
+    /// Condição que impede a impressão
+    pub fn blocking_problem(&self) -> Option<&'static str> {
+        if self.paper_out {
+            Some("Impressora sem papel")
+        } else if self.cover_open {
+            Some("Tampa da impressora aberta")
+        } else if self.error {
+            Some("Impressora em erro (guilhotina/mecanismo)")
+        } else if !self.online {
+            Some("Impressora offline")
+        } else {
+            None
+        }
+    }
+
+    /// Aviso que não impede a impressão
+    pub fn warning(&self) -> Option<&'static str> {
+        self.paper_near_end.then_some("Papel próximo do fim")
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// PARSE DAS RESPOSTAS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Respostas DLE EOT têm bits fixos: 0 e 7 em 0, 1 e 4 em 1
+fn valid_dle_eot(byte: u8) -> bool {
+    byte & 0x93 == 0x12
+}
+
+/// Respostas GS r têm os bits 4 e 7 em 0
+fn valid_gs_r(byte: u8) -> bool {
+    byte & 0x90 == 0
+}
+
+fn check(byte: u8, valid: fn(u8) -> bool) -> HardwareResult<u8> {
+    if valid(byte) {
+        Ok(byte)
+    } else {
+        Err(HardwareError::ProtocolError(format!(
+            "Resposta de status inválida: 0x{:02X}",
+            byte
+        )))
+    }
+}
+
+/// Aplica as respostas de DLE EOT 1, 2, 3 e 4
+pub fn parse_dle_eot(
+    printer: u8,
+    offline: u8,
+    error: u8,
+    paper: u8,
+) -> HardwareResult<PrinterSensorStatus> {
+    let printer = check(printer, valid_dle_eot)?;
+    let offline = check(offline, valid_dle_eot)?;
+    let error = check(error, valid_dle_eot)?;
+    let paper = check(paper, valid_dle_eot)?;
+
+    Ok(PrinterSensorStatus {
+        online: printer & 0x08 == 0,
+        drawer_pin_high: printer & 0x04 != 0,
+        cover_open: offline & 0x04 != 0,
+        paper_out: offline & 0x20 != 0 || paper & 0x60 != 0,
+        error: offline & 0x40 != 0 || error & 0x2C != 0,
+        paper_near_end: paper & 0x0C != 0,
+    })
+}
+
+/// Aplica as respostas de GS r 1 e 2
+pub fn parse_gs_r(paper: u8, drawer: u8) -> HardwareResult<PrinterSensorStatus> {
+    let paper = check(paper, valid_gs_r)?;
+    let drawer = check(drawer, valid_gs_r)?;
+
+    Ok(PrinterSensorStatus {
+        online: true,
+        paper_near_end: paper & 0x03 != 0,
+        paper_out: paper & 0x0C != 0,
+        cover_open: false,
+        error: false,
+        drawer_pin_high: drawer & 0x01 != 0,
+    })
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// CONSULTA
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Envia um comando de status e lê o byte de resposta
+fn exchange<T: Read + Write + ?Sized>(port: &mut T, cmd: &[u8]) -> HardwareResult<u8> {
+    port.write_all(cmd).map_err(HardwareError::IoError)?;
+    port.flush().map_err(HardwareError::IoError)?;
+
+    let mut byte = [0u8; 1];
+    match port.read(&mut byte) {
+        Ok(1) => Ok(byte[0]),
+        Ok(_) => Err(HardwareError::Timeout),
+        Err(e)
+            if e.kind() == std::io::ErrorKind::TimedOut
+                || e.kind() == std::io::ErrorKind::WouldBlock =>
+        {
+            Err(HardwareError::Timeout)
+        }
+        Err(e) => Err(HardwareError::IoError(e)),
+    }
+}
+
+/// Lê todos os sensores por uma conexão já aberta
+pub fn read_status<T: Read + Write + ?Sized>(
+    port: &mut T,
+    protocol: StatusProtocol,
+) -> HardwareResult<PrinterSensorStatus> {
+    match protocol {
+        StatusProtocol::DleEot => {
+            let printer = exchange(port, &DLE_EOT_PRINTER)?;
+            let offline = exchange(port, &DLE_EOT_OFFLINE)?;
+            let error = exchange(port, &DLE_EOT_ERROR)?;
+            let paper = exchange(port, &DLE_EOT_PAPER)?;
+            parse_dle_eot(printer, offline, error, paper)
+        }
+        StatusProtocol::GsR => {
+            let paper = exchange(port, &GS_R_PAPER)?;
+            let drawer = exchange(port, &GS_R_DRAWER)?;
+            parse_gs_r(paper, drawer)
+        }
+    }
+}
+
+/// Serializa o acesso à porta da impressora entre impressão, consulta de
+/// status e monitoramento da gaveta (portas seriais abrem em modo exclusivo)
+pub fn printer_port_lock() -> MutexGuard<'static, ()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Consulta os sensores usando a conexão configurada (bloqueante)
+pub fn query_printer_sensors(config: &PrinterConfig) -> HardwareResult<PrinterSensorStatus> {
+    if config.mock_mode {
+        return Ok(PrinterSensorStatus {
+            online: true,
+            drawer_pin_high: true,
+            ..Default::default()
+        });
+    }
+
+    let protocol = StatusProtocol::for_model(&config.model);
+    let timeout = Duration::from_millis(config.timeout_ms.clamp(200, 3000));
+    let _guard = printer_port_lock();
+
+    match config.connection {
+        PrinterConnection::Serial => {
+            let builder = serialport::new(&config.port, config.baud_rate)
+                .data_bits(match config.data_bits {
+                    7 => serialport::DataBits::Seven,
+                    _ => serialport::DataBits::Eight,
+                })
+                .parity(match config.parity.as_str() {
+                    "odd" => serialport::Parity::Odd,
+                    "even" => serialport::Parity::Even,
+                    _ => serialport::Parity::None,
+                })
+                .stop_bits(serialport::StopBits::One)
+                .timeout(timeout);
+            let mut port = builder
+                .open()
+                .map_err(|e| HardwareError::ConnectionFailed(e.to_string()))?;
+            read_status(&mut *port, protocol)
+        }
+        PrinterConnection::Network => {
+            use std::net::{TcpStream, ToSocketAddrs};
+
+            let address = config
+                .port
+                .to_socket_addrs()
+                .map_err(|e| HardwareError::InvalidPort(format!("Endereço inválido: {}", e)))?
+                .next()
+                .ok_or_else(|| HardwareError::InvalidPort(config.port.clone()))?;
+            let mut stream = TcpStream::connect_timeout(&address, timeout)
+                .map_err(|e| HardwareError::ConnectionFailed(e.to_string()))?;
+            stream
+                .set_read_timeout(Some(timeout))
+                .map_err(HardwareError::IoError)?;
+            read_status(&mut stream, protocol)
+        }
+        PrinterConnection::Usb => Err(HardwareError::NotConfigured(
+            "Status em tempo real indisponível via USB".into(),
+        )),
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TESTES
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Porta simulada: responde um byte por comando recebido
+    struct FakePort {
+        responses: VecDeque<u8>,
+        written: Vec<u8>,
+    }
+
+    impl FakePort {
+        fn new(responses: &[u8]) -> Self {
+            Self {
+                responses: responses.iter().copied().collect(),
+                written: Vec::new(),
+            }
+        }
+    }
+
+    impl Read for FakePort {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self.responses.pop_front() {
+                Some(byte) => {
+                    buf[0] = byte;
+                    Ok(1)
+                }
+                None => Err(std::io::ErrorKind::TimedOut.into()),
+            }
+        }
+    }
+
+    impl Write for FakePort {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_dle_eot_all_ok() {
+        let status = parse_dle_eot(0x16, 0x12, 0x12, 0x12).unwrap();
+        assert!(status.online);
+        assert!(status.drawer_pin_high);
+        assert_eq!(status.blocking_problem(), None);
+        assert_eq!(status.warning(), None);
+        // Pino alto = gaveta fechada (sensor padrão)
+        assert!(!status.drawer_open(false));
+        assert!(status.drawer_open(true));
+    }
+
+    #[test]
+    fn test_dle_eot_paper_and_cover() {
+        // Offline, tampa aberta, papel acabando e no fim
+        let status = parse_dle_eot(0x1A, 0x16, 0x12, 0x7E).unwrap();
+        assert!(!status.online);
+        assert!(status.cover_open);
+        assert!(status.paper_near_end);
+        assert!(status.paper_out);
+        assert_eq!(status.blocking_problem(), Some("Impressora sem papel"));
+        assert!(status.drawer_open(false));
+    }
+
+    #[test]
+    fn test_dle_eot_cutter_error() {
+        let status = parse_dle_eot(0x1A, 0x52, 0x16, 0x12).unwrap();
+        assert!(status.error);
+        assert!(!status.paper_out);
+    }
+
+    #[test]
+    fn test_invalid_response_rejected() {
+        assert!(parse_dle_eot(0x00, 0x12, 0x12, 0x12).is_err());
+        assert!(parse_gs_r(0x80, 0x00).is_err());
+    }
+
+    #[test]
+    fn test_gs_r() {
+        let status = parse_gs_r(0x03, 0x01).unwrap();
+        assert!(status.paper_near_end);
+        assert!(!status.paper_out);
+        assert!(status.drawer_pin_high);
+
+        let status = parse_gs_r(0x0F, 0x00).unwrap();
+        assert!(status.paper_out);
+        assert!(!status.drawer_pin_high);
+    }
+
+    #[test]
+    fn test_read_status_per_model() {
+        let mut port = FakePort::new(&[0x16, 0x12, 0x12, 0x1E]);
+        let status = read_status(&mut port, StatusProtocol::DleEot).unwrap();
+        assert!(status.paper_near_end);
+        assert_eq!(
+            port.written,
+            [
+                DLE_EOT_PRINTER,
+                DLE_EOT_OFFLINE,
+                DLE_EOT_ERROR,
+                DLE_EOT_PAPER
+            ]
+            .concat()
+        );
+
+        let mut port = FakePort::new(&[0x0C, 0x00]);
+        let protocol = StatusProtocol::for_model(&PrinterModel::Bematech);
+        let status = read_status(&mut port, protocol).unwrap();
+        assert!(status.paper_out);
+        assert_eq!(port.written, [GS_R_PAPER, GS_R_DRAWER].concat());
+    }
+
+    #[test]
+    fn test_no_response_is_timeout() {
+        let mut port = FakePort::new(&[0x16]);
+        assert!(matches!(
+            read_status(&mut port, StatusProtocol::DleEot),
+            Err(HardwareError::Timeout)
+        ));
+    }
+}
@@ -0,0 +1,389 @@
+//! Impressão de Imagens (Logo) em ESC/POS
+//!
+//! Converte o logo da loja (PNG gravado nas configurações) em bitmap
+//! monocromático com dithering Floyd-Steinberg, reduzido para a largura da
+//! bobina em pontos, e gera os comandos de impressão:
+//! - `GS v 0` - raster, aceito pela maioria (Epson, Elgin, C3Tech, genéricas)
+//! - `ESC *` - modo bit image em faixas de 24 pontos, para Bematech e Daruma
+//!
+//! A conversão é cara para ser refeita a cada cupom, então o bitmap fica em
+//! cache (`LogoCache`) até o logo ou a largura do papel mudar.
+
+use super::printer::{escpos, PrinterConfig, PrinterModel, ThermalPrinter};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// Chave da configuração com o PNG do logo (base64)
+pub const LOGO_SETTING_KEY: &str = "hardware.printer.logo";
+
+/// Tamanho máximo do arquivo do logo
+pub const MAX_LOGO_BYTES: usize = 512 * 1024;
+
+/// Altura máxima do logo impresso (~5 cm a 203 dpi)
+const MAX_LOGO_HEIGHT: u32 = 400;
+
+/// Pontos por coluna de texto na fonte A (48 col = 576 pts, 32 col = 384 pts)
+const DOTS_PER_COLUMN: u32 = 12;
+
+/// Faixas de até 24 linhas (limite de buffer das impressoras mais simples)
+const BAND_ROWS: u32 = 24;
+
+// ════════════════════════════════════════════════════════════════════════════
+// TIPOS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Dimensões do logo convertido, em pontos
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PrinterLogoInfo {
+    pub width_dots: u32,
+    pub height_dots: u32,
+}
+
+/// Comando usado para imprimir imagens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RasterMode {
+    /// `GS v 0` (raster)
+    GsV0,
+    /// `ESC * 33` (bit image 24 pontos, firmwares mais antigos)
+    EscStar,
+}
+
+impl RasterMode {
+    pub fn for_model(model: &PrinterModel) -> Self {
+        match model {
+            PrinterModel::Bematech | PrinterModel::Daruma => Self::EscStar,
+            _ => Self::GsV0,
+        }
+    }
+}
+
+/// Imagem monocromática, 1 bit por ponto (MSB à esquerda, 1 = preto)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonoBitmap {
+    pub width: u32,
+    pub height: u32,
+    data: Vec<u8>,
+}
+
+impl MonoBitmap {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; (width.div_ceil(8) * height) as usize],
+        }
+    }
+
+    pub fn bytes_per_row(&self) -> usize {
+        self.width.div_ceil(8) as usize
+    }
+
+    pub fn is_black(&self, x: u32, y: u32) -> bool {
+        let byte = self.data[y as usize * self.bytes_per_row() + (x / 8) as usize];
+        byte & (0x80 >> (x % 8)) != 0
+    }
+
+    fn set_black(&mut self, x: u32, y: u32) {
+        let index = y as usize * self.bytes_per_row() + (x / 8) as usize;
+        self.data[index] |= 0x80 >> (x % 8);
+    }
+
+    /// Decodifica a imagem e reduz para no máximo `max_width` pontos
+    pub fn from_image_bytes(bytes: &[u8], max_width: u32) -> Result<Self, String> {
+        let img =
+            image::load_from_memory(bytes).map_err(|e| format!("Erro ao abrir imagem: {}", e))?;
+        Ok(Self::from_image(&img, max_width))
+    }
+
+    /// Converte para preto e branco com dithering Floyd-Steinberg. Áreas
+    /// transparentes viram branco (fundo do papel)
+    pub fn from_image(img: &image::DynamicImage, max_width: u32) -> Self {
+        let img = if img.width() > max_width || img.height() > MAX_LOGO_HEIGHT {
+            img.resize(
+                max_width,
+                MAX_LOGO_HEIGHT,
+                image::imageops::FilterType::Triangle,
+            )
+        } else {
+            img.clone()
+        };
+
+        let rgba = img.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        let mut gray: Vec<f32> = rgba
+            .pixels()
+            .map(|p| {
+                let [r, g, b, a] = p.0;
+                let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+                let alpha = a as f32 / 255.0;
+                luma * alpha + 255.0 * (1.0 - alpha)
+            })
+            .collect();
+
+        let mut bitmap = Self::new(width, height);
+        let w = width as usize;
+        for y in 0..height as usize {
+            for x in 0..w {
+                let old = gray[y * w + x];
+                let new = if old < 128.0 { 0.0 } else { 255.0 };
+                if new == 0.0 {
+                    bitmap.set_black(x as u32, y as u32);
+                }
+
+                let error = old - new;
+                if x + 1 < w {
+                    gray[y * w + x + 1] += error * 7.0 / 16.0;
+                }
+                if y + 1 < height as usize {
+                    if x > 0 {
+                        gray[(y + 1) * w + x - 1] += error * 3.0 / 16.0;
+                    }
+                    gray[(y + 1) * w + x] += error * 5.0 / 16.0;
+                    if x + 1 < w {
+                        gray[(y + 1) * w + x + 1] += error / 16.0;
+                    }
+                }
+            }
+        }
+        bitmap
+    }
+
+    /// Comandos `GS v 0 m xL xH yL yH d1...dk` em faixas
+    pub fn to_gs_v0(&self) -> Vec<u8> {
+        let bytes_per_row = self.bytes_per_row();
+        let mut cmds =
+            Vec::with_capacity(self.data.len() + 8 * self.height.div_ceil(BAND_ROWS) as usize);
+
+        for y0 in (0..self.height).step_by(BAND_ROWS as usize) {
+            let rows = BAND_ROWS.min(self.height - y0);
+            cmds.extend_from_slice(&[escpos::GS, b'v', b'0', 0x00]);
+            cmds.push((bytes_per_row & 0xFF) as u8);
+            cmds.push(((bytes_per_row >> 8) & 0xFF) as u8);
+            cmds.push((rows & 0xFF) as u8);
+            cmds.push(((rows >> 8) & 0xFF) as u8);
+
+            let start = y0 as usize * bytes_per_row;
+            cmds.extend_from_slice(&self.data[start..start + rows as usize * bytes_per_row]);
+        }
+        cmds
+    }
+
+    /// Comandos `ESC * 33 nL nH` (24 pontos verticais por coluna, 3 bytes)
+    pub fn to_esc_star(&self) -> Vec<u8> {
+        let mut cmds = Vec::new();
+        cmds.extend_from_slice(&escpos::LINE_SPACING_SET);
+
+        for y0 in (0..self.height).step_by(BAND_ROWS as usize) {
+            cmds.extend_from_slice(&[escpos::ESC, b'*', 33]);
+            cmds.push((self.width & 0xFF) as u8);
+            cmds.push(((self.width >> 8) & 0xFF) as u8);
+
+            for x in 0..self.width {
+                for slice in 0..3 {
+                    let mut byte = 0u8;
+                    for bit in 0..8 {
+                        let y = y0 + slice * 8 + bit;
+                        if y < self.height && self.is_black(x, y) {
+                            byte |= 0x80 >> bit;
+                        }
+                    }
+                    cmds.push(byte);
+                }
+            }
+            cmds.push(escpos::LF);
+        }
+
+        cmds.extend_from_slice(&escpos::LINE_SPACING_DEFAULT);
+        cmds
+    }
+}
+
+/// Largura útil da bobina em pontos
+pub fn paper_width_dots(config: &PrinterConfig) -> u32 {
+    config.paper_width as u32 * DOTS_PER_COLUMN
+}
+
+/// Bytes do logo gravado em base64 (aceita o prefixo `data:image/png;base64,`)
+pub fn decode_logo(source: &str) -> Result<Vec<u8>, String> {
+    let data = source
+        .split_once(";base64,")
+        .map_or(source, |(_, data)| data)
+        .trim();
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| format!("Logo em base64 inválido: {}", e))?;
+    if bytes.len() > MAX_LOGO_BYTES {
+        return Err(format!(
+            "Logo muito grande ({} KB, máximo {} KB)",
+            bytes.len() / 1024,
+            MAX_LOGO_BYTES / 1024
+        ));
+    }
+    Ok(bytes)
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// CACHE
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Último logo convertido, identificado pelo conteúdo e pela largura
+#[derive(Default)]
+pub struct LogoCache {
+    entry: Mutex<Option<(u64, u32, Arc<MonoBitmap>)>>,
+}
+
+impl LogoCache {
+    /// Bitmap do logo (base64) para a largura informada, convertendo apenas
+    /// quando o logo ou a largura mudaram
+    pub fn get_or_convert(&self, source: &str, width_dots: u32) -> Result<Arc<MonoBitmap>, String> {
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        let key = hasher.finish();
+
+        let mut entry = self.entry.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((cached_key, cached_width, bitmap)) = entry.as_ref() {
+            if *cached_key == key && *cached_width == width_dots {
+                return Ok(bitmap.clone());
+            }
+        }
+
+        let bitmap = Arc::new(MonoBitmap::from_image_bytes(
+            &decode_logo(source)?,
+            width_dots,
+        )?);
+        *entry = Some((key, width_dots, bitmap.clone()));
+        Ok(bitmap)
+    }
+
+    pub fn clear(&self) {
+        *self.entry.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+impl ThermalPrinter {
+    /// Imprime bitmap centralizado no comando suportado pelo modelo
+    pub fn raster_image(&mut self, bitmap: &MonoBitmap) -> &mut Self {
+        let cmds = match RasterMode::for_model(&self.config().model) {
+            RasterMode::GsV0 => bitmap.to_gs_v0(),
+            RasterMode::EscStar => bitmap.to_esc_star(),
+        };
+        self.raw(&escpos::ALIGN_CENTER);
+        self.raw(&cmds);
+        self
+    }
+
+    /// Imprime o logo da loja, se configurado
+    pub fn print_logo(&mut self) -> &mut Self {
+        if let Some(logo) = self.logo() {
+            self.raster_image(&logo);
+        }
+        self
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TESTES
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, GrayImage, Luma, Rgba, RgbaImage};
+
+    fn gray(width: u32, height: u32, value: u8) -> DynamicImage {
+        DynamicImage::ImageLuma8(GrayImage::from_pixel(width, height, Luma([value])))
+    }
+
+    #[test]
+    fn test_threshold_and_transparency() {
+        let black = MonoBitmap::from_image(&gray(10, 2, 0), 576);
+        assert_eq!((black.width, black.height), (10, 2));
+        assert!((0..10).all(|x| black.is_black(x, 0) && black.is_black(x, 1)));
+
+        let transparent = DynamicImage::ImageRgba8(RgbaImage::from_pixel(8, 8, Rgba([0, 0, 0, 0])));
+        let bitmap = MonoBitmap::from_image(&transparent, 576);
+        assert!((0..8).all(|x| (0..8).all(|y| !bitmap.is_black(x, y))));
+    }
+
+    #[test]
+    fn test_dithering_mid_gray_is_half_black() {
+        let bitmap = MonoBitmap::from_image(&gray(32, 32, 127), 576);
+        let black = (0..32)
+            .flat_map(|y| (0..32).map(move |x| (x, y)))
+            .filter(|&(x, y)| bitmap.is_black(x, y))
+            .count();
+        assert!((480..=544).contains(&black), "{}", black);
+    }
+
+    #[test]
+    fn test_resize_to_paper_width() {
+        let bitmap = MonoBitmap::from_image(&gray(1152, 200, 0), 576);
+        assert_eq!((bitmap.width, bitmap.height), (576, 100));
+        assert_eq!(
+            paper_width_dots(&PrinterConfig {
+                paper_width: 32,
+                ..Default::default()
+            }),
+            384
+        );
+    }
+
+    #[test]
+    fn test_gs_v0_bands() {
+        let bitmap = MonoBitmap::from_image(&gray(12, 30, 0), 576);
+        let cmds = bitmap.to_gs_v0();
+
+        // Faixa 1: 24 linhas de 2 bytes; faixa 2: 6 linhas
+        assert_eq!(&cmds[..8], &[escpos::GS, b'v', b'0', 0, 2, 0, 24, 0]);
+        assert_eq!(cmds[8], 0xFF);
+        assert_eq!(cmds[9], 0xF0);
+        let second = 8 + 24 * 2;
+        assert_eq!(
+            &cmds[second..second + 8],
+            &[escpos::GS, b'v', b'0', 0, 2, 0, 6, 0]
+        );
+        assert_eq!(cmds.len(), 16 + 30 * 2);
+    }
+
+    #[test]
+    fn test_esc_star_columns() {
+        let bitmap = MonoBitmap::from_image(&gray(4, 10, 0), 576);
+        let cmds = bitmap.to_esc_star();
+
+        assert_eq!(&cmds[..3], &escpos::LINE_SPACING_SET);
+        assert_eq!(&cmds[3..8], &[escpos::ESC, b'*', 33, 4, 0]);
+        // 10 linhas pretas: 8 no primeiro byte, 2 no segundo, nenhuma no terceiro
+        assert_eq!(&cmds[8..11], &[0xFF, 0xC0, 0x00]);
+        assert!(cmds.ends_with(&escpos::LINE_SPACING_DEFAULT));
+        assert_eq!(
+            RasterMode::for_model(&PrinterModel::Bematech),
+            RasterMode::EscStar
+        );
+    }
+
+    #[test]
+    fn test_logo_cache_and_decode() {
+        let mut png = Vec::new();
+        gray(16, 4, 0)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let source = format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(&png)
+        );
+
+        let cache = LogoCache::default();
+        let first = cache.get_or_convert(&source, 576).unwrap();
+        let second = cache.get_or_convert(&source, 576).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!((first.width, first.height), (16, 4));
+
+        let narrower = cache.get_or_convert(&source, 8).unwrap();
+        assert_eq!(narrower.width, 8);
+        assert!(decode_logo("não é base64").is_err());
+    }
+}
@@ -0,0 +1,979 @@
+//! Modelos de Cupom Configuráveis
+//!
+//! Cada loja define o próprio layout do cupom de venda, da ordem de serviço e
+//! do pedido do atendente: cabeçalho, logo, mensagens de rodapé, campos
+//! exibidos e tamanho da fonte. O modelo é um JSON gravado nas configurações
+//! (`hardware.printer.template.*`) e renderizado sobre os mesmos dados de
+//! `Receipt`, `ServiceOrderReceipt` e `AttendantOrderReceipt`, usando as
+//! primitivas do `ThermalPrinter` (`align`, `style`, `separator`,
+//! `barcode`, `qrcode`).
+//!
+//! ## Marcadores
+//!
+//! Os textos aceitam `{{campo}}` com o nome em camelCase do dado
+//! (`{{companyName}}`, `{{saleNumber|pad6}}`). Filtros:
+//! - `money` - duas casas decimais
+//! - `qty` - três casas decimais
+//! - `upper` - caixa alta
+//! - `padN` - completa com zeros à esquerda até N dígitos
+//! - `maxN` - corta em N caracteres
+//!
+//! Dentro de um bloco `items` ficam disponíveis os campos do item (`code`,
+//! `name`, `quantity`, `unit`, `unitPrice`, `total`, `discount`,
+//! `discountReason`) e `index` (1, 2, ...).
+
+use super::barcode::BarcodeSymbology;
+use super::printer::{escpos, TextAlign, TextStyle, ThermalPrinter};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Logo gravado na memória da impressora (NV graphics `FS p 1 0`)
+const NV_LOGO: [u8; 4] = [escpos::FS, b'p', 0x01, 0x00];
+
+/// Limite de linhas de um bloco `feed`
+const MAX_FEED_LINES: u8 = 10;
+
+// ════════════════════════════════════════════════════════════════════════════
+// TIPOS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Documento impresso que aceita modelo próprio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateDocument {
+    Receipt,
+    ServiceOrder,
+    AttendantOrder,
+}
+
+/// Alinhamento de um bloco de texto
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Estilo (negrito, sublinhado e tamanho da fonte) de um bloco de texto
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TemplateStyle {
+    pub bold: bool,
+    pub underline: bool,
+    pub double_height: bool,
+    pub double_width: bool,
+}
+
+/// Bloco do modelo
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TemplateBlock {
+    /// Linha de texto; `right` é encostado na margem direita da mesma linha
+    Text {
+        text: String,
+        #[serde(default)]
+        right: Option<String>,
+        #[serde(default)]
+        align: TemplateAlign,
+        #[serde(default)]
+        style: TemplateStyle,
+    },
+    /// Linha preenchida com o caractere
+    Separator {
+        #[serde(default = "default_separator")]
+        char: char,
+    },
+    /// Linhas em branco
+    Feed {
+        #[serde(default = "default_feed")]
+        lines: u8,
+    },
+    /// Blocos impressos apenas quando o campo estiver preenchido
+    If {
+        field: String,
+        blocks: Vec<TemplateBlock>,
+    },
+    /// Blocos repetidos para cada item
+    Items { blocks: Vec<TemplateBlock> },
+    /// Código de barras (EAN-13 por padrão)
+    Barcode {
+        value: String,
+        #[serde(default)]
+        symbology: BarcodeSymbology,
+    },
+    /// QR Code
+    QrCode { value: String },
+    /// Logo da loja (raster configurado ou, na falta dele, o gravado na
+    /// memória da impressora)
+    Logo,
+}
+
+fn default_separator() -> char {
+    '-'
+}
+
+fn default_feed() -> u8 {
+    1
+}
+
+/// Modelo de impressão de um documento
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptTemplate {
+    pub blocks: Vec<TemplateBlock>,
+}
+
+/// Modelo vigente de um documento
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptTemplateSetting {
+    pub document: TemplateDocument,
+    /// Falso quando a loja usa o layout padrão (o modelo devolvido é o
+    /// equivalente embutido, ponto de partida para personalizar)
+    pub custom: bool,
+    pub template: ReceiptTemplate,
+}
+
+/// Operação de impressão resultante do modelo
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateOp {
+    Line {
+        text: String,
+        align: TemplateAlign,
+        style: TemplateStyle,
+    },
+    Separator(char),
+    Feed(u8),
+    Barcode(BarcodeSymbology, String),
+    QrCode(String),
+    Logo,
+}
+
+impl TemplateDocument {
+    /// Chave da configuração onde o modelo é gravado
+    pub fn setting_key(self) -> &'static str {
+        match self {
+            Self::Receipt => "hardware.printer.template.receipt",
+            Self::ServiceOrder => "hardware.printer.template.service_order",
+            Self::AttendantOrder => "hardware.printer.template.attendant_order",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Receipt => "cupom de venda",
+            Self::ServiceOrder => "ordem de serviço",
+            Self::AttendantOrder => "pedido do atendente",
+        }
+    }
+
+    /// Modelo equivalente ao layout padrão do documento
+    pub fn default_template(self) -> ReceiptTemplate {
+        let json = match self {
+            Self::Receipt => DEFAULT_RECEIPT,
+            Self::ServiceOrder => DEFAULT_SERVICE_ORDER,
+            Self::AttendantOrder => DEFAULT_ATTENDANT_ORDER,
+        };
+        serde_json::from_str(json).expect("modelo padrão inválido")
+    }
+
+    /// Dados fictícios para pré-visualização do modelo
+    pub fn sample_data(self) -> Value {
+        let items = serde_json::json!([
+            { "code": "7891000100103", "name": "ARROZ TIPO 1 5KG", "quantity": 2.0, "unit": "UN", "unitPrice": 27.9, "total": 55.8, "discount": 0.0 },
+            { "code": "2000001", "name": "BANANA PRATA", "quantity": 1.235, "unit": "KG", "unitPrice": 6.99, "total": 8.63, "discount": 0.0 }
+        ]);
+        match self {
+            Self::Receipt => serde_json::json!({
+                "companyName": "MERCEARIA EXEMPLO",
+                "companyAddress": "Rua das Flores, 123 - Centro",
+                "companyCnpj": "00.000.000/0001-00",
+                "companyPhone": "(11) 3333-4444",
+                "saleNumber": 42,
+                "operatorName": "Maria",
+                "dateTime": "18/10/2026 14:30",
+                "customerName": "João da Silva",
+                "customerDocument": "123.456.789-09",
+                "items": items,
+                "subtotal": 64.43,
+                "discount": 0.0,
+                "total": 64.43,
+                "paymentMethod": "DINHEIRO",
+                "amountPaid": 70.0,
+                "change": 5.57
+            }),
+            Self::ServiceOrder => serde_json::json!({
+                "companyName": "OFICINA EXEMPLO",
+                "companyAddress": "Av. Brasil, 1000",
+                "companyCnpj": "00.000.000/0001-00",
+                "companyPhone": "(11) 3333-4444",
+                "orderNumber": 40,
+                "dateTime": "18/10/2026 09:15",
+                "status": "Em andamento",
+                "mechanicName": "Carlos",
+                "customerName": "João da Silva",
+                "customerPhone": "(11) 98888-7777",
+                "vehicleDisplayName": "Honda CG 160",
+                "vehiclePlate": "ABC1D23",
+                "vehicleKm": 35200,
+                "symptoms": "Barulho na corrente",
+                "items": items,
+                "laborCost": 80.0,
+                "partsCost": 64.43,
+                "discount": 0.0,
+                "total": 144.43,
+                "warrantyDays": 90,
+                "notes": null
+            }),
+            Self::AttendantOrder => serde_json::json!({
+                "companyName": "MERCEARIA EXEMPLO",
+                "companyAddress": "Rua das Flores, 123 - Centro",
+                "companyPhone": "(11) 3333-4444",
+                "orderNumber": "A-017",
+                "dateTime": "18/10/2026 14:30",
+                "attendantName": "Pedro",
+                "customerName": null,
+                "customerPhone": null,
+                "items": items,
+                "subtotal": 64.43,
+                "discount": 0.0,
+                "total": 64.43,
+                "notes": null
+            }),
+        }
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// RENDERIZAÇÃO
+// ════════════════════════════════════════════════════════════════════════════
+
+impl ReceiptTemplate {
+    /// Confere filtros e limites antes de gravar o modelo
+    pub fn validate(&self) -> Result<(), String> {
+        if self.blocks.is_empty() {
+            return Err("O modelo não possui blocos".into());
+        }
+        validate_blocks(&self.blocks)
+    }
+
+    /// Resolve os marcadores contra os dados do documento
+    pub fn render(&self, data: &Value, width: usize) -> Vec<TemplateOp> {
+        let mut ops = Vec::new();
+        render_blocks(&self.blocks, &[data], width, &mut ops);
+        ops
+    }
+
+    /// Texto aproximado do documento impresso, para o editor de modelos
+    pub fn preview(&self, data: &Value, width: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        for op in self.render(data, width) {
+            match op {
+                TemplateOp::Line { text, align, style } => {
+                    let columns = columns_for(width, style);
+                    let text: String = text.chars().take(columns).collect();
+                    let pad = columns.saturating_sub(text.chars().count());
+                    let line = match align {
+                        TemplateAlign::Left => text,
+                        TemplateAlign::Center => format!("{}{}", " ".repeat(pad / 2), text),
+                        TemplateAlign::Right => format!("{}{}", " ".repeat(pad), text),
+                    };
+                    lines.push(line);
+                }
+                TemplateOp::Separator(c) => lines.push(c.to_string().repeat(width)),
+                TemplateOp::Feed(n) => lines.extend((0..n).map(|_| String::new())),
+                TemplateOp::Barcode(symbology, code) => {
+                    lines.push(format!("[{} {}]", symbology.label(), code))
+                }
+                TemplateOp::QrCode(_) => lines.push("[QR CODE]".into()),
+                TemplateOp::Logo => lines.push("[LOGO]".into()),
+            }
+        }
+        lines
+    }
+}
+
+impl ThermalPrinter {
+    /// Imprime o documento seguindo um modelo configurado pela loja
+    pub fn print_template<T: Serialize>(
+        &mut self,
+        template: &ReceiptTemplate,
+        data: &T,
+    ) -> &mut Self {
+        self.init();
+        let data = serde_json::to_value(data).unwrap_or(Value::Null);
+        let width = self.config().paper_width as usize;
+
+        let mut current: Option<(TemplateAlign, TemplateStyle)> = None;
+        for op in template.render(&data, width) {
+            match op {
+                TemplateOp::Line { text, align, style } => {
+                    if current != Some((align, style)) {
+                        self.align(align.into());
+                        self.style(style.into());
+                        current = Some((align, style));
+                    }
+                    self.line(&text);
+                }
+                TemplateOp::Separator(c) => {
+                    self.separator(c);
+                }
+                TemplateOp::Feed(n) => {
+                    self.feed(n);
+                }
+                TemplateOp::Barcode(symbology, code) => {
+                    self.barcode(symbology, &code);
+                }
+                TemplateOp::QrCode(data) => {
+                    self.qrcode(&data);
+                }
+                TemplateOp::Logo => match self.logo() {
+                    Some(logo) => {
+                        self.raster_image(&logo);
+                        current = None;
+                    }
+                    None => {
+                        self.raw(&NV_LOGO);
+                        self.feed(1);
+                    }
+                },
+            }
+        }
+
+        self.style(TextStyle::default());
+        if self.config().auto_cut {
+            self.cut(true);
+        } else {
+            self.feed(4);
+        }
+
+        self
+    }
+}
+
+impl From<TemplateAlign> for TextAlign {
+    fn from(align: TemplateAlign) -> Self {
+        match align {
+            TemplateAlign::Left => TextAlign::Left,
+            TemplateAlign::Center => TextAlign::Center,
+            TemplateAlign::Right => TextAlign::Right,
+        }
+    }
+}
+
+impl From<TemplateStyle> for TextStyle {
+    fn from(style: TemplateStyle) -> Self {
+        TextStyle {
+            bold: style.bold,
+            underline: style.underline,
+            double_height: style.double_height,
+            double_width: style.double_width,
+        }
+    }
+}
+
+/// Colunas disponíveis na linha (a fonte larga ocupa duas colunas)
+fn columns_for(width: usize, style: TemplateStyle) -> usize {
+    if style.double_width {
+        width / 2
+    } else {
+        width
+    }
+}
+
+fn render_blocks(
+    blocks: &[TemplateBlock],
+    scope: &[&Value],
+    width: usize,
+    ops: &mut Vec<TemplateOp>,
+) {
+    for block in blocks {
+        match block {
+            TemplateBlock::Text {
+                text,
+                right,
+                align,
+                style,
+            } => {
+                let left = interpolate(text, scope);
+                let Some(right) = right else {
+                    for line in left.lines() {
+                        ops.push(TemplateOp::Line {
+                            text: line.to_string(),
+                            align: *align,
+                            style: *style,
+                        });
+                    }
+                    continue;
+                };
+
+                let right = interpolate(right, scope);
+                let columns = columns_for(width, *style);
+                let used = left.chars().count() + right.chars().count();
+                if used < columns {
+                    ops.push(TemplateOp::Line {
+                        text: format!("{}{}{}", left, " ".repeat(columns - used), right),
+                        align: TemplateAlign::Left,
+                        style: *style,
+                    });
+                } else {
+                    // Não cabe na mesma linha: o valor desce alinhado à direita
+                    ops.push(TemplateOp::Line {
+                        text: left,
+                        align: TemplateAlign::Left,
+                        style: *style,
+                    });
+                    ops.push(TemplateOp::Line {
+                        text: right,
+                        align: TemplateAlign::Right,
+                        style: *style,
+                    });
+                }
+            }
+            TemplateBlock::Separator { char } => ops.push(TemplateOp::Separator(*char)),
+            TemplateBlock::Feed { lines } => ops.push(TemplateOp::Feed(*lines)),
+            TemplateBlock::If { field, blocks } => {
+                if lookup(scope, field).is_some_and(is_truthy) {
+                    render_blocks(blocks, scope, width, ops);
+                }
+            }
+            TemplateBlock::Items { blocks } => {
+                let Some(Value::Array(items)) = lookup(scope, "items") else {
+                    continue;
+                };
+                for (i, item) in items.iter().enumerate() {
+                    let mut item = item.clone();
+                    if let Value::Object(map) = &mut item {
+                        map.insert("index".into(), Value::from(i + 1));
+                    }
+                    let mut inner = scope.to_vec();
+                    inner.push(&item);
+                    render_blocks(blocks, &inner, width, ops);
+                }
+            }
+            TemplateBlock::Barcode { value, symbology } => {
+                let code = interpolate(value, scope);
+                if !code.is_empty() {
+                    ops.push(TemplateOp::Barcode(*symbology, code));
+                }
+            }
+            TemplateBlock::QrCode { value } => {
+                let data = interpolate(value, scope);
+                if !data.is_empty() {
+                    ops.push(TemplateOp::QrCode(data));
+                }
+            }
+            TemplateBlock::Logo => ops.push(TemplateOp::Logo),
+        }
+    }
+}
+
+fn validate_blocks(blocks: &[TemplateBlock]) -> Result<(), String> {
+    for block in blocks {
+        match block {
+            TemplateBlock::Text { text, right, .. } => {
+                validate_text(text)?;
+                if let Some(right) = right {
+                    validate_text(right)?;
+                }
+            }
+            TemplateBlock::Feed { lines } if *lines > MAX_FEED_LINES => {
+                return Err(format!("Avanço máximo de {} linhas", MAX_FEED_LINES));
+            }
+            TemplateBlock::If { field, blocks } => {
+                if field.trim().is_empty() {
+                    return Err("Bloco condicional sem campo".into());
+                }
+                validate_blocks(blocks)?;
+            }
+            TemplateBlock::Items { blocks } => validate_blocks(blocks)?,
+            TemplateBlock::Barcode { value, .. } | TemplateBlock::QrCode { value } => {
+                validate_text(value)?
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn validate_text(text: &str) -> Result<(), String> {
+    for (field, filters) in placeholders(text)? {
+        if field.is_empty() {
+            return Err(format!("Marcador vazio em \"{}\"", text));
+        }
+        for filter in filters {
+            if Filter::parse(filter).is_none() {
+                return Err(format!(
+                    "Filtro desconhecido \"{}\" em \"{}\"",
+                    filter, text
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Marcadores `{{campo|filtro}}` do texto
+fn placeholders(text: &str) -> Result<Vec<(&str, Vec<&str>)>, String> {
+    let mut found = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| format!("Marcador sem fechamento em \"{}\"", text))?;
+        let mut parts = after[..end].split('|').map(str::trim);
+        let field = parts.next().unwrap_or_default();
+        found.push((field, parts.collect()));
+        rest = &after[end + 2..];
+    }
+    Ok(found)
+}
+
+/// Substitui os marcadores pelos valores formatados
+fn interpolate(text: &str, scope: &[&Value]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+
+        let mut parts = after[..end].split('|').map(str::trim);
+        let field = parts.next().unwrap_or_default();
+        let value = lookup(scope, field).unwrap_or(&Value::Null);
+        let formatted = parts
+            .filter_map(Filter::parse)
+            .fold(format_value(value), |acc, filter| filter.apply(value, acc));
+        out.push_str(&formatted);
+
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Busca o campo do escopo mais interno (item) para o mais externo (documento)
+fn lookup<'a>(scope: &[&'a Value], field: &str) -> Option<&'a Value> {
+    scope
+        .iter()
+        .rev()
+        .find_map(|frame| frame.get(field).filter(|v| !v.is_null()))
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.trim().is_empty(),
+        Value::Array(a) => !a.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(true) => "Sim".into(),
+        Value::Bool(false) => "Não".into(),
+        Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => i.to_string(),
+            (None, Some(f)) if f.fract() == 0.0 => format!("{:.0}", f),
+            _ => n.to_string(),
+        },
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Filtro aplicado a um marcador
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Filter {
+    Money,
+    Qty,
+    Upper,
+    Pad(usize),
+    Max(usize),
+}
+
+impl Filter {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "money" => Some(Self::Money),
+            "qty" => Some(Self::Qty),
+            "upper" => Some(Self::Upper),
+            _ => {
+                if let Some(n) = name.strip_prefix("pad") {
+                    n.parse().ok().map(Self::Pad)
+                } else if let Some(n) = name.strip_prefix("max") {
+                    n.parse().ok().map(Self::Max)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn apply(self, value: &Value, text: String) -> String {
+        match self {
+            Self::Money => value.as_f64().map_or(text, |v| format!("{:.2}", v)),
+            Self::Qty => value.as_f64().map_or(text, |v| format!("{:.3}", v)),
+            Self::Upper => text.to_uppercase(),
+            Self::Pad(width) => format!("{:0>width$}", text, width = width),
+            Self::Max(n) => text.chars().take(n).collect(),
+        }
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// MODELOS PADRÃO
+// ════════════════════════════════════════════════════════════════════════════
+
+const DEFAULT_RECEIPT: &str = r#"{
+  "blocks": [
+    { "type": "text", "text": "{{companyName}}", "align": "center", "style": { "bold": true, "doubleHeight": true, "doubleWidth": true } },
+    { "type": "if", "field": "companyCnpj", "blocks": [
+      { "type": "text", "text": "CNPJ: {{companyCnpj}}", "align": "center" }
+    ] },
+    { "type": "text", "text": "{{companyAddress}}", "align": "center" },
+    { "type": "if", "field": "companyPhone", "blocks": [
+      { "type": "text", "text": "Tel: {{companyPhone}}", "align": "center" }
+    ] },
+    { "type": "feed" },
+    { "type": "separator", "char": "=" },
+    { "type": "text", "text": "CUPOM NÃO FISCAL", "align": "center", "style": { "bold": true } },
+    { "type": "separator", "char": "=" },
+    { "type": "text", "text": "VENDA: #{{saleNumber|pad6}}" },
+    { "type": "text", "text": "DATA:  {{dateTime}}" },
+    { "type": "text", "text": "OPER:  {{operatorName}}" },
+    { "type": "if", "field": "customerName", "blocks": [
+      { "type": "text", "text": "CLIENTE: {{customerName}}" }
+    ] },
+    { "type": "if", "field": "customerDocument", "blocks": [
+      { "type": "text", "text": "CPF/CNPJ: {{customerDocument}}" }
+    ] },
+    { "type": "separator", "char": "-" },
+    { "type": "text", "text": "ITEM   DESC   QTD   UN   VL.UNIT   TOTAL", "style": { "bold": true } },
+    { "type": "separator", "char": "-" },
+    { "type": "items", "blocks": [
+      { "type": "text", "text": "{{index|pad3}} {{name|max30}}" },
+      { "type": "text", "text": "      {{quantity|qty}}{{unit}} x R$ {{unitPrice|money}}", "right": "R$ {{total|money}}" },
+      { "type": "if", "field": "discount", "blocks": [
+        { "type": "text", "text": "      {{discountReason|max24}}", "right": "-R$ {{discount|money}}" }
+      ] }
+    ] },
+    { "type": "separator", "char": "-" },
+    { "type": "text", "text": "SUBTOTAL: R$ {{subtotal|money}}", "align": "right" },
+    { "type": "if", "field": "discount", "blocks": [
+      { "type": "text", "text": "DESCONTO: -R$ {{discount|money}}", "align": "right" }
+    ] },
+    { "type": "feed" },
+    { "type": "text", "text": "TOTAL: R$ {{total|money}}", "align": "right", "style": { "bold": true, "doubleHeight": true, "doubleWidth": true } },
+    { "type": "separator", "char": "=" },
+    { "type": "text", "text": "FORMA PGTO: {{paymentMethod}}" },
+    { "type": "text", "text": "VALOR PAGO: R$ {{amountPaid|money}}" },
+    { "type": "if", "field": "change", "blocks": [
+      { "type": "text", "text": "TROCO: R$ {{change|money}}", "style": { "bold": true, "doubleHeight": true } }
+    ] },
+    { "type": "feed", "lines": 2 },
+    { "type": "text", "text": "Obrigado pela preferência!", "align": "center" },
+    { "type": "text", "text": "Volte sempre!", "align": "center" },
+    { "type": "feed" },
+    { "type": "qrCode", "value": "SALE:{{saleNumber|pad6}}:{{dateTime}}" }
+  ]
+}"#;
+
+const DEFAULT_SERVICE_ORDER: &str = r#"{
+  "blocks": [
+    { "type": "text", "text": "{{companyName}}", "align": "center", "style": { "bold": true, "doubleHeight": true, "doubleWidth": true } },
+    { "type": "if", "field": "companyCnpj", "blocks": [
+      { "type": "text", "text": "CNPJ: {{companyCnpj}}", "align": "center" }
+    ] },
+    { "type": "text", "text": "{{companyAddress}}", "align": "center" },
+    { "type": "if", "field": "companyPhone", "blocks": [
+      { "type": "text", "text": "TEL: {{companyPhone}}", "align": "center" }
+    ] },
+    { "type": "feed" },
+    { "type": "separator", "char": "=" },
+    { "type": "text", "text": "ORDEM DE SERVIÇO", "align": "center", "style": { "bold": true, "doubleHeight": true } },
+    { "type": "separator", "char": "=" },
+    { "type": "text", "text": "OS NÚMERO: #{{orderNumber|pad6}}", "style": { "bold": true } },
+    { "type": "text", "text": "STATUS: {{status|upper}}" },
+    { "type": "text", "text": "DATA:   {{dateTime}}" },
+    { "type": "text", "text": "MECÂNICO: {{mechanicName}}" },
+    { "type": "separator", "char": "-" },
+    { "type": "text", "text": "DADOS DO CLIENTE / VEÍCULO", "style": { "bold": true } },
+    { "type": "text", "text": "CLIENTE: {{customerName}}" },
+    { "type": "if", "field": "customerPhone", "blocks": [
+      { "type": "text", "text": "FONE:    {{customerPhone}}" }
+    ] },
+    { "type": "text", "text": "VEÍCULO: {{vehicleDisplayName}}" },
+    { "type": "if", "field": "vehiclePlate", "blocks": [
+      { "type": "text", "text": "PLACA:   {{vehiclePlate}}" }
+    ] },
+    { "type": "if", "field": "vehicleKm", "blocks": [
+      { "type": "text", "text": "KM:      {{vehicleKm}}" }
+    ] },
+    { "type": "if", "field": "warrantyDays", "blocks": [
+      { "type": "feed" },
+      { "type": "text", "text": "GARANTIA: {{warrantyDays}} DIAS", "align": "center", "style": { "bold": true, "underline": true } }
+    ] },
+    { "type": "if", "field": "symptoms", "blocks": [
+      { "type": "feed" },
+      { "type": "text", "text": "SINTOMAS / RELATO:" },
+      { "type": "text", "text": "{{symptoms}}" }
+    ] },
+    { "type": "separator", "char": "-" },
+    { "type": "text", "text": "PEÇAS E SERVIÇOS", "style": { "bold": true } },
+    { "type": "separator", "char": "-" },
+    { "type": "items", "blocks": [
+      { "type": "text", "text": "{{name|max30}}" },
+      { "type": "text", "text": "  {{quantity|money}}{{unit}} x R$ {{unitPrice|money}}", "right": "R$ {{total|money}}" }
+    ] },
+    { "type": "separator", "char": "-" },
+    { "type": "if", "field": "laborCost", "blocks": [
+      { "type": "text", "text": "MÃO DE OBRA: R$ {{laborCost|money}}", "align": "right" }
+    ] },
+    { "type": "if", "field": "partsCost", "blocks": [
+      { "type": "text", "text": "PEÇAS:       R$ {{partsCost|money}}", "align": "right" }
+    ] },
+    { "type": "if", "field": "discount", "blocks": [
+      { "type": "text", "text": "DESCONTO:   -R$ {{discount|money}}", "align": "right", "style": { "bold": true } }
+    ] },
+    { "type": "feed" },
+    { "type": "text", "text": "TOTAL: R$ {{total|money}}", "align": "right", "style": { "bold": true, "doubleHeight": true, "doubleWidth": true } },
+    { "type": "separator", "char": "=" },
+    { "type": "if", "field": "notes", "blocks": [
+      { "type": "text", "text": "OBSERVAÇÕES:" },
+      { "type": "text", "text": "{{notes}}" },
+      { "type": "feed" }
+    ] },
+    { "type": "feed", "lines": 2 },
+    { "type": "text", "text": "________________________", "align": "center" },
+    { "type": "text", "text": "ASSINATURA DO CLIENTE", "align": "center" },
+    { "type": "feed" },
+    { "type": "text", "text": "Obrigado pela confiança!", "align": "center" },
+    { "type": "qrCode", "value": "OS:{{orderNumber|pad6}}:{{dateTime}}" }
+  ]
+}"#;
+
+const DEFAULT_ATTENDANT_ORDER: &str = r#"{
+  "blocks": [
+    { "type": "text", "text": "{{companyName}}", "align": "center", "style": { "bold": true, "doubleHeight": true, "doubleWidth": true } },
+    { "type": "text", "text": "{{companyAddress}}", "align": "center" },
+    { "type": "if", "field": "companyPhone", "blocks": [
+      { "type": "text", "text": "TEL: {{companyPhone}}", "align": "center" }
+    ] },
+    { "type": "feed" },
+    { "type": "separator", "char": "=" },
+    { "type": "text", "text": "*** PEDIDO ***", "align": "center", "style": { "bold": true, "doubleHeight": true } },
+    { "type": "separator", "char": "=" },
+    { "type": "text", "text": "PEDIDO: #{{orderNumber}}", "style": { "bold": true } },
+    { "type": "text", "text": "{{dateTime}}" },
+    { "type": "text", "text": "ATENDENTE: {{attendantName}}" },
+    { "type": "if", "field": "customerName", "blocks": [
+      { "type": "text", "text": "CLIENTE: {{customerName}}" }
+    ] },
+    { "type": "if", "field": "customerPhone", "blocks": [
+      { "type": "text", "text": "TELEFONE: {{customerPhone}}" }
+    ] },
+    { "type": "separator", "char": "-" },
+    { "type": "text", "text": "ITENS DO PEDIDO", "style": { "bold": true } },
+    { "type": "separator", "char": "-" },
+    { "type": "items", "blocks": [
+      { "type": "text", "text": "{{index|pad2}}. {{name|max28}}" },
+      { "type": "text", "text": "    {{quantity|money}}{{unit}} x R$ {{unitPrice|money}}", "right": "R$ {{total|money}}" }
+    ] },
+    { "type": "separator", "char": "-" },
+    { "type": "text", "text": "SUBTOTAL: R$ {{subtotal|money}}", "align": "right" },
+    { "type": "if", "field": "discount", "blocks": [
+      { "type": "text", "text": "DESCONTO: -R$ {{discount|money}}", "align": "right", "style": { "bold": true } }
+    ] },
+    { "type": "feed" },
+    { "type": "text", "text": "TOTAL: R$ {{total|money}}", "align": "right", "style": { "bold": true, "doubleHeight": true, "doubleWidth": true } },
+    { "type": "separator", "char": "=" },
+    { "type": "feed" },
+    { "type": "text", "text": ">>> APRESENTAR NO CAIXA <<<", "align": "center", "style": { "bold": true } },
+    { "type": "feed" },
+    { "type": "if", "field": "notes", "blocks": [
+      { "type": "text", "text": "OBS:" },
+      { "type": "text", "text": "{{notes}}" },
+      { "type": "feed" }
+    ] },
+    { "type": "qrCode", "value": "PEDIDO:{{orderNumber}}" },
+    { "type": "feed" },
+    { "type": "text", "text": "Obrigado pela preferência!", "align": "center" }
+  ]
+}"#;
+
+// ════════════════════════════════════════════════════════════════════════════
+// TESTES
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::printer::PrinterConfig;
+    use serde_json::json;
+
+    fn text(text: &str) -> TemplateBlock {
+        TemplateBlock::Text {
+            text: text.into(),
+            right: None,
+            align: TemplateAlign::Left,
+            style: TemplateStyle::default(),
+        }
+    }
+
+    fn lines(ops: &[TemplateOp]) -> Vec<&str> {
+        ops.iter()
+            .filter_map(|op| match op {
+                TemplateOp::Line { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_default_templates_are_valid() {
+        for document in [
+            TemplateDocument::Receipt,
+            TemplateDocument::ServiceOrder,
+            TemplateDocument::AttendantOrder,
+        ] {
+            let template = document.default_template();
+            assert!(template.validate().is_ok(), "{:?}", document);
+            assert!(!template.preview(&document.sample_data(), 48).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_placeholders_and_filters() {
+        let data = json!({ "saleNumber": 42, "total": 12.5, "operatorName": "maria", "name": "ARROZ TIPO 1" });
+        let ops = ReceiptTemplate {
+            blocks: vec![
+                text("VENDA #{{saleNumber|pad6}}"),
+                text("TOTAL R$ {{ total | money }}"),
+                text("{{operatorName|upper}} {{name|max5}} {{missing}}."),
+            ],
+        }
+        .render(&data, 48);
+
+        assert_eq!(
+            lines(&ops),
+            vec!["VENDA #000042", "TOTAL R$ 12.50", "MARIA ARROZ ."]
+        );
+    }
+
+    #[test]
+    fn test_conditional_hides_empty_fields() {
+        let template = ReceiptTemplate {
+            blocks: vec![TemplateBlock::If {
+                field: "customerDocument".into(),
+                blocks: vec![text("CPF: {{customerDocument}}")],
+            }],
+        };
+
+        assert!(template
+            .render(&json!({ "customerDocument": null }), 48)
+            .is_empty());
+        assert!(template
+            .render(&json!({ "customerDocument": "" }), 48)
+            .is_empty());
+        assert_eq!(
+            lines(&template.render(&json!({ "customerDocument": "123" }), 48)),
+            vec!["CPF: 123"]
+        );
+    }
+
+    #[test]
+    fn test_items_and_right_column() {
+        let data = json!({
+            "companyName": "LOJA",
+            "items": [
+                { "name": "A", "total": 1.0 },
+                { "name": "B", "total": 20.0 }
+            ]
+        });
+        let template = ReceiptTemplate {
+            blocks: vec![TemplateBlock::Items {
+                blocks: vec![TemplateBlock::Text {
+                    text: "{{index}} {{name}} {{companyName}}".into(),
+                    right: Some("{{total|money}}".into()),
+                    align: TemplateAlign::Left,
+                    style: TemplateStyle::default(),
+                }],
+            }],
+        };
+
+        let ops = template.render(&data, 20);
+        assert_eq!(
+            lines(&ops),
+            vec!["1 A LOJA        1.00", "2 B LOJA       20.00"]
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_filter() {
+        let template = ReceiptTemplate {
+            blocks: vec![text("{{total|moeda}}")],
+        };
+        assert!(template.validate().is_err());
+
+        let template = ReceiptTemplate {
+            blocks: vec![text("{{total")],
+        };
+        assert!(template.validate().is_err());
+    }
+
+    #[test]
+    fn test_template_json_format() {
+        let template: ReceiptTemplate = serde_json::from_str(
+            r#"{ "blocks": [
+                { "type": "logo" },
+                { "type": "text", "text": "{{companyName}}", "align": "center", "style": { "doubleWidth": true } },
+                { "type": "separator" },
+                { "type": "qrCode", "value": "{{saleNumber}}" }
+            ] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(template.blocks[0], TemplateBlock::Logo);
+        assert_eq!(template.blocks[2], TemplateBlock::Separator { char: '-' });
+    }
+
+    #[test]
+    fn test_print_template_uses_printer_primitives() {
+        let mut printer = ThermalPrinter::new(PrinterConfig {
+            auto_cut: true,
+            ..Default::default()
+        });
+        let template = ReceiptTemplate {
+            blocks: vec![
+                TemplateBlock::Logo,
+                text("{{companyName}}"),
+                TemplateBlock::QrCode {
+                    value: "SALE:{{saleNumber}}".into(),
+                },
+            ],
+        };
+
+        printer.print_template(
+            &template,
+            &json!({ "companyName": "LOJA", "saleNumber": 7 }),
+        );
+        let buffer = printer.get_buffer();
+
+        assert_eq!(&buffer[0..2], &escpos::INIT);
+        assert!(buffer.windows(NV_LOGO.len()).any(|w| w == NV_LOGO));
+        assert!(buffer.windows(4).any(|w| w == b"LOJA"));
+        assert!(buffer.windows(6).any(|w| w == b"SALE:7"));
+        assert!(buffer.ends_with(&escpos::CUT_PARTIAL));
+    }
+}
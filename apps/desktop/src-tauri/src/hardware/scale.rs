@@ -6,11 +6,27 @@
 //! - Elgin DP, SM100
 //! - Urano US15, US20
 //! - Genéricas
+//!
+//! A leitura pode ser avulsa (`read_weight`) ou contínua (`stream`): no modo
+//! contínuo a porta fica aberta e cada quadro recebido vira um `ScaleReading`
+//! — por consulta (ENQ) ou com a balança transmitindo sozinha (Toledo Prix em
+//! modo contínuo). Balanças computadoras recebem o preço por kg do PDV.
 
 use super::{HardwareError, HardwareResult};
 use serde::{Deserialize, Serialize};
 use std::io::{Read, Write};
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+/// Intervalo entre consultas (ENQ) no modo contínuo por consulta
+const STREAM_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Sem quadros válidos por esse tempo, a conexão é considerada perdida
+const STREAM_SILENCE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Limite do buffer de recepção sem encontrar um quadro
+const STREAM_BUFFER_LIMIT: usize = 256;
 
 // ════════════════════════════════════════════════════════════════════════════
 // TIPOS
@@ -38,6 +54,12 @@ pub struct ScaleConfig {
     pub parity: String, // none, odd, even
     pub stop_bits: u8,  // 1 ou 2
     pub mock_mode: bool,
+    /// A balança transmite o peso sem consulta (ex.: Toledo Prix modo contínuo)
+    #[serde(default)]
+    pub continuous: bool,
+    /// Balança computadora: recebe o preço por kg e calcula o total
+    #[serde(default)]
+    pub price_computing: bool,
 }
 
 impl Default for ScaleConfig {
@@ -51,12 +73,14 @@ impl Default for ScaleConfig {
             parity: "none".to_string(),
             stop_bits: 1,
             mock_mode: false,
+            continuous: false,
+            price_computing: false,
         }
     }
 }
 
 /// Resposta de leitura da balança
-#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct ScaleReading {
     /// Peso em quilogramas
@@ -69,6 +93,15 @@ pub struct ScaleReading {
     pub overload: bool,
     /// Se o peso é negativo (tara)
     pub negative: bool,
+    /// Tara ativa (peso líquido)
+    #[serde(default)]
+    pub tare: bool,
+    /// Preço por kg na balança computadora
+    #[serde(default)]
+    pub unit_price: Option<f64>,
+    /// Total calculado pela balança computadora
+    #[serde(default)]
+    pub total_price: Option<f64>,
     /// Protocolo usado
     pub protocol: ScaleProtocol,
 }
@@ -81,9 +114,24 @@ impl ScaleReading {
             stable,
             overload: false,
             negative: false,
+            tare: false,
+            unit_price: None,
+            total_price: None,
             protocol: ScaleProtocol::Generic,
         }
     }
+
+    /// Peso utilizável na venda (estável, sem sobrecarga e não negativo)
+    pub fn is_usable(&self) -> bool {
+        self.stable && !self.overload && !self.negative
+    }
+}
+
+/// Comandos enviados à balança durante o modo contínuo
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScaleCommand {
+    /// Preço por kg para a balança computadora
+    SetUnitPrice(f64),
 }
 
 // ════════════════════════════════════════════════════════════════════════════
@@ -100,52 +148,28 @@ mod control {
 
 /// Parser para protocolo Toledo
 ///
-/// Formato: STX [6 bytes peso] [status] ETX
+/// Formato: STX [peso 5-6 dígitos] [status] ETX
 /// Exemplo: 0x02 "001234" 0x20 0x03 = 1.234 kg, estável
+///
+/// No lugar do peso a balança envia "IIIII" (instável), "NNNNN" (negativo)
+/// ou "SSSSS" (sobrecarga). Status: 0x10 tara, 0x20 estável, 0x40 negativo,
+/// 0x80 sobrecarga. Nas computadoras o peso vem seguido de mais dois campos
+/// STX/ETX: preço por kg e total, em centavos.
 mod toledo {
     use super::*;
 
     pub fn parse(data: &[u8]) -> HardwareResult<ScaleReading> {
-        // Tamanho mínimo: STX + 6 peso + 1 status + ETX = 9 bytes
-        if data.len() < 9 {
-            return Err(HardwareError::ProtocolError(
-                "Resposta Toledo muito curta".to_string(),
-            ));
-        }
-
-        // Encontrar STX e ETX
-        let start = data.iter().position(|&b| b == control::STX);
-        let end = data.iter().position(|&b| b == control::ETX);
-
-        match (start, end) {
-            (Some(s), Some(e)) if e > s + 6 => {
-                let payload = &data[s + 1..e];
-
-                // 6 bytes de peso (ASCII)
-                let weight_str = String::from_utf8_lossy(&payload[0..6]).trim().to_string();
-
-                let weight_grams: u32 = weight_str.parse().unwrap_or(0);
-
-                // Status byte
-                let status = if payload.len() > 6 { payload[6] } else { 0x20 };
-
-                let stable = (status & 0x20) != 0 || status == 0x20;
-                let negative = (status & 0x40) != 0;
-                let overload = (status & 0x80) != 0;
-
-                Ok(ScaleReading {
-                    weight_kg: weight_grams as f64 / 1000.0,
-                    weight_grams,
-                    stable,
-                    negative,
-                    overload,
-                    protocol: ScaleProtocol::Toledo,
-                })
-            }
-            _ => Err(HardwareError::ProtocolError(
-                "Formato Toledo inválido".to_string(),
-            )),
+        let fields = stx_fields(data);
+        let mut reading = fields
+            .first()
+            .and_then(|field| parse_weight_field(field, ScaleProtocol::Toledo))
+            .ok_or_else(|| HardwareError::ProtocolError("Formato Toledo inválido".to_string()))?;
+
+        if let [_, unit, total, ..] = fields.as_slice() {
+            reading.unit_price = parse_cents(unit);
+            reading.total_price = parse_cents(total);
         }
+        Ok(reading)
     }
 
     pub fn command_read() -> &'static [u8] {
@@ -157,6 +181,9 @@ mod toledo {
 ///
 /// Formato: [sinal][5 bytes peso inteiro][1 byte decimal][status]CR
 /// Exemplo: "+00123401" = 1.234 kg (1234 * 0.001)
+///
+/// Estados como na Toledo: "IIIII" instável, "NNNNN" negativo e "SSSSS"
+/// sobrecarga; "T" após o peso indica tara ativa.
 mod filizola {
     use super::*;
 
@@ -168,6 +195,10 @@ mod filizola {
             .copied()
             .collect();
 
+        if let Some(reading) = parse_state_field(&clean, ScaleProtocol::Filizola) {
+            return Ok(reading);
+        }
+
         if clean.len() < 7 {
             return Err(HardwareError::ProtocolError(
                 "Resposta Filizola muito curta".to_string(),
@@ -199,6 +230,9 @@ mod filizola {
             stable,
             negative,
             overload: false,
+            tare: last_char == 'T',
+            unit_price: None,
+            total_price: None,
             protocol: ScaleProtocol::Filizola,
         })
     }
@@ -228,18 +262,22 @@ mod elgin {
 }
 
 /// Parser para protocolo Urano
+///
+/// Formato: PPPPPP onde P é peso em gramas; "-" indica negativo, "SOBRE"
+/// sobrecarga e "TARA"/"LIQ" peso líquido com tara.
 mod urano {
     use super::*;
 
     pub fn parse(data: &[u8]) -> HardwareResult<ScaleReading> {
-        // Formato: PPPPPP onde P é peso em gramas
+        let text = String::from_utf8_lossy(data).to_uppercase();
+        let overload = text.contains("SOBRE");
         let clean: String = data
             .iter()
             .filter(|&&b| b.is_ascii_digit())
             .map(|&b| b as char)
             .collect();
 
-        if clean.len() < 4 {
+        if clean.len() < 4 && !overload {
             return Err(HardwareError::ProtocolError(
                 "Resposta Urano muito curta".to_string(),
             ));
@@ -250,9 +288,12 @@ mod urano {
         Ok(ScaleReading {
             weight_kg: weight_grams as f64 / 1000.0,
             weight_grams,
-            stable: true,
-            negative: false,
-            overload: false,
+            stable: !overload,
+            negative: text.contains('-'),
+            overload,
+            tare: text.contains("TARA") || text.contains("LIQ"),
+            unit_price: None,
+            total_price: None,
             protocol: ScaleProtocol::Urano,
         })
     }
@@ -262,6 +303,141 @@ mod urano {
     }
 }
 
+/// Campos entre STX e ETX (bytes fora dos delimitadores são ignorados)
+fn stx_fields(data: &[u8]) -> Vec<&[u8]> {
+    let mut fields = Vec::new();
+    let mut rest = data;
+    while let Some(start) = rest.iter().position(|&b| b == control::STX) {
+        let after = &rest[start + 1..];
+        let Some(end) = after.iter().position(|&b| b == control::ETX) else {
+            break;
+        };
+        fields.push(&after[..end]);
+        rest = &after[end + 1..];
+    }
+    fields
+}
+
+/// Campo de peso no padrão Toledo: até 6 dígitos em gramas e o byte de status
+fn parse_weight_field(field: &[u8], protocol: ScaleProtocol) -> Option<ScaleReading> {
+    if let Some(reading) = parse_state_field(field, protocol.clone()) {
+        return Some(reading);
+    }
+
+    let digits = field
+        .iter()
+        .take(6)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    if digits < 5 {
+        return None;
+    }
+    let weight_grams: u32 = std::str::from_utf8(&field[..digits]).ok()?.parse().ok()?;
+    let status = field.get(digits).copied().unwrap_or(0x20);
+
+    Some(ScaleReading {
+        weight_kg: weight_grams as f64 / 1000.0,
+        weight_grams,
+        stable: (status & 0x20) != 0,
+        negative: (status & 0x40) != 0,
+        overload: (status & 0x80) != 0,
+        tare: (status & 0x10) != 0,
+        unit_price: None,
+        total_price: None,
+        protocol,
+    })
+}
+
+/// Estados sinalizados no lugar do peso ("IIIII", "NNNNN", "SSSSS")
+fn parse_state_field(field: &[u8], protocol: ScaleProtocol) -> Option<ScaleReading> {
+    let state = field.iter().copied().find(|b| !b.is_ascii_whitespace())?;
+    if field.iter().filter(|&&b| b == state).count() < 5 {
+        return None;
+    }
+    let (negative, overload) = match state {
+        b'I' => (false, false),
+        b'N' => (true, false),
+        b'S' => (false, true),
+        _ => return None,
+    };
+    Some(ScaleReading {
+        stable: false,
+        negative,
+        overload,
+        protocol,
+        ..ScaleReading::new(0, false)
+    })
+}
+
+/// Valor em centavos (ex.: "002990" = 29,90)
+fn parse_cents(field: &[u8]) -> Option<f64> {
+    if field.is_empty() || !field.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let cents: u64 = std::str::from_utf8(field).ok()?.parse().ok()?;
+    Some(cents as f64 / 100.0)
+}
+
+/// Comando de leitura do protocolo
+fn command_read(protocol: &ScaleProtocol) -> &'static [u8] {
+    match protocol {
+        ScaleProtocol::Toledo => toledo::command_read(),
+        ScaleProtocol::Filizola => filizola::command_read(),
+        ScaleProtocol::Elgin => elgin::command_read(),
+        ScaleProtocol::Urano => urano::command_read(),
+        ScaleProtocol::Generic => &[control::ENQ],
+    }
+}
+
+/// Interpreta a resposta de acordo com o protocolo
+pub fn parse_reading(protocol: &ScaleProtocol, data: &[u8]) -> HardwareResult<ScaleReading> {
+    match protocol {
+        ScaleProtocol::Toledo => toledo::parse(data),
+        ScaleProtocol::Filizola => filizola::parse(data),
+        ScaleProtocol::Elgin => elgin::parse(data),
+        ScaleProtocol::Urano => urano::parse(data),
+        ScaleProtocol::Generic => {
+            // Tenta todos os protocolos
+            toledo::parse(data)
+                .or_else(|_| filizola::parse(data))
+                .or_else(|_| elgin::parse(data))
+                .or_else(|_| urano::parse(data))
+        }
+    }
+}
+
+/// Preço por kg para a balança computadora: STX + 6 dígitos em centavos +
+/// ETX (padrão Toledo, aceito também por Filizola, Elgin e Urano)
+pub fn price_command(unit_price: f64) -> Vec<u8> {
+    let cents = (unit_price.max(0.0) * 100.0).round().min(999_999.0) as u32;
+    let mut cmd = vec![control::STX];
+    cmd.extend_from_slice(format!("{:06}", cents).as_bytes());
+    cmd.push(control::ETX);
+    cmd
+}
+
+/// Separa os quadros completos recebidos no modo contínuo: STX/ETX nos
+/// protocolos Toledo, Filizola e Elgin; fim de linha em Urano e genéricas
+pub fn take_frames(protocol: &ScaleProtocol, buffer: &mut Vec<u8>) -> Vec<Vec<u8>> {
+    let terminator = |b: u8| match protocol {
+        ScaleProtocol::Urano | ScaleProtocol::Generic => b == b'\r' || b == b'\n',
+        _ => b == control::ETX,
+    };
+
+    let mut frames = Vec::new();
+    while let Some(end) = buffer.iter().position(|&b| terminator(b)) {
+        let frame: Vec<u8> = buffer.drain(..=end).collect();
+        if frame.len() > 1 {
+            frames.push(frame);
+        }
+    }
+    if buffer.len() > STREAM_BUFFER_LIMIT {
+        let excess = buffer.len() - STREAM_BUFFER_LIMIT;
+        buffer.drain(..excess);
+    }
+    frames
+}
+
 // ════════════════════════════════════════════════════════════════════════════
 // BALANÇA
 // ════════════════════════════════════════════════════════════════════════════
@@ -282,25 +458,23 @@ impl Scale {
         Ok(Self { config })
     }
 
-    /// Lê o peso da balança
-    pub fn read_weight(&self) -> HardwareResult<ScaleReading> {
-        if self.config.mock_mode {
-            return Ok(ScaleReading {
-                weight_kg: 1.234,
-                weight_grams: 1234,
-                stable: true,
-                overload: false,
-                negative: false,
-                protocol: self.config.protocol.clone(),
-            });
-        }
-        if !self.config.enabled {
-            return Err(HardwareError::NotConfigured(
-                "Balança não habilitada".to_string(),
-            ));
+    /// Leitura simulada do modo mock
+    fn mock_reading(&self, unit_price: Option<f64>) -> ScaleReading {
+        ScaleReading {
+            weight_kg: 1.234,
+            weight_grams: 1234,
+            stable: true,
+            overload: false,
+            negative: false,
+            tare: false,
+            unit_price,
+            total_price: unit_price.map(|p| (p * 1.234 * 100.0).round() / 100.0),
+            protocol: self.config.protocol.clone(),
         }
+    }
 
-        // Abre porta serial com as configurações especificadas
+    /// Abre a porta serial com as configurações especificadas
+    fn open_port(&self, timeout: Duration) -> HardwareResult<Box<dyn serialport::SerialPort>> {
         let mut builder = serialport::new(&self.config.port, self.config.baud_rate);
 
         builder = match self.config.data_bits {
@@ -319,27 +493,36 @@ impl Scale {
             _ => builder.stop_bits(serialport::StopBits::One),
         };
 
-        let mut port = builder
-            .timeout(Duration::from_millis(500))
+        builder
+            .timeout(timeout)
             .open()
-            .map_err(|e| HardwareError::CommunicationError(e.to_string()))?;
-
-        // Envia comando de leitura
-        let cmd = match self.config.protocol {
-            ScaleProtocol::Toledo => toledo::command_read(),
-            ScaleProtocol::Filizola => filizola::command_read(),
-            ScaleProtocol::Elgin => elgin::command_read(),
-            ScaleProtocol::Urano => urano::command_read(),
-            ScaleProtocol::Generic => &[control::ENQ],
-        };
+            .map_err(|e| HardwareError::CommunicationError(e.to_string()))
+    }
+
+    /// Lê o peso da balança
+    pub fn read_weight(&self) -> HardwareResult<ScaleReading> {
+        if self.config.mock_mode {
+            return Ok(self.mock_reading(None));
+        }
+        if !self.config.enabled {
+            return Err(HardwareError::NotConfigured(
+                "Balança não habilitada".to_string(),
+            ));
+        }
+
+        let mut port = self.open_port(Duration::from_millis(500))?;
 
-        port.write_all(cmd).map_err(HardwareError::IoError)?;
+        // Envia comando de leitura (no modo contínuo a balança já transmite)
+        if !self.config.continuous {
+            port.write_all(command_read(&self.config.protocol))
+                .map_err(HardwareError::IoError)?;
+        }
 
         // Aguarda resposta
         std::thread::sleep(Duration::from_millis(100));
 
         // Lê resposta
-        let mut buffer = [0u8; 32];
+        let mut buffer = [0u8; 64];
         let bytes_read = port.read(&mut buffer).map_err(|e| {
             if e.kind() == std::io::ErrorKind::TimedOut {
                 HardwareError::Timeout
@@ -348,22 +531,118 @@ impl Scale {
             }
         })?;
 
-        let data = &buffer[..bytes_read];
-
-        // Parse de acordo com protocolo
-        match self.config.protocol {
-            ScaleProtocol::Toledo => toledo::parse(data),
-            ScaleProtocol::Filizola => filizola::parse(data),
-            ScaleProtocol::Elgin => elgin::parse(data),
-            ScaleProtocol::Urano => urano::parse(data),
-            ScaleProtocol::Generic => {
-                // Tenta todos os protocolos
-                toledo::parse(data)
-                    .or_else(|_| filizola::parse(data))
-                    .or_else(|_| elgin::parse(data))
-                    .or_else(|_| urano::parse(data))
+        let mut data = buffer[..bytes_read].to_vec();
+        if self.config.continuous {
+            // Último quadro completo recebido
+            if let Some(frame) = take_frames(&self.config.protocol, &mut data).pop() {
+                data = frame;
             }
         }
+
+        parse_reading(&self.config.protocol, &data)
+    }
+
+    /// Envia o preço por kg para a balança computadora
+    pub fn send_unit_price(&self, unit_price: f64) -> HardwareResult<()> {
+        if self.config.mock_mode {
+            return Ok(());
+        }
+        if !self.config.enabled {
+            return Err(HardwareError::NotConfigured(
+                "Balança não habilitada".to_string(),
+            ));
+        }
+
+        let mut port = self.open_port(Duration::from_millis(500))?;
+        port.write_all(&price_command(unit_price))
+            .map_err(HardwareError::IoError)?;
+        port.flush().map_err(HardwareError::IoError)
+    }
+
+    /// Leitura contínua até `stop`: mantém a porta aberta, consulta a cada
+    /// `STREAM_POLL_INTERVAL` (ou só escuta, no modo contínuo da balança) e
+    /// entrega cada leitura a `on_reading`. Retorna erro quando a conexão cai
+    /// ou a balança fica muda, para o chamador reconectar.
+    pub fn stream(
+        &self,
+        stop: &AtomicBool,
+        commands: &Receiver<ScaleCommand>,
+        mut on_reading: impl FnMut(ScaleReading),
+    ) -> HardwareResult<()> {
+        if self.config.mock_mode {
+            let mut unit_price = None;
+            on_reading(self.mock_reading(unit_price));
+            loop {
+                while let Ok(ScaleCommand::SetUnitPrice(price)) = commands.try_recv() {
+                    unit_price = Some(price);
+                    on_reading(self.mock_reading(unit_price));
+                }
+                if stop.load(Ordering::Relaxed) {
+                    return Ok(());
+                }
+                std::thread::sleep(STREAM_POLL_INTERVAL);
+            }
+        }
+        if !self.config.enabled {
+            return Err(HardwareError::NotConfigured(
+                "Balança não habilitada".to_string(),
+            ));
+        }
+
+        let mut port = self.open_port(Duration::from_millis(100))?;
+        let protocol = &self.config.protocol;
+        let mut buffer = Vec::with_capacity(STREAM_BUFFER_LIMIT);
+        let mut last_poll: Option<Instant> = None;
+        let mut last_frame = Instant::now();
+
+        while !stop.load(Ordering::Relaxed) {
+            while let Ok(command) = commands.try_recv() {
+                match command {
+                    ScaleCommand::SetUnitPrice(price) => {
+                        port.write_all(&price_command(price))
+                            .map_err(HardwareError::IoError)?;
+                    }
+                }
+            }
+
+            // Por consulta: a resposta acumulada desde o último ENQ é um quadro
+            if !self.config.continuous
+                && last_poll.map_or(true, |t| t.elapsed() >= STREAM_POLL_INTERVAL)
+            {
+                if !buffer.is_empty() {
+                    if let Ok(reading) = parse_reading(protocol, &buffer) {
+                        last_frame = Instant::now();
+                        on_reading(reading);
+                    }
+                    buffer.clear();
+                }
+                port.write_all(command_read(protocol))
+                    .map_err(HardwareError::IoError)?;
+                last_poll = Some(Instant::now());
+            }
+
+            let mut chunk = [0u8; 64];
+            match port.read(&mut chunk) {
+                Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => return Err(HardwareError::IoError(e)),
+            }
+
+            if self.config.continuous {
+                for frame in take_frames(protocol, &mut buffer) {
+                    if let Ok(reading) = parse_reading(protocol, &frame) {
+                        last_frame = Instant::now();
+                        on_reading(reading);
+                    }
+                }
+            }
+
+            if last_frame.elapsed() > STREAM_SILENCE_TIMEOUT {
+                return Err(HardwareError::Timeout);
+            }
+        }
+
+        Ok(())
     }
 
     /// Lê peso aguardando estabilização
@@ -477,13 +756,137 @@ pub async fn auto_detect_scale_async() -> ScaleAutoDetectResult {
 // CÓDIGOS PESADOS
 // ════════════════════════════════════════════════════════════════════════════
 
-/// Parser para código de barras pesado (prefixo 2)
+/// Chave da configuração de códigos pesados nas configurações da loja
+pub const WEIGHTED_BARCODE_SETTING: &str = "pdv.weighted_barcode.config";
+
+/// Valor embutido no código pesado
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum WeightedValueKind {
+    /// Peso em gramas
+    Weight,
+    /// Preço total em centavos
+    Price,
+}
+
+/// Layout de código pesado (EAN-13 iniciado em 2)
 ///
-/// Formato: 2PPPPP KKKKK C
-/// - 2: Prefixo indicando produto pesado
-/// - PPPPP: Código do produto (5 dígitos)
-/// - KKKKK: Peso em gramas ou preço em centavos (5 dígitos)
-/// - C: Dígito verificador
+/// Formato: prefixo + código do produto + valor + dígito verificador; o dígito
+/// que sobrar antes do verificador (verificador do preço em algumas balanças)
+/// é ignorado. Ex.: 2 CCCCC VVVVV X D (prefixo "2", código 5, valor 5) ou
+/// 20 CCCC VVVVVV D (prefixo "20", código 4, valor 6).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WeightedBarcodeLayout {
+    /// "2" (qualquer código pesado) ou "20" a "29"
+    pub prefix: String,
+    /// O segundo dígito do prefixo também é o primeiro do código do produto
+    /// (2 CCCCC VVVVV X D com o prefixo "2C" escolhendo peso ou preço)
+    #[serde(default)]
+    pub prefix_in_code: bool,
+    /// Dígitos do código do produto na balança (4 a 6)
+    pub code_digits: u8,
+    /// Dígitos do valor: gramas ou centavos (4 a 6)
+    pub value_digits: u8,
+    pub value_kind: WeightedValueKind,
+}
+
+impl WeightedBarcodeLayout {
+    fn validate(&self) -> Result<(), String> {
+        let prefix_ok = matches!(self.prefix.len(), 1 | 2)
+            && self.prefix.starts_with('2')
+            && self.prefix.bytes().all(|b| b.is_ascii_digit());
+        if !prefix_ok {
+            return Err(format!(
+                "Prefixo de código pesado inválido: '{}' (use 2 ou 20 a 29)",
+                self.prefix
+            ));
+        }
+        if self.prefix_in_code && self.prefix.len() != 2 {
+            return Err(format!(
+                "Prefixo {}: só prefixos de 2 dígitos podem fazer parte do código",
+                self.prefix
+            ));
+        }
+        if !(4..=6).contains(&self.code_digits) || !(4..=6).contains(&self.value_digits) {
+            return Err(format!(
+                "Prefixo {}: código e valor devem ter de 4 a 6 dígitos",
+                self.prefix
+            ));
+        }
+        if self.code_start() + (self.code_digits + self.value_digits) as usize > 12 {
+            return Err(format!(
+                "Prefixo {}: código e valor não cabem nos 12 dígitos antes do verificador",
+                self.prefix
+            ));
+        }
+        Ok(())
+    }
+
+    /// Posição do primeiro dígito do código do produto
+    fn code_start(&self) -> usize {
+        if self.prefix_in_code {
+            1
+        } else {
+            self.prefix.len()
+        }
+    }
+}
+
+/// Layouts de código pesado da loja
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WeightedBarcodeConfig {
+    pub enabled: bool,
+    /// O prefixo mais longo que casar com o código define o layout
+    pub layouts: Vec<WeightedBarcodeLayout>,
+}
+
+impl Default for WeightedBarcodeConfig {
+    /// Leitura das versões anteriores: 2 CCCCC VVVVV X D, com o primeiro dígito
+    /// do código definindo o valor (0 a 4: peso em gramas, 5 a 9: preço total)
+    fn default() -> Self {
+        let layouts = (0..10)
+            .map(|digit| WeightedBarcodeLayout {
+                prefix: format!("2{}", digit),
+                prefix_in_code: true,
+                code_digits: 5,
+                value_digits: 5,
+                value_kind: if digit < 5 {
+                    WeightedValueKind::Weight
+                } else {
+                    WeightedValueKind::Price
+                },
+            })
+            .collect();
+        Self {
+            enabled: true,
+            layouts,
+        }
+    }
+}
+
+impl WeightedBarcodeConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, layout) in self.layouts.iter().enumerate() {
+            layout.validate()?;
+            if self.layouts[..i].iter().any(|l| l.prefix == layout.prefix) {
+                return Err(format!("Prefixo {} repetido", layout.prefix));
+            }
+        }
+        Ok(())
+    }
+
+    /// Layout do código (prefixo mais longo que casar)
+    pub fn layout_for(&self, code: &str) -> Option<&WeightedBarcodeLayout> {
+        self.layouts
+            .iter()
+            .filter(|l| code.starts_with(l.prefix.as_str()))
+            .max_by_key(|l| l.prefix.len())
+    }
+}
+
+/// Código de barras pesado decodificado
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct WeightedBarcode {
@@ -494,22 +897,25 @@ pub struct WeightedBarcode {
 }
 
 impl WeightedBarcode {
-    /// Decodifica código de barras pesado
-    pub fn decode(barcode: &str) -> Option<Self> {
+    /// Decodifica código pesado pelo layout da loja; `None` se o código não
+    /// for pesado, não casar com nenhum layout ou o verificador não conferir
+    pub fn decode(barcode: &str, config: &WeightedBarcodeConfig) -> Option<Self> {
         let clean: String = barcode.chars().filter(|c| c.is_ascii_digit()).collect();
 
-        if clean.len() != 13 || !clean.starts_with('2') {
+        if !config.enabled || clean.len() != 13 || !clean.starts_with('2') {
+            return None;
+        }
+        if !super::barcode::validate_gs1_check_digit(&clean) {
             return None;
         }
 
-        let product_code = clean[1..6].to_string();
-        let value: u32 = clean[6..11].parse().ok()?;
-
-        // Detecta se é peso ou preço pelo primeiro dígito do código do produto
-        // Códigos que começam com 0-4: peso
-        // Códigos que começam com 5-9: preço
-        let first_digit: u32 = clean[1..2].parse().ok()?;
-        let is_weight = first_digit < 5;
+        let layout = config.layout_for(&clean)?;
+        let code_start = layout.code_start();
+        let value_start = code_start + layout.code_digits as usize;
+        let product_code = clean.get(code_start..value_start)?.to_string();
+        let value_end = value_start + layout.value_digits as usize;
+        let value: u32 = clean.get(value_start..value_end.min(12))?.parse().ok()?;
+        let is_weight = layout.value_kind == WeightedValueKind::Weight;
 
         Some(Self {
             product_code,
@@ -519,6 +925,21 @@ impl WeightedBarcode {
         })
     }
 
+    /// Quantidade vendida: o peso, ou o preço embutido dividido pelo preço
+    /// por kg (arredondada em gramas)
+    pub fn quantity(&self, unit_price: f64) -> Option<f64> {
+        match self.price_cents {
+            None => Some(self.weight_kg()),
+            Some(_) if unit_price <= 0.0 => None,
+            Some(cents) => Some((cents as f64 / 100.0 / unit_price * 1000.0).round() / 1000.0),
+        }
+    }
+
+    /// Preço total embutido no código
+    pub fn total_price(&self) -> Option<f64> {
+        self.price_cents.map(|cents| cents as f64 / 100.0)
+    }
+
     /// Peso em kg
     pub fn weight_kg(&self) -> f64 {
         self.weight_grams as f64 / 1000.0
@@ -585,25 +1006,216 @@ mod tests {
         assert!(reading.stable);
     }
 
+    #[test]
+    fn test_toledo_states() {
+        let unstable = toledo::parse(b"\x02IIIII\x03").unwrap();
+        assert!(!unstable.stable);
+        assert!(!unstable.is_usable());
+
+        let negative = toledo::parse(b"\x02NNNNN\x03").unwrap();
+        assert!(negative.negative);
+
+        let overload = toledo::parse(b"\x02SSSSS\x03").unwrap();
+        assert!(overload.overload);
+
+        // Tara ativa (status 0x30 = estável + tara)
+        let tare = toledo::parse(&[0x02, b'0', b'0', b'0', b'5', b'0', b'0', 0x30, 0x03]).unwrap();
+        assert_eq!(tare.weight_grams, 500);
+        assert!(tare.tare && tare.stable);
+
+        assert!(toledo::parse(b"\x02AB\x03").is_err());
+    }
+
+    #[test]
+    fn test_toledo_price_computing() {
+        // Peso, preço por kg (R$ 29,90) e total (R$ 36,90)
+        let data = b"\x0201234 \x03\x02002990\x03\x02003690\x03";
+        let reading = toledo::parse(data).unwrap();
+        assert_eq!(reading.weight_grams, 1234);
+        assert_eq!(reading.unit_price, Some(29.9));
+        assert_eq!(reading.total_price, Some(36.9));
+
+        assert_eq!(price_command(29.9), b"\x02002990\x03".to_vec());
+        assert_eq!(price_command(-1.0), b"\x02000000\x03".to_vec());
+    }
+
+    #[test]
+    fn test_filizola_and_urano_states() {
+        assert!(filizola::parse(b"\x02SSSSS\x03").unwrap().overload);
+        let negative = filizola::parse(b"-001234 1").unwrap();
+        assert!(negative.negative);
+        assert_eq!(negative.weight_grams, 1234);
+
+        let urano = urano::parse(b"TARA 000850\r\n").unwrap();
+        assert_eq!(urano.weight_grams, 850);
+        assert!(urano.tare);
+        assert!(urano::parse(b"SOBRECARGA\r\n").unwrap().overload);
+    }
+
+    #[test]
+    fn test_take_frames() {
+        // Toledo em modo contínuo: quadros seguidos e um incompleto no fim
+        let mut buffer = b"\x02001200 \x03\x02001210 \x03\x02001".to_vec();
+        let frames = take_frames(&ScaleProtocol::Toledo, &mut buffer);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(
+            parse_reading(&ScaleProtocol::Toledo, &frames[1])
+                .unwrap()
+                .weight_grams,
+            1210
+        );
+        assert_eq!(buffer, b"\x02001".to_vec());
+
+        let mut buffer = b"001234\r\n001240\r\n".to_vec();
+        let frames = take_frames(&ScaleProtocol::Urano, &mut buffer);
+        assert_eq!(frames, vec![b"001234\r".to_vec(), b"001240\r".to_vec()]);
+        assert!(buffer.is_empty());
+
+        let mut buffer = vec![b'x'; STREAM_BUFFER_LIMIT + 10];
+        assert!(take_frames(&ScaleProtocol::Toledo, &mut buffer).is_empty());
+        assert_eq!(buffer.len(), STREAM_BUFFER_LIMIT);
+    }
+
+    #[test]
+    fn test_mock_stream_with_unit_price() {
+        let scale = Scale::new(ScaleConfig {
+            port: "MOCK".into(),
+            mock_mode: true,
+            price_computing: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let stop = AtomicBool::new(false);
+        let (tx, rx) = std::sync::mpsc::channel();
+        tx.send(ScaleCommand::SetUnitPrice(10.0)).unwrap();
+
+        let mut readings = Vec::new();
+        scale
+            .stream(&stop, &rx, |reading| {
+                readings.push(reading);
+                stop.store(true, Ordering::Relaxed);
+            })
+            .unwrap();
+
+        assert_eq!(readings.len(), 2);
+        assert_eq!(readings[0].unit_price, None);
+        assert_eq!(readings[1].unit_price, Some(10.0));
+        assert_eq!(readings[1].total_price, Some(12.34));
+    }
+
+    fn weighted_layouts(layouts: &[(&str, u8, u8, WeightedValueKind)]) -> WeightedBarcodeConfig {
+        WeightedBarcodeConfig {
+            enabled: true,
+            layouts: layouts
+                .iter()
+                .map(
+                    |&(prefix, code_digits, value_digits, value_kind)| WeightedBarcodeLayout {
+                        prefix: prefix.into(),
+                        prefix_in_code: false,
+                        code_digits,
+                        value_digits,
+                        value_kind,
+                    },
+                )
+                .collect(),
+        }
+    }
+
     #[test]
     fn test_weighted_barcode() {
-        // Código pesado: produto 12345, peso 1.234kg
-        let barcode = "2123451234050";
-        let parsed = WeightedBarcode::decode(barcode).unwrap();
+        // Código pesado: produto 12345, peso 12.340kg
+        let config = weighted_layouts(&[("2", 5, 5, WeightedValueKind::Weight)]);
+        let parsed = WeightedBarcode::decode("2123451234058", &config).unwrap();
 
         assert_eq!(parsed.product_code, "12345");
         assert_eq!(parsed.weight_grams, 12340);
         assert!(parsed.is_weight);
+        assert_eq!(parsed.quantity(10.0), Some(12.34));
+
+        // Dígito verificador errado
+        assert!(WeightedBarcode::decode("2123451234050", &config).is_none());
+        // Não é código pesado
+        assert!(WeightedBarcode::decode("7891234567895", &config).is_none());
     }
 
     #[test]
     fn test_weighted_barcode_price() {
-        // Código de preço: produto 51234, preço R$ 12,34
-        let barcode = "2512341234051";
-        let parsed = WeightedBarcode::decode(barcode).unwrap();
+        // Código de preço: produto 51234, preço R$ 123,40
+        let config = WeightedBarcodeConfig::default();
+        let parsed = WeightedBarcode::decode("2512341234054", &config).unwrap();
 
         assert_eq!(parsed.product_code, "51234");
         assert!(!parsed.is_weight);
         assert_eq!(parsed.price_cents, Some(12340));
+        assert_eq!(parsed.total_price(), Some(123.40));
+        // R$ 123,40 a R$ 40,00/kg = 3,085 kg
+        assert_eq!(parsed.quantity(40.0), Some(3.085));
+        assert_eq!(parsed.quantity(0.0), None);
+    }
+
+    #[test]
+    fn test_weighted_barcode_default_keeps_legacy_reading() {
+        // Sem layouts gravados: código iniciado em 0 a 4 é peso, em 5 a 9 é preço
+        let config = WeightedBarcodeConfig::default();
+        assert!(config.validate().is_ok());
+
+        let weight = WeightedBarcode::decode("2123451234058", &config).unwrap();
+        assert_eq!(weight.product_code, "12345");
+        assert!(weight.is_weight);
+        assert_eq!(weight.weight_grams, 12340);
+
+        let legacy: WeightedBarcodeLayout = serde_json::from_str(
+            r#"{"prefix":"2","codeDigits":5,"valueDigits":5,"valueKind":"price"}"#,
+        )
+        .unwrap();
+        assert!(!legacy.prefix_in_code);
+    }
+
+    #[test]
+    fn test_weighted_barcode_layout_by_prefix() {
+        let config = weighted_layouts(&[
+            ("2", 5, 5, WeightedValueKind::Price),
+            ("20", 4, 6, WeightedValueKind::Weight),
+            ("25", 4, 6, WeightedValueKind::Price),
+        ]);
+        assert!(config.validate().is_ok());
+
+        // 20 CCCC VVVVVV D: produto 0123, 12,345 kg
+        let weight = WeightedBarcode::decode("2001230123451", &config).unwrap();
+        assert_eq!(weight.product_code, "0123");
+        assert_eq!(weight.weight_grams, 12345);
+
+        // 25 CCCC VVVVVV D: produto 1234, R$ 123,45
+        let price = WeightedBarcode::decode("2512340123458", &config).unwrap();
+        assert_eq!(price.product_code, "1234");
+        assert_eq!(price.price_cents, Some(12345));
+
+        // Prefixo 21 cai no layout genérico "2"
+        let generic = WeightedBarcode::decode("2123450123407", &config).unwrap();
+        assert_eq!(generic.product_code, "12345");
+        assert_eq!(generic.price_cents, Some(1234));
+
+        let disabled = WeightedBarcodeConfig {
+            enabled: false,
+            ..config
+        };
+        assert!(WeightedBarcode::decode("2123450123407", &disabled).is_none());
+    }
+
+    #[test]
+    fn test_weighted_barcode_config_validation() {
+        assert!(WeightedBarcodeConfig::default().validate().is_ok());
+        let invalid = [
+            vec![("3", 5, 5, WeightedValueKind::Price)],
+            vec![("20", 7, 5, WeightedValueKind::Price)],
+            vec![("2", 6, 6, WeightedValueKind::Weight)],
+            vec![
+                ("21", 4, 6, WeightedValueKind::Price),
+                ("21", 5, 5, WeightedValueKind::Weight),
+            ],
+        ];
+        for layouts in invalid {
+            assert!(weighted_layouts(&layouts).validate().is_err());
+        }
     }
 }
@@ -0,0 +1,421 @@
+//! Cadastro de Itens das Balanças de Balcão
+//!
+//! Exporta os produtos vendidos por kg para os arquivos lidos pelas balanças
+//! etiquetadoras (açougue, frios, padaria), em vez de digitar item por item:
+//! - Toledo MGV (`ITENSMGV.TXT`)
+//! - Filizola (`CADTXT.TXT`)
+//!
+//! O código do item (PLU) é o código interno numérico do produto — o mesmo
+//! que volta no código pesado da etiqueta. O arquivo pode ser gravado na pasta
+//! lida pelo MGV/software da balança e/ou enviado pela porta de rede.
+
+use super::{HardwareError, HardwareResult};
+use crate::models::Product;
+use encoding_rs::WINDOWS_1252;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Chave da configuração nas configurações da loja
+pub const SCALE_CATALOG_SETTING: &str = "hardware.scale_catalog.config";
+
+/// Tempo máximo de envio do arquivo pela rede
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ════════════════════════════════════════════════════════════════════════════
+// TIPOS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Formato do arquivo de itens
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ScaleCatalogFormat {
+    /// Toledo MGV 5/6 (`ITENSMGV.TXT`)
+    #[default]
+    Toledo,
+    /// Filizola Smart/Platina (`CADTXT.TXT`)
+    Filizola,
+}
+
+impl ScaleCatalogFormat {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Toledo => "ITENSMGV.TXT",
+            Self::Filizola => "CADTXT.TXT",
+        }
+    }
+
+    /// Maior preço (centavos) que cabe no campo
+    fn max_price_cents(self) -> u64 {
+        match self {
+            Self::Toledo => 999_999,
+            Self::Filizola => 9_999_999,
+        }
+    }
+}
+
+/// Configuração da exportação
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaleCatalogConfig {
+    pub format: ScaleCatalogFormat,
+    /// Departamento dos itens na Toledo (1 a 99)
+    pub department: u8,
+    /// Validade impressa na etiqueta (0 = não imprime)
+    pub validity_days: u16,
+    /// Pasta onde gravar o arquivo (lida pelo MGV ou software da Filizola)
+    pub output_dir: Option<String>,
+    /// Endereço "ip:porta" da balança (ou do concentrador) para envio direto
+    pub network_address: Option<String>,
+}
+
+impl Default for ScaleCatalogConfig {
+    fn default() -> Self {
+        Self {
+            format: ScaleCatalogFormat::Toledo,
+            department: 1,
+            validity_days: 0,
+            output_dir: None,
+            network_address: None,
+        }
+    }
+}
+
+impl ScaleCatalogConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=99).contains(&self.department) {
+            return Err("Departamento deve estar entre 1 e 99".into());
+        }
+        if self.validity_days > 999 {
+            return Err("Validade deve ter no máximo 999 dias".into());
+        }
+        if let Some(address) = self.network_address.as_deref() {
+            let valid = address
+                .rsplit_once(':')
+                .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+            if !valid {
+                return Err(format!(
+                    "Endereço da balança inválido: '{}' (use ip:porta)",
+                    address
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Item exportado para a balança
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleCatalogItem {
+    pub plu: u32,
+    pub description: String,
+    pub price_cents: u64,
+}
+
+impl ScaleCatalogItem {
+    /// Item a partir do produto; `Err` com o motivo quando não cabe na balança
+    pub fn from_product(product: &Product, format: ScaleCatalogFormat) -> Result<Self, String> {
+        let code = product.internal_code.trim();
+        let plu = code
+            .parse::<u32>()
+            .ok()
+            .filter(|plu| (1..=999_999).contains(plu) && code.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or_else(|| {
+                format!(
+                    "código interno '{}' não é um PLU numérico de até 6 dígitos",
+                    code
+                )
+            })?;
+
+        let price_cents = (product.sale_price * 100.0).round();
+        if !(price_cents > 0.0 && price_cents <= format.max_price_cents() as f64) {
+            return Err(format!(
+                "preço por kg R$ {:.2} fora da faixa da balança",
+                product.sale_price
+            ));
+        }
+
+        Ok(Self {
+            plu,
+            description: product.name.clone(),
+            price_cents: price_cents as u64,
+        })
+    }
+}
+
+/// Resultado da exportação
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaleCatalogExport {
+    pub file_name: String,
+    /// Conteúdo do arquivo (para conferência ou download)
+    pub content: String,
+    pub exported: u32,
+    /// Produtos por kg que ficaram de fora, com o motivo
+    pub skipped: Vec<String>,
+    /// Caminho gravado, quando há pasta configurada
+    pub written_path: Option<String>,
+    /// Se o arquivo foi enviado pela rede
+    pub pushed: bool,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// ARQUIVOS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Texto em maiúsculas, só com caracteres de um byte, cortado/completado
+fn field(text: &str, width: usize) -> String {
+    let mut out: String = text
+        .chars()
+        .flat_map(char::to_uppercase)
+        .map(|c| {
+            if c.is_control() || c as u32 > 0xFF {
+                ' '
+            } else {
+                c
+            }
+        })
+        .take(width)
+        .collect();
+    let len = out.chars().count();
+    out.extend(std::iter::repeat(' ').take(width - len));
+    out
+}
+
+/// Divide a descrição em duas linhas sem cortar palavras (quando possível)
+fn split_description(text: &str, width: usize) -> (String, String) {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= width {
+        return (text, String::new());
+    }
+    let cut = text
+        .char_indices()
+        .take_while(|(i, _)| text[..*i].chars().count() <= width)
+        .filter(|(_, c)| *c == ' ')
+        .map(|(i, _)| i)
+        .last()
+        .unwrap_or_else(|| {
+            text.char_indices()
+                .nth(width)
+                .map_or(text.len(), |(i, _)| i)
+        });
+    (
+        text[..cut].trim_end().to_string(),
+        text[cut..].trim_start().to_string(),
+    )
+}
+
+/// Linha do `ITENSMGV.TXT` (MGV 5/6):
+/// departamento(2) tipo(1: 0 = peso) código(6) preço/kg(6) validade(3)
+/// descritivo 1(25) descritivo 2(25) informação extra(6) imagem(4)
+/// informação nutricional(6) imprime validade(1) imprime embalagem(1)
+fn toledo_line(item: &ScaleCatalogItem, config: &ScaleCatalogConfig) -> String {
+    let (line1, line2) = split_description(&item.description, 25);
+    format!(
+        "{:02}0{:06}{:06}{:03}{}{}0000000000000000{}1",
+        config.department,
+        item.plu,
+        item.price_cents,
+        config.validity_days,
+        field(&line1, 25),
+        field(&line2, 25),
+        u8::from(config.validity_days > 0),
+    )
+}
+
+/// Linha do `CADTXT.TXT`:
+/// código(6) tipo(1: P = peso) descrição(22) preço/kg(7) validade(3)
+fn filizola_line(item: &ScaleCatalogItem, config: &ScaleCatalogConfig) -> String {
+    format!(
+        "{:06}P{}{:07}{:03}",
+        item.plu,
+        field(&item.description, 22),
+        item.price_cents,
+        config.validity_days,
+    )
+}
+
+/// Monta o arquivo de itens (linhas CRLF, Windows-1252)
+pub fn render_catalog(items: &[ScaleCatalogItem], config: &ScaleCatalogConfig) -> Vec<u8> {
+    let mut text = String::new();
+    for item in items {
+        text.push_str(&match config.format {
+            ScaleCatalogFormat::Toledo => toledo_line(item, config),
+            ScaleCatalogFormat::Filizola => filizola_line(item, config),
+        });
+        text.push_str("\r\n");
+    }
+    let (bytes, _, _) = WINDOWS_1252.encode(&text);
+    bytes.into_owned()
+}
+
+/// Grava o arquivo na pasta configurada; retorna o caminho completo
+pub fn write_catalog(
+    dir: &str,
+    format: ScaleCatalogFormat,
+    bytes: &[u8],
+) -> HardwareResult<String> {
+    let path = std::path::Path::new(dir).join(format.file_name());
+    std::fs::write(&path, bytes).map_err(HardwareError::IoError)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Envia o arquivo pela porta de rede da balança (TCP cru)
+pub async fn push_catalog(address: &str, bytes: &[u8]) -> HardwareResult<()> {
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpStream;
+
+    let send = async {
+        let mut stream = TcpStream::connect(address)
+            .await
+            .map_err(|e| HardwareError::CommunicationError(e.to_string()))?;
+        stream
+            .write_all(bytes)
+            .await
+            .map_err(HardwareError::IoError)?;
+        stream.shutdown().await.map_err(HardwareError::IoError)
+    };
+    tokio::time::timeout(PUSH_TIMEOUT, send)
+        .await
+        .map_err(|_| HardwareError::Timeout)?
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TESTES
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(code: &str, name: &str, price: f64) -> Product {
+        Product {
+            id: "p1".into(),
+            barcode: None,
+            internal_code: code.into(),
+            name: name.into(),
+            description: None,
+            notes: None,
+            unit: "KILOGRAM".into(),
+            is_weighted: true,
+            sale_price: price,
+            cost_price: 0.0,
+            current_stock: 0.0,
+            min_stock: 0.0,
+            max_stock: None,
+            is_active: true,
+            category_id: "c1".into(),
+            oem_code: None,
+            aftermarket_code: None,
+            part_brand: None,
+            application: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_item_from_product() {
+        let format = ScaleCatalogFormat::Toledo;
+        let item = ScaleCatalogItem::from_product(&product("0123", "Queijo", 49.9), format);
+        assert_eq!(
+            item,
+            Ok(ScaleCatalogItem {
+                plu: 123,
+                description: "Queijo".into(),
+                price_cents: 4990,
+            })
+        );
+
+        assert!(ScaleCatalogItem::from_product(&product("MRC-00001", "X", 1.0), format).is_err());
+        assert!(ScaleCatalogItem::from_product(&product("1234567", "X", 1.0), format).is_err());
+        assert!(ScaleCatalogItem::from_product(&product("12", "X", 0.0), format).is_err());
+        assert!(ScaleCatalogItem::from_product(&product("12", "X", 10_000.0), format).is_err());
+        assert!(ScaleCatalogItem::from_product(
+            &product("12", "X", 10_000.0),
+            ScaleCatalogFormat::Filizola
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_toledo_line() {
+        let config = ScaleCatalogConfig {
+            department: 2,
+            validity_days: 5,
+            ..Default::default()
+        };
+        let item = ScaleCatalogItem {
+            plu: 123,
+            description: "Linguiça Toscana Artesanal Temperada da Casa".into(),
+            price_cents: 3490,
+        };
+        let line = toledo_line(&item, &config);
+
+        assert_eq!(
+            line.chars().count(),
+            2 + 1 + 6 + 6 + 3 + 25 + 25 + 6 + 4 + 6 + 1 + 1
+        );
+        assert!(line.starts_with("020000123003490005LINGUIÇA TOSCANA"));
+        assert!(line.contains("ARTESANAL TEMPERADA DA CA0000"));
+        assert!(line.ends_with("000000000000000011"));
+
+        let bytes = render_catalog(&[item], &config);
+        assert!(bytes.ends_with(b"\r\n"));
+        // Ç em Windows-1252 ocupa um byte: largura fixa preservada
+        assert_eq!(bytes.len(), 86 + 2);
+    }
+
+    #[test]
+    fn test_filizola_line() {
+        let config = ScaleCatalogConfig {
+            format: ScaleCatalogFormat::Filizola,
+            validity_days: 3,
+            ..Default::default()
+        };
+        let item = ScaleCatalogItem {
+            plu: 45,
+            description: "Pão Francês".into(),
+            price_cents: 1599,
+        };
+        assert_eq!(
+            filizola_line(&item, &config),
+            "000045PPÃO FRANCÊS           0001599003"
+        );
+    }
+
+    #[test]
+    fn test_split_description() {
+        assert_eq!(
+            split_description("Carne  moída de primeira", 25),
+            ("Carne moída de primeira".into(), String::new())
+        );
+        assert_eq!(
+            split_description("Queijo Mussarela Fatiado Especial", 25),
+            ("Queijo Mussarela Fatiado".into(), "Especial".into())
+        );
+        assert_eq!(
+            split_description("ABCDEFGHIJKLMNOPQRSTUVWXYZ", 25),
+            ("ABCDEFGHIJKLMNOPQRSTUVWXY".into(), "Z".into())
+        );
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(ScaleCatalogConfig::default().validate().is_ok());
+        let config = ScaleCatalogConfig {
+            network_address: Some("192.168.0.50:3001".into()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        let config = ScaleCatalogConfig {
+            network_address: Some("192.168.0.50".into()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        let config = ScaleCatalogConfig {
+            department: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Leitura Contínua da Balança
+//!
+//! Uma thread dedicada mantém a porta da balança aberta (`Scale::stream`) e
+//! publica cada mudança de peso como `HardwareEvent` com `scale_reading`
+//! preenchido, sem o PDV precisar consultar a balança. Quedas de conexão são
+//! retentadas com backoff. Enquanto o streaming está ativo a porta fica com
+//! ele: leituras avulsas devolvem o último peso recebido e o preço por kg da
+//! balança computadora segue pelo mesmo canal.
+
+use super::{
+    manager::{DeviceStatus, DeviceType, HardwareEvent, HardwareManager},
+    scale::{Scale, ScaleCommand, ScaleConfig, ScaleReading},
+    HardwareError, HardwareResult,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Espera antes de reabrir a porta, dobrada a cada falha seguida
+const RECONNECT_BASE: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(30);
+
+/// Streaming em execução
+pub(super) struct ScaleStreamHandle {
+    stop: Arc<AtomicBool>,
+    commands: Sender<ScaleCommand>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// Espera antes da reconexão após `failures` falhas seguidas
+fn reconnect_backoff(failures: u32) -> Duration {
+    let exponent = failures.saturating_sub(1).min(8);
+    (RECONNECT_BASE * (1 << exponent)).min(RECONNECT_MAX)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl HardwareManager {
+    /// Inicia (ou reinicia com a nova configuração) a leitura contínua
+    pub async fn start_scale_stream(self: &Arc<Self>, config: ScaleConfig) -> HardwareResult<()> {
+        if !config.enabled && !config.mock_mode {
+            return Err(HardwareError::NotConfigured(
+                "Balança não habilitada".to_string(),
+            ));
+        }
+        let scale = Scale::new(config.clone())?;
+        self.stop_scale_stream().await;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let (commands, receiver) = mpsc::channel();
+        let task = tokio::task::spawn_blocking({
+            let manager = Arc::clone(self);
+            let stop = Arc::clone(&stop);
+            move || manager.run_scale_stream(scale, config.mock_mode, &stop, receiver)
+        });
+
+        *lock(&self.scale_stream) = Some(ScaleStreamHandle {
+            stop,
+            commands,
+            task,
+        });
+        Ok(())
+    }
+
+    /// Encerra a leitura contínua (se ativa) e libera a porta
+    pub async fn stop_scale_stream(&self) {
+        let handle = lock(&self.scale_stream).take();
+        if let Some(handle) = handle {
+            handle.stop.store(true, Ordering::Relaxed);
+            if let Err(e) = handle.task.await {
+                tracing::error!("[Scale] Task panic: {}", e);
+            }
+        }
+        *lock(&self.scale_reading) = None;
+    }
+
+    /// Se a leitura contínua está ativa
+    pub fn is_scale_streaming(&self) -> bool {
+        lock(&self.scale_stream).is_some()
+    }
+
+    /// Último peso recebido pelo streaming
+    pub fn latest_scale_reading(&self) -> Option<ScaleReading> {
+        lock(&self.scale_reading).clone()
+    }
+
+    /// Envia o preço por kg à balança computadora, pelo streaming quando ativo
+    pub async fn send_scale_unit_price(
+        &self,
+        config: &ScaleConfig,
+        unit_price: f64,
+    ) -> HardwareResult<()> {
+        if !config.price_computing {
+            return Err(HardwareError::NotConfigured(
+                "Balança não configurada como computadora (preço por kg)".to_string(),
+            ));
+        }
+
+        if let Some(handle) = lock(&self.scale_stream).as_ref() {
+            return handle
+                .commands
+                .send(ScaleCommand::SetUnitPrice(unit_price))
+                .map_err(|_| {
+                    HardwareError::CommunicationError("Leitura da balança encerrada".into())
+                });
+        }
+
+        let scale = Scale::new(config.clone())?;
+        tokio::task::spawn_blocking(move || scale.send_unit_price(unit_price))
+            .await
+            .map_err(|e| HardwareError::CommunicationError(format!("Task error: {}", e)))?
+    }
+
+    /// Laço da thread de leitura: reconecta com backoff até ser parado
+    fn run_scale_stream(
+        &self,
+        scale: Scale,
+        mock: bool,
+        stop: &AtomicBool,
+        commands: Receiver<ScaleCommand>,
+    ) {
+        let mut failures = 0;
+        while !stop.load(Ordering::Relaxed) {
+            let result = scale.stream(stop, &commands, |reading| {
+                failures = 0;
+                self.publish_scale_reading(reading, mock);
+            });
+
+            let Err(e) = result else {
+                break;
+            };
+            failures += 1;
+            tracing::warn!("[Scale] Leitura contínua interrompida: {}", e);
+            *lock(&self.scale_reading) = None;
+            self.set_scale_stream_status(DeviceStatus::Disconnected, Some(e.to_string()));
+
+            let until = Instant::now() + reconnect_backoff(failures);
+            while Instant::now() < until && !stop.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        }
+    }
+
+    /// Publica o peso quando ele (ou o estado da balança) muda
+    fn publish_scale_reading(&self, reading: ScaleReading, mock: bool) {
+        {
+            let mut last = lock(&self.scale_reading);
+            if last.as_ref() == Some(&reading) {
+                return;
+            }
+            *last = Some(reading.clone());
+        }
+
+        let status = if mock {
+            DeviceStatus::MockMode
+        } else {
+            DeviceStatus::Connected
+        };
+        self.set_scale_stream_status(status.clone(), None);
+
+        let _ = self.event_tx.send(HardwareEvent {
+            device_type: DeviceType::Scale,
+            old_status: status.clone(),
+            new_status: status,
+            message: None,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            print_job: None,
+            scale_reading: Some(reading),
+        });
+    }
+
+    /// Atualiza o status da balança a partir da thread de leitura
+    fn set_scale_stream_status(&self, new: DeviceStatus, error: Option<String>) {
+        let old = {
+            let mut status = self.scale_status.blocking_write();
+            let old = status.status.clone();
+            status.status = new.clone();
+            if error.is_some() {
+                status.reconnect_attempts += 1;
+            } else {
+                status.reconnect_attempts = 0;
+            }
+            status.last_error = error.clone();
+            old
+        };
+        self.emit_event(DeviceType::Scale, old, new, error);
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TESTES
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_backoff() {
+        assert_eq!(reconnect_backoff(1), Duration::from_secs(1));
+        assert_eq!(reconnect_backoff(3), Duration::from_secs(4));
+        assert_eq!(reconnect_backoff(20), RECONNECT_MAX);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mock_stream_publishes_readings() {
+        let manager = HardwareManager::new();
+        let mut events = manager.subscribe();
+        let config = ScaleConfig {
+            port: "MOCK".into(),
+            mock_mode: true,
+            price_computing: true,
+            ..Default::default()
+        };
+
+        manager.start_scale_stream(config.clone()).await.unwrap();
+        assert!(manager.is_scale_streaming());
+
+        let reading = loop {
+            let event = events.recv().await.unwrap();
+            if let Some(reading) = event.scale_reading {
+                break reading;
+            }
+        };
+        assert_eq!(reading.weight_grams, 1234);
+
+        manager.send_scale_unit_price(&config, 10.0).await.unwrap();
+        let reading = loop {
+            let event = events.recv().await.unwrap();
+            if let Some(reading) = event.scale_reading {
+                break reading;
+            }
+        };
+        assert_eq!(reading.total_price, Some(12.34));
+        assert_eq!(manager.latest_scale_reading(), Some(reading));
+
+        manager.stop_scale_stream().await;
+        assert!(!manager.is_scale_streaming());
+        assert!(manager.latest_scale_reading().is_none());
+    }
+}
@@ -4,6 +4,9 @@
 //! - USB HID (leitoras que emulam teclado)
 //! - WebSocket (scanner mobile via PWA)
 
+use super::barcode::{
+    gtin_to_product_code, parse_gs1, strip_symbology_id, validate_gs1_check_digit, Gs1Data,
+};
 use super::{HardwareError, HardwareResult};
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
@@ -22,23 +25,112 @@ pub enum BarcodeFormat {
     Ean13,
     Ean8,
     Code128,
+    #[serde(rename = "GS1_128")]
+    Gs1128,
     Code39,
+    Itf,
+    Itf14,
+    DataMatrix,
     Qr,
     Unknown,
 }
 
 impl BarcodeFormat {
-    /// Detecta formato pelo tamanho e prefixo
+    /// Detecta o formato pelo identificador de simbologia AIM (quando a
+    /// leitora envia), pela estrutura GS1 e pelo dígito verificador
     pub fn detect(code: &str) -> Self {
-        let clean: String = code.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+        let (symbology, data) = strip_symbology_id(code.trim());
+        if let Some(format) = symbology.and_then(|id| Self::from_symbology_id(id, data)) {
+            return format;
+        }
+        if parse_gs1(code).is_some() {
+            return Self::Gs1128;
+        }
 
+        let clean = normalize_barcode(data);
+        let digits = !clean.is_empty() && clean.chars().all(|c| c.is_ascii_digit());
         match clean.len() {
-            13 if clean.chars().all(|c| c.is_ascii_digit()) => Self::Ean13,
-            8 if clean.chars().all(|c| c.is_ascii_digit()) => Self::Ean8,
-            _ if clean.chars().all(|c| c.is_ascii_digit()) => Self::Code128,
+            13 if digits && validate_ean13(&clean) => Self::Ean13,
+            8 if digits && validate_ean8(&clean) => Self::Ean8,
+            14 if digits && validate_gs1_check_digit(&clean) => Self::Itf14,
+            _ if digits => Self::Code128,
             _ => Self::Unknown,
         }
     }
+
+    /// Formato informado pelo scanner mobile, conferido contra o código
+    pub fn from_hint(hint: &str, code: &str) -> Self {
+        let detected = Self::detect(code);
+        match hint {
+            "EAN-13" => Self::Ean13,
+            "EAN-8" => Self::Ean8,
+            "CODE-128" if detected == Self::Gs1128 => Self::Gs1128,
+            "CODE-128" => Self::Code128,
+            "GS1-128" => Self::Gs1128,
+            "CODE-39" => Self::Code39,
+            "ITF" | "ITF-14" if detected == Self::Itf14 => Self::Itf14,
+            "ITF" => Self::Itf,
+            "DATA-MATRIX" | "DATAMATRIX" => Self::DataMatrix,
+            "QR" => Self::Qr,
+            _ => detected,
+        }
+    }
+
+    fn from_symbology_id(id: &str, data: &str) -> Option<Self> {
+        let format = match id.as_bytes() {
+            [b'C', b'1'] => Self::Gs1128,
+            [b'C', _] => Self::Code128,
+            [b'E', b'4'] => Self::Ean8,
+            [b'E', _] => Self::Ean13,
+            [b'A', _] => Self::Code39,
+            [b'I', _] if data.len() == 14 && validate_gs1_check_digit(data) => Self::Itf14,
+            [b'I', _] => Self::Itf,
+            [b'd', _] => Self::DataMatrix,
+            [b'Q', _] => Self::Qr,
+            _ => return None,
+        };
+        Some(format)
+    }
+}
+
+/// Código escaneado já interpretado
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedBarcode {
+    /// Código lido, sem identificador de simbologia
+    pub code: String,
+    pub format: BarcodeFormat,
+    /// Código do produto (EAN) quando o lido é GS1 ou caixa ITF-14
+    pub product_code: Option<String>,
+    /// Lote, validade e demais dados GS1
+    pub gs1: Option<Gs1Data>,
+}
+
+impl ParsedBarcode {
+    pub fn parse(raw: &str) -> Self {
+        Self::with_format(raw, BarcodeFormat::detect(raw))
+    }
+
+    pub fn with_format(raw: &str, format: BarcodeFormat) -> Self {
+        let (_, data) = strip_symbology_id(raw.trim());
+        let gs1 = parse_gs1(raw);
+        let product_code = match (&gs1, &format) {
+            (Some(gs1), _) => gs1.gtin.as_deref().and_then(gtin_to_product_code),
+            (None, BarcodeFormat::Itf14) => gtin_to_product_code(data),
+            _ => None,
+        };
+        Self {
+            code: data.to_string(),
+            format,
+            product_code,
+            gs1,
+        }
+    }
+
+    /// Código usado para localizar o produto
+    pub fn lookup_code(&self) -> &str {
+        self.product_code.as_deref().unwrap_or(&self.code)
+    }
 }
 
 /// Evento de scan recebido
@@ -49,6 +141,10 @@ pub struct ScanEvent {
     pub code: String,
     /// Formato detectado
     pub format: BarcodeFormat,
+    /// Código do produto (EAN) quando o lido é GS1 ou caixa ITF-14
+    pub product_code: Option<String>,
+    /// Lote e validade lidos de códigos GS1
+    pub gs1: Option<Gs1Data>,
     /// Timestamp em milissegundos
     pub timestamp: i64,
     /// ID do dispositivo (para mobile)
@@ -341,17 +437,18 @@ async fn handle_scanner_connection(
                             }
 
                             // Envia evento
+                            let parsed = match format {
+                                Some(hint) => ParsedBarcode::with_format(
+                                    &code,
+                                    BarcodeFormat::from_hint(&hint, &code),
+                                ),
+                                None => ParsedBarcode::parse(&code),
+                            };
                             let event = ScanEvent {
-                                code: code.clone(),
-                                format: format
-                                    .map(|f| match f.as_str() {
-                                        "EAN-13" => BarcodeFormat::Ean13,
-                                        "EAN-8" => BarcodeFormat::Ean8,
-                                        "CODE-128" => BarcodeFormat::Code128,
-                                        "QR" => BarcodeFormat::Qr,
-                                        _ => BarcodeFormat::detect(&code),
-                                    })
-                                    .unwrap_or_else(|| BarcodeFormat::detect(&code)),
+                                code: parsed.code.clone(),
+                                format: parsed.format.clone(),
+                                product_code: parsed.product_code.clone(),
+                                gs1: parsed.gs1.clone(),
                                 timestamp,
                                 device_id: device_id.clone(),
                                 source: ScanSource::Mobile,
@@ -360,7 +457,8 @@ async fn handle_scanner_connection(
                             state.send_scan_event(event);
 
                             // Busca nome do produto no banco de dados
-                            let product_name = state.lookup_product_name(&code).await;
+                            let product_name =
+                                state.lookup_product_name(parsed.lookup_code()).await;
 
                             // Envia ACK com nome do produto
                             let ack = ServerMessage::Ack { code, product_name };
@@ -514,9 +612,12 @@ pub fn start_serial_scanner(
                         match sp.read(buf.as_mut_slice()) {
                             Ok(n) if n > 0 => {
                                 if let Some(code) = parse_serial_input(&buf[..n]) {
+                                    let parsed = ParsedBarcode::parse(&code);
                                     let event = ScanEvent {
-                                        code: code.clone(),
-                                        format: BarcodeFormat::detect(&code),
+                                        code: parsed.code,
+                                        format: parsed.format,
+                                        product_code: parsed.product_code,
+                                        gs1: parsed.gs1,
                                         timestamp: chrono::Utc::now().timestamp_millis(),
                                         device_id: None,
                                         source: ScanSource::Usb,
@@ -600,8 +701,37 @@ mod tests {
 
     #[test]
     fn test_barcode_format_detection() {
-        assert_eq!(BarcodeFormat::detect("7891234567890"), BarcodeFormat::Ean13);
-        assert_eq!(BarcodeFormat::detect("12345678"), BarcodeFormat::Ean8);
+        assert_eq!(BarcodeFormat::detect("7891234567895"), BarcodeFormat::Ean13);
+        assert_eq!(BarcodeFormat::detect("12345670"), BarcodeFormat::Ean8);
+        // Dígito verificador inválido não é EAN
+        assert_eq!(
+            BarcodeFormat::detect("7891234567890"),
+            BarcodeFormat::Code128
+        );
+        assert_eq!(
+            BarcodeFormat::detect("17891234567892"),
+            BarcodeFormat::Itf14
+        );
+        assert_eq!(
+            BarcodeFormat::detect("(01)07891234567895(10)L1"),
+            BarcodeFormat::Gs1128
+        );
+        assert_eq!(BarcodeFormat::detect("]d2ABC"), BarcodeFormat::DataMatrix);
+        assert_eq!(BarcodeFormat::detect("]A0CODE39"), BarcodeFormat::Code39);
+    }
+
+    #[test]
+    fn test_parsed_barcode_fills_lot_and_expiry() {
+        let parsed = ParsedBarcode::parse("]C101078912345678951726123110LOTE42");
+        assert_eq!(parsed.format, BarcodeFormat::Gs1128);
+        assert_eq!(parsed.lookup_code(), "7891234567895");
+        let gs1 = parsed.gs1.unwrap();
+        assert_eq!(gs1.lot.as_deref(), Some("LOTE42"));
+        assert_eq!(gs1.expiration_date.as_deref(), Some("2026-12-31"));
+
+        let caixa = ParsedBarcode::parse("17891234567892");
+        assert_eq!(caixa.product_code.as_deref(), Some("7891234567895"));
+        assert!(caixa.gs1.is_none());
     }
 
     #[test]
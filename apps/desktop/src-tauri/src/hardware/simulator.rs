@@ -0,0 +1,1028 @@
+//! Simuladores de Hardware
+//!
+//! Equipamentos falsos para testes (`cargo test`) e para o modo de
+//! demonstração, sem balança ou impressora conectadas:
+//!
+//! - `ScaleSimulator`: balança Toledo, Filizola, Elgin ou Urano em um
+//!   pseudo-terminal. O PDV abre o lado escravo (`port_name`) como se fosse a
+//!   porta serial; o simulador responde às consultas e recebe o preço por kg.
+//! - `PrinterSimulator`: impressora de rede (porta 9100) que guarda cada
+//!   trabalho recebido e responde DLE EOT / GS r com os sensores configurados.
+//! - `decode_escpos`: transcreve o fluxo ESC/POS em texto legível, usado para
+//!   comparar layouts de cupom com arquivos golden (`tests/golden`).
+
+use super::{
+    printer::{PrinterConfig, PrinterConnection},
+    printer_status::PrinterSensorStatus,
+    scale::{ScaleConfig, ScaleProtocol},
+    HardwareError, HardwareResult,
+};
+use serde::Serialize;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+const STX: u8 = 0x02;
+const ETX: u8 = 0x03;
+const ENQ: u8 = 0x05;
+const ELGIN_READ: u8 = b'$';
+const ESC: u8 = 0x1B;
+const GS: u8 = 0x1D;
+const DLE: u8 = 0x10;
+const EOT: u8 = 0x04;
+const LF: u8 = 0x0A;
+const CR: u8 = 0x0D;
+
+/// Intervalo entre quadros da balança no modo contínuo
+const CONTINUOUS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Endereço padrão das impressoras de rede (RAW/JetDirect)
+pub const PRINTER_SIMULATOR_ADDRESS: &str = "127.0.0.1:9100";
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// BALANÇA
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Peso exibido pela balança simulada
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedWeight {
+    pub grams: u32,
+    pub stable: bool,
+    pub tare: bool,
+    pub negative: bool,
+    pub overload: bool,
+}
+
+impl Default for SimulatedWeight {
+    fn default() -> Self {
+        Self::grams(0)
+    }
+}
+
+impl SimulatedWeight {
+    /// Peso estável, sem tara
+    pub fn grams(grams: u32) -> Self {
+        Self {
+            grams,
+            stable: true,
+            tare: false,
+            negative: false,
+            overload: false,
+        }
+    }
+}
+
+/// Quadro que a balança envia para o peso informado. `unit_price` (preço por
+/// kg recebido) acrescenta preço e total nas Toledo/Elgin computadoras; no
+/// modo contínuo só o peso é transmitido.
+pub fn scale_frame(
+    protocol: &ScaleProtocol,
+    weight: &SimulatedWeight,
+    unit_price: Option<f64>,
+    continuous: bool,
+) -> Vec<u8> {
+    match protocol {
+        ScaleProtocol::Filizola => {
+            let body = if weight.overload {
+                "SSSSS".to_string()
+            } else if weight.negative {
+                "NNNNN".to_string()
+            } else if !weight.stable {
+                "IIIII".to_string()
+            } else {
+                let status = if weight.tare { 'T' } else { '1' };
+                format!("+{:06}{}", weight.grams, status)
+            };
+            if continuous {
+                [&[STX], body.as_bytes(), &[ETX]].concat()
+            } else {
+                [body.as_bytes(), &[CR]].concat()
+            }
+        }
+        ScaleProtocol::Urano => {
+            let body = if weight.overload {
+                "SOBRE".to_string()
+            } else {
+                format!(
+                    "{}{}{:06} g",
+                    if weight.tare { "LIQ " } else { "" },
+                    if weight.negative { "-" } else { "" },
+                    weight.grams
+                )
+            };
+            [body.as_bytes(), b"\r\n"].concat()
+        }
+        ScaleProtocol::Toledo | ScaleProtocol::Elgin | ScaleProtocol::Generic => {
+            let mut frame = vec![STX];
+            if weight.overload {
+                frame.extend_from_slice(b"SSSSS");
+            } else if weight.negative {
+                frame.extend_from_slice(b"NNNNN");
+            } else if !weight.stable {
+                frame.extend_from_slice(b"IIIII");
+            } else {
+                frame.extend_from_slice(format!("{:06}", weight.grams).as_bytes());
+                frame.push(if weight.tare { 0x30 } else { 0x20 });
+            }
+            frame.push(ETX);
+
+            let priced = weight.stable && !weight.overload && !weight.negative;
+            if let (Some(price), false, true) = (unit_price, continuous, priced) {
+                let unit_cents = (price * 100.0).round() as u64;
+                let total_cents = (price * weight.grams as f64 / 10.0).round() as u64;
+                for cents in [unit_cents, total_cents] {
+                    frame.push(STX);
+                    frame.extend_from_slice(format!("{:06}", cents.min(999_999)).as_bytes());
+                    frame.push(ETX);
+                }
+            }
+            frame
+        }
+    }
+}
+
+#[derive(Default)]
+struct ScaleSimulatorState {
+    weight: SimulatedWeight,
+    unit_price: Option<f64>,
+    requests: usize,
+}
+
+/// Balança simulada em um pseudo-terminal
+pub struct ScaleSimulator {
+    protocol: ScaleProtocol,
+    continuous: bool,
+    port_name: String,
+    state: Arc<Mutex<ScaleSimulatorState>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl ScaleSimulator {
+    /// Cria o pseudo-terminal e começa a responder no protocolo informado
+    #[cfg(unix)]
+    pub fn start(protocol: ScaleProtocol, continuous: bool) -> HardwareResult<Self> {
+        let (master, slave) = serialport::TTYPort::pair()
+            .map_err(|e| HardwareError::ConnectionFailed(e.to_string()))?;
+        let port_name = serialport::SerialPort::name(&slave)
+            .ok_or_else(|| HardwareError::InvalidPort("pseudo-terminal sem nome".into()))?;
+
+        let state = Arc::new(Mutex::new(ScaleSimulatorState::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = std::thread::spawn({
+            let protocol = protocol.clone();
+            let state = Arc::clone(&state);
+            let stop = Arc::clone(&stop);
+            move || run_scale(master, slave, protocol, continuous, &state, &stop)
+        });
+
+        Ok(Self {
+            protocol,
+            continuous,
+            port_name,
+            state,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Pseudo-terminais não existem no Windows
+    #[cfg(not(unix))]
+    pub fn start(_protocol: ScaleProtocol, _continuous: bool) -> HardwareResult<Self> {
+        Err(HardwareError::NotConfigured(
+            "Simulador de balança disponível apenas em Linux/macOS".into(),
+        ))
+    }
+
+    /// Caminho da porta a configurar no PDV (ex.: /dev/pts/3)
+    pub fn port_name(&self) -> &str {
+        &self.port_name
+    }
+
+    /// Configuração da balança apontando para o simulador
+    pub fn scale_config(&self) -> ScaleConfig {
+        ScaleConfig {
+            enabled: true,
+            protocol: self.protocol.clone(),
+            port: self.port_name.clone(),
+            continuous: self.continuous,
+            price_computing: matches!(self.protocol, ScaleProtocol::Toledo | ScaleProtocol::Elgin),
+            ..Default::default()
+        }
+    }
+
+    /// Coloca um peso no prato
+    pub fn set_weight(&self, weight: SimulatedWeight) {
+        lock(&self.state).weight = weight;
+    }
+
+    /// Preço por kg recebido do PDV
+    pub fn unit_price(&self) -> Option<f64> {
+        lock(&self.state).unit_price
+    }
+
+    /// Consultas de peso atendidas
+    pub fn requests(&self) -> usize {
+        lock(&self.state).requests
+    }
+}
+
+impl Drop for ScaleSimulator {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Laço do lado mestre: responde consultas e guarda o preço por kg. O lado
+/// escravo fica aberto para o pseudo-terminal sobreviver entre as aberturas
+/// da porta pelo PDV; como `serialport` abre portas em modo exclusivo, a
+/// exclusividade é desfeita a cada volta para a próxima abertura funcionar.
+#[cfg(unix)]
+fn run_scale(
+    mut master: serialport::TTYPort,
+    mut slave: serialport::TTYPort,
+    protocol: ScaleProtocol,
+    continuous: bool,
+    state: &Mutex<ScaleSimulatorState>,
+    stop: &AtomicBool,
+) {
+    use serialport::{ClearBuffer, SerialPort};
+    use std::io::{Read, Write};
+
+    let read_command = match protocol {
+        ScaleProtocol::Elgin => ELGIN_READ,
+        _ => ENQ,
+    };
+    let frame = |state: &Mutex<ScaleSimulatorState>| {
+        let mut state = lock(state);
+        state.requests += 1;
+        scale_frame(&protocol, &state.weight, state.unit_price, continuous)
+    };
+
+    let _ = master.set_timeout(Duration::from_millis(20));
+    let mut price: Option<Vec<u8>> = None;
+    let mut last_frame = std::time::Instant::now();
+
+    while !stop.load(Ordering::Relaxed) {
+        let _ = slave.set_exclusive(false);
+
+        // Modo contínuo: só o quadro mais recente fica na fila da porta
+        if continuous && last_frame.elapsed() >= CONTINUOUS_INTERVAL {
+            let _ = slave.clear(ClearBuffer::Input);
+            let _ = master.write_all(&frame(state));
+            last_frame = std::time::Instant::now();
+        }
+
+        let mut chunk = [0u8; 64];
+        let n = match master.read(&mut chunk) {
+            Ok(n) => n,
+            Err(_) => continue,
+        };
+        for &byte in &chunk[..n] {
+            match (&mut price, byte) {
+                (Some(digits), ETX) => {
+                    let cents = std::str::from_utf8(digits)
+                        .ok()
+                        .and_then(|d| d.parse::<u32>().ok());
+                    if let Some(cents) = cents {
+                        lock(state).unit_price = Some(cents as f64 / 100.0);
+                    }
+                    price = None;
+                }
+                (Some(digits), _) => digits.push(byte),
+                (None, STX) => price = Some(Vec::new()),
+                (None, b) if b == read_command && !continuous => {
+                    let _ = master.write_all(&frame(state));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// IMPRESSORA DE REDE
+// ════════════════════════════════════════════════════════════════════════════
+
+#[derive(Default)]
+struct PrinterSimulatorState {
+    jobs: Vec<Vec<u8>>,
+    sensors: PrinterSensorStatus,
+}
+
+/// Impressora ESC/POS de rede simulada
+pub struct PrinterSimulator {
+    addr: SocketAddr,
+    state: Arc<Mutex<PrinterSimulatorState>>,
+    job_received: Arc<Notify>,
+    server: JoinHandle<()>,
+}
+
+impl PrinterSimulator {
+    /// Escuta no endereço informado (`127.0.0.1:0` para porta livre)
+    pub async fn start(address: &str) -> HardwareResult<Self> {
+        let listener = TcpListener::bind(address)
+            .await
+            .map_err(|e| HardwareError::ConnectionFailed(format!("{}: {}", address, e)))?;
+        let addr = listener.local_addr().map_err(HardwareError::IoError)?;
+        let state = Arc::new(Mutex::new(PrinterSimulatorState {
+            jobs: Vec::new(),
+            sensors: PrinterSensorStatus {
+                online: true,
+                drawer_pin_high: true,
+                ..Default::default()
+            },
+        }));
+        let job_received = Arc::new(Notify::new());
+
+        let server = tokio::spawn({
+            let state = Arc::clone(&state);
+            let job_received = Arc::clone(&job_received);
+            async move {
+                loop {
+                    let Ok((stream, _)) = listener.accept().await else {
+                        continue;
+                    };
+                    let state = Arc::clone(&state);
+                    let job_received = Arc::clone(&job_received);
+                    tokio::spawn(async move {
+                        let job = serve_printer(stream, &state).await;
+                        if !job.is_empty() {
+                            lock(&state).jobs.push(job);
+                            job_received.notify_waiters();
+                        }
+                    });
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            job_received,
+            server,
+        })
+    }
+
+    /// Endereço `host:porta` a configurar na impressora de rede
+    pub fn address(&self) -> String {
+        self.addr.to_string()
+    }
+
+    /// Configuração de impressora de rede apontando para o simulador
+    pub fn printer_config(&self, base: &PrinterConfig) -> PrinterConfig {
+        PrinterConfig {
+            enabled: true,
+            connection: PrinterConnection::Network,
+            port: self.address(),
+            mock_mode: false,
+            ..base.clone()
+        }
+    }
+
+    /// Define o que os sensores de papel, tampa e gaveta informam
+    pub fn set_sensors(&self, sensors: PrinterSensorStatus) {
+        lock(&self.state).sensors = sensors;
+    }
+
+    /// Trabalhos recebidos, em ordem
+    pub fn jobs(&self) -> Vec<Vec<u8>> {
+        lock(&self.state).jobs.clone()
+    }
+
+    /// Transcrição do último trabalho recebido
+    pub fn last_transcript(&self) -> Option<String> {
+        lock(&self.state).jobs.last().map(|job| decode_escpos(job))
+    }
+
+    /// Aguarda até `count` trabalhos terem sido recebidos
+    pub async fn wait_for_jobs(&self, count: usize, timeout: Duration) -> HardwareResult<()> {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let notified = self.job_received.notified();
+                if lock(&self.state).jobs.len() >= count {
+                    return;
+                }
+                notified.await;
+            }
+        })
+        .await
+        .map_err(|_| HardwareError::Timeout)
+    }
+}
+
+impl Drop for PrinterSimulator {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Atende uma conexão: consultas de status são respondidas na hora (como na
+/// impressora real) e o restante compõe o trabalho impresso
+async fn serve_printer(mut stream: TcpStream, state: &Mutex<PrinterSimulatorState>) -> Vec<u8> {
+    let mut job = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = match stream.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        let response = match &chunk[..n] {
+            [DLE, EOT, n] => Some(dle_eot_response(*n, &lock(state).sensors)),
+            [GS, b'r', n] => Some(gs_r_response(*n, &lock(state).sensors)),
+            _ => None,
+        };
+        match response {
+            Some(byte) => {
+                if stream.write_all(&[byte]).await.is_err() {
+                    break;
+                }
+            }
+            None => job.extend_from_slice(&chunk[..n]),
+        }
+    }
+    job
+}
+
+/// Resposta a DLE EOT n (bits 1 e 4 fixos em 1)
+fn dle_eot_response(n: u8, sensors: &PrinterSensorStatus) -> u8 {
+    let flag = |on: bool, bits: u8| if on { bits } else { 0 };
+    0x12 | match n {
+        1 => flag(!sensors.online, 0x08) | flag(sensors.drawer_pin_high, 0x04),
+        2 => {
+            flag(sensors.cover_open, 0x04)
+                | flag(sensors.paper_out, 0x20)
+                | flag(sensors.error, 0x40)
+        }
+        3 => flag(sensors.error, 0x08),
+        4 => flag(sensors.paper_near_end, 0x0C) | flag(sensors.paper_out, 0x60),
+        _ => 0,
+    }
+}
+
+/// Resposta a GS r n (1 = papel, 2 = gaveta)
+fn gs_r_response(n: u8, sensors: &PrinterSensorStatus) -> u8 {
+    match n {
+        1 => {
+            (if sensors.paper_near_end { 0x03 } else { 0 })
+                | (if sensors.paper_out { 0x0C } else { 0 })
+        }
+        2 => u8::from(sensors.drawer_pin_high),
+        _ => 0,
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TRANSCRIÇÃO ESC/POS
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Converte o fluxo ESC/POS em texto: o texto impresso em WINDOWS-1252, cada
+/// comando como `[TOKEN]` na posição em que aparece e LF como quebra de linha.
+/// Dados de imagem são resumidos às dimensões; bytes desconhecidos aparecem
+/// em hexadecimal.
+pub fn decode_escpos(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let mut text = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            LF => {
+                flush_text(&mut out, &mut text);
+                out.push('\n');
+                i += 1;
+            }
+            CR => i += 1,
+            ESC | GS | DLE => {
+                flush_text(&mut out, &mut text);
+                let (token, len) = decode_command(&bytes[i..]);
+                out.push_str(&token);
+                i += len;
+            }
+            byte if byte >= 0x20 => {
+                text.push(byte);
+                i += 1;
+            }
+            byte => {
+                flush_text(&mut out, &mut text);
+                out.push_str(&format!("[0x{:02X}]", byte));
+                i += 1;
+            }
+        }
+    }
+    flush_text(&mut out, &mut text);
+    out
+}
+
+fn flush_text(out: &mut String, text: &mut Vec<u8>) {
+    if !text.is_empty() {
+        let decoded = encoding_rs::WINDOWS_1252
+            .decode_without_bom_handling(text)
+            .0;
+        out.push_str(&decoded);
+        text.clear();
+    }
+}
+
+/// Dados de código de barras/QR com bytes não imprimíveis escapados
+fn escape_data(data: &[u8]) -> String {
+    data.iter()
+        .map(|&b| {
+            if (0x20..0x7F).contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("\\x{:02X}", b)
+            }
+        })
+        .collect()
+}
+
+fn barcode_name(m: u8) -> String {
+    match m {
+        0 | 65 => "UPC-A".into(),
+        1 | 66 => "UPC-E".into(),
+        2 | 67 => "EAN13".into(),
+        3 | 68 => "EAN8".into(),
+        4 | 69 => "CODE39".into(),
+        5 | 70 => "ITF".into(),
+        6 | 71 => "CODABAR".into(),
+        72 => "CODE93".into(),
+        73 => "CODE128".into(),
+        other => format!("m={}", other),
+    }
+}
+
+/// Decodifica o comando no início de `cmd`: (token, bytes consumidos)
+fn decode_command(cmd: &[u8]) -> (String, usize) {
+    let arg = |k: usize| cmd.get(k).copied();
+    let word = |k: usize| Some(arg(k)? as usize | (arg(k + 1)? as usize) << 8);
+    let truncated = || ("[TRUNCATED]".to_string(), cmd.len());
+    let Some(op) = arg(1) else {
+        return truncated();
+    };
+
+    let decoded = match (cmd[0], op) {
+        (ESC, b'@') => Some(("[INIT]".to_string(), 2)),
+        (ESC, b'2') => Some(("[LINE SPACING DEFAULT]".to_string(), 2)),
+        (ESC, b'3') => arg(2).map(|n| (format!("[LINE SPACING {}]", n), 3)),
+        (ESC, b'a') => arg(2).map(|n| {
+            let align = match n {
+                1 | b'1' => "CENTER",
+                2 | b'2' => "RIGHT",
+                _ => "LEFT",
+            };
+            (format!("[ALIGN {}]", align), 3)
+        }),
+        (ESC, b'E') => arg(2).map(|n| {
+            let on = if n & 1 != 0 { "ON" } else { "OFF" };
+            (format!("[BOLD {}]", on), 3)
+        }),
+        (ESC, b'-') => arg(2).map(|n| {
+            let on = if n & 3 != 0 { "ON" } else { "OFF" };
+            (format!("[UNDERLINE {}]", on), 3)
+        }),
+        (ESC, b'd') => arg(2).map(|n| (format!("[FEED {}]", n), 3)),
+        (ESC, b'J') => arg(2).map(|n| (format!("[FEED {} DOTS]", n), 3)),
+        (ESC, b't') => arg(2).map(|n| (format!("[CODEPAGE {}]", n), 3)),
+        (ESC, b'M') => arg(2).map(|n| (format!("[FONT {}]", n), 3)),
+        (ESC, b'p') => arg(2).and(arg(4)).map(|_| {
+            let pin = if cmd[2] & 1 != 0 { 5 } else { 2 };
+            (format!("[DRAWER PIN {}]", pin), 5)
+        }),
+        (ESC, b'*') => arg(2).zip(word(3)).and_then(|(m, width)| {
+            let rows = if m >= 32 { 3 } else { 1 };
+            let len = 5 + width * rows;
+            (cmd.len() >= len).then(|| (format!("[BIT IMAGE {}x{}]", width, rows * 8), len))
+        }),
+        (GS, b'!') => arg(2).map(|n| {
+            let size = match n {
+                0x00 => "NORMAL".to_string(),
+                0x01 => "DOUBLE HEIGHT".to_string(),
+                0x10 => "DOUBLE WIDTH".to_string(),
+                0x11 => "DOUBLE".to_string(),
+                other => format!("0x{:02X}", other),
+            };
+            (format!("[SIZE {}]", size), 3)
+        }),
+        (GS, b'V') => arg(2).and_then(|m| match m {
+            0 | b'0' => Some(("[CUT FULL]".to_string(), 3)),
+            1 | b'1' => Some(("[CUT PARTIAL]".to_string(), 3)),
+            65 | 66 => arg(3).map(|_| ("[CUT PARTIAL]".to_string(), 4)),
+            _ => Some((format!("[CUT m={}]", m), 3)),
+        }),
+        (GS, b'h') => arg(2).map(|n| (format!("[BARCODE HEIGHT {}]", n), 3)),
+        (GS, b'w') => arg(2).map(|n| (format!("[BARCODE WIDTH {}]", n), 3)),
+        (GS, b'H') => arg(2).map(|n| (format!("[BARCODE HRI {}]", n), 3)),
+        (GS, b'k') => arg(2).and_then(|m| {
+            let (data, len) = if m <= 6 {
+                let end = cmd[3..].iter().position(|&b| b == 0)?;
+                (&cmd[3..3 + end], 3 + end + 1)
+            } else {
+                let n = arg(3)? as usize;
+                (cmd.get(4..4 + n)?, 4 + n)
+            };
+            Some((
+                format!("[BARCODE {} {}]", barcode_name(m), escape_data(data)),
+                len,
+            ))
+        }),
+        (GS, b'(') if arg(2) == Some(b'k') => word(3).and_then(|p| {
+            let len = 5 + p;
+            let body = cmd.get(5..len)?;
+            let token = match body {
+                [49, 67, size] => format!("[QR SIZE {}]", size),
+                [49, 80, 48, data @ ..] => format!("[QR {}]", escape_data(data)),
+                [49, ..] => String::new(),
+                _ => "[GS ( k]".to_string(),
+            };
+            Some((token, len))
+        }),
+        (GS, b'v') if arg(2) == Some(b'0') => word(4).zip(word(6)).and_then(|(x, y)| {
+            let len = 8 + x * y;
+            (cmd.len() >= len).then(|| (format!("[RASTER {}x{}]", x * 8, y), len))
+        }),
+        (GS, b'r') => arg(2).map(|n| (format!("[STATUS GS r {}]", n), 3)),
+        (DLE, EOT) => arg(2).map(|n| (format!("[STATUS DLE EOT {}]", n), 3)),
+        (ESC, other) => Some((format!("[ESC 0x{:02X}]", other), 2)),
+        (GS, other) => Some((format!("[GS 0x{:02X}]", other), 2)),
+        _ => Some(("[DLE]".to_string(), 1)),
+    };
+    decoded.unwrap_or_else(truncated)
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// MODO DE DEMONSTRAÇÃO
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Situação do modo de demonstração exibida na tela de hardware
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DemoHardwareInfo {
+    /// Porta da balança simulada (ausente no Windows, onde a balança fica em mock)
+    pub scale_port: Option<String>,
+    pub printer_address: String,
+    pub printed_jobs: u32,
+    /// Último cupom recebido pela impressora simulada
+    pub last_transcript: Option<String>,
+}
+
+/// Balança e impressora simuladas usadas no modo de demonstração
+pub struct DemoHardware {
+    pub scale: Option<ScaleSimulator>,
+    pub printer: PrinterSimulator,
+}
+
+impl DemoHardware {
+    /// Sobe a balança Toledo computadora e a impressora na porta 9100 (ou em
+    /// porta livre, se a 9100 estiver ocupada)
+    pub async fn start() -> HardwareResult<Self> {
+        let printer = match PrinterSimulator::start(PRINTER_SIMULATOR_ADDRESS).await {
+            Ok(printer) => printer,
+            Err(e) => {
+                tracing::warn!("[Demo] {}; usando porta livre", e);
+                PrinterSimulator::start("127.0.0.1:0").await?
+            }
+        };
+
+        let scale = match ScaleSimulator::start(ScaleProtocol::Toledo, false) {
+            Ok(scale) => {
+                scale.set_weight(SimulatedWeight::grams(1234));
+                Some(scale)
+            }
+            Err(e) => {
+                tracing::warn!("[Demo] Balança simulada indisponível: {}", e);
+                None
+            }
+        };
+
+        Ok(Self { scale, printer })
+    }
+
+    /// Configuração da balança: o simulador ou, sem ele, o modo mock
+    pub fn scale_config(&self) -> ScaleConfig {
+        match &self.scale {
+            Some(scale) => scale.scale_config(),
+            None => ScaleConfig {
+                port: "MOCK".into(),
+                mock_mode: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn info(&self) -> DemoHardwareInfo {
+        DemoHardwareInfo {
+            scale_port: self.scale.as_ref().map(|s| s.port_name().to_string()),
+            printer_address: self.printer.address(),
+            printed_jobs: self.printer.jobs().len() as u32,
+            last_transcript: self.printer.last_transcript(),
+        }
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// GOLDEN FILES
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Compara `actual` com `tests/golden/<name>`. Arquivo ausente (ou
+/// `UPDATE_GOLDEN=1`) é gravado, para a mudança de layout aparecer no diff.
+#[cfg(test)]
+pub(crate) fn assert_golden(name: &str, actual: &str) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(name);
+    let update = std::env::var("UPDATE_GOLDEN").is_ok_and(|v| v == "1");
+
+    match std::fs::read_to_string(&path) {
+        Ok(expected) if !update => assert_eq!(
+            actual,
+            expected,
+            "Saída difere de {} (UPDATE_GOLDEN=1 para regravar)",
+            path.display()
+        ),
+        _ => {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, actual).unwrap();
+        }
+    }
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// TESTES
+// ════════════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::printer::{Receipt, ReceiptItem, TextAlign, TextStyle, ThermalPrinter};
+    use crate::hardware::printer_status::query_printer_sensors;
+    use crate::hardware::scale::parse_reading;
+
+    fn receipt() -> Receipt {
+        Receipt {
+            company_name: "MERCADINHO GIRO".into(),
+            company_address: "Rua das Flores, 123 - Centro".into(),
+            company_cnpj: Some("12.345.678/0001-90".into()),
+            company_phone: Some("(11) 3333-4444".into()),
+            sale_number: 42,
+            operator_name: "Maria".into(),
+            date_time: "18/10/2026 10:30".into(),
+            customer_name: None,
+            customer_document: None,
+            items: vec![
+                ReceiptItem {
+                    code: "7891234567895".into(),
+                    name: "Açúcar Cristal 1kg".into(),
+                    quantity: 2.0,
+                    unit: "UN".into(),
+                    unit_price: 4.99,
+                    total: 9.98,
+                    discount: 0.0,
+                    discount_reason: None,
+                },
+                ReceiptItem {
+                    code: "00012".into(),
+                    name: "Queijo Minas".into(),
+                    quantity: 0.512,
+                    unit: "KG".into(),
+                    unit_price: 39.90,
+                    total: 20.43,
+                    discount: 0.0,
+                    discount_reason: None,
+                },
+            ],
+            subtotal: 30.41,
+            discount: 0.41,
+            total: 30.0,
+            payment_method: "Dinheiro".into(),
+            amount_paid: 50.0,
+            change: 20.0,
+        }
+    }
+
+    #[test]
+    fn test_decode_text_and_styles() {
+        let mut printer = ThermalPrinter::new(PrinterConfig::default());
+        printer
+            .init()
+            .align(TextAlign::Center)
+            .style(TextStyle {
+                bold: true,
+                ..Default::default()
+            })
+            .line("AÇÚCAR")
+            .cut(true);
+
+        assert_eq!(
+            decode_escpos(printer.get_buffer()),
+            "[INIT][LINE SPACING DEFAULT][ALIGN CENTER][SIZE NORMAL][BOLD OFF]\
+             [UNDERLINE OFF][BOLD ON]AÇÚCAR\n\n\n\n[CUT PARTIAL]"
+        );
+    }
+
+    #[test]
+    fn test_decode_barcode_qr_drawer_and_raster() {
+        let mut printer = ThermalPrinter::new(PrinterConfig::default());
+        printer
+            .barcode_ean13("7891234567895")
+            .qrcode("https://giro.app")
+            .open_drawer()
+            .raw(&[GS, b'v', b'0', 0, 2, 0, 3, 0])
+            .raw(&[0xFF; 6])
+            .raw(&[0x07]);
+
+        assert_eq!(
+            decode_escpos(printer.get_buffer()),
+            "[BARCODE HEIGHT 80][BARCODE WIDTH 2][BARCODE HRI 2][BARCODE EAN13 7891234567895]\n\
+             [QR SIZE 6][QR https://giro.app]\n\
+             [DRAWER PIN 2][RASTER 16x3][0x07]"
+        );
+    }
+
+    #[test]
+    fn test_decode_truncated_command() {
+        assert_eq!(decode_escpos(&[b'A', GS, b'v', b'0', 0, 9]), "A[TRUNCATED]");
+        assert_eq!(decode_escpos(&[ESC, b'*', 33, 2, 0, 1]), "[TRUNCATED]");
+    }
+
+    #[test]
+    fn test_scale_frames_parse() {
+        let weight = SimulatedWeight::grams(1234);
+        for protocol in [
+            ScaleProtocol::Toledo,
+            ScaleProtocol::Filizola,
+            ScaleProtocol::Elgin,
+            ScaleProtocol::Urano,
+        ] {
+            let frame = scale_frame(&protocol, &weight, None, false);
+            let reading = parse_reading(&protocol, &frame).unwrap();
+            assert_eq!(reading.weight_grams, 1234, "{:?}", protocol);
+            assert!(reading.is_usable(), "{:?}", protocol);
+        }
+
+        let priced = scale_frame(&ScaleProtocol::Toledo, &weight, Some(29.9), false);
+        let reading = parse_reading(&ScaleProtocol::Toledo, &priced).unwrap();
+        assert_eq!(reading.unit_price, Some(29.9));
+        assert_eq!(reading.total_price, Some(36.9));
+
+        let tare = SimulatedWeight {
+            tare: true,
+            ..weight.clone()
+        };
+        let frame = scale_frame(&ScaleProtocol::Toledo, &tare, None, false);
+        assert!(parse_reading(&ScaleProtocol::Toledo, &frame).unwrap().tare);
+
+        let overload = SimulatedWeight {
+            overload: true,
+            ..weight.clone()
+        };
+        for protocol in [ScaleProtocol::Toledo, ScaleProtocol::Urano] {
+            let frame = scale_frame(&protocol, &overload, None, false);
+            assert!(parse_reading(&protocol, &frame).unwrap().overload);
+        }
+
+        let unstable = SimulatedWeight {
+            stable: false,
+            ..weight
+        };
+        let frame = scale_frame(&ScaleProtocol::Filizola, &unstable, None, false);
+        assert!(
+            !parse_reading(&ScaleProtocol::Filizola, &frame)
+                .unwrap()
+                .stable
+        );
+    }
+
+    #[test]
+    fn test_status_responses() {
+        let sensors = PrinterSensorStatus {
+            online: true,
+            paper_out: true,
+            ..Default::default()
+        };
+        let status = crate::hardware::printer_status::parse_dle_eot(
+            dle_eot_response(1, &sensors),
+            dle_eot_response(2, &sensors),
+            dle_eot_response(3, &sensors),
+            dle_eot_response(4, &sensors),
+        )
+        .unwrap();
+        assert_eq!(status, sensors);
+
+        let status = crate::hardware::printer_status::parse_gs_r(
+            gs_r_response(1, &sensors),
+            gs_r_response(2, &sensors),
+        )
+        .unwrap();
+        assert!(status.paper_out);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scale_simulator_round_trip() {
+        use crate::hardware::scale::Scale;
+
+        for protocol in [
+            ScaleProtocol::Toledo,
+            ScaleProtocol::Filizola,
+            ScaleProtocol::Elgin,
+            ScaleProtocol::Urano,
+        ] {
+            let sim = ScaleSimulator::start(protocol.clone(), false).unwrap();
+            sim.set_weight(SimulatedWeight::grams(2500));
+            let scale = Scale::new(sim.scale_config()).unwrap();
+
+            let reading = scale.read_weight().unwrap();
+            assert_eq!(reading.weight_grams, 2500, "{:?}", protocol);
+            assert_eq!(sim.requests(), 1);
+
+            // A porta pode ser reaberta a cada leitura
+            sim.set_weight(SimulatedWeight::grams(750));
+            assert_eq!(scale.read_weight().unwrap().weight_grams, 750);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scale_simulator_price_and_continuous() {
+        use crate::hardware::scale::Scale;
+
+        let sim = ScaleSimulator::start(ScaleProtocol::Toledo, false).unwrap();
+        sim.set_weight(SimulatedWeight::grams(500));
+        let scale = Scale::new(sim.scale_config()).unwrap();
+        scale.send_unit_price(12.5).unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        assert_eq!(sim.unit_price(), Some(12.5));
+
+        let reading = scale.read_weight().unwrap();
+        assert_eq!(reading.total_price, Some(6.25));
+
+        let sim = ScaleSimulator::start(ScaleProtocol::Filizola, true).unwrap();
+        sim.set_weight(SimulatedWeight::grams(1800));
+        let scale = Scale::new(sim.scale_config()).unwrap();
+        assert_eq!(scale.read_weight().unwrap().weight_grams, 1800);
+        assert!(sim.requests() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_printer_simulator_receipt_golden() {
+        let sim = PrinterSimulator::start("127.0.0.1:0").await.unwrap();
+        let mut printer = ThermalPrinter::new(sim.printer_config(&PrinterConfig::default()));
+        printer.print_receipt(&receipt());
+        printer.print().await.unwrap();
+
+        sim.wait_for_jobs(1, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(sim.jobs()[0], printer.get_buffer());
+        let transcript = sim.last_transcript().unwrap();
+        assert!(transcript.starts_with("[INIT]"));
+        assert!(transcript.contains("MERCADINHO GIRO"));
+        assert!(transcript.contains("Açúcar Cristal 1kg"));
+        assert_golden("receipt_48_columns.txt", &transcript);
+
+        let mut printer = ThermalPrinter::new(sim.printer_config(&PrinterConfig {
+            paper_width: 32,
+            ..Default::default()
+        }));
+        printer.build_test_page();
+        printer.print().await.unwrap();
+        sim.wait_for_jobs(2, Duration::from_secs(5)).await.unwrap();
+        assert_golden("test_page_32_columns.txt", &sim.last_transcript().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_printer_simulator_answers_status() {
+        let sim = PrinterSimulator::start("127.0.0.1:0").await.unwrap();
+        sim.set_sensors(PrinterSensorStatus {
+            online: true,
+            paper_near_end: true,
+            cover_open: true,
+            ..Default::default()
+        });
+        let config = sim.printer_config(&PrinterConfig::default());
+
+        let status = tokio::task::spawn_blocking(move || query_printer_sensors(&config))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(status.paper_near_end);
+        assert!(status.cover_open);
+        assert_eq!(
+            status.blocking_problem(),
+            Some("Tampa da impressora aberta")
+        );
+        assert!(sim.jobs().is_empty());
+    }
+}
@@ -29,6 +29,7 @@ async fn main() {
             commands::get_products_paginated,
             commands::get_product_by_id,
             commands::get_product_by_barcode,
+            commands::scan_product_barcode,
             commands::search_products,
             commands::get_low_stock_products,
             commands::create_product,
@@ -91,6 +92,10 @@ async fn main() {
             commands::close_cash_session,
             commands::add_cash_movement,
             commands::get_cash_session_summary,
+            commands::close_cash_session_blind,
+            commands::approve_cash_closing,
+            commands::get_pending_cash_closings,
+            commands::get_cash_closing_report,
             // Stock
             commands::get_recent_stock_movements,
             commands::get_product_stock_movements,
@@ -98,6 +103,9 @@ async fn main() {
             commands::get_product_lots,
             commands::get_expiring_lots,
             commands::get_expired_lots,
+            commands::preview_purchase_nfe,
+            commands::import_purchase_nfe,
+            commands::get_purchase_invoices,
             // Suppliers
             commands::get_suppliers,
             commands::get_supplier_by_id,
@@ -176,6 +184,30 @@ async fn main() {
             commands::get_price_history_by_product,
             commands::get_recent_price_history,
             commands::get_price_history_by_id,
+            // Promoções
+            commands::get_pricing_rules,
+            commands::save_pricing_rule,
+            commands::delete_pricing_rule,
+            commands::preview_sale_pricing,
+            commands::get_customer_price_group,
+            commands::set_customer_price_group,
+            // Devoluções e Trocas
+            commands::get_returnable_sale_items,
+            commands::get_sale_returns,
+            commands::create_sale_return,
+            // Vale-Crédito e Vale-Presente
+            commands::get_store_credit,
+            commands::get_store_credit_by_code,
+            commands::get_customer_store_credits,
+            commands::get_store_credit_transactions,
+            commands::issue_gift_card,
+            commands::set_store_credit_active,
+            // Crediário (Fiado)
+            commands::get_credit_account,
+            commands::save_credit_account,
+            commands::get_customer_statement,
+            commands::get_overdue_receivables,
+            commands::receive_account_payment,
             // System
             commands::get_app_data_path,
             commands::get_database_path,
@@ -213,10 +245,21 @@ async fn main() {
             commands::print_sale_by_id,
             commands::print_service_order,
             commands::print_attendant_order,
+            commands::print_customer_statement,
+            commands::print_store_credit,
+            commands::print_cash_closing_report,
+            commands::list_print_jobs,
+            commands::reprint_print_job,
+            commands::cancel_print_job,
             commands::test_printer,
             commands::test_printer_connection,
             commands::print_test_documents,
             commands::get_printer_config,
+            commands::get_printer_sensors,
+            commands::get_receipt_template,
+            commands::save_receipt_template,
+            commands::preview_receipt_template,
+            commands::set_printer_logo,
             commands::configure_scale,
             commands::load_hardware_configs,
             commands::hardware_health_check,
@@ -236,6 +279,24 @@ async fn main() {
             commands::get_scanner_server_info,
             commands::generate_pairing_qr,
             commands::generate_qr_svg,
+            commands::parse_barcode,
+            commands::start_scale_stream,
+            commands::stop_scale_stream,
+            commands::set_scale_unit_price,
+            commands::get_weighted_barcode_config,
+            commands::save_weighted_barcode_config,
+            commands::get_scale_catalog_config,
+            commands::save_scale_catalog_config,
+            commands::export_scale_catalog,
+            commands::start_demo_hardware,
+            commands::stop_demo_hardware,
+            commands::get_demo_hardware,
+            commands::set_demo_scale_weight,
+            commands::configure_label_printer,
+            commands::get_label_printer_config,
+            commands::test_label_printer,
+            commands::print_product_labels,
+            commands::print_price_change_labels,
             // Backups
             commands::backup::create_backup,
             commands::backup::restore_backup,
@@ -628,6 +689,9 @@ async fn main() {
                 }
             }
 
+            // Retransmissão automática de NFC-e em contingência
+            nfce::retransmission::spawn(handle.clone());
+
             tauri::async_runtime::spawn(async move {
                 let state = handle.state::<AppState>();
                 let hw_state = handle.state::<HardwareState>();
@@ -639,6 +703,11 @@ async fn main() {
                     tracing::error!("Erro ao carregar configurações de hardware: {:?}", e);
                 }
 
+                // 1.1 Fila de impressão persistente (retoma trabalhos pendentes)
+                if let Err(e) = commands::start_print_queue(handle.clone(), &state, &hw_state).await {
+                    tracing::error!("Erro ao iniciar fila de impressão: {:?}", e);
+                }
+
                 // 2. Verificar Auto-start de Rede (Master/Satellite)
                 let settings_repo = giro_lib::repositories::SettingsRepository::new(state.pool());
                 let role = settings_repo.get_value("network.role").await.ok().flatten().unwrap_or_else(|| "STANDALONE".to_string());
@@ -678,6 +747,19 @@ async fn main() {
                     Ok(_) => tracing::info!("✅ Alertas de Garantia: Nenhuma garantia expirando em breve"),
                     Err(e) => tracing::error!("❌ Erro ao verificar garantias de OS: {:?}", e),
                 }
+
+                // 4. Verificar parcelas de crediário (fiado) vencidas
+                match alert_repo.check_overdue_receivables().await {
+                    Ok(count) if count > 0 => {
+                        tracing::info!("✅ Alertas de Fiado: {} clientes com parcelas em atraso", count);
+                        giro_lib::services::NotificationService::alert(
+                            &handle,
+                            &format!("{} cliente(s) com parcelas de fiado em atraso!", count)
+                        );
+                    },
+                    Ok(_) => tracing::info!("✅ Alertas de Fiado: Nenhuma parcela em atraso"),
+                    Err(e) => tracing::error!("❌ Erro ao verificar parcelas em atraso: {:?}", e),
+                }
             });
             tracing::info!("Aplicação inicializada com sucesso");
             Ok(())
@@ -688,6 +770,7 @@ async fn main() {
             commands::get_products_paginated,
             commands::get_product_by_id,
             commands::get_product_by_barcode,
+            commands::scan_product_barcode,
             commands::search_products,
             commands::get_low_stock_products,
             commands::create_product,
@@ -743,6 +826,10 @@ async fn main() {
             commands::close_cash_session,
             commands::add_cash_movement,
             commands::get_cash_session_summary,
+            commands::close_cash_session_blind,
+            commands::approve_cash_closing,
+            commands::get_pending_cash_closings,
+            commands::get_cash_closing_report,
             commands::get_cash_session_history,
             // Estoque
             commands::get_recent_stock_movements,
@@ -751,6 +838,9 @@ async fn main() {
             commands::get_product_lots,
             commands::get_expiring_lots,
             commands::get_expired_lots,
+            commands::preview_purchase_nfe,
+            commands::import_purchase_nfe,
+            commands::get_purchase_invoices,
             // Alertas
             commands::get_alerts,
             commands::get_unread_alerts,
@@ -819,10 +909,21 @@ async fn main() {
             commands::print_sale_by_id,
             commands::print_service_order,
             commands::print_attendant_order,
+            commands::print_customer_statement,
+            commands::print_store_credit,
+            commands::print_cash_closing_report,
+            commands::list_print_jobs,
+            commands::reprint_print_job,
+            commands::cancel_print_job,
             commands::test_printer,
             commands::test_printer_connection,
             commands::print_test_documents,
             commands::get_printer_config,
+            commands::get_printer_sensors,
+            commands::get_receipt_template,
+            commands::save_receipt_template,
+            commands::preview_receipt_template,
+            commands::set_printer_logo,
             commands::configure_scale,
             commands::load_hardware_configs,
             commands::hardware_health_check,
@@ -842,6 +943,24 @@ async fn main() {
             commands::get_scanner_server_info,
             commands::generate_pairing_qr,
             commands::generate_qr_svg,
+            commands::parse_barcode,
+            commands::start_scale_stream,
+            commands::stop_scale_stream,
+            commands::set_scale_unit_price,
+            commands::get_weighted_barcode_config,
+            commands::save_weighted_barcode_config,
+            commands::get_scale_catalog_config,
+            commands::save_scale_catalog_config,
+            commands::export_scale_catalog,
+            commands::start_demo_hardware,
+            commands::stop_demo_hardware,
+            commands::get_demo_hardware,
+            commands::set_demo_scale_weight,
+            commands::configure_label_printer,
+            commands::get_label_printer_config,
+            commands::test_label_printer,
+            commands::print_product_labels,
+            commands::print_price_change_labels,
             commands::start_mobile_server,
             commands::stop_mobile_server,
             commands::get_mobile_server_info,
@@ -859,6 +978,30 @@ async fn main() {
             commands::get_price_history_by_product,
             commands::get_recent_price_history,
             commands::get_price_history_by_id,
+            // Promoções
+            commands::get_pricing_rules,
+            commands::save_pricing_rule,
+            commands::delete_pricing_rule,
+            commands::preview_sale_pricing,
+            commands::get_customer_price_group,
+            commands::set_customer_price_group,
+            // Devoluções e Trocas
+            commands::get_returnable_sale_items,
+            commands::get_sale_returns,
+            commands::create_sale_return,
+            // Vale-Crédito e Vale-Presente
+            commands::get_store_credit,
+            commands::get_store_credit_by_code,
+            commands::get_customer_store_credits,
+            commands::get_store_credit_transactions,
+            commands::issue_gift_card,
+            commands::set_store_credit_active,
+            // Crediário (Fiado)
+            commands::get_credit_account,
+            commands::save_credit_account,
+            commands::get_customer_statement,
+            commands::get_overdue_receivables,
+            commands::receive_account_payment,
             // Veículos (Motopeças)
             commands::get_vehicle_brands,
             commands::get_vehicle_brand_by_id,
@@ -934,10 +1077,23 @@ async fn main() {
             // NFC-e
             nfce::commands::emit_nfce,
             nfce::commands::check_sefaz_status,
+            nfce::commands::reprint_danfe,
             nfce::commands::list_offline_notes,
             nfce::commands::transmit_offline_note,
+            nfce::commands::cancel_nfce,
+            nfce::commands::inutilize_nfce_numbers,
+            nfce::commands::export_fiscal_documents,
             nfce::commands::get_fiscal_settings,
             nfce::commands::update_fiscal_settings,
+            nfce::commands::list_tax_rules,
+            nfce::commands::save_tax_rule,
+            nfce::commands::delete_tax_rule,
+            nfce::commands::assign_tax_rule,
+            nfce::commands::import_ibpt_table,
+            nfce::commands::emit_nfe,
+            nfce::commands::get_customer_fiscal_data,
+            nfce::commands::update_customer_fiscal_data,
+            nfce::commands::set_product_ncm,
             // License
             commands::get_hardware_id,
             commands::activate_license,
@@ -20,6 +20,7 @@ pub enum AuditAction {
     // Vendas
     SaleCreated,
     SaleCanceled,
+    SaleReturned,
     HeldSaleCreated,
     HeldSaleUpdated,
     HeldSaleResumed,
@@ -29,6 +30,7 @@ pub enum AuditAction {
     // Caixa
     CashSessionOpened,
     CashSessionClosed,
+    CashClosingApproved,
     CashWithdrawal,
     CashDeposit,
     CashSupply,
@@ -49,6 +51,10 @@ pub enum AuditAction {
     CustomerCreated,
     CustomerUpdated,
     CustomerDeleted,
+    CreditAccountUpdated,
+    AccountPaymentReceived,
+    GiftCardIssued,
+    StoreCreditBlocked,
 
     // Funcionários
     EmployeeCreated,
@@ -37,6 +37,7 @@ pub enum Permission {
     ViewCashMovements,
     CreateCashMovement,
     ManageCash,
+    ApproveCashClosing,
 
     // Funcionários
     ViewEmployees,
@@ -106,6 +107,7 @@ impl Permission {
                     Permission::ViewCashMovements,
                     Permission::CreateCashMovement,
                     Permission::ManageCash,
+                    Permission::ApproveCashClosing,
                     Permission::ViewEmployees,
                     Permission::CreateEmployees,
                     Permission::UpdateEmployees,
@@ -152,6 +154,7 @@ impl Permission {
                     Permission::ViewCashMovements,
                     Permission::CreateCashMovement,
                     Permission::ManageCash,
+                    Permission::ApproveCashClosing,
                     Permission::ViewEmployees,
                     Permission::ViewReports,
                     Permission::ExportReports,
@@ -387,6 +390,18 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_only_managers_approve_cash_closing() {
+        assert!(Permission::has_permission(
+            EmployeeRole::Manager,
+            Permission::ApproveCashClosing
+        ));
+        assert!(!Permission::has_permission(
+            EmployeeRole::Cashier,
+            Permission::ApproveCashClosing
+        ));
+    }
+
     #[test]
     fn test_viewer_readonly() {
         assert!(Permission::has_permission(
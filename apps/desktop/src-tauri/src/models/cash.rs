@@ -1,5 +1,6 @@
 //! Modelos de Caixa (Sessão e Movimentações)
 
+use super::sale::PaymentMethod;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use sqlx::FromRow;
@@ -16,8 +17,11 @@ pub struct CashSession {
     pub expected_balance: Option<f64>,
     pub actual_balance: Option<f64>,
     pub difference: Option<f64>,
-    pub status: String,
+    pub status: String, // OPEN, PENDING_APPROVAL, CLOSED
     pub notes: Option<String>,
+    /// Gerente que aprovou a diferença do fechamento
+    pub approved_by_id: Option<String>,
+    pub approved_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -66,8 +70,113 @@ pub struct CashSessionSummary {
     pub total_canceled: f64,
     pub total_withdrawals: f64,
     pub total_supplies: f64,
+    /// Estornos de devoluções feitos na sessão (todas as formas)
+    #[serde(default)]
+    pub total_refunds: f64,
+    /// Recebimentos de crediário (fiado) na sessão, com encargos
+    #[serde(default)]
+    pub total_account_receipts: f64,
+    /// Vale-presentes vendidos na sessão
+    #[serde(default)]
+    pub total_gift_card_sales: f64,
     #[specta(type = i32)]
     pub movement_count: i64,
     pub sales_by_method: Vec<crate::models::PaymentMethodSummary>,
-    pub cash_in_drawer: f64, // Opening + Supply - Bleed + Cash Sales
+    pub cash_in_drawer: f64, // Opening + Supply - Bleed + Cash Sales + Cash Receipts + Cash Gift Cards - Cash Refunds
+    /// Esperado por forma de pagamento (base do fechamento e do relatório Z)
+    #[serde(default)]
+    pub by_method: Vec<CashMethodBreakdown>,
+}
+
+impl CashSessionSummary {
+    /// Fechamento cego: zera tudo que permite deduzir o valor esperado na gaveta
+    pub fn hide_expected(&mut self) {
+        self.total_sales = 0.0;
+        self.total_refunds = 0.0;
+        self.total_account_receipts = 0.0;
+        self.total_gift_card_sales = 0.0;
+        self.cash_in_drawer = 0.0;
+        self.sales_by_method.clear();
+        self.by_method.clear();
+    }
+}
+
+/// Movimento da sessão em uma forma de pagamento
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CashMethodBreakdown {
+    pub method: String,
+    pub sales: f64,
+    /// Recebimentos de fiado e vale-presentes vendidos
+    pub receipts: f64,
+    pub refunds: f64,
+    /// Em dinheiro inclui abertura, suprimentos e sangrias
+    pub expected: f64,
+}
+
+// ════════════════════════════════════════════════════════════════════════════
+// FECHAMENTO CEGO
+// ════════════════════════════════════════════════════════════════════════════
+
+/// Quantidade contada de uma cédula ou moeda
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DenominationCount {
+    pub denomination: f64,
+    pub quantity: u32,
+}
+
+/// Total apurado de uma forma de pagamento eletrônica (PIX, cartões, vale)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MethodCount {
+    pub method: PaymentMethod,
+    pub amount: f64,
+}
+
+/// Para fechar o caixa sem ver o valor esperado
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BlindCashClosing {
+    pub session_id: String,
+    pub denominations: Vec<DenominationCount>,
+    pub method_counts: Vec<MethodCount>,
+    pub notes: Option<String>,
+}
+
+/// Contagem gravada no fechamento, por forma de pagamento
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CashClosingCount {
+    pub id: String,
+    pub session_id: String,
+    pub method: String,
+    pub expected_amount: f64,
+    pub counted_amount: f64,
+    pub difference: f64,
+    pub created_at: String,
+}
+
+/// Cédulas e moedas contadas no fechamento
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CashClosingDenomination {
+    pub id: String,
+    pub session_id: String,
+    pub denomination: f64,
+    #[specta(type = i32)]
+    pub quantity: i64,
+    pub amount: f64,
+}
+
+/// Relatório de fechamento (Redução Z do caixa)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CashClosingReport {
+    pub summary: CashSessionSummary,
+    pub employee_name: String,
+    pub approved_by_name: Option<String>,
+    pub counts: Vec<CashClosingCount>,
+    pub denominations: Vec<CashClosingDenomination>,
+    pub tolerance: f64,
 }
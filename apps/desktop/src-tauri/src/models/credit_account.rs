@@ -0,0 +1,122 @@
+//! Modelos de Crediário (Fiado)
+
+use super::sale::PaymentMethod;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sqlx::FromRow;
+
+/// Conta de crediário do cliente: limite e regras de cobrança
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerCreditAccount {
+    pub customer_id: String,
+    pub credit_limit: f64,
+    /// Juros de mora em % ao mês (pro rata dia)
+    pub interest_rate: f64,
+    /// Multa em % sobre a parcela em atraso
+    pub fine_percent: f64,
+    /// Dias após o vencimento sem encargos
+    pub grace_days: i32,
+    pub default_installments: i32,
+    pub installment_interval_days: i32,
+    pub is_blocked: bool,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Para criar ou atualizar o crediário do cliente
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveCreditAccount {
+    pub customer_id: String,
+    pub credit_limit: f64,
+    pub interest_rate: f64,
+    pub fine_percent: f64,
+    pub grace_days: i32,
+    pub default_installments: i32,
+    pub installment_interval_days: i32,
+    pub is_blocked: bool,
+    pub notes: Option<String>,
+}
+
+/// Parcela a receber (gerada por venda fiado)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Receivable {
+    pub id: String,
+    pub customer_id: String,
+    pub sale_id: Option<String>,
+    pub installment_number: i32,
+    pub installment_count: i32,
+    pub amount: f64,
+    pub paid_amount: f64,
+    pub due_date: String,
+    pub status: String, // OPEN, PAID, CANCELED
+    pub interest_paid_until: Option<String>,
+    pub fine_charged: bool,
+    pub paid_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Encargos calculados para hoje (não persistidos)
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub days_overdue: i32,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub fine: f64,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub interest: f64,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub amount_due: f64,
+}
+
+/// Recebimento de parcela no caixa
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceivablePayment {
+    pub id: String,
+    pub customer_id: String,
+    pub receivable_id: String,
+    /// Principal abatido
+    pub amount: f64,
+    pub interest: f64,
+    pub fine: f64,
+    pub total: f64,
+    pub method: String,
+    pub cash_session_id: Option<String>,
+    pub employee_id: String,
+    pub created_at: String,
+}
+
+/// Para receber pagamento do crediário (quita as parcelas mais antigas primeiro)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiveAccountPayment {
+    pub customer_id: String,
+    pub amount: f64,
+    pub method: PaymentMethod,
+    pub employee_id: String,
+    pub cash_session_id: Option<String>,
+}
+
+/// Extrato do crediário do cliente
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerStatement {
+    pub customer_id: String,
+    pub customer_name: String,
+    pub account: Option<CustomerCreditAccount>,
+    /// Principal em aberto
+    pub balance: f64,
+    /// Multa e juros calculados para hoje
+    pub charges: f64,
+    pub total_due: f64,
+    pub overdue_amount: f64,
+    pub available_credit: f64,
+    pub open_receivables: Vec<Receivable>,
+    pub recent_payments: Vec<ReceivablePayment>,
+    pub generated_at: String,
+}
@@ -13,6 +13,9 @@ pub struct FiscalSettings {
     pub csc: Option<String>,
     pub cert_path: Option<String>,
     pub cert_password: Option<String>,
+    pub crt: i32, // 1=Simples Nacional, 3=Regime Normal
+    pub nfe_serie: i32,
+    pub nfe_next_number: i32,
     pub updated_at: Option<String>,
 }
 
@@ -28,4 +31,184 @@ pub struct UpdateFiscalSettings {
     pub csc: Option<String>,
     pub cert_path: Option<String>,
     pub cert_password: Option<String>,
+    pub crt: Option<i32>,
+    pub nfe_serie: Option<i32>,
+    pub nfe_next_number: Option<i32>,
+}
+
+/// NFC-e vinculada a uma venda
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct SaleFiscalLink {
+    pub sale_id: String,
+    pub nfce_access_key: Option<String>,
+    pub nfce_protocol: Option<String>,
+    pub nfce_authorized_at: Option<String>,
+    pub nfce_cancel_protocol: Option<String>,
+}
+
+/// Evento fiscal registrado (cancelamento ou inutilização)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct FiscalEvent {
+    pub id: String,
+    pub sale_id: Option<String>,
+    pub access_key: Option<String>,
+    pub event_type: String,
+    pub sequence: i32,
+    pub serie: Option<i32>,
+    pub number_start: Option<i32>,
+    pub number_end: Option<i32>,
+    pub justification: String,
+    pub status_code: Option<String>,
+    pub status_message: Option<String>,
+    pub protocol: Option<String>,
+    pub xml: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CreateFiscalEvent {
+    pub sale_id: Option<String>,
+    pub access_key: Option<String>,
+    pub event_type: String,
+    pub sequence: i32,
+    pub serie: Option<i32>,
+    pub number_start: Option<i32>,
+    pub number_end: Option<i32>,
+    pub justification: String,
+    pub status_code: Option<String>,
+    pub status_message: Option<String>,
+    pub protocol: Option<String>,
+    pub xml: Option<String>,
+}
+
+/// Documento fiscal emitido (NFC-e/NF-e) com o XML armazenado
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct FiscalDocument {
+    pub id: String,
+    pub sale_id: Option<String>,
+    pub service_order_id: Option<String>,
+    pub access_key: String,
+    pub model: i32,
+    pub serie: i32,
+    pub number: i32,
+    pub emission_type: i32,
+    pub environment: i32,
+    pub status: String,
+    pub protocol: Option<String>,
+    pub total: f64,
+    #[serde(skip_serializing)]
+    pub xml: String,
+    pub issued_at: String,
+    pub authorized_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateFiscalDocument {
+    pub sale_id: Option<String>,
+    pub service_order_id: Option<String>,
+    pub access_key: String,
+    pub model: i32,
+    pub serie: i32,
+    pub number: i32,
+    pub emission_type: i32,
+    pub environment: i32,
+    pub status: String,
+    pub protocol: Option<String>,
+    pub total: f64,
+    pub xml: String,
+    pub issued_at: String,
+}
+
+/// Regra tributária (ICMS/PIS/COFINS) aplicada aos itens da nota
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxRule {
+    pub id: String,
+    pub name: String,
+    pub crt: i32,
+    pub ncm: Option<String>,
+    pub cfop: Option<String>,
+    pub icms_origin: i32,
+    pub icms_code: String, // CSOSN (Simples) ou CST (Regime Normal)
+    pub icms_rate: f64,
+    pub icms_base_reduction: f64,
+    pub st_mva: f64,
+    pub st_rate: f64,
+    pub fcp_rate: f64,
+    pub pis_cst: String,
+    pub pis_rate: f64,
+    pub cofins_cst: String,
+    pub cofins_rate: f64,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Para criar ou atualizar regra tributária
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveTaxRule {
+    pub id: Option<String>,
+    pub name: String,
+    pub crt: i32,
+    pub ncm: Option<String>,
+    pub cfop: Option<String>,
+    pub icms_origin: i32,
+    pub icms_code: String,
+    #[serde(default)]
+    pub icms_rate: f64,
+    #[serde(default)]
+    pub icms_base_reduction: f64,
+    #[serde(default)]
+    pub st_mva: f64,
+    #[serde(default)]
+    pub st_rate: f64,
+    #[serde(default)]
+    pub fcp_rate: f64,
+    pub pis_cst: String,
+    #[serde(default)]
+    pub pis_rate: f64,
+    pub cofins_cst: String,
+    #[serde(default)]
+    pub cofins_rate: f64,
+}
+
+/// Alíquotas aproximadas da tabela IBPT por NCM (Lei 12.741/2012)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct IbptRate {
+    pub ncm: String,
+    pub description: Option<String>,
+    pub federal_rate: f64,
+    pub import_rate: f64,
+    pub state_rate: f64,
+    pub municipal_rate: f64,
+    pub valid_until: Option<String>,
+    pub version: Option<String>,
+    pub source: Option<String>,
+}
+
+/// Dados fiscais do cliente usados no grupo `dest` da NF-e
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerFiscalData {
+    pub customer_id: String,
+    pub cnpj: Option<String>,
+    pub state_registration: Option<String>, // IE ou "ISENTO"
+    pub city_code: Option<String>,          // Código IBGE do município
+}
+
+/// Dados do produto necessários ao item da NF-e
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductFiscalInfo {
+    pub id: String,
+    pub internal_code: String,
+    pub barcode: Option<String>,
+    pub ncm: Option<String>,
 }
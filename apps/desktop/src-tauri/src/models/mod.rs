@@ -7,6 +7,7 @@ use specta::Type;
 pub mod alert;
 pub mod cash;
 pub mod category;
+pub mod credit_account;
 pub mod customer;
 pub mod employee;
 pub mod enterprise;
@@ -14,11 +15,16 @@ pub mod fiscal;
 pub mod held_sale;
 pub mod inventory;
 pub mod price_history;
+pub mod pricing_rule;
+pub mod print_job;
 pub mod product;
+pub mod purchase;
 pub mod sale;
+pub mod sale_return;
 pub mod service_order;
 pub mod settings;
 pub mod stock;
+pub mod store_credit;
 pub mod supplier;
 pub mod vehicle;
 pub mod warranty;
@@ -26,6 +32,7 @@ pub mod warranty;
 pub use alert::*;
 pub use cash::*;
 pub use category::*;
+pub use credit_account::*;
 pub use customer::*;
 pub use employee::*;
 pub use enterprise::*;
@@ -33,11 +40,16 @@ pub use fiscal::*;
 pub use held_sale::*;
 pub use inventory::*;
 pub use price_history::*;
+pub use pricing_rule::*;
+pub use print_job::*;
 pub use product::*;
+pub use purchase::*;
 pub use sale::*;
+pub use sale_return::*;
 pub use service_order::*;
 pub use settings::*;
 pub use stock::*;
+pub use store_credit::*;
 pub use supplier::*;
 pub use vehicle::*;
 pub use warranty::*;
@@ -11,6 +11,7 @@ pub struct PriceHistory {
     pub product_id: String,
     pub old_price: f64,
     pub new_price: f64,
+    pub price_type: String, // SALE | COST
     pub reason: Option<String>,
     pub employee_id: Option<String>,
     pub created_at: String,
@@ -35,6 +36,7 @@ pub struct PriceHistoryWithProduct {
     pub product_id: String,
     pub old_price: f64,
     pub new_price: f64,
+    pub price_type: String,
     pub reason: Option<String>,
     pub employee_id: Option<String>,
     pub created_at: String,
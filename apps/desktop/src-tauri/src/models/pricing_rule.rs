@@ -0,0 +1,89 @@
+//! Modelos de Regras de Preço (Promoções)
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sqlx::FromRow;
+
+/// Regra de preço/promoção avaliada na criação da venda
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingRule {
+    pub id: String,
+    pub name: String,
+    pub kind: String, // BUY_X_PAY_Y, NTH_ITEM_PERCENT, COMBO_PRICE, HAPPY_HOUR, CATEGORY_PERCENT, CUSTOMER_GROUP_PRICE, WHOLESALE
+    pub product_id: Option<String>,
+    pub category_id: Option<String>,
+    pub customer_group: Option<String>,
+    pub quantity: f64,
+    pub pay_quantity: f64,
+    pub percent: f64,
+    pub price: Option<f64>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub weekdays: Option<String>,
+    pub valid_from: Option<String>,
+    pub valid_until: Option<String>,
+    pub priority: i32,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub items: Vec<PricingRuleItem>,
+}
+
+/// Produto que compõe um combo/kit
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingRuleItem {
+    pub product_id: String,
+    pub quantity: f64,
+}
+
+/// Para criar ou atualizar regra de preço
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SavePricingRule {
+    pub id: Option<String>,
+    pub name: String,
+    pub kind: String,
+    pub product_id: Option<String>,
+    pub category_id: Option<String>,
+    pub customer_group: Option<String>,
+    #[serde(default)]
+    pub quantity: f64,
+    #[serde(default)]
+    pub pay_quantity: f64,
+    #[serde(default)]
+    pub percent: f64,
+    pub price: Option<f64>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub weekdays: Option<String>,
+    pub valid_from: Option<String>,
+    pub valid_until: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub items: Vec<PricingRuleItem>,
+}
+
+/// Promoção aplicada a um item do carrinho
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemPromotion {
+    /// Posição do item na lista enviada
+    pub item_index: u32,
+    pub rule_id: String,
+    /// Motivo impresso no cupom (nome da regra)
+    pub reason: String,
+    pub discount: f64,
+}
+
+/// Resultado da avaliação das promoções de um carrinho
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SalePricing {
+    pub items: Vec<ItemPromotion>,
+    pub total_discount: f64,
+}
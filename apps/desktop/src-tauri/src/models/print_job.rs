@@ -0,0 +1,22 @@
+//! Modelos de Fila de Impressão
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sqlx::FromRow;
+
+/// Trabalho da fila de impressão (sem os bytes ESC/POS)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintJob {
+    pub id: String,
+    pub kind: String, // RECEIPT, SERVICE_ORDER, ATTENDANT_ORDER, DANFE, TEST
+    pub reference: Option<String>,
+    pub status: String, // PENDING, PRINTING, DONE, FAILED, CANCELED
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: String,
+    pub printed_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
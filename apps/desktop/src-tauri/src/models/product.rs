@@ -120,6 +120,18 @@ pub struct ProductWithCategory {
     pub category_color: Option<String>,
 }
 
+/// Produto localizado pelo código lido no PDV
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScannedProduct {
+    pub product: Product,
+    /// 1 para códigos comuns; peso em kg nos códigos pesados
+    pub quantity: f64,
+    /// Preço total impresso na etiqueta da balança (código de preço)
+    pub total_price: Option<f64>,
+    pub weighted: Option<crate::hardware::WeightedBarcode>,
+}
+
 /// Para criar produto
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,138 @@
+//! Modelos de Entrada por NF-e de Compra
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sqlx::FromRow;
+
+/// NF-e de compra já lançada no estoque
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseInvoice {
+    pub id: String,
+    pub access_key: String,
+    pub supplier_id: String,
+    pub number: i32,
+    pub serie: i32,
+    pub issued_at: String,
+    pub total: f64,
+    pub items_imported: i32,
+    pub items_skipped: i32,
+    pub employee_id: Option<String>,
+    pub created_at: String,
+}
+
+/// Código do produto no cadastro do fornecedor
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplierProductCode {
+    pub product_id: String,
+    pub supplier_id: String,
+    pub supplier_code: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Lote do item (grupo `rastro`)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseLotInfo {
+    pub lot_number: Option<String>,
+    pub quantity: f64,
+    pub manufacturing_date: Option<String>,
+    pub expiration_date: Option<String>,
+}
+
+/// Item da nota com o produto sugerido
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchasePreviewItem {
+    pub item_number: i32,
+    pub supplier_code: String,
+    pub ean: Option<String>,
+    pub description: String,
+    pub ncm: Option<String>,
+    pub unit: String,
+    pub quantity: f64,
+    pub unit_cost: f64,
+    pub total_cost: f64,
+    pub lots: Vec<PurchaseLotInfo>,
+    pub product_id: Option<String>,
+    pub product_name: Option<String>,
+    pub match_source: Option<String>, // SUPPLIER_CODE | EAN
+}
+
+/// Prévia da importação (não altera o banco)
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseImportPreview {
+    pub access_key: String,
+    pub number: i32,
+    pub serie: i32,
+    pub issued_at: String,
+    pub total: f64,
+    pub supplier_cnpj: String,
+    pub supplier_name: String,
+    pub supplier_id: Option<String>, // Ausente = fornecedor será cadastrado
+    pub already_imported: bool,
+    pub items: Vec<PurchasePreviewItem>,
+}
+
+/// Produto escolhido para cada item (ausente = item ignorado)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseItemMatch {
+    pub item_number: i32,
+    pub product_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPurchaseNfe {
+    pub file_path: String,
+    pub matches: Vec<PurchaseItemMatch>,
+    /// Memoriza o vínculo código do fornecedor → produto para as próximas notas
+    #[serde(default = "default_true")]
+    pub remember_codes: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Entrada de um item já resolvido para gravação
+#[derive(Debug, Clone)]
+pub struct PurchaseEntry {
+    pub product_id: String,
+    pub supplier_code: String,
+    pub quantity: f64,
+    pub unit_cost: f64,
+    pub lots: Vec<PurchaseLotInfo>,
+}
+
+/// Dados da nota para gravação
+#[derive(Debug, Clone)]
+pub struct CreatePurchaseInvoice {
+    pub access_key: String,
+    pub supplier_id: String,
+    pub number: i32,
+    pub serie: i32,
+    pub issued_at: String,
+    pub total: f64,
+    pub xml: String,
+    pub employee_id: Option<String>,
+    pub entries: Vec<PurchaseEntry>,
+    pub items_skipped: i32,
+    pub remember_codes: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseImportResult {
+    pub invoice_id: String,
+    pub supplier_id: String,
+    pub supplier_created: bool,
+    pub items_imported: i32,
+    pub items_skipped: i32,
+    pub lots_created: i32,
+    pub cost_changes: i32,
+}
@@ -16,6 +16,45 @@ pub enum PaymentMethod {
     Debit,
     Voucher,
     Other,
+    /// Fiado: lançado no crediário do cliente
+    Account,
+    /// Vale-crédito ou vale-presente com saldo
+    StoreCredit,
+    /// Valor de itens devolvidos abatido na venda da troca (não entra no caixa)
+    ExchangeCredit,
+}
+
+impl PaymentMethod {
+    /// Código persistido (o mesmo da serialização)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cash => "CASH",
+            Self::Pix => "PIX",
+            Self::Credit => "CREDIT",
+            Self::Debit => "DEBIT",
+            Self::Voucher => "VOUCHER",
+            Self::Other => "OTHER",
+            Self::Account => "ACCOUNT",
+            Self::StoreCredit => "STORE_CREDIT",
+            Self::ExchangeCredit => "EXCHANGE_CREDIT",
+        }
+    }
+
+    /// Inverso de `as_str`
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "CASH" => Some(Self::Cash),
+            "PIX" => Some(Self::Pix),
+            "CREDIT" => Some(Self::Credit),
+            "DEBIT" => Some(Self::Debit),
+            "VOUCHER" => Some(Self::Voucher),
+            "OTHER" => Some(Self::Other),
+            "ACCOUNT" => Some(Self::Account),
+            "STORE_CREDIT" => Some(Self::StoreCredit),
+            "EXCHANGE_CREDIT" => Some(Self::ExchangeCredit),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for PaymentMethod {
@@ -27,6 +66,9 @@ impl std::fmt::Display for PaymentMethod {
             Self::Debit => write!(f, "Débito"),
             Self::Voucher => write!(f, "Vale"),
             Self::Other => write!(f, "Outro"),
+            Self::Account => write!(f, "Fiado"),
+            Self::StoreCredit => write!(f, "Vale-Crédito"),
+            Self::ExchangeCredit => write!(f, "Crédito de Troca"),
         }
     }
 }
@@ -85,15 +127,40 @@ pub struct SalePayment {
     pub sale_id: String,
     pub method: PaymentMethod,
     pub amount: f64,
+    /// 1=TEF, 2=POS (somente cartão)
+    pub card_integration: Option<u8>,
+    pub acquirer_cnpj: Option<String>,
+    pub card_brand: Option<String>,
+    pub authorization_code: Option<String>,
     pub created_at: String,
 }
 
+/// Dados do cartão informados pelo TEF/POS
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SalePaymentCard {
+    /// 1=TEF, 2=POS (padrão)
+    pub card_integration: Option<u8>,
+    pub acquirer_cnpj: Option<String>,
+    pub card_brand: Option<String>,
+    pub authorization_code: Option<String>,
+}
+
 /// Dados para registro de pagamento
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateSalePayment {
     pub method: PaymentMethod,
     pub amount: f64,
+    /// Parcelas do fiado (padrão do crediário do cliente quando ausente)
+    #[serde(default)]
+    pub installments: Option<u32>,
+    /// Código do vale (obrigatório para vale-crédito)
+    #[serde(default)]
+    pub store_credit_code: Option<String>,
+    /// Cartão: integração, credenciadora, bandeira e autorização
+    #[serde(default)]
+    pub card: Option<SalePaymentCard>,
 }
 
 /// Venda com informações relacionadas
@@ -123,6 +190,8 @@ pub struct SaleItem {
     pub product_barcode: Option<String>,
     pub product_unit: String,
     pub lot_id: Option<String>,
+    pub pricing_rule_id: Option<String>,
+    pub discount_reason: Option<String>,
     pub created_at: String,
 }
 
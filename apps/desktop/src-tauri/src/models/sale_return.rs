@@ -0,0 +1,88 @@
+//! Modelos de Devolução e Troca
+
+use super::sale::{CreateSale, PaymentMethod};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sqlx::FromRow;
+
+/// Devolução (parcial ou total) de uma venda
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaleReturn {
+    pub id: String,
+    pub sale_id: String,
+    pub customer_id: Option<String>,
+    pub employee_id: String,
+    pub cash_session_id: Option<String>,
+    pub total: f64,
+    pub refund_type: String, // PAYMENT, STORE_CREDIT
+    pub refund_method: Option<String>,
+    pub refund_amount: f64,
+    pub exchange_sale_id: Option<String>,
+    pub store_credit_id: Option<String>,
+    pub reason: Option<String>,
+    pub fiscal_access_key: Option<String>,
+    pub created_at: String,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub items: Vec<SaleReturnItem>,
+}
+
+/// Item devolvido, ligado ao item da venda original
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SaleReturnItem {
+    pub id: String,
+    pub return_id: String,
+    pub sale_item_id: String,
+    pub product_id: String,
+    pub product_name: String,
+    pub lot_id: Option<String>,
+    pub quantity: f64,
+    pub unit_price: f64,
+    pub total: f64,
+    pub restocked: bool,
+    pub created_at: String,
+}
+
+/// Item a devolver
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSaleReturnItem {
+    pub sale_item_id: String,
+    pub quantity: f64,
+    /// Lote de destino (padrão: lote de onde o item saiu)
+    pub lot_id: Option<String>,
+    /// `false` para item avariado, que não volta ao estoque
+    pub restock: Option<bool>,
+}
+
+/// Para registrar devolução ou troca
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSaleReturn {
+    pub sale_id: String,
+    pub items: Vec<CreateSaleReturnItem>,
+    /// PAYMENT ou STORE_CREDIT
+    pub refund_type: String,
+    /// Forma do estorno quando PAYMENT
+    pub refund_method: Option<PaymentMethod>,
+    pub reason: Option<String>,
+    pub employee_id: String,
+    pub cash_session_id: Option<String>,
+    /// Troca: venda nova abatida do valor devolvido (pagamentos cobrem só a diferença)
+    pub exchange: Option<CreateSale>,
+}
+
+/// Quantidade ainda devolvível de cada item da venda
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnableSaleItem {
+    pub sale_item_id: String,
+    pub product_id: String,
+    pub product_name: String,
+    pub lot_id: Option<String>,
+    pub sold_quantity: f64,
+    pub returned_quantity: f64,
+    pub unit_price: f64,
+}
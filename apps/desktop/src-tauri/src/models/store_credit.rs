@@ -0,0 +1,56 @@
+//! Modelos de Vale-Crédito e Vale-Presente
+
+use super::sale::PaymentMethod;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use sqlx::FromRow;
+
+/// Vale com saldo (código EAN-13 impresso no recibo)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreCredit {
+    pub id: String,
+    pub code: String,
+    pub kind: String, // STORE_CREDIT (devolução), GIFT_CARD (vale-presente)
+    pub customer_id: Option<String>,
+    pub return_id: Option<String>,
+    pub amount: f64,
+    pub balance: f64,
+    pub is_active: bool,
+    pub expires_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Movimentação do saldo do vale
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreCreditTransaction {
+    pub id: String,
+    pub store_credit_id: String,
+    #[sqlx(rename = "type")]
+    #[serde(rename = "type")]
+    pub transaction_type: String, // ISSUE, REDEEM, REVERSAL
+    /// Positivo credita, negativo debita
+    pub amount: f64,
+    pub balance_after: f64,
+    pub sale_id: Option<String>,
+    pub return_id: Option<String>,
+    pub payment_method: Option<String>,
+    pub cash_session_id: Option<String>,
+    pub employee_id: Option<String>,
+    pub created_at: String,
+}
+
+/// Para vender um vale-presente no caixa
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueGiftCard {
+    pub amount: f64,
+    pub customer_id: Option<String>,
+    /// Validade (YYYY-MM-DD)
+    pub expires_at: Option<String>,
+    pub payment_method: PaymentMethod,
+    pub employee_id: String,
+    pub cash_session_id: Option<String>,
+}
@@ -3,15 +3,26 @@
 // ════════════════════════════════════════════════════════════════════════════
 //! Comandos expostos para o frontend
 
-use chrono::Utc;
+use chrono::{Datelike, Utc};
 use serde::{Deserialize, Serialize};
 use tauri::{command, State};
 
-use crate::models::{FiscalSettings, UpdateFiscalSettings};
+use crate::commands::hardware::HardwareState;
+use crate::hardware::print_queue::PrintJobKind;
+use crate::models::{
+    CreateFiscalDocument, CreateFiscalEvent, CustomerFiscalData, FiscalDocument, FiscalEvent,
+    FiscalSettings, SaveTaxRule, TaxRule, UpdateFiscalSettings,
+};
 use crate::nfce::contingency::ContingencyManager;
+use crate::nfce::event::{self, CancellationEvent, Inutilization};
+use crate::nfce::export::{write_month_archive, FiscalExportSummary};
+use crate::nfce::nfe_proc::{build_event_proc, build_nfe_proc, ProtocolInfo};
+use crate::nfce::webservice::AuthorizationResponse;
 use crate::nfce::{
-    AccessKey, Certificate, DanfeData, DanfeItem, DanfePrinter, Environment, NfceData, NfceItem,
-    NfceXmlBuilder, QrCodeGenerator, QrCodeParams, SefazClient, XmlSigner,
+    validate_nfe_xml, AccessKey, Certificate, DanfeA4, DanfeData, DanfeItem, DanfeLayout,
+    DanfePayment, DanfePrinter, Environment, IbptRates, NfceCardInfo, NfceData, NfceItem,
+    NfcePayment, NfceXmlBuilder, NfeAddress, NfeCarrier, NfeFreight, NfeRecipient, NfeVolumes,
+    QrCodeGenerator, QrCodeParams, SefazClient, TaxProfile, ValidationIssue, XmlSigner,
 };
 use crate::AppState;
 
@@ -23,8 +34,14 @@ pub struct EmitNfceRequest {
     pub items: Vec<EmissionItem>,
     pub total: f64,
     pub discount: f64,
+    // Pagamento único (legado) - usado apenas se `payments` vier vazio e não houver sale_id
+    #[serde(default)]
     pub payment_method: String,
+    #[serde(default)]
     pub payment_value: f64,
+    // Pagamentos múltiplos (um detPag por entrada)
+    #[serde(default)]
+    pub payments: Vec<EmissionPayment>,
 
     // Configuração Emitente (provavelmente viria do banco em prod real, mas passamos aqui para flexibilidade)
     pub emitter_cnpj: String,
@@ -32,6 +49,8 @@ pub struct EmitNfceRequest {
     pub emitter_name: String,
     pub emitter_trade_name: Option<String>,
     pub emitter_address: String,
+    #[serde(default)]
+    pub emitter_district: Option<String>,
     pub emitter_city: String,
     pub emitter_city_code: String,
     pub emitter_state: String,
@@ -56,6 +75,8 @@ pub struct EmitNfceRequest {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EmissionItem {
+    #[serde(default)]
+    pub product_id: Option<String>,
     pub code: String,
     pub description: String,
     pub ncm: String,
@@ -66,13 +87,26 @@ pub struct EmissionItem {
     pub total_value: f64,
     pub ean: Option<String>,
 
-    // Impostos básicos
+    // Impostos básicos (usados quando não há regra tributária cadastrada)
     pub icms_origin: u8,
     pub icms_cst: String,
     pub pis_cst: String,
     pub cofins_cst: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmissionPayment {
+    pub method: String, // CASH, PIX, CREDIT, DEBIT, VOUCHER, OTHER
+    pub value: f64,
+
+    // Dados do cartão (crédito/débito)
+    pub card_integration: Option<u8>, // 1=TEF, 2=POS (padrão)
+    pub acquirer_cnpj: Option<String>,
+    pub card_brand: Option<String>,
+    pub authorization_code: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EmissionResponse {
@@ -83,6 +117,8 @@ pub struct EmissionResponse {
     pub xml: Option<String>,
     pub danfe_escpos: Option<Vec<u8>>, // Bytes para impressão direta
     pub qrcode_url: Option<String>,
+    /// Erros da validação local (esquema/regras); a nota não foi assinada nem enviada
+    pub validation_errors: Vec<ValidationIssue>,
 }
 
 #[derive(Debug, Serialize)]
@@ -100,6 +136,7 @@ pub async fn emit_nfce(
     app_handle: tauri::AppHandle,
     request: EmitNfceRequest,
     state: State<'_, AppState>,
+    hw_state: State<'_, HardwareState>,
 ) -> Result<EmissionResponse, String> {
     println!("Iniciando emissão de NFC-e...");
 
@@ -132,28 +169,9 @@ pub async fn emit_nfce(
         return Err("Certificado digital expirado ou inválido".to_string());
     }
 
-    // Preparar Itens
-    let nfce_items: Vec<NfceItem> = request
-        .items
-        .iter()
-        .enumerate()
-        .map(|(idx, item)| NfceItem {
-            number: (idx + 1) as u16,
-            code: item.code.clone(),
-            ean: item.ean.clone(),
-            description: item.description.clone(),
-            ncm: item.ncm.clone(),
-            cfop: item.cfop.clone(),
-            unit: item.unit.clone(),
-            quantity: item.quantity,
-            unit_value: item.unit_value,
-            total_value: item.total_value,
-            icms_origin: item.icms_origin,
-            icms_cst: item.icms_cst.clone(),
-            pis_cst: item.pis_cst.clone(),
-            cofins_cst: item.cofins_cst.clone(),
-        })
-        .collect();
+    // Preparar Itens (tributação resolvida por produto → categoria → NCM/CFOP)
+    let crt = fiscal_settings.crt as u8;
+    let nfce_items = build_items(pool, &request.items, crt).await?;
 
     let environment = if fiscal_settings.environment == 1 {
         Environment::Production
@@ -161,6 +179,10 @@ pub async fn emit_nfce(
         Environment::Homologation
     };
 
+    let payments = resolve_payments(pool, &request).await?;
+    let (total_products, total_discount, total_note) =
+        resolve_totals(pool, &request, &nfce_items).await?;
+
     let emitter_uf = fiscal_settings.uf.clone();
     let emitter_cnpj = request.emitter_cnpj.clone();
     let serie = fiscal_settings.serie as u16;
@@ -182,6 +204,8 @@ pub async fn emit_nfce(
     println!("Chave gerada (Normal): {}", access_key);
 
     let mut data = NfceData {
+        model: 65,
+        nature_of_operation: "VENDA".to_string(),
         uf: emitter_uf.clone(),
         cnpj: emitter_cnpj.clone(),
         serie,
@@ -189,22 +213,29 @@ pub async fn emit_nfce(
         emission_date,
         emission_type: 1, // Normal
         environment: fiscal_settings.environment as u8,
+        crt,
         emitter_name: request.emitter_name.clone(),
         emitter_trade_name: request.emitter_trade_name.clone(),
         emitter_ie: request.emitter_ie.clone(),
         emitter_address: request.emitter_address.clone(),
+        emitter_district: request.emitter_district.clone(),
         emitter_city: request.emitter_city.clone(),
         emitter_city_code: request.emitter_city_code.clone(),
         emitter_state: request.emitter_state.clone(),
         emitter_cep: request.emitter_cep.clone(),
         recipient_cpf: request.recipient_cpf,
         recipient_name: request.recipient_name,
+        recipient: None,
+        freight: None,
+        additional_info: None,
+        operation_type: 1,
+        purpose: 1,
+        referenced_keys: Vec::new(),
         items: nfce_items.clone(),
-        total_products: request.total,
-        total_discount: request.discount,
-        total_note: request.total - request.discount,
-        payment_method: map_payment_method(&request.payment_method),
-        payment_value: request.payment_value,
+        total_products,
+        total_discount,
+        total_note,
+        payments,
         csc_id: fiscal_settings.csc_id.clone().unwrap_or_default(),
         csc: fiscal_settings.csc.clone().unwrap_or_default(),
     };
@@ -212,6 +243,24 @@ pub async fn emit_nfce(
     let xml_builder = NfceXmlBuilder::new(data.clone(), access_key.clone());
     let xml = xml_builder.build()?;
 
+    // Validação local antes de assinar: erros voltam para a tela sem transmitir
+    let validation_errors = validate_nfe_xml(&xml);
+    if !validation_errors.is_empty() {
+        return Ok(EmissionResponse {
+            success: false,
+            message: format!(
+                "NFC-e reprovada na validação local ({} erro(s)); nada foi enviado à SEFAZ",
+                validation_errors.len()
+            ),
+            access_key: Some(access_key),
+            protocol: None,
+            xml: Some(xml),
+            danfe_escpos: None,
+            qrcode_url: None,
+            validation_errors,
+        });
+    }
+
     let signer = XmlSigner::new(cert);
     let signed_xml = signer.sign(&xml)?;
 
@@ -221,84 +270,120 @@ pub async fn emit_nfce(
         Some(cert_path),
         Some(cert_password),
     )?;
+    let manager = ContingencyManager::new(&app_handle);
 
     println!("Enviando para SEFAZ...");
-    let response_result = client.authorize(&signed_xml).await;
-
     let mut protocol: Option<String> = None;
-    let mut success_xml = signed_xml.clone();
+    let success_xml;
+    let stored_xml;
     let mut is_contingency = false;
 
-    match response_result {
-        Ok(response) => {
-            println!(
-                "Resposta SEFAZ: {} - {}",
-                response.status_code, response.status_message
-            );
-
-            if response.status_code == "100" {
-                // SUCESSO ONLINE
-                protocol = response.protocol;
-                // Incrementar número apenas em caso de sucesso
-                let _ = fiscal_repo.increment_number().await;
-            } else {
-                return Ok(EmissionResponse {
-                    success: false,
-                    message: format!(
-                        "Rejeição SEFAZ: {} - {}",
-                        response.status_code, response.status_message
-                    ),
-                    access_key: Some(access_key),
-                    protocol: None,
-                    xml: Some(signed_xml),
-                    danfe_escpos: None,
-                    qrcode_url: None,
-                });
-            }
+    match transmit_nfce(
+        &client,
+        &signer,
+        &manager,
+        &mut data,
+        &access_key,
+        signed_xml,
+    )
+    .await?
+    {
+        Transmission::Authorized {
+            signed_xml,
+            response,
+        } => {
+            // SUCESSO ONLINE
+            protocol = response.protocol.clone();
+            stored_xml = build_nfe_proc(
+                &signed_xml,
+                &response.xml,
+                &ProtocolInfo {
+                    access_key: access_key.clone(),
+                    protocol: protocol.clone().unwrap_or_default(),
+                    environment: data.environment,
+                    status_code: response.status_code.clone(),
+                    status_message: response.status_message.clone(),
+                    received_at: Utc::now().to_rfc3339(),
+                },
+            )?;
+            success_xml = signed_xml;
+            // Incrementar número apenas em caso de sucesso
+            let _ = fiscal_repo.increment_number().await;
         }
-        Err(err_msg) => {
-            // ERRO DE COMUNICAÇÃO -> CONTINGÊNCIA
-            println!(
-                "Erro de comunicação com SEFAZ: {}. Iniciando Contingência Offline...",
-                err_msg
-            );
+        Transmission::Rejected {
+            signed_xml,
+            response,
+        } => {
+            return Ok(EmissionResponse {
+                success: false,
+                message: format!(
+                    "Rejeição SEFAZ: {} - {}",
+                    response.status_code, response.status_message
+                ),
+                access_key: Some(access_key),
+                protocol: None,
+                xml: Some(signed_xml),
+                danfe_escpos: None,
+                qrcode_url: None,
+                validation_errors: Vec::new(),
+            });
+        }
+        Transmission::Contingency {
+            access_key: access_key_offline,
+            signed_xml,
+        } => {
             is_contingency = true;
+            access_key = access_key_offline;
+            success_xml = signed_xml.clone();
+            stored_xml = signed_xml;
 
-            // 1. Recarregar Certificado para a nova assinatura
-            let cert_offline = Certificate::from_pfx(cert_path, cert_password)?;
-
-            // 2. Gerar Nova Chave (tpEmis = 9)
-            let access_key_offline = AccessKey::generate(
-                &emitter_uf,
-                emission_date.naive_utc(),
-                &emitter_cnpj,
-                65,
-                serie,
-                numero,
-                9, // Contingência Offline
-            )?;
-            access_key = access_key_offline.key;
-            println!("Chave gerada (Contingência): {}", access_key);
-
-            // 3. Atualizar Dados
-            data.emission_type = 9;
-
-            // 4. Gerar e Assinar novo XML
-            let xml_builder_off = NfceXmlBuilder::new(data.clone(), access_key.clone());
-            let xml_off = xml_builder_off.build()?;
-            let signer_off = XmlSigner::new(cert_offline);
-            let signed_xml_off = signer_off.sign(&xml_off)?;
-            success_xml = signed_xml_off.clone();
-
-            // 5. Salvar em Disco
-            let manager = ContingencyManager::new(&app_handle);
-            manager.save_note(&access_key, &signed_xml_off)?;
-
-            // 6. Incrementar número (nota emitida, mesmo offline)
+            // Incrementar número (nota emitida, mesmo offline)
             let _ = fiscal_repo.increment_number().await;
         }
     }
 
+    // Vincular nota à venda (protocolo ausente = contingência pendente)
+    if let Some(sale_id) = &request.sale_id {
+        if let Err(e) = fiscal_repo
+            .link_sale(sale_id, &access_key, protocol.as_deref())
+            .await
+        {
+            tracing::warn!(
+                "Falha ao vincular NFC-e {} à venda {}: {}",
+                access_key,
+                sale_id,
+                e
+            );
+        }
+    }
+
+    // Armazenar XML (nfeProc quando autorizada) para consulta e exportação contábil
+    let document_repo = crate::repositories::FiscalDocumentRepository::new(pool);
+    if let Err(e) = document_repo
+        .upsert(CreateFiscalDocument {
+            sale_id: request.sale_id.clone(),
+            service_order_id: None,
+            access_key: access_key.clone(),
+            model: 65,
+            serie: serie as i32,
+            number: numero as i32,
+            emission_type: data.emission_type as i32,
+            environment: data.environment as i32,
+            status: if is_contingency {
+                "CONTINGENCY".to_string()
+            } else {
+                "AUTHORIZED".to_string()
+            },
+            protocol: protocol.clone(),
+            total: data.total_note,
+            xml: stored_xml,
+            issued_at: emission_date.to_rfc3339(),
+        })
+        .await
+    {
+        tracing::error!("Falha ao armazenar XML da NFC-e {}: {}", access_key, e);
+    }
+
     // 7. Gerar QR Code
     // Extrair digest realizado no XML assinado
     let digest_value = extract_digest_from_xml(&success_xml).unwrap_or_else(|| "ERROR".to_string());
@@ -314,6 +399,10 @@ pub async fn emit_nfce(
         csc: data.csc.clone(),
     };
 
+    let note_builder = NfceXmlBuilder::new(data.clone(), access_key.clone());
+    let change = note_builder.change();
+    let transparency = note_builder.transparency_info();
+
     let qrcode_url = QrCodeGenerator::generate_url(&qr_params)?;
     let qrcode_png = QrCodeGenerator::generate_png(&qr_params)?;
 
@@ -331,11 +420,14 @@ pub async fn emit_nfce(
         })
         .collect();
 
-    let additional_msg = if is_contingency {
+    let mut additional_msg = if is_contingency {
         "EMITIDA EM CONTINGÊNCIA - Pendente de Autorização".to_string()
     } else {
         "OBRIGADO PELA PREFERENCIA".to_string()
     };
+    if let Some(message) = transparency {
+        additional_msg = format!("{}\n{}", message, additional_msg);
+    }
 
     let danfe_data = DanfeData {
         emitter_name: request.emitter_name,
@@ -355,13 +447,21 @@ pub async fn emit_nfce(
         total: data.total_note,
         discount: data.total_discount,
         total_items: data.total_products,
-        payment_method: data.payment_method,
-        payment_value: data.payment_value,
+        payments: data
+            .payments
+            .iter()
+            .map(|p| DanfePayment {
+                method: p.method.clone(),
+                value: p.value,
+            })
+            .collect(),
+        change,
         qrcode_png,
         additional_info: Some(additional_msg),
     };
 
-    let escpos_bytes = DanfePrinter::generate_escpos(&danfe_data)?;
+    let layout = DanfeLayout::from_config(&*hw_state.printer_config.read().await);
+    let escpos_bytes = DanfePrinter::generate_escpos_with_layout(&danfe_data, layout)?;
 
     let status_msg = if is_contingency {
         "NFC-e emitida em Contingência Offline (Sem Internet)".to_string()
@@ -377,9 +477,177 @@ pub async fn emit_nfce(
         xml: Some(success_xml),
         danfe_escpos: Some(escpos_bytes),
         qrcode_url: Some(qrcode_url),
+        validation_errors: Vec::new(),
     })
 }
 
+/// Monta os itens da NFC-e com o perfil tributário e as alíquotas IBPT de cada um
+async fn build_items(
+    pool: &sqlx::SqlitePool,
+    items: &[EmissionItem],
+    crt: u8,
+) -> Result<Vec<NfceItem>, String> {
+    let tax_repo = crate::repositories::TaxRuleRepository::new(pool);
+    let ibpt_repo = crate::repositories::IbptRepository::new(pool);
+    let mut nfce_items = Vec::with_capacity(items.len());
+
+    for (idx, item) in items.iter().enumerate() {
+        let rule = tax_repo
+            .resolve(
+                item.product_id.as_deref(),
+                &item.ncm,
+                &item.cfop,
+                crt as i32,
+            )
+            .await
+            .map_err(|e| format!("Erro ao buscar regra tributária: {}", e))?;
+        let tax = match &rule {
+            Some(rule) => TaxProfile::from(rule),
+            None => TaxProfile::simples(
+                item.icms_origin,
+                &item.icms_cst,
+                &item.pis_cst,
+                &item.cofins_cst,
+            ),
+        };
+        let ibpt = ibpt_repo
+            .find_by_ncm(&item.ncm)
+            .await
+            .map_err(|e| format!("Erro ao buscar alíquotas IBPT: {}", e))?
+            .map(|rate| IbptRates::from_rate(&rate, tax.icms_origin));
+
+        nfce_items.push(NfceItem {
+            number: (idx + 1) as u16,
+            code: item.code.clone(),
+            ean: item.ean.clone(),
+            description: item.description.clone(),
+            ncm: item.ncm.clone(),
+            cfop: item.cfop.clone(),
+            unit: item.unit.clone(),
+            quantity: item.quantity,
+            unit_value: item.unit_value,
+            total_value: item.total_value,
+            tax,
+            ibpt,
+        });
+    }
+
+    Ok(nfce_items)
+}
+
+/// vProd, vDesc e vNF da nota. Com venda vinculada o vNF é o total gravado e o
+/// vDesc reúne descontos dos itens, promoções e desconto geral
+async fn resolve_totals(
+    pool: &sqlx::SqlitePool,
+    request: &EmitNfceRequest,
+    items: &[NfceItem],
+) -> Result<(f64, f64, f64), String> {
+    let Some(sale_id) = &request.sale_id else {
+        return Ok((
+            request.total,
+            request.discount,
+            request.total - request.discount,
+        ));
+    };
+    let sale = crate::repositories::SaleRepository::new(pool)
+        .find_by_id(sale_id)
+        .await
+        .map_err(|e| format!("Erro ao carregar a venda: {}", e))?
+        .ok_or("Venda não encontrada")?;
+
+    let total_products = round_cents(items.iter().map(|i| i.total_value).sum());
+    let total_discount = round_cents((total_products - sale.total).max(0.0));
+    Ok((total_products, total_discount, round_cents(sale.total)))
+}
+
+/// Monta os grupos detPag a partir do request ou dos `SalePayment` da venda
+async fn resolve_payments(
+    pool: &sqlx::SqlitePool,
+    request: &EmitNfceRequest,
+) -> Result<Vec<NfcePayment>, String> {
+    if !request.payments.is_empty() {
+        return Ok(request
+            .payments
+            .iter()
+            .map(|p| {
+                build_payment(
+                    &p.method,
+                    p.value,
+                    p.card_integration,
+                    p.acquirer_cnpj.clone(),
+                    p.card_brand.clone(),
+                    p.authorization_code.clone(),
+                )
+            })
+            .collect());
+    }
+
+    if let Some(sale_id) = &request.sale_id {
+        let sale_repo = crate::repositories::SaleRepository::new(pool);
+        let sale_payments = sale_repo
+            .find_payments_by_sale(sale_id)
+            .await
+            .map_err(|e| format!("Erro ao carregar pagamentos da venda: {}", e))?;
+
+        if !sale_payments.is_empty() {
+            return Ok(sale_payments
+                .iter()
+                .map(|p| {
+                    build_payment(
+                        p.method.as_str(),
+                        p.amount,
+                        p.card_integration,
+                        p.acquirer_cnpj.clone(),
+                        p.card_brand.clone(),
+                        p.authorization_code.clone(),
+                    )
+                })
+                .collect());
+        }
+    }
+
+    if request.payment_method.is_empty() {
+        return Err("Nenhuma forma de pagamento informada".to_string());
+    }
+
+    Ok(vec![build_payment(
+        &request.payment_method,
+        request.payment_value,
+        None,
+        None,
+        None,
+        None,
+    )])
+}
+
+fn build_payment(
+    method: &str,
+    value: f64,
+    card_integration: Option<u8>,
+    acquirer_cnpj: Option<String>,
+    card_brand: Option<String>,
+    authorization_code: Option<String>,
+) -> NfcePayment {
+    let code = map_payment_method(method);
+    // Cartão de crédito/débito exige o grupo card
+    let card = if code == "03" || code == "04" {
+        Some(NfceCardInfo {
+            integration_type: card_integration.unwrap_or(2),
+            acquirer_cnpj,
+            brand: card_brand,
+            authorization_code,
+        })
+    } else {
+        None
+    };
+
+    NfcePayment {
+        method: code,
+        value,
+        card,
+    }
+}
+
 fn extract_digest_from_xml(xml: &str) -> Option<String> {
     use roxmltree::Document;
     if let Ok(doc) = Document::parse(xml) {
@@ -392,6 +660,81 @@ fn extract_digest_from_xml(xml: &str) -> Option<String> {
     None
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DanfeReprintResponse {
+    pub access_key: String,
+    /// Enviado à fila da impressora térmica configurada
+    pub printed: bool,
+    pub danfe_escpos: Option<Vec<u8>>,
+    pub danfe_pdf: Option<Vec<u8>>,
+    pub pdf_path: Option<String>,
+}
+
+/// Reimprime o DANFE NFC-e pela chave de acesso, a partir do XML armazenado.
+/// `format`: "ESCPOS" (impressora térmica, na largura configurada) ou "PDF"
+/// (envio ao cliente por e-mail/WhatsApp; gravado em `pdf_path` se informado).
+#[command]
+pub async fn reprint_danfe(
+    access_key: String,
+    format: String,
+    pdf_path: Option<String>,
+    state: State<'_, AppState>,
+    hw_state: State<'_, HardwareState>,
+) -> Result<DanfeReprintResponse, String> {
+    let pool = state.pool();
+    let document = crate::repositories::FiscalDocumentRepository::new(pool)
+        .find_by_access_key(&access_key)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Documento fiscal {} não encontrado", access_key))?;
+
+    let settings = crate::repositories::FiscalRepository::new(pool)
+        .get()
+        .await
+        .map_err(|e| format!("Erro ao carregar configurações fiscais: {}", e))?;
+    let danfe_data = DanfeData::from_xml(
+        &document.xml,
+        settings.csc_id.as_deref().unwrap_or_default(),
+        settings.csc.as_deref().unwrap_or_default(),
+    )?;
+
+    let mut response = DanfeReprintResponse {
+        access_key,
+        printed: false,
+        danfe_escpos: None,
+        danfe_pdf: None,
+        pdf_path: None,
+    };
+
+    if format.eq_ignore_ascii_case("PDF") {
+        let pdf = DanfePrinter::generate_pdf(&danfe_data)?;
+        if let Some(path) = &pdf_path {
+            std::fs::write(path, &pdf)
+                .map_err(|e| format!("Erro ao salvar PDF {}: {}", path, e))?;
+        }
+        response.danfe_pdf = Some(pdf);
+        response.pdf_path = pdf_path;
+        return Ok(response);
+    }
+
+    let config = hw_state.printer_config.read().await.clone();
+    let escpos =
+        DanfePrinter::generate_escpos_with_layout(&danfe_data, DanfeLayout::from_config(&config))?;
+
+    // Sem impressora habilitada, devolve os bytes para o frontend
+    if config.enabled {
+        hw_state
+            .manager
+            .enqueue_print(PrintJobKind::Danfe, Some(&response.access_key), &escpos)
+            .await
+            .map_err(|e| e.to_string())?;
+        response.printed = true;
+    }
+    response.danfe_escpos = Some(escpos);
+    Ok(response)
+}
+
 /// Consulta status da SEFAZ
 #[command]
 pub async fn check_sefaz_status(
@@ -433,6 +776,7 @@ pub async fn transmit_offline_note(
     cert_password: String,
     emitter_uf: String,
     environment: u8,
+    state: State<'_, AppState>,
 ) -> Result<EmissionResponse, String> {
     let manager = ContingencyManager::new(&app_handle);
     let notes = manager.list_pending_notes()?;
@@ -460,6 +804,14 @@ pub async fn transmit_offline_note(
     if response.status_code == "100" || response.status_code == "101" {
         // 100=Autorizado, 101=Cancelamento? Não, 100 é o que queremos.
         manager.mark_as_transmitted(&access_key)?;
+        record_late_authorization(
+            state.pool(),
+            &access_key,
+            &signed_xml,
+            &response,
+            environment,
+        )
+        .await?;
 
         Ok(EmissionResponse {
             success: true,
@@ -469,6 +821,7 @@ pub async fn transmit_offline_note(
             xml: Some(signed_xml),
             danfe_escpos: None, // Não precisa reimprimir se já imprimiu
             qrcode_url: None,
+            validation_errors: Vec::new(),
         })
     } else {
         Ok(EmissionResponse {
@@ -482,10 +835,427 @@ pub async fn transmit_offline_note(
             xml: Some(signed_xml),
             danfe_escpos: None,
             qrcode_url: None,
+            validation_errors: Vec::new(),
         })
     }
 }
 
+/// Resultado do envio de uma NFC-e recém-assinada
+#[derive(Debug)]
+pub enum Transmission {
+    /// cStat 100 - autorizada online
+    Authorized {
+        signed_xml: String,
+        response: AuthorizationResponse,
+    },
+    /// SEFAZ respondeu com rejeição (nada é gravado na fila)
+    Rejected {
+        signed_xml: String,
+        response: AuthorizationResponse,
+    },
+    /// Falha de comunicação: nota reassinada em contingência offline e
+    /// gravada na fila de retransmissão
+    Contingency {
+        access_key: String,
+        signed_xml: String,
+    },
+}
+
+/// Envia a NFC-e assinada; sem comunicação com a SEFAZ, gera nova chave com
+/// tpEmis = 9, reassina com o mesmo certificado e salva na contingência
+pub async fn transmit_nfce(
+    client: &SefazClient,
+    signer: &XmlSigner,
+    manager: &ContingencyManager,
+    data: &mut NfceData,
+    access_key: &str,
+    signed_xml: String,
+) -> Result<Transmission, String> {
+    let err_msg = match client.authorize(&signed_xml).await {
+        Ok(response) => {
+            println!(
+                "Resposta SEFAZ: {} - {}",
+                response.status_code, response.status_message
+            );
+            return Ok(if response.status_code == "100" {
+                Transmission::Authorized {
+                    signed_xml,
+                    response,
+                }
+            } else {
+                Transmission::Rejected {
+                    signed_xml,
+                    response,
+                }
+            });
+        }
+        Err(err_msg) => err_msg,
+    };
+
+    // ERRO DE COMUNICAÇÃO -> CONTINGÊNCIA
+    println!(
+        "Erro de comunicação com SEFAZ ({}): {}. Iniciando Contingência Offline...",
+        access_key, err_msg
+    );
+
+    // 1. Gerar Nova Chave (tpEmis = 9)
+    let access_key = AccessKey::generate(
+        &data.uf,
+        data.emission_date.naive_utc(),
+        &data.cnpj,
+        data.model,
+        data.serie,
+        data.numero,
+        9, // Contingência Offline
+    )?
+    .key;
+    println!("Chave gerada (Contingência): {}", access_key);
+
+    // 2. Atualizar Dados
+    data.emission_type = 9;
+
+    // 3. Gerar e Assinar novo XML
+    let xml = NfceXmlBuilder::new(data.clone(), access_key.clone()).build()?;
+    let signed_xml = signer.sign(&xml)?;
+
+    // 4. Salvar em Disco
+    manager.save_note(&access_key, &signed_xml)?;
+
+    Ok(Transmission::Contingency {
+        access_key,
+        signed_xml,
+    })
+}
+
+/// Atualiza o documento armazenado (e a venda) quando uma nota de contingência é autorizada
+pub async fn record_late_authorization(
+    pool: &sqlx::SqlitePool,
+    access_key: &str,
+    signed_xml: &str,
+    response: &AuthorizationResponse,
+    environment: u8,
+) -> Result<(), String> {
+    let protocol = response.protocol.clone().unwrap_or_default();
+    let proc_xml = build_nfe_proc(
+        signed_xml,
+        &response.xml,
+        &ProtocolInfo {
+            access_key: access_key.to_string(),
+            protocol: protocol.clone(),
+            environment,
+            status_code: response.status_code.clone(),
+            status_message: response.status_message.clone(),
+            received_at: Utc::now().to_rfc3339(),
+        },
+    )?;
+
+    let document_repo = crate::repositories::FiscalDocumentRepository::new(pool);
+    document_repo
+        .mark_authorized(access_key, &protocol, &proc_xml)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(sale_id) = document_repo
+        .find_by_access_key(access_key)
+        .await
+        .map_err(|e| e.to_string())?
+        .and_then(|doc| doc.sale_id)
+    {
+        crate::repositories::FiscalRepository::new(pool)
+            .link_sale(&sale_id, access_key, Some(&protocol))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Exporta os XMLs (procNFe e procEventoNFe) de um mês em um ZIP com resumo CSV para a contabilidade
+#[command]
+pub async fn export_fiscal_documents(
+    year_month: String,
+    output_path: String,
+    state: State<'_, AppState>,
+) -> Result<FiscalExportSummary, String> {
+    let document_repo = crate::repositories::FiscalDocumentRepository::new(state.pool());
+    let documents = document_repo
+        .find_by_month(&year_month)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if documents.is_empty() {
+        return Err(format!("Nenhum documento fiscal emitido em {}", year_month));
+    }
+
+    // Eventos de cancelamento comprovam a situação das notas canceladas
+    let fiscal_repo = crate::repositories::FiscalRepository::new(state.pool());
+    let mut events = Vec::new();
+    for doc in documents.iter().filter(|d| d.status == "CANCELED") {
+        events.extend(
+            fiscal_repo
+                .find_events_by_access_key(&doc.access_key)
+                .await
+                .map_err(|e| e.to_string())?,
+        );
+    }
+
+    let file = std::fs::File::create(&output_path)
+        .map_err(|e| format!("Erro ao criar arquivo {}: {}", output_path, e))?;
+    write_month_archive(&documents, &events, std::io::BufWriter::new(file))
+}
+
+/// Cancela a NFC-e vinculada a uma venda (evento 110111)
+#[command]
+pub async fn cancel_nfce(
+    sale_id: String,
+    justification: String,
+    state: State<'_, AppState>,
+) -> Result<FiscalEvent, String> {
+    cancel_sale_nfce(state.pool(), &sale_id, &justification)
+        .await?
+        .ok_or_else(|| "Venda não possui NFC-e autorizada".to_string())
+}
+
+/// Inutiliza uma faixa de numeração não utilizada
+#[command]
+pub async fn inutilize_nfce_numbers(
+    emitter_cnpj: String,
+    serie: u16,
+    number_start: u32,
+    number_end: u32,
+    justification: String,
+    state: State<'_, AppState>,
+) -> Result<FiscalEvent, String> {
+    let fiscal_repo = crate::repositories::FiscalRepository::new(state.pool());
+    let settings = load_enabled_settings(&fiscal_repo).await?;
+    let (cert_path, cert_password) = settings_certificate(&settings)?;
+
+    let inutilization = Inutilization {
+        uf: settings.uf.clone(),
+        cnpj: emitter_cnpj,
+        model: 65,
+        serie,
+        number_start,
+        number_end,
+        year: Utc::now().year() as u16,
+        justification: justification.clone(),
+        environment: settings.environment as u8,
+    };
+
+    let cert = Certificate::from_pfx(cert_path, cert_password)?;
+    let signed_xml = XmlSigner::new(cert).sign_element(&inutilization.build()?, "infInut")?;
+
+    let client = SefazClient::new(
+        settings.uf.clone(),
+        settings_environment(&settings),
+        Some(cert_path),
+        Some(cert_password),
+    )?;
+    let response = client.inutilize(&signed_xml).await?;
+
+    // 102 = Inutilização de número homologado
+    let authorized = response.status_code == "102";
+    let sequence = fiscal_repo
+        .next_inutilization_sequence(serie as i32, number_start as i32, number_end as i32)
+        .await
+        .map_err(|e| e.to_string())?;
+    let event = fiscal_repo
+        .record_event(CreateFiscalEvent {
+            event_type: "INUTILIZATION".to_string(),
+            sequence,
+            serie: Some(serie as i32),
+            number_start: Some(number_start as i32),
+            number_end: Some(number_end as i32),
+            justification,
+            status_code: Some(response.status_code.clone()),
+            status_message: Some(response.status_message.clone()),
+            protocol: if authorized { response.protocol } else { None },
+            xml: Some(response.xml),
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !authorized {
+        return Err(format!(
+            "Rejeição SEFAZ: {} - {}",
+            response.status_code, response.status_message
+        ));
+    }
+
+    Ok(event)
+}
+
+/// Cancela na SEFAZ a NFC-e autorizada de uma venda, se houver.
+///
+/// Retorna `Ok(None)` quando o módulo fiscal está desativado ou a venda não
+/// possui nota autorizada (sem protocolo), e erro quando o prazo legal expirou
+/// ou a SEFAZ rejeitou o evento. O evento é sempre registrado em `fiscal_events`.
+pub async fn cancel_sale_nfce(
+    pool: &sqlx::SqlitePool,
+    sale_id: &str,
+    justification: &str,
+) -> Result<Option<FiscalEvent>, String> {
+    let fiscal_repo = crate::repositories::FiscalRepository::new(pool);
+    let settings = fiscal_repo.get().await.map_err(|e| e.to_string())?;
+    if !settings.enabled {
+        return Ok(None);
+    }
+
+    let link = match fiscal_repo
+        .find_sale_link(sale_id)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        Some(link) => link,
+        None => return Ok(None),
+    };
+
+    if link.nfce_cancel_protocol.is_some() {
+        return Err("NFC-e desta venda já foi cancelada".to_string());
+    }
+
+    let (access_key, protocol) = match (link.nfce_access_key, link.nfce_protocol) {
+        (Some(key), Some(protocol)) => (key, protocol),
+        _ => return Ok(None),
+    };
+
+    // Sem data de autorização válida não há como conferir o prazo: não envia
+    let authorized_at = link
+        .nfce_authorized_at
+        .as_deref()
+        .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+        .ok_or_else(|| {
+            "Data de autorização da NFC-e ausente ou inválida; não é possível conferir o prazo de cancelamento".to_string()
+        })?;
+    let window_minutes = crate::repositories::SettingsRepository::new(pool)
+        .get_number("fiscal.nfce_cancel_window_minutes")
+        .await
+        .map_err(|e| e.to_string())?
+        .map(|minutes| minutes as i64)
+        .unwrap_or(event::DEFAULT_CANCEL_WINDOW_MINUTES);
+    if !event::is_within_cancel_window(
+        authorized_at.naive_utc(),
+        Utc::now().naive_utc(),
+        window_minutes,
+    ) {
+        return Err(format!(
+            "Prazo de {} minutos para cancelamento da NFC-e expirado",
+            window_minutes
+        ));
+    }
+
+    // Retentativa após rejeição usa o próximo nSeqEvento
+    let sequence = fiscal_repo
+        .next_event_sequence(&access_key, "CANCELLATION")
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let (cert_path, cert_password) = settings_certificate(&settings)?;
+    let cancellation = CancellationEvent {
+        access_key: access_key.clone(),
+        protocol,
+        justification: justification.to_string(),
+        environment: settings.environment as u8,
+        sequence: sequence as u8,
+        event_date: chrono::Local::now().fixed_offset(),
+    };
+
+    let cert = Certificate::from_pfx(cert_path, cert_password)?;
+    let signed_xml = XmlSigner::new(cert).sign_element(&cancellation.build()?, "infEvento")?;
+
+    let client = SefazClient::new(
+        settings.uf.clone(),
+        settings_environment(&settings),
+        Some(cert_path),
+        Some(cert_password),
+    )?;
+    let response = client.send_event(&signed_xml).await?;
+
+    // 135 = Evento registrado e vinculado; 155 = Cancelamento homologado fora de prazo
+    let authorized = response.status_code == "135" || response.status_code == "155";
+    // Evento homologado é guardado como procEventoNFe para a exportação contábil
+    let event_xml = if authorized {
+        build_event_proc(&signed_xml, &response.xml).unwrap_or_else(|_| response.xml.clone())
+    } else {
+        response.xml.clone()
+    };
+    let event = fiscal_repo
+        .record_event(CreateFiscalEvent {
+            sale_id: Some(sale_id.to_string()),
+            access_key: Some(access_key),
+            event_type: "CANCELLATION".to_string(),
+            sequence,
+            justification: justification.to_string(),
+            status_code: Some(response.status_code.clone()),
+            status_message: Some(response.status_message.clone()),
+            protocol: if authorized {
+                response.protocol.clone()
+            } else {
+                None
+            },
+            xml: Some(event_xml),
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !authorized {
+        return Err(format!(
+            "Rejeição SEFAZ: {} - {}",
+            response.status_code, response.status_message
+        ));
+    }
+
+    if let Some(cancel_protocol) = &event.protocol {
+        fiscal_repo
+            .set_sale_cancel_protocol(sale_id, cancel_protocol)
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Some(key) = &event.access_key {
+            crate::repositories::FiscalDocumentRepository::new(pool)
+                .update_status(key, "CANCELED")
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(Some(event))
+}
+
+pub(crate) async fn load_enabled_settings(
+    fiscal_repo: &crate::repositories::FiscalRepository<'_>,
+) -> Result<FiscalSettings, String> {
+    let settings = fiscal_repo
+        .get()
+        .await
+        .map_err(|e| format!("Erro ao carregar configurações fiscais: {}", e))?;
+    if !settings.enabled {
+        return Err("Módulo fiscal desativado nas configurações".to_string());
+    }
+    Ok(settings)
+}
+
+pub(crate) fn settings_certificate(settings: &FiscalSettings) -> Result<(&str, &str), String> {
+    let cert_path = settings
+        .cert_path
+        .as_deref()
+        .ok_or("Arquivo de certificado não configurado")?;
+    let cert_password = settings
+        .cert_password
+        .as_deref()
+        .ok_or("Senha do certificado não configurada")?;
+    Ok((cert_path, cert_password))
+}
+
+pub(crate) fn settings_environment(settings: &FiscalSettings) -> Environment {
+    if settings.environment == 1 {
+        Environment::Production
+    } else {
+        Environment::Homologation
+    }
+}
+
 #[command]
 pub async fn get_fiscal_settings(state: State<'_, AppState>) -> Result<FiscalSettings, String> {
     let repo = crate::repositories::FiscalRepository::new(state.pool());
@@ -501,6 +1271,872 @@ pub async fn update_fiscal_settings(
     repo.update(data).await.map_err(|e| e.to_string())
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// REGRAS TRIBUTÁRIAS E TABELA IBPT
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[command]
+pub async fn list_tax_rules(state: State<'_, AppState>) -> Result<Vec<TaxRule>, String> {
+    let repo = crate::repositories::TaxRuleRepository::new(state.pool());
+    repo.find_all().await.map_err(|e| e.to_string())
+}
+
+/// Cria ou atualiza uma regra tributária (validada contra o regime informado)
+#[command]
+pub async fn save_tax_rule(
+    data: SaveTaxRule,
+    state: State<'_, AppState>,
+) -> Result<TaxRule, String> {
+    let profile = TaxProfile {
+        icms_origin: data.icms_origin as u8,
+        icms_code: data.icms_code.clone(),
+        icms_rate: data.icms_rate,
+        icms_base_reduction: data.icms_base_reduction,
+        st_mva: data.st_mva,
+        st_rate: data.st_rate,
+        fcp_rate: data.fcp_rate,
+        pis_cst: data.pis_cst.clone(),
+        pis_rate: data.pis_rate,
+        cofins_cst: data.cofins_cst.clone(),
+        cofins_rate: data.cofins_rate,
+    };
+    profile.validate(data.crt as u8)?;
+
+    let repo = crate::repositories::TaxRuleRepository::new(state.pool());
+    repo.save(data).await.map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn delete_tax_rule(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    let repo = crate::repositories::TaxRuleRepository::new(state.pool());
+    repo.delete(&id).await.map_err(|e| e.to_string())
+}
+
+/// Vincula (ou remove, com `tax_rule_id` nulo) a regra a um produto ou categoria
+#[command]
+pub async fn assign_tax_rule(
+    target: String, // PRODUCT ou CATEGORY
+    target_id: String,
+    tax_rule_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let repo = crate::repositories::TaxRuleRepository::new(state.pool());
+    let result = match target.as_str() {
+        "PRODUCT" => {
+            repo.assign_to_product(&target_id, tax_rule_id.as_deref())
+                .await
+        }
+        "CATEGORY" => {
+            repo.assign_to_category(&target_id, tax_rule_id.as_deref())
+                .await
+        }
+        other => return Err(format!("Destino inválido: {}", other)),
+    };
+    result.map_err(|e| e.to_string())
+}
+
+/// Importa a tabela IBPT (CSV oficial) e retorna a quantidade de NCMs atualizados
+#[command]
+pub async fn import_ibpt_table(
+    file_path: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let bytes = std::fs::read(&file_path)
+        .map_err(|e| format!("Erro ao ler arquivo {}: {}", file_path, e))?;
+    // Os arquivos do IBPT são distribuídos em Latin-1
+    let content = match String::from_utf8(bytes) {
+        Ok(content) => content,
+        Err(e) => e.into_bytes().iter().map(|&b| b as char).collect(),
+    };
+
+    let rates = crate::nfce::tax::parse_ibpt_csv(&content)?;
+    if rates.is_empty() {
+        return Err("Nenhum NCM encontrado no arquivo IBPT".to_string());
+    }
+
+    let repo = crate::repositories::IbptRepository::new(state.pool());
+    repo.upsert_many(&rates).await.map_err(|e| e.to_string())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// NF-e MODELO 55 (VENDA A EMPRESAS / ORDEM DE SERVIÇO)
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmitNfeRequest {
+    // Origem: SALE, SERVICE_ORDER ou RETURN (devolução de venda)
+    pub source: String,
+    pub source_id: String,
+
+    // Emitente
+    pub emitter_cnpj: String,
+    pub emitter_ie: String,
+    pub emitter_name: String,
+    pub emitter_trade_name: Option<String>,
+    pub emitter_address: String,
+    #[serde(default)]
+    pub emitter_district: Option<String>,
+    pub emitter_city: String,
+    pub emitter_city_code: String,
+    pub emitter_state: String,
+    pub emitter_cep: String,
+
+    // Operação
+    pub nature_of_operation: Option<String>, // Padrão: VENDA DE MERCADORIA (DEVOLUÇÃO DE VENDA)
+    pub cfop: Option<String>, // CFOP interno (padrão 5102/1202); convertido para 6xxx/2xxx se interestadual
+    pub additional_info: Option<String>,
+
+    // Tributação padrão dos itens sem regra tributária cadastrada
+    #[serde(default)]
+    pub icms_origin: u8,
+    pub icms_cst: Option<String>,
+    pub pis_cst: Option<String>,
+    pub cofins_cst: Option<String>,
+
+    // Transporte
+    pub freight: Option<EmissionFreight>,
+
+    // Caminho para gravar o DANFE (PDF); ausente = apenas retorna os bytes
+    pub pdf_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmissionFreight {
+    pub modality: u8, // 0=CIF, 1=FOB, 2=Terceiros, 3/4=Próprio, 9=Sem frete
+    #[serde(default)]
+    pub value: f64,
+    pub carrier_document: Option<String>,
+    pub carrier_name: Option<String>,
+    pub carrier_ie: Option<String>,
+    pub carrier_address: Option<String>,
+    pub carrier_city: Option<String>,
+    pub carrier_uf: Option<String>,
+    pub volumes: Option<u32>,
+    pub species: Option<String>,
+    pub net_weight: Option<f64>,
+    pub gross_weight: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NfeEmissionResponse {
+    pub success: bool,
+    pub message: String,
+    pub access_key: Option<String>,
+    pub protocol: Option<String>,
+    pub xml: Option<String>,
+    pub danfe_pdf: Option<Vec<u8>>,
+    pub pdf_path: Option<String>,
+    pub validation_errors: Vec<ValidationIssue>,
+}
+
+/// Itens e valores extraídos da venda ou da OS
+struct NfeSource {
+    sale_id: Option<String>,
+    service_order_id: Option<String>,
+    return_id: Option<String>,
+    // Nota da venda original (NFref da devolução)
+    referenced_key: Option<String>,
+    customer_id: String,
+    items: Vec<EmissionItem>,
+    total_products: f64,
+    total_discount: f64,
+    sale_payments: Vec<NfcePayment>,
+    primary_method: String,
+    reference: String,
+}
+
+/// Emite NF-e (modelo 55) a partir de uma venda ou ordem de serviço
+#[command]
+pub async fn emit_nfe(
+    request: EmitNfeRequest,
+    state: State<'_, AppState>,
+) -> Result<NfeEmissionResponse, String> {
+    let pool = state.pool();
+    let fiscal_repo = crate::repositories::FiscalRepository::new(pool);
+    let fiscal_settings = fiscal_repo
+        .get()
+        .await
+        .map_err(|e| format!("Erro ao carregar configurações fiscais: {}", e))?;
+
+    if !fiscal_settings.enabled {
+        return Err("Módulo fiscal desativado nas configurações".to_string());
+    }
+
+    let cert_path = fiscal_settings
+        .cert_path
+        .as_ref()
+        .ok_or("Arquivo de certificado não configurado")?;
+    let cert_password = fiscal_settings
+        .cert_password
+        .as_ref()
+        .ok_or("Senha do certificado não configurada")?;
+
+    let cert = Certificate::from_pfx(cert_path, cert_password)?;
+    if !cert.is_valid() {
+        return Err("Certificado digital expirado ou inválido".to_string());
+    }
+
+    let crt = fiscal_settings.crt as u8;
+    let source = load_nfe_source(pool, &request, crt).await?;
+    let recipient = load_nfe_recipient(pool, &source.customer_id).await?;
+
+    // CFOP interestadual (6xxx saída, 2xxx entrada) quando o destinatário está em outra UF
+    let emitter_uf = fiscal_settings.uf.clone();
+    let interstate = !recipient.address.uf.eq_ignore_ascii_case(&emitter_uf);
+    let mut items = source.items;
+    for item in &mut items {
+        if interstate && item.cfop.starts_with('5') {
+            item.cfop.replace_range(0..1, "6");
+        } else if interstate && item.cfop.starts_with('1') {
+            item.cfop.replace_range(0..1, "2");
+        }
+    }
+    let nfe_items = build_items(pool, &items, crt).await?;
+
+    let freight = request.freight.as_ref().map(build_freight);
+    let freight_value = freight.as_ref().map(|f| f.value).unwrap_or(0.0);
+    let total_note = round_cents(source.total_products - source.total_discount + freight_value);
+
+    // Pagamentos da venda só valem se cobrirem exatamente o valor da nota;
+    // devolução não tem pagamento (tPag 90, valor zero)
+    let paid: f64 = source.sale_payments.iter().map(|p| p.value).sum();
+    let payments = if source.return_id.is_some() {
+        vec![build_payment("NONE", 0.0, None, None, None, None)]
+    } else if !source.sale_payments.is_empty() && (paid - total_note).abs() < 0.01 {
+        source.sale_payments
+    } else {
+        vec![build_payment(
+            &source.primary_method,
+            total_note,
+            None,
+            None,
+            None,
+            None,
+        )]
+    };
+
+    let additional_info = match &request.additional_info {
+        Some(info) if !info.trim().is_empty() => Some(format!("{} | {}", source.reference, info)),
+        _ => Some(source.reference.clone()),
+    };
+
+    let serie = fiscal_settings.nfe_serie as u16;
+    let numero = fiscal_settings.nfe_next_number as u32;
+    let emission_date = Utc::now();
+    let access_key = AccessKey::generate(
+        &emitter_uf,
+        emission_date.naive_utc(),
+        &request.emitter_cnpj,
+        55, // Modelo 55 = NF-e
+        serie,
+        numero,
+        1,
+    )?
+    .key;
+
+    let is_return = source.return_id.is_some();
+    let data = NfceData {
+        model: 55,
+        nature_of_operation: request
+            .nature_of_operation
+            .clone()
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| {
+                if is_return {
+                    "DEVOLUÇÃO DE VENDA".to_string()
+                } else {
+                    "VENDA DE MERCADORIA".to_string()
+                }
+            }),
+        uf: emitter_uf.clone(),
+        cnpj: request.emitter_cnpj.clone(),
+        serie,
+        numero,
+        emission_date,
+        emission_type: 1,
+        environment: fiscal_settings.environment as u8,
+        crt,
+        emitter_name: request.emitter_name.clone(),
+        emitter_trade_name: request.emitter_trade_name.clone(),
+        emitter_ie: request.emitter_ie.clone(),
+        emitter_address: request.emitter_address.clone(),
+        emitter_district: request.emitter_district.clone(),
+        emitter_city: request.emitter_city.clone(),
+        emitter_city_code: request.emitter_city_code.clone(),
+        emitter_state: request.emitter_state.clone(),
+        emitter_cep: request.emitter_cep.clone(),
+        recipient_cpf: None,
+        recipient_name: None,
+        recipient: Some(recipient),
+        freight,
+        additional_info,
+        operation_type: if is_return { 0 } else { 1 },
+        purpose: if is_return { 4 } else { 1 },
+        referenced_keys: source.referenced_key.into_iter().collect(),
+        items: nfe_items,
+        total_products: source.total_products,
+        total_discount: source.total_discount,
+        total_note,
+        payments,
+        csc_id: String::new(),
+        csc: String::new(),
+    };
+
+    let xml = NfceXmlBuilder::new(data.clone(), access_key.clone()).build()?;
+    let validation_errors = validate_nfe_xml(&xml);
+    if !validation_errors.is_empty() {
+        return Ok(NfeEmissionResponse {
+            success: false,
+            message: format!(
+                "NF-e reprovada na validação local ({} erro(s)); nada foi enviado à SEFAZ",
+                validation_errors.len()
+            ),
+            access_key: Some(access_key),
+            protocol: None,
+            xml: Some(xml),
+            danfe_pdf: None,
+            pdf_path: None,
+            validation_errors,
+        });
+    }
+    let signed_xml = XmlSigner::new(cert).sign(&xml)?;
+
+    let client = SefazClient::new(
+        emitter_uf,
+        settings_environment(&fiscal_settings),
+        Some(cert_path),
+        Some(cert_password),
+    )?
+    .with_model(55);
+
+    let response = client
+        .authorize(&signed_xml)
+        .await
+        .map_err(|e| format!("Falha de comunicação com a SEFAZ (NF-e não emitida): {}", e))?;
+
+    if response.status_code != "100" {
+        return Ok(NfeEmissionResponse {
+            success: false,
+            message: format!(
+                "Rejeição SEFAZ: {} - {}",
+                response.status_code, response.status_message
+            ),
+            access_key: Some(access_key),
+            protocol: None,
+            xml: Some(signed_xml),
+            danfe_pdf: None,
+            pdf_path: None,
+            validation_errors: Vec::new(),
+        });
+    }
+
+    let protocol = response.protocol.clone();
+    let proc_xml = build_nfe_proc(
+        &signed_xml,
+        &response.xml,
+        &ProtocolInfo {
+            access_key: access_key.clone(),
+            protocol: protocol.clone().unwrap_or_default(),
+            environment: data.environment,
+            status_code: response.status_code.clone(),
+            status_message: response.status_message.clone(),
+            received_at: Utc::now().to_rfc3339(),
+        },
+    )?;
+    let _ = fiscal_repo.increment_nfe_number().await;
+
+    let document_repo = crate::repositories::FiscalDocumentRepository::new(pool);
+    if let Err(e) = document_repo
+        .upsert(CreateFiscalDocument {
+            sale_id: source.sale_id,
+            service_order_id: source.service_order_id,
+            access_key: access_key.clone(),
+            model: 55,
+            serie: serie as i32,
+            number: numero as i32,
+            emission_type: 1,
+            environment: data.environment as i32,
+            status: "AUTHORIZED".to_string(),
+            protocol: protocol.clone(),
+            total: data.total_note,
+            xml: proc_xml.clone(),
+            issued_at: emission_date.to_rfc3339(),
+        })
+        .await
+    {
+        tracing::error!("Falha ao armazenar XML da NF-e {}: {}", access_key, e);
+    }
+    if let Some(return_id) = &source.return_id {
+        if let Err(e) = crate::repositories::SaleReturnRepository::new(pool)
+            .set_fiscal_access_key(return_id, &access_key)
+            .await
+        {
+            tracing::error!("Falha ao vincular NF-e {} à devolução: {}", access_key, e);
+        }
+    }
+
+    let pdf = DanfeA4::new(data, access_key.clone(), protocol.clone()).generate_pdf()?;
+    if let Some(path) = &request.pdf_path {
+        std::fs::write(path, &pdf).map_err(|e| format!("Erro ao gravar DANFE {}: {}", path, e))?;
+    }
+
+    Ok(NfeEmissionResponse {
+        success: true,
+        message: "NF-e autorizada com sucesso".to_string(),
+        access_key: Some(access_key),
+        protocol,
+        xml: Some(proc_xml),
+        danfe_pdf: Some(pdf),
+        pdf_path: request.pdf_path,
+        validation_errors: Vec::new(),
+    })
+}
+
+/// Carrega itens, valores e pagamentos da venda ou das peças da OS
+/// Impede segunda nota para a mesma origem: NFC-e ou NF-e autorizada (ou em
+/// contingência, aguardando transmissão) já documenta a operação
+fn ensure_not_invoiced(documents: Vec<FiscalDocument>, origin: &str) -> Result<(), String> {
+    match documents
+        .into_iter()
+        .find(|d| d.status == "AUTHORIZED" || d.status == "CONTINGENCY")
+    {
+        Some(doc) => Err(format!(
+            "{} já possui {} emitida (chave {})",
+            origin,
+            if doc.model == 65 { "NFC-e" } else { "NF-e" },
+            doc.access_key
+        )),
+        None => Ok(()),
+    }
+}
+
+async fn load_nfe_source(
+    pool: &sqlx::SqlitePool,
+    request: &EmitNfeRequest,
+    crt: u8,
+) -> Result<NfeSource, String> {
+    let fiscal_repo = crate::repositories::FiscalRepository::new(pool);
+    let default_cfop = if request.source == "RETURN" {
+        "1202" // Devolução de venda de mercadoria
+    } else {
+        "5102"
+    };
+    let cfop = request
+        .cfop
+        .clone()
+        .unwrap_or_else(|| default_cfop.to_string());
+    let default_icms = if crt == crate::nfce::tax::CRT_NORMAL {
+        "00"
+    } else {
+        "102"
+    };
+
+    // (product_id, descrição, unidade, quantidade, preço unitário)
+    let (lines, mut source) = match request.source.as_str() {
+        "SALE" => {
+            let sale_repo = crate::repositories::SaleRepository::new(pool);
+            let sale = sale_repo
+                .find_by_id(&request.source_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or("Venda não encontrada")?;
+            if sale.status == "CANCELED" {
+                return Err("Venda cancelada não pode ser faturada".to_string());
+            }
+            ensure_not_invoiced(
+                crate::repositories::FiscalDocumentRepository::new(pool)
+                    .find_by_sale(&sale.id)
+                    .await
+                    .map_err(|e| e.to_string())?,
+                "Venda",
+            )?;
+            let customer_id = sale
+                .customer_id
+                .clone()
+                .ok_or("Venda sem cliente identificado")?;
+            let items = sale_repo
+                .find_items_by_sale(&sale.id)
+                .await
+                .map_err(|e| e.to_string())?;
+            let payments = sale_repo
+                .find_payments_by_sale(&sale.id)
+                .await
+                .map_err(|e| e.to_string())?
+                .iter()
+                .map(|p| {
+                    build_payment(
+                        p.method.as_str(),
+                        p.amount,
+                        p.card_integration,
+                        p.acquirer_cnpj.clone(),
+                        p.card_brand.clone(),
+                        p.authorization_code.clone(),
+                    )
+                })
+                .collect();
+
+            let lines: Vec<_> = items
+                .into_iter()
+                .map(|i| {
+                    (
+                        Some(i.product_id),
+                        i.product_name,
+                        commercial_unit(&i.product_unit),
+                        i.quantity,
+                        i.unit_price,
+                    )
+                })
+                .collect();
+            let gross: f64 = lines.iter().map(|l| l.3 * l.4).sum();
+            let source = NfeSource {
+                sale_id: Some(sale.id.clone()),
+                service_order_id: None,
+                return_id: None,
+                referenced_key: None,
+                customer_id,
+                items: Vec::new(),
+                total_products: 0.0,
+                total_discount: round_cents((gross - sale.total).max(0.0)),
+                sale_payments: payments,
+                primary_method: sale.payment_method.clone(),
+                reference: format!("Venda nº {}", sale.daily_number),
+            };
+            (lines, source)
+        }
+        "SERVICE_ORDER" => {
+            let order_repo = crate::repositories::ServiceOrderRepository::new(pool.clone());
+            let order = order_repo
+                .find_by_id(&request.source_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or("Ordem de serviço não encontrada")?;
+            if order.status == "CANCELED" {
+                return Err("Ordem de serviço cancelada não pode ser faturada".to_string());
+            }
+            ensure_not_invoiced(
+                crate::repositories::FiscalDocumentRepository::new(pool)
+                    .find_by_service_order(&order.id)
+                    .await
+                    .map_err(|e| e.to_string())?,
+                "Ordem de serviço",
+            )?;
+
+            // Mão de obra é serviço (ISS) e fica fora da NF-e; apenas as peças são faturadas
+            let parts: Vec<_> = order_repo
+                .find_order_items(&order.id)
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .filter(|i| i.item_type == "PART")
+                .collect();
+            let discount: f64 = parts.iter().map(|p| (p.subtotal - p.total).max(0.0)).sum();
+            let lines = parts
+                .into_iter()
+                .map(|p| {
+                    (
+                        p.product_id,
+                        p.description,
+                        "UN".to_string(),
+                        p.quantity,
+                        p.unit_price,
+                    )
+                })
+                .collect();
+            let source = NfeSource {
+                sale_id: None,
+                service_order_id: Some(order.id.clone()),
+                return_id: None,
+                referenced_key: None,
+                customer_id: order.customer_id.clone(),
+                items: Vec::new(),
+                total_products: 0.0,
+                total_discount: round_cents(discount),
+                sale_payments: Vec::new(),
+                primary_method: order.payment_method.clone().unwrap_or_default(),
+                reference: format!("Referente à OS nº {}", order.order_number),
+            };
+            (lines, source)
+        }
+        "RETURN" => {
+            let return_repo = crate::repositories::SaleReturnRepository::new(pool);
+            let sale_return = return_repo
+                .find_by_id(&request.source_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or("Devolução não encontrada")?;
+            if sale_return.fiscal_access_key.is_some() {
+                return Err("Devolução já possui NF-e emitida".to_string());
+            }
+            let customer_id = sale_return
+                .customer_id
+                .clone()
+                .ok_or("Devolução sem cliente identificado")?;
+
+            // A devolução referencia a nota (NFC-e ou NF-e) autorizada da venda
+            let referenced_key = crate::repositories::FiscalDocumentRepository::new(pool)
+                .find_by_sale(&sale_return.sale_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .find(|d| d.status == "AUTHORIZED")
+                .map(|d| d.access_key)
+                .ok_or("Venda original sem nota fiscal autorizada para referenciar")?;
+
+            let sale_items = crate::repositories::SaleRepository::new(pool)
+                .find_items_by_sale(&sale_return.sale_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            let lines = sale_return
+                .items
+                .into_iter()
+                .map(|i| {
+                    let unit = sale_items
+                        .iter()
+                        .find(|s| s.id == i.sale_item_id)
+                        .map(|s| commercial_unit(&s.product_unit))
+                        .unwrap_or_else(|| "UN".to_string());
+                    (
+                        Some(i.product_id),
+                        i.product_name,
+                        unit,
+                        i.quantity,
+                        i.unit_price,
+                    )
+                })
+                .collect();
+            let reference = format!("Devolução referente à chave {}", referenced_key);
+            let source = NfeSource {
+                sale_id: None,
+                service_order_id: None,
+                return_id: Some(sale_return.id.clone()),
+                referenced_key: Some(referenced_key),
+                customer_id,
+                items: Vec::new(),
+                total_products: 0.0,
+                total_discount: 0.0,
+                sale_payments: Vec::new(),
+                primary_method: "NONE".to_string(),
+                reference,
+            };
+            (lines, source)
+        }
+        other => return Err(format!("Origem inválida para NF-e: {}", other)),
+    };
+
+    if lines.is_empty() {
+        return Err("Nenhuma mercadoria a faturar".to_string());
+    }
+
+    for (product_id, description, unit, quantity, unit_price) in lines {
+        let info = match &product_id {
+            Some(id) => fiscal_repo
+                .find_product_fiscal_info(id)
+                .await
+                .map_err(|e| e.to_string())?,
+            None => None,
+        };
+        let Some((info, ncm)) = info.and_then(|i| i.ncm.clone().map(|ncm| (i, ncm))) else {
+            return Err(format!("Produto '{}' sem NCM cadastrado", description));
+        };
+
+        source.items.push(EmissionItem {
+            product_id,
+            code: info.internal_code,
+            description,
+            ncm,
+            cfop: cfop.clone(),
+            unit,
+            quantity,
+            unit_value: unit_price,
+            total_value: round_cents(quantity * unit_price),
+            ean: info.barcode,
+            icms_origin: request.icms_origin,
+            icms_cst: request
+                .icms_cst
+                .clone()
+                .unwrap_or_else(|| default_icms.to_string()),
+            pis_cst: request.pis_cst.clone().unwrap_or_else(|| "07".to_string()),
+            cofins_cst: request
+                .cofins_cst
+                .clone()
+                .unwrap_or_else(|| "07".to_string()),
+        });
+    }
+
+    source.total_products = round_cents(source.items.iter().map(|i| i.total_value).sum());
+    Ok(source)
+}
+
+/// Monta o destinatário a partir do cadastro do cliente (CNPJ/IE/IBGE em dados fiscais)
+async fn load_nfe_recipient(
+    pool: &sqlx::SqlitePool,
+    customer_id: &str,
+) -> Result<NfeRecipient, String> {
+    let customer = crate::repositories::CustomerRepository::new(pool)
+        .find_by_id(customer_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Cliente não encontrado")?;
+    let fiscal = crate::repositories::FiscalRepository::new(pool)
+        .find_customer_fiscal_data(customer_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let digits = |value: Option<String>| {
+        value
+            .map(|v| v.chars().filter(char::is_ascii_digit).collect::<String>())
+            .filter(|v| !v.is_empty())
+    };
+    let document = digits(fiscal.as_ref().and_then(|f| f.cnpj.clone()))
+        .or_else(|| digits(customer.cpf.clone()))
+        .ok_or("Cliente sem CNPJ/CPF cadastrado")?;
+
+    // UF define idDest e o CFOP (interna x interestadual): não pode ser presumida
+    let uf = customer
+        .state
+        .map(|s| s.trim().to_uppercase())
+        .filter(|s| !s.is_empty())
+        .ok_or("Cliente sem UF cadastrada no endereço")?;
+    crate::nfce::event::uf_code(&uf)
+        .map_err(|_| format!("UF inválida no endereço do cliente: {}", uf))?;
+
+    Ok(NfeRecipient {
+        document,
+        name: customer.name,
+        state_registration: fiscal.as_ref().and_then(|f| f.state_registration.clone()),
+        email: customer.email,
+        address: NfeAddress {
+            street: customer.street.unwrap_or_default(),
+            number: customer.number.unwrap_or_else(|| "S/N".to_string()),
+            complement: customer.complement,
+            district: customer.neighborhood.unwrap_or_default(),
+            city_code: fiscal.and_then(|f| f.city_code).unwrap_or_default(),
+            city: customer.city.unwrap_or_default(),
+            uf,
+            cep: digits(customer.zip_code).unwrap_or_default(),
+            phone: digits(customer.phone),
+        },
+    })
+}
+
+fn build_freight(input: &EmissionFreight) -> NfeFreight {
+    let carrier = match (&input.carrier_document, &input.carrier_name) {
+        (Some(document), Some(name)) => Some(NfeCarrier {
+            document: document.chars().filter(char::is_ascii_digit).collect(),
+            name: name.clone(),
+            state_registration: input.carrier_ie.clone(),
+            address: input.carrier_address.clone(),
+            city: input.carrier_city.clone(),
+            uf: input.carrier_uf.clone(),
+        }),
+        _ => None,
+    };
+    let volumes = input.volumes.map(|quantity| NfeVolumes {
+        quantity,
+        species: input.species.clone(),
+        net_weight: input.net_weight,
+        gross_weight: input.gross_weight,
+    });
+
+    NfeFreight {
+        modality: input.modality,
+        value: round_cents(input.value),
+        carrier,
+        volumes,
+    }
+}
+
+/// Unidade comercial (uCom) a partir da unidade gravada no produto
+fn commercial_unit(unit: &str) -> String {
+    match unit.to_uppercase().as_str() {
+        "KILOGRAM" => "KG",
+        "GRAM" => "G",
+        "LITER" => "L",
+        "MILLILITER" => "ML",
+        "METER" => "M",
+        "CENTIMETER" => "CM",
+        "BOX" => "CX",
+        "PACK" => "PCT",
+        "DOZEN" => "DZ",
+        "KIT" => "KIT",
+        "PAIR" => "PAR",
+        _ => "UN",
+    }
+    .to_string()
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[command]
+pub async fn get_customer_fiscal_data(
+    customer_id: String,
+    state: State<'_, AppState>,
+) -> Result<Option<CustomerFiscalData>, String> {
+    let repo = crate::repositories::FiscalRepository::new(state.pool());
+    repo.find_customer_fiscal_data(&customer_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Atualiza CNPJ, IE e código IBGE do cliente (dados do destinatário da NF-e)
+#[command]
+pub async fn update_customer_fiscal_data(
+    data: CustomerFiscalData,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let digits = |value: &Option<String>| {
+        value
+            .as_ref()
+            .map(|v| v.chars().filter(char::is_ascii_digit).collect::<String>())
+            .filter(|v| !v.is_empty())
+    };
+    let cnpj = digits(&data.cnpj);
+    if cnpj.as_ref().is_some_and(|c| c.len() != 14) {
+        return Err("CNPJ deve ter 14 dígitos".to_string());
+    }
+    let city_code = digits(&data.city_code);
+    if city_code.as_ref().is_some_and(|c| c.len() != 7) {
+        return Err("Código IBGE do município deve ter 7 dígitos".to_string());
+    }
+
+    let repo = crate::repositories::FiscalRepository::new(state.pool());
+    repo.update_customer_fiscal_data(&CustomerFiscalData {
+        customer_id: data.customer_id,
+        cnpj,
+        state_registration: data
+            .state_registration
+            .map(|ie| ie.trim().to_uppercase())
+            .filter(|ie| !ie.is_empty()),
+        city_code,
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn set_product_ncm(
+    product_id: String,
+    ncm: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let ncm = ncm
+        .map(|n| n.chars().filter(char::is_ascii_digit).collect::<String>())
+        .filter(|n| !n.is_empty());
+    if ncm.as_ref().is_some_and(|n| n.len() != 8) {
+        return Err("NCM deve ter 8 dígitos".to_string());
+    }
+
+    let repo = crate::repositories::FiscalRepository::new(state.pool());
+    repo.set_product_ncm(&product_id, ncm.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
 fn map_payment_method(method: &str) -> String {
     match method.to_uppercase().as_str() {
         "CASH" | "DINHEIRO" => "01".to_string(),
@@ -508,6 +2144,8 @@ fn map_payment_method(method: &str) -> String {
         "DEBIT" | "DEBITO" => "04".to_string(),
         "PIX" => "17".to_string(),
         "VOUCHER" | "VALE" => "10".to_string(), // Vale Alimentação = 10, Refeição = 11
-        _ => "99".to_string(),                  // Outros
+        "ACCOUNT" | "FIADO" | "STORE_CREDIT" | "EXCHANGE_CREDIT" => "05".to_string(), // Crédito Loja
+        "NONE" => "90".to_string(), // Sem pagamento (devolução)
+        _ => "99".to_string(),      // Outros
     }
 }
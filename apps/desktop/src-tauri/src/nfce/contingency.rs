@@ -16,6 +16,16 @@ pub struct OfflineNote {
     pub xml: String,
     pub created_at: String,
     pub status: String, // "PENDING", "TRANSMITTED", "ERROR"
+
+    // Controle da retransmissão automática
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub next_attempt_at: Option<String>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub deadline_alerted: bool,
 }
 
 pub struct ContingencyManager {
@@ -29,8 +39,11 @@ impl ContingencyManager {
             .app_data_dir()
             .expect("Failed to get app data dir");
 
-        let storage_path = app_dir.join("nfce_contingency");
+        Self::with_path(app_dir.join("nfce_contingency"))
+    }
 
+    /// Fila gravada em um diretório específico (ex: testes de integração)
+    pub fn with_path(storage_path: PathBuf) -> Self {
         if !storage_path.exists() {
             fs::create_dir_all(&storage_path).expect("Failed to create contingency dir");
         }
@@ -45,12 +58,21 @@ impl ContingencyManager {
             xml: signed_xml.to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
             status: "PENDING".to_string(),
+            attempts: 0,
+            next_attempt_at: None,
+            last_error: None,
+            deadline_alerted: false,
         };
 
-        let file_name = format!("{}.json", access_key);
+        self.update_note(&note)
+    }
+
+    /// Regrava a nota na fila (tentativas, próximo envio, último erro)
+    pub fn update_note(&self, note: &OfflineNote) -> Result<(), String> {
+        let file_name = format!("{}.json", note.access_key);
         let file_path = self.storage_path.join(file_name);
 
-        let json = serde_json::to_string_pretty(&note).map_err(|e| e.to_string())?;
+        let json = serde_json::to_string_pretty(note).map_err(|e| e.to_string())?;
         fs::write(file_path, json).map_err(|e| e.to_string())?;
 
         Ok(())
@@ -77,9 +99,24 @@ impl ContingencyManager {
             }
         }
 
+        // Ordem de emissão: a SEFAZ deve receber as notas na sequência em que foram geradas
+        notes.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
         Ok(notes)
     }
 
+    /// Retira a nota da fila automática após rejeição definitiva (requer ação manual)
+    pub fn mark_as_error(&self, access_key: &str, message: &str) -> Result<(), String> {
+        let file_path = self.storage_path.join(format!("{}.json", access_key));
+        let content = fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
+        let mut note: OfflineNote = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+
+        note.status = "ERROR".to_string();
+        note.last_error = Some(message.to_string());
+
+        self.update_note(&note)
+    }
+
     /// Marca uma nota como transmitida (move para histórico ou deleta)
     pub fn mark_as_transmitted(&self, access_key: &str) -> Result<(), String> {
         let file_name = format!("{}.json", access_key);
@@ -151,4 +188,33 @@ mod tests {
 
         let _ = fs::remove_dir_all(manager.get_storage_path());
     }
+
+    #[test]
+    fn test_mark_as_error_leaves_queue() {
+        let manager = create_tmp_manager();
+        let access_key = "35260100123456780001906500100000000111234567892";
+        let xml = "<NFe><infNFe Id=\"NFe3\"></infNFe></NFe>";
+
+        manager.save_note(access_key, xml).expect("save failed");
+        manager
+            .mark_as_error(access_key, "539 - Duplicidade")
+            .expect("mark failed");
+
+        assert!(manager
+            .list_pending_notes()
+            .expect("list failed")
+            .is_empty());
+
+        let content = fs::read_to_string(
+            manager
+                .get_storage_path()
+                .join(format!("{}.json", access_key)),
+        )
+        .unwrap();
+        let note: OfflineNote = serde_json::from_str(&content).unwrap();
+        assert_eq!(note.status, "ERROR");
+        assert_eq!(note.last_error.as_deref(), Some("539 - Duplicidade"));
+
+        let _ = fs::remove_dir_all(manager.get_storage_path());
+    }
 }
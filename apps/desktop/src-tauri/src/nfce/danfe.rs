@@ -1,9 +1,17 @@
 // ════════════════════════════════════════════════════════════════════════════
 // GERADOR DE DANFE NFC-e - Impressão Térmica ESC/POS
 // ════════════════════════════════════════════════════════════════════════════
-//! Geração de DANFE simplificado para impressoras térmicas 80mm
+//! Geração do DANFE NFC-e para impressoras térmicas (58mm e 80mm), em PDF
+//! (envio por e-mail/WhatsApp) e reconstrução a partir do XML armazenado
+//! para reimpressão pela chave de acesso.
 
 use chrono::{DateTime, Utc};
+use encoding_rs::WINDOWS_1252;
+use roxmltree::{Document, Node};
+
+use crate::hardware::printer::PrinterConfig;
+use crate::nfce::pdf::{fit_text, wrap_text, Font, PdfDocument, PdfPage};
+use crate::nfce::qrcode::{QrCodeGenerator, QrCodeParams};
 
 #[derive(Debug, Clone)]
 pub struct DanfeData {
@@ -33,8 +41,8 @@ pub struct DanfeData {
     pub total_items: f64,
 
     // Pagamento
-    pub payment_method: String,
-    pub payment_value: f64,
+    pub payments: Vec<DanfePayment>,
+    pub change: f64,
 
     // QR Code (imagem PNG em bytes)
     pub qrcode_png: Vec<u8>,
@@ -53,193 +61,205 @@ pub struct DanfeItem {
     pub total_value: f64,
 }
 
+#[derive(Debug, Clone)]
+pub struct DanfePayment {
+    pub method: String, // Código tPag
+    pub value: f64,
+}
+
+/// Largura do DANFE em colunas da fonte A (12 pontos por caractere)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DanfeLayout {
+    pub columns: usize,
+}
+
+impl DanfeLayout {
+    /// Bobina de 80mm: 48 colunas / 576 pontos
+    pub const PAPER_80MM: Self = Self { columns: 48 };
+    /// Bobina de 58mm: 32 colunas / 384 pontos
+    pub const PAPER_58MM: Self = Self { columns: 32 };
+
+    /// Usa a largura (em colunas) configurada para a impressora
+    pub fn from_config(config: &PrinterConfig) -> Self {
+        Self {
+            columns: (config.paper_width as usize).clamp(24, 64),
+        }
+    }
+
+    /// Largura do QR Code em pontos: 2/3 da área imprimível
+    fn qrcode_dots(&self) -> u32 {
+        (self.columns as u32 * 12) * 2 / 3
+    }
+}
+
+impl Default for DanfeLayout {
+    fn default() -> Self {
+        Self::PAPER_80MM
+    }
+}
+
 pub struct DanfePrinter;
 
 impl DanfePrinter {
-    /// Gera comandos ESC/POS para impressão
+    /// Gera comandos ESC/POS para impressão em bobina de 80mm
     pub fn generate_escpos(data: &DanfeData) -> Result<Vec<u8>, String> {
-        let mut commands = Vec::new();
+        Self::generate_escpos_with_layout(data, DanfeLayout::default())
+    }
 
-        // Inicializar impressora
-        commands.extend_from_slice(&Self::cmd_init());
+    /// Gera comandos ESC/POS quebrando as linhas na largura do papel
+    pub fn generate_escpos_with_layout(
+        data: &DanfeData,
+        layout: DanfeLayout,
+    ) -> Result<Vec<u8>, String> {
+        let cols = layout.columns;
+        let mut out = EscPosWriter::default();
 
-        // Centralizar texto
-        commands.extend_from_slice(&Self::cmd_align_center());
+        // Inicializar impressora
+        out.raw(&Self::cmd_init());
 
         // Cabeçalho - Nome do estabelecimento
-        commands.extend_from_slice(&Self::cmd_bold_on());
-        commands.extend_from_slice(&Self::cmd_double_height());
-        commands.extend_from_slice(data.emitter_name.as_bytes());
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands.extend_from_slice(&Self::cmd_normal_size());
-        commands.extend_from_slice(&Self::cmd_bold_off());
+        out.raw(&Self::cmd_align_center());
+        out.raw(&Self::cmd_bold_on());
+        out.wrapped(&data.emitter_name, cols);
+        out.raw(&Self::cmd_bold_off());
 
         // Nome fantasia
         if let Some(trade_name) = &data.emitter_trade_name {
-            commands.extend_from_slice(trade_name.as_bytes());
-            commands.extend_from_slice(&Self::cmd_lf());
+            out.wrapped(trade_name, cols);
         }
 
         // CNPJ/IE
-        commands.extend_from_slice(
-            format!("CNPJ: {}", Self::format_cnpj(&data.emitter_cnpj)).as_bytes(),
+        out.wrapped(
+            &format!(
+                "CNPJ: {} IE: {}",
+                Self::format_cnpj(&data.emitter_cnpj),
+                data.emitter_ie
+            ),
+            cols,
         );
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands.extend_from_slice(format!("IE: {}", data.emitter_ie).as_bytes());
-        commands.extend_from_slice(&Self::cmd_lf());
 
         // Endereço
-        commands.extend_from_slice(&Self::cmd_font_small());
-        commands.extend_from_slice(data.emitter_address.as_bytes());
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands
-            .extend_from_slice(format!("{} - {}", data.emitter_city, data.emitter_uf).as_bytes());
-        commands.extend_from_slice(&Self::cmd_lf());
+        out.wrapped(&data.emitter_address, cols);
+        out.wrapped(
+            &format!("{} - {}", data.emitter_city, data.emitter_uf),
+            cols,
+        );
         if let Some(phone) = &data.emitter_phone {
-            commands.extend_from_slice(format!("Tel: {}", phone).as_bytes());
-            commands.extend_from_slice(&Self::cmd_lf());
+            out.line(&format!("Tel: {}", phone));
         }
-        commands.extend_from_slice(&Self::cmd_font_normal());
-
-        // Linha separadora
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands.extend_from_slice(b"------------------------------------------------");
-        commands.extend_from_slice(&Self::cmd_lf());
 
         // Título DANFE NFC-e
-        commands.extend_from_slice(&Self::cmd_bold_on());
-        commands.extend_from_slice(b"DANFE NFC-e");
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands.extend_from_slice(b"Documento Auxiliar da NF-e");
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands.extend_from_slice(&Self::cmd_bold_off());
-
-        // Número e série
-        commands.extend_from_slice(format!("No. {} Serie {}", data.number, data.series).as_bytes());
-        commands.extend_from_slice(&Self::cmd_lf());
-
-        // Data de emissão
-        commands.extend_from_slice(
-            format!(
-                "Emissao: {}",
-                data.emission_date.format("%d/%m/%Y %H:%M:%S")
-            )
-            .as_bytes(),
+        out.line(&separator(cols));
+        out.raw(&Self::cmd_bold_on());
+        out.wrapped(
+            "DANFE NFC-e - Documento Auxiliar da Nota Fiscal de Consumidor Eletronica",
+            cols,
         );
-        commands.extend_from_slice(&Self::cmd_lf());
-
-        // Linha separadora
-        commands.extend_from_slice(b"------------------------------------------------");
-        commands.extend_from_slice(&Self::cmd_lf());
+        out.raw(&Self::cmd_bold_off());
+        out.line(&separator(cols));
 
         // Itens
-        commands.extend_from_slice(&Self::cmd_align_left());
-        commands.extend_from_slice(&Self::cmd_font_small());
-
+        out.raw(&Self::cmd_align_left());
+        out.line(&columns_line("Cod Descricao", "Vl Total", cols));
         for item in &data.items {
-            // Código e descrição
-            commands.extend_from_slice(format!("#{} {}", item.code, item.description).as_bytes());
-            commands.extend_from_slice(&Self::cmd_lf());
-
-            // Quantidade, valor unitário e total
-            commands.extend_from_slice(
-                format!(
-                    "{:.3} {} x {:.2} = {:.2}",
-                    item.quantity, item.unit, item.unit_value, item.total_value
-                )
-                .as_bytes(),
-            );
-            commands.extend_from_slice(&Self::cmd_lf());
+            out.wrapped(&format!("{} {}", item.code, item.description), cols);
+            out.line(&columns_line(
+                &format!(
+                    "  {} {} x {}",
+                    format_quantity(item.quantity),
+                    item.unit,
+                    format_money(item.unit_value)
+                ),
+                &format_money(item.total_value),
+                cols,
+            ));
         }
-
-        commands.extend_from_slice(&Self::cmd_font_normal());
-
-        // Linha separadora
-        commands.extend_from_slice(b"------------------------------------------------");
-        commands.extend_from_slice(&Self::cmd_lf());
+        out.line(&separator(cols));
 
         // Totais
-        commands.extend_from_slice(&Self::cmd_align_right());
-        commands.extend_from_slice(format!("Total de Itens: {:.2}", data.total_items).as_bytes());
-        commands.extend_from_slice(&Self::cmd_lf());
-
+        out.line(&columns_line(
+            "Qtde. total de itens",
+            &data.items.len().to_string(),
+            cols,
+        ));
+        out.line(&columns_line(
+            "Valor total R$",
+            &format_money(data.total_items),
+            cols,
+        ));
         if data.discount > 0.0 {
-            commands.extend_from_slice(format!("Desconto: -{:.2}", data.discount).as_bytes());
-            commands.extend_from_slice(&Self::cmd_lf());
+            out.line(&columns_line(
+                "Desconto R$",
+                &format!("-{}", format_money(data.discount)),
+                cols,
+            ));
         }
+        out.raw(&Self::cmd_bold_on());
+        out.line(&columns_line(
+            "Valor a pagar R$",
+            &format_money(data.total),
+            cols,
+        ));
+        out.raw(&Self::cmd_bold_off());
+
+        // Formas de pagamento
+        out.line(&columns_line("FORMA PAGAMENTO", "VALOR PAGO R$", cols));
+        for payment in &data.payments {
+            out.line(&columns_line(
+                Self::format_payment_method(&payment.method),
+                &format_money(payment.value),
+                cols,
+            ));
+        }
+        if data.change > 0.0 {
+            out.line(&columns_line("Troco R$", &format_money(data.change), cols));
+        }
+        out.line(&separator(cols));
+
+        // Consulta pela chave
+        out.raw(&Self::cmd_align_center());
+        out.wrapped("Consulte pela Chave de Acesso:", cols);
+        out.wrapped(&Self::format_access_key(&data.access_key), cols);
+        out.line(&format!(
+            "NFC-e n. {} Serie {} {}",
+            data.number,
+            data.series,
+            data.emission_date.format("%d/%m/%Y %H:%M:%S")
+        ));
 
-        commands.extend_from_slice(&Self::cmd_bold_on());
-        commands.extend_from_slice(&Self::cmd_double_height());
-        commands.extend_from_slice(format!("TOTAL R$ {:.2}", data.total).as_bytes());
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands.extend_from_slice(&Self::cmd_normal_size());
-        commands.extend_from_slice(&Self::cmd_bold_off());
-
-        // Forma de pagamento
-        commands.extend_from_slice(&Self::cmd_align_left());
-        commands.extend_from_slice(
-            format!(
-                "Pagamento: {} - R$ {:.2}",
-                Self::format_payment_method(&data.payment_method),
-                data.payment_value
-            )
-            .as_bytes(),
-        );
-        commands.extend_from_slice(&Self::cmd_lf());
-
-        // Linha separadora
-        commands.extend_from_slice(b"------------------------------------------------");
-        commands.extend_from_slice(&Self::cmd_lf());
+        // Protocolo de autorização
+        if let Some(protocol) = &data.protocol {
+            out.wrapped(&format!("Protocolo de Autorizacao: {}", protocol), cols);
+        }
 
-        // QR Code
-        commands.extend_from_slice(&Self::cmd_align_center());
-        commands.extend_from_slice(b"Consulte pela Chave de Acesso:");
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands.extend_from_slice(&Self::cmd_font_small());
-        commands.extend_from_slice(Self::format_access_key(&data.access_key).as_bytes());
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands.extend_from_slice(&Self::cmd_font_normal());
-
-        // Imprimir QR Code (se suportado)
+        // QR Code (raster)
         if !data.qrcode_png.is_empty() {
-            commands.extend_from_slice(&Self::cmd_lf());
-            // Tentar renderizar PNG para ESC/POS raster (GS v 0)
-            if let Ok(img_cmds) = Self::rasterize_png_to_escpos(&data.qrcode_png) {
-                commands.extend_from_slice(&img_cmds);
-            } else {
+            out.raw(&Self::cmd_lf());
+            match Self::rasterize_png_to_escpos(&data.qrcode_png, layout.qrcode_dots()) {
+                Ok(image) => out.raw(&image),
                 // Fallback: apenas espaço
-                commands.extend_from_slice(&Self::cmd_lf());
-                commands.extend_from_slice(&Self::cmd_lf());
+                Err(_) => out.raw(&Self::cmd_lf()),
             }
-            commands.extend_from_slice(&Self::cmd_lf());
-        }
-
-        // Protocolo de autorização
-        if let Some(protocol) = &data.protocol {
-            commands.extend_from_slice(b"Protocolo de Autorizacao:");
-            commands.extend_from_slice(&Self::cmd_lf());
-            commands.extend_from_slice(protocol.as_bytes());
-            commands.extend_from_slice(&Self::cmd_lf());
+            out.raw(&Self::cmd_lf());
         }
 
         // Informações adicionais
         if let Some(info) = &data.additional_info {
-            commands.extend_from_slice(&Self::cmd_lf());
-            commands.extend_from_slice(&Self::cmd_font_small());
-            commands.extend_from_slice(info.as_bytes());
-            commands.extend_from_slice(&Self::cmd_lf());
-            commands.extend_from_slice(&Self::cmd_font_normal());
+            out.raw(&Self::cmd_lf());
+            for line in info.lines() {
+                out.wrapped(line, cols);
+            }
         }
 
         // Rodapé
-        commands.extend_from_slice(&Self::cmd_lf());
-        commands.extend_from_slice(b"Emitido via Sistema GIRO");
-        commands.extend_from_slice(&Self::cmd_lf());
+        out.raw(&Self::cmd_lf());
+        out.line("Emitido via Sistema GIRO");
+        out.raw(&Self::cmd_lf());
 
         // Cortar papel
-        commands.extend_from_slice(&Self::cmd_cut());
+        out.raw(&Self::cmd_cut());
 
-        Ok(commands)
+        Ok(out.bytes)
     }
 
     // ========================================
@@ -258,10 +278,6 @@ impl DanfePrinter {
         vec![0x1B, 0x61, 0x00] // ESC a 0
     }
 
-    fn cmd_align_right() -> Vec<u8> {
-        vec![0x1B, 0x61, 0x02] // ESC a 2
-    }
-
     fn cmd_bold_on() -> Vec<u8> {
         vec![0x1B, 0x45, 0x01] // ESC E 1
     }
@@ -270,22 +286,6 @@ impl DanfePrinter {
         vec![0x1B, 0x45, 0x00] // ESC E 0
     }
 
-    fn cmd_double_height() -> Vec<u8> {
-        vec![0x1B, 0x21, 0x10] // ESC ! 16
-    }
-
-    fn cmd_normal_size() -> Vec<u8> {
-        vec![0x1B, 0x21, 0x00] // ESC ! 0
-    }
-
-    fn cmd_font_small() -> Vec<u8> {
-        vec![0x1B, 0x21, 0x01] // ESC ! 1
-    }
-
-    fn cmd_font_normal() -> Vec<u8> {
-        vec![0x1B, 0x21, 0x00] // ESC ! 0
-    }
-
     fn cmd_lf() -> Vec<u8> {
         vec![0x0A] // LF
     }
@@ -298,7 +298,7 @@ impl DanfePrinter {
     // FORMATADORES
     // ========================================
 
-    fn format_cnpj(cnpj: &str) -> String {
+    pub(crate) fn format_cnpj(cnpj: &str) -> String {
         if cnpj.len() == 14 {
             format!(
                 "{}.{}.{}/{}-{}",
@@ -313,7 +313,7 @@ impl DanfePrinter {
         }
     }
 
-    fn format_access_key(key: &str) -> String {
+    pub(crate) fn format_access_key(key: &str) -> String {
         // Formatar em grupos de 4 dígitos
         if key.len() == 44 {
             format!(
@@ -347,6 +347,7 @@ impl DanfePrinter {
             "12" => "Vale Presente",
             "13" => "Vale Combustivel",
             "15" => "Boleto Bancario",
+            "17" => "PIX",
             "90" => "Sem Pagamento",
             "99" => "Outros",
             _ => "Nao Identificado",
@@ -355,113 +356,532 @@ impl DanfePrinter {
 }
 
 impl DanfePrinter {
-    /// Converte PNG (bytes) para comandos ESC/POS raster (GS v 0)
-    fn rasterize_png_to_escpos(png: &[u8]) -> Result<Vec<u8>, String> {
-        use image::GenericImageView;
-        let img = image::load_from_memory(png).map_err(|e| format!("Erro ao abrir PNG: {}", e))?;
-
-        // Target width for 80mm thermal printers (commonly 384 pixels)
-        let target_width: u32 = 384;
-
-        // Resize image if wider than target, preserving aspect ratio
-        let (width, height) = img.dimensions();
-        let img = if width > target_width {
-            let scale = target_width as f32 / width as f32;
-            let new_h = (height as f32 * scale).max(1.0) as u32;
-            img.resize(target_width, new_h, image::imageops::FilterType::Lanczos3)
+    /// Converte PNG (bytes) para comandos ESC/POS raster (GS v 0), limitado
+    /// a `target_width` pontos
+    fn rasterize_png_to_escpos(png: &[u8], target_width: u32) -> Result<Vec<u8>, String> {
+        let (width, height, black) = monochrome(png, target_width)?;
+
+        let bytes_per_row = width.div_ceil(8) as usize;
+        let mut cmds: Vec<u8> = Vec::new();
+
+        // Faixas de até 24 linhas (limite de buffer das impressoras mais simples)
+        for y0 in (0..height).step_by(24) {
+            let stripe_h = std::cmp::min(24, height - y0);
+
+            // GS v 0 m xL xH yL yH
+            cmds.extend_from_slice(&[0x1D, 0x76, 0x30, 0x00]);
+            cmds.push((bytes_per_row & 0xFF) as u8);
+            cmds.push(((bytes_per_row >> 8) & 0xFF) as u8);
+            cmds.push((stripe_h & 0xFF) as u8);
+            cmds.push(((stripe_h >> 8) & 0xFF) as u8);
+
+            // Dados linha a linha, 8 pixels horizontais por byte (MSB à esquerda)
+            for y in y0..y0 + stripe_h {
+                let row = &black[(y * width) as usize..((y + 1) * width) as usize];
+                for chunk in row.chunks(8) {
+                    let mut byte: u8 = 0;
+                    for (bit, &pixel) in chunk.iter().enumerate() {
+                        if pixel {
+                            byte |= 0x80 >> bit;
+                        }
+                    }
+                    cmds.push(byte);
+                }
+            }
+        }
+
+        Ok(cmds)
+    }
+
+    // ========================================
+    // PDF (80mm)
+    // ========================================
+
+    /// DANFE NFC-e em PDF com largura de bobina (80mm), para envio ao cliente
+    pub fn generate_pdf(data: &DanfeData) -> Result<Vec<u8>, String> {
+        let qrcode = if data.qrcode_png.is_empty() {
+            None
         } else {
-            img
+            Some(monochrome(&data.qrcode_png, u32::MAX)?)
         };
 
-        let (width, height) = img.dimensions();
+        // Primeira passagem mede a altura do cupom
+        let mut measure = PdfDocument::new(PDF_WIDTH, 10_000.0);
+        let height = Self::draw_pdf(measure.add_page(), data, qrcode.as_ref()) + PDF_MARGIN;
 
-        // Convert to grayscale
-        let mut gray = img.to_luma8();
+        let mut doc = PdfDocument::new(PDF_WIDTH, height);
+        Self::draw_pdf(doc.add_page(), data, qrcode.as_ref());
+        Ok(doc.to_bytes())
+    }
 
-        if width == 0 || height == 0 {
-            return Err("Imagem vazia".to_string());
+    /// Desenha o cupom e retorna a posição vertical final (mm)
+    fn draw_pdf(
+        page: &mut PdfPage,
+        data: &DanfeData,
+        qrcode: Option<&(u32, u32, Vec<bool>)>,
+    ) -> f64 {
+        let left = PDF_MARGIN;
+        let right = PDF_WIDTH - PDF_MARGIN;
+        let width = right - left;
+        let center = PDF_WIDTH / 2.0;
+        let mut y = PDF_MARGIN + 3.0;
+
+        let centered = |page: &mut PdfPage, y: &mut f64, text: &str, size: f64, font| {
+            for line in wrap_text(text, width, size, font) {
+                page.text_center(center, *y, size, font, &line);
+                *y += size * 0.45;
+            }
+        };
+
+        // Emitente
+        centered(page, &mut y, &data.emitter_name, 9.0, Font::Bold);
+        if let Some(trade_name) = &data.emitter_trade_name {
+            centered(page, &mut y, trade_name, 7.0, Font::Regular);
+        }
+        centered(
+            page,
+            &mut y,
+            &format!(
+                "CNPJ: {}   IE: {}",
+                Self::format_cnpj(&data.emitter_cnpj),
+                data.emitter_ie
+            ),
+            7.0,
+            Font::Regular,
+        );
+        centered(
+            page,
+            &mut y,
+            &format!(
+                "{} - {} - {}",
+                data.emitter_address, data.emitter_city, data.emitter_uf
+            ),
+            7.0,
+            Font::Regular,
+        );
+        if let Some(phone) = &data.emitter_phone {
+            centered(page, &mut y, &format!("Tel: {}", phone), 7.0, Font::Regular);
         }
 
-        // Apply Floyd–Steinberg dithering to convert to monochrome
-        for y in 0..height {
-            for x in 0..width {
-                let old = gray.get_pixel(x, y).0[0] as i16;
-                let new_val = if old < 128 { 0i16 } else { 255i16 };
-                let err = old - new_val;
-                gray.put_pixel(x, y, image::Luma([new_val as u8]));
+        page.line(left, y - 1.5, right, y - 1.5, 0.5);
+        y += 1.5;
+        centered(
+            page,
+            &mut y,
+            "DANFE NFC-e - Documento Auxiliar da Nota Fiscal de Consumidor Eletrônica",
+            7.5,
+            Font::Bold,
+        );
+        page.line(left, y - 1.5, right, y - 1.5, 0.5);
+        y += 1.5;
 
-                if x + 1 < width {
-                    let p = gray.get_pixel(x + 1, y).0[0] as i16;
-                    let v = (p + (err * 7) / 16).clamp(0, 255) as u8;
-                    gray.put_pixel(x + 1, y, image::Luma([v]));
-                }
-                if x > 0 && y + 1 < height {
-                    let p = gray.get_pixel(x - 1, y + 1).0[0] as i16;
-                    let v = (p + (err * 3) / 16).clamp(0, 255) as u8;
-                    gray.put_pixel(x - 1, y + 1, image::Luma([v]));
-                }
-                if y + 1 < height {
-                    let p = gray.get_pixel(x, y + 1).0[0] as i16;
-                    let v = (p + (err * 5) / 16).clamp(0, 255) as u8;
-                    gray.put_pixel(x, y + 1, image::Luma([v]));
-                }
-                if x + 1 < width && y + 1 < height {
-                    let p = gray.get_pixel(x + 1, y + 1).0[0] as i16;
-                    let v = (p + err / 16).clamp(0, 255) as u8;
-                    gray.put_pixel(x + 1, y + 1, image::Luma([v]));
-                }
+        // Itens
+        page.text(left, y, 6.5, Font::Bold, "CÓDIGO  DESCRIÇÃO");
+        page.text_right(right, y, 6.5, Font::Bold, "VL TOTAL");
+        y += 3.5;
+        for item in &data.items {
+            for line in wrap_text(
+                &format!("{} {}", item.code, item.description),
+                width,
+                6.5,
+                Font::Regular,
+            ) {
+                page.text(left, y, 6.5, Font::Regular, &line);
+                y += 3.0;
             }
+            page.text(
+                left + 3.0,
+                y,
+                6.5,
+                Font::Regular,
+                &format!(
+                    "{} {} x {}",
+                    format_quantity(item.quantity),
+                    item.unit,
+                    format_money(item.unit_value)
+                ),
+            );
+            page.text_right(
+                right,
+                y,
+                6.5,
+                Font::Regular,
+                &format_money(item.total_value),
+            );
+            y += 3.5;
+        }
+        page.line(left, y - 1.5, right, y - 1.5, 0.5);
+        y += 2.0;
+
+        // Totais e pagamento
+        let row = |page: &mut PdfPage, y: &mut f64, label: &str, value: &str, font| {
+            page.text(
+                left,
+                *y,
+                7.0,
+                font,
+                &fit_text(label, width - 20.0, 7.0, font),
+            );
+            page.text_right(right, *y, 7.0, font, value);
+            *y += 3.5;
+        };
+        row(
+            page,
+            &mut y,
+            "Qtde. total de itens",
+            &data.items.len().to_string(),
+            Font::Regular,
+        );
+        row(
+            page,
+            &mut y,
+            "Valor total R$",
+            &format_money(data.total_items),
+            Font::Regular,
+        );
+        if data.discount > 0.0 {
+            row(
+                page,
+                &mut y,
+                "Desconto R$",
+                &format!("-{}", format_money(data.discount)),
+                Font::Regular,
+            );
+        }
+        row(
+            page,
+            &mut y,
+            "Valor a pagar R$",
+            &format_money(data.total),
+            Font::Bold,
+        );
+        row(page, &mut y, "FORMA PAGAMENTO", "VALOR PAGO R$", Font::Bold);
+        for payment in &data.payments {
+            row(
+                page,
+                &mut y,
+                Self::format_payment_method(&payment.method),
+                &format_money(payment.value),
+                Font::Regular,
+            );
+        }
+        if data.change > 0.0 {
+            row(
+                page,
+                &mut y,
+                "Troco R$",
+                &format_money(data.change),
+                Font::Regular,
+            );
+        }
+        page.line(left, y - 1.5, right, y - 1.5, 0.5);
+        y += 2.0;
+
+        // Consulta e chave de acesso
+        centered(
+            page,
+            &mut y,
+            "Consulte pela Chave de Acesso:",
+            7.0,
+            Font::Bold,
+        );
+        centered(
+            page,
+            &mut y,
+            &Self::format_access_key(&data.access_key),
+            7.0,
+            Font::Regular,
+        );
+        centered(
+            page,
+            &mut y,
+            &format!(
+                "NFC-e nº {}  Série {}  {}",
+                data.number,
+                data.series,
+                data.emission_date.format("%d/%m/%Y %H:%M:%S")
+            ),
+            7.0,
+            Font::Regular,
+        );
+        if let Some(protocol) = &data.protocol {
+            centered(
+                page,
+                &mut y,
+                &format!("Protocolo de Autorização: {}", protocol),
+                7.0,
+                Font::Regular,
+            );
         }
 
-        // Build monochrome buffer (1 for black, 0 for white)
-        let mut mono: Vec<u8> = vec![0; (width * height) as usize];
-        for y in 0..height {
-            for x in 0..width {
-                let pix = gray.get_pixel(x, y).0[0];
-                mono[(y * width + x) as usize] = if pix == 0 { 1 } else { 0 };
-            }
+        // QR Code
+        if let Some((columns, _, black)) = qrcode {
+            page.mono_image(
+                center - PDF_QRCODE / 2.0,
+                y,
+                PDF_QRCODE,
+                PDF_QRCODE,
+                *columns as usize,
+                black,
+            );
+            y += PDF_QRCODE + 4.0;
         }
 
-        let bytes_per_row = width.div_ceil(8) as usize;
-        let mut cmds: Vec<u8> = Vec::new();
+        // Informações adicionais
+        if let Some(info) = &data.additional_info {
+            centered(page, &mut y, info, 6.5, Font::Regular);
+        }
 
-        // For each row stripe of 8 pixels tall
-        for y0 in (0..height).step_by(8) {
-            // GS v 0 m
-            cmds.extend_from_slice(&[0x1D, 0x76, 0x30, 0x00]);
+        y
+    }
+}
 
-            // xL xH
-            let x_l = (bytes_per_row & 0xFF) as u8;
-            let x_h = ((bytes_per_row >> 8) & 0xFF) as u8;
-            cmds.push(x_l);
-            cmds.push(x_h);
-
-            // yL yH (height of this stripe)
-            let stripe_h = std::cmp::min(8, height - y0) as u16;
-            let y_l = (stripe_h & 0xFF) as u8;
-            let y_h = ((stripe_h >> 8) & 0xFF) as u8;
-            cmds.push(y_l);
-            cmds.push(y_h);
-
-            // data: for each column, build bytes representing 8 vertical pixels
-            for x in 0..width {
-                let mut byte: u8 = 0;
-                for bit in 0..8 {
-                    let yy = y0 + bit;
-                    if yy >= height {
-                        continue;
-                    }
-                    let v = mono[(yy * width + x) as usize];
-                    if v != 0 {
-                        byte |= 1 << (7 - bit);
-                    }
-                }
-                cmds.push(byte);
+// ═══════════════════════════════════════════════════════════════════════════
+// REIMPRESSÃO A PARTIR DO XML
+// ═══════════════════════════════════════════════════════════════════════════
+
+impl DanfeData {
+    /// Reconstrói o DANFE de uma NFC-e a partir do XML armazenado (NFe
+    /// assinada ou nfeProc). O QR Code é recalculado com o CSC informado.
+    pub fn from_xml(xml: &str, csc_id: &str, csc: &str) -> Result<Self, String> {
+        let doc = Document::parse(xml).map_err(|e| format!("XML inválido: {}", e))?;
+
+        let inf_nfe = find(doc.root(), "infNFe").ok_or("XML não contém infNFe")?;
+        let access_key = inf_nfe
+            .attribute("Id")
+            .map(|id| id.trim_start_matches("NFe").to_string())
+            .filter(|key| key.len() == 44)
+            .ok_or("Chave de acesso ausente ou inválida")?;
+
+        let ide = find(inf_nfe, "ide").ok_or("Grupo ide ausente")?;
+        if text(ide, "mod").as_deref() != Some("65") {
+            return Err("Reimpressão de DANFE disponível apenas para NFC-e (modelo 65)".into());
+        }
+        let emission = text(ide, "dhEmi").unwrap_or_default();
+        let emission_date = DateTime::parse_from_rfc3339(&emission)
+            .map(|d| d.with_timezone(&Utc))
+            .map_err(|_| format!("Data de emissão inválida: {}", emission))?;
+        let environment: u8 = text(ide, "tpAmb").and_then(|v| v.parse().ok()).unwrap_or(2);
+
+        let emit = find(inf_nfe, "emit").ok_or("Grupo emit ausente")?;
+        let address = find(emit, "enderEmit");
+        let address_text = |tag| address.and_then(|a| text(a, tag));
+        let emitter_uf = address_text("UF").unwrap_or_default();
+
+        let items = inf_nfe
+            .children()
+            .filter(|n| n.tag_name().name() == "det")
+            .filter_map(|det| find(det, "prod"))
+            .map(|prod| DanfeItem {
+                code: text(prod, "cProd").unwrap_or_default(),
+                description: text(prod, "xProd").unwrap_or_default(),
+                quantity: number(prod, "qCom"),
+                unit: text(prod, "uCom").unwrap_or_default(),
+                unit_value: number(prod, "vUnCom"),
+                total_value: number(prod, "vProd"),
+            })
+            .collect();
+
+        let totals = find(inf_nfe, "ICMSTot").ok_or("Grupo ICMSTot ausente")?;
+        let total = number(totals, "vNF");
+
+        let pag = find(inf_nfe, "pag");
+        let payments = pag
+            .map(|pag| {
+                pag.children()
+                    .filter(|n| n.tag_name().name() == "detPag")
+                    .map(|det| DanfePayment {
+                        method: text(det, "tPag").unwrap_or_default(),
+                        value: number(det, "vPag"),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let protocol = find(doc.root(), "infProt").and_then(|p| text(p, "nProt"));
+        let mut additional_info = find(inf_nfe, "infAdic").and_then(|a| text(a, "infCpl"));
+        if text(ide, "tpEmis").as_deref() == Some("9") && protocol.is_none() {
+            let pending = "EMITIDA EM CONTINGÊNCIA - Pendente de Autorização".to_string();
+            additional_info = Some(match additional_info {
+                Some(info) => format!("{}\n{}", info, pending),
+                None => pending,
+            });
+        }
+
+        let digest_value = find(doc.root(), "DigestValue")
+            .and_then(|n| n.text())
+            .unwrap_or_default()
+            .to_string();
+        let qrcode_png = QrCodeGenerator::generate_png(&QrCodeParams {
+            access_key: access_key.clone(),
+            uf: emitter_uf.clone(),
+            environment,
+            emission_date: emission,
+            total_value: total,
+            digest_value,
+            csc_id: csc_id.to_string(),
+            csc: csc.to_string(),
+        })?;
+
+        Ok(Self {
+            emitter_name: text(emit, "xNome").unwrap_or_default(),
+            emitter_trade_name: text(emit, "xFant"),
+            emitter_cnpj: text(emit, "CNPJ").unwrap_or_default(),
+            emitter_ie: text(emit, "IE").unwrap_or_default(),
+            emitter_address: match (address_text("xLgr"), address_text("nro")) {
+                (Some(street), Some(nro)) => format!("{}, {}", street, nro),
+                (street, _) => street.unwrap_or_default(),
+            },
+            emitter_city: address_text("xMun").unwrap_or_default(),
+            emitter_uf,
+            emitter_phone: address_text("fone"),
+            number: text(ide, "nNF").and_then(|v| v.parse().ok()).unwrap_or(0),
+            series: text(ide, "serie").and_then(|v| v.parse().ok()).unwrap_or(0),
+            emission_date,
+            access_key,
+            protocol,
+            items,
+            total,
+            discount: number(totals, "vDesc"),
+            total_items: number(totals, "vProd"),
+            payments,
+            change: pag.map(|p| number(p, "vTroco")).unwrap_or(0.0),
+            qrcode_png,
+            additional_info,
+        })
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// AUXILIARES
+// ═══════════════════════════════════════════════════════════════════════════
+
+const PDF_WIDTH: f64 = 80.0;
+const PDF_MARGIN: f64 = 4.0;
+const PDF_QRCODE: f64 = 35.0;
+
+/// Acumula o cupom convertendo o texto para Windows-1252
+#[derive(Default)]
+struct EscPosWriter {
+    bytes: Vec<u8>,
+}
+
+impl EscPosWriter {
+    fn raw(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn line(&mut self, text: &str) {
+        let (encoded, _, _) = WINDOWS_1252.encode(text);
+        self.bytes.extend_from_slice(&encoded);
+        self.bytes.push(0x0A);
+    }
+
+    fn wrapped(&mut self, text: &str, columns: usize) {
+        for line in wrap_columns(text, columns) {
+            self.line(&line);
+        }
+    }
+}
+
+fn separator(columns: usize) -> String {
+    "-".repeat(columns)
+}
+
+/// Quebra o texto por palavras em linhas de até `columns` caracteres
+fn wrap_columns(text: &str, columns: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        // Palavra maior que a linha é cortada
+        while word.len() > columns {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
             }
+            lines.push(word.drain(..columns).collect());
+        }
+        let word: String = word.into_iter().collect();
+        if word.is_empty() {
+            continue;
         }
 
-        Ok(cmds)
+        let len = current.chars().count();
+        if len > 0 && len + 1 + word.chars().count() > columns {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Texto à esquerda e valor alinhado à direita na mesma linha
+fn columns_line(left: &str, right: &str, columns: usize) -> String {
+    let right_len = right.chars().count();
+    let max_left = columns.saturating_sub(right_len + 1);
+    let left: String = left.chars().take(max_left).collect();
+    let padding = columns.saturating_sub(left.chars().count() + right_len);
+    format!("{}{}{}", left, " ".repeat(padding), right)
+}
+
+fn format_money(value: f64) -> String {
+    format!("{:.2}", value).replace('.', ",")
+}
+
+/// Quantidade sem casas decimais quando inteira (ex: 2 ou 1,250)
+fn format_quantity(quantity: f64) -> String {
+    if quantity.fract().abs() < 1e-9 {
+        format!("{:.0}", quantity)
+    } else {
+        format!("{:.3}", quantity).replace('.', ",")
+    }
+}
+
+/// Decodifica o PNG em pixels pretos/brancos (largura, altura, pixels),
+/// reduzindo para no máximo `max_width` pontos
+fn monochrome(png: &[u8], max_width: u32) -> Result<(u32, u32, Vec<bool>), String> {
+    use image::GenericImageView;
+    let img = image::load_from_memory(png).map_err(|e| format!("Erro ao abrir PNG: {}", e))?;
+
+    // QR Code é reduzido sem suavização para manter os módulos nítidos
+    let (width, height) = img.dimensions();
+    let img = if width > max_width {
+        let new_h = ((height as f64 * max_width as f64 / width as f64) as u32).max(1);
+        img.resize_exact(max_width, new_h, image::imageops::FilterType::Nearest)
+    } else {
+        img
+    };
+
+    let gray = img.to_luma8();
+    let (width, height) = gray.dimensions();
+    if width == 0 || height == 0 {
+        return Err("Imagem vazia".to_string());
     }
+
+    let black = gray.pixels().map(|p| p.0[0] < 128).collect();
+    Ok((width, height, black))
+}
+
+fn find<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.descendants().find(|n| n.tag_name().name() == tag)
+}
+
+fn text(node: Node, tag: &str) -> Option<String> {
+    node.children()
+        .find(|n| n.tag_name().name() == tag)
+        .and_then(|n| n.text())
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+fn number(node: Node, tag: &str) -> f64 {
+    text(node, tag).and_then(|v| v.parse().ok()).unwrap_or(0.0)
 }
 
 #[cfg(test)]
@@ -494,8 +914,11 @@ mod tests {
             total: 10.0,
             discount: 0.0,
             total_items: 10.0,
-            payment_method: "01".to_string(),
-            payment_value: 10.0,
+            payments: vec![DanfePayment {
+                method: "01".to_string(),
+                value: 10.0,
+            }],
+            change: 0.0,
             qrcode_png: vec![],
             additional_info: Some("Nota gerada para teste".to_string()),
         }
@@ -511,6 +934,124 @@ mod tests {
         assert!(commands.starts_with(&[0x1B, 0x40])); // ESC @
     }
 
+    /// Linhas de texto do cupom sem os comandos ESC/POS
+    fn printed_lines(commands: &[u8]) -> Vec<String> {
+        let mut text = Vec::new();
+        let mut i = 0;
+        while i < commands.len() {
+            match commands[i] {
+                0x1B if commands.get(i + 1) == Some(&0x40) => i += 2,
+                0x1B => i += 3,
+                0x1D => i += 4,
+                byte => {
+                    text.push(byte);
+                    i += 1;
+                }
+            }
+        }
+        String::from_utf8_lossy(&text)
+            .lines()
+            .map(|l| l.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_layout_respects_paper_width() {
+        let mut data = create_test_data();
+        data.items[0].description =
+            "PASTILHA DE FREIO DIANTEIRA PARA MOTOCICLETA MODELO ESPECIAL".to_string();
+
+        for layout in [DanfeLayout::PAPER_58MM, DanfeLayout::PAPER_80MM] {
+            let commands = DanfePrinter::generate_escpos_with_layout(&data, layout).unwrap();
+            let lines = printed_lines(&commands);
+            assert!(lines.iter().all(|l| l.chars().count() <= layout.columns));
+            assert!(lines.contains(&"-".repeat(layout.columns)));
+            // Valor do item alinhado à direita
+            assert!(lines.iter().any(|l| l.starts_with("  1 UN x 10,00")
+                && l.ends_with("10,00")
+                && l.len() == layout.columns));
+        }
+    }
+
+    #[test]
+    fn test_layout_from_config() {
+        let mut config = PrinterConfig::default();
+        assert_eq!(DanfeLayout::from_config(&config), DanfeLayout::PAPER_80MM);
+        config.paper_width = 32;
+        assert_eq!(DanfeLayout::from_config(&config), DanfeLayout::PAPER_58MM);
+        assert_eq!(DanfeLayout::PAPER_58MM.qrcode_dots(), 256);
+    }
+
+    #[test]
+    fn test_wrap_columns() {
+        assert_eq!(
+            wrap_columns("CONSULTE PELA CHAVE DE ACESSO", 12),
+            vec!["CONSULTE", "PELA CHAVE", "DE ACESSO"]
+        );
+        assert_eq!(wrap_columns("ABCDEFGHIJ", 4), vec!["ABCD", "EFGH", "IJ"]);
+        assert_eq!(columns_line("Troco R$", "5,00", 16), "Troco R$    5,00");
+    }
+
+    #[test]
+    fn test_generate_pdf_with_qrcode() {
+        let mut data = create_test_data();
+        data.qrcode_png = QrCodeGenerator::generate_png(&QrCodeParams {
+            access_key: "35260111222333000181650010000000151000000155".to_string(),
+            uf: "SP".to_string(),
+            environment: 2,
+            emission_date: "2026-01-20T10:00:00-03:00".to_string(),
+            total_value: 10.0,
+            digest_value: "abc=".to_string(),
+            csc_id: "1".to_string(),
+            csc: "123456".to_string(),
+        })
+        .unwrap();
+
+        let pdf = DanfePrinter::generate_pdf(&data).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/CS /G /BPC 1"));
+        assert!(text.contains("DANFE NFC-e"));
+        // Largura de 80mm em pontos
+        assert!(text.contains("/MediaBox [0 0 226.77"));
+    }
+
+    #[test]
+    fn test_from_xml() {
+        let xml = r#"<nfeProc xmlns="http://www.portalfiscal.inf.br/nfe" versao="4.00"><NFe><infNFe Id="NFe35260111222333000181650010000000159000000155" versao="4.00">
+<ide><cUF>35</cUF><mod>65</mod><serie>1</serie><nNF>15</nNF><dhEmi>2026-01-20T10:00:00-03:00</dhEmi><tpEmis>9</tpEmis><tpAmb>2</tpAmb></ide>
+<emit><CNPJ>11222333000181</CNPJ><xNome>MOTO PECAS TESTE LTDA</xNome><enderEmit><xLgr>RUA DAS OFICINAS</xLgr><nro>10</nro><xMun>SAO PAULO</xMun><UF>SP</UF></enderEmit><IE>123456789012</IE></emit>
+<det nItem="1"><prod><cProd>001</cProd><xProd>CORRENTE 428H</xProd><uCom>UN</uCom><qCom>2.0000</qCom><vUnCom>15.00</vUnCom><vProd>30.00</vProd></prod></det>
+<total><ICMSTot><vProd>30.00</vProd><vDesc>5.00</vDesc><vNF>25.00</vNF></ICMSTot></total>
+<pag><detPag><tPag>01</tPag><vPag>30.00</vPag></detPag><vTroco>5.00</vTroco></pag>
+<infAdic><infCpl>Tributos aproximados R$ 3,00</infCpl></infAdic>
+</infNFe><Signature><SignedInfo><Reference><DigestValue>abc=</DigestValue></Reference></SignedInfo></Signature></NFe>
+<protNFe versao="4.00"><infProt><nProt>135260000000001</nProt><cStat>100</cStat></infProt></protNFe></nfeProc>"#;
+
+        let data = DanfeData::from_xml(xml, "1", "123456").unwrap();
+        assert_eq!(
+            data.access_key,
+            "35260111222333000181650010000000159000000155"
+        );
+        assert_eq!(data.number, 15);
+        assert_eq!(data.emitter_address, "RUA DAS OFICINAS, 10");
+        assert_eq!(data.items.len(), 1);
+        assert_eq!(data.items[0].quantity, 2.0);
+        assert_eq!(data.total, 25.0);
+        assert_eq!(data.discount, 5.0);
+        assert_eq!(data.change, 5.0);
+        assert_eq!(data.protocol.as_deref(), Some("135260000000001"));
+        // Autorizada: sem aviso de contingência pendente
+        assert_eq!(
+            data.additional_info.as_deref(),
+            Some("Tributos aproximados R$ 3,00")
+        );
+        assert!(!data.qrcode_png.is_empty());
+
+        let nfe = xml.replace("<mod>65</mod>", "<mod>55</mod>");
+        assert!(DanfeData::from_xml(&nfe, "1", "123456").is_err());
+    }
+
     #[test]
     fn test_format_cnpj() {
         let cnpj = "12345678000190";
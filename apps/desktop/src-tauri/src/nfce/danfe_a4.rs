@@ -0,0 +1,914 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// DANFE NF-e - Retrato A4 (PDF)
+// ═══════════════════════════════════════════════════════════════════════════
+//! Documento Auxiliar da NF-e (modelo 55) em folha A4, gerado como PDF a
+//! partir dos mesmos dados usados na montagem do XML.
+//!
+//! Blocos: canhoto, emitente/DANFE/chave (com código de barras CODE-128C),
+//! destinatário, cálculo do imposto, transportador, produtos e dados
+//! adicionais. Itens excedentes continuam em folhas seguintes.
+
+use chrono::Local;
+
+use crate::nfce::danfe::DanfePrinter;
+use crate::nfce::pdf::{fit_text, wrap_text, Font, PdfDocument, PdfPage, A4_WIDTH_MM};
+use crate::nfce::tax::ItemTaxes;
+use crate::nfce::xml_builder::{NfceData, NfceXmlBuilder};
+
+const MARGIN: f64 = 7.0;
+const WIDTH: f64 = A4_WIDTH_MM - 2.0 * MARGIN;
+const ROW: f64 = 8.0;
+const ITEM_ROW: f64 = 4.0;
+
+/// Início da tabela de itens e limite inferior na primeira folha / nas seguintes
+const FIRST_PAGE_ITEMS_TOP: f64 = 152.5;
+const FIRST_PAGE_ITEMS_BOTTOM: f64 = 258.0;
+const NEXT_PAGE_ITEMS_TOP: f64 = 52.5;
+const NEXT_PAGE_ITEMS_BOTTOM: f64 = 288.0;
+
+/// Colunas da tabela de produtos: (título, largura, alinhamento à direita)
+const ITEM_COLUMNS: [(&str, f64, bool); 12] = [
+    ("CÓDIGO", 16.0, false),
+    ("DESCRIÇÃO DO PRODUTO / SERVIÇO", 58.0, false),
+    ("NCM/SH", 14.0, false),
+    ("CST", 9.0, false),
+    ("CFOP", 9.0, false),
+    ("UN", 8.0, false),
+    ("QUANT.", 15.0, true),
+    ("V. UNIT.", 16.0, true),
+    ("V. TOTAL", 16.0, true),
+    ("BC ICMS", 14.0, true),
+    ("V. ICMS", 12.0, true),
+    ("ALÍQ.", 9.0, true),
+];
+
+#[derive(Clone, Copy)]
+enum Align {
+    Left,
+    Right,
+}
+
+pub struct DanfeA4 {
+    data: NfceData,
+    access_key: String,
+    protocol: Option<String>,
+}
+
+impl DanfeA4 {
+    pub fn new(data: NfceData, access_key: String, protocol: Option<String>) -> Self {
+        Self {
+            data,
+            access_key,
+            protocol,
+        }
+    }
+
+    /// Quantidade de folhas necessárias para os itens
+    pub fn page_count(&self) -> usize {
+        let first = Self::rows_between(FIRST_PAGE_ITEMS_TOP, FIRST_PAGE_ITEMS_BOTTOM);
+        let next = Self::rows_between(NEXT_PAGE_ITEMS_TOP, NEXT_PAGE_ITEMS_BOTTOM);
+        let remaining = self.data.items.len().saturating_sub(first);
+        1 + remaining.div_ceil(next)
+    }
+
+    fn rows_between(top: f64, bottom: f64) -> usize {
+        ((bottom - top) / ITEM_ROW).floor() as usize
+    }
+
+    /// Gera o PDF do DANFE
+    pub fn generate_pdf(&self) -> Result<Vec<u8>, String> {
+        if self.data.model != 55 {
+            return Err("DANFE A4 disponível apenas para NF-e (modelo 55)".to_string());
+        }
+
+        let builder = NfceXmlBuilder::new(self.data.clone(), self.access_key.clone());
+        let items_taxes = builder.items_taxes();
+        let totals = builder.total_taxes();
+        let pages = self.page_count();
+
+        let mut doc = PdfDocument::a4();
+        let mut next_item = 0;
+
+        for page_number in 1..=pages {
+            let page = doc.add_page();
+            let items_top = if page_number == 1 {
+                self.draw_receipt_stub(page);
+                self.draw_header(page, 27.0, page_number, pages)?;
+                self.draw_operation(page, 61.0);
+                self.draw_recipient(page, 79.0);
+                self.draw_tax_totals(page, 106.0, &builder, &totals);
+                self.draw_transport(page, 125.0);
+                self.draw_additional_info(page, &builder);
+                FIRST_PAGE_ITEMS_TOP
+            } else {
+                self.draw_header(page, MARGIN, page_number, pages)?;
+                NEXT_PAGE_ITEMS_TOP
+            };
+
+            let bottom = if page_number == 1 {
+                FIRST_PAGE_ITEMS_BOTTOM
+            } else {
+                NEXT_PAGE_ITEMS_BOTTOM
+            };
+            next_item = self.draw_items(page, items_top, bottom, next_item, &items_taxes);
+
+            if self.data.environment == 2 {
+                page.text_center(
+                    A4_WIDTH_MM / 2.0,
+                    215.0,
+                    22.0,
+                    Font::Bold,
+                    "SEM VALOR FISCAL - HOMOLOGAÇÃO",
+                );
+            }
+        }
+
+        Ok(doc.to_bytes())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // BLOCOS
+    // ═══════════════════════════════════════════════════════════════════════
+
+    fn draw_receipt_stub(&self, page: &mut PdfPage) {
+        let y = MARGIN;
+        let stub_width = WIDTH - 40.0;
+        page.rect(MARGIN, y, stub_width, 8.0, 0.5);
+        page.text(
+            MARGIN + 1.0,
+            y + 3.0,
+            5.5,
+            Font::Regular,
+            &fit_text(
+                &format!(
+                    "RECEBEMOS DE {} OS PRODUTOS/SERVIÇOS CONSTANTES DA NOTA FISCAL INDICADA AO LADO",
+                    self.data.emitter_name
+                ),
+                stub_width - 2.0,
+                5.5,
+                Font::Regular,
+            ),
+        );
+        self.field(
+            page,
+            MARGIN,
+            y + 8.0,
+            40.0,
+            8.0,
+            "DATA DE RECEBIMENTO",
+            "",
+            Align::Left,
+        );
+        self.field(
+            page,
+            MARGIN + 40.0,
+            y + 8.0,
+            stub_width - 40.0,
+            8.0,
+            "IDENTIFICAÇÃO E ASSINATURA DO RECEBEDOR",
+            "",
+            Align::Left,
+        );
+
+        let box_x = MARGIN + stub_width;
+        page.rect(box_x, y, 40.0, 16.0, 0.5);
+        page.text_center(box_x + 20.0, y + 5.0, 10.0, Font::Bold, "NF-e");
+        page.text_center(
+            box_x + 20.0,
+            y + 10.0,
+            8.0,
+            Font::Bold,
+            &format!("Nº {}", format_note_number(self.data.numero)),
+        );
+        page.text_center(
+            box_x + 20.0,
+            y + 14.0,
+            8.0,
+            Font::Bold,
+            &format!("SÉRIE {:03}", self.data.serie),
+        );
+
+        // Linha de corte do canhoto
+        page.line(MARGIN, y + 18.5, MARGIN + WIDTH, y + 18.5, 0.3);
+    }
+
+    fn draw_header(
+        &self,
+        page: &mut PdfPage,
+        y: f64,
+        page_number: usize,
+        pages: usize,
+    ) -> Result<(), String> {
+        let height = 34.0;
+
+        // Emitente
+        let emitter_width = 80.0;
+        page.rect(MARGIN, y, emitter_width, height, 0.5);
+        let mut line_y = y + 6.0;
+        for line in wrap_text(
+            &self.data.emitter_name,
+            emitter_width - 4.0,
+            10.0,
+            Font::Bold,
+        )
+        .iter()
+        .take(2)
+        {
+            page.text_center(MARGIN + emitter_width / 2.0, line_y, 10.0, Font::Bold, line);
+            line_y += 4.5;
+        }
+        let district = self.data.emitter_district.as_deref().unwrap_or_default();
+        let address_lines = [
+            self.data.emitter_address.clone(),
+            if district.is_empty() {
+                format!("CEP: {}", format_cep(&self.data.emitter_cep))
+            } else {
+                format!("{} - CEP: {}", district, format_cep(&self.data.emitter_cep))
+            },
+            format!("{} - {}", self.data.emitter_city, self.data.uf),
+        ];
+        for line in &address_lines {
+            page.text_center(
+                MARGIN + emitter_width / 2.0,
+                line_y + 1.0,
+                7.0,
+                Font::Regular,
+                &fit_text(line, emitter_width - 4.0, 7.0, Font::Regular),
+            );
+            line_y += 3.8;
+        }
+
+        // Identificação do DANFE
+        let danfe_x = MARGIN + emitter_width;
+        let danfe_width = 34.0;
+        let center = danfe_x + danfe_width / 2.0;
+        page.rect(danfe_x, y, danfe_width, height, 0.5);
+        page.text_center(center, y + 6.0, 12.0, Font::Bold, "DANFE");
+        page.text_center(center, y + 9.5, 5.5, Font::Regular, "DOCUMENTO AUXILIAR DA");
+        page.text_center(
+            center,
+            y + 12.0,
+            5.5,
+            Font::Regular,
+            "NOTA FISCAL ELETRÔNICA",
+        );
+        page.text(danfe_x + 3.0, y + 16.5, 6.0, Font::Regular, "0 - ENTRADA");
+        page.text(danfe_x + 3.0, y + 19.5, 6.0, Font::Regular, "1 - SAÍDA");
+        page.rect(danfe_x + 24.0, y + 14.5, 6.0, 6.0, 0.5);
+        page.text_center(danfe_x + 27.0, y + 19.0, 10.0, Font::Bold, "1");
+        page.text_center(
+            center,
+            y + 25.0,
+            8.0,
+            Font::Bold,
+            &format!("Nº {}", format_note_number(self.data.numero)),
+        );
+        page.text_center(
+            center,
+            y + 28.5,
+            8.0,
+            Font::Bold,
+            &format!("SÉRIE {:03}", self.data.serie),
+        );
+        page.text_center(
+            center,
+            y + 32.0,
+            7.0,
+            Font::Regular,
+            &format!("FOLHA {}/{}", page_number, pages),
+        );
+
+        // Chave de acesso
+        let key_x = danfe_x + danfe_width;
+        let key_width = WIDTH - emitter_width - danfe_width;
+        page.rect(key_x, y, key_width, height, 0.5);
+        page.barcode_code128c(
+            key_x + 4.0,
+            y + 2.0,
+            key_width - 8.0,
+            11.0,
+            &self.access_key,
+        )?;
+        page.text(key_x + 1.0, y + 16.0, 5.0, Font::Regular, "CHAVE DE ACESSO");
+        page.text_center(
+            key_x + key_width / 2.0,
+            y + 19.5,
+            7.5,
+            Font::Bold,
+            &DanfePrinter::format_access_key(&self.access_key),
+        );
+        page.text_center(
+            key_x + key_width / 2.0,
+            y + 25.0,
+            6.0,
+            Font::Regular,
+            "Consulta de autenticidade no portal nacional da NF-e",
+        );
+        page.text_center(
+            key_x + key_width / 2.0,
+            y + 28.0,
+            6.0,
+            Font::Regular,
+            "www.nfe.fazenda.gov.br/portal ou no site da Sefaz Autorizadora",
+        );
+        Ok(())
+    }
+
+    fn draw_operation(&self, page: &mut PdfPage, y: f64) {
+        self.field(
+            page,
+            MARGIN,
+            y,
+            WIDTH - 82.0,
+            ROW,
+            "NATUREZA DA OPERAÇÃO",
+            &self.data.nature_of_operation,
+            Align::Left,
+        );
+        let protocol = match &self.protocol {
+            Some(protocol) => protocol.clone(),
+            None if self.data.emission_type == 9 => "EMITIDA EM CONTINGÊNCIA".to_string(),
+            None => String::new(),
+        };
+        self.field(
+            page,
+            MARGIN + WIDTH - 82.0,
+            y,
+            82.0,
+            ROW,
+            "PROTOCOLO DE AUTORIZAÇÃO DE USO",
+            &protocol,
+            Align::Left,
+        );
+
+        let row_y = y + ROW;
+        self.field(
+            page,
+            MARGIN,
+            row_y,
+            65.0,
+            ROW,
+            "INSCRIÇÃO ESTADUAL",
+            &self.data.emitter_ie,
+            Align::Left,
+        );
+        self.field(
+            page,
+            MARGIN + 65.0,
+            row_y,
+            65.0,
+            ROW,
+            "INSCRIÇÃO ESTADUAL DO SUBST. TRIB.",
+            "",
+            Align::Left,
+        );
+        self.field(
+            page,
+            MARGIN + 130.0,
+            row_y,
+            WIDTH - 130.0,
+            ROW,
+            "CNPJ",
+            &DanfePrinter::format_cnpj(&self.data.cnpj),
+            Align::Left,
+        );
+    }
+
+    fn draw_recipient(&self, page: &mut PdfPage, y: f64) {
+        self.section(page, y, "DESTINATÁRIO / REMETENTE");
+        let Some(recipient) = &self.data.recipient else {
+            return;
+        };
+        let address = &recipient.address;
+        let top = y + 1.5;
+        let emission = self.data.emission_date.with_timezone(&Local);
+
+        let street = match address.complement.as_deref().filter(|c| !c.is_empty()) {
+            Some(complement) => format!("{}, {} - {}", address.street, address.number, complement),
+            None => format!("{}, {}", address.street, address.number),
+        };
+        let ie = match recipient.ie_indicator() {
+            1 => recipient.state_registration.clone().unwrap_or_default(),
+            2 => "ISENTO".to_string(),
+            _ => String::new(),
+        };
+
+        let rows: [&[(&str, f64, String)]; 3] = [
+            &[
+                ("NOME / RAZÃO SOCIAL", 120.0, recipient.name.clone()),
+                ("CNPJ / CPF", 46.0, format_document(&recipient.document)),
+                (
+                    "DATA DA EMISSÃO",
+                    30.0,
+                    emission.format("%d/%m/%Y").to_string(),
+                ),
+            ],
+            &[
+                ("ENDEREÇO", 96.0, street),
+                ("BAIRRO / DISTRITO", 45.0, address.district.clone()),
+                ("CEP", 25.0, format_cep(&address.cep)),
+                ("DATA DA SAÍDA/ENTRADA", 30.0, String::new()),
+            ],
+            &[
+                ("MUNICÍPIO", 76.0, address.city.clone()),
+                (
+                    "FONE / FAX",
+                    35.0,
+                    address.phone.clone().unwrap_or_default(),
+                ),
+                ("UF", 10.0, address.uf.clone()),
+                ("INSCRIÇÃO ESTADUAL", 45.0, ie),
+                ("HORA DA SAÍDA", 30.0, String::new()),
+            ],
+        ];
+
+        for (idx, row) in rows.iter().enumerate() {
+            let row_y = top + idx as f64 * ROW;
+            let mut x = MARGIN;
+            for (label, width, value) in row.iter() {
+                self.field(page, x, row_y, *width, ROW, label, value, Align::Left);
+                x += width;
+            }
+        }
+    }
+
+    fn draw_tax_totals(
+        &self,
+        page: &mut PdfPage,
+        y: f64,
+        builder: &NfceXmlBuilder,
+        totals: &ItemTaxes,
+    ) {
+        self.section(page, y, "CÁLCULO DO IMPOSTO");
+        let approx = totals.federal_tax + totals.state_tax + totals.municipal_tax;
+        let rows = [
+            [
+                ("BASE DE CÁLC. DO ICMS", totals.icms_base),
+                ("VALOR DO ICMS", totals.icms_value),
+                ("BASE DE CÁLC. ICMS S.T.", totals.st_base),
+                ("VALOR DO ICMS SUBST.", totals.st_value),
+                ("V. APROX. TRIBUTOS", approx),
+                ("V. TOTAL PRODUTOS", self.data.total_products),
+            ],
+            [
+                ("VALOR DO FRETE", builder.freight_value()),
+                ("VALOR DO SEGURO", 0.0),
+                ("DESCONTO", self.data.total_discount),
+                ("OUTRAS DESPESAS", 0.0),
+                ("VALOR TOTAL DO IPI", 0.0),
+                ("V. TOTAL DA NOTA", self.data.total_note),
+            ],
+        ];
+
+        let width = WIDTH / 6.0;
+        for (idx, row) in rows.iter().enumerate() {
+            let row_y = y + 1.5 + idx as f64 * ROW;
+            for (col, (label, value)) in row.iter().enumerate() {
+                self.field(
+                    page,
+                    MARGIN + col as f64 * width,
+                    row_y,
+                    width,
+                    ROW,
+                    label,
+                    &format_decimal(*value, 2),
+                    Align::Right,
+                );
+            }
+        }
+    }
+
+    fn draw_transport(&self, page: &mut PdfPage, y: f64) {
+        self.section(page, y, "TRANSPORTADOR / VOLUMES TRANSPORTADOS");
+        let freight = self.data.freight.as_ref();
+        let carrier = freight.and_then(|f| f.carrier.as_ref());
+        let volumes = freight.and_then(|f| f.volumes.as_ref());
+        let modality = freight.map(|f| f.modality).unwrap_or(9);
+
+        let weight = |value: Option<f64>| value.map(|v| format_decimal(v, 3)).unwrap_or_default();
+        let rows: [&[(&str, f64, String)]; 2] = [
+            &[
+                (
+                    "NOME / RAZÃO SOCIAL",
+                    80.0,
+                    carrier.map(|c| c.name.clone()).unwrap_or_default(),
+                ),
+                (
+                    "FRETE POR CONTA",
+                    36.0,
+                    freight_modality_label(modality).to_string(),
+                ),
+                (
+                    "CNPJ / CPF",
+                    40.0,
+                    carrier
+                        .map(|c| format_document(&c.document))
+                        .unwrap_or_default(),
+                ),
+                (
+                    "UF",
+                    10.0,
+                    carrier.and_then(|c| c.uf.clone()).unwrap_or_default(),
+                ),
+                (
+                    "INSCRIÇÃO ESTADUAL",
+                    30.0,
+                    carrier
+                        .and_then(|c| c.state_registration.clone())
+                        .unwrap_or_default(),
+                ),
+            ],
+            &[
+                (
+                    "QUANTIDADE",
+                    30.0,
+                    volumes.map(|v| v.quantity.to_string()).unwrap_or_default(),
+                ),
+                (
+                    "ESPÉCIE",
+                    46.0,
+                    volumes.and_then(|v| v.species.clone()).unwrap_or_default(),
+                ),
+                (
+                    "MUNICÍPIO",
+                    60.0,
+                    carrier.and_then(|c| c.city.clone()).unwrap_or_default(),
+                ),
+                (
+                    "PESO BRUTO",
+                    30.0,
+                    weight(volumes.and_then(|v| v.gross_weight)),
+                ),
+                (
+                    "PESO LÍQUIDO",
+                    30.0,
+                    weight(volumes.and_then(|v| v.net_weight)),
+                ),
+            ],
+        ];
+
+        for (idx, row) in rows.iter().enumerate() {
+            let row_y = y + 1.5 + idx as f64 * ROW;
+            let mut x = MARGIN;
+            for (label, width, value) in row.iter() {
+                self.field(page, x, row_y, *width, ROW, label, value, Align::Left);
+                x += width;
+            }
+        }
+    }
+
+    /// Desenha os itens a partir de `first` e retorna o índice do próximo item
+    fn draw_items(
+        &self,
+        page: &mut PdfPage,
+        top: f64,
+        bottom: f64,
+        first: usize,
+        items_taxes: &[ItemTaxes],
+    ) -> usize {
+        self.section(page, top - 8.5, "DADOS DOS PRODUTOS / SERVIÇOS");
+
+        // Cabeçalho da tabela
+        let header_y = top - 7.0;
+        let mut x = MARGIN;
+        for (title, width, _) in ITEM_COLUMNS.iter() {
+            page.rect(x, header_y, *width, 7.0, 0.5);
+            page.text_center(
+                x + width / 2.0,
+                header_y + 4.5,
+                5.0,
+                Font::Bold,
+                &fit_text(title, width - 1.0, 5.0, Font::Bold),
+            );
+            x += width;
+        }
+        page.rect(MARGIN, top, WIDTH, bottom - top, 0.5);
+
+        let capacity = Self::rows_between(top, bottom);
+        let last = (first + capacity).min(self.data.items.len());
+        for (row, idx) in (first..last).enumerate() {
+            let item = &self.data.items[idx];
+            let taxes = &items_taxes[idx];
+            let row_y = top + (row as f64 + 1.0) * ITEM_ROW - 1.2;
+            let values = [
+                item.code.clone(),
+                item.description.clone(),
+                item.ncm.clone(),
+                format!("{}{}", item.tax.icms_origin, item.tax.icms_code),
+                item.cfop.clone(),
+                item.unit.clone(),
+                format_decimal(item.quantity, 4),
+                format_decimal(item.unit_value, 2),
+                format_decimal(item.total_value, 2),
+                format_decimal(taxes.icms_base, 2),
+                format_decimal(taxes.icms_value, 2),
+                format_decimal(item.tax.icms_rate, 2),
+            ];
+
+            let mut x = MARGIN;
+            for ((_, width, right), value) in ITEM_COLUMNS.iter().zip(values.iter()) {
+                let text = fit_text(value, width - 1.5, 6.5, Font::Regular);
+                if *right {
+                    page.text_right(x + width - 0.8, row_y, 6.5, Font::Regular, &text);
+                } else {
+                    page.text(x + 0.8, row_y, 6.5, Font::Regular, &text);
+                }
+                x += width;
+            }
+        }
+
+        // Divisórias verticais das colunas
+        let mut x = MARGIN;
+        for (_, width, _) in ITEM_COLUMNS.iter().take(ITEM_COLUMNS.len() - 1) {
+            x += width;
+            page.line(x, top, x, bottom, 0.3);
+        }
+
+        last
+    }
+
+    fn draw_additional_info(&self, page: &mut PdfPage, builder: &NfceXmlBuilder) {
+        let y = FIRST_PAGE_ITEMS_BOTTOM + 2.0;
+        self.section(page, y, "DADOS ADICIONAIS");
+        let box_y = y + 1.5;
+        let height = 297.0 - MARGIN - box_y;
+        let info_width = 130.0;
+
+        page.rect(MARGIN, box_y, info_width, height, 0.5);
+        page.text(
+            MARGIN + 1.0,
+            box_y + 2.3,
+            5.0,
+            Font::Regular,
+            "INFORMAÇÕES COMPLEMENTARES",
+        );
+        let mut info = builder.complementary_info();
+        if self.data.environment == 2 {
+            info = format!(
+                "DOCUMENTO EMITIDO EM AMBIENTE DE HOMOLOGAÇÃO - SEM VALOR FISCAL. {}",
+                info
+            );
+        }
+        let max_lines = ((height - 4.0) / 3.0) as usize;
+        for (idx, line) in wrap_text(&info, info_width - 2.0, 6.0, Font::Regular)
+            .iter()
+            .take(max_lines)
+            .enumerate()
+        {
+            page.text(
+                MARGIN + 1.0,
+                box_y + 5.5 + idx as f64 * 3.0,
+                6.0,
+                Font::Regular,
+                line,
+            );
+        }
+
+        page.rect(MARGIN + info_width, box_y, WIDTH - info_width, height, 0.5);
+        page.text(
+            MARGIN + info_width + 1.0,
+            box_y + 2.3,
+            5.0,
+            Font::Regular,
+            "RESERVADO AO FISCO",
+        );
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // PRIMITIVAS
+    // ═══════════════════════════════════════════════════════════════════════
+
+    fn section(&self, page: &mut PdfPage, y: f64, title: &str) {
+        page.text(MARGIN, y, 6.5, Font::Bold, title);
+    }
+
+    /// Caixa com rótulo pequeno no topo e valor na base
+    #[allow(clippy::too_many_arguments)]
+    fn field(
+        &self,
+        page: &mut PdfPage,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        label: &str,
+        value: &str,
+        align: Align,
+    ) {
+        page.rect(x, y, width, height, 0.5);
+        page.text(
+            x + 1.0,
+            y + 2.3,
+            5.0,
+            Font::Regular,
+            &fit_text(label, width - 2.0, 5.0, Font::Regular),
+        );
+        let text = fit_text(value, width - 2.0, 8.0, Font::Regular);
+        match align {
+            Align::Left => page.text(x + 1.0, y + height - 1.6, 8.0, Font::Regular, &text),
+            Align::Right => {
+                page.text_right(x + width - 1.0, y + height - 1.6, 8.0, Font::Regular, &text)
+            }
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// FORMATADORES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Número com separador de milhar "." e decimal "," (padrão brasileiro)
+pub fn format_decimal(value: f64, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+
+    let mut grouped = String::new();
+    for (idx, c) in integer.chars().enumerate() {
+        if idx > 0 && (integer.len() - idx) % 3 == 0 {
+            grouped.push('.');
+        }
+        grouped.push(c);
+    }
+
+    let sign = if value < 0.0 && formatted.chars().any(|c| c != '0' && c != '.') {
+        "-"
+    } else {
+        ""
+    };
+    if fraction.is_empty() {
+        format!("{}{}", sign, grouped)
+    } else {
+        format!("{}{},{}", sign, grouped, fraction)
+    }
+}
+
+/// Número da nota no formato 000.000.001
+fn format_note_number(number: u32) -> String {
+    let digits = format!("{:09}", number);
+    format!("{}.{}.{}", &digits[0..3], &digits[3..6], &digits[6..9])
+}
+
+fn format_document(document: &str) -> String {
+    match document.len() {
+        14 => DanfePrinter::format_cnpj(document),
+        11 => format!(
+            "{}.{}.{}-{}",
+            &document[0..3],
+            &document[3..6],
+            &document[6..9],
+            &document[9..11]
+        ),
+        _ => document.to_string(),
+    }
+}
+
+fn format_cep(cep: &str) -> String {
+    if cep.len() == 8 {
+        format!("{}-{}", &cep[0..5], &cep[5..8])
+    } else {
+        cep.to_string()
+    }
+}
+
+fn freight_modality_label(modality: u8) -> &'static str {
+    match modality {
+        0 => "0 - REMETENTE (CIF)",
+        1 => "1 - DESTINATÁRIO (FOB)",
+        2 => "2 - TERCEIROS",
+        3 => "3 - PRÓPRIO REMETENTE",
+        4 => "4 - PRÓPRIO DESTINATÁRIO",
+        _ => "9 - SEM FRETE",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nfce::tax::TaxProfile;
+    use crate::nfce::xml_builder::{NfceItem, NfcePayment, NfeAddress, NfeRecipient};
+    use chrono::Utc;
+
+    fn create_nfe_data(items: usize) -> NfceData {
+        NfceData {
+            model: 55,
+            nature_of_operation: "VENDA DE MERCADORIA".to_string(),
+            uf: "SP".to_string(),
+            cnpj: "12345678000190".to_string(),
+            serie: 1,
+            numero: 42,
+            emission_date: Utc::now(),
+            emission_type: 1,
+            environment: 2,
+            crt: 1,
+            emitter_name: "GIRO MOTO PECAS LTDA".to_string(),
+            emitter_trade_name: None,
+            emitter_ie: "123456789".to_string(),
+            emitter_address: "RUA DAS OFICINAS, 10".to_string(),
+            emitter_district: Some("CENTRO".to_string()),
+            emitter_city: "SAO PAULO".to_string(),
+            emitter_city_code: "3550308".to_string(),
+            emitter_state: "SP".to_string(),
+            emitter_cep: "01234567".to_string(),
+            recipient_cpf: None,
+            recipient_name: None,
+            recipient: Some(NfeRecipient {
+                document: "11222333000181".to_string(),
+                name: "OFICINA CLIENTE LTDA".to_string(),
+                state_registration: Some("987654321".to_string()),
+                email: None,
+                address: NfeAddress {
+                    street: "AV PAULISTA".to_string(),
+                    number: "1000".to_string(),
+                    complement: None,
+                    district: "BELA VISTA".to_string(),
+                    city_code: "3550308".to_string(),
+                    city: "SAO PAULO".to_string(),
+                    uf: "SP".to_string(),
+                    cep: "01310100".to_string(),
+                    phone: None,
+                },
+            }),
+            freight: None,
+            additional_info: Some("Referente à OS nº 15".to_string()),
+            operation_type: 1,
+            purpose: 1,
+            referenced_keys: Vec::new(),
+            items: (0..items)
+                .map(|idx| NfceItem {
+                    number: (idx + 1) as u16,
+                    code: format!("P{:03}", idx + 1),
+                    ean: None,
+                    description: format!("PECA DE REPOSICAO {}", idx + 1),
+                    ncm: "87141000".to_string(),
+                    cfop: "5102".to_string(),
+                    unit: "UN".to_string(),
+                    quantity: 1.0,
+                    unit_value: 10.0,
+                    total_value: 10.0,
+                    tax: TaxProfile::simples(0, "102", "07", "07"),
+                    ibpt: None,
+                })
+                .collect(),
+            total_products: items as f64 * 10.0,
+            total_discount: 0.0,
+            total_note: items as f64 * 10.0,
+            payments: vec![NfcePayment {
+                method: "01".to_string(),
+                value: items as f64 * 10.0,
+                card: None,
+            }],
+            csc_id: String::new(),
+            csc: String::new(),
+        }
+    }
+
+    const KEY: &str = "35260112345678000190550010000000421123456789";
+
+    #[test]
+    fn test_generate_pdf() {
+        let danfe = DanfeA4::new(
+            create_nfe_data(3),
+            KEY.to_string(),
+            Some("135260000000001".into()),
+        );
+        let pdf = danfe.generate_pdf().unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/Count 1"));
+        assert!(text.contains("(DANFE)"));
+        assert!(text.contains("OFICINA CLIENTE LTDA"));
+        assert!(text.contains("000.000.042"));
+        assert!(text.contains("135260000000001"));
+    }
+
+    #[test]
+    fn test_items_continue_on_next_pages() {
+        let danfe = DanfeA4::new(create_nfe_data(100), KEY.to_string(), None);
+        assert_eq!(danfe.page_count(), 3);
+
+        let pdf = danfe.generate_pdf().unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Count 3"));
+        assert!(text.contains("FOLHA 3/3"));
+        assert!(text.contains("PECA DE REPOSICAO 100"));
+    }
+
+    #[test]
+    fn test_rejects_nfce() {
+        let mut data = create_nfe_data(1);
+        data.model = 65;
+        assert!(DanfeA4::new(data, KEY.to_string(), None)
+            .generate_pdf()
+            .is_err());
+    }
+
+    #[test]
+    fn test_format_decimal() {
+        assert_eq!(format_decimal(1234567.891, 2), "1.234.567,89");
+        assert_eq!(format_decimal(0.5, 2), "0,50");
+        assert_eq!(format_decimal(-12.3, 2), "-12,30");
+        assert_eq!(format_decimal(2.0, 4), "2,0000");
+        assert_eq!(format_note_number(42), "000.000.042");
+    }
+}
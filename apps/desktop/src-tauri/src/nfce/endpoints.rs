@@ -10,6 +10,10 @@
 //! ## SVAN/SVRS
 //! - **SVAN**: Sefaz Virtual Ambiente Nacional (RJ/RS)
 //! - **SVRS**: Sefaz Virtual Rio Grande do Sul (demais estados)
+//!
+//! ## Modelos
+//! - **65 (NFC-e)**: `get_url` e derivados
+//! - **55 (NF-e)**: `get_nfe_url`; os demais serviços via `get_service_url`
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Environment {
@@ -67,6 +71,65 @@ impl SefazEndpoints {
         Ok(base_url.to_string())
     }
 
+    /// Retorna URL do WebService de autorização da NF-e (modelo 55) para a UF
+    pub fn get_nfe_url(uf: &str, env: Environment) -> Result<String, String> {
+        let base_url = match (uf, env) {
+            // São Paulo (nomes de serviço em minúsculas)
+            ("SP", Environment::Production) => {
+                "https://nfe.fazenda.sp.gov.br/ws/nfeautorizacao4.asmx"
+            }
+            ("SP", Environment::Homologation) => {
+                "https://homologacao.nfe.fazenda.sp.gov.br/ws/nfeautorizacao4.asmx"
+            }
+
+            // Minas Gerais
+            ("MG", Environment::Production) => {
+                "https://nfe.fazenda.mg.gov.br/nfe2/services/NFeAutorizacao4"
+            }
+            ("MG", Environment::Homologation) => {
+                "https://hnfe.fazenda.mg.gov.br/nfe2/services/NFeAutorizacao4"
+            }
+
+            // Rio Grande do Sul
+            ("RS", Environment::Production) => {
+                "https://nfe.sefazrs.rs.gov.br/ws/NfeAutorizacao/NFeAutorizacao4.asmx"
+            }
+            ("RS", Environment::Homologation) => {
+                "https://nfe-homologacao.sefazrs.rs.gov.br/ws/NfeAutorizacao/NFeAutorizacao4.asmx"
+            }
+
+            // Demais estados (inclusive RJ) usam SVRS
+            (_, Environment::Production) => {
+                "https://nfe.svrs.rs.gov.br/ws/NfeAutorizacao/NFeAutorizacao4.asmx"
+            }
+            (_, Environment::Homologation) => {
+                "https://nfe-homologacao.svrs.rs.gov.br/ws/NfeAutorizacao/NFeAutorizacao4.asmx"
+            }
+        };
+
+        Ok(base_url.to_string())
+    }
+
+    /// URL de um serviço (ex: "NFeStatusServico4") para o modelo informado (55 ou 65)
+    pub fn get_service_url(
+        model: u8,
+        uf: &str,
+        env: Environment,
+        service: &str,
+    ) -> Result<String, String> {
+        let base_url = match model {
+            55 => Self::get_nfe_url(uf, env)?,
+            65 => Self::get_url(uf, env)?,
+            other => return Err(format!("Modelo de documento não suportado: {}", other)),
+        };
+
+        if base_url.contains("nfeautorizacao4") {
+            Ok(base_url.replace("nfeautorizacao4", &service.to_lowercase()))
+        } else {
+            Ok(base_url.replace("NFeAutorizacao4", service))
+        }
+    }
+
     /// URL para consulta de status do serviço
     pub fn get_status_url(uf: &str, env: Environment) -> Result<String, String> {
         let base_url = Self::get_url(uf, env)?;
@@ -78,6 +141,18 @@ impl SefazEndpoints {
         let base_url = Self::get_url(uf, env)?;
         Ok(base_url.replace("NFeAutorizacao4", "NFeConsultaProtocolo4"))
     }
+
+    /// URL para recepção de eventos (cancelamento)
+    pub fn get_event_url(uf: &str, env: Environment) -> Result<String, String> {
+        let base_url = Self::get_url(uf, env)?;
+        Ok(base_url.replace("NFeAutorizacao4", "NFeRecepcaoEvento4"))
+    }
+
+    /// URL para inutilização de numeração
+    pub fn get_inutilization_url(uf: &str, env: Environment) -> Result<String, String> {
+        let base_url = Self::get_url(uf, env)?;
+        Ok(base_url.replace("NFeAutorizacao4", "NFeInutilizacao4"))
+    }
 }
 
 #[cfg(test)]
@@ -108,4 +183,52 @@ mod tests {
         let url = SefazEndpoints::get_status_url("SP", Environment::Production).unwrap();
         assert!(url.contains("NFeStatusServico4"));
     }
+
+    #[test]
+    fn test_event_and_inutilization_urls() {
+        let event = SefazEndpoints::get_event_url("SP", Environment::Homologation).unwrap();
+        assert!(event.contains("NFeRecepcaoEvento4"));
+
+        let inut = SefazEndpoints::get_inutilization_url("SP", Environment::Homologation).unwrap();
+        assert!(inut.contains("NFeInutilizacao4"));
+    }
+
+    #[test]
+    fn test_nfe_service_urls() {
+        let sp =
+            SefazEndpoints::get_service_url(55, "SP", Environment::Production, "NFeAutorizacao4")
+                .unwrap();
+        assert_eq!(sp, "https://nfe.fazenda.sp.gov.br/ws/nfeautorizacao4.asmx");
+
+        let sp_status =
+            SefazEndpoints::get_service_url(55, "SP", Environment::Production, "NFeStatusServico4")
+                .unwrap();
+        assert!(sp_status.ends_with("/ws/nfestatusservico4.asmx"));
+
+        let rj = SefazEndpoints::get_service_url(
+            55,
+            "RJ",
+            Environment::Homologation,
+            "NFeRecepcaoEvento4",
+        )
+        .unwrap();
+        assert!(rj.starts_with("https://nfe-homologacao.svrs.rs.gov.br"));
+        assert!(rj.contains("NFeRecepcaoEvento4"));
+
+        let nfce =
+            SefazEndpoints::get_service_url(65, "SP", Environment::Production, "NFeStatusServico4")
+                .unwrap();
+        assert_eq!(
+            nfce,
+            SefazEndpoints::get_status_url("SP", Environment::Production).unwrap()
+        );
+
+        assert!(SefazEndpoints::get_service_url(
+            59,
+            "SP",
+            Environment::Production,
+            "NFeAutorizacao4"
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,234 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// EVENTOS NFC-e - CANCELAMENTO E INUTILIZAÇÃO
+// ═══════════════════════════════════════════════════════════════════════════
+//! Montagem dos XMLs de evento de cancelamento (tpEvento 110111, layout 1.00)
+//! e de inutilização de numeração (inutNFe, layout 4.00).
+//!
+//! Os XMLs gerados aqui ainda precisam ser assinados com
+//! `XmlSigner::sign_element` (`infEvento` / `infInut`) antes do envio.
+
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use quick_xml::escape::escape;
+
+use crate::nfce::access_key::UF_CODES;
+
+/// Código do evento de cancelamento
+pub const EVENT_CANCELLATION: &str = "110111";
+
+/// Prazo padrão para cancelamento de NFC-e (minutos após a autorização);
+/// a UF pode definir outro em `fiscal.nfce_cancel_window_minutes`
+pub const DEFAULT_CANCEL_WINDOW_MINUTES: i64 = 30;
+
+/// Evento de cancelamento de NFC-e autorizada
+#[derive(Debug, Clone)]
+pub struct CancellationEvent {
+    pub access_key: String,
+    pub protocol: String,
+    pub justification: String,
+    pub environment: u8, // 1=Produção, 2=Homologação
+    pub sequence: u8,
+    pub event_date: DateTime<FixedOffset>,
+}
+
+/// Pedido de inutilização de uma faixa de numeração
+#[derive(Debug, Clone)]
+pub struct Inutilization {
+    pub uf: String,
+    pub cnpj: String,
+    pub model: u8,
+    pub serie: u16,
+    pub number_start: u32,
+    pub number_end: u32,
+    pub year: u16,
+    pub justification: String,
+    pub environment: u8,
+}
+
+impl CancellationEvent {
+    /// Id do infEvento: "ID" + tpEvento + chave + nSeqEvento (2 dígitos)
+    pub fn id(&self) -> String {
+        format!(
+            "ID{}{}{:02}",
+            EVENT_CANCELLATION, self.access_key, self.sequence
+        )
+    }
+
+    /// Gera o `envEvento` com um único evento de cancelamento (não assinado)
+    pub fn build(&self) -> Result<String, String> {
+        if self.access_key.len() != 44 {
+            return Err("Chave de acesso deve ter 44 dígitos".to_string());
+        }
+        if self.protocol.trim().is_empty() {
+            return Err("Protocolo de autorização é obrigatório para cancelar".to_string());
+        }
+        validate_justification(&self.justification)?;
+
+        // cOrgao = cUF da chave; CNPJ do emitente = posições 6..20 da chave
+        let c_orgao = &self.access_key[0..2];
+        let cnpj = &self.access_key[6..20];
+
+        Ok(format!(
+            r#"<envEvento xmlns="http://www.portalfiscal.inf.br/nfe" versao="1.00"><idLote>{lote}</idLote><evento versao="1.00"><infEvento Id="{id}"><cOrgao>{c_orgao}</cOrgao><tpAmb>{tp_amb}</tpAmb><CNPJ>{cnpj}</CNPJ><chNFe>{key}</chNFe><dhEvento>{dh}</dhEvento><tpEvento>{tp}</tpEvento><nSeqEvento>{seq}</nSeqEvento><verEvento>1.00</verEvento><detEvento versao="1.00"><descEvento>Cancelamento</descEvento><nProt>{prot}</nProt><xJust>{just}</xJust></detEvento></infEvento></evento></envEvento>"#,
+            lote = Utc::now().timestamp(),
+            id = self.id(),
+            c_orgao = c_orgao,
+            tp_amb = self.environment,
+            cnpj = cnpj,
+            key = self.access_key,
+            dh = self.event_date.format("%Y-%m-%dT%H:%M:%S%:z"),
+            tp = EVENT_CANCELLATION,
+            seq = self.sequence,
+            prot = self.protocol.trim(),
+            just = escape(self.justification.trim()),
+        ))
+    }
+}
+
+impl Inutilization {
+    /// Id do infInut: "ID" + cUF + AA + CNPJ + mod + serie(3) + nNFIni(9) + nNFFin(9)
+    pub fn id(&self) -> Result<String, String> {
+        Ok(format!(
+            "ID{:02}{:02}{}{:02}{:03}{:09}{:09}",
+            uf_code(&self.uf)?,
+            self.year % 100,
+            self.cnpj,
+            self.model,
+            self.serie,
+            self.number_start,
+            self.number_end
+        ))
+    }
+
+    /// Gera o `inutNFe` (não assinado)
+    pub fn build(&self) -> Result<String, String> {
+        if self.cnpj.len() != 14 {
+            return Err("CNPJ deve ter 14 dígitos".to_string());
+        }
+        if self.number_start == 0 || self.number_end < self.number_start {
+            return Err("Faixa de numeração inválida".to_string());
+        }
+        validate_justification(&self.justification)?;
+
+        Ok(format!(
+            r#"<inutNFe xmlns="http://www.portalfiscal.inf.br/nfe" versao="4.00"><infInut Id="{id}"><tpAmb>{tp_amb}</tpAmb><xServ>INUTILIZAR</xServ><cUF>{c_uf:02}</cUF><ano>{ano:02}</ano><CNPJ>{cnpj}</CNPJ><mod>{model}</mod><serie>{serie}</serie><nNFIni>{ini}</nNFIni><nNFFin>{fin}</nNFFin><xJust>{just}</xJust></infInut></inutNFe>"#,
+            id = self.id()?,
+            tp_amb = self.environment,
+            c_uf = uf_code(&self.uf)?,
+            ano = self.year % 100,
+            cnpj = self.cnpj,
+            model = self.model,
+            serie = self.serie,
+            ini = self.number_start,
+            fin = self.number_end,
+            just = escape(self.justification.trim()),
+        ))
+    }
+}
+
+/// Justificativa deve ter entre 15 e 255 caracteres
+pub fn validate_justification(justification: &str) -> Result<(), String> {
+    let len = justification.trim().chars().count();
+    if !(15..=255).contains(&len) {
+        return Err("Justificativa deve ter entre 15 e 255 caracteres".to_string());
+    }
+    Ok(())
+}
+
+/// Verifica se a nota ainda pode ser cancelada dentro do prazo legal
+pub fn is_within_cancel_window(
+    authorized_at: NaiveDateTime,
+    now: NaiveDateTime,
+    window_minutes: i64,
+) -> bool {
+    let elapsed = now - authorized_at;
+    elapsed >= chrono::Duration::zero() && elapsed <= chrono::Duration::minutes(window_minutes)
+}
+
+pub(crate) fn uf_code(uf: &str) -> Result<u8, String> {
+    UF_CODES
+        .iter()
+        .find(|(state, _)| *state == uf)
+        .map(|(_, code)| *code)
+        .ok_or_else(|| format!("UF inválida: {}", uf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "35260112345678000190650010000000011123456789";
+
+    fn create_cancellation() -> CancellationEvent {
+        CancellationEvent {
+            access_key: KEY.to_string(),
+            protocol: "135260000000001".to_string(),
+            justification: "Venda cancelada a pedido do cliente".to_string(),
+            environment: 2,
+            sequence: 1,
+            event_date: DateTime::parse_from_rfc3339("2026-01-10T10:00:00-03:00").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_cancellation_event_xml() {
+        let xml = create_cancellation().build().unwrap();
+
+        assert!(xml.contains(&format!("Id=\"ID110111{}01\"", KEY)));
+        assert!(xml.contains("<cOrgao>35</cOrgao>"));
+        assert!(xml.contains("<CNPJ>12345678000190</CNPJ>"));
+        assert!(xml.contains("<nProt>135260000000001</nProt>"));
+        assert!(xml.contains("<dhEvento>2026-01-10T10:00:00-03:00</dhEvento>"));
+    }
+
+    #[test]
+    fn test_cancellation_requires_justification() {
+        let mut event = create_cancellation();
+        event.justification = "curta".to_string();
+
+        assert!(event.build().is_err());
+    }
+
+    #[test]
+    fn test_inutilization_xml() {
+        let inut = Inutilization {
+            uf: "SP".to_string(),
+            cnpj: "12345678000190".to_string(),
+            model: 65,
+            serie: 1,
+            number_start: 10,
+            number_end: 12,
+            year: 2026,
+            justification: "Falha no sistema ao emitir as notas".to_string(),
+            environment: 2,
+        };
+        let xml = inut.build().unwrap();
+
+        assert!(xml.contains("Id=\"ID35261234567800019065001000000010000000012\""));
+        assert!(xml.contains("<xServ>INUTILIZAR</xServ>"));
+        assert!(xml.contains("<nNFIni>10</nNFIni>"));
+        assert!(xml.contains("<nNFFin>12</nNFFin>"));
+    }
+
+    #[test]
+    fn test_cancel_window() {
+        let authorized =
+            NaiveDateTime::parse_from_str("2026-01-10 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert!(is_within_cancel_window(
+            authorized,
+            authorized + chrono::Duration::minutes(29),
+            DEFAULT_CANCEL_WINDOW_MINUTES
+        ));
+        assert!(!is_within_cancel_window(
+            authorized,
+            authorized + chrono::Duration::minutes(31),
+            DEFAULT_CANCEL_WINDOW_MINUTES
+        ));
+        // UF com prazo maior (ex.: 24 horas)
+        assert!(is_within_cancel_window(
+            authorized,
+            authorized + chrono::Duration::minutes(31),
+            1440
+        ));
+    }
+}
@@ -0,0 +1,226 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// EXPORTAÇÃO DE XMLs PARA A CONTABILIDADE
+// ═══════════════════════════════════════════════════════════════════════════
+//! Gera o pacote mensal (ZIP) com os XMLs `procNFe` armazenados em
+//! `fiscal_documents`, os `procEventoNFe` de cancelamento das notas canceladas
+//! e um `resumo.csv` com os totais por situação.
+
+use serde::Serialize;
+use std::io::{Seek, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::models::{FiscalDocument, FiscalEvent};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiscalExportSummary {
+    pub documents: usize,
+    pub authorized: usize,
+    pub canceled: usize,
+    pub pending: usize,
+    pub total_authorized: f64,
+}
+
+/// Escreve o ZIP com um arquivo XML por documento, os eventos de cancelamento
+/// homologados e o resumo CSV
+pub fn write_month_archive<W: Write + Seek>(
+    documents: &[FiscalDocument],
+    events: &[FiscalEvent],
+    writer: W,
+) -> Result<FiscalExportSummary, String> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for doc in documents {
+        zip.start_file(file_name(doc), options)
+            .map_err(|e| format!("Erro ao criar arquivo no ZIP: {}", e))?;
+        zip.write_all(doc.xml.as_bytes())
+            .map_err(|e| format!("Erro ao gravar XML no ZIP: {}", e))?;
+    }
+
+    for event in events
+        .iter()
+        .filter(|e| e.event_type == "CANCELLATION" && e.protocol.is_some())
+    {
+        let (Some(key), Some(xml)) = (&event.access_key, &event.xml) else {
+            continue;
+        };
+        zip.start_file(event_file_name(key, event.sequence), options)
+            .map_err(|e| format!("Erro ao criar arquivo no ZIP: {}", e))?;
+        zip.write_all(xml.as_bytes())
+            .map_err(|e| format!("Erro ao gravar evento no ZIP: {}", e))?;
+    }
+
+    zip.start_file("resumo.csv", options)
+        .map_err(|e| format!("Erro ao criar resumo no ZIP: {}", e))?;
+    zip.write_all(summary_csv(documents).as_bytes())
+        .map_err(|e| format!("Erro ao gravar resumo no ZIP: {}", e))?;
+
+    zip.finish()
+        .map_err(|e| format!("Erro ao finalizar ZIP: {}", e))?;
+
+    Ok(summarize(documents))
+}
+
+/// Nome do arquivo no pacote: procNFe para notas com protocolo, NFe para pendentes
+fn file_name(doc: &FiscalDocument) -> String {
+    if doc.protocol.is_some() {
+        format!("{}-procNFe.xml", doc.access_key)
+    } else {
+        format!("pendentes/{}-NFe.xml", doc.access_key)
+    }
+}
+
+/// Nome do evento de cancelamento: tpEvento + chave + nSeqEvento (mesmo Id do infEvento)
+fn event_file_name(access_key: &str, sequence: i32) -> String {
+    format!(
+        "{}{}{:02}-procEventoNFe.xml",
+        crate::nfce::event::EVENT_CANCELLATION,
+        access_key,
+        sequence
+    )
+}
+
+/// CSV separado por `;` com valores no formato brasileiro (vírgula decimal)
+pub fn summary_csv(documents: &[FiscalDocument]) -> String {
+    let mut csv = String::from("chave;modelo;serie;numero;emissao;situacao;protocolo;valor\n");
+
+    for doc in documents {
+        csv.push_str(&format!(
+            "{};{};{};{};{};{};{};{}\n",
+            doc.access_key,
+            doc.model,
+            doc.serie,
+            doc.number,
+            doc.issued_at,
+            status_label(&doc.status),
+            doc.protocol.as_deref().unwrap_or(""),
+            format_decimal(doc.total)
+        ));
+    }
+
+    let summary = summarize(documents);
+    csv.push_str(&format!(
+        "TOTAL AUTORIZADO;;;;;{} nota(s);;{}\n",
+        summary.authorized,
+        format_decimal(summary.total_authorized)
+    ));
+
+    csv
+}
+
+fn summarize(documents: &[FiscalDocument]) -> FiscalExportSummary {
+    let authorized: Vec<&FiscalDocument> = documents
+        .iter()
+        .filter(|d| d.status == "AUTHORIZED")
+        .collect();
+    let canceled = documents.iter().filter(|d| d.status == "CANCELED").count();
+
+    FiscalExportSummary {
+        documents: documents.len(),
+        authorized: authorized.len(),
+        canceled,
+        pending: documents.len() - authorized.len() - canceled,
+        total_authorized: authorized.iter().map(|d| d.total).sum(),
+    }
+}
+
+fn status_label(status: &str) -> &str {
+    match status {
+        "AUTHORIZED" => "Autorizada",
+        "CANCELED" => "Cancelada",
+        "CONTINGENCY" => "Contingencia (pendente)",
+        "REJECTED" => "Rejeitada",
+        other => other,
+    }
+}
+
+fn format_decimal(value: f64) -> String {
+    format!("{:.2}", value).replace('.', ",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    fn document(key: &str, status: &str, protocol: Option<&str>, total: f64) -> FiscalDocument {
+        FiscalDocument {
+            id: key.to_string(),
+            sale_id: None,
+            service_order_id: None,
+            access_key: key.to_string(),
+            model: 65,
+            serie: 1,
+            number: 1,
+            emission_type: 1,
+            environment: 2,
+            status: status.to_string(),
+            protocol: protocol.map(|p| p.to_string()),
+            total,
+            xml: format!("<nfeProc>{}</nfeProc>", key),
+            issued_at: "2026-01-10T10:00:00Z".to_string(),
+            authorized_at: None,
+            created_at: "2026-01-10T10:00:00Z".to_string(),
+            updated_at: "2026-01-10T10:00:00Z".to_string(),
+        }
+    }
+
+    fn cancellation(key: &str, protocol: Option<&str>) -> FiscalEvent {
+        FiscalEvent {
+            id: format!("ev-{}", key),
+            sale_id: None,
+            access_key: Some(key.to_string()),
+            event_type: "CANCELLATION".to_string(),
+            sequence: 1,
+            serie: None,
+            number_start: None,
+            number_end: None,
+            justification: "Cancelamento da venda: teste".to_string(),
+            status_code: Some("135".to_string()),
+            status_message: None,
+            protocol: protocol.map(|p| p.to_string()),
+            xml: Some(format!("<procEventoNFe>{}</procEventoNFe>", key)),
+            created_at: "2026-01-10T10:10:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_write_month_archive() {
+        let docs = vec![
+            document("KEY1", "AUTHORIZED", Some("1351"), 10.5),
+            document("KEY2", "CANCELED", Some("1352"), 3.0),
+            document("KEY3", "CONTINGENCY", None, 7.0),
+        ];
+
+        let events = vec![
+            cancellation("KEY2", Some("1359")),
+            cancellation("KEY3", None), // rejeitado pela SEFAZ
+        ];
+
+        let mut buffer = Cursor::new(Vec::new());
+        let summary = write_month_archive(&docs, &events, &mut buffer).unwrap();
+
+        assert_eq!(summary.documents, 3);
+        assert_eq!(summary.authorized, 1);
+        assert_eq!(summary.canceled, 1);
+        assert_eq!(summary.pending, 1);
+        assert_eq!(summary.total_authorized, 10.5);
+
+        let mut archive = zip::ZipArchive::new(buffer).unwrap();
+        assert_eq!(archive.len(), 5);
+        assert!(archive.by_name("KEY1-procNFe.xml").is_ok());
+        assert!(archive.by_name("110111KEY201-procEventoNFe.xml").is_ok());
+        assert!(archive.by_name("pendentes/KEY3-NFe.xml").is_ok());
+
+        let mut csv = String::new();
+        archive
+            .by_name("resumo.csv")
+            .unwrap()
+            .read_to_string(&mut csv)
+            .unwrap();
+        assert!(csv.contains("KEY1;65;1;1;2026-01-10T10:00:00Z;Autorizada;1351;10,50"));
+        assert!(csv.contains("TOTAL AUTORIZADO;;;;;1 nota(s);;10,50"));
+    }
+}
@@ -2,7 +2,8 @@
 // MÓDULO NFC-e - Nota Fiscal de Consumidor Eletrônica
 // ═══════════════════════════════════════════════════════════════════════════
 //! Implementação completa de geração e transmissão de NFC-e (Modelo 65)
-//! conforme especificações da SEFAZ (NT 2019.001 versão 1.60).
+//! conforme especificações da SEFAZ (NT 2019.001 versão 1.60), além da
+//! NF-e (Modelo 55) para vendas a empresas e ordens de serviço.
 //!
 //! ## Componentes
 //!
@@ -13,15 +14,26 @@
 //! - `webservice`: Cliente SOAP para comunicação com SEFAZ
 //! - `endpoints`: URLs dos WebServices por UF e ambiente
 //! - `qrcode`: Geração de QR Code conforme NT 2019.001
-//! - `danfe`: Impressão de DANFE NFC-e em impressora térmica
+//! - `danfe`: DANFE NFC-e para impressora térmica (58/80mm) e PDF, com reimpressão pelo XML
+//! - `danfe_a4`: DANFE NF-e retrato A4 em PDF
+//! - `pdf`: Gerador mínimo de PDF (texto, linhas e código de barras)
 //! - `contingency`: Modo offline e EPEC
+//! - `event`: Eventos de cancelamento (110111) e inutilização de numeração
+//! - `nfe_proc`: XML de distribuição (nota + protocolo) para armazenamento
+//! - `export`: Pacote mensal de XMLs para a contabilidade
+//! - `purchase`: Leitura da NF-e de compra para entrada no estoque
+//! - `retransmission`: Reenvio automático das notas em contingência
+//! - `tax`: Motor tributário (ICMS/PIS/COFINS, ST, FCP) e tabela IBPT
+//! - `schema`: Esquema XSD do leiaute 4.00 embutido
+//! - `validation`: Validação prévia (esquema + regras de negócio) antes da assinatura
+//! - `simulator`: SEFAZ simulada em servidor local para os testes de integração
 //!
 //! ## Fluxo de Emissão
 //!
 //! ```text
-//! 1. Validar Certificado → 2. Gerar XML → 3. Assinar (XMLDSig)
-//!    ↓                       ↓              ↓
-//! 4. Enviar SEFAZ ←─────── 5. Gerar QR Code ←─── 6. Imprimir DANFE
+//! 1. Validar Certificado → 2. Gerar XML → 3. Validar (XSD + regras) → 4. Assinar (XMLDSig)
+//!                                                                          ↓
+//! 7. Imprimir DANFE ←───── 6. Gerar QR Code ←───────────────────── 5. Enviar SEFAZ
 //! ```
 
 pub mod access_key;
@@ -29,9 +41,21 @@ pub mod certificate;
 pub mod commands;
 pub mod contingency;
 pub mod danfe;
+pub mod danfe_a4;
 pub mod endpoints;
+pub mod event;
+pub mod export;
+pub mod nfe_proc;
+pub mod pdf;
+pub mod purchase;
 pub mod qrcode;
+pub mod retransmission;
+pub mod schema;
 pub mod signer;
+#[cfg(test)]
+pub mod simulator;
+pub mod tax;
+pub mod validation;
 pub mod webservice;
 pub mod xml_builder;
 
@@ -39,9 +63,16 @@ pub mod xml_builder;
 pub use access_key::AccessKey;
 pub use certificate::Certificate;
 pub use contingency::ContingencyManager;
-pub use danfe::{DanfeData, DanfeItem, DanfePrinter};
+pub use danfe::{DanfeData, DanfeItem, DanfeLayout, DanfePayment, DanfePrinter};
+pub use danfe_a4::DanfeA4;
 pub use endpoints::{Environment, SefazEndpoints};
+pub use event::{CancellationEvent, Inutilization};
 pub use qrcode::{QrCodeGenerator, QrCodeParams};
 pub use signer::XmlSigner;
+pub use tax::{IbptRates, TaxProfile};
+pub use validation::{validate_nfe_xml, ValidationIssue};
 pub use webservice::SefazClient;
-pub use xml_builder::{NfceData, NfceItem, NfceXmlBuilder};
+pub use xml_builder::{
+    NfceCardInfo, NfceData, NfceItem, NfcePayment, NfceXmlBuilder, NfeAddress, NfeCarrier,
+    NfeFreight, NfeRecipient, NfeVolumes,
+};
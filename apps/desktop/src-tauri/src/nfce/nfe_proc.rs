@@ -0,0 +1,161 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// nfeProc - NF-e/NFC-e + PROTOCOLO DE AUTORIZAÇÃO
+// ═══════════════════════════════════════════════════════════════════════════
+//! Montagem do XML de distribuição (`nfeProc`), que é o arquivo legalmente
+//! válido a ser guardado e enviado à contabilidade: a nota assinada seguida
+//! do `protNFe` devolvido pela SEFAZ. Eventos (cancelamento) seguem o mesmo
+//! formato em `procEventoNFe`: evento assinado + `retEvento`.
+
+use roxmltree::Document;
+
+/// Dados mínimos do protocolo, usados quando a resposta da SEFAZ não traz `protNFe`
+#[derive(Debug, Clone)]
+pub struct ProtocolInfo {
+    pub access_key: String,
+    pub protocol: String,
+    pub environment: u8,
+    pub status_code: String,
+    pub status_message: String,
+    pub received_at: String,
+}
+
+/// Monta o `nfeProc` a partir do XML assinado e da resposta de autorização
+pub fn build_nfe_proc(
+    signed_xml: &str,
+    sefaz_response_xml: &str,
+    fallback: &ProtocolInfo,
+) -> Result<String, String> {
+    let nfe = extract_element(signed_xml, "NFe")
+        .ok_or_else(|| "Elemento NFe não encontrado no XML assinado".to_string())?;
+
+    let prot = extract_element(sefaz_response_xml, "protNFe")
+        .map(|prot| ensure_namespace(prot, "protNFe"))
+        .unwrap_or_else(|| build_prot_nfe(fallback, &digest_of(signed_xml)));
+
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><nfeProc xmlns="http://www.portalfiscal.inf.br/nfe" versao="4.00">{}{}</nfeProc>"#,
+        nfe, prot
+    ))
+}
+
+/// Monta o `procEventoNFe` a partir do `envEvento` assinado e do retorno da SEFAZ
+pub fn build_event_proc(
+    signed_event_xml: &str,
+    sefaz_response_xml: &str,
+) -> Result<String, String> {
+    let evento = extract_element(signed_event_xml, "evento")
+        .map(|evento| ensure_namespace(evento, "evento"))
+        .ok_or_else(|| "Elemento evento não encontrado no XML assinado".to_string())?;
+    let ret = extract_element(sefaz_response_xml, "retEvento")
+        .map(|ret| ensure_namespace(ret, "retEvento"))
+        .ok_or_else(|| "Elemento retEvento não encontrado na resposta da SEFAZ".to_string())?;
+
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><procEventoNFe xmlns="http://www.portalfiscal.inf.br/nfe" versao="1.00">{}{}</procEventoNFe>"#,
+        evento, ret
+    ))
+}
+
+/// Extrai o trecho original de um elemento (primeira ocorrência)
+fn extract_element(xml: &str, tag: &str) -> Option<String> {
+    let doc = Document::parse(xml).ok()?;
+    doc.descendants()
+        .find(|n| n.tag_name().name() == tag)
+        .map(|n| xml[n.range()].to_string())
+}
+
+fn ensure_namespace(element: String, tag: &str) -> String {
+    let open = format!("<{}", tag);
+    let head = element.split('>').next().unwrap_or_default();
+    if head.contains("xmlns=") {
+        element
+    } else {
+        element.replacen(
+            &open,
+            &format!(r#"{} xmlns="http://www.portalfiscal.inf.br/nfe""#, open),
+            1,
+        )
+    }
+}
+
+fn digest_of(signed_xml: &str) -> String {
+    Document::parse(signed_xml)
+        .ok()
+        .and_then(|doc| {
+            doc.descendants()
+                .find(|n| n.tag_name().name() == "DigestValue")
+                .and_then(|n| n.text().map(|s| s.to_string()))
+        })
+        .unwrap_or_default()
+}
+
+fn build_prot_nfe(info: &ProtocolInfo, digest: &str) -> String {
+    format!(
+        r#"<protNFe versao="4.00"><infProt><tpAmb>{}</tpAmb><verAplic>GIRO</verAplic><chNFe>{}</chNFe><dhRecbto>{}</dhRecbto><nProt>{}</nProt><digVal>{}</digVal><cStat>{}</cStat><xMotivo>{}</xMotivo></infProt></protNFe>"#,
+        info.environment,
+        info.access_key,
+        info.received_at,
+        info.protocol,
+        digest,
+        info.status_code,
+        quick_xml::escape::escape(&info.status_message)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<NFe xmlns="http://www.portalfiscal.inf.br/nfe"><infNFe Id="NFe35"><ide/></infNFe><Signature><SignedInfo><Reference><DigestValue>abc=</DigestValue></Reference></SignedInfo></Signature></NFe>"#;
+
+    fn fallback() -> ProtocolInfo {
+        ProtocolInfo {
+            access_key: "35260112345678000190650010000000011123456789".to_string(),
+            protocol: "135260000000001".to_string(),
+            environment: 2,
+            status_code: "100".to_string(),
+            status_message: "Autorizado o uso da NF-e".to_string(),
+            received_at: "2026-01-10T10:00:00-03:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_with_sefaz_protocol() {
+        let response = r#"<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope"><soap:Body><retEnviNFe xmlns="http://www.portalfiscal.inf.br/nfe"><cStat>104</cStat><protNFe versao="4.00"><infProt><nProt>999</nProt><cStat>100</cStat></infProt></protNFe></retEnviNFe></soap:Body></soap:Envelope>"#;
+
+        let proc_xml = build_nfe_proc(SIGNED, response, &fallback()).unwrap();
+
+        assert!(proc_xml.contains("<nfeProc"));
+        assert!(proc_xml.contains("<NFe xmlns"));
+        assert!(proc_xml.contains("<nProt>999</nProt>"));
+        assert!(Document::parse(&proc_xml).is_ok());
+    }
+
+    #[test]
+    fn test_build_with_fallback_protocol() {
+        let response = "<retEnviNFe><cStat>100</cStat><nProt>135260000000001</nProt></retEnviNFe>";
+
+        let proc_xml = build_nfe_proc(SIGNED, response, &fallback()).unwrap();
+
+        assert!(proc_xml.contains("<nProt>135260000000001</nProt>"));
+        assert!(proc_xml.contains("<digVal>abc=</digVal>"));
+        assert!(Document::parse(&proc_xml).is_ok());
+    }
+
+    #[test]
+    fn test_build_event_proc() {
+        let signed = r#"<envEvento xmlns="http://www.portalfiscal.inf.br/nfe" versao="1.00"><idLote>1</idLote><evento versao="1.00"><infEvento Id="ID1101113526"><tpEvento>110111</tpEvento></infEvento><Signature/></evento></envEvento>"#;
+        let response = r#"<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope"><soap:Body><retEnvEvento xmlns="http://www.portalfiscal.inf.br/nfe"><cStat>128</cStat><retEvento versao="1.00"><infEvento><cStat>135</cStat><nProt>135260000000009</nProt></infEvento></retEvento></retEnvEvento></soap:Body></soap:Envelope>"#;
+
+        let proc_xml = build_event_proc(signed, response).unwrap();
+
+        assert!(proc_xml.contains("<procEventoNFe"));
+        assert!(proc_xml
+            .contains(r#"<evento xmlns="http://www.portalfiscal.inf.br/nfe" versao="1.00">"#));
+        assert!(proc_xml.contains("<nProt>135260000000009</nProt>"));
+        assert!(Document::parse(&proc_xml).is_ok());
+
+        assert!(build_event_proc(signed, "<retEnvEvento/>").is_err());
+    }
+}
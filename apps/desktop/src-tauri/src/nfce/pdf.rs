@@ -0,0 +1,518 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// GERADOR DE PDF
+// ═══════════════════════════════════════════════════════════════════════════
+//! Escritor PDF 1.4 mínimo para os documentos auxiliares (DANFE).
+//!
+//! Usa somente as fontes padrão Helvetica e Helvetica-Bold com
+//! `WinAnsiEncoding` (acentos do português), sem embutir fontes. Imagens
+//! monocromáticas (QR Code) vão inline no fluxo de conteúdo.
+//! Coordenadas em milímetros a partir do canto superior esquerdo da página;
+//! para textos, `y` é a linha de base.
+
+const MM_TO_PT: f64 = 72.0 / 25.4;
+
+pub const A4_WIDTH_MM: f64 = 210.0;
+pub const A4_HEIGHT_MM: f64 = 297.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+pub struct PdfDocument {
+    width_mm: f64,
+    height_mm: f64,
+    pages: Vec<PdfPage>,
+}
+
+pub struct PdfPage {
+    height_mm: f64,
+    content: String,
+}
+
+impl PdfDocument {
+    pub fn new(width_mm: f64, height_mm: f64) -> Self {
+        Self {
+            width_mm,
+            height_mm,
+            pages: Vec::new(),
+        }
+    }
+
+    pub fn a4() -> Self {
+        Self::new(A4_WIDTH_MM, A4_HEIGHT_MM)
+    }
+
+    pub fn add_page(&mut self) -> &mut PdfPage {
+        self.pages.push(PdfPage {
+            height_mm: self.height_mm,
+            content: String::new(),
+        });
+        self.pages.last_mut().unwrap()
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    pub fn page_mut(&mut self, index: usize) -> Option<&mut PdfPage> {
+        self.pages.get_mut(index)
+    }
+
+    /// Serializa o documento (catálogo, páginas, fontes e tabela xref)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+        let page_count = self.pages.len().max(1);
+
+        // 1: Catálogo, 2: Árvore de páginas, 3/4: Fontes, 5+: página + conteúdo
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        let kids = (0..page_count)
+            .map(|i| format!("{} 0 R", 5 + i * 2))
+            .collect::<Vec<_>>()
+            .join(" ");
+        objects.push(
+            format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids, page_count).into_bytes(),
+        );
+        objects.push(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        );
+        objects.push(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        );
+
+        let empty = String::new();
+        for i in 0..page_count {
+            let content = self.pages.get(i).map(|p| &p.content).unwrap_or(&empty);
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    self.width_mm * MM_TO_PT,
+                    self.height_mm * MM_TO_PT,
+                    6 + i * 2
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(content.as_bytes());
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (idx, body) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", idx + 1).as_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+        out.extend_from_slice(b"0000000000 65535 f \n");
+        for offset in offsets {
+            out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        out
+    }
+}
+
+impl PdfPage {
+    fn x(&self, mm: f64) -> f64 {
+        mm * MM_TO_PT
+    }
+
+    fn y(&self, mm: f64) -> f64 {
+        (self.height_mm - mm) * MM_TO_PT
+    }
+
+    /// Texto com a linha de base em `y`
+    pub fn text(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.content.push_str(&format!(
+            "BT /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            font.resource(),
+            size,
+            self.x(x),
+            self.y(y),
+            encode_text(text)
+        ));
+    }
+
+    /// Texto alinhado à direita terminando em `right`
+    pub fn text_right(&mut self, right: f64, y: f64, size: f64, font: Font, text: &str) {
+        let width = text_width(text, size, font);
+        self.text(right - width, y, size, font, text);
+    }
+
+    /// Texto centralizado em `center`
+    pub fn text_center(&mut self, center: f64, y: f64, size: f64, font: Font, text: &str) {
+        let width = text_width(text, size, font);
+        self.text(center - width / 2.0, y, size, font, text);
+    }
+
+    pub fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, width_pt: f64) {
+        self.content.push_str(&format!(
+            "{:.2} w {:.2} {:.2} m {:.2} {:.2} l S\n",
+            width_pt,
+            self.x(x1),
+            self.y(y1),
+            self.x(x2),
+            self.y(y2)
+        ));
+    }
+
+    /// Retângulo (contorno) com o canto superior esquerdo em (x, y)
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, width_pt: f64) {
+        self.content.push_str(&format!(
+            "{:.2} w {:.2} {:.2} {:.2} {:.2} re S\n",
+            width_pt,
+            self.x(x),
+            self.y(y + height),
+            width * MM_TO_PT,
+            height * MM_TO_PT
+        ));
+    }
+
+    /// Retângulo preenchido em preto
+    pub fn fill_rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.content.push_str(&format!(
+            "{:.3} {:.3} {:.3} {:.3} re f\n",
+            self.x(x),
+            self.y(y + height),
+            width * MM_TO_PT,
+            height * MM_TO_PT
+        ));
+    }
+
+    /// Imagem monocromática inline (`true` = preto, linha a linha) esticada
+    /// no retângulo com canto superior esquerdo em (x, y)
+    pub fn mono_image(
+        &mut self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        columns: usize,
+        black: &[bool],
+    ) {
+        if columns == 0 || black.is_empty() {
+            return;
+        }
+        let rows = black.len() / columns;
+
+        // 1 bit por pixel (1 = branco), linhas completadas até o byte
+        let mut hex = String::with_capacity(rows * columns.div_ceil(8) * 2 + 1);
+        for row in black.chunks(columns).take(rows) {
+            for byte in row.chunks(8) {
+                let mut value = 0xFFu8;
+                for (bit, &pixel) in byte.iter().enumerate() {
+                    if pixel {
+                        value &= !(0x80 >> bit);
+                    }
+                }
+                hex.push_str(&format!("{:02X}", value));
+            }
+        }
+        hex.push('>');
+
+        self.content.push_str(&format!(
+            "q {:.3} 0 0 {:.3} {:.3} {:.3} cm\nBI /W {} /H {} /CS /G /BPC 1 /F /AHx ID\n{}\nEI Q\n",
+            width * MM_TO_PT,
+            height * MM_TO_PT,
+            self.x(x),
+            self.y(y + height),
+            columns,
+            rows,
+            hex
+        ));
+    }
+
+    /// Código de barras CODE-128C (somente dígitos) ocupando a largura informada
+    pub fn barcode_code128c(
+        &mut self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        digits: &str,
+    ) -> Result<(), String> {
+        let modules = code128c_modules(digits)?;
+        let module_width = width / modules.len() as f64;
+
+        let mut start: Option<usize> = None;
+        for (idx, &bar) in modules.iter().chain(std::iter::once(&false)).enumerate() {
+            match (bar, start) {
+                (true, None) => start = Some(idx),
+                (false, Some(begin)) => {
+                    self.fill_rect(
+                        x + begin as f64 * module_width,
+                        y,
+                        (idx - begin) as f64 * module_width,
+                        height,
+                    );
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TEXTO (WinAnsiEncoding)
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Converte o caractere para o byte WinAnsi (Latin-1 + extras do cp1252)
+fn win_ansi_byte(c: char) -> u8 {
+    match c as u32 {
+        0x20..=0x7E | 0xA0..=0xFF => c as u32 as u8,
+        _ => match c {
+            '€' => 0x80,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        },
+    }
+}
+
+/// String literal PDF: escapa delimitadores e usa octal fora do ASCII
+fn encode_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match win_ansi_byte(c) {
+            b'(' => out.push_str("\\("),
+            b')' => out.push_str("\\)"),
+            b'\\' => out.push_str("\\\\"),
+            b @ 0x20..=0x7E => out.push(b as char),
+            b => out.push_str(&format!("\\{:03o}", b)),
+        }
+    }
+    out
+}
+
+/// Larguras da Helvetica (1/1000 em) para ASCII 32..126
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0..?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @..O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P.._
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // `..o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p..~
+];
+
+/// Largura aproximada do texto em milímetros
+pub fn text_width(text: &str, size: f64, font: Font) -> f64 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c as u32 {
+            code @ 32..=126 => HELVETICA_WIDTHS[(code - 32) as usize] as u32,
+            _ => 556,
+        })
+        .sum();
+    // Helvetica-Bold é em média ~6% mais larga
+    let factor = if font == Font::Bold { 1.06 } else { 1.0 };
+    units as f64 / 1000.0 * size * factor / MM_TO_PT
+}
+
+/// Trunca o texto para caber na largura (com reticências)
+pub fn fit_text(text: &str, max_width: f64, size: f64, font: Font) -> String {
+    if text_width(text, size, font) <= max_width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.to_string();
+    while !truncated.is_empty() && text_width(&format!("{}...", truncated), size, font) > max_width
+    {
+        truncated.pop();
+    }
+    format!("{}...", truncated.trim_end())
+}
+
+/// Quebra o texto em linhas que caibam na largura informada
+pub fn wrap_text(text: &str, max_width: f64, size: f64, font: Font) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut current = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", current, word)
+            };
+            if text_width(&candidate, size, font) > max_width && !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                current = word.to_string();
+            } else {
+                current = candidate;
+            }
+        }
+        lines.push(current);
+    }
+    lines
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CÓDIGO DE BARRAS CODE-128 (CONJUNTO C)
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Larguras barra/espaço dos símbolos 0..=105 (106 = stop, 7 elementos)
+const CODE128_PATTERNS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+
+const CODE128_START_C: usize = 105;
+const CODE128_STOP: usize = 106;
+
+/// Módulos (true = barra) do CODE-128C para uma sequência par de dígitos
+pub fn code128c_modules(digits: &str) -> Result<Vec<bool>, String> {
+    if digits.is_empty() || digits.len() % 2 != 0 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err("CODE-128C exige quantidade par de dígitos".to_string());
+    }
+
+    let values: Vec<usize> = digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| ((pair[0] - b'0') * 10 + (pair[1] - b'0')) as usize)
+        .collect();
+
+    let checksum = values
+        .iter()
+        .enumerate()
+        .fold(CODE128_START_C, |acc, (idx, value)| acc + value * (idx + 1))
+        % 103;
+
+    let symbols = std::iter::once(CODE128_START_C)
+        .chain(values)
+        .chain([checksum, CODE128_STOP]);
+
+    let mut modules = Vec::new();
+    for symbol in symbols {
+        for (idx, width) in CODE128_PATTERNS[symbol].bytes().enumerate() {
+            let bar = idx % 2 == 0;
+            modules.extend(std::iter::repeat(bar).take((width - b'0') as usize));
+        }
+    }
+    Ok(modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code128_patterns_are_well_formed() {
+        for (idx, pattern) in CODE128_PATTERNS.iter().enumerate() {
+            let sum: u32 = pattern.bytes().map(|b| (b - b'0') as u32).sum();
+            let expected = if idx == CODE128_STOP { 13 } else { 11 };
+            assert_eq!(sum, expected, "padrão {}", idx);
+        }
+    }
+
+    #[test]
+    fn test_code128c_access_key() {
+        let key = "35260112345678000190550010000000011123456789";
+        let modules = code128c_modules(key).unwrap();
+        // start + 22 pares + checksum (11 módulos cada) + stop (13)
+        assert_eq!(modules.len(), 11 * 24 + 13);
+        assert!(modules[0]);
+        assert!(modules[modules.len() - 1]);
+
+        assert!(code128c_modules("123").is_err());
+        assert!(code128c_modules("12a4").is_err());
+    }
+
+    #[test]
+    fn test_encode_text() {
+        assert_eq!(encode_text("NF-e (55)"), "NF-e \\(55\\)");
+        assert_eq!(encode_text("SÉRIE"), "S\\311RIE");
+        assert_eq!(encode_text("a\\b"), "a\\\\b");
+    }
+
+    #[test]
+    fn test_pdf_structure() {
+        let mut doc = PdfDocument::a4();
+        let page = doc.add_page();
+        page.text(10.0, 10.0, 10.0, Font::Bold, "DANFE");
+        page.rect(5.0, 5.0, 200.0, 287.0, 0.5);
+        doc.add_page()
+            .text(10.0, 10.0, 8.0, Font::Regular, "Folha 2");
+
+        let bytes = doc.to_bytes();
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("/BaseFont /Helvetica-Bold"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+
+        // startxref aponta para a tabela xref
+        let start = text.rfind("startxref\n").unwrap() + "startxref\n".len();
+        let offset: usize = text[start..].lines().next().unwrap().parse().unwrap();
+        assert_eq!(&bytes[offset..offset + 4], b"xref");
+    }
+
+    #[test]
+    fn test_mono_image_inline() {
+        let mut doc = PdfDocument::new(80.0, 100.0);
+        let page = doc.add_page();
+        // 2x2: diagonal preta
+        page.mono_image(10.0, 10.0, 20.0, 20.0, 2, &[true, false, false, true]);
+
+        let content = &doc.pages[0].content;
+        assert!(content.contains("BI /W 2 /H 2 /CS /G /BPC 1 /F /AHx ID"));
+        // 0b0111_1111 e 0b1011_1111
+        assert!(content.contains("\n7FBF>\nEI Q"));
+    }
+
+    #[test]
+    fn test_fit_and_wrap_text() {
+        let long = "PASTILHA DE FREIO DIANTEIRA PARA MOTOCICLETA MODELO ESPECIAL";
+        let fitted = fit_text(long, 30.0, 7.0, Font::Regular);
+        assert!(fitted.ends_with("..."));
+        assert!(text_width(&fitted, 7.0, Font::Regular) <= 30.0);
+
+        let lines = wrap_text(long, 40.0, 7.0, Font::Regular);
+        assert!(lines.len() > 1);
+        assert!(lines
+            .iter()
+            .all(|l| text_width(l, 7.0, Font::Regular) <= 40.0));
+    }
+}
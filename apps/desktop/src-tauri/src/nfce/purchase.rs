@@ -0,0 +1,315 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// NF-e DE COMPRA - Leitura do XML do fornecedor
+// ═══════════════════════════════════════════════════════════════════════════
+//! Interpreta o XML de uma NF-e recebida (procNFe ou NFe avulsa) para dar
+//! entrada no estoque: emitente, itens com custo efetivo e lotes (`rastro`).
+
+use roxmltree::{Document, Node};
+
+/// Emitente da nota (fornecedor)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseSupplier {
+    pub cnpj: String,
+    pub name: String,
+    pub trade_name: Option<String>,
+    pub phone: Option<String>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub uf: Option<String>,
+}
+
+/// Lote informado no grupo `rastro` do item
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseLot {
+    pub number: String,
+    pub quantity: f64,
+    pub manufacturing_date: Option<String>, // AAAA-MM-DD
+    pub expiration_date: Option<String>,    // AAAA-MM-DD
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseItem {
+    pub number: u32,
+    pub supplier_code: String,
+    pub ean: Option<String>,
+    pub description: String,
+    pub ncm: Option<String>,
+    pub cfop: String,
+    pub unit: String,
+    pub quantity: f64,
+    pub unit_value: f64,
+    /// vProd - vDesc + vFrete + vSeg + vOutro + IPI + ICMS-ST
+    pub total_cost: f64,
+    pub lots: Vec<PurchaseLot>,
+}
+
+impl PurchaseItem {
+    /// Custo unitário efetivo (rateios e impostos não recuperáveis incluídos)
+    pub fn unit_cost(&self) -> f64 {
+        if self.quantity <= 0.0 {
+            return self.unit_value;
+        }
+        (self.total_cost / self.quantity * 10_000.0).round() / 10_000.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseNfe {
+    pub access_key: String,
+    pub number: u32,
+    pub serie: u16,
+    pub issued_at: String,
+    pub supplier: PurchaseSupplier,
+    pub items: Vec<PurchaseItem>,
+    pub total: f64,
+    /// Protocolo de autorização (presente no procNFe)
+    pub protocol: Option<String>,
+}
+
+/// Lê o XML da NF-e do fornecedor
+pub fn parse_purchase_nfe(xml: &str) -> Result<PurchaseNfe, String> {
+    let doc = Document::parse(xml).map_err(|e| format!("XML inválido: {}", e))?;
+
+    let inf_nfe = find(doc.root(), "infNFe").ok_or("XML não contém uma NF-e (infNFe)")?;
+    let access_key = inf_nfe
+        .attribute("Id")
+        .map(|id| id.trim_start_matches("NFe").to_string())
+        .filter(|key| key.len() == 44)
+        .ok_or("Chave de acesso ausente ou inválida")?;
+
+    let ide = find(inf_nfe, "ide").ok_or("Grupo ide ausente")?;
+    let model = text(ide, "mod").unwrap_or_default();
+    if model != "55" {
+        return Err(format!(
+            "Documento modelo {} não é uma NF-e de compra",
+            model
+        ));
+    }
+
+    let emit = find(inf_nfe, "emit").ok_or("Grupo emit ausente")?;
+    let cnpj = text(emit, "CNPJ")
+        .or_else(|| text(emit, "CPF"))
+        .ok_or("Emitente sem CNPJ/CPF")?;
+    let address = find(emit, "enderEmit");
+    let supplier = PurchaseSupplier {
+        cnpj,
+        name: text(emit, "xNome").ok_or("Emitente sem razão social")?,
+        trade_name: text(emit, "xFant"),
+        phone: address.and_then(|a| text(a, "fone")),
+        address: address.and_then(|a| {
+            let street = text(a, "xLgr")?;
+            Some(match (text(a, "nro"), text(a, "xBairro")) {
+                (Some(nro), Some(district)) => format!("{}, {} - {}", street, nro, district),
+                (Some(nro), None) => format!("{}, {}", street, nro),
+                _ => street,
+            })
+        }),
+        city: address.and_then(|a| text(a, "xMun")),
+        uf: address.and_then(|a| text(a, "UF")),
+    };
+
+    let mut items = Vec::new();
+    for det in inf_nfe.children().filter(|n| n.tag_name().name() == "det") {
+        items.push(parse_item(det)?);
+    }
+    if items.is_empty() {
+        return Err("NF-e sem itens".to_string());
+    }
+
+    let total = find(inf_nfe, "ICMSTot")
+        .and_then(|t| number(t, "vNF"))
+        .unwrap_or_else(|| items.iter().map(|i| i.total_cost).sum());
+
+    // Protocolo só é considerado quando a nota foi autorizada (cStat 100)
+    let protocol = find(doc.root(), "infProt")
+        .filter(|p| text(*p, "cStat").as_deref() == Some("100"))
+        .and_then(|p| text(p, "nProt"));
+
+    Ok(PurchaseNfe {
+        access_key,
+        number: text(ide, "nNF")
+            .and_then(|n| n.parse().ok())
+            .ok_or("Número da NF-e inválido")?,
+        serie: text(ide, "serie").and_then(|s| s.parse().ok()).unwrap_or(0),
+        issued_at: text(ide, "dhEmi")
+            .or_else(|| text(ide, "dEmi"))
+            .unwrap_or_default(),
+        supplier,
+        items,
+        total,
+        protocol,
+    })
+}
+
+fn parse_item(det: Node) -> Result<PurchaseItem, String> {
+    let number = det
+        .attribute("nItem")
+        .and_then(|n| n.parse().ok())
+        .ok_or("Item sem nItem")?;
+    let prod = find(det, "prod").ok_or_else(|| format!("Item {} sem grupo prod", number))?;
+
+    let quantity = number_or_zero(prod, "qCom");
+    if quantity <= 0.0 {
+        return Err(format!("Item {} com quantidade inválida", number));
+    }
+
+    let imposto = find(det, "imposto");
+    let ipi = imposto
+        .and_then(|i| find(i, "IPI"))
+        .map(|i| number_or_zero(i, "vIPI"))
+        .unwrap_or(0.0);
+    let st = imposto
+        .and_then(|i| find(i, "ICMS"))
+        .map(|i| number_or_zero(i, "vICMSST"))
+        .unwrap_or(0.0);
+
+    let total_cost = number_or_zero(prod, "vProd") - number_or_zero(prod, "vDesc")
+        + number_or_zero(prod, "vFrete")
+        + number_or_zero(prod, "vSeg")
+        + number_or_zero(prod, "vOutro")
+        + ipi
+        + st;
+
+    let lots = prod
+        .children()
+        .filter(|n| n.tag_name().name() == "rastro")
+        .filter_map(|r| {
+            Some(PurchaseLot {
+                number: text(r, "nLote")?,
+                quantity: number_or_zero(r, "qLote"),
+                manufacturing_date: text(r, "dFab"),
+                expiration_date: text(r, "dVal"),
+            })
+        })
+        .collect();
+
+    Ok(PurchaseItem {
+        number,
+        supplier_code: text(prod, "cProd").ok_or_else(|| format!("Item {} sem cProd", number))?,
+        ean: text(prod, "cEAN").filter(|ean| is_gtin(ean)),
+        description: text(prod, "xProd").unwrap_or_default(),
+        ncm: text(prod, "NCM"),
+        cfop: text(prod, "CFOP").unwrap_or_default(),
+        unit: text(prod, "uCom").unwrap_or_else(|| "UN".to_string()),
+        quantity,
+        unit_value: number_or_zero(prod, "vUnCom"),
+        total_cost: (total_cost * 100.0).round() / 100.0,
+        lots,
+    })
+}
+
+/// "SEM GTIN" e valores não numéricos não identificam o produto
+fn is_gtin(value: &str) -> bool {
+    matches!(value.len(), 8 | 12 | 13 | 14) && value.chars().all(|c| c.is_ascii_digit())
+}
+
+fn find<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.descendants().find(|n| n.tag_name().name() == tag)
+}
+
+fn text(node: Node, tag: &str) -> Option<String> {
+    find(node, tag)
+        .and_then(|n| n.text())
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+fn number(node: Node, tag: &str) -> Option<f64> {
+    text(node, tag).and_then(|v| v.parse().ok())
+}
+
+fn number_or_zero(node: Node, tag: &str) -> f64 {
+    number(node, tag).unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "35260198765432000110550010000012341000012345";
+
+    fn purchase_xml() -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<nfeProc xmlns="http://www.portalfiscal.inf.br/nfe" versao="4.00">
+  <NFe>
+    <infNFe Id="NFe{key}" versao="4.00">
+      <ide><cUF>35</cUF><natOp>VENDA</natOp><mod>55</mod><serie>1</serie><nNF>1234</nNF><dhEmi>2026-01-15T10:00:00-03:00</dhEmi></ide>
+      <emit>
+        <CNPJ>98765432000110</CNPJ><xNome>DISTRIBUIDORA DE PECAS LTDA</xNome><xFant>DISPECAS</xFant>
+        <enderEmit><xLgr>RUA DO COMERCIO</xLgr><nro>100</nro><xBairro>CENTRO</xBairro><xMun>CAMPINAS</xMun><UF>SP</UF><fone>1933334444</fone></enderEmit>
+      </emit>
+      <det nItem="1">
+        <prod>
+          <cProd>KIT-0042</cProd><cEAN>7891234567895</cEAN><xProd>KIT RELACAO CG 160</xProd><NCM>87141000</NCM><CFOP>6102</CFOP>
+          <uCom>UN</uCom><qCom>10.0000</qCom><vUnCom>50.00</vUnCom><vProd>500.00</vProd><vFrete>20.00</vFrete><vDesc>10.00</vDesc>
+          <rastro><nLote>L2026A</nLote><qLote>6.000</qLote><dFab>2026-01-02</dFab><dVal>2028-01-02</dVal></rastro>
+          <rastro><nLote>L2026B</nLote><qLote>4.000</qLote><dFab>2026-01-05</dFab><dVal>2028-01-05</dVal></rastro>
+        </prod>
+        <imposto><IPI><IPITrib><vIPI>25.00</vIPI></IPITrib></IPI></imposto>
+      </det>
+      <det nItem="2">
+        <prod>
+          <cProd>OL-20W50</cProd><cEAN>SEM GTIN</cEAN><xProd>OLEO 20W50 1L</xProd><NCM>27101932</NCM><CFOP>6102</CFOP>
+          <uCom>UN</uCom><qCom>24</qCom><vUnCom>18.50</vUnCom><vProd>444.00</vProd>
+        </prod>
+        <imposto><ICMS><ICMS10><vICMSST>12.00</vICMSST></ICMS10></ICMS></imposto>
+      </det>
+      <total><ICMSTot><vNF>991.00</vNF></ICMSTot></total>
+    </infNFe>
+  </NFe>
+  <protNFe versao="4.00"><infProt><chNFe>{key}</chNFe><cStat>100</cStat><nProt>135260000012345</nProt></infProt></protNFe>
+</nfeProc>"#,
+            key = KEY
+        )
+    }
+
+    #[test]
+    fn test_parse_proc_nfe() {
+        let nfe = parse_purchase_nfe(&purchase_xml()).unwrap();
+
+        assert_eq!(nfe.access_key, KEY);
+        assert_eq!(nfe.number, 1234);
+        assert_eq!(nfe.serie, 1);
+        assert_eq!(nfe.total, 991.0);
+        assert_eq!(nfe.protocol.as_deref(), Some("135260000012345"));
+        assert_eq!(nfe.supplier.cnpj, "98765432000110");
+        assert_eq!(nfe.supplier.trade_name.as_deref(), Some("DISPECAS"));
+        assert_eq!(
+            nfe.supplier.address.as_deref(),
+            Some("RUA DO COMERCIO, 100 - CENTRO")
+        );
+        assert_eq!(nfe.items.len(), 2);
+    }
+
+    #[test]
+    fn test_item_cost_and_lots() {
+        let nfe = parse_purchase_nfe(&purchase_xml()).unwrap();
+
+        // 500 - 10 (desconto) + 20 (frete) + 25 (IPI)
+        let kit = &nfe.items[0];
+        assert_eq!(kit.supplier_code, "KIT-0042");
+        assert_eq!(kit.ean.as_deref(), Some("7891234567895"));
+        assert_eq!(kit.total_cost, 535.0);
+        assert_eq!(kit.unit_cost(), 53.5);
+        assert_eq!(kit.lots.len(), 2);
+        assert_eq!(kit.lots[0].number, "L2026A");
+        assert_eq!(kit.lots[0].quantity, 6.0);
+        assert_eq!(kit.lots[1].expiration_date.as_deref(), Some("2028-01-05"));
+
+        // SEM GTIN não é EAN; ICMS-ST compõe o custo
+        let oil = &nfe.items[1];
+        assert_eq!(oil.ean, None);
+        assert_eq!(oil.total_cost, 456.0);
+        assert_eq!(oil.unit_cost(), 19.0);
+        assert!(oil.lots.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_other_documents() {
+        let nfce = purchase_xml().replace("<mod>55</mod>", "<mod>65</mod>");
+        assert!(parse_purchase_nfe(&nfce).is_err());
+        assert!(parse_purchase_nfe("<evento/>").is_err());
+        assert!(parse_purchase_nfe("não é xml").is_err());
+    }
+}
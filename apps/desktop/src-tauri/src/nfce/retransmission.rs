@@ -0,0 +1,667 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// RETRANSMISSÃO AUTOMÁTICA DE CONTINGÊNCIA
+// ═══════════════════════════════════════════════════════════════════════════
+//! Tarefa em segundo plano que reenvia as NFC-e emitidas em contingência
+//! offline (tpEmis = 9) assim que a SEFAZ volta a responder.
+//!
+//! - Consulta `NFeStatusServico4` antes de cada rodada (cStat 107 = em operação)
+//! - Envia as notas pendentes na ordem de emissão, com backoff exponencial
+//! - Duplicidade (204): consulta o protocolo e reconcilia a nota localmente
+//! - Gera um `Alert` quando a nota se aproxima do prazo legal de 24h
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager};
+
+use crate::models::CreateAlert;
+use crate::nfce::commands::{
+    load_enabled_settings, record_late_authorization, settings_certificate, settings_environment,
+};
+use crate::nfce::contingency::{ContingencyManager, OfflineNote};
+use crate::nfce::webservice::AuthorizationResponse;
+use crate::nfce::SefazClient;
+use crate::repositories::{AlertRepository, FiscalDocumentRepository, FiscalRepository};
+use crate::services::NotificationService;
+use crate::AppState;
+
+/// Prazo legal para transmitir uma nota emitida em contingência
+pub const DEADLINE_HOURS: i64 = 24;
+
+/// Antecedência (horas) com que o alerta de prazo é gerado
+pub const DEADLINE_ALERT_HOURS: i64 = 4;
+
+/// Intervalo entre verificações quando não há nada a fazer
+const IDLE_INTERVAL_SECS: i64 = 60;
+
+const BACKOFF_BASE_SECS: i64 = 30;
+const BACKOFF_MAX_SECS: i64 = 30 * 60;
+
+/// Como tratar o retorno da SEFAZ para uma nota retransmitida
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransmissionOutcome {
+    /// 100/150 - Autorizada
+    Authorized,
+    /// 204 - Duplicidade: a nota já foi recebida, consultar protocolo
+    Duplicate,
+    /// Serviço indisponível ou lote em processamento - tentar novamente
+    Retry,
+    /// Rejeição definitiva - requer correção manual
+    Rejected,
+}
+
+pub fn classify_status(status_code: &str) -> TransmissionOutcome {
+    match status_code {
+        "100" | "150" => TransmissionOutcome::Authorized,
+        "204" => TransmissionOutcome::Duplicate,
+        "103" | "105" | "108" | "109" | "999" => TransmissionOutcome::Retry,
+        _ => TransmissionOutcome::Rejected,
+    }
+}
+
+/// Espera exponencial: 30s, 60s, 120s... limitada a 30 minutos
+pub fn backoff_delay(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    let secs = BACKOFF_BASE_SECS.saturating_mul(1 << exponent);
+    Duration::seconds(secs.min(BACKOFF_MAX_SECS))
+}
+
+/// Horas restantes até o prazo de 24h (negativo se já expirou)
+pub fn hours_until_deadline(created_at: &str, now: DateTime<Utc>) -> Option<i64> {
+    let created = DateTime::parse_from_rfc3339(created_at).ok()?;
+    let deadline = created.with_timezone(&Utc) + Duration::hours(DEADLINE_HOURS);
+    Some((deadline - now).num_hours())
+}
+
+/// A nota ainda deve aguardar o backoff antes de nova tentativa?
+fn is_waiting(note: &OfflineNote, now: DateTime<Utc>) -> bool {
+    note.next_attempt_at
+        .as_deref()
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+        .is_some_and(|at| at.with_timezone(&Utc) > now)
+}
+
+/// Inicia o worker de retransmissão (chamado no setup da aplicação)
+pub fn spawn(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut worker = RetransmissionWorker {
+            app_handle,
+            consecutive_failures: 0,
+        };
+
+        loop {
+            let delay = worker.run_cycle().await;
+            tokio::time::sleep(delay.to_std().unwrap_or_default()).await;
+        }
+    });
+}
+
+struct RetransmissionWorker {
+    app_handle: AppHandle,
+    consecutive_failures: u32,
+}
+
+impl RetransmissionWorker {
+    /// Executa uma rodada e retorna quanto tempo esperar até a próxima
+    async fn run_cycle(&mut self) -> Duration {
+        let idle = Duration::seconds(IDLE_INTERVAL_SECS);
+        let manager = ContingencyManager::new(&self.app_handle);
+
+        let notes = match manager.list_pending_notes() {
+            Ok(notes) if !notes.is_empty() => notes,
+            Ok(_) => return idle,
+            Err(e) => {
+                tracing::error!("Erro ao listar notas em contingência: {}", e);
+                return idle;
+            }
+        };
+
+        let now = Utc::now();
+        for note in &notes {
+            self.check_deadline(&manager, note, now).await;
+        }
+
+        match self.transmit_pending(&manager, notes).await {
+            Ok(()) => {
+                self.consecutive_failures = 0;
+                idle
+            }
+            Err(e) => {
+                self.consecutive_failures += 1;
+                let delay = backoff_delay(self.consecutive_failures);
+                tracing::warn!(
+                    "Retransmissão de contingência adiada por {}s: {}",
+                    delay.num_seconds(),
+                    e
+                );
+                delay
+            }
+        }
+    }
+
+    /// Carrega as configurações fiscais e executa uma rodada de envio
+    async fn transmit_pending(
+        &self,
+        manager: &ContingencyManager,
+        notes: Vec<OfflineNote>,
+    ) -> Result<(), String> {
+        let state = self.app_handle.state::<AppState>();
+        let pool = state.pool();
+
+        let settings = load_enabled_settings(&FiscalRepository::new(pool)).await?;
+        let (cert_path, cert_password) = settings_certificate(&settings)?;
+        let client = SefazClient::new(
+            settings.uf.clone(),
+            settings_environment(&settings),
+            Some(cert_path),
+            Some(cert_password),
+        )?;
+
+        let mut round =
+            RetransmissionRound::new(&client, manager, pool, settings.environment as u8);
+        let result = round.run(notes).await;
+
+        for message in &round.notifications {
+            NotificationService::alert(&self.app_handle, message);
+        }
+        result
+    }
+
+    async fn check_deadline(
+        &self,
+        manager: &ContingencyManager,
+        note: &OfflineNote,
+        now: DateTime<Utc>,
+    ) {
+        if note.deadline_alerted {
+            return;
+        }
+        let Some(hours_left) = hours_until_deadline(&note.created_at, now) else {
+            return;
+        };
+        if hours_left > DEADLINE_ALERT_HOURS {
+            return;
+        }
+
+        let message = if hours_left > 0 {
+            format!(
+                "A NFC-e {} emitida em contingência ainda não foi transmitida. Restam {}h do prazo de 24h.",
+                note.access_key, hours_left
+            )
+        } else {
+            format!(
+                "A NFC-e {} emitida em contingência ultrapassou o prazo de 24h para transmissão.",
+                note.access_key
+            )
+        };
+        self.raise_alert("NFC-e em contingência perto do prazo", &message)
+            .await;
+
+        let mut note = note.clone();
+        note.deadline_alerted = true;
+        if let Err(e) = manager.update_note(&note) {
+            tracing::error!("Erro ao atualizar nota em contingência: {}", e);
+        }
+    }
+
+    async fn raise_alert(&self, title: &str, message: &str) {
+        let state = self.app_handle.state::<AppState>();
+        create_alert(state.pool(), title, message).await;
+        NotificationService::alert(&self.app_handle, message);
+    }
+}
+
+/// Rodada de envio da fila, independente do `AppHandle` para poder ser
+/// exercitada nos testes contra o simulador da SEFAZ
+pub struct RetransmissionRound<'a> {
+    client: &'a SefazClient,
+    manager: &'a ContingencyManager,
+    pool: &'a SqlitePool,
+    environment: u8,
+    /// Mensagens de alerta geradas na rodada (notificadas pelo worker)
+    pub notifications: Vec<String>,
+}
+
+impl<'a> RetransmissionRound<'a> {
+    pub fn new(
+        client: &'a SefazClient,
+        manager: &'a ContingencyManager,
+        pool: &'a SqlitePool,
+        environment: u8,
+    ) -> Self {
+        Self {
+            client,
+            manager,
+            pool,
+            environment,
+            notifications: Vec::new(),
+        }
+    }
+
+    /// Envia as notas em ordem; retorna erro se a SEFAZ estiver indisponível
+    pub async fn run(&mut self, notes: Vec<OfflineNote>) -> Result<(), String> {
+        let status = self.client.check_status().await?;
+        if status.status_code != "107" {
+            return Err(format!(
+                "SEFAZ fora de operação: {} - {}",
+                status.status_code, status.status_message
+            ));
+        }
+
+        let now = Utc::now();
+        for mut note in notes {
+            if is_waiting(&note, now) {
+                continue;
+            }
+
+            let response = match self.client.authorize(&note.xml).await {
+                Ok(response) => response,
+                Err(e) => {
+                    // Falha de comunicação: reagenda e interrompe a rodada
+                    self.reschedule(&mut note, &e)?;
+                    return Err(e);
+                }
+            };
+
+            match classify_status(&response.status_code) {
+                TransmissionOutcome::Authorized => {
+                    self.reconcile(&note, &response).await?;
+                }
+                TransmissionOutcome::Duplicate => {
+                    let query = self.client.query_protocol(&note.access_key).await?;
+                    if query.status_code == "100" && query.protocol.is_some() {
+                        let authorized = AuthorizationResponse {
+                            status_code: query.status_code,
+                            status_message: query.status_message,
+                            protocol: query.protocol,
+                            xml: query.xml.unwrap_or_default(),
+                        };
+                        self.reconcile(&note, &authorized).await?;
+                    } else {
+                        let message = format!(
+                            "Duplicidade sem protocolo localizado: {} - {}",
+                            query.status_code, query.status_message
+                        );
+                        self.reject(&note, &message).await?;
+                    }
+                }
+                TransmissionOutcome::Retry => {
+                    let message = format!("{} - {}", response.status_code, response.status_message);
+                    self.reschedule(&mut note, &message)?;
+                }
+                TransmissionOutcome::Rejected => {
+                    let message = format!("{} - {}", response.status_code, response.status_message);
+                    self.reject(&note, &message).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn reconcile(
+        &self,
+        note: &OfflineNote,
+        response: &AuthorizationResponse,
+    ) -> Result<(), String> {
+        record_late_authorization(
+            self.pool,
+            &note.access_key,
+            &note.xml,
+            response,
+            self.environment,
+        )
+        .await?;
+        self.manager.mark_as_transmitted(&note.access_key)?;
+
+        tracing::info!(
+            "NFC-e de contingência {} autorizada (protocolo {})",
+            note.access_key,
+            response.protocol.as_deref().unwrap_or("-")
+        );
+        Ok(())
+    }
+
+    fn reschedule(&self, note: &mut OfflineNote, error: &str) -> Result<(), String> {
+        note.attempts += 1;
+        note.last_error = Some(error.to_string());
+        note.next_attempt_at = Some((Utc::now() + backoff_delay(note.attempts)).to_rfc3339());
+        self.manager.update_note(note)
+    }
+
+    async fn reject(&mut self, note: &OfflineNote, message: &str) -> Result<(), String> {
+        self.manager.mark_as_error(&note.access_key, message)?;
+
+        FiscalDocumentRepository::new(self.pool)
+            .update_status(&note.access_key, "REJECTED")
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let message = format!(
+            "A NFC-e {} foi rejeitada na retransmissão ({}). Corrija e reemita a nota.",
+            note.access_key, message
+        );
+        create_alert(self.pool, "NFC-e de contingência rejeitada", &message).await;
+        self.notifications.push(message);
+        Ok(())
+    }
+}
+
+async fn create_alert(pool: &SqlitePool, title: &str, message: &str) {
+    let result = AlertRepository::new(pool)
+        .create(CreateAlert {
+            alert_type: "NFCE_CONTINGENCY".to_string(),
+            severity: "CRITICAL".to_string(),
+            title: title.to_string(),
+            message: message.to_string(),
+            product_id: None,
+            lot_id: None,
+        })
+        .await;
+
+    if let Err(e) = result {
+        tracing::error!("Erro ao criar alerta de contingência: {:?}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateFiscalDocument;
+    use crate::nfce::commands::{transmit_nfce, Transmission};
+    use crate::nfce::simulator::{
+        test_certificate, SefazSimulator, SimulatedResponse, AUTHORIZATION, QUERY,
+    };
+    use crate::nfce::tax::TaxProfile;
+    use crate::nfce::xml_builder::{NfceData, NfceItem, NfcePayment, NfceXmlBuilder};
+    use crate::nfce::{AccessKey, Environment, XmlSigner};
+    use chrono::TimeZone;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    const CNPJ: &str = "11222333000181";
+
+    #[test]
+    fn test_classify_status() {
+        assert_eq!(classify_status("100"), TransmissionOutcome::Authorized);
+        assert_eq!(classify_status("204"), TransmissionOutcome::Duplicate);
+        assert_eq!(classify_status("108"), TransmissionOutcome::Retry);
+        assert_eq!(classify_status("539"), TransmissionOutcome::Rejected);
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        assert_eq!(backoff_delay(1).num_seconds(), 30);
+        assert_eq!(backoff_delay(2).num_seconds(), 60);
+        assert_eq!(backoff_delay(4).num_seconds(), 240);
+        assert_eq!(backoff_delay(50).num_seconds(), BACKOFF_MAX_SECS);
+    }
+
+    #[test]
+    fn test_hours_until_deadline() {
+        let now = DateTime::parse_from_rfc3339("2026-01-11T08:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            hours_until_deadline("2026-01-10T12:00:00+00:00", now),
+            Some(4)
+        );
+        assert_eq!(
+            hours_until_deadline("2026-01-10T06:00:00+00:00", now),
+            Some(-2)
+        );
+        assert_eq!(hours_until_deadline("invalid", now), None);
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // FLUXO COMPLETO CONTRA O SIMULADOR DA SEFAZ
+    // ═══════════════════════════════════════════════════════════════════════
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        pool
+    }
+
+    fn create_tmp_manager() -> ContingencyManager {
+        let ts = Utc::now().timestamp_nanos_opt().unwrap_or(0);
+        ContingencyManager::with_path(std::env::temp_dir().join(format!("giro_test_retx_{}", ts)))
+    }
+
+    fn create_data() -> NfceData {
+        NfceData {
+            model: 65,
+            nature_of_operation: "VENDA".to_string(),
+            uf: "SP".to_string(),
+            cnpj: CNPJ.to_string(),
+            serie: 1,
+            numero: 42,
+            emission_date: Utc.with_ymd_and_hms(2026, 1, 20, 13, 0, 0).unwrap(),
+            emission_type: 1,
+            environment: 2,
+            crt: 1,
+            emitter_name: "MOTO PECAS TESTE LTDA".to_string(),
+            emitter_trade_name: None,
+            emitter_ie: "123456789012".to_string(),
+            emitter_address: "RUA DAS OFICINAS".to_string(),
+            emitter_district: Some("CENTRO".to_string()),
+            emitter_city: "SAO PAULO".to_string(),
+            emitter_city_code: "3550308".to_string(),
+            emitter_state: "SP".to_string(),
+            emitter_cep: "01001000".to_string(),
+            recipient_cpf: None,
+            recipient_name: None,
+            recipient: None,
+            freight: None,
+            additional_info: None,
+            operation_type: 1,
+            purpose: 1,
+            referenced_keys: Vec::new(),
+            items: vec![NfceItem {
+                number: 1,
+                code: "001".to_string(),
+                ean: None,
+                description: "CORRENTE 428H".to_string(),
+                ncm: "87149990".to_string(),
+                cfop: "5102".to_string(),
+                unit: "UN".to_string(),
+                quantity: 1.0,
+                unit_value: 30.0,
+                total_value: 30.0,
+                tax: TaxProfile::simples(0, "102", "07", "07"),
+                ibpt: None,
+            }],
+            total_products: 30.0,
+            total_discount: 0.0,
+            total_note: 30.0,
+            payments: vec![NfcePayment {
+                method: "01".to_string(),
+                value: 30.0,
+                card: None,
+            }],
+            csc_id: "1".to_string(),
+            csc: "123456".to_string(),
+        }
+    }
+
+    /// Emite com a SEFAZ fora do ar: a nota deve cair na contingência
+    /// (tpEmis 9) e ser registrada como documento pendente
+    async fn emit_offline(
+        client: &SefazClient,
+        manager: &ContingencyManager,
+        pool: &SqlitePool,
+    ) -> String {
+        let mut data = create_data();
+        let signer = XmlSigner::new(test_certificate(CNPJ));
+        let key = AccessKey::generate(
+            &data.uf,
+            data.emission_date.naive_utc(),
+            &data.cnpj,
+            data.model,
+            data.serie,
+            data.numero,
+            1,
+        )
+        .unwrap()
+        .key;
+        let xml = NfceXmlBuilder::new(data.clone(), key.clone())
+            .build()
+            .unwrap();
+        let signed_xml = signer.sign(&xml).unwrap();
+
+        let transmission = transmit_nfce(client, &signer, manager, &mut data, &key, signed_xml)
+            .await
+            .unwrap();
+        let Transmission::Contingency {
+            access_key,
+            signed_xml,
+        } = transmission
+        else {
+            panic!("esperava contingência, obteve {:?}", transmission);
+        };
+        assert_eq!(&access_key[34..35], "9");
+        assert_eq!(data.emission_type, 9);
+
+        FiscalDocumentRepository::new(pool)
+            .upsert(CreateFiscalDocument {
+                sale_id: None,
+                service_order_id: None,
+                access_key: access_key.clone(),
+                model: 65,
+                serie: 1,
+                number: 42,
+                emission_type: 9,
+                environment: 2,
+                status: "CONTINGENCY".to_string(),
+                protocol: None,
+                total: data.total_note,
+                xml: signed_xml,
+                issued_at: data.emission_date.to_rfc3339(),
+            })
+            .await
+            .unwrap();
+
+        access_key
+    }
+
+    async fn start() -> (SefazSimulator, SefazClient, ContingencyManager, SqlitePool) {
+        let sim = SefazSimulator::start().await;
+        let client = SefazClient::with_base_url(
+            "SP".to_string(),
+            Environment::Homologation,
+            &sim.base_url(),
+        )
+        .unwrap()
+        .with_timeout(std::time::Duration::from_millis(500));
+        (sim, client, create_tmp_manager(), setup_test_db().await)
+    }
+
+    #[tokio::test]
+    async fn test_contingency_then_late_authorization() {
+        let (sim, client, manager, pool) = start().await;
+        sim.set_response(AUTHORIZATION, SimulatedResponse::ServerError(503));
+
+        let key = emit_offline(&client, &manager, &pool).await;
+        assert_eq!(manager.list_pending_notes().unwrap().len(), 1);
+
+        // SEFAZ volta: a rodada autoriza e reconcilia o documento
+        sim.set_response(AUTHORIZATION, SimulatedResponse::Authorized);
+        let mut round = RetransmissionRound::new(&client, &manager, &pool, 2);
+        round
+            .run(manager.list_pending_notes().unwrap())
+            .await
+            .unwrap();
+
+        assert!(manager.list_pending_notes().unwrap().is_empty());
+        let doc = FiscalDocumentRepository::new(&pool)
+            .find_by_access_key(&key)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(doc.status, "AUTHORIZED");
+        assert_eq!(doc.protocol, sim.protocol_of(&key));
+        assert!(doc.xml.contains("<nfeProc"));
+        assert!(doc.xml.contains("<protNFe"));
+
+        let _ = std::fs::remove_dir_all(manager.get_storage_path());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_reschedules_and_duplicate_reconciles() {
+        let (sim, client, manager, pool) = start().await;
+        sim.set_response(
+            AUTHORIZATION,
+            SimulatedResponse::Timeout(std::time::Duration::from_secs(2)),
+        );
+        let key = emit_offline(&client, &manager, &pool).await;
+
+        // Nova queda durante a retransmissão: nota reagendada com backoff
+        let mut round = RetransmissionRound::new(&client, &manager, &pool, 2);
+        assert!(round
+            .run(manager.list_pending_notes().unwrap())
+            .await
+            .is_err());
+        let note = manager.list_pending_notes().unwrap().remove(0);
+        assert_eq!(note.attempts, 1);
+        assert!(note.next_attempt_at.is_some());
+
+        // A SEFAZ já havia recebido a nota: 204 → consulta → reconcilia
+        sim.set_response(AUTHORIZATION, SimulatedResponse::Authorized);
+        client.authorize(&note.xml).await.unwrap();
+        let mut note = note;
+        note.next_attempt_at = None;
+        manager.update_note(&note).unwrap();
+
+        let mut round = RetransmissionRound::new(&client, &manager, &pool, 2);
+        round
+            .run(manager.list_pending_notes().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(sim.requests(QUERY), 1);
+        assert!(manager.list_pending_notes().unwrap().is_empty());
+        let doc = FiscalDocumentRepository::new(&pool)
+            .find_by_access_key(&key)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(doc.status, "AUTHORIZED");
+        assert_eq!(doc.protocol, sim.protocol_of(&key));
+
+        let _ = std::fs::remove_dir_all(manager.get_storage_path());
+    }
+
+    #[tokio::test]
+    async fn test_late_rejection_raises_alert() {
+        let (sim, client, manager, pool) = start().await;
+        sim.set_response(AUTHORIZATION, SimulatedResponse::ServerError(500));
+        let key = emit_offline(&client, &manager, &pool).await;
+
+        sim.set_response(
+            AUTHORIZATION,
+            SimulatedResponse::rejected("778", "Rejeicao: Informado NCM inexistente"),
+        );
+        let mut round = RetransmissionRound::new(&client, &manager, &pool, 2);
+        round
+            .run(manager.list_pending_notes().unwrap())
+            .await
+            .unwrap();
+
+        assert!(manager.list_pending_notes().unwrap().is_empty());
+        assert_eq!(round.notifications.len(), 1);
+        assert!(round.notifications[0].contains("778"));
+        let doc = FiscalDocumentRepository::new(&pool)
+            .find_by_access_key(&key)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(doc.status, "REJECTED");
+
+        let _ = std::fs::remove_dir_all(manager.get_storage_path());
+    }
+}
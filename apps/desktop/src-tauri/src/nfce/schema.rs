@@ -0,0 +1,884 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// ESQUEMA XSD - Leiaute NF-e/NFC-e 4.00
+// ═══════════════════════════════════════════════════════════════════════════
+//! Validação estrutural do XML contra os XSD do pacote de schemas 4.00
+//! (`nfe_v4.00.xsd`, `leiauteNFe_v4.00.xsd`, `tiposBasico_v4.00.xsd` e
+//! `xmldsig-core-schema_v1.01.xsd`), embutidos no binário a partir de
+//! `nfce/schemas/`. Para atualizar o leiaute basta trocar os arquivos.
+//!
+//! Os XSD são interpretados na primeira validação, com o subconjunto usado
+//! pela SEFAZ: elementos (locais, globais e `ref`), `xs:sequence`/`xs:choice`
+//! com ocorrências, atributos, `simpleContent` e tipos simples derivados por
+//! restrição (pattern, enumeration, length, minLength, maxLength).
+//!
+//! A `Signature` é obrigatória no XSD, mas a validação ocorre antes da
+//! assinatura; por isso a ausência dela não é reportada.
+
+use regex::Regex;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::nfce::validation::ValidationIssue;
+
+const XSD_NS: &str = "http://www.w3.org/2001/XMLSchema";
+const NFE_NS: &str = "http://www.portalfiscal.inf.br/nfe";
+const DSIG_NS: &str = "http://www.w3.org/2000/09/xmldsig#";
+
+/// Schema de entrada (declara o elemento NFe)
+const ROOT_SCHEMA: &str = "nfe_v4.00.xsd";
+
+/// Arquivos do pacote, pelo nome usado em `schemaLocation`
+const SCHEMA_FILES: &[(&str, &str)] = &[
+    ("nfe_v4.00.xsd", include_str!("schemas/nfe_v4.00.xsd")),
+    (
+        "leiauteNFe_v4.00.xsd",
+        include_str!("schemas/leiauteNFe_v4.00.xsd"),
+    ),
+    (
+        "tiposBasico_v4.00.xsd",
+        include_str!("schemas/tiposBasico_v4.00.xsd"),
+    ),
+    (
+        "xmldsig-core-schema_v1.01.xsd",
+        include_str!("schemas/xmldsig-core-schema_v1.01.xsd"),
+    ),
+];
+
+/// Nome qualificado: (namespace, nome local)
+type QName = (String, String);
+
+fn qname(namespace: &str, name: &str) -> QName {
+    (namespace.to_string(), name.to_string())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// MODELO DO SCHEMA
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Default)]
+struct Schema {
+    elements: HashMap<QName, ElementDecl>,
+    complex_types: HashMap<QName, ComplexType>,
+    simple_types: HashMap<QName, SimpleType>,
+}
+
+struct ElementDecl {
+    name: QName,
+    ty: TypeRef,
+}
+
+enum TypeRef {
+    /// Tipo global (`type="..."`), inclusive os nativos `xs:*`
+    Named(QName),
+    Complex(Box<ComplexType>),
+    Simple(Box<SimpleType>),
+    /// Sem tipo declarado (`xs:anyType`)
+    Any,
+}
+
+#[derive(Default)]
+struct ComplexType {
+    content: Option<Particle>,
+    attributes: Vec<AttributeDecl>,
+    /// Tipo do texto em `xs:simpleContent`
+    text: Option<TypeRef>,
+}
+
+struct AttributeDecl {
+    name: String,
+    ty: TypeRef,
+    required: bool,
+    fixed: Option<String>,
+}
+
+struct Particle {
+    min: u32,
+    max: u32,
+    term: Term,
+}
+
+enum Term {
+    Element(ElementDecl),
+    Ref(QName),
+    Sequence(Vec<Particle>),
+    Choice(Vec<Particle>),
+}
+
+/// Tipo simples derivado por restrição; as facetas da base também se aplicam
+#[derive(Default)]
+struct SimpleType {
+    base: Option<QName>,
+    /// Os `xs:pattern` de um mesmo nível são alternativos (unidos num regex)
+    pattern: Option<Regex>,
+    enumeration: Vec<String>,
+    length: Option<usize>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+}
+
+enum Resolved<'a> {
+    Complex(&'a ComplexType),
+    Simple(&'a SimpleType),
+    Any,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CARGA DOS XSD
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        let mut schema = Schema::default();
+        let mut loaded: Vec<&str> = Vec::new();
+        schema.load(ROOT_SCHEMA, &mut loaded);
+        schema
+    })
+}
+
+impl Schema {
+    /// Carrega o arquivo e os que ele inclui/importa (uma vez cada)
+    fn load(&mut self, file: &'static str, loaded: &mut Vec<&'static str>) {
+        if loaded.contains(&file) {
+            return;
+        }
+        loaded.push(file);
+
+        let (_, source) = SCHEMA_FILES
+            .iter()
+            .find(|(name, _)| *name == file)
+            .unwrap_or_else(|| panic!("Arquivo do leiaute ausente: {}", file));
+        let doc =
+            Document::parse(source).unwrap_or_else(|e| panic!("XSD inválido ({}): {}", file, e));
+        let root = doc.root_element();
+        let target = root.attribute("targetNamespace").unwrap_or("");
+
+        for node in xsd_children(root) {
+            let name = node.attribute("name").unwrap_or("");
+            match node.tag_name().name() {
+                "include" | "import" => {
+                    if let Some(location) = node.attribute("schemaLocation") {
+                        let included = SCHEMA_FILES
+                            .iter()
+                            .find(|(name, _)| *name == location)
+                            .map(|(name, _)| *name)
+                            .unwrap_or_else(|| panic!("Arquivo do leiaute ausente: {}", location));
+                        self.load(included, loaded);
+                    }
+                }
+                "element" => {
+                    let decl = element_decl(node, target);
+                    self.elements.insert(decl.name.clone(), decl);
+                }
+                "complexType" => {
+                    self.complex_types
+                        .insert(qname(target, name), complex_type(node, target));
+                }
+                "simpleType" => {
+                    self.simple_types
+                        .insert(qname(target, name), simple_type(node));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn resolve<'a>(&'a self, ty: &'a TypeRef) -> Resolved<'a> {
+        match ty {
+            TypeRef::Complex(complex) => Resolved::Complex(complex),
+            TypeRef::Simple(simple) => Resolved::Simple(simple),
+            TypeRef::Any => Resolved::Any,
+            TypeRef::Named(name) => {
+                if let Some(complex) = self.complex_types.get(name) {
+                    Resolved::Complex(complex)
+                } else if let Some(simple) = self.simple_types.get(name) {
+                    Resolved::Simple(simple)
+                } else {
+                    // Tipos nativos (xs:string, xs:base64Binary...) não restringem o valor
+                    Resolved::Any
+                }
+            }
+        }
+    }
+}
+
+/// Filhos `xs:*` do nó, sem `xs:annotation`
+fn xsd_children<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(|c| {
+        c.is_element()
+            && c.tag_name().namespace() == Some(XSD_NS)
+            && c.tag_name().name() != "annotation"
+    })
+}
+
+fn xsd_child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    xsd_children(node).find(|c| c.tag_name().name() == name)
+}
+
+/// Resolve o prefixo de um valor `prefixo:nome` no contexto do nó do XSD
+fn resolve_qname(node: Node, value: &str) -> QName {
+    let (prefix, name) = match value.split_once(':') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, value),
+    };
+    let namespace = node.lookup_namespace_uri(prefix).unwrap_or("");
+    qname(namespace, name)
+}
+
+fn occurs(node: Node, attribute: &str) -> u32 {
+    match node.attribute(attribute) {
+        Some("unbounded") => u32::MAX,
+        Some(value) => value.parse().unwrap_or(1),
+        None => 1,
+    }
+}
+
+/// Elementos locais são qualificados (`elementFormDefault="qualified"` nos XSD da SEFAZ)
+fn element_decl(node: Node, target: &str) -> ElementDecl {
+    let name = qname(target, node.attribute("name").unwrap_or(""));
+    let ty = if let Some(ty) = node.attribute("type") {
+        TypeRef::Named(resolve_qname(node, ty))
+    } else if let Some(complex) = xsd_child(node, "complexType") {
+        TypeRef::Complex(Box::new(complex_type(complex, target)))
+    } else if let Some(simple) = xsd_child(node, "simpleType") {
+        TypeRef::Simple(Box::new(simple_type(simple)))
+    } else {
+        TypeRef::Any
+    };
+    ElementDecl { name, ty }
+}
+
+fn particle(node: Node, target: &str) -> Option<Particle> {
+    let term = match node.tag_name().name() {
+        "element" => match node.attribute("ref") {
+            Some(reference) => Term::Ref(resolve_qname(node, reference)),
+            None => Term::Element(element_decl(node, target)),
+        },
+        "sequence" => Term::Sequence(
+            xsd_children(node)
+                .filter_map(|c| particle(c, target))
+                .collect(),
+        ),
+        "choice" => Term::Choice(
+            xsd_children(node)
+                .filter_map(|c| particle(c, target))
+                .collect(),
+        ),
+        _ => return None,
+    };
+    Some(Particle {
+        min: occurs(node, "minOccurs"),
+        max: occurs(node, "maxOccurs"),
+        term,
+    })
+}
+
+fn complex_type(node: Node, target: &str) -> ComplexType {
+    let mut complex = ComplexType::default();
+    for child in xsd_children(node) {
+        match child.tag_name().name() {
+            "sequence" | "choice" => complex.content = particle(child, target),
+            "attribute" => complex.attributes.push(attribute_decl(child)),
+            "simpleContent" => {
+                if let Some(extension) = xsd_child(child, "extension") {
+                    complex.text = extension
+                        .attribute("base")
+                        .map(|base| TypeRef::Named(resolve_qname(extension, base)));
+                    complex.attributes.extend(
+                        xsd_children(extension)
+                            .filter(|a| a.tag_name().name() == "attribute")
+                            .map(attribute_decl),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    complex
+}
+
+fn attribute_decl(node: Node) -> AttributeDecl {
+    let ty = if let Some(ty) = node.attribute("type") {
+        TypeRef::Named(resolve_qname(node, ty))
+    } else if let Some(simple) = xsd_child(node, "simpleType") {
+        TypeRef::Simple(Box::new(simple_type(simple)))
+    } else {
+        TypeRef::Any
+    };
+    AttributeDecl {
+        name: node.attribute("name").unwrap_or("").to_string(),
+        ty,
+        required: node.attribute("use") == Some("required"),
+        fixed: node.attribute("fixed").map(str::to_string),
+    }
+}
+
+fn simple_type(node: Node) -> SimpleType {
+    let mut simple = SimpleType::default();
+    let Some(restriction) = xsd_child(node, "restriction") else {
+        return simple;
+    };
+    simple.base = restriction
+        .attribute("base")
+        .map(|base| resolve_qname(restriction, base));
+
+    let mut patterns = Vec::new();
+    for facet in xsd_children(restriction) {
+        let value = facet.attribute("value").unwrap_or("");
+        match facet.tag_name().name() {
+            "pattern" => patterns.push(format!("(?:{})", xsd_regex(value))),
+            "enumeration" => simple.enumeration.push(value.to_string()),
+            "length" => simple.length = value.parse().ok(),
+            "minLength" => simple.min_length = value.parse().ok(),
+            "maxLength" => simple.max_length = value.parse().ok(),
+            _ => {}
+        }
+    }
+    if !patterns.is_empty() {
+        let regex = format!("^(?:{})$", patterns.join("|"));
+        simple.pattern = Some(
+            Regex::new(&regex).unwrap_or_else(|e| panic!("xs:pattern inválido {}: {}", regex, e)),
+        );
+    }
+    simple
+}
+
+/// Regex do XSD para o dialeto do crate `regex`: no XSD `^` e `$` são literais
+/// fora de classes e o padrão sempre vale para o valor inteiro
+fn xsd_regex(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut in_class = false;
+    let mut escaped = false;
+    for c in pattern.chars() {
+        if escaped {
+            out.push(c);
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '^' | '$' if !in_class => out.push('\\'),
+            _ => {}
+        }
+        out.push(c);
+    }
+    out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// VALIDADOR
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Valida a estrutura e os tipos do documento (NFe como raiz)
+pub fn validate(doc: &Document) -> Vec<ValidationIssue> {
+    let mut validator = SchemaValidator {
+        schema: schema(),
+        issues: Vec::new(),
+    };
+
+    let root = doc.root_element();
+    let root_name = node_qname(root);
+    match validator.schema.elements.get(&root_name) {
+        Some(decl) if root_name == qname(NFE_NS, "NFe") => {
+            validator.validate_element(root, &decl.ty, "NFe")
+        }
+        _ => validator.error(
+            root.tag_name().name(),
+            format!("Elemento raiz deve ser NFe (namespace {})", NFE_NS),
+        ),
+    }
+    validator.issues
+}
+
+fn node_qname(node: Node) -> QName {
+    qname(
+        node.tag_name().namespace().unwrap_or(""),
+        node.tag_name().name(),
+    )
+}
+
+struct SchemaValidator {
+    schema: &'static Schema,
+    issues: Vec<ValidationIssue>,
+}
+
+impl SchemaValidator {
+    fn error(&mut self, path: &str, message: String) {
+        self.issues.push(ValidationIssue::schema(path, message));
+    }
+
+    fn validate_element(&mut self, node: Node, ty: &TypeRef, path: &str) {
+        let has_children = node.children().any(|c| c.is_element());
+        match self.schema.resolve(ty) {
+            Resolved::Complex(complex) => {
+                self.validate_attributes(node, complex, path);
+                if let Some(text) = &complex.text {
+                    self.validate_text(node, text, has_children, path);
+                } else if let Some(content) = &complex.content {
+                    self.validate_content(node, content, path);
+                } else if has_children {
+                    self.error(
+                        path,
+                        format!("{} não pode conter elementos", node.tag_name().name()),
+                    );
+                }
+            }
+            Resolved::Simple(_) => self.validate_text(node, ty, has_children, path),
+            Resolved::Any => {}
+        }
+    }
+
+    fn validate_text(&mut self, node: Node, ty: &TypeRef, has_children: bool, path: &str) {
+        if has_children {
+            self.error(
+                path,
+                format!("{} não pode conter elementos", node.tag_name().name()),
+            );
+            return;
+        }
+        if let Some(message) = self.check_value(ty, node.text().unwrap_or("")) {
+            self.error(path, message);
+        }
+    }
+
+    fn validate_attributes(&mut self, node: Node, complex: &ComplexType, path: &str) {
+        for decl in &complex.attributes {
+            let Some(value) = node.attribute(decl.name.as_str()) else {
+                if decl.required {
+                    self.error(path, format!("Atributo {} ausente", decl.name));
+                }
+                continue;
+            };
+            if let Some(fixed) = decl.fixed.as_deref().filter(|f| *f != value) {
+                self.error(path, format!("Atributo {} deve ser {}", decl.name, fixed));
+            } else if let Some(message) = self.check_value(&decl.ty, value) {
+                self.error(path, format!("Atributo {}: {}", decl.name, message));
+            }
+        }
+
+        for attribute in node.attributes() {
+            let declared = attribute.namespace().is_none()
+                && complex
+                    .attributes
+                    .iter()
+                    .any(|a| a.name == attribute.name());
+            if !declared {
+                self.error(path, format!("Atributo {} não permitido", attribute.name()));
+            }
+        }
+    }
+
+    /// Confere o valor contra o tipo simples e suas bases (primeira falha)
+    fn check_value(&self, ty: &TypeRef, value: &str) -> Option<String> {
+        let Resolved::Simple(simple) = self.schema.resolve(ty) else {
+            return None;
+        };
+
+        let len = value.chars().count();
+        let (min, max) = match simple.length {
+            Some(length) => (Some(length), Some(length)),
+            None => (simple.min_length, simple.max_length),
+        };
+        if min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) {
+            return Some(format!(
+                "Tamanho {} fora do limite ({} a {})",
+                len,
+                min.unwrap_or(0),
+                max.map_or("∞".to_string(), |max| max.to_string())
+            ));
+        }
+        if !simple.enumeration.is_empty() && !simple.enumeration.iter().any(|v| v == value) {
+            return Some(format!(
+                "Valor '{}' não permitido (aceitos: {})",
+                value,
+                simple.enumeration.join(", ")
+            ));
+        }
+        if simple.pattern.as_ref().is_some_and(|p| !p.is_match(value)) {
+            return Some(format!("Valor '{}' fora do formato do leiaute", value));
+        }
+
+        let base = simple.base.as_ref()?;
+        self.check_value(&TypeRef::Named(base.clone()), value)
+    }
+
+    /// Confere o conteúdo do tipo complexo: ordem, obrigatoriedade e número de
+    /// ocorrências. Um filho fora de ordem é reportado e ignorado, e a
+    /// conferência continua a partir dele.
+    fn validate_content(&mut self, node: Node, content: &Particle, path: &str) {
+        let children: Vec<Node> = node.children().filter(|c| c.is_element()).collect();
+        let items = match &content.term {
+            Term::Sequence(items) if content.min == 1 && content.max == 1 => items.as_slice(),
+            _ => std::slice::from_ref(content),
+        };
+
+        let mut position = 0;
+        let mut index = 0;
+        let mut count = 0;
+        while position < children.len() {
+            let child = children[position];
+            if index < items.len()
+                && count < items[index].max
+                && self.starts(&items[index].term, child)
+            {
+                position = self.match_term(&items[index].term, &children, position, path);
+                count += 1;
+                continue;
+            }
+
+            let found = (index + 1..items.len()).find(|&i| self.starts(&items[i].term, child));
+            if let Some(next) = found {
+                if index < items.len() && count < items[index].min {
+                    self.missing(path, &items[index]);
+                }
+                for item in &items[index + 1..next] {
+                    if item.min > 0 {
+                        self.missing(path, item);
+                    }
+                }
+                index = next;
+                count = 0;
+                continue;
+            }
+
+            let name = child.tag_name().name();
+            let message = if index < items.len() && self.starts(&items[index].term, child) {
+                format!(
+                    "{} excede o máximo de {} ocorrência(s)",
+                    name, items[index].max
+                )
+            } else {
+                format!("Elemento {} não esperado nesta posição", name)
+            };
+            self.error(&element_path(path, &children, position), message);
+            position += 1;
+        }
+
+        if index < items.len() && count < items[index].min {
+            self.missing(path, &items[index]);
+        }
+        for item in items.iter().skip(index + 1) {
+            if item.min > 0 {
+                self.missing(path, item);
+            }
+        }
+    }
+
+    /// Consome as ocorrências da partícula a partir de `position`
+    fn match_particle(
+        &mut self,
+        particle: &Particle,
+        children: &[Node],
+        mut position: usize,
+        path: &str,
+    ) -> usize {
+        let mut count = 0;
+        while count < particle.max
+            && position < children.len()
+            && self.starts(&particle.term, children[position])
+        {
+            position = self.match_term(&particle.term, children, position, path);
+            count += 1;
+        }
+        if count < particle.min {
+            self.missing(path, particle);
+        }
+        position
+    }
+
+    /// Consome uma ocorrência do termo (o filho em `position` já o inicia)
+    fn match_term(&mut self, term: &Term, children: &[Node], position: usize, path: &str) -> usize {
+        match term {
+            Term::Element(decl) => {
+                let child_path = element_path(path, children, position);
+                self.validate_element(children[position], &decl.ty, &child_path);
+                position + 1
+            }
+            Term::Ref(name) => {
+                let child_path = element_path(path, children, position);
+                if let Some(decl) = self.schema.elements.get(name) {
+                    self.validate_element(children[position], &decl.ty, &child_path);
+                }
+                position + 1
+            }
+            Term::Choice(branches) => {
+                match branches
+                    .iter()
+                    .find(|b| self.starts(&b.term, children[position]))
+                {
+                    Some(branch) => self.match_particle(branch, children, position, path),
+                    None => position,
+                }
+            }
+            Term::Sequence(items) => items.iter().fold(position, |position, item| {
+                self.match_particle(item, children, position, path)
+            }),
+        }
+    }
+
+    /// O elemento pode iniciar o termo?
+    fn starts(&self, term: &Term, node: Node) -> bool {
+        match term {
+            Term::Element(decl) => decl.name == node_qname(node),
+            Term::Ref(name) => *name == node_qname(node),
+            Term::Choice(branches) => branches.iter().any(|b| self.starts(&b.term, node)),
+            Term::Sequence(items) => {
+                for item in items {
+                    if self.starts(&item.term, node) {
+                        return true;
+                    }
+                    if !nullable(item) {
+                        return false;
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    fn missing(&mut self, path: &str, particle: &Particle) {
+        if matches!(&particle.term, Term::Ref(name) if *name == qname(DSIG_NS, "Signature")) {
+            return;
+        }
+        let names = first_names(&particle.term);
+        let message = if names.len() > 1 {
+            format!("Um dos elementos {} é obrigatório", names.join(" | "))
+        } else if particle.min > 1 {
+            format!("{} exige ao menos {} ocorrências", names[0], particle.min)
+        } else {
+            format!("Elemento obrigatório {} ausente", names[0])
+        };
+        self.error(path, message);
+    }
+}
+
+/// A partícula pode não ocorrer?
+fn nullable(particle: &Particle) -> bool {
+    particle.min == 0
+        || match &particle.term {
+            Term::Element(_) | Term::Ref(_) => false,
+            Term::Sequence(items) => items.iter().all(nullable),
+            Term::Choice(branches) => branches.iter().any(nullable),
+        }
+}
+
+/// Nomes dos elementos que podem iniciar o termo (para as mensagens)
+fn first_names(term: &Term) -> Vec<&str> {
+    match term {
+        Term::Element(decl) => vec![decl.name.1.as_str()],
+        Term::Ref(name) => vec![name.1.as_str()],
+        Term::Choice(branches) => branches.iter().flat_map(|b| first_names(&b.term)).collect(),
+        Term::Sequence(items) => {
+            let mut names = Vec::new();
+            for item in items {
+                names.extend(first_names(&item.term));
+                if !nullable(item) {
+                    break;
+                }
+            }
+            names
+        }
+    }
+}
+
+/// Caminho do elemento, com índice quando há irmãos de mesmo nome (ex: det[2])
+fn element_path(parent: &str, siblings: &[Node], position: usize) -> String {
+    let name = siblings[position].tag_name().name();
+    let same_name: Vec<usize> = siblings
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.tag_name().name() == name)
+        .map(|(i, _)| i)
+        .collect();
+    let base = if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", parent, name)
+    };
+    if same_name.len() > 1 {
+        let index = same_name.iter().position(|&i| i == position).unwrap_or(0) + 1;
+        format!("{}[{}]", base, index)
+    } else {
+        base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(xml: &str) -> Vec<ValidationIssue> {
+        validate(&Document::parse(xml).unwrap())
+    }
+
+    /// Valor do campo contra o tipo declarado no leiaute
+    fn check(group: &str, field: &str, value: &str) -> Option<String> {
+        let validator = SchemaValidator {
+            schema: schema(),
+            issues: Vec::new(),
+        };
+        let complex = find_complex(&validator.schema.elements[&qname(NFE_NS, "NFe")].ty, group)
+            .expect("grupo do leiaute");
+        let decl = find_element(complex.content.as_ref().unwrap(), field).expect("campo do grupo");
+        validator.check_value(&decl.ty, value)
+    }
+
+    fn find_complex<'a>(ty: &'a TypeRef, group: &str) -> Option<&'a ComplexType> {
+        let Resolved::Complex(complex) = schema().resolve(ty) else {
+            return None;
+        };
+        complex.content.as_ref().and_then(|c| find_group(c, group))
+    }
+
+    fn find_group<'a>(particle: &'a Particle, group: &str) -> Option<&'a ComplexType> {
+        match &particle.term {
+            Term::Element(decl) => match schema().resolve(&decl.ty) {
+                Resolved::Complex(complex) if decl.name.1 == group => Some(complex),
+                Resolved::Complex(_) => find_complex(&decl.ty, group),
+                _ => None,
+            },
+            Term::Ref(_) => None,
+            Term::Sequence(items) | Term::Choice(items) => {
+                items.iter().find_map(|i| find_group(i, group))
+            }
+        }
+    }
+
+    fn find_element<'a>(particle: &'a Particle, field: &str) -> Option<&'a ElementDecl> {
+        match &particle.term {
+            Term::Element(decl) if decl.name.1 == field => Some(decl),
+            Term::Sequence(items) | Term::Choice(items) => {
+                items.iter().find_map(|i| find_element(i, field))
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_sequence_order_and_missing() {
+        let xml = r#"<NFe xmlns="http://www.portalfiscal.inf.br/nfe"><infNFe versao="4.00" Id="NFe35260112345678000195650010000000011123456780"><emit/><ide/></infNFe></NFe>"#;
+        let found = issues(xml);
+
+        assert!(found
+            .iter()
+            .any(|i| i.path == "NFe/infNFe/ide" && i.message.contains("não esperado")));
+        assert!(found
+            .iter()
+            .any(|i| i.path == "NFe/infNFe" && i.message.contains("det")));
+        assert!(found
+            .iter()
+            .any(|i| i.path == "NFe/infNFe/emit" && i.message.contains("CNPJ | CPF")));
+        // Validação antes da assinatura
+        assert!(!found.iter().any(|i| i.message.contains("Signature")));
+    }
+
+    #[test]
+    fn test_attributes_and_namespace() {
+        let xml = r#"<NFe xmlns="http://www.portalfiscal.inf.br/nfe"><infNFe versao="3.10" Id="NFe123"/></NFe>"#;
+        let found = issues(xml);
+        assert!(found
+            .iter()
+            .any(|i| i.path == "NFe/infNFe" && i.message.starts_with("Atributo versao")));
+        assert!(found
+            .iter()
+            .any(|i| i.path == "NFe/infNFe" && i.message.starts_with("Atributo Id")));
+
+        let without_namespace = issues("<NFe><infNFe/></NFe>");
+        assert_eq!(without_namespace.len(), 1);
+        assert!(without_namespace[0].message.contains(NFE_NS));
+    }
+
+    #[test]
+    fn test_simple_types() {
+        assert_eq!(check("prod", "vProd", "10.00"), None);
+        assert_eq!(check("ide", "dhEmi", "2026-10-18T10:00:00-03:00"), None);
+        assert_eq!(check("emit", "xNome", "PEÇAS & CIA"), None);
+
+        assert!(check("prod", "vProd", "10.5").is_some());
+        assert!(check("ide", "dhEmi", "2026-10-18T10:00:00.123+00:00").is_some());
+        assert!(check("emit", "xNome", " PEÇAS").is_some());
+        assert!(check("emit", "xNome", "PEÇAS — CIA").is_some());
+        assert!(check("emit", "xNome", "A").unwrap().contains("Tamanho 1"));
+        assert!(check("detPag", "tPag", "07")
+            .unwrap()
+            .contains("não permitido"));
+    }
+
+    #[test]
+    fn test_contextual_cst() {
+        assert_eq!(check("PISAliq", "CST", "01"), None);
+        assert!(check("PISNT", "CST", "01").is_some());
+        assert_eq!(check("ICMS00", "CST", "00"), None);
+        assert!(check("ICMS00", "CST", "01").is_some());
+    }
+
+    #[test]
+    fn test_xsd_regex() {
+        assert_eq!(xsd_regex(r"[^0-9]$"), r"[^0-9]\$");
+        assert_eq!(xsd_regex(r"4\.00"), r"4\.00");
+    }
+
+    /// Todo tipo e `ref` citado nos XSD existe (um nome errado viraria xs:anyType)
+    #[test]
+    fn test_schema_references_resolve() {
+        fn check_type(ty: &TypeRef, unresolved: &mut Vec<String>) {
+            match ty {
+                TypeRef::Named(name) if name.0 != XSD_NS => {
+                    if matches!(schema().resolve(ty), Resolved::Any) {
+                        unresolved.push(name.1.clone());
+                    }
+                }
+                TypeRef::Complex(complex) => check_complex(complex, unresolved),
+                TypeRef::Simple(simple) => check_simple(simple, unresolved),
+                _ => {}
+            }
+        }
+        fn check_simple(simple: &SimpleType, unresolved: &mut Vec<String>) {
+            if let Some(base) = &simple.base {
+                check_type(&TypeRef::Named(base.clone()), unresolved);
+            }
+        }
+        fn check_complex(complex: &ComplexType, unresolved: &mut Vec<String>) {
+            for attribute in &complex.attributes {
+                check_type(&attribute.ty, unresolved);
+            }
+            if let Some(text) = &complex.text {
+                check_type(text, unresolved);
+            }
+            if let Some(content) = &complex.content {
+                check_particle(content, unresolved);
+            }
+        }
+        fn check_particle(particle: &Particle, unresolved: &mut Vec<String>) {
+            match &particle.term {
+                Term::Element(decl) => check_type(&decl.ty, unresolved),
+                Term::Ref(name) => {
+                    if !schema().elements.contains_key(name) {
+                        unresolved.push(name.1.clone());
+                    }
+                }
+                Term::Sequence(items) | Term::Choice(items) => {
+                    for item in items {
+                        check_particle(item, unresolved);
+                    }
+                }
+            }
+        }
+
+        let schema = schema();
+        let mut unresolved = Vec::new();
+        for decl in schema.elements.values() {
+            check_type(&decl.ty, &mut unresolved);
+        }
+        for complex in schema.complex_types.values() {
+            check_complex(complex, &mut unresolved);
+        }
+        for simple in schema.simple_types.values() {
+            check_simple(simple, &mut unresolved);
+        }
+        assert!(unresolved.is_empty(), "{:?}", unresolved);
+    }
+}
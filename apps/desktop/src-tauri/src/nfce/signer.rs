@@ -19,10 +19,15 @@ impl XmlSigner {
     }
 
     pub fn sign(&self, xml: &str) -> Result<String, String> {
+        self.sign_element(xml, "infNFe")
+    }
+
+    /// Assina o elemento `tag` (ex: `infEvento`, `infInut`) identificado pelo atributo Id
+    pub fn sign_element(&self, xml: &str, tag: &str) -> Result<String, String> {
         let doc = Document::parse(xml).map_err(|e| format!("Parse error: {}", e))?;
-        let (inf_nfe_xml, id) = self.extract_inf_nfe(&doc)?;
+        let (element_xml, id) = self.extract_element(&doc, tag)?;
 
-        let canonical_xml = self.canonicalize(&inf_nfe_xml)?;
+        let canonical_xml = self.canonicalize(&element_xml)?;
         let digest_value = self.calculate_digest(&canonical_xml)?;
 
         // SignedInfo deve ser canônico
@@ -33,20 +38,20 @@ impl XmlSigner {
         let signature_element =
             self.create_signature_element(&digest_value, &signature_value, &id)?;
 
-        self.insert_signature(xml, &signature_element)
+        self.insert_signature(xml, &signature_element, tag)
     }
 
-    fn extract_inf_nfe(&self, doc: &Document) -> Result<(String, String), String> {
+    fn extract_element(&self, doc: &Document, tag: &str) -> Result<(String, String), String> {
         for node in doc.descendants() {
-            if node.tag_name().name() == "infNFe" {
+            if node.tag_name().name() == tag {
                 let id = node
                     .attribute("Id")
-                    .ok_or("infNFe Id attribute not found")?;
+                    .ok_or_else(|| format!("{} Id attribute not found", tag))?;
                 let xml = self.node_to_xml(doc.input_text(), node.range());
                 return Ok((xml, id.to_string()));
             }
         }
-        Err(String::from("infNFe not found"))
+        Err(format!("{} not found", tag))
     }
 
     fn node_to_xml(&self, input: &str, range: std::ops::Range<usize>) -> String {
@@ -149,16 +154,18 @@ impl XmlSigner {
         Ok(signature)
     }
 
-    fn insert_signature(&self, xml: &str, signature: &str) -> Result<String, String> {
-        match xml.find("</infNFe>") {
+    fn insert_signature(&self, xml: &str, signature: &str, tag: &str) -> Result<String, String> {
+        let closing = format!("</{}>", tag);
+        match xml.find(&closing) {
             Some(pos) => {
+                let end = pos + closing.len();
                 let mut result = String::new();
-                result.push_str(&xml[..pos + 9]);
+                result.push_str(&xml[..end]);
                 result.push_str(signature);
-                result.push_str(&xml[pos + 9..]);
+                result.push_str(&xml[end..]);
                 Ok(result)
             }
-            None => Err(String::from("infNFe tag not found")),
+            None => Err(format!("{} tag not found", tag)),
         }
     }
 }
@@ -231,4 +238,17 @@ mod tests {
             "Certificate not embedded"
         );
     }
+
+    #[test]
+    fn test_sign_element_event() {
+        let cert = make_test_certificate();
+        let signer = XmlSigner::new(cert);
+
+        let xml = r#"<evento versao="1.00"><infEvento Id="ID1101113526"><tpEvento>110111</tpEvento></infEvento></evento>"#;
+
+        let signed = signer.sign_element(xml, "infEvento").expect("sign failed");
+
+        assert!(signed.contains("</infEvento><Signature"));
+        assert!(signed.contains("<Reference URI=\"#ID1101113526\">"));
+    }
 }
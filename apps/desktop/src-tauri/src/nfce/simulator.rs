@@ -0,0 +1,576 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// SIMULADOR OFFLINE DA SEFAZ (TESTES DE INTEGRAÇÃO)
+// ═══════════════════════════════════════════════════════════════════════════
+//! Servidor SOAP local (hyper) que imita os WebServices da SEFAZ para
+//! exercitar emissão → contingência → retransmissão em `cargo test`, sem rede
+//! e sem certificado ICP-Brasil.
+//!
+//! Conecte um `SefazClient::with_base_url(uf, ambiente, &sim.base_url())` e
+//! configure o retorno de cada serviço:
+//!
+//! - `Authorized`: comportamento normal (100, 107, 135, 102...), com controle
+//!   de chaves já autorizadas (204 Duplicidade e consulta por protocolo)
+//! - `Rejected`: cStat/xMotivo informados no grupo de retorno do serviço
+//! - `Timeout`: segura a resposta pelo tempo informado
+//! - `ServerError`: HTTP 5xx sem corpo SOAP
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use openssl::asn1::Asn1Time;
+use openssl::bn::BigNum;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::rsa::Rsa;
+use openssl::x509::{X509Builder, X509NameBuilder};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+use crate::nfce::certificate::Certificate;
+
+pub const AUTHORIZATION: &str = "NFeAutorizacao4";
+pub const STATUS: &str = "NFeStatusServico4";
+pub const QUERY: &str = "NFeConsultaProtocolo4";
+pub const EVENT: &str = "NFeRecepcaoEvento4";
+pub const INUTILIZATION: &str = "NFeInutilizacao4";
+
+/// Retorno configurado para um serviço
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulatedResponse {
+    Authorized,
+    Rejected {
+        status_code: String,
+        message: String,
+    },
+    Timeout(Duration),
+    ServerError(u16),
+}
+
+impl SimulatedResponse {
+    pub fn rejected(status_code: &str, message: &str) -> Self {
+        Self::Rejected {
+            status_code: status_code.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SimulatorState {
+    /// Retorno fixo por serviço (ausente = `Authorized`)
+    defaults: HashMap<String, SimulatedResponse>,
+    /// Retornos de uso único, consumidos antes do fixo
+    queued: HashMap<String, VecDeque<SimulatedResponse>>,
+    /// Chave de acesso → protocolo de autorização
+    authorized: HashMap<String, String>,
+    requests: HashMap<String, usize>,
+    last_protocol: u64,
+}
+
+impl SimulatorState {
+    fn next_response(&mut self, service: &str) -> SimulatedResponse {
+        *self.requests.entry(service.to_string()).or_default() += 1;
+
+        self.queued
+            .get_mut(service)
+            .and_then(|queue| queue.pop_front())
+            .or_else(|| self.defaults.get(service).cloned())
+            .unwrap_or(SimulatedResponse::Authorized)
+    }
+
+    fn next_protocol(&mut self) -> String {
+        self.last_protocol += 1;
+        format!("1352600{:08}", self.last_protocol)
+    }
+}
+
+pub struct SefazSimulator {
+    addr: SocketAddr,
+    state: Arc<Mutex<SimulatorState>>,
+    server: JoinHandle<()>,
+}
+
+impl SefazSimulator {
+    /// Sobe o servidor em uma porta livre de 127.0.0.1
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("Failed to bind SEFAZ simulator");
+        let addr = listener.local_addr().expect("Simulator address");
+        let state = Arc::new(Mutex::new(SimulatorState::default()));
+
+        let shared = state.clone();
+        let server = tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    continue;
+                };
+                let state = shared.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(move |request| handle(state.clone(), request));
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+
+        Self {
+            addr,
+            state,
+            server,
+        }
+    }
+
+    /// URL base para `SefazClient::with_base_url`
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Define o retorno de todas as próximas chamadas ao serviço
+    pub fn set_response(&self, service: &str, response: SimulatedResponse) {
+        self.lock().defaults.insert(service.to_string(), response);
+    }
+
+    /// Enfileira um retorno para a próxima chamada ao serviço
+    pub fn enqueue(&self, service: &str, response: SimulatedResponse) {
+        self.lock()
+            .queued
+            .entry(service.to_string())
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Quantidade de requisições recebidas pelo serviço
+    pub fn requests(&self, service: &str) -> usize {
+        self.lock().requests.get(service).copied().unwrap_or(0)
+    }
+
+    /// Protocolo com que a chave foi autorizada, se foi
+    pub fn protocol_of(&self, access_key: &str) -> Option<String> {
+        self.lock().authorized.get(access_key).cloned()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SimulatorState> {
+        self.state.lock().expect("Simulator state poisoned")
+    }
+}
+
+impl Drop for SefazSimulator {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// ATENDIMENTO DAS REQUISIÇÕES
+// ═══════════════════════════════════════════════════════════════════════════
+
+async fn handle(
+    state: Arc<Mutex<SimulatorState>>,
+    request: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let service = request
+        .uri()
+        .path()
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let body = match request.into_body().collect().await {
+        Ok(collected) => String::from_utf8_lossy(&collected.to_bytes()).to_string(),
+        Err(_) => String::new(),
+    };
+
+    let simulated = state.lock().unwrap().next_response(&service);
+    let outcome = match simulated {
+        SimulatedResponse::ServerError(code) => return Ok(reply(code, String::new())),
+        SimulatedResponse::Timeout(delay) => {
+            tokio::time::sleep(delay).await;
+            None
+        }
+        SimulatedResponse::Authorized => None,
+        SimulatedResponse::Rejected {
+            status_code,
+            message,
+        } => Some((status_code, message)),
+    };
+
+    let xml = {
+        let mut state = state.lock().unwrap();
+        match service.as_str() {
+            AUTHORIZATION => authorization(&mut state, &body, outcome),
+            STATUS => status(&body, outcome),
+            QUERY => query(&state, &body, outcome),
+            EVENT => event(&mut state, &body, outcome),
+            INUTILIZATION => inutilization(&mut state, &body, outcome),
+            _ => return Ok(reply(404, String::new())),
+        }
+    };
+
+    Ok(reply(200, soap(&service, &xml)))
+}
+
+fn reply(status: u16, body: String) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() =
+        StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        "application/soap+xml; charset=utf-8".parse().unwrap(),
+    );
+    response
+}
+
+fn soap(service: &str, xml: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope"><soap:Body><nfeResultMsg xmlns="http://www.portalfiscal.inf.br/nfe/wsdl/{}">{}</nfeResultMsg></soap:Body></soap:Envelope>"#,
+        service, xml
+    )
+}
+
+/// NFeAutorizacao4 (síncrono): lote 104 com o `protNFe` da nota
+fn authorization(
+    state: &mut SimulatorState,
+    body: &str,
+    outcome: Option<(String, String)>,
+) -> String {
+    let access_key = between(body, r#"Id="NFe"#, "\"").unwrap_or_default();
+    let digest = between(body, "<DigestValue>", "</DigestValue>").unwrap_or_default();
+
+    let (status_code, message, protocol) = match outcome {
+        Some((code, message)) => (code, message, None),
+        None if state.authorized.contains_key(&access_key) => (
+            "204".to_string(),
+            "Rejeicao: Duplicidade de NF-e".to_string(),
+            None,
+        ),
+        None => {
+            let protocol = state.next_protocol();
+            state
+                .authorized
+                .insert(access_key.clone(), protocol.clone());
+            (
+                "100".to_string(),
+                "Autorizado o uso da NF-e".to_string(),
+                Some(protocol),
+            )
+        }
+    };
+
+    format!(
+        r#"<retEnviNFe xmlns="http://www.portalfiscal.inf.br/nfe" versao="4.00"><tpAmb>{amb}</tpAmb><verAplic>SIMULADOR</verAplic><cStat>104</cStat><xMotivo>Lote processado</xMotivo><cUF>35</cUF><dhRecbto>{now}</dhRecbto>{prot}</retEnviNFe>"#,
+        amb = environment(body),
+        now = now(),
+        prot = prot_nfe(
+            body,
+            &access_key,
+            &digest,
+            &status_code,
+            &message,
+            protocol.as_deref()
+        ),
+    )
+}
+
+/// NFeStatusServico4: 107 em operação (rejeição = ex: 108 paralisado)
+fn status(body: &str, outcome: Option<(String, String)>) -> String {
+    let (status_code, message) =
+        outcome.unwrap_or_else(|| ("107".to_string(), "Servico em Operacao".to_string()));
+    format!(
+        r#"<retConsStatServ xmlns="http://www.portalfiscal.inf.br/nfe" versao="4.00"><tpAmb>{}</tpAmb><verAplic>SIMULADOR</verAplic><cStat>{}</cStat><xMotivo>{}</xMotivo><cUF>35</cUF><dhRecbto>{}</dhRecbto></retConsStatServ>"#,
+        environment(body),
+        status_code,
+        message,
+        now()
+    )
+}
+
+/// NFeConsultaProtocolo4: 100 com `protNFe` para chaves autorizadas, senão 217
+fn query(state: &SimulatorState, body: &str, outcome: Option<(String, String)>) -> String {
+    let access_key = between(body, "<chNFe>", "</chNFe>").unwrap_or_default();
+    let (status_code, message, prot) = match (outcome, state.authorized.get(&access_key)) {
+        (Some((code, message)), _) => (code, message, String::new()),
+        (None, Some(protocol)) => (
+            "100".to_string(),
+            "Autorizado o uso da NF-e".to_string(),
+            prot_nfe(
+                body,
+                &access_key,
+                "",
+                "100",
+                "Autorizado o uso da NF-e",
+                Some(protocol),
+            ),
+        ),
+        (None, None) => (
+            "217".to_string(),
+            "Rejeicao: NF-e nao consta na base de dados da SEFAZ".to_string(),
+            String::new(),
+        ),
+    };
+
+    format!(
+        r#"<retConsSitNFe xmlns="http://www.portalfiscal.inf.br/nfe" versao="4.00"><tpAmb>{}</tpAmb><verAplic>SIMULADOR</verAplic><cStat>{}</cStat><xMotivo>{}</xMotivo><cUF>35</cUF><dhRecbto>{}</dhRecbto><chNFe>{}</chNFe>{}</retConsSitNFe>"#,
+        environment(body),
+        status_code,
+        message,
+        now(),
+        access_key,
+        prot
+    )
+}
+
+/// NFeRecepcaoEvento4: lote 128 com o evento 135 (registrado)
+fn event(state: &mut SimulatorState, body: &str, outcome: Option<(String, String)>) -> String {
+    let (status_code, message, protocol) = match outcome {
+        Some((code, message)) => (code, message, None),
+        None => (
+            "135".to_string(),
+            "Evento registrado e vinculado a NF-e".to_string(),
+            Some(state.next_protocol()),
+        ),
+    };
+
+    format!(
+        r#"<retEnvEvento xmlns="http://www.portalfiscal.inf.br/nfe" versao="1.00"><idLote>1</idLote><tpAmb>{amb}</tpAmb><verAplic>SIMULADOR</verAplic><cOrgao>35</cOrgao><cStat>128</cStat><xMotivo>Lote de Evento Processado</xMotivo><retEvento versao="1.00"><infEvento><tpAmb>{amb}</tpAmb><verAplic>SIMULADOR</verAplic><cOrgao>35</cOrgao><cStat>{code}</cStat><xMotivo>{message}</xMotivo><chNFe>{key}</chNFe><tpEvento>{tp}</tpEvento><nSeqEvento>1</nSeqEvento><dhRegEvento>{now}</dhRegEvento>{prot}</infEvento></retEvento></retEnvEvento>"#,
+        amb = environment(body),
+        code = status_code,
+        message = message,
+        key = between(body, "<chNFe>", "</chNFe>").unwrap_or_default(),
+        tp = between(body, "<tpEvento>", "</tpEvento>").unwrap_or_default(),
+        now = now(),
+        prot = protocol
+            .map(|p| format!("<nProt>{}</nProt>", p))
+            .unwrap_or_default(),
+    )
+}
+
+/// NFeInutilizacao4: 102 (inutilização homologada)
+fn inutilization(
+    state: &mut SimulatorState,
+    body: &str,
+    outcome: Option<(String, String)>,
+) -> String {
+    let (status_code, message, protocol) = match outcome {
+        Some((code, message)) => (code, message, None),
+        None => (
+            "102".to_string(),
+            "Inutilizacao de numero homologado".to_string(),
+            Some(state.next_protocol()),
+        ),
+    };
+
+    format!(
+        r#"<retInutNFe xmlns="http://www.portalfiscal.inf.br/nfe" versao="4.00"><infInut><tpAmb>{}</tpAmb><verAplic>SIMULADOR</verAplic><cStat>{}</cStat><xMotivo>{}</xMotivo><cUF>35</cUF><dhRecbto>{}</dhRecbto>{}</infInut></retInutNFe>"#,
+        environment(body),
+        status_code,
+        message,
+        now(),
+        protocol
+            .map(|p| format!("<nProt>{}</nProt>", p))
+            .unwrap_or_default(),
+    )
+}
+
+fn prot_nfe(
+    body: &str,
+    access_key: &str,
+    digest: &str,
+    status_code: &str,
+    message: &str,
+    protocol: Option<&str>,
+) -> String {
+    format!(
+        r#"<protNFe versao="4.00"><infProt><tpAmb>{}</tpAmb><verAplic>SIMULADOR</verAplic><chNFe>{}</chNFe><dhRecbto>{}</dhRecbto>{}<digVal>{}</digVal><cStat>{}</cStat><xMotivo>{}</xMotivo></infProt></protNFe>"#,
+        environment(body),
+        access_key,
+        now(),
+        protocol
+            .map(|p| format!("<nProt>{}</nProt>", p))
+            .unwrap_or_default(),
+        digest,
+        status_code,
+        message
+    )
+}
+
+fn environment(body: &str) -> String {
+    between(body, "<tpAmb>", "</tpAmb>").unwrap_or_else(|| "2".to_string())
+}
+
+fn now() -> String {
+    Utc::now().format("%Y-%m-%dT%H:%M:%S-03:00").to_string()
+}
+
+/// Texto entre a primeira ocorrência de `start` e o `end` seguinte
+fn between(text: &str, start: &str, end: &str) -> Option<String> {
+    let from = text.find(start)? + start.len();
+    let len = text[from..].find(end)?;
+    Some(text[from..from + len].trim().to_string())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// CERTIFICADO DE TESTE
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Certificado autoassinado para assinar as notas enviadas ao simulador
+pub fn test_certificate(cnpj: &str) -> Certificate {
+    let rsa = Rsa::generate(2048).unwrap();
+    let pkey = PKey::from_rsa(rsa).unwrap();
+
+    let mut name_builder = X509NameBuilder::new().unwrap();
+    name_builder
+        .append_entry_by_text("CN", &format!("SIMULADOR:{}", cnpj))
+        .unwrap();
+    let name = name_builder.build();
+
+    let mut builder = X509Builder::new().unwrap();
+    builder.set_version(2).unwrap();
+    let serial = BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap();
+    builder.set_serial_number(&serial).unwrap();
+    builder.set_subject_name(&name).unwrap();
+    builder.set_issuer_name(&name).unwrap();
+    builder.set_pubkey(&pkey).unwrap();
+    builder
+        .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+        .unwrap();
+    builder
+        .set_not_after(&Asn1Time::days_from_now(365).unwrap())
+        .unwrap();
+    builder.sign(&pkey, MessageDigest::sha256()).unwrap();
+
+    Certificate {
+        x509: builder.build(),
+        private_key: pkey,
+        cnpj: cnpj.to_string(),
+        valid_until: Utc::now().naive_utc() + chrono::Duration::days(365),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nfce::endpoints::Environment;
+    use crate::nfce::SefazClient;
+
+    const KEY: &str = "35260111222333000181650010000000151000000155";
+
+    fn client(sim: &SefazSimulator) -> SefazClient {
+        SefazClient::with_base_url("SP".to_string(), Environment::Homologation, &sim.base_url())
+            .unwrap()
+            .with_timeout(Duration::from_millis(500))
+    }
+
+    fn signed_nfe(key: &str) -> String {
+        format!(
+            r#"<NFe xmlns="http://www.portalfiscal.inf.br/nfe"><infNFe Id="NFe{}" versao="4.00"><ide><tpAmb>2</tpAmb></ide></infNFe><Signature><SignedInfo><Reference><DigestValue>abc=</DigestValue></Reference></SignedInfo></Signature></NFe>"#,
+            key
+        )
+    }
+
+    #[tokio::test]
+    async fn test_authorize_then_duplicate() {
+        let sim = SefazSimulator::start().await;
+        let client = client(&sim);
+
+        let first = client.authorize(&signed_nfe(KEY)).await.unwrap();
+        assert_eq!(first.status_code, "100");
+        assert_eq!(first.protocol, sim.protocol_of(KEY));
+        assert!(first.xml.contains("<digVal>abc=</digVal>"));
+
+        let second = client.authorize(&signed_nfe(KEY)).await.unwrap();
+        assert_eq!(second.status_code, "204");
+        assert_eq!(sim.requests(AUTHORIZATION), 2);
+
+        let query = client.query_protocol(KEY).await.unwrap();
+        assert_eq!(query.status_code, "100");
+        assert_eq!(query.protocol, sim.protocol_of(KEY));
+    }
+
+    #[tokio::test]
+    async fn test_rejection_and_unknown_key() {
+        let sim = SefazSimulator::start().await;
+        let client = client(&sim);
+        sim.enqueue(
+            AUTHORIZATION,
+            SimulatedResponse::rejected("539", "Rejeicao: Duplicidade com diferenca na Chave"),
+        );
+
+        let rejected = client.authorize(&signed_nfe(KEY)).await.unwrap();
+        assert_eq!(rejected.status_code, "539");
+        assert!(rejected.protocol.is_none());
+
+        let query = client.query_protocol(KEY).await.unwrap();
+        assert_eq!(query.status_code, "217");
+
+        // Retorno enfileirado é de uso único
+        let authorized = client.authorize(&signed_nfe(KEY)).await.unwrap();
+        assert_eq!(authorized.status_code, "100");
+    }
+
+    #[tokio::test]
+    async fn test_status_and_failures() {
+        let sim = SefazSimulator::start().await;
+        let client = client(&sim);
+
+        assert_eq!(client.check_status().await.unwrap().status_code, "107");
+
+        sim.set_response(
+            STATUS,
+            SimulatedResponse::rejected("108", "Servico Paralisado"),
+        );
+        assert_eq!(client.check_status().await.unwrap().status_code, "108");
+
+        sim.set_response(STATUS, SimulatedResponse::ServerError(503));
+        let err = client.check_status().await.unwrap_err();
+        assert!(err.contains("HTTP 503"));
+
+        sim.set_response(
+            AUTHORIZATION,
+            SimulatedResponse::Timeout(Duration::from_secs(5)),
+        );
+        assert!(client.authorize(&signed_nfe(KEY)).await.is_err());
+        assert_eq!(sim.requests(AUTHORIZATION), 1);
+    }
+
+    #[tokio::test]
+    async fn test_event_and_inutilization() {
+        let sim = SefazSimulator::start().await;
+        let client = client(&sim);
+
+        let event = client
+            .send_event(&format!(
+                "<envEvento><evento><infEvento><tpAmb>2</tpAmb><chNFe>{}</chNFe><tpEvento>110111</tpEvento></infEvento></evento></envEvento>",
+                KEY
+            ))
+            .await
+            .unwrap();
+        assert_eq!(event.status_code, "135");
+        assert!(event.protocol.is_some());
+
+        let inut = client
+            .inutilize("<inutNFe><infInut><tpAmb>2</tpAmb></infInut></inutNFe>")
+            .await
+            .unwrap();
+        assert_eq!(inut.status_code, "102");
+
+        sim.set_response(
+            EVENT,
+            SimulatedResponse::rejected("573", "Duplicidade de Evento"),
+        );
+        let duplicate = client.send_event("<envEvento/>").await.unwrap();
+        assert_eq!(duplicate.status_code, "573");
+        assert!(duplicate.protocol.is_none());
+    }
+}
@@ -0,0 +1,443 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// MOTOR TRIBUTÁRIO - ICMS / PIS / COFINS / IBPT
+// ═══════════════════════════════════════════════════════════════════════════
+//! Cálculo dos tributos por item da NFC-e a partir da regra tributária
+//! (`TaxRule`) resolvida para o produto.
+//!
+//! ## Códigos suportados
+//! - **Simples Nacional (CRT 1)**: CSOSN 102, 103, 300, 400 (sem crédito),
+//!   201/202 (com ST) e 500 (ST cobrada anteriormente)
+//! - **Regime Normal (CRT 3)**: CST 00, 20 (base reduzida), 10 (com ST) e 60
+//! - **PIS/COFINS**: CST 01/02 (alíquota), 04-09 (não tributado) e 49-99 (outras)
+//!
+//! O valor aproximado dos tributos (`vTotTrib`, Lei 12.741/2012) é calculado
+//! com as alíquotas da tabela IBPT importada.
+
+use crate::models::{IbptRate, TaxRule};
+
+/// Código de Regime Tributário: Simples Nacional
+pub const CRT_SIMPLES: u8 = 1;
+/// Código de Regime Tributário: Regime Normal
+pub const CRT_NORMAL: u8 = 3;
+
+const SIMPLES_CODES: &[&str] = &["102", "103", "201", "202", "300", "400", "500"];
+const NORMAL_CODES: &[&str] = &["00", "10", "20", "60"];
+const ST_CODES: &[&str] = &["10", "201", "202"];
+
+/// Parâmetros tributários de um item (origem, códigos e alíquotas em %)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaxProfile {
+    pub icms_origin: u8,
+    pub icms_code: String, // CSOSN (CRT 1) ou CST (CRT 3)
+    pub icms_rate: f64,
+    pub icms_base_reduction: f64,
+    pub st_mva: f64,
+    pub st_rate: f64,
+    pub fcp_rate: f64,
+    pub pis_cst: String,
+    pub pis_rate: f64,
+    pub cofins_cst: String,
+    pub cofins_rate: f64,
+}
+
+/// Alíquotas aproximadas IBPT (%) usadas no vTotTrib
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IbptRates {
+    pub federal: f64,
+    pub state: f64,
+    pub municipal: f64,
+    pub source: String,
+}
+
+/// Valores calculados para o item
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ItemTaxes {
+    pub icms_base: f64,
+    pub icms_value: f64,
+    pub fcp_value: f64,
+    pub st_base: f64,
+    pub st_value: f64,
+    pub fcp_st_value: f64,
+    pub pis_base: f64,
+    pub pis_value: f64,
+    pub cofins_base: f64,
+    pub cofins_value: f64,
+    pub federal_tax: f64,
+    pub state_tax: f64,
+    pub municipal_tax: f64,
+}
+
+impl TaxProfile {
+    /// Perfil mínimo do Simples Nacional (sem alíquotas)
+    pub fn simples(icms_origin: u8, csosn: &str, pis_cst: &str, cofins_cst: &str) -> Self {
+        Self {
+            icms_origin,
+            icms_code: csosn.to_string(),
+            icms_rate: 0.0,
+            icms_base_reduction: 0.0,
+            st_mva: 0.0,
+            st_rate: 0.0,
+            fcp_rate: 0.0,
+            pis_cst: pis_cst.to_string(),
+            pis_rate: 0.0,
+            cofins_cst: cofins_cst.to_string(),
+            cofins_rate: 0.0,
+        }
+    }
+
+    pub fn has_st(&self) -> bool {
+        ST_CODES.contains(&self.icms_code.as_str())
+    }
+
+    /// Valida o perfil contra o regime do emitente
+    pub fn validate(&self, crt: u8) -> Result<(), String> {
+        let allowed = if crt == CRT_NORMAL {
+            NORMAL_CODES
+        } else {
+            SIMPLES_CODES
+        };
+        if !allowed.contains(&self.icms_code.as_str()) {
+            let kind = if crt == CRT_NORMAL { "CST" } else { "CSOSN" };
+            return Err(format!(
+                "{} {} não suportado para o CRT {}",
+                kind, self.icms_code, crt
+            ));
+        }
+        if self.icms_origin > 8 {
+            return Err(format!(
+                "Origem da mercadoria inválida: {}",
+                self.icms_origin
+            ));
+        }
+
+        for (name, rate) in [
+            ("ICMS", self.icms_rate),
+            ("redução de base", self.icms_base_reduction),
+            ("ICMS ST", self.st_rate),
+            ("FCP", self.fcp_rate),
+            ("PIS", self.pis_rate),
+            ("COFINS", self.cofins_rate),
+        ] {
+            if !(0.0..=100.0).contains(&rate) {
+                return Err(format!("Alíquota de {} fora da faixa: {}", name, rate));
+            }
+        }
+        if self.st_mva < 0.0 {
+            return Err(format!("MVA inválida: {}", self.st_mva));
+        }
+
+        if matches!(self.icms_code.as_str(), "00" | "10" | "20") && self.icms_rate <= 0.0 {
+            return Err(format!("CST {} exige alíquota de ICMS", self.icms_code));
+        }
+        if self.icms_code == "20" && self.icms_base_reduction <= 0.0 {
+            return Err("CST 20 exige percentual de redução da base".to_string());
+        }
+        if self.has_st() && self.st_rate <= 0.0 {
+            return Err(format!(
+                "{} {} exige alíquota de ICMS ST",
+                if crt == CRT_NORMAL { "CST" } else { "CSOSN" },
+                self.icms_code
+            ));
+        }
+
+        for (name, cst, rate) in [
+            ("PIS", &self.pis_cst, self.pis_rate),
+            ("COFINS", &self.cofins_cst, self.cofins_rate),
+        ] {
+            match contribution_group(cst) {
+                Some(ContributionGroup::Aliquot) if rate <= 0.0 => {
+                    return Err(format!("CST {} de {} exige alíquota", cst, name));
+                }
+                None => return Err(format!("CST de {} inválido: {}", name, cst)),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&TaxRule> for TaxProfile {
+    fn from(rule: &TaxRule) -> Self {
+        Self {
+            icms_origin: rule.icms_origin as u8,
+            icms_code: rule.icms_code.clone(),
+            icms_rate: rule.icms_rate,
+            icms_base_reduction: rule.icms_base_reduction,
+            st_mva: rule.st_mva,
+            st_rate: rule.st_rate,
+            fcp_rate: rule.fcp_rate,
+            pis_cst: rule.pis_cst.clone(),
+            pis_rate: rule.pis_rate,
+            cofins_cst: rule.cofins_cst.clone(),
+            cofins_rate: rule.cofins_rate,
+        }
+    }
+}
+
+impl IbptRates {
+    /// Usa a alíquota federal de importados para origens estrangeiras (1, 2, 3 e 8)
+    pub fn from_rate(rate: &IbptRate, icms_origin: u8) -> Self {
+        let federal = if matches!(icms_origin, 1 | 2 | 3 | 8) {
+            rate.import_rate
+        } else {
+            rate.federal_rate
+        };
+        Self {
+            federal,
+            state: rate.state_rate,
+            municipal: rate.municipal_rate,
+            source: rate.source.clone().unwrap_or_else(|| "IBPT".to_string()),
+        }
+    }
+}
+
+impl ItemTaxes {
+    /// vTotTrib do item
+    pub fn total_approx(&self) -> f64 {
+        round2(self.federal_tax + self.state_tax + self.municipal_tax)
+    }
+}
+
+/// Grupo de PIS/COFINS conforme o CST
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContributionGroup {
+    Aliquot,  // PISAliq / COFINSAliq
+    NotTaxed, // PISNT / COFINSNT
+    Other,    // PISOutr / COFINSOutr
+}
+
+pub fn contribution_group(cst: &str) -> Option<ContributionGroup> {
+    match cst {
+        "01" | "02" => Some(ContributionGroup::Aliquot),
+        "04" | "05" | "06" | "07" | "08" | "09" => Some(ContributionGroup::NotTaxed),
+        _ => match cst.parse::<u8>() {
+            Ok(49..=99) if cst.len() == 2 => Some(ContributionGroup::Other),
+            _ => None,
+        },
+    }
+}
+
+/// Calcula os tributos do item sobre `value` (vProd - vDesc)
+pub fn compute_item_taxes(value: f64, profile: &TaxProfile, ibpt: Option<&IbptRates>) -> ItemTaxes {
+    let mut taxes = ItemTaxes::default();
+
+    match profile.icms_code.as_str() {
+        "00" | "10" => {
+            taxes.icms_base = round2(value);
+        }
+        "20" => {
+            taxes.icms_base = round2(value * (1.0 - profile.icms_base_reduction / 100.0));
+        }
+        _ => {}
+    }
+    if taxes.icms_base > 0.0 {
+        taxes.icms_value = round2(taxes.icms_base * profile.icms_rate / 100.0);
+        taxes.fcp_value = round2(taxes.icms_base * profile.fcp_rate / 100.0);
+    }
+
+    if profile.has_st() {
+        // ICMS próprio a deduzir: destacado (CST 10) ou apenas calculado (CSOSN 201/202)
+        let own_icms = round2(value * profile.icms_rate / 100.0);
+        taxes.st_base = round2(value * (1.0 + profile.st_mva / 100.0));
+        taxes.st_value = round2((taxes.st_base * profile.st_rate / 100.0 - own_icms).max(0.0));
+        taxes.fcp_st_value = round2(taxes.st_base * profile.fcp_rate / 100.0);
+    }
+
+    if contribution_group(&profile.pis_cst) != Some(ContributionGroup::NotTaxed) {
+        taxes.pis_base = round2(value);
+        taxes.pis_value = round2(value * profile.pis_rate / 100.0);
+    }
+    if contribution_group(&profile.cofins_cst) != Some(ContributionGroup::NotTaxed) {
+        taxes.cofins_base = round2(value);
+        taxes.cofins_value = round2(value * profile.cofins_rate / 100.0);
+    }
+
+    if let Some(rates) = ibpt {
+        taxes.federal_tax = round2(value * rates.federal / 100.0);
+        taxes.state_tax = round2(value * rates.state / 100.0);
+        taxes.municipal_tax = round2(value * rates.municipal / 100.0);
+    }
+
+    taxes
+}
+
+/// Lê a tabela IBPT (CSV `;` oficial). Apenas linhas de NCM (tipo 0) sem exceção
+pub fn parse_ibpt_csv(content: &str) -> Result<Vec<IbptRate>, String> {
+    let mut lines = content.lines();
+    let header = lines
+        .next()
+        .ok_or_else(|| "Arquivo IBPT vazio".to_string())?
+        .trim_start_matches('\u{feff}')
+        .to_lowercase();
+    let columns: Vec<&str> = header.split(';').map(|c| c.trim()).collect();
+    let index_of = |name: &str| {
+        columns
+            .iter()
+            .position(|c| *c == name)
+            .ok_or_else(|| format!("Coluna '{}' não encontrada no arquivo IBPT", name))
+    };
+
+    let codigo = index_of("codigo")?;
+    let ex = index_of("ex")?;
+    let tipo = index_of("tipo")?;
+    let descricao = index_of("descricao")?;
+    let federal = index_of("nacionalfederal")?;
+    let import = index_of("importadosfederal")?;
+    let state = index_of("estadual")?;
+    let municipal = index_of("municipal")?;
+    let valid_until = columns.iter().position(|c| *c == "vigenciafim");
+    let version = columns.iter().position(|c| *c == "versao");
+    let source = columns.iter().position(|c| *c == "fonte");
+
+    let mut rates = Vec::new();
+    for (line_no, line) in lines.enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line
+            .split(';')
+            .map(|f| f.trim().trim_matches('"'))
+            .collect();
+        let field = |idx: usize| fields.get(idx).copied().unwrap_or("");
+        if field(tipo) != "0" || !field(ex).is_empty() {
+            continue;
+        }
+
+        let parse_rate = |idx: usize| {
+            let raw = field(idx).replace(',', ".");
+            raw.parse::<f64>()
+                .map_err(|_| format!("Linha {}: alíquota inválida '{}'", line_no + 2, raw))
+        };
+        let optional = |idx: Option<usize>| {
+            idx.map(|i| field(i).to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        rates.push(IbptRate {
+            ncm: field(codigo).to_string(),
+            description: Some(field(descricao).to_string()).filter(|d| !d.is_empty()),
+            federal_rate: parse_rate(federal)?,
+            import_rate: parse_rate(import)?,
+            state_rate: parse_rate(state)?,
+            municipal_rate: parse_rate(municipal)?,
+            valid_until: optional(valid_until),
+            version: optional(version),
+            source: optional(source),
+        });
+    }
+
+    Ok(rates)
+}
+
+/// Mensagem "Trib aprox" para o infCpl / DANFE
+pub fn transparency_message(federal: f64, state: f64, municipal: f64, source: &str) -> String {
+    format!(
+        "Trib aprox R$ {:.2} Federal, R$ {:.2} Estadual e R$ {:.2} Municipal. Fonte: {}",
+        federal, state, municipal, source
+    )
+}
+
+/// Arredonda a centavos (meio centavo para cima, imune a erro de ponto flutuante)
+pub fn round2(value: f64) -> f64 {
+    let cents = value * 100.0;
+    (cents + cents.signum() * 1e-6).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normal_profile(cst: &str) -> TaxProfile {
+        TaxProfile {
+            icms_origin: 0,
+            icms_code: cst.to_string(),
+            icms_rate: 18.0,
+            icms_base_reduction: 0.0,
+            st_mva: 0.0,
+            st_rate: 0.0,
+            fcp_rate: 2.0,
+            pis_cst: "01".to_string(),
+            pis_rate: 1.65,
+            cofins_cst: "01".to_string(),
+            cofins_rate: 7.6,
+        }
+    }
+
+    #[test]
+    fn test_cst_00_taxes() {
+        let taxes = compute_item_taxes(100.0, &normal_profile("00"), None);
+
+        assert_eq!(taxes.icms_base, 100.0);
+        assert_eq!(taxes.icms_value, 18.0);
+        assert_eq!(taxes.fcp_value, 2.0);
+        assert_eq!(taxes.pis_value, 1.65);
+        assert_eq!(taxes.cofins_value, 7.6);
+        assert_eq!(taxes.st_value, 0.0);
+    }
+
+    #[test]
+    fn test_cst_10_substitution() {
+        let mut profile = normal_profile("10");
+        profile.st_mva = 40.0;
+        profile.st_rate = 18.0;
+        profile.fcp_rate = 0.0;
+
+        let taxes = compute_item_taxes(100.0, &profile, None);
+
+        assert_eq!(taxes.st_base, 140.0);
+        // 140 * 18% - 18 (ICMS próprio)
+        assert_eq!(taxes.st_value, 7.2);
+    }
+
+    #[test]
+    fn test_simples_has_no_icms_base() {
+        let profile = TaxProfile::simples(0, "102", "07", "07");
+        let ibpt = IbptRates {
+            federal: 13.45,
+            state: 18.0,
+            municipal: 0.0,
+            source: "IBPT".to_string(),
+        };
+        let taxes = compute_item_taxes(50.0, &profile, Some(&ibpt));
+
+        assert_eq!(taxes.icms_base, 0.0);
+        assert_eq!(taxes.pis_value, 0.0);
+        assert_eq!(taxes.federal_tax, 6.73);
+        assert_eq!(taxes.total_approx(), 15.73);
+        assert!(profile.validate(CRT_SIMPLES).is_ok());
+    }
+
+    #[test]
+    fn test_validate_regime_codes() {
+        assert!(TaxProfile::simples(0, "00", "07", "07")
+            .validate(CRT_SIMPLES)
+            .is_err());
+        assert!(normal_profile("00").validate(CRT_NORMAL).is_ok());
+        assert!(normal_profile("102").validate(CRT_NORMAL).is_err());
+
+        let mut missing_st = normal_profile("10");
+        missing_st.st_rate = 0.0;
+        assert!(missing_st.validate(CRT_NORMAL).is_err());
+
+        let mut invalid_pis = normal_profile("00");
+        invalid_pis.pis_cst = "03".to_string();
+        assert!(invalid_pis.validate(CRT_NORMAL).is_err());
+    }
+
+    #[test]
+    fn test_parse_ibpt_csv() {
+        let csv = "codigo;ex;tipo;descricao;nacionalfederal;importadosfederal;estadual;municipal;vigenciainicio;vigenciafim;chave;versao;fonte\n\
+22021000;;0;Refrigerantes;13.45;15.45;18.00;0.00;01/01/2026;30/06/2026;ABC;26.1.A;IBPTax\n\
+22021000;01;0;Refrigerantes ex;10.00;10.00;10.00;0.00;01/01/2026;30/06/2026;ABC;26.1.A;IBPTax\n\
+01.01;;1;Servico;5.00;5.00;0.00;2.00;01/01/2026;30/06/2026;ABC;26.1.A;IBPTax\n";
+
+        let rates = parse_ibpt_csv(csv).unwrap();
+
+        assert_eq!(rates.len(), 1);
+        assert_eq!(rates[0].ncm, "22021000");
+        assert_eq!(rates[0].federal_rate, 13.45);
+        assert_eq!(rates[0].state_rate, 18.0);
+        assert_eq!(rates[0].version.as_deref(), Some("26.1.A"));
+    }
+}
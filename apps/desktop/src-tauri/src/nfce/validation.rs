@@ -0,0 +1,601 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// VALIDAÇÃO PRÉVIA - Esquema e regras de negócio
+// ═══════════════════════════════════════════════════════════════════════════
+//! Confere o XML gerado por `NfceXmlBuilder::build` antes da assinatura, para
+//! que erros que a SEFAZ rejeitaria sejam mostrados ao operador sem consumir
+//! uma transmissão.
+//!
+//! Duas camadas, ambas reportadas juntas:
+//! - **SCHEMA**: estrutura e tipos do leiaute 4.00 (`schema`)
+//! - **RULE**: regras de validação da SEFAZ que o XSD não expressa — totais
+//!   iguais à soma dos itens, dígito do GTIN, NCM, dígitos de CPF/CNPJ e chave
+
+use roxmltree::{Document, Node};
+use serde::Serialize;
+
+use crate::nfce::schema;
+use crate::nfce::AccessKey;
+
+/// Problema encontrado no XML (`path` no formato NFe/infNFe/det[2]/prod/cEAN)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub source: String, // SCHEMA | RULE
+    pub path: String,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn schema(path: &str, message: String) -> Self {
+        Self {
+            source: "SCHEMA".to_string(),
+            path: path.to_string(),
+            message,
+        }
+    }
+
+    pub fn rule(path: &str, message: String) -> Self {
+        Self {
+            source: "RULE".to_string(),
+            path: path.to_string(),
+            message,
+        }
+    }
+}
+
+/// Valida o XML (não assinado) da NF-e/NFC-e; lista vazia = apto para assinar
+pub fn validate_nfe_xml(xml: &str) -> Vec<ValidationIssue> {
+    let doc = match Document::parse(xml) {
+        Ok(doc) => doc,
+        Err(e) => {
+            return vec![ValidationIssue::schema(
+                "NFe",
+                format!("XML malformado: {}", e),
+            )]
+        }
+    };
+
+    let mut issues = schema::validate(&doc);
+    if let Some(inf_nfe) = doc
+        .root_element()
+        .children()
+        .find(|n| n.has_tag_name("infNFe"))
+    {
+        issues.extend(business_rules(inf_nfe));
+    }
+    issues
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// REGRAS DE NEGÓCIO
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn business_rules(inf_nfe: Node) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let model = path_text(inf_nfe, &["ide", "mod"]).unwrap_or_default();
+
+    check_access_key(inf_nfe, &mut issues);
+
+    for (tag, path) in [
+        ("emit", "NFe/infNFe/emit"),
+        ("dest", "NFe/infNFe/dest"),
+        ("transp", "NFe/infNFe/transp/transporta"),
+    ] {
+        let Some(group) = child(inf_nfe, tag) else {
+            continue;
+        };
+        let group = if tag == "transp" {
+            match child(group, "transporta") {
+                Some(carrier) => carrier,
+                None => continue,
+            }
+        } else {
+            group
+        };
+        check_document(group, path, &mut issues);
+    }
+
+    let items: Vec<Node> = inf_nfe
+        .children()
+        .filter(|n| n.has_tag_name("det"))
+        .collect();
+    let mut sums = ItemSums::default();
+    for (idx, det) in items.iter().enumerate() {
+        let path = if items.len() > 1 {
+            format!("NFe/infNFe/det[{}]", idx + 1)
+        } else {
+            "NFe/infNFe/det".to_string()
+        };
+        check_item(*det, &path, &model, &mut sums, &mut issues);
+    }
+
+    if let Some(totals) = child(inf_nfe, "total").and_then(|t| child(t, "ICMSTot")) {
+        check_totals(totals, &sums, &mut issues);
+        if let Some(pag) = child(inf_nfe, "pag") {
+            check_payments(pag, number(totals, "vNF"), &model, &mut issues);
+        }
+    }
+
+    issues
+}
+
+/// Id = NFe + chave; DV, cNF, cDV, modelo, série e número coerentes com a chave
+fn check_access_key(inf_nfe: Node, issues: &mut Vec<ValidationIssue>) {
+    let path = "NFe/infNFe";
+    let Some(key) = inf_nfe
+        .attribute("Id")
+        .and_then(|id| id.strip_prefix("NFe"))
+    else {
+        return;
+    };
+    if key.len() != 44 || !key.chars().all(|c| c.is_ascii_digit()) {
+        return; // já reportado pelo esquema
+    }
+    if !AccessKey::validate(key) {
+        issues.push(ValidationIssue::rule(
+            path,
+            "Dígito verificador da chave de acesso inválido".to_string(),
+        ));
+    }
+
+    let Some(ide) = child(inf_nfe, "ide") else {
+        return;
+    };
+    let expected = [
+        ("cUF", &key[0..2], 0),
+        ("mod", &key[20..22], 0),
+        ("serie", &key[22..25], 3),
+        ("nNF", &key[25..34], 9),
+        ("tpEmis", &key[34..35], 0),
+        ("cNF", &key[35..43], 0),
+        ("cDV", &key[43..44], 0),
+    ];
+    for (tag, part, width) in expected {
+        let Some(value) = text(ide, tag) else {
+            continue;
+        };
+        let value = if width > 0 {
+            format!("{:0>width$}", value, width = width)
+        } else {
+            value.to_string()
+        };
+        if value != part {
+            issues.push(ValidationIssue::rule(
+                &format!("NFe/infNFe/ide/{}", tag),
+                format!("{} difere do informado na chave de acesso ({})", tag, part),
+            ));
+        }
+    }
+    if let Some(cnpj) = path_text(inf_nfe, &["emit", "CNPJ"]) {
+        if cnpj != key[6..20] {
+            issues.push(ValidationIssue::rule(
+                "NFe/infNFe/emit/CNPJ",
+                "CNPJ do emitente difere do informado na chave de acesso".to_string(),
+            ));
+        }
+    }
+}
+
+fn check_document(group: Node, path: &str, issues: &mut Vec<ValidationIssue>) {
+    if let Some(cnpj) = text(group, "CNPJ") {
+        if !is_valid_cnpj(cnpj) {
+            issues.push(ValidationIssue::rule(
+                &format!("{}/CNPJ", path),
+                format!("CNPJ {} com dígitos verificadores inválidos", cnpj),
+            ));
+        }
+    }
+    if let Some(cpf) = text(group, "CPF") {
+        if !is_valid_cpf(cpf) {
+            issues.push(ValidationIssue::rule(
+                &format!("{}/CPF", path),
+                format!("CPF {} com dígitos verificadores inválidos", cpf),
+            ));
+        }
+    }
+}
+
+/// Somatórios dos itens que devem bater com o grupo ICMSTot
+#[derive(Debug, Default)]
+struct ItemSums {
+    products: f64,
+    freight: f64,
+    discount: f64,
+    icms_base: f64,
+    icms: f64,
+    st: f64,
+    pis: f64,
+    cofins: f64,
+}
+
+fn check_item(
+    det: Node,
+    path: &str,
+    model: &str,
+    sums: &mut ItemSums,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if let Some(prod) = child(det, "prod") {
+        for tag in ["cEAN", "cEANTrib"] {
+            if let Some(gtin) = text(prod, tag).filter(|g| *g != "SEM GTIN") {
+                if !is_valid_gtin(gtin) {
+                    issues.push(ValidationIssue::rule(
+                        &format!("{}/prod/{}", path, tag),
+                        format!("GTIN {} com dígito verificador inválido", gtin),
+                    ));
+                }
+            }
+        }
+
+        if let Some(ncm) = text(prod, "NCM") {
+            if ncm.len() != 8 || !ncm.chars().all(|c| c.is_ascii_digit()) {
+                issues.push(ValidationIssue::rule(
+                    &format!("{}/prod/NCM", path),
+                    format!("NCM {} deve ter 8 dígitos", ncm),
+                ));
+            } else if model == "65" && ncm == "00000000" {
+                issues.push(ValidationIssue::rule(
+                    &format!("{}/prod/NCM", path),
+                    "NCM 00000000 não é aceito em mercadorias".to_string(),
+                ));
+            }
+        }
+
+        // vProd = qCom x vUnCom (tolerância de 1 centavo)
+        let quantity = number(prod, "qCom");
+        let unit_value = number(prod, "vUnCom");
+        let value = number(prod, "vProd");
+        if (cents(quantity * unit_value) - cents(value)).abs() > 1 {
+            issues.push(ValidationIssue::rule(
+                &format!("{}/prod/vProd", path),
+                format!(
+                    "vProd {:.2} difere de qCom x vUnCom ({:.2})",
+                    value,
+                    quantity * unit_value
+                ),
+            ));
+        }
+
+        if text(prod, "indTot") == Some("1") {
+            sums.products += value;
+        }
+        sums.freight += number(prod, "vFrete");
+        sums.discount += number(prod, "vDesc");
+    }
+
+    if let Some(imposto) = child(det, "imposto") {
+        if let Some(icms) = child(imposto, "ICMS").and_then(first_element) {
+            sums.icms_base += number(icms, "vBC");
+            sums.icms += number(icms, "vICMS");
+            sums.st += number(icms, "vICMSST");
+        }
+        if let Some(pis) = child(imposto, "PIS").and_then(first_element) {
+            sums.pis += number(pis, "vPIS");
+        }
+        if let Some(cofins) = child(imposto, "COFINS").and_then(first_element) {
+            sums.cofins += number(cofins, "vCOFINS");
+        }
+    }
+}
+
+fn check_totals(totals: Node, sums: &ItemSums, issues: &mut Vec<ValidationIssue>) {
+    for (tag, sum) in [
+        ("vProd", sums.products),
+        ("vFrete", sums.freight),
+        ("vDesc", sums.discount),
+        ("vBC", sums.icms_base),
+        ("vICMS", sums.icms),
+        ("vST", sums.st),
+        ("vPIS", sums.pis),
+        ("vCOFINS", sums.cofins),
+    ] {
+        let total = number(totals, tag);
+        if cents(total) != cents(sum) {
+            issues.push(ValidationIssue::rule(
+                &format!("NFe/infNFe/total/ICMSTot/{}", tag),
+                format!(
+                    "{} total {:.2} difere da soma dos itens ({:.2})",
+                    tag, total, sum
+                ),
+            ));
+        }
+    }
+
+    // vNF = vProd - vDesc - vICMSDeson + vST + vFCPST + vFrete + vSeg + vOutro + vII + vIPI + vIPIDevol
+    let expected = number(totals, "vProd") - number(totals, "vDesc") - number(totals, "vICMSDeson")
+        + number(totals, "vST")
+        + number(totals, "vFCPST")
+        + number(totals, "vFrete")
+        + number(totals, "vSeg")
+        + number(totals, "vOutro")
+        + number(totals, "vII")
+        + number(totals, "vIPI")
+        + number(totals, "vIPIDevol");
+    let note = number(totals, "vNF");
+    if cents(note) != cents(expected) {
+        issues.push(ValidationIssue::rule(
+            "NFe/infNFe/total/ICMSTot/vNF",
+            format!(
+                "vNF {:.2} difere do calculado pelos totais ({:.2})",
+                note, expected
+            ),
+        ));
+    }
+}
+
+/// Pagamentos cobrem a nota e o troco é exatamente o excedente
+fn check_payments(pag: Node, note: f64, model: &str, issues: &mut Vec<ValidationIssue>) {
+    let paid: f64 = pag
+        .children()
+        .filter(|n| n.has_tag_name("detPag"))
+        .map(|p| number(p, "vPag"))
+        .sum();
+    let change = number(pag, "vTroco");
+
+    // tPag 90 (sem pagamento) é permitido com valor zero na NF-e
+    let without_payment = pag
+        .children()
+        .filter(|n| n.has_tag_name("detPag"))
+        .all(|p| text(p, "tPag") == Some("90"));
+    if without_payment && model == "55" {
+        return;
+    }
+
+    if cents(paid) < cents(note) {
+        issues.push(ValidationIssue::rule(
+            "NFe/infNFe/pag",
+            format!(
+                "Soma dos pagamentos ({:.2}) menor que o valor da nota ({:.2})",
+                paid, note
+            ),
+        ));
+    } else if cents(paid - note) != cents(change) {
+        issues.push(ValidationIssue::rule(
+            "NFe/infNFe/pag/vTroco",
+            format!(
+                "Troco {:.2} difere do excedente pago ({:.2})",
+                change,
+                paid - note
+            ),
+        ));
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// DÍGITOS VERIFICADORES
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// GTIN-8/12/13/14: módulo 10 com pesos 3 e 1 da direita para a esquerda
+pub fn is_valid_gtin(gtin: &str) -> bool {
+    if !matches!(gtin.len(), 8 | 12 | 13 | 14) || !gtin.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    let digits: Vec<u32> = gtin.chars().filter_map(|c| c.to_digit(10)).collect();
+    let (body, check) = digits.split_at(digits.len() - 1);
+    let sum: u32 = body
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| if i % 2 == 0 { d * 3 } else { *d })
+        .sum();
+    (10 - sum % 10) % 10 == check[0]
+}
+
+pub fn is_valid_cpf(cpf: &str) -> bool {
+    let digits: Vec<u32> = cpf.chars().filter_map(|c| c.to_digit(10)).collect();
+    if cpf.len() != 11 || digits.len() != 11 || digits.iter().all(|&d| d == digits[0]) {
+        return false;
+    }
+    let check = |len: usize| {
+        let sum: u32 = digits[..len]
+            .iter()
+            .enumerate()
+            .map(|(i, d)| d * (len as u32 + 1 - i as u32))
+            .sum();
+        let rest = (sum * 10) % 11;
+        if rest == 10 {
+            0
+        } else {
+            rest
+        }
+    };
+    check(9) == digits[9] && check(10) == digits[10]
+}
+
+pub fn is_valid_cnpj(cnpj: &str) -> bool {
+    let digits: Vec<u32> = cnpj.chars().filter_map(|c| c.to_digit(10)).collect();
+    if cnpj.len() != 14 || digits.len() != 14 || digits.iter().all(|&d| d == digits[0]) {
+        return false;
+    }
+    let check = |len: usize| {
+        let weights = [6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+        let offset = 13 - len;
+        let sum: u32 = digits[..len]
+            .iter()
+            .zip(&weights[offset..])
+            .map(|(d, w)| d * w)
+            .sum();
+        let rest = sum % 11;
+        if rest < 2 {
+            0
+        } else {
+            11 - rest
+        }
+    };
+    check(12) == digits[12] && check(13) == digits[13]
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// AUXILIARES
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+fn first_element<'a, 'input>(node: Node<'a, 'input>) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.is_element())
+}
+
+fn text<'a>(node: Node<'a, '_>, tag: &str) -> Option<&'a str> {
+    child(node, tag).and_then(|n| n.text()).map(str::trim)
+}
+
+fn path_text(node: Node, tags: &[&str]) -> Option<String> {
+    let mut current = node;
+    for tag in tags {
+        current = child(current, tag)?;
+    }
+    current.text().map(|t| t.trim().to_string())
+}
+
+fn number(node: Node, tag: &str) -> f64 {
+    text(node, tag)
+        .and_then(|t| t.parse::<f64>().ok())
+        .unwrap_or(0.0)
+}
+
+fn cents(value: f64) -> i64 {
+    (value * 100.0).round() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nfce::tax::TaxProfile;
+    use crate::nfce::xml_builder::{NfceData, NfceItem, NfcePayment, NfceXmlBuilder};
+    use chrono::{TimeZone, Utc};
+
+    fn create_data() -> NfceData {
+        let item = |number: u16, ean: &str, value: f64| NfceItem {
+            number,
+            code: format!("00{}", number),
+            ean: Some(ean.to_string()),
+            description: "CORRENTE 428H".to_string(),
+            ncm: "87149990".to_string(),
+            cfop: "5102".to_string(),
+            unit: "UN".to_string(),
+            quantity: 2.0,
+            unit_value: value / 2.0,
+            total_value: value,
+            tax: TaxProfile::simples(0, "102", "07", "07"),
+            ibpt: None,
+        };
+        NfceData {
+            model: 65,
+            nature_of_operation: "VENDA".to_string(),
+            uf: "SP".to_string(),
+            cnpj: "11222333000181".to_string(),
+            serie: 1,
+            numero: 15,
+            emission_date: Utc.with_ymd_and_hms(2026, 1, 20, 13, 0, 0).unwrap(),
+            emission_type: 1,
+            environment: 2,
+            crt: 1,
+            emitter_name: "MOTO PECAS TESTE LTDA".to_string(),
+            emitter_trade_name: None,
+            emitter_ie: "123456789012".to_string(),
+            emitter_address: "RUA DAS OFICINAS".to_string(),
+            emitter_district: Some("CENTRO".to_string()),
+            emitter_city: "SAO PAULO".to_string(),
+            emitter_city_code: "3550308".to_string(),
+            emitter_state: "SP".to_string(),
+            emitter_cep: "01001000".to_string(),
+            recipient_cpf: Some("52998224725".to_string()),
+            recipient_name: Some("CONSUMIDOR TESTE".to_string()),
+            recipient: None,
+            freight: None,
+            additional_info: None,
+            operation_type: 1,
+            purpose: 1,
+            referenced_keys: Vec::new(),
+            items: vec![item(1, "7891234567895", 30.0), item(2, "78912342", 20.0)],
+            total_products: 50.0,
+            total_discount: 5.0,
+            total_note: 45.0,
+            payments: vec![NfcePayment {
+                method: "01".to_string(),
+                value: 50.0,
+                card: None,
+            }],
+            csc_id: "1".to_string(),
+            csc: "123456".to_string(),
+        }
+    }
+
+    fn access_key(data: &NfceData) -> String {
+        AccessKey::generate(
+            &data.uf,
+            data.emission_date.naive_utc(),
+            &data.cnpj,
+            data.model,
+            data.serie,
+            data.numero,
+            data.emission_type,
+        )
+        .unwrap()
+        .key
+    }
+
+    fn validate(data: NfceData) -> Vec<ValidationIssue> {
+        let key = access_key(&data);
+        let xml = NfceXmlBuilder::new(data, key).build().unwrap();
+        validate_nfe_xml(&xml)
+    }
+
+    #[test]
+    fn test_builder_output_is_valid() {
+        let issues = validate(create_data());
+        assert!(issues.is_empty(), "{:#?}", issues);
+    }
+
+    #[test]
+    fn test_business_rules_reported_with_path() {
+        let mut data = create_data();
+        data.items[1].ean = Some("78912347".to_string()); // DV errado
+        data.recipient_cpf = Some("52998224724".to_string());
+        data.total_note = 46.0; // vNF ≠ vProd - vDesc
+
+        let issues = validate(data);
+        let paths: Vec<&str> = issues.iter().map(|i| i.path.as_str()).collect();
+        assert!(paths.contains(&"NFe/infNFe/det[2]/prod/cEAN"));
+        assert!(paths.contains(&"NFe/infNFe/det[2]/prod/cEANTrib"));
+        assert!(paths.contains(&"NFe/infNFe/dest/CPF"));
+        assert!(paths.contains(&"NFe/infNFe/total/ICMSTot/vNF"));
+        assert!(issues.iter().all(|i| i.source == "RULE"));
+    }
+
+    #[test]
+    fn test_schema_errors_from_xml() {
+        let data = create_data();
+        let key = access_key(&data);
+        let xml = NfceXmlBuilder::new(data, key).build().unwrap();
+        let broken = xml
+            .replace("<NCM>87149990</NCM>", "<NCM>8714</NCM>")
+            .replace("<tpAmb>2</tpAmb>", "<tpAmb>3</tpAmb>");
+
+        let issues = validate_nfe_xml(&broken);
+        assert!(issues
+            .iter()
+            .any(|i| i.source == "SCHEMA" && i.path == "NFe/infNFe/ide/tpAmb"));
+        assert!(issues
+            .iter()
+            .any(|i| i.source == "RULE" && i.path == "NFe/infNFe/det[1]/prod/NCM"));
+
+        let malformed = validate_nfe_xml("<NFe><infNFe>");
+        assert_eq!(malformed.len(), 1);
+        assert!(malformed[0].message.contains("malformado"));
+    }
+
+    #[test]
+    fn test_check_digits() {
+        assert!(is_valid_gtin("7891234567895"));
+        assert!(is_valid_gtin("78912342"));
+        assert!(!is_valid_gtin("78912346"));
+        assert!(!is_valid_gtin("7891234567890"));
+        assert!(is_valid_cpf("52998224725"));
+        assert!(!is_valid_cpf("11111111111"));
+        assert!(is_valid_cnpj("11222333000181"));
+        assert!(!is_valid_cnpj("12345678000190"));
+    }
+}
@@ -7,6 +7,7 @@ use openssl::pkcs12::Pkcs12;
 use reqwest::{Client, Identity};
 use roxmltree::Document;
 use std::fs;
+use std::time::Duration;
 
 use crate::nfce::endpoints::{Environment, SefazEndpoints};
 
@@ -15,8 +16,14 @@ pub struct SefazClient {
     client: Client,
     uf: String,
     environment: Environment,
+    model: u8,                // 65=NFC-e (padrão), 55=NF-e
+    base_url: Option<String>, // Substitui os endpoints oficiais (simulador local)
+    timeout: Duration,
 }
 
+/// Tempo máximo de espera por uma resposta da SEFAZ
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone)]
 pub struct AuthorizationResponse {
     pub status_code: String,
@@ -25,10 +32,19 @@ pub struct AuthorizationResponse {
     pub xml: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct EventResponse {
+    pub status_code: String,
+    pub status_message: String,
+    pub protocol: Option<String>,
+    pub xml: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct QueryResponse {
     pub status_code: String,
     pub status_message: String,
+    pub protocol: Option<String>,
     pub xml: Option<String>,
 }
 
@@ -39,7 +55,7 @@ impl SefazClient {
         cert_path: Option<&str>,
         cert_password: Option<&str>,
     ) -> Result<Self, String> {
-        let mut builder = Client::builder().timeout(std::time::Duration::from_secs(30));
+        let mut builder = Client::builder();
 
         if let Some(path) = cert_path {
             let cert_content =
@@ -103,76 +119,171 @@ impl SefazClient {
             client,
             uf,
             environment,
+            model: 65,
+            base_url: None,
+            timeout: DEFAULT_TIMEOUT,
         })
     }
 
-    /// Envia NFC-e para autorização
-    pub async fn authorize(&self, signed_xml: &str) -> Result<AuthorizationResponse, String> {
-        let url = SefazEndpoints::get_url(&self.uf, self.environment)?;
+    /// Cliente sem certificado que envia todos os serviços para `base_url`
+    /// (`{base_url}/NFeAutorizacao4`, ...). Usado com o simulador offline
+    /// da SEFAZ nos testes de integração.
+    pub fn with_base_url(
+        uf: String,
+        environment: Environment,
+        base_url: &str,
+    ) -> Result<Self, String> {
+        let mut client = Self::new(uf, environment, None, None)?;
+        client.base_url = Some(base_url.trim_end_matches('/').to_string());
+        Ok(client)
+    }
 
-        let soap_envelope = self.create_authorization_envelope(signed_xml)?;
+    /// Usa os WebServices do modelo informado (55 = NF-e)
+    pub fn with_model(mut self, model: u8) -> Self {
+        self.model = model;
+        self
+    }
 
-        let response = self
-            .client
-            .post(url)
-            .header("Content-Type", "application/soap+xml; charset=utf-8")
-            .body(soap_envelope)
-            .send()
-            .await
-            .map_err(|e| format!("Erro ao enviar requisição: {}", e))?;
+    /// Altera o tempo máximo de espera por requisição (padrão: 30s)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
 
-        let response_text = response
-            .text()
-            .await
-            .map_err(|e| format!("Erro ao ler resposta: {}", e))?;
+    fn service_url(&self, service: &str) -> Result<String, String> {
+        match &self.base_url {
+            Some(base) => Ok(format!("{}/{}", base, service)),
+            None => {
+                SefazEndpoints::get_service_url(self.model, &self.uf, self.environment, service)
+            }
+        }
+    }
+
+    /// Envia NFC-e (ou NF-e, conforme o modelo) para autorização
+    pub async fn authorize(&self, signed_xml: &str) -> Result<AuthorizationResponse, String> {
+        let url = self.service_url("NFeAutorizacao4")?;
+
+        let soap_envelope = self.create_authorization_envelope(signed_xml)?;
+        let response_text = self.post(url, soap_envelope).await?;
 
         self.parse_authorization_response(&response_text)
     }
 
     /// Consulta status do serviço
     pub async fn check_status(&self) -> Result<QueryResponse, String> {
-        let url = SefazEndpoints::get_url(&self.uf, self.environment)?;
+        let url = self.service_url("NFeStatusServico4")?;
 
         let soap_envelope = self.create_status_envelope()?;
-
-        let response = self
-            .client
-            .post(url)
-            .header("Content-Type", "application/soap+xml; charset=utf-8")
-            .body(soap_envelope)
-            .send()
-            .await
-            .map_err(|e| format!("Erro ao enviar requisição: {}", e))?;
-
-        let response_text = response
-            .text()
-            .await
-            .map_err(|e| format!("Erro ao ler resposta: {}", e))?;
+        let response_text = self.post(url, soap_envelope).await?;
 
         self.parse_status_response(&response_text)
     }
 
     /// Consulta protocolo de autorização
     pub async fn query_protocol(&self, access_key: &str) -> Result<QueryResponse, String> {
-        let url = SefazEndpoints::get_url(&self.uf, self.environment)?;
+        let url = self.service_url("NFeConsultaProtocolo4")?;
 
         let soap_envelope = self.create_query_envelope(access_key)?;
+        let response_text = self.post(url, soap_envelope).await?;
+
+        self.parse_query_response(&response_text)
+    }
+
+    /// Envia evento assinado (envEvento) - ex: cancelamento 110111
+    pub async fn send_event(&self, signed_event_xml: &str) -> Result<EventResponse, String> {
+        let url = self.service_url("NFeRecepcaoEvento4")?;
+        let soap_envelope = self.create_event_envelope(signed_event_xml)?;
+        let response_text = self.post(url, soap_envelope).await?;
+
+        self.parse_event_response(&response_text, "infEvento")
+    }
 
+    /// Envia pedido assinado de inutilização de numeração (inutNFe)
+    pub async fn inutilize(&self, signed_inut_xml: &str) -> Result<EventResponse, String> {
+        let url = self.service_url("NFeInutilizacao4")?;
+        let soap_envelope = self.create_inutilization_envelope(signed_inut_xml)?;
+        let response_text = self.post(url, soap_envelope).await?;
+
+        self.parse_event_response(&response_text, "infInut")
+    }
+
+    async fn post(&self, url: String, soap_envelope: String) -> Result<String, String> {
         let response = self
             .client
             .post(url)
             .header("Content-Type", "application/soap+xml; charset=utf-8")
             .body(soap_envelope)
+            .timeout(self.timeout)
             .send()
             .await
             .map_err(|e| format!("Erro ao enviar requisição: {}", e))?;
 
-        let response_text = response
+        // Erro HTTP (ex: 503 em manutenção) não traz retorno SOAP válido
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("SEFAZ respondeu HTTP {}", status.as_u16()));
+        }
+
+        response
             .text()
             .await
-            .map_err(|e| format!("Erro ao ler resposta: {}", e))?;
+            .map_err(|e| format!("Erro ao ler resposta: {}", e))
+    }
 
-        self.parse_query_response(&response_text)
+    fn create_event_envelope(&self, event_xml: &str) -> Result<String, String> {
+        Ok(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<soap12:Envelope xmlns:soap12="http://www.w3.org/2003/05/soap-envelope" xmlns:nfe="http://www.portalfiscal.inf.br/nfe/wsdl/NFeRecepcaoEvento4">
+  <soap12:Header/>
+  <soap12:Body>
+    <nfe:nfeDadosMsg>{}</nfe:nfeDadosMsg>
+  </soap12:Body>
+</soap12:Envelope>"#,
+            event_xml
+        ))
+    }
+
+    fn create_inutilization_envelope(&self, inut_xml: &str) -> Result<String, String> {
+        Ok(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<soap12:Envelope xmlns:soap12="http://www.w3.org/2003/05/soap-envelope" xmlns:nfe="http://www.portalfiscal.inf.br/nfe/wsdl/NFeInutilizacao4">
+  <soap12:Header/>
+  <soap12:Body>
+    <nfe:nfeDadosMsg>{}</nfe:nfeDadosMsg>
+  </soap12:Body>
+</soap12:Envelope>"#,
+            inut_xml
+        ))
+    }
+
+    /// Lê cStat/xMotivo/nProt do grupo de retorno (`infEvento` ou `infInut`).
+    /// Se o grupo não existir (ex: rejeição do lote), usa o status do lote.
+    fn parse_event_response(&self, xml: &str, group: &str) -> Result<EventResponse, String> {
+        let doc =
+            Document::parse(xml).map_err(|e| format!("Erro ao fazer parse da resposta: {}", e))?;
+
+        let scope = doc
+            .descendants()
+            .find(|n| n.tag_name().name() == group)
+            .unwrap_or_else(|| doc.root_element());
+
+        let text_of = |tag: &str| {
+            scope
+                .descendants()
+                .find(|n| n.tag_name().name() == tag)
+                .map(|n| n.text().unwrap_or("").to_string())
+        };
+
+        let status_code = text_of("cStat").unwrap_or_default();
+        let status_message = text_of("xMotivo").unwrap_or_default();
+        let protocol = text_of("nProt");
+
+        Ok(EventResponse {
+            status_code,
+            status_message,
+            protocol,
+            xml: xml.to_string(),
+        })
     }
 
     fn create_authorization_envelope(&self, nfce_xml: &str) -> Result<String, String> {
@@ -287,6 +398,7 @@ impl SefazClient {
 
         let mut status_code = String::new();
         let mut status_message = String::new();
+        let mut protocol = None;
 
         for node in doc.descendants() {
             match node.tag_name().name() {
@@ -296,6 +408,9 @@ impl SefazClient {
                 "xMotivo" => {
                     status_message = node.text().unwrap_or("").to_string();
                 }
+                "nProt" => {
+                    protocol = Some(node.text().unwrap_or("").to_string());
+                }
                 _ => {}
             }
         }
@@ -303,6 +418,7 @@ impl SefazClient {
         Ok(QueryResponse {
             status_code,
             status_message,
+            protocol,
             xml: Some(xml.to_string()),
         })
     }
@@ -385,4 +501,48 @@ mod tests {
         assert_eq!(response.status_code, "107");
         assert_eq!(response.status_message, "Serviço em Operação");
     }
+
+    #[test]
+    fn test_parse_cancellation_response() {
+        let client = SefazClient::new("SP".to_string(), Environment::Homologation, None, None)
+            .expect("Failed to create SefazClient");
+
+        let xml = r#"<?xml version="1.0"?>
+<retEnvEvento>
+    <cStat>128</cStat>
+    <xMotivo>Lote de Evento Processado</xMotivo>
+    <retEvento versao="1.00">
+        <infEvento>
+            <cStat>135</cStat>
+            <xMotivo>Evento registrado e vinculado a NF-e</xMotivo>
+            <nProt>135260000000002</nProt>
+        </infEvento>
+    </retEvento>
+</retEnvEvento>"#;
+
+        let response = client.parse_event_response(xml, "infEvento").unwrap();
+
+        assert_eq!(response.status_code, "135");
+        assert_eq!(response.protocol, Some("135260000000002".to_string()));
+    }
+
+    #[test]
+    fn test_parse_inutilization_response() {
+        let client = SefazClient::new("SP".to_string(), Environment::Homologation, None, None)
+            .expect("Failed to create SefazClient");
+
+        let xml = r#"<?xml version="1.0"?>
+<retInutNFe versao="4.00">
+    <infInut>
+        <cStat>102</cStat>
+        <xMotivo>Inutilizacao de numero homologado</xMotivo>
+        <nProt>135260000000003</nProt>
+    </infInut>
+</retInutNFe>"#;
+
+        let response = client.parse_event_response(xml, "infInut").unwrap();
+
+        assert_eq!(response.status_code, "102");
+        assert_eq!(response.protocol, Some("135260000000003".to_string()));
+    }
 }
@@ -2,28 +2,43 @@
 // CONSTRUTOR DE XML NFC-e
 // ═══════════════════════════════════════════════════════════════════════════
 //! Geração de XML conforme layout SEFAZ 4.00 (NT 2019.001)
+//!
+//! O mesmo construtor atende a NFC-e (modelo 65) e a NF-e (modelo 55). Na
+//! NF-e o grupo `dest` é completo (endereço, IE) e o transporte é informado.
 
 use chrono::{DateTime, Utc};
 use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
 use std::io::Cursor;
 
+use crate::nfce::tax::{
+    compute_item_taxes, contribution_group, transparency_message, ContributionGroup, IbptRates,
+    ItemTaxes, TaxProfile,
+};
+
 #[derive(Debug, Clone)]
 pub struct NfceData {
     // Identificação
+    pub model: u8, // 65=NFC-e, 55=NF-e
+    pub nature_of_operation: String,
     pub uf: String,
     pub cnpj: String,
     pub serie: u16,
     pub numero: u32,
     pub emission_date: DateTime<Utc>,
-    pub emission_type: u8, // 1=Normal, 9=Contingência
-    pub environment: u8,   // 1=Produção, 2=Homologação
+    pub emission_type: u8,            // 1=Normal, 9=Contingência
+    pub environment: u8,              // 1=Produção, 2=Homologação
+    pub operation_type: u8,           // tpNF: 0=Entrada, 1=Saída
+    pub purpose: u8,                  // finNFe: 1=Normal, 4=Devolução
+    pub referenced_keys: Vec<String>, // NFref/refNFe (obrigatório na devolução)
 
     // Emitente
+    pub crt: u8, // 1=Simples Nacional, 3=Regime Normal
     pub emitter_name: String,
     pub emitter_trade_name: Option<String>,
     pub emitter_ie: String,
     pub emitter_address: String,
+    pub emitter_district: Option<String>,
     pub emitter_city: String,
     pub emitter_city_code: String,
     pub emitter_state: String,
@@ -33,6 +48,15 @@ pub struct NfceData {
     pub recipient_cpf: Option<String>,
     pub recipient_name: Option<String>,
 
+    // Destinatário completo (obrigatório na NF-e)
+    pub recipient: Option<NfeRecipient>,
+
+    // Transporte (NF-e); ausente = sem frete
+    pub freight: Option<NfeFreight>,
+
+    // Informações complementares (infCpl), ex: referência à OS
+    pub additional_info: Option<String>,
+
     // Itens
     pub items: Vec<NfceItem>,
 
@@ -41,9 +65,8 @@ pub struct NfceData {
     pub total_discount: f64,
     pub total_note: f64,
 
-    // Pagamento
-    pub payment_method: String, // 01=Dinheiro, 03=Cartão Crédito, etc
-    pub payment_value: f64,
+    // Pagamento (um detPag por forma de pagamento)
+    pub payments: Vec<NfcePayment>,
 
     // CSC (Código de Segurança do Contribuinte)
     pub csc_id: String,
@@ -63,13 +86,92 @@ pub struct NfceItem {
     pub unit_value: f64,
     pub total_value: f64,
 
-    // ICMS
-    pub icms_origin: u8,
-    pub icms_cst: String,
+    // Tributação (ICMS/PIS/COFINS) e alíquotas IBPT para o vTotTrib
+    pub tax: TaxProfile,
+    pub ibpt: Option<IbptRates>,
+}
+
+impl NfceItem {
+    pub fn taxes(&self) -> ItemTaxes {
+        compute_item_taxes(self.total_value, &self.tax, self.ibpt.as_ref())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NfcePayment {
+    pub method: String, // tPag: 01=Dinheiro, 03=Cartão Crédito, 04=Débito, 17=PIX, etc
+    pub value: f64,
+    pub card: Option<NfceCardInfo>,
+}
+
+/// Grupo `card` do detPag (obrigatório para tPag 03/04)
+#[derive(Debug, Clone)]
+pub struct NfceCardInfo {
+    pub integration_type: u8, // tpIntegra: 1=Integrado (TEF), 2=Não integrado (POS)
+    pub acquirer_cnpj: Option<String>,
+    pub brand: Option<String>, // tBand: 01=Visa, 02=Mastercard, 03=Amex, ...
+    pub authorization_code: Option<String>,
+}
+
+/// Destinatário da NF-e (grupo `dest`)
+#[derive(Debug, Clone)]
+pub struct NfeRecipient {
+    pub document: String, // CNPJ (14 dígitos) ou CPF (11 dígitos)
+    pub name: String,
+    pub state_registration: Option<String>, // IE ou "ISENTO"
+    pub email: Option<String>,
+    pub address: NfeAddress,
+}
+
+impl NfeRecipient {
+    /// indIEDest: 1=Contribuinte ICMS, 2=Contribuinte isento, 9=Não contribuinte
+    pub fn ie_indicator(&self) -> u8 {
+        match self.state_registration.as_deref().map(str::trim) {
+            Some(ie) if ie.eq_ignore_ascii_case("ISENTO") => 2,
+            Some(ie) if !ie.is_empty() => 1,
+            _ => 9,
+        }
+    }
+}
 
-    // PIS/COFINS
-    pub pis_cst: String,
-    pub cofins_cst: String,
+#[derive(Debug, Clone)]
+pub struct NfeAddress {
+    pub street: String,
+    pub number: String,
+    pub complement: Option<String>,
+    pub district: String,
+    pub city_code: String, // Código IBGE (7 dígitos)
+    pub city: String,
+    pub uf: String,
+    pub cep: String,
+    pub phone: Option<String>,
+}
+
+/// Grupo `transp` da NF-e
+#[derive(Debug, Clone)]
+pub struct NfeFreight {
+    pub modality: u8, // modFrete: 0=Emitente (CIF), 1=Destinatário (FOB), 2=Terceiros, 9=Sem frete
+    pub value: f64,   // Rateado entre os itens (vFrete)
+    pub carrier: Option<NfeCarrier>,
+    pub volumes: Option<NfeVolumes>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NfeCarrier {
+    pub document: String, // CNPJ ou CPF
+    pub name: String,
+    pub state_registration: Option<String>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub uf: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NfeVolumes {
+    pub quantity: u32,
+    pub species: Option<String>, // Ex: CAIXA, VOLUME
+    pub net_weight: Option<f64>,
+    pub gross_weight: Option<f64>,
 }
 
 pub struct NfceXmlBuilder {
@@ -84,6 +186,8 @@ impl NfceXmlBuilder {
 
     /// Gera XML completo da NFC-e
     pub fn build(&self) -> Result<String, String> {
+        self.validate()?;
+
         let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
 
         // XML Declaration
@@ -110,6 +214,202 @@ impl NfceXmlBuilder {
         String::from_utf8(result).map_err(|e| e.to_string())
     }
 
+    /// Regras de negócio dos itens verificadas antes da montagem/assinatura
+    pub fn validate(&self) -> Result<(), String> {
+        if self.data.items.is_empty() {
+            return Err(format!("{} sem itens", self.document_name()));
+        }
+
+        match self.data.model {
+            65 => {
+                if self.data.recipient.is_some() {
+                    return Err("NFC-e não aceita destinatário com endereço".to_string());
+                }
+                if self.freight_value() > 0.0
+                    || self.data.freight.as_ref().is_some_and(|f| f.modality != 9)
+                {
+                    return Err("NFC-e não admite frete (modFrete deve ser 9)".to_string());
+                }
+            }
+            55 => self.validate_nfe()?,
+            other => return Err(format!("Modelo de documento não suportado: {}", other)),
+        }
+
+        // Devolução referencia a nota original (chave de 44 dígitos)
+        if self.data.purpose == 4 && self.data.referenced_keys.is_empty() {
+            return Err("Nota de devolução exige a chave da nota referenciada".to_string());
+        }
+        for key in &self.data.referenced_keys {
+            if key.len() != 44 || !key.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("Chave referenciada inválida: {}", key));
+            }
+        }
+
+        // Saída: interna 5xxx, interestadual 6xxx; entrada: 1xxx e 2xxx
+        let cfop_prefix = match (self.data.operation_type, self.is_interstate()) {
+            (0, false) => '1',
+            (0, true) => '2',
+            (_, false) => '5',
+            (_, true) => '6',
+        };
+
+        for item in &self.data.items {
+            let prefix = format!("Item {} ({})", item.number, item.description);
+            if item.ncm.len() != 8 || !item.ncm.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("{}: NCM deve ter 8 dígitos", prefix));
+            }
+            if item.cfop.len() != 4 || !item.cfop.starts_with(cfop_prefix) {
+                return Err(format!(
+                    "{}: CFOP {} inválido para operação {} ({}xxx)",
+                    prefix,
+                    item.cfop,
+                    if self.is_interstate() {
+                        "interestadual"
+                    } else {
+                        "interna"
+                    },
+                    cfop_prefix
+                ));
+            }
+            item.tax
+                .validate(self.data.crt)
+                .map_err(|e| format!("{}: {}", prefix, e))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_nfe(&self) -> Result<(), String> {
+        let recipient = self
+            .data
+            .recipient
+            .as_ref()
+            .ok_or("NF-e exige destinatário com endereço completo")?;
+
+        let document_len = recipient.document.len();
+        if !(document_len == 11 || document_len == 14)
+            || !recipient.document.chars().all(|c| c.is_ascii_digit())
+        {
+            return Err("Documento do destinatário deve ser CNPJ (14) ou CPF (11 dígitos)".into());
+        }
+        if recipient.name.trim().is_empty() {
+            return Err("Nome do destinatário não informado".to_string());
+        }
+
+        let address = &recipient.address;
+        if address.street.trim().is_empty() || address.district.trim().is_empty() {
+            return Err("Endereço do destinatário incompleto (logradouro/bairro)".to_string());
+        }
+        if address.city_code.len() != 7 || !address.city_code.chars().all(|c| c.is_ascii_digit()) {
+            return Err("Código IBGE do município do destinatário deve ter 7 dígitos".to_string());
+        }
+        if address.uf.len() != 2 {
+            return Err("UF do destinatário inválida".to_string());
+        }
+
+        // Venda interestadual a consumidor final exige a partilha do ICMS (DIFAL)
+        if self.is_interstate() && self.is_final_consumer() {
+            return Err(
+                "Operação interestadual com consumidor final exige o grupo ICMSUFDest (DIFAL), não suportado"
+                    .to_string(),
+            );
+        }
+
+        if let Some(freight) = &self.data.freight {
+            if !matches!(freight.modality, 0..=4 | 9) {
+                return Err(format!(
+                    "Modalidade de frete inválida: {}",
+                    freight.modality
+                ));
+            }
+            if freight.value < 0.0 {
+                return Err("Valor do frete não pode ser negativo".to_string());
+            }
+            if freight.modality == 9 && freight.value > 0.0 {
+                return Err("Frete informado com modalidade 'sem frete' (9)".to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn document_name(&self) -> &'static str {
+        if self.data.model == 55 {
+            "NF-e"
+        } else {
+            "NFC-e"
+        }
+    }
+
+    /// dhEmi no formato do leiaute (TDateTimeUTC, sem fração de segundo)
+    pub fn emission_timestamp(&self) -> String {
+        self.data
+            .emission_date
+            .format("%Y-%m-%dT%H:%M:%S%:z")
+            .to_string()
+    }
+
+    /// idDest = 2 quando o destinatário está em outra UF
+    pub fn is_interstate(&self) -> bool {
+        self.data
+            .recipient
+            .as_ref()
+            .is_some_and(|r| !r.address.uf.eq_ignore_ascii_case(&self.data.uf))
+    }
+
+    /// indFinal: contribuinte do ICMS (IE informada) compra para revenda/industrialização
+    pub fn is_final_consumer(&self) -> bool {
+        match (&self.data.recipient, self.data.model) {
+            (Some(recipient), 55) => recipient.ie_indicator() != 1,
+            _ => true,
+        }
+    }
+
+    /// Valor total do frete (vFrete)
+    pub fn freight_value(&self) -> f64 {
+        self.data.freight.as_ref().map(|f| f.value).unwrap_or(0.0)
+    }
+
+    /// Rateio do frete entre os itens, proporcional ao valor; o último recebe o resíduo
+    pub fn freight_shares(&self) -> Vec<f64> {
+        self.prorate(self.freight_value())
+    }
+
+    /// Rateio do desconto da nota (vDesc de cada item soma o vDesc total)
+    pub fn discount_shares(&self) -> Vec<f64> {
+        self.prorate(self.data.total_discount)
+    }
+
+    fn prorate(&self, amount: f64) -> Vec<f64> {
+        let items = &self.data.items;
+        let total: f64 = items.iter().map(|i| i.total_value).sum();
+        if amount <= 0.0 || total <= 0.0 {
+            return vec![0.0; items.len()];
+        }
+
+        let mut shares = Vec::with_capacity(items.len());
+        let mut allocated = 0.0;
+        for (idx, item) in items.iter().enumerate() {
+            let share = if idx + 1 == items.len() {
+                ((amount - allocated) * 100.0).round() / 100.0
+            } else {
+                ((amount * item.total_value / total) * 100.0).round() / 100.0
+            };
+            allocated += share;
+            shares.push(share);
+        }
+        shares
+    }
+
+    /// Tributos do item: a base considera a parcela do frete e abate a do desconto
+    fn item_taxes(&self, item: &NfceItem, freight_share: f64, discount_share: f64) -> ItemTaxes {
+        compute_item_taxes(
+            item.total_value + freight_share - discount_share,
+            &item.tax,
+            item.ibpt.as_ref(),
+        )
+    }
+
     fn write_inf_nfe<W: std::io::Write>(&self, writer: &mut Writer<W>) -> Result<(), String> {
         let mut inf_nfe = BytesStart::new("infNFe");
         inf_nfe.push_attribute(("versao", "4.00"));
@@ -126,13 +426,23 @@ impl NfceXmlBuilder {
         self.write_emit(writer)?;
 
         // <dest> - Destinatário (se houver)
-        if self.data.recipient_cpf.is_some() {
+        if let Some(recipient) = &self.data.recipient {
+            self.write_nfe_dest(writer, recipient)?;
+        } else if self.data.recipient_cpf.is_some() {
             self.write_dest(writer)?;
         }
 
         // <det> - Detalhamento (itens)
+        let freight_shares = self.freight_shares();
+        let discount_shares = self.discount_shares();
         for (idx, item) in self.data.items.iter().enumerate() {
-            self.write_det(writer, item, idx + 1)?;
+            self.write_det(
+                writer,
+                item,
+                idx + 1,
+                freight_shares[idx],
+                discount_shares[idx],
+            )?;
         }
 
         // <total> - Totais
@@ -169,15 +479,20 @@ impl NfceXmlBuilder {
         };
 
         self.write_element(writer, "cNF", cnf)?;
-        self.write_element(writer, "natOp", "VENDA")?;
-        self.write_element(writer, "mod", "65")?; // 65 = NFC-e
+        self.write_element(writer, "natOp", &self.data.nature_of_operation)?;
+        self.write_element(writer, "mod", &self.data.model.to_string())?; // 65 = NFC-e, 55 = NF-e
         self.write_element(writer, "serie", &self.data.serie.to_string())?;
         self.write_element(writer, "nNF", &self.data.numero.to_string())?;
-        self.write_element(writer, "dhEmi", &self.data.emission_date.to_rfc3339())?;
-        self.write_element(writer, "tpNF", "1")?; // 1 = Saída
-        self.write_element(writer, "idDest", "1")?; // 1 = Operação interna
+        self.write_element(writer, "dhEmi", &self.emission_timestamp())?;
+        // tpNF: 0 = Entrada (devolução), 1 = Saída
+        self.write_element(writer, "tpNF", &self.data.operation_type.to_string())?;
+        // idDest: 1 = Operação interna, 2 = Interestadual
+        let id_dest = if self.is_interstate() { "2" } else { "1" };
+        self.write_element(writer, "idDest", id_dest)?;
         self.write_element(writer, "cMunFG", &self.data.emitter_city_code)?;
-        self.write_element(writer, "tpImp", "4")?; // 4 = DANFE NFC-e
+        // tpImp: 4 = DANFE NFC-e, 1 = DANFE retrato (A4)
+        let print_type = if self.data.model == 55 { "1" } else { "4" };
+        self.write_element(writer, "tpImp", print_type)?;
         self.write_element(writer, "tpEmis", &self.data.emission_type.to_string())?;
         self.write_element(
             writer,
@@ -185,12 +500,24 @@ impl NfceXmlBuilder {
             &self.access_key.chars().last().unwrap().to_string(),
         )?;
         self.write_element(writer, "tpAmb", &self.data.environment.to_string())?;
-        self.write_element(writer, "finNFe", "1")?; // 1 = NF-e normal
-        self.write_element(writer, "indFinal", "1")?; // 1 = Consumidor final
+        self.write_element(writer, "finNFe", &self.data.purpose.to_string())?; // 1 = Normal, 4 = Devolução
+        let final_consumer = if self.is_final_consumer() { "1" } else { "0" };
+        self.write_element(writer, "indFinal", final_consumer)?; // 1 = Consumidor final
         self.write_element(writer, "indPres", "1")?; // 1 = Operação presencial
         self.write_element(writer, "procEmi", "0")?; // 0 = Emissão com aplicativo próprio
         self.write_element(writer, "verProc", "1.0.0")?;
 
+        // Documentos referenciados (devolução aponta para a nota da venda)
+        for key in &self.data.referenced_keys {
+            writer
+                .write_event(Event::Start(BytesStart::new("NFref")))
+                .map_err(|e| e.to_string())?;
+            self.write_element(writer, "refNFe", key)?;
+            writer
+                .write_event(Event::End(BytesEnd::new("NFref")))
+                .map_err(|e| e.to_string())?;
+        }
+
         writer
             .write_event(Event::End(BytesEnd::new("ide")))
             .map_err(|e| e.to_string())?;
@@ -214,16 +541,24 @@ impl NfceXmlBuilder {
             .map_err(|e| e.to_string())?;
         self.write_element(writer, "xLgr", &self.data.emitter_address)?;
         self.write_element(writer, "nro", "SN")?;
-        self.write_element(writer, "xMun", &self.data.emitter_city)?;
-        self.write_element(writer, "CEP", &self.data.emitter_cep)?;
+        self.write_element(
+            writer,
+            "xBairro",
+            self.data
+                .emitter_district
+                .as_deref()
+                .unwrap_or("NAO INFORMADO"),
+        )?;
         self.write_element(writer, "cMun", &self.data.emitter_city_code)?;
+        self.write_element(writer, "xMun", &self.data.emitter_city)?;
         self.write_element(writer, "UF", &self.data.uf)?;
+        self.write_element(writer, "CEP", &self.data.emitter_cep)?;
         writer
             .write_event(Event::End(BytesEnd::new("enderEmit")))
             .map_err(|e| e.to_string())?;
 
         self.write_element(writer, "IE", &self.data.emitter_ie)?;
-        self.write_element(writer, "CRT", "1")?; // 1 = Simples Nacional
+        self.write_element(writer, "CRT", &self.data.crt.to_string())?;
 
         writer
             .write_event(Event::End(BytesEnd::new("emit")))
@@ -246,11 +581,78 @@ impl NfceXmlBuilder {
         Ok(())
     }
 
+    /// Grupo `dest` completo da NF-e (documento, endereço e indicador de IE)
+    fn write_nfe_dest<W: std::io::Write>(
+        &self,
+        writer: &mut Writer<W>,
+        recipient: &NfeRecipient,
+    ) -> Result<(), String> {
+        writer
+            .write_event(Event::Start(BytesStart::new("dest")))
+            .map_err(|e| e.to_string())?;
+
+        let document_tag = if recipient.document.len() == 14 {
+            "CNPJ"
+        } else {
+            "CPF"
+        };
+        self.write_element(writer, document_tag, &recipient.document)?;
+
+        // Em homologação a SEFAZ exige o nome padrão no destinatário da NF-e
+        let name = if self.data.environment == 2 {
+            "NF-E EMITIDA EM AMBIENTE DE HOMOLOGACAO - SEM VALOR FISCAL"
+        } else {
+            recipient.name.as_str()
+        };
+        self.write_element(writer, "xNome", name)?;
+
+        let address = &recipient.address;
+        writer
+            .write_event(Event::Start(BytesStart::new("enderDest")))
+            .map_err(|e| e.to_string())?;
+        self.write_element(writer, "xLgr", &address.street)?;
+        self.write_element(writer, "nro", &address.number)?;
+        if let Some(complement) = address.complement.as_deref().filter(|c| !c.is_empty()) {
+            self.write_element(writer, "xCpl", complement)?;
+        }
+        self.write_element(writer, "xBairro", &address.district)?;
+        self.write_element(writer, "cMun", &address.city_code)?;
+        self.write_element(writer, "xMun", &address.city)?;
+        self.write_element(writer, "UF", &address.uf)?;
+        self.write_element(writer, "CEP", &address.cep)?;
+        self.write_element(writer, "cPais", "1058")?;
+        self.write_element(writer, "xPais", "BRASIL")?;
+        if let Some(phone) = address.phone.as_deref().filter(|p| !p.is_empty()) {
+            self.write_element(writer, "fone", phone)?;
+        }
+        writer
+            .write_event(Event::End(BytesEnd::new("enderDest")))
+            .map_err(|e| e.to_string())?;
+
+        let indicator = recipient.ie_indicator();
+        self.write_element(writer, "indIEDest", &indicator.to_string())?;
+        if indicator == 1 {
+            if let Some(ie) = &recipient.state_registration {
+                self.write_element(writer, "IE", ie.trim())?;
+            }
+        }
+        if let Some(email) = recipient.email.as_deref().filter(|e| !e.is_empty()) {
+            self.write_element(writer, "email", email)?;
+        }
+
+        writer
+            .write_event(Event::End(BytesEnd::new("dest")))
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
     fn write_det<W: std::io::Write>(
         &self,
         writer: &mut Writer<W>,
         item: &NfceItem,
         num: usize,
+        freight_share: f64,
+        discount_share: f64,
     ) -> Result<(), String> {
         let mut det = BytesStart::new("det");
         det.push_attribute(("nItem", num.to_string().as_str()));
@@ -283,13 +685,19 @@ impl NfceXmlBuilder {
         self.write_element(writer, "uTrib", &item.unit)?;
         self.write_element(writer, "qTrib", &format!("{:.4}", item.quantity))?;
         self.write_element(writer, "vUnTrib", &format!("{:.2}", item.unit_value))?;
+        if freight_share > 0.0 {
+            self.write_element(writer, "vFrete", &money(freight_share))?;
+        }
+        if discount_share > 0.0 {
+            self.write_element(writer, "vDesc", &money(discount_share))?;
+        }
         self.write_element(writer, "indTot", "1")?; // 1 = Compõe total
         writer
             .write_event(Event::End(BytesEnd::new("prod")))
             .map_err(|e| e.to_string())?;
 
         // <imposto>
-        self.write_imposto(writer, item)?;
+        self.write_imposto(writer, item, freight_share, discount_share)?;
 
         writer
             .write_event(Event::End(BytesEnd::new("det")))
@@ -301,63 +709,197 @@ impl NfceXmlBuilder {
         &self,
         writer: &mut Writer<W>,
         item: &NfceItem,
+        freight_share: f64,
+        discount_share: f64,
     ) -> Result<(), String> {
+        let taxes = self.item_taxes(item, freight_share, discount_share);
+        let tax = &item.tax;
+
         writer
             .write_event(Event::Start(BytesStart::new("imposto")))
             .map_err(|e| e.to_string())?;
 
+        if item.ibpt.is_some() {
+            self.write_element(writer, "vTotTrib", &money(taxes.total_approx()))?;
+        }
+
         // ICMS
         writer
             .write_event(Event::Start(BytesStart::new("ICMS")))
             .map_err(|e| e.to_string())?;
+        let group = match tax.icms_code.as_str() {
+            "102" | "103" | "300" | "400" => "ICMSSN102",
+            "201" => "ICMSSN201",
+            "202" => "ICMSSN202",
+            "500" => "ICMSSN500",
+            "00" => "ICMS00",
+            "10" => "ICMS10",
+            "20" => "ICMS20",
+            "60" => "ICMS60",
+            other => return Err(format!("Código ICMS não suportado: {}", other)),
+        };
         writer
-            .write_event(Event::Start(BytesStart::new("ICMSSN102")))
+            .write_event(Event::Start(BytesStart::new(group)))
             .map_err(|e| e.to_string())?;
-        self.write_element(writer, "orig", &item.icms_origin.to_string())?;
-        self.write_element(writer, "CSOSN", &item.icms_cst)?;
+        self.write_element(writer, "orig", &tax.icms_origin.to_string())?;
+        if group.starts_with("ICMSSN") {
+            self.write_element(writer, "CSOSN", &tax.icms_code)?;
+        } else {
+            self.write_element(writer, "CST", &tax.icms_code)?;
+        }
+
+        if matches!(group, "ICMS00" | "ICMS10" | "ICMS20") {
+            self.write_element(writer, "modBC", "3")?; // 3 = Valor da operação
+            if group == "ICMS20" {
+                self.write_element(writer, "pRedBC", &rate(tax.icms_base_reduction))?;
+            }
+            self.write_element(writer, "vBC", &money(taxes.icms_base))?;
+            self.write_element(writer, "pICMS", &rate(tax.icms_rate))?;
+            self.write_element(writer, "vICMS", &money(taxes.icms_value))?;
+            if tax.fcp_rate > 0.0 {
+                // No ICMS00 a base do FCP é a própria vBC (sem vBCFCP)
+                if group != "ICMS00" {
+                    self.write_element(writer, "vBCFCP", &money(taxes.icms_base))?;
+                }
+                self.write_element(writer, "pFCP", &rate(tax.fcp_rate))?;
+                self.write_element(writer, "vFCP", &money(taxes.fcp_value))?;
+            }
+        }
+
+        if tax.has_st() {
+            self.write_element(writer, "modBCST", "4")?; // 4 = Margem Valor Agregado
+            self.write_element(writer, "pMVAST", &rate(tax.st_mva))?;
+            self.write_element(writer, "vBCST", &money(taxes.st_base))?;
+            self.write_element(writer, "pICMSST", &rate(tax.st_rate))?;
+            self.write_element(writer, "vICMSST", &money(taxes.st_value))?;
+            if tax.fcp_rate > 0.0 {
+                self.write_element(writer, "vBCFCPST", &money(taxes.st_base))?;
+                self.write_element(writer, "pFCPST", &rate(tax.fcp_rate))?;
+                self.write_element(writer, "vFCPST", &money(taxes.fcp_st_value))?;
+            }
+            if group == "ICMSSN201" {
+                // Crédito do Simples não aproveitado em venda a consumidor
+                self.write_element(writer, "pCredSN", "0.0000")?;
+                self.write_element(writer, "vCredICMSSN", "0.00")?;
+            }
+        }
+
         writer
-            .write_event(Event::End(BytesEnd::new("ICMSSN102")))
+            .write_event(Event::End(BytesEnd::new(group)))
             .map_err(|e| e.to_string())?;
         writer
             .write_event(Event::End(BytesEnd::new("ICMS")))
             .map_err(|e| e.to_string())?;
 
-        // PIS
-        writer
-            .write_event(Event::Start(BytesStart::new("PIS")))
-            .map_err(|e| e.to_string())?;
-        writer
-            .write_event(Event::Start(BytesStart::new("PISNT")))
-            .map_err(|e| e.to_string())?;
-        self.write_element(writer, "CST", &item.pis_cst)?;
-        writer
-            .write_event(Event::End(BytesEnd::new("PISNT")))
-            .map_err(|e| e.to_string())?;
-        writer
-            .write_event(Event::End(BytesEnd::new("PIS")))
-            .map_err(|e| e.to_string())?;
+        // PIS / COFINS
+        self.write_contribution(
+            writer,
+            "PIS",
+            &tax.pis_cst,
+            taxes.pis_base,
+            tax.pis_rate,
+            taxes.pis_value,
+        )?;
+        self.write_contribution(
+            writer,
+            "COFINS",
+            &tax.cofins_cst,
+            taxes.cofins_base,
+            tax.cofins_rate,
+            taxes.cofins_value,
+        )?;
 
-        // COFINS
         writer
-            .write_event(Event::Start(BytesStart::new("COFINS")))
+            .write_event(Event::End(BytesEnd::new("imposto")))
             .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Grupo PIS ou COFINS (Aliq / NT / Outr) conforme o CST
+    fn write_contribution<W: std::io::Write>(
+        &self,
+        writer: &mut Writer<W>,
+        tag: &str,
+        cst: &str,
+        base: f64,
+        aliquot: f64,
+        value: f64,
+    ) -> Result<(), String> {
+        let group = match contribution_group(cst) {
+            Some(ContributionGroup::Aliquot) => format!("{}Aliq", tag),
+            Some(ContributionGroup::NotTaxed) => format!("{}NT", tag),
+            Some(ContributionGroup::Other) => format!("{}Outr", tag),
+            None => return Err(format!("CST de {} inválido: {}", tag, cst)),
+        };
+
         writer
-            .write_event(Event::Start(BytesStart::new("COFINSNT")))
+            .write_event(Event::Start(BytesStart::new(tag)))
             .map_err(|e| e.to_string())?;
-        self.write_element(writer, "CST", &item.cofins_cst)?;
         writer
-            .write_event(Event::End(BytesEnd::new("COFINSNT")))
+            .write_event(Event::Start(BytesStart::new(group.as_str())))
             .map_err(|e| e.to_string())?;
+        self.write_element(writer, "CST", cst)?;
+        if contribution_group(cst) != Some(ContributionGroup::NotTaxed) {
+            self.write_element(writer, "vBC", &money(base))?;
+            self.write_element(writer, &format!("p{}", tag), &rate(aliquot))?;
+            self.write_element(writer, &format!("v{}", tag), &money(value))?;
+        }
         writer
-            .write_event(Event::End(BytesEnd::new("COFINS")))
+            .write_event(Event::End(BytesEnd::new(group.as_str())))
             .map_err(|e| e.to_string())?;
-
         writer
-            .write_event(Event::End(BytesEnd::new("imposto")))
+            .write_event(Event::End(BytesEnd::new(tag)))
             .map_err(|e| e.to_string())?;
         Ok(())
     }
 
+    /// Tributos calculados de cada item, na ordem dos itens
+    pub fn items_taxes(&self) -> Vec<ItemTaxes> {
+        self.data
+            .items
+            .iter()
+            .zip(
+                self.freight_shares()
+                    .into_iter()
+                    .zip(self.discount_shares()),
+            )
+            .map(|(item, (freight, discount))| self.item_taxes(item, freight, discount))
+            .collect()
+    }
+
+    /// Soma dos tributos de todos os itens (grupo ICMSTot)
+    pub fn total_taxes(&self) -> ItemTaxes {
+        self.items_taxes()
+            .into_iter()
+            .fold(ItemTaxes::default(), |acc, t| ItemTaxes {
+                icms_base: acc.icms_base + t.icms_base,
+                icms_value: acc.icms_value + t.icms_value,
+                fcp_value: acc.fcp_value + t.fcp_value,
+                st_base: acc.st_base + t.st_base,
+                st_value: acc.st_value + t.st_value,
+                fcp_st_value: acc.fcp_st_value + t.fcp_st_value,
+                pis_base: acc.pis_base + t.pis_base,
+                pis_value: acc.pis_value + t.pis_value,
+                cofins_base: acc.cofins_base + t.cofins_base,
+                cofins_value: acc.cofins_value + t.cofins_value,
+                federal_tax: acc.federal_tax + t.federal_tax,
+                state_tax: acc.state_tax + t.state_tax,
+                municipal_tax: acc.municipal_tax + t.municipal_tax,
+            })
+    }
+
+    /// Mensagem da Lei da Transparência (None se nenhum item tem alíquota IBPT)
+    pub fn transparency_info(&self) -> Option<String> {
+        let source = self.data.items.iter().find_map(|i| i.ibpt.as_ref())?;
+        let totals = self.total_taxes();
+        Some(transparency_message(
+            totals.federal_tax,
+            totals.state_tax,
+            totals.municipal_tax,
+            &source.source,
+        ))
+    }
+
     fn write_total<W: std::io::Write>(&self, writer: &mut Writer<W>) -> Result<(), String> {
         writer
             .write_event(Event::Start(BytesStart::new("total")))
@@ -366,25 +908,30 @@ impl NfceXmlBuilder {
             .write_event(Event::Start(BytesStart::new("ICMSTot")))
             .map_err(|e| e.to_string())?;
 
-        self.write_element(writer, "vBC", "0.00")?;
-        self.write_element(writer, "vICMS", "0.00")?;
+        let totals = self.total_taxes();
+        self.write_element(writer, "vBC", &money(totals.icms_base))?;
+        self.write_element(writer, "vICMS", &money(totals.icms_value))?;
         self.write_element(writer, "vICMSDeson", "0.00")?;
-        self.write_element(writer, "vFCP", "0.00")?;
-        self.write_element(writer, "vBCST", "0.00")?;
-        self.write_element(writer, "vST", "0.00")?;
-        self.write_element(writer, "vFCPST", "0.00")?;
+        self.write_element(writer, "vFCP", &money(totals.fcp_value))?;
+        self.write_element(writer, "vBCST", &money(totals.st_base))?;
+        self.write_element(writer, "vST", &money(totals.st_value))?;
+        self.write_element(writer, "vFCPST", &money(totals.fcp_st_value))?;
         self.write_element(writer, "vFCPSTRet", "0.00")?;
         self.write_element(writer, "vProd", &format!("{:.2}", self.data.total_products))?;
-        self.write_element(writer, "vFrete", "0.00")?;
+        self.write_element(writer, "vFrete", &money(self.freight_value()))?;
         self.write_element(writer, "vSeg", "0.00")?;
         self.write_element(writer, "vDesc", &format!("{:.2}", self.data.total_discount))?;
         self.write_element(writer, "vII", "0.00")?;
         self.write_element(writer, "vIPI", "0.00")?;
         self.write_element(writer, "vIPIDevol", "0.00")?;
-        self.write_element(writer, "vPIS", "0.00")?;
-        self.write_element(writer, "vCOFINS", "0.00")?;
+        self.write_element(writer, "vPIS", &money(totals.pis_value))?;
+        self.write_element(writer, "vCOFINS", &money(totals.cofins_value))?;
         self.write_element(writer, "vOutro", "0.00")?;
         self.write_element(writer, "vNF", &format!("{:.2}", self.data.total_note))?;
+        if self.transparency_info().is_some() {
+            let approx = totals.federal_tax + totals.state_tax + totals.municipal_tax;
+            self.write_element(writer, "vTotTrib", &money(approx))?;
+        }
 
         writer
             .write_event(Event::End(BytesEnd::new("ICMSTot")))
@@ -399,7 +946,67 @@ impl NfceXmlBuilder {
         writer
             .write_event(Event::Start(BytesStart::new("transp")))
             .map_err(|e| e.to_string())?;
-        self.write_element(writer, "modFrete", "9")?; // 9 = Sem frete
+        let Some(freight) = &self.data.freight else {
+            self.write_element(writer, "modFrete", "9")?; // 9 = Sem frete
+            return writer
+                .write_event(Event::End(BytesEnd::new("transp")))
+                .map(|_| ())
+                .map_err(|e| e.to_string());
+        };
+
+        self.write_element(writer, "modFrete", &freight.modality.to_string())?;
+
+        if let Some(carrier) = &freight.carrier {
+            writer
+                .write_event(Event::Start(BytesStart::new("transporta")))
+                .map_err(|e| e.to_string())?;
+            let document_tag = if carrier.document.len() == 14 {
+                "CNPJ"
+            } else {
+                "CPF"
+            };
+            self.write_element(writer, document_tag, &carrier.document)?;
+            self.write_element(writer, "xNome", &carrier.name)?;
+            if let Some(ie) = carrier
+                .state_registration
+                .as_deref()
+                .filter(|s| !s.is_empty())
+            {
+                self.write_element(writer, "IE", ie)?;
+            }
+            if let Some(address) = carrier.address.as_deref().filter(|s| !s.is_empty()) {
+                self.write_element(writer, "xEnder", address)?;
+            }
+            if let Some(city) = carrier.city.as_deref().filter(|s| !s.is_empty()) {
+                self.write_element(writer, "xMun", city)?;
+            }
+            if let Some(uf) = carrier.uf.as_deref().filter(|s| !s.is_empty()) {
+                self.write_element(writer, "UF", uf)?;
+            }
+            writer
+                .write_event(Event::End(BytesEnd::new("transporta")))
+                .map_err(|e| e.to_string())?;
+        }
+
+        if let Some(volumes) = &freight.volumes {
+            writer
+                .write_event(Event::Start(BytesStart::new("vol")))
+                .map_err(|e| e.to_string())?;
+            self.write_element(writer, "qVol", &volumes.quantity.to_string())?;
+            if let Some(species) = volumes.species.as_deref().filter(|s| !s.is_empty()) {
+                self.write_element(writer, "esp", species)?;
+            }
+            if let Some(net) = volumes.net_weight {
+                self.write_element(writer, "pesoL", &format!("{:.3}", net))?;
+            }
+            if let Some(gross) = volumes.gross_weight {
+                self.write_element(writer, "pesoB", &format!("{:.3}", gross))?;
+            }
+            writer
+                .write_event(Event::End(BytesEnd::new("vol")))
+                .map_err(|e| e.to_string())?;
+        }
+
         writer
             .write_event(Event::End(BytesEnd::new("transp")))
             .map_err(|e| e.to_string())?;
@@ -407,36 +1014,106 @@ impl NfceXmlBuilder {
     }
 
     fn write_pag<W: std::io::Write>(&self, writer: &mut Writer<W>) -> Result<(), String> {
+        if self.data.payments.is_empty() {
+            return Err(format!("{} sem forma de pagamento", self.document_name()));
+        }
+
         writer
             .write_event(Event::Start(BytesStart::new("pag")))
             .map_err(|e| e.to_string())?;
+
+        for payment in &self.data.payments {
+            self.write_det_pag(writer, payment)?;
+        }
+
+        // vTroco: diferença entre o total pago e o valor da nota
+        let change = self.change();
+        if change > 0.0 {
+            self.write_element(writer, "vTroco", &format!("{:.2}", change))?;
+        }
+
+        writer
+            .write_event(Event::End(BytesEnd::new("pag")))
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    fn write_det_pag<W: std::io::Write>(
+        &self,
+        writer: &mut Writer<W>,
+        payment: &NfcePayment,
+    ) -> Result<(), String> {
         writer
             .write_event(Event::Start(BytesStart::new("detPag")))
             .map_err(|e| e.to_string())?;
 
-        self.write_element(writer, "tPag", &self.data.payment_method)?;
-        self.write_element(writer, "vPag", &format!("{:.2}", self.data.payment_value))?;
+        self.write_element(writer, "tPag", &payment.method)?;
+        if payment.method == "99" {
+            self.write_element(writer, "xPag", "Outros")?;
+        }
+        self.write_element(writer, "vPag", &format!("{:.2}", payment.value))?;
+
+        if let Some(card) = &payment.card {
+            writer
+                .write_event(Event::Start(BytesStart::new("card")))
+                .map_err(|e| e.to_string())?;
+            self.write_element(writer, "tpIntegra", &card.integration_type.to_string())?;
+            if let Some(cnpj) = &card.acquirer_cnpj {
+                self.write_element(writer, "CNPJ", cnpj)?;
+            }
+            if let Some(brand) = &card.brand {
+                self.write_element(writer, "tBand", brand)?;
+            }
+            if let Some(auth) = &card.authorization_code {
+                self.write_element(writer, "cAut", auth)?;
+            }
+            writer
+                .write_event(Event::End(BytesEnd::new("card")))
+                .map_err(|e| e.to_string())?;
+        }
 
         writer
             .write_event(Event::End(BytesEnd::new("detPag")))
             .map_err(|e| e.to_string())?;
-        writer
-            .write_event(Event::End(BytesEnd::new("pag")))
-            .map_err(|e| e.to_string())?;
         Ok(())
     }
 
+    /// Troco devolvido ao consumidor (soma dos vPag - vNF), arredondado a centavos
+    pub fn change(&self) -> f64 {
+        let paid: f64 = self.data.payments.iter().map(|p| p.value).sum();
+        let change = ((paid - self.data.total_note) * 100.0).round() / 100.0;
+        change.max(0.0)
+    }
+
     fn write_inf_adic<W: std::io::Write>(&self, writer: &mut Writer<W>) -> Result<(), String> {
         writer
             .write_event(Event::Start(BytesStart::new("infAdic")))
             .map_err(|e| e.to_string())?;
-        self.write_element(writer, "infCpl", "Nota Fiscal gerada pelo Sistema GIRO")?;
+        self.write_element(writer, "infCpl", &self.complementary_info())?;
         writer
             .write_event(Event::End(BytesEnd::new("infAdic")))
             .map_err(|e| e.to_string())?;
         Ok(())
     }
 
+    /// Conteúdo do infCpl (também impresso nos dados adicionais do DANFE)
+    pub fn complementary_info(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(info) = self
+            .data
+            .additional_info
+            .as_deref()
+            .filter(|i| !i.is_empty())
+        {
+            parts.push(info.to_string());
+        }
+        if let Some(message) = self.transparency_info() {
+            parts.push(message);
+        }
+        parts.push("Nota Fiscal gerada pelo Sistema GIRO".to_string());
+        parts.join(" | ")
+    }
+
     fn write_element<W: std::io::Write>(
         &self,
         writer: &mut Writer<W>,
@@ -465,28 +1142,46 @@ impl NfceXmlBuilder {
     }
 }
 
+fn money(value: f64) -> String {
+    format!("{:.2}", value)
+}
+
+fn rate(value: f64) -> String {
+    format!("{:.4}", value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     fn create_test_data() -> NfceData {
         NfceData {
+            model: 65,
+            nature_of_operation: "VENDA".to_string(),
             uf: "SP".to_string(),
             cnpj: "12345678000190".to_string(),
             serie: 1,
             numero: 1,
             emission_date: Utc::now(),
             emission_type: 1,
+            crt: 1,
             emitter_name: "EMPRESA TESTE LTDA".to_string(),
             emitter_trade_name: Some("TESTE".to_string()),
             emitter_ie: "123456789".to_string(),
             emitter_address: "RUA TESTE, 123".to_string(),
+            emitter_district: Some("CENTRO".to_string()),
             emitter_city: "SAO PAULO".to_string(),
             emitter_city_code: "3550308".to_string(),
             emitter_state: "SP".to_string(),
             emitter_cep: "01234567".to_string(),
             recipient_cpf: None,
             recipient_name: None,
+            recipient: None,
+            freight: None,
+            additional_info: None,
+            operation_type: 1,
+            purpose: 1,
+            referenced_keys: Vec::new(),
             items: vec![NfceItem {
                 number: 1,
                 code: "001".to_string(),
@@ -498,17 +1193,18 @@ mod tests {
                 quantity: 1.0,
                 unit_value: 10.0,
                 total_value: 10.0,
-                icms_origin: 0,
-                icms_cst: "102".to_string(),
-                pis_cst: "07".to_string(),
-                cofins_cst: "07".to_string(),
+                tax: TaxProfile::simples(0, "102", "07", "07"),
+                ibpt: None,
             }],
             total_products: 10.0,
             total_discount: 0.0,
             total_note: 10.0,
             environment: 2,
-            payment_method: "01".to_string(),
-            payment_value: 10.0,
+            payments: vec![NfcePayment {
+                method: "01".to_string(),
+                value: 10.0,
+                card: None,
+            }],
             csc_id: "1".to_string(),
             csc: "123456".to_string(),
         }
@@ -564,4 +1260,284 @@ mod tests {
         assert!(xml.contains("<ICMSTot>"));
         assert!(xml.contains("<vNF>10.00</vNF>"));
     }
+
+    #[test]
+    fn test_xml_multiple_payments() {
+        let mut data = create_test_data();
+        data.payments = vec![
+            NfcePayment {
+                method: "17".to_string(),
+                value: 5.0,
+                card: None,
+            },
+            NfcePayment {
+                method: "01".to_string(),
+                value: 5.0,
+                card: None,
+            },
+        ];
+        let access_key = "35260100123456780001906500100000000111234567890".to_string();
+        let xml = NfceXmlBuilder::new(data, access_key).build().unwrap();
+
+        assert_eq!(xml.matches("<detPag>").count(), 2);
+        assert!(xml.contains("<tPag>17</tPag>"));
+        assert!(xml.contains("<tPag>01</tPag>"));
+        assert!(!xml.contains("<vTroco>"));
+    }
+
+    #[test]
+    fn test_xml_change_and_card_group() {
+        let mut data = create_test_data();
+        data.payments = vec![
+            NfcePayment {
+                method: "03".to_string(),
+                value: 4.0,
+                card: Some(NfceCardInfo {
+                    integration_type: 2,
+                    acquirer_cnpj: Some("01027058000191".to_string()),
+                    brand: Some("01".to_string()),
+                    authorization_code: Some("123456".to_string()),
+                }),
+            },
+            NfcePayment {
+                method: "01".to_string(),
+                value: 10.0,
+                card: None,
+            },
+        ];
+        let access_key = "35260100123456780001906500100000000111234567890".to_string();
+        let builder = NfceXmlBuilder::new(data, access_key);
+        let xml = builder.build().unwrap();
+
+        assert_eq!(builder.change(), 4.0);
+        assert!(xml.contains("<vTroco>4.00</vTroco>"));
+        assert!(xml.contains("<tpIntegra>2</tpIntegra>"));
+        assert!(xml.contains("<CNPJ>01027058000191</CNPJ>"));
+        assert!(xml.contains("<tBand>01</tBand>"));
+        assert!(xml.contains("<cAut>123456</cAut>"));
+    }
+
+    #[test]
+    fn test_xml_without_payments_fails() {
+        let mut data = create_test_data();
+        data.payments.clear();
+        let access_key = "35260100123456780001906500100000000111234567890".to_string();
+
+        assert!(NfceXmlBuilder::new(data, access_key).build().is_err());
+    }
+
+    #[test]
+    fn test_xml_normal_regime_taxes() {
+        let mut data = create_test_data();
+        data.crt = 3;
+        data.items[0].tax = TaxProfile {
+            icms_origin: 0,
+            icms_code: "00".to_string(),
+            icms_rate: 18.0,
+            icms_base_reduction: 0.0,
+            st_mva: 0.0,
+            st_rate: 0.0,
+            fcp_rate: 0.0,
+            pis_cst: "01".to_string(),
+            pis_rate: 1.65,
+            cofins_cst: "01".to_string(),
+            cofins_rate: 7.6,
+        };
+        data.items[0].ibpt = Some(IbptRates {
+            federal: 13.45,
+            state: 18.0,
+            municipal: 0.0,
+            source: "IBPT".to_string(),
+        });
+        let access_key = "35260100123456780001906500100000000111234567890".to_string();
+        let xml = NfceXmlBuilder::new(data, access_key).build().unwrap();
+
+        assert!(xml.contains("<CRT>3</CRT>"));
+        assert!(xml.contains("<ICMS00>"));
+        assert!(xml.contains("<vICMS>1.80</vICMS>"));
+        assert!(xml.contains("<PISAliq>"));
+        assert!(xml.contains("<vPIS>0.17</vPIS>"));
+        assert!(xml.contains("<vCOFINS>0.76</vCOFINS>"));
+        assert!(xml.contains("<vTotTrib>3.15</vTotTrib>"));
+        assert!(xml.contains("Trib aprox R$ 1.35 Federal, R$ 1.80 Estadual"));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_item() {
+        let mut data = create_test_data();
+        data.items[0].ncm = "1234".to_string();
+        let access_key = "35260100123456780001906500100000000111234567890".to_string();
+        assert!(NfceXmlBuilder::new(data, access_key.clone())
+            .build()
+            .is_err());
+
+        let mut data = create_test_data();
+        data.items[0].tax.icms_code = "00".to_string(); // CST em emitente do Simples
+        assert!(NfceXmlBuilder::new(data, access_key).build().is_err());
+    }
+
+    fn nfe_recipient(uf: &str, ie: Option<&str>) -> NfeRecipient {
+        NfeRecipient {
+            document: "11222333000181".to_string(),
+            name: "MOTO PECAS CLIENTE LTDA".to_string(),
+            state_registration: ie.map(|s| s.to_string()),
+            email: Some("compras@cliente.com.br".to_string()),
+            address: NfeAddress {
+                street: "AV BRASIL".to_string(),
+                number: "1000".to_string(),
+                complement: None,
+                district: "CENTRO".to_string(),
+                city_code: if uf == "SP" { "3550308" } else { "3304557" }.to_string(),
+                city: if uf == "SP" {
+                    "SAO PAULO"
+                } else {
+                    "RIO DE JANEIRO"
+                }
+                .to_string(),
+                uf: uf.to_string(),
+                cep: "20000000".to_string(),
+                phone: None,
+            },
+        }
+    }
+
+    fn create_nfe_data() -> NfceData {
+        let mut data = create_test_data();
+        data.model = 55;
+        data.nature_of_operation = "VENDA DE MERCADORIA".to_string();
+        data.environment = 1;
+        data.recipient = Some(nfe_recipient("RJ", Some("123456789")));
+        data.items[0].cfop = "6102".to_string();
+        let mut second = data.items[0].clone();
+        second.number = 2;
+        second.total_value = 30.0;
+        second.unit_value = 30.0;
+        data.items.push(second);
+        data.total_products = 40.0;
+        data.freight = Some(NfeFreight {
+            modality: 0,
+            value: 10.0,
+            carrier: Some(NfeCarrier {
+                document: "99888777000166".to_string(),
+                name: "TRANSPORTADORA TESTE".to_string(),
+                state_registration: None,
+                address: None,
+                city: Some("SAO PAULO".to_string()),
+                uf: Some("SP".to_string()),
+            }),
+            volumes: Some(NfeVolumes {
+                quantity: 2,
+                species: Some("CAIXA".to_string()),
+                net_weight: Some(3.5),
+                gross_weight: Some(4.0),
+            }),
+        });
+        data.total_note = 50.0;
+        data.payments[0].value = 50.0;
+        data
+    }
+
+    #[test]
+    fn test_nfe_interstate_with_dest_and_freight() {
+        let access_key = "35260100123456780001905500100000000111234567890".to_string();
+        let builder = NfceXmlBuilder::new(create_nfe_data(), access_key);
+        let xml = builder.build().unwrap();
+
+        assert!(xml.contains("<mod>55</mod>"));
+        assert!(xml.contains("<natOp>VENDA DE MERCADORIA</natOp>"));
+        assert!(xml.contains("<idDest>2</idDest>"));
+        assert!(xml.contains("<tpImp>1</tpImp>"));
+        assert!(xml.contains("<indFinal>0</indFinal>"));
+        assert!(xml.contains("<CNPJ>11222333000181</CNPJ>"));
+        assert!(xml.contains("<enderDest>"));
+        assert!(xml.contains("<indIEDest>1</indIEDest>"));
+        assert!(xml.contains("<IE>123456789</IE>"));
+        assert!(xml.contains("<modFrete>0</modFrete>"));
+        assert!(xml.contains("<transporta>"));
+        assert!(xml.contains("<qVol>2</qVol>"));
+        assert!(xml.contains("<pesoB>4.000</pesoB>"));
+
+        // Frete rateado proporcionalmente (10 + 30 → 2.50 + 7.50)
+        assert_eq!(builder.freight_shares(), vec![2.5, 7.5]);
+        assert!(xml.contains("<vFrete>2.50</vFrete>"));
+        assert!(xml.contains("<vFrete>7.50</vFrete>"));
+        assert!(xml.contains("<vFrete>10.00</vFrete>"));
+        assert!(xml.contains("<vNF>50.00</vNF>"));
+    }
+
+    #[test]
+    fn test_nfe_validation_rules() {
+        let access_key = "35260100123456780001905500100000000111234567890".to_string();
+
+        // CFOP interno em operação interestadual
+        let mut data = create_nfe_data();
+        data.items[0].cfop = "5102".to_string();
+        assert!(NfceXmlBuilder::new(data, access_key.clone())
+            .build()
+            .is_err());
+
+        // NF-e sem destinatário
+        let mut data = create_nfe_data();
+        data.recipient = None;
+        assert!(NfceXmlBuilder::new(data, access_key.clone())
+            .build()
+            .is_err());
+
+        // Interestadual para não contribuinte exige DIFAL
+        let mut data = create_nfe_data();
+        data.recipient = Some(nfe_recipient("RJ", None));
+        assert!(NfceXmlBuilder::new(data, access_key.clone())
+            .build()
+            .is_err());
+
+        // Operação interna para não contribuinte é aceita com CFOP 5xxx
+        let mut data = create_nfe_data();
+        data.recipient = Some(nfe_recipient("SP", None));
+        for item in &mut data.items {
+            item.cfop = "5102".to_string();
+        }
+        let xml = NfceXmlBuilder::new(data, access_key.clone())
+            .build()
+            .unwrap();
+        assert!(xml.contains("<idDest>1</idDest>"));
+        assert!(xml.contains("<indIEDest>9</indIEDest>"));
+        assert!(xml.contains("<indFinal>1</indFinal>"));
+
+        // NFC-e não admite frete
+        let mut data = create_test_data();
+        data.freight = create_nfe_data().freight;
+        assert!(NfceXmlBuilder::new(data, access_key).build().is_err());
+    }
+
+    #[test]
+    fn test_nfe_return_references_original_note() {
+        let access_key = "35260100123456780001905500100000000111234567890".to_string();
+        let original = "35260100123456780001655001000000042112345678".to_string();
+
+        let mut data = create_nfe_data();
+        data.nature_of_operation = "DEVOLUÇÃO DE VENDA".to_string();
+        data.operation_type = 0;
+        data.purpose = 4;
+        for item in &mut data.items {
+            item.cfop = "2202".to_string();
+        }
+
+        // Sem a nota referenciada
+        assert!(NfceXmlBuilder::new(data.clone(), access_key.clone())
+            .build()
+            .is_err());
+
+        data.referenced_keys = vec![original.clone()];
+        let xml = NfceXmlBuilder::new(data.clone(), access_key.clone())
+            .build()
+            .unwrap();
+        assert!(xml.contains("<tpNF>0</tpNF>"));
+        assert!(xml.contains("<finNFe>4</finNFe>"));
+        assert!(xml.contains(&format!("<refNFe>{}</refNFe>", original)));
+        assert!(xml.find("<verProc>").unwrap() < xml.find("<NFref>").unwrap());
+
+        // CFOP de saída não vale para nota de entrada
+        data.items[0].cfop = "6102".to_string();
+        assert!(NfceXmlBuilder::new(data, access_key).build().is_err());
+    }
 }
@@ -175,4 +175,58 @@ impl<'a> AlertRepository<'a> {
 
         Ok(created)
     }
+
+    /// Gera um alerta por cliente com parcelas de crediário vencidas (após a carência).
+    /// Não repete enquanto o alerta anterior do cliente não for lido.
+    pub async fn check_overdue_receivables(&self) -> AppResult<i32> {
+        use sqlx::Row;
+
+        let query = r#"
+            SELECT
+                r.customer_id,
+                c.name as customer_name,
+                COUNT(*) as installments,
+                COALESCE(SUM(r.amount - r.paid_amount), 0.0) as overdue,
+                MIN(r.due_date) as oldest_due
+            FROM receivables r
+            JOIN customers c ON c.id = r.customer_id
+            LEFT JOIN customer_credit_accounts a ON a.customer_id = r.customer_id
+            WHERE r.status = 'OPEN'
+              AND date(r.due_date, '+' || COALESCE(a.grace_days, 0) || ' days') < date('now', 'localtime')
+              AND NOT EXISTS (
+                  SELECT 1 FROM alerts al
+                  WHERE al.type = 'OVERDUE_RECEIVABLE'
+                    AND al.is_read = 0
+                    AND al.message LIKE '%' || r.customer_id || '%'
+              )
+            GROUP BY r.customer_id, c.name
+        "#;
+
+        let rows = sqlx::query(query).fetch_all(self.pool).await?;
+
+        let mut created = 0;
+        for row in rows {
+            let customer_id: String = row.get("customer_id");
+            let customer: String = row.get("customer_name");
+            let installments: i64 = row.get("installments");
+            let overdue: f64 = row.get("overdue");
+            let oldest_due: String = row.get("oldest_due");
+
+            self.create(CreateAlert {
+                alert_type: "OVERDUE_RECEIVABLE".to_string(),
+                severity: "WARNING".to_string(),
+                title: format!("Fiado em Atraso: {}", customer),
+                message: format!(
+                    "{} parcela(s) em atraso desde {}, total R$ {:.2}. ID Cliente: {}",
+                    installments, oldest_due, overdue, customer_id
+                ),
+                product_id: None,
+                lot_id: None,
+            })
+            .await?;
+            created += 1;
+        }
+
+        Ok(created)
+    }
 }
@@ -2,8 +2,9 @@
 
 use crate::error::AppResult;
 use crate::models::{
-    CashMovement, CashSession, CashSessionSummary, CreateCashMovement, CreateCashSession,
-    PaymentMethodSummary,
+    BlindCashClosing, CashClosingCount, CashClosingDenomination, CashClosingReport,
+    CashMethodBreakdown, CashMovement, CashSession, CashSessionSummary, CreateCashMovement,
+    CreateCashSession, PaymentMethod, PaymentMethodSummary,
 };
 use crate::repositories::new_id;
 use sqlx::{Row, SqlitePool};
@@ -17,7 +18,10 @@ impl<'a> CashRepository<'a> {
         Self { pool }
     }
 
-    const SESSION_COLS: &'static str = "id, employee_id, opened_at, closed_at, opening_balance, expected_balance, actual_balance, difference, status, notes, created_at, updated_at";
+    const SESSION_COLS: &'static str = "id, employee_id, opened_at, closed_at, opening_balance, expected_balance, actual_balance, difference, status, notes, approved_by_id, approved_at, created_at, updated_at";
+    const COUNT_COLS: &'static str =
+        "id, session_id, method, expected_amount, counted_amount, difference, created_at";
+    const DENOMINATION_COLS: &'static str = "id, session_id, denomination, quantity, amount";
     const MOVEMENT_COLS: &'static str = "id, session_id, type, amount, description, created_at";
 
     pub async fn find_session_by_id(&self, id: &str) -> AppResult<Option<CashSession>> {
@@ -150,12 +154,13 @@ impl<'a> CashRepository<'a> {
         let total_sales: f64 = sales_row.try_get("total_sales")?;
         let total_canceled: f64 = sales_row.try_get("total_canceled")?;
 
-        // 2. Get Sales by Payment Method
+        // 2. Get Sales by Payment Method (crédito de troca não é recebimento)
         let payment_rows = sqlx::query(
             r#"
             SELECT method as payment_method, COALESCE(SUM(amount), 0.0) as total, COUNT(*) as count
             FROM sale_payments
             WHERE sale_id IN (SELECT id FROM sales WHERE cash_session_id = ? AND status = 'COMPLETED')
+              AND method != 'EXCHANGE_CREDIT'
             GROUP BY method
             "#,
         )
@@ -195,10 +200,94 @@ impl<'a> CashRepository<'a> {
             }
         }
 
-        // 4. Calculate Expected Cash in Drawer
-        // Opening + Supplies - Bleeds + CASH Sales
-        let cash_in_drawer =
-            session.opening_balance + total_supplies - total_withdrawals + cash_sales;
+        // 4. Refunds from returns (only CASH leaves the drawer)
+        let refund_rows = sqlx::query(
+            r#"
+            SELECT refund_method as method, COALESCE(SUM(refund_amount), 0.0) as total
+            FROM sale_returns
+            WHERE cash_session_id = ? AND refund_type = 'PAYMENT' AND refund_method IS NOT NULL
+            GROUP BY refund_method
+            "#,
+        )
+        .bind(session_id)
+        .fetch_all(self.pool)
+        .await?;
+
+        // 5. Account (fiado) receipts taken at this register
+        let receipt_rows = sqlx::query(
+            r#"
+            SELECT method, COALESCE(SUM(total), 0.0) as total
+            FROM receivable_payments
+            WHERE cash_session_id = ?
+            GROUP BY method
+            "#,
+        )
+        .bind(session_id)
+        .fetch_all(self.pool)
+        .await?;
+
+        // 6. Gift cards sold at this register
+        let gift_card_rows = sqlx::query(
+            r#"
+            SELECT payment_method as method, COALESCE(SUM(amount), 0.0) as total
+            FROM store_credit_transactions
+            WHERE cash_session_id = ? AND type = 'ISSUE' AND payment_method IS NOT NULL
+            GROUP BY payment_method
+            "#,
+        )
+        .bind(session_id)
+        .fetch_all(self.pool)
+        .await?;
+
+        let mut by_method: Vec<CashMethodBreakdown> = Vec::new();
+        method_entry(&mut by_method, "CASH");
+        // Vale usado como pagamento já entrou no caixa quando foi vendido (passo 6)
+        for summary in sales_by_method
+            .iter()
+            .filter(|s| s.method != "STORE_CREDIT")
+        {
+            method_entry(&mut by_method, &summary.method).sales += summary.amount;
+        }
+
+        let mut total_refunds = 0.0;
+        for row in refund_rows {
+            let method: String = row.try_get("method")?;
+            let amount: f64 = row.try_get("total")?;
+            total_refunds += amount;
+            method_entry(&mut by_method, &method).refunds += amount;
+        }
+
+        let mut total_account_receipts = 0.0;
+        for row in receipt_rows {
+            let method: String = row.try_get("method")?;
+            let amount: f64 = row.try_get("total")?;
+            total_account_receipts += amount;
+            method_entry(&mut by_method, &method).receipts += amount;
+        }
+
+        let mut total_gift_card_sales = 0.0;
+        for row in gift_card_rows {
+            let method: String = row.try_get("method")?;
+            let amount: f64 = row.try_get("total")?;
+            total_gift_card_sales += amount;
+            method_entry(&mut by_method, &method).receipts += amount;
+        }
+
+        // 7. Calculate Expected Cash in Drawer
+        // Opening + Supplies - Bleeds + CASH Sales + CASH Receipts + CASH Gift Cards - CASH Refunds
+        let cash = method_entry(&mut by_method, "CASH");
+        let cash_in_drawer = session.opening_balance + total_supplies - total_withdrawals
+            + cash_sales
+            + cash.receipts
+            - cash.refunds;
+
+        for entry in by_method.iter_mut() {
+            entry.expected = if entry.method == "CASH" {
+                cash_in_drawer
+            } else {
+                entry.sales + entry.receipts - entry.refunds
+            };
+        }
 
         Ok(CashSessionSummary {
             session,
@@ -206,9 +295,13 @@ impl<'a> CashRepository<'a> {
             total_canceled,
             total_withdrawals,
             total_supplies,
+            total_refunds,
+            total_account_receipts,
+            total_gift_card_sales,
             movement_count: movements.len() as i64,
             sales_by_method,
             cash_in_drawer,
+            by_method,
         })
     }
 
@@ -254,6 +347,272 @@ impl<'a> CashRepository<'a> {
             })
     }
 
+    /// Fechamento cego: o operador informa cédulas/moedas e o total de cada forma
+    /// eletrônica sem ver o esperado. Diferença acima da tolerância em qualquer
+    /// forma deixa a sessão aguardando aprovação do gerente.
+    pub async fn close_session_blind(
+        &self,
+        data: BlindCashClosing,
+        tolerance: f64,
+    ) -> AppResult<CashSession> {
+        let session = self
+            .find_session_by_id(&data.session_id)
+            .await?
+            .ok_or(crate::error::AppError::CashSessionNotOpen)?;
+        if session.status != "OPEN" {
+            return Err(crate::error::AppError::CashSessionNotOpen);
+        }
+
+        // Cédulas repetidas são somadas
+        let mut denominations: Vec<(f64, i64)> = Vec::new();
+        for d in &data.denominations {
+            if d.denomination <= 0.0 {
+                return Err(crate::error::AppError::Validation(
+                    "Cédula/moeda inválida".into(),
+                ));
+            }
+            let denomination = round_cents(d.denomination);
+            match denominations
+                .iter_mut()
+                .find(|(value, _)| (*value - denomination).abs() < 0.001)
+            {
+                Some((_, quantity)) => *quantity += i64::from(d.quantity),
+                None => denominations.push((denomination, i64::from(d.quantity))),
+            }
+        }
+        let counted_cash = round_cents(
+            denominations
+                .iter()
+                .map(|(value, quantity)| value * *quantity as f64)
+                .sum(),
+        );
+
+        let mut counted: Vec<(PaymentMethod, f64)> = vec![(PaymentMethod::Cash, counted_cash)];
+        for c in &data.method_counts {
+            if !COUNTED_METHODS.contains(&c.method) || c.method == PaymentMethod::Cash {
+                return Err(crate::error::AppError::Validation(format!(
+                    "{} não é contado no fechamento",
+                    c.method
+                )));
+            }
+            if c.amount < 0.0 {
+                return Err(crate::error::AppError::Validation(
+                    "Valor contado não pode ser negativo".into(),
+                ));
+            }
+            match counted.iter_mut().find(|(method, _)| *method == c.method) {
+                Some((_, amount)) => *amount += c.amount,
+                None => counted.push((c.method, c.amount)),
+            }
+        }
+
+        let summary = self.get_session_summary(&data.session_id).await?;
+        let mut counts: Vec<(PaymentMethod, f64, f64, f64)> = Vec::new();
+        for method in COUNTED_METHODS {
+            let expected = summary
+                .by_method
+                .iter()
+                .find(|m| m.method == method.as_str())
+                .map(|m| round_cents(m.expected))
+                .unwrap_or(0.0);
+            let amount = counted
+                .iter()
+                .find(|(m, _)| *m == method)
+                .map(|(_, amount)| round_cents(*amount))
+                .unwrap_or(0.0);
+            if method != PaymentMethod::Cash && expected == 0.0 && amount == 0.0 {
+                continue;
+            }
+            counts.push((method, expected, amount, round_cents(amount - expected)));
+        }
+
+        let requires_approval = counts
+            .iter()
+            .any(|(_, _, _, difference)| difference.abs() > tolerance + 0.005);
+        let status = if requires_approval {
+            "PENDING_APPROVAL"
+        } else {
+            "CLOSED"
+        };
+        let cash_expected = round_cents(summary.cash_in_drawer);
+        let now = chrono::Utc::now().to_rfc3339();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "UPDATE cash_sessions SET closed_at = ?, expected_balance = ?, actual_balance = ?, difference = ?, status = ?, notes = ?, updated_at = ? WHERE id = ?"
+        )
+        .bind(&now)
+        .bind(cash_expected)
+        .bind(counted_cash)
+        .bind(round_cents(counted_cash - cash_expected))
+        .bind(status)
+        .bind(&data.notes)
+        .bind(&now)
+        .bind(&data.session_id)
+        .execute(&mut *tx)
+        .await?;
+
+        for (method, expected, amount, difference) in &counts {
+            sqlx::query(
+                "INSERT INTO cash_closing_counts (id, session_id, method, expected_amount, counted_amount, difference, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(new_id())
+            .bind(&data.session_id)
+            .bind(method.as_str())
+            .bind(expected)
+            .bind(amount)
+            .bind(difference)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for (denomination, quantity) in &denominations {
+            sqlx::query(
+                "INSERT INTO cash_closing_denominations (id, session_id, denomination, quantity, amount) VALUES (?, ?, ?, ?, ?)"
+            )
+            .bind(new_id())
+            .bind(&data.session_id)
+            .bind(denomination)
+            .bind(quantity)
+            .bind(round_cents(denomination * *quantity as f64))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.find_session_by_id(&data.session_id)
+            .await?
+            .ok_or_else(|| crate::error::AppError::NotFound {
+                entity: "CashSession".into(),
+                id: data.session_id.clone(),
+            })
+    }
+
+    /// Gerente aprova a diferença do fechamento e encerra a sessão
+    pub async fn approve_closing(
+        &self,
+        id: &str,
+        approver_id: &str,
+        notes: Option<String>,
+    ) -> AppResult<CashSession> {
+        let session =
+            self.find_session_by_id(id)
+                .await?
+                .ok_or_else(|| crate::error::AppError::NotFound {
+                    entity: "CashSession".into(),
+                    id: id.into(),
+                })?;
+        if session.status != "PENDING_APPROVAL" {
+            return Err(crate::error::AppError::Validation(
+                "Fechamento não está aguardando aprovação".into(),
+            ));
+        }
+        if session.employee_id == approver_id {
+            return Err(crate::error::AppError::Validation(
+                "O fechamento deve ser aprovado por outro funcionário".into(),
+            ));
+        }
+
+        let notes = match (session.notes, notes) {
+            (Some(current), Some(approval)) => {
+                Some(format!("{}\nAprovação: {}", current, approval))
+            }
+            (None, Some(approval)) => Some(format!("Aprovação: {}", approval)),
+            (current, None) => current,
+        };
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            "UPDATE cash_sessions SET status = 'CLOSED', approved_by_id = ?, approved_at = ?, notes = ?, updated_at = ? WHERE id = ?"
+        )
+        .bind(approver_id)
+        .bind(&now)
+        .bind(&notes)
+        .bind(&now)
+        .bind(id)
+        .execute(self.pool)
+        .await?;
+
+        self.find_session_by_id(id)
+            .await?
+            .ok_or_else(|| crate::error::AppError::NotFound {
+                entity: "CashSession".into(),
+                id: id.into(),
+            })
+    }
+
+    /// Fechamentos aguardando aprovação do gerente
+    pub async fn find_pending_approval(&self) -> AppResult<Vec<CashSession>> {
+        let query = format!(
+            "SELECT {} FROM cash_sessions WHERE status = 'PENDING_APPROVAL' ORDER BY closed_at",
+            Self::SESSION_COLS
+        );
+        let result = sqlx::query_as::<_, CashSession>(&query)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_closing_counts(&self, session_id: &str) -> AppResult<Vec<CashClosingCount>> {
+        let query = format!(
+            "SELECT {} FROM cash_closing_counts WHERE session_id = ? ORDER BY rowid",
+            Self::COUNT_COLS
+        );
+        let result = sqlx::query_as::<_, CashClosingCount>(&query)
+            .bind(session_id)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_closing_denominations(
+        &self,
+        session_id: &str,
+    ) -> AppResult<Vec<CashClosingDenomination>> {
+        let query = format!(
+            "SELECT {} FROM cash_closing_denominations WHERE session_id = ? ORDER BY denomination DESC",
+            Self::DENOMINATION_COLS
+        );
+        let result = sqlx::query_as::<_, CashClosingDenomination>(&query)
+            .bind(session_id)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Dados do relatório de fechamento (Z) ou da leitura parcial (X) da sessão
+    pub async fn get_closing_report(
+        &self,
+        session_id: &str,
+        tolerance: f64,
+    ) -> AppResult<CashClosingReport> {
+        let summary = self.get_session_summary(session_id).await?;
+
+        let employee_name: Option<String> =
+            sqlx::query_scalar("SELECT name FROM employees WHERE id = ?")
+                .bind(&summary.session.employee_id)
+                .fetch_optional(self.pool)
+                .await?;
+        let approved_by_name: Option<String> = match summary.session.approved_by_id.as_deref() {
+            Some(approver_id) => {
+                sqlx::query_scalar("SELECT name FROM employees WHERE id = ?")
+                    .bind(approver_id)
+                    .fetch_optional(self.pool)
+                    .await?
+            }
+            None => None,
+        };
+
+        Ok(CashClosingReport {
+            employee_name: employee_name.unwrap_or_default(),
+            approved_by_name,
+            counts: self.find_closing_counts(session_id).await?,
+            denominations: self.find_closing_denominations(session_id).await?,
+            tolerance,
+            summary,
+        })
+    }
+
     pub async fn add_movement(&self, data: CreateCashMovement) -> AppResult<CashMovement> {
         // Validate BLEED (withdrawal) doesn't exceed available cash in drawer
         if data.movement_type == "BLEED" {
@@ -312,3 +671,37 @@ impl<'a> CashRepository<'a> {
 #[cfg(test)]
 #[path = "cash_repository_test.rs"]
 mod cash_repository_test;
+
+/// Formas conferidas no fechamento (fiado e vale-crédito não passam pelo caixa)
+const COUNTED_METHODS: [PaymentMethod; 6] = [
+    PaymentMethod::Cash,
+    PaymentMethod::Pix,
+    PaymentMethod::Debit,
+    PaymentMethod::Credit,
+    PaymentMethod::Voucher,
+    PaymentMethod::Other,
+];
+
+fn method_entry<'v>(
+    entries: &'v mut Vec<CashMethodBreakdown>,
+    method: &str,
+) -> &'v mut CashMethodBreakdown {
+    let index = match entries.iter().position(|e| e.method == method) {
+        Some(index) => index,
+        None => {
+            entries.push(CashMethodBreakdown {
+                method: method.to_string(),
+                sales: 0.0,
+                receipts: 0.0,
+                refunds: 0.0,
+                expected: 0.0,
+            });
+            entries.len() - 1
+        }
+    };
+    &mut entries[index]
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
@@ -3,7 +3,10 @@
 #[cfg(test)]
 mod tests {
     use super::super::*;
-    use crate::models::{CreateCashMovement, CreateCashSession};
+    use crate::models::{
+        BlindCashClosing, CreateCashMovement, CreateCashSession, DenominationCount, MethodCount,
+        PaymentMethod,
+    };
     use sqlx::sqlite::SqlitePoolOptions;
     use sqlx::SqlitePool;
 
@@ -320,4 +323,195 @@ mod tests {
         assert_eq!(summary.total_withdrawals, 30.0);
         assert_eq!(summary.total_sales, 700.0); // 200 + 500
     }
+
+    #[tokio::test]
+    async fn test_blind_summary_hides_expected() {
+        let pool = setup_test_db().await;
+        let repo = CashRepository::new(&pool);
+        let session = repo
+            .open_session(CreateCashSession {
+                employee_id: "emp-test-001".to_string(),
+                opening_balance: 100.0,
+                notes: None,
+            })
+            .await
+            .unwrap();
+        insert_paid_sale(&pool, &session.id, "sale-blind-001", "CASH", 80.0).await;
+        insert_paid_sale(&pool, &session.id, "sale-blind-002", "PIX", 20.0).await;
+
+        let mut summary = repo.get_session_summary(&session.id).await.unwrap();
+        assert_eq!(summary.total_sales, 100.0);
+        summary.hide_expected();
+
+        // Abertura e movimentos do próprio operador continuam visíveis
+        assert_eq!(summary.session.opening_balance, 100.0);
+        assert_eq!(summary.total_sales, 0.0);
+        assert_eq!(summary.total_refunds, 0.0);
+        assert_eq!(summary.total_account_receipts, 0.0);
+        assert_eq!(summary.total_gift_card_sales, 0.0);
+        assert_eq!(summary.cash_in_drawer, 0.0);
+        assert!(summary.sales_by_method.is_empty());
+        assert!(summary.by_method.is_empty());
+    }
+
+    async fn insert_paid_sale(
+        pool: &SqlitePool,
+        session_id: &str,
+        id: &str,
+        method: &str,
+        amount: f64,
+    ) {
+        sqlx::query(
+            "INSERT INTO sales (id, subtotal, discount_value, total, payment_method, amount_paid, change, status, cash_session_id, employee_id, created_at)
+             VALUES (?, ?, 0.0, ?, ?, ?, 0.0, 'COMPLETED', ?, 'emp-test-001', datetime('now'))"
+        )
+        .bind(id)
+        .bind(amount)
+        .bind(amount)
+        .bind(method)
+        .bind(amount)
+        .bind(session_id)
+        .execute(pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO sale_payments (id, sale_id, method, amount, created_at) VALUES (?, ?, ?, ?, datetime('now'))"
+        )
+        .bind(format!("pay-{}", id))
+        .bind(id)
+        .bind(method)
+        .bind(amount)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    fn blind_count(session_id: &str, notes_of_100: u32, pix: f64) -> BlindCashClosing {
+        BlindCashClosing {
+            session_id: session_id.to_string(),
+            denominations: vec![
+                DenominationCount {
+                    denomination: 100.0,
+                    quantity: notes_of_100,
+                },
+                DenominationCount {
+                    denomination: 0.5,
+                    quantity: 4,
+                },
+            ],
+            method_counts: vec![MethodCount {
+                method: PaymentMethod::Pix,
+                amount: pix,
+            }],
+            notes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_blind_closing_within_tolerance() {
+        let pool = setup_test_db().await;
+        let repo = CashRepository::new(&pool);
+        let session = repo
+            .open_session(CreateCashSession {
+                employee_id: "emp-test-001".to_string(),
+                opening_balance: 100.0,
+                notes: None,
+            })
+            .await
+            .unwrap();
+        insert_paid_sale(&pool, &session.id, "sale-cash", "CASH", 200.0).await;
+        insert_paid_sale(&pool, &session.id, "sale-pix", "PIX", 50.0).await;
+
+        let summary = repo.get_session_summary(&session.id).await.unwrap();
+        assert_eq!(summary.by_method[0].method, "CASH");
+        assert_eq!(summary.by_method[0].expected, 300.0);
+        let pix = summary
+            .by_method
+            .iter()
+            .find(|m| m.method == "PIX")
+            .unwrap();
+        assert_eq!(pix.expected, 50.0);
+
+        // Fiado não é conferido no fechamento
+        let mut invalid = blind_count(&session.id, 3, 50.0);
+        invalid.method_counts[0].method = PaymentMethod::Account;
+        assert!(repo.close_session_blind(invalid, 5.0).await.is_err());
+
+        // R$ 302 na gaveta (+2), PIX confere
+        let closed = repo
+            .close_session_blind(blind_count(&session.id, 3, 50.0), 5.0)
+            .await
+            .unwrap();
+        assert_eq!(closed.status, "CLOSED");
+        assert_eq!(closed.actual_balance, Some(302.0));
+        assert_eq!(closed.difference, Some(2.0));
+
+        let counts = repo.find_closing_counts(&session.id).await.unwrap();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0].method, "CASH");
+        assert_eq!(counts[1].method, "PIX");
+        assert_eq!(counts[1].difference, 0.0);
+
+        let denominations = repo.find_closing_denominations(&session.id).await.unwrap();
+        assert_eq!(denominations.len(), 2);
+        assert_eq!(denominations[0].amount, 300.0);
+
+        // Sessão já fechada
+        assert!(repo
+            .close_session_blind(blind_count(&session.id, 3, 50.0), 5.0)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_blind_closing_requires_manager_approval() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            "INSERT INTO employees (id, name, pin, role, is_active, created_at, updated_at)
+             VALUES ('emp-manager', 'Gerente', 'manager-pin', 'MANAGER', 1, datetime('now'), datetime('now'))"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let repo = CashRepository::new(&pool);
+        let session = repo
+            .open_session(CreateCashSession {
+                employee_id: "emp-test-001".to_string(),
+                opening_balance: 100.0,
+                notes: None,
+            })
+            .await
+            .unwrap();
+        insert_paid_sale(&pool, &session.id, "sale-cash", "CASH", 200.0).await;
+        insert_paid_sale(&pool, &session.id, "sale-pix", "PIX", 50.0).await;
+
+        // Gaveta confere, mas faltam R$ 20 no PIX
+        let pending = repo
+            .close_session_blind(blind_count(&session.id, 3, 30.0), 5.0)
+            .await
+            .unwrap();
+        assert_eq!(pending.status, "PENDING_APPROVAL");
+        assert_eq!(repo.find_pending_approval().await.unwrap().len(), 1);
+
+        // O próprio operador não aprova
+        assert!(repo
+            .approve_closing(&session.id, "emp-test-001", None)
+            .await
+            .is_err());
+
+        let approved = repo
+            .approve_closing(&session.id, "emp-manager", Some("Estorno PIX".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(approved.status, "CLOSED");
+        assert_eq!(approved.approved_by_id.as_deref(), Some("emp-manager"));
+        assert!(approved.approved_at.is_some());
+
+        let report = repo.get_closing_report(&session.id, 5.0).await.unwrap();
+        assert_eq!(report.employee_name, "Test Employee");
+        assert_eq!(report.approved_by_name.as_deref(), Some("Gerente"));
+        let pix = report.counts.iter().find(|c| c.method == "PIX").unwrap();
+        assert_eq!(pix.difference, -20.0);
+    }
 }
@@ -0,0 +1,506 @@
+//! Repositório de Crediário (Fiado)
+
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    CustomerCreditAccount, CustomerStatement, PaymentMethod, Receivable, ReceivablePayment,
+    ReceiveAccountPayment, SaveCreditAccount,
+};
+use crate::repositories::new_id;
+use crate::services::credit::{installment_schedule, late_charges, round_cents, LateFeeRules};
+use chrono::{Duration, NaiveDate};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// Máximo de parcelas por venda fiado
+pub const MAX_INSTALLMENTS: u32 = 24;
+
+pub struct CreditAccountRepository<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> CreditAccountRepository<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    const ACCOUNT_COLS: &'static str = "customer_id, credit_limit, interest_rate, fine_percent, grace_days, default_installments, installment_interval_days, is_blocked, notes, created_at, updated_at";
+    const RECEIVABLE_COLS: &'static str = "id, customer_id, sale_id, installment_number, installment_count, amount, paid_amount, due_date, status, interest_paid_until, fine_charged, paid_at, created_at, updated_at";
+    const PAYMENT_COLS: &'static str = "id, customer_id, receivable_id, amount, interest, fine, total, method, cash_session_id, employee_id, created_at";
+
+    // ════════════════════════════════════════════════════════════════════════
+    // CONTA
+    // ════════════════════════════════════════════════════════════════════════
+
+    pub async fn find_account(
+        &self,
+        customer_id: &str,
+    ) -> AppResult<Option<CustomerCreditAccount>> {
+        let query = format!(
+            "SELECT {} FROM customer_credit_accounts WHERE customer_id = ?",
+            Self::ACCOUNT_COLS
+        );
+        let result = sqlx::query_as::<_, CustomerCreditAccount>(&query)
+            .bind(customer_id)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn save_account(&self, data: SaveCreditAccount) -> AppResult<CustomerCreditAccount> {
+        if data.credit_limit < 0.0 || data.interest_rate < 0.0 || data.fine_percent < 0.0 {
+            return Err(AppError::Validation(
+                "Limite, juros e multa não podem ser negativos".into(),
+            ));
+        }
+        if data.grace_days < 0 || data.installment_interval_days < 1 {
+            return Err(AppError::Validation(
+                "Carência e intervalo entre parcelas inválidos".into(),
+            ));
+        }
+        if data.default_installments < 1 || data.default_installments as u32 > MAX_INSTALLMENTS {
+            return Err(AppError::Validation(format!(
+                "Parcelas devem estar entre 1 e {}",
+                MAX_INSTALLMENTS
+            )));
+        }
+
+        let customer: Option<(String,)> = sqlx::query_as("SELECT id FROM customers WHERE id = ?")
+            .bind(&data.customer_id)
+            .fetch_optional(self.pool)
+            .await?;
+        if customer.is_none() {
+            return Err(AppError::NotFound {
+                entity: "Customer".into(),
+                id: data.customer_id,
+            });
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            "INSERT INTO customer_credit_accounts (customer_id, credit_limit, interest_rate, fine_percent, grace_days, default_installments, installment_interval_days, is_blocked, notes, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(customer_id) DO UPDATE SET
+                credit_limit = excluded.credit_limit,
+                interest_rate = excluded.interest_rate,
+                fine_percent = excluded.fine_percent,
+                grace_days = excluded.grace_days,
+                default_installments = excluded.default_installments,
+                installment_interval_days = excluded.installment_interval_days,
+                is_blocked = excluded.is_blocked,
+                notes = excluded.notes,
+                updated_at = excluded.updated_at",
+        )
+        .bind(&data.customer_id)
+        .bind(data.credit_limit)
+        .bind(data.interest_rate)
+        .bind(data.fine_percent)
+        .bind(data.grace_days)
+        .bind(data.default_installments)
+        .bind(data.installment_interval_days)
+        .bind(data.is_blocked)
+        .bind(&data.notes)
+        .bind(&now)
+        .bind(&now)
+        .execute(self.pool)
+        .await?;
+
+        self.find_account(&data.customer_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "CustomerCreditAccount".into(),
+                id: data.customer_id,
+            })
+    }
+
+    // ════════════════════════════════════════════════════════════════════════
+    // PARCELAS
+    // ════════════════════════════════════════════════════════════════════════
+
+    /// Parcelas em aberto do cliente, com encargos calculados para hoje
+    pub async fn find_open_receivables(&self, customer_id: &str) -> AppResult<Vec<Receivable>> {
+        let query = format!(
+            "SELECT {} FROM receivables WHERE customer_id = ? AND status = 'OPEN' ORDER BY due_date, installment_number",
+            Self::RECEIVABLE_COLS
+        );
+        let receivables = sqlx::query_as::<_, Receivable>(&query)
+            .bind(customer_id)
+            .fetch_all(self.pool)
+            .await?;
+        let rules = self.rules_for(customer_id).await?;
+        let today = chrono::Local::now().date_naive();
+        Ok(receivables
+            .into_iter()
+            .map(|r| with_charges(r, &rules, today))
+            .collect())
+    }
+
+    pub async fn find_by_sale(&self, sale_id: &str) -> AppResult<Vec<Receivable>> {
+        let query = format!(
+            "SELECT {} FROM receivables WHERE sale_id = ? ORDER BY installment_number",
+            Self::RECEIVABLE_COLS
+        );
+        let result = sqlx::query_as::<_, Receivable>(&query)
+            .bind(sale_id)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Parcelas vencidas além da carência, de todos os clientes
+    pub async fn find_overdue(&self) -> AppResult<Vec<Receivable>> {
+        let today = chrono::Local::now().date_naive();
+        let query = format!(
+            "SELECT {} FROM receivables WHERE status = 'OPEN' AND due_date < ? ORDER BY due_date, customer_id",
+            Self::RECEIVABLE_COLS
+        );
+        let receivables = sqlx::query_as::<_, Receivable>(&query)
+            .bind(today.format("%Y-%m-%d").to_string())
+            .fetch_all(self.pool)
+            .await?;
+
+        let mut rules_by_customer: HashMap<String, LateFeeRules> = HashMap::new();
+        let mut overdue = Vec::new();
+        for receivable in receivables {
+            if !rules_by_customer.contains_key(&receivable.customer_id) {
+                let rules = self.rules_for(&receivable.customer_id).await?;
+                rules_by_customer.insert(receivable.customer_id.clone(), rules);
+            }
+            let rules = rules_by_customer[&receivable.customer_id];
+            let receivable = with_charges(receivable, &rules, today);
+            if receivable.days_overdue as i64 > rules.grace_days {
+                overdue.push(receivable);
+            }
+        }
+        Ok(overdue)
+    }
+
+    /// Principal em aberto do cliente
+    pub async fn outstanding_balance(&self, customer_id: &str) -> AppResult<f64> {
+        let result: (f64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(amount - paid_amount), 0.0) FROM receivables WHERE customer_id = ? AND status = 'OPEN'",
+        )
+        .bind(customer_id)
+        .fetch_one(self.pool)
+        .await?;
+        Ok(round_cents(result.0))
+    }
+
+    /// Lança a parte fiado de uma venda no crediário (dentro da transação da venda)
+    pub async fn post_sale_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        customer_id: &str,
+        sale_id: &str,
+        amount: f64,
+        installments: Option<u32>,
+    ) -> AppResult<()> {
+        let query = format!(
+            "SELECT {} FROM customer_credit_accounts WHERE customer_id = ?",
+            Self::ACCOUNT_COLS
+        );
+        let account = sqlx::query_as::<_, CustomerCreditAccount>(&query)
+            .bind(customer_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| {
+                AppError::Validation("Cliente não possui crediário cadastrado".into())
+            })?;
+        if account.is_blocked {
+            return Err(AppError::Validation(
+                "Crediário do cliente está bloqueado".into(),
+            ));
+        }
+
+        let today = chrono::Local::now().date_naive();
+        let cutoff = today - Duration::days(account.grace_days as i64);
+        let overdue: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM receivables WHERE customer_id = ? AND status = 'OPEN' AND due_date < ?",
+        )
+        .bind(customer_id)
+        .bind(cutoff.format("%Y-%m-%d").to_string())
+        .fetch_one(&mut **tx)
+        .await?;
+        if overdue.0 > 0 {
+            return Err(AppError::Validation(
+                "Cliente possui parcelas em atraso no crediário".into(),
+            ));
+        }
+
+        let balance: (f64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(amount - paid_amount), 0.0) FROM receivables WHERE customer_id = ? AND status = 'OPEN'",
+        )
+        .bind(customer_id)
+        .fetch_one(&mut **tx)
+        .await?;
+        let available = round_cents(account.credit_limit - balance.0).max(0.0);
+        if amount > available + 0.005 {
+            return Err(AppError::Validation(format!(
+                "Limite de crédito excedido (disponível: R$ {:.2})",
+                available
+            )));
+        }
+
+        let count = installments.unwrap_or(account.default_installments.max(1) as u32);
+        if count == 0 || count > MAX_INSTALLMENTS {
+            return Err(AppError::Validation(format!(
+                "Parcelas devem estar entre 1 e {}",
+                MAX_INSTALLMENTS
+            )));
+        }
+
+        let interval = account.installment_interval_days.max(1) as i64;
+        let schedule =
+            installment_schedule(amount, count, today + Duration::days(interval), interval);
+        let now = chrono::Utc::now().to_rfc3339();
+        for (index, (due_date, value)) in schedule.into_iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO receivables (id, customer_id, sale_id, installment_number, installment_count, amount, paid_amount, due_date, status, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, 0, ?, 'OPEN', ?, ?)",
+            )
+            .bind(new_id())
+            .bind(customer_id)
+            .bind(sale_id)
+            .bind(index as i32 + 1)
+            .bind(count as i32)
+            .bind(value)
+            .bind(due_date.format("%Y-%m-%d").to_string())
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    // ════════════════════════════════════════════════════════════════════════
+    // RECEBIMENTOS
+    // ════════════════════════════════════════════════════════════════════════
+
+    /// Recebe um valor do cliente, quitando primeiro as parcelas mais antigas.
+    /// Em cada parcela os encargos (multa e juros) são pagos antes do principal.
+    pub async fn receive_payment(
+        &self,
+        data: ReceiveAccountPayment,
+    ) -> AppResult<Vec<ReceivablePayment>> {
+        if data.amount <= 0.0 {
+            return Err(AppError::Validation("Valor deve ser maior que zero".into()));
+        }
+        if data.method == PaymentMethod::Account {
+            return Err(AppError::Validation(
+                "Fiado não pode ser usado para pagar o crediário".into(),
+            ));
+        }
+        let cash_session_id = data
+            .cash_session_id
+            .clone()
+            .ok_or(AppError::CashSessionNotOpen)?;
+
+        let open = self.find_open_receivables(&data.customer_id).await?;
+        if open.is_empty() {
+            return Err(AppError::Validation(
+                "Cliente não possui parcelas em aberto".into(),
+            ));
+        }
+        let total_due = round_cents(open.iter().map(|r| r.amount_due).sum());
+        let amount = round_cents(data.amount);
+        if amount > total_due + 0.005 {
+            return Err(AppError::Validation(format!(
+                "Valor maior que o saldo devedor (R$ {:.2})",
+                total_due
+            )));
+        }
+
+        let method = data.method.as_str();
+        let today = chrono::Local::now()
+            .date_naive()
+            .format("%Y-%m-%d")
+            .to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut remaining = amount;
+        let mut payment_ids = Vec::new();
+
+        let mut tx = self.pool.begin().await?;
+        for receivable in open {
+            if remaining < 0.005 {
+                break;
+            }
+            let charges = round_cents(receivable.fine + receivable.interest);
+            if remaining + 0.005 < charges {
+                return Err(AppError::Validation(format!(
+                    "Valor não cobre os encargos da parcela {}/{} (R$ {:.2})",
+                    receivable.installment_number, receivable.installment_count, charges
+                )));
+            }
+
+            let outstanding = round_cents(receivable.amount - receivable.paid_amount);
+            let principal = round_cents((remaining - charges).min(outstanding));
+            let total = round_cents(principal + charges);
+            let paid_amount = round_cents(receivable.paid_amount + principal);
+            let settled = paid_amount + 0.005 >= receivable.amount;
+            let interest_paid_until = if charges > 0.0 {
+                Some(today.clone())
+            } else {
+                receivable.interest_paid_until.clone()
+            };
+
+            sqlx::query(
+                "UPDATE receivables SET paid_amount = ?, status = ?, paid_at = ?, interest_paid_until = ?, fine_charged = ?, updated_at = ? WHERE id = ?",
+            )
+            .bind(paid_amount)
+            .bind(if settled { "PAID" } else { "OPEN" })
+            .bind(if settled { Some(&now) } else { None })
+            .bind(&interest_paid_until)
+            .bind(receivable.fine_charged || receivable.fine > 0.0)
+            .bind(&now)
+            .bind(&receivable.id)
+            .execute(&mut *tx)
+            .await?;
+
+            let payment_id = new_id();
+            sqlx::query(
+                "INSERT INTO receivable_payments (id, customer_id, receivable_id, amount, interest, fine, total, method, cash_session_id, employee_id, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&payment_id)
+            .bind(&data.customer_id)
+            .bind(&receivable.id)
+            .bind(principal)
+            .bind(receivable.interest)
+            .bind(receivable.fine)
+            .bind(total)
+            .bind(method)
+            .bind(&cash_session_id)
+            .bind(&data.employee_id)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+
+            payment_ids.push(payment_id);
+            remaining = round_cents(remaining - total);
+        }
+        tx.commit().await?;
+
+        let mut payments = Vec::new();
+        for id in payment_ids {
+            payments.extend(self.find_payment(&id).await?);
+        }
+        Ok(payments)
+    }
+
+    async fn find_payment(&self, id: &str) -> AppResult<Option<ReceivablePayment>> {
+        let query = format!(
+            "SELECT {} FROM receivable_payments WHERE id = ?",
+            Self::PAYMENT_COLS
+        );
+        let result = sqlx::query_as::<_, ReceivablePayment>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_payments_by_customer(
+        &self,
+        customer_id: &str,
+        limit: i32,
+    ) -> AppResult<Vec<ReceivablePayment>> {
+        let query = format!(
+            "SELECT {} FROM receivable_payments WHERE customer_id = ? ORDER BY created_at DESC LIMIT ?",
+            Self::PAYMENT_COLS
+        );
+        let result = sqlx::query_as::<_, ReceivablePayment>(&query)
+            .bind(customer_id)
+            .bind(limit)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    // ════════════════════════════════════════════════════════════════════════
+    // EXTRATO
+    // ════════════════════════════════════════════════════════════════════════
+
+    pub async fn get_statement(&self, customer_id: &str) -> AppResult<CustomerStatement> {
+        let customer: (String,) = sqlx::query_as("SELECT name FROM customers WHERE id = ?")
+            .bind(customer_id)
+            .fetch_optional(self.pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "Customer".into(),
+                id: customer_id.into(),
+            })?;
+
+        let account = self.find_account(customer_id).await?;
+        let open_receivables = self.find_open_receivables(customer_id).await?;
+        let recent_payments = self.find_payments_by_customer(customer_id, 20).await?;
+
+        let balance = round_cents(
+            open_receivables
+                .iter()
+                .map(|r| r.amount - r.paid_amount)
+                .sum(),
+        );
+        let charges = round_cents(open_receivables.iter().map(|r| r.fine + r.interest).sum());
+        let overdue_amount = round_cents(
+            open_receivables
+                .iter()
+                .filter(|r| r.days_overdue > 0)
+                .map(|r| r.amount_due)
+                .sum(),
+        );
+        let available_credit = account
+            .as_ref()
+            .map(|a| round_cents(a.credit_limit - balance).max(0.0))
+            .unwrap_or(0.0);
+
+        Ok(CustomerStatement {
+            customer_id: customer_id.to_string(),
+            customer_name: customer.0,
+            account,
+            balance,
+            charges,
+            total_due: round_cents(balance + charges),
+            overdue_amount,
+            available_credit,
+            open_receivables,
+            recent_payments,
+            generated_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    async fn rules_for(&self, customer_id: &str) -> AppResult<LateFeeRules> {
+        Ok(self
+            .find_account(customer_id)
+            .await?
+            .map(|a| LateFeeRules {
+                interest_rate: a.interest_rate,
+                fine_percent: a.fine_percent,
+                grace_days: a.grace_days as i64,
+            })
+            .unwrap_or_default())
+    }
+}
+
+/// Preenche multa, juros e valor devido da parcela para a data informada
+fn with_charges(mut receivable: Receivable, rules: &LateFeeRules, today: NaiveDate) -> Receivable {
+    let outstanding = round_cents(receivable.amount - receivable.paid_amount);
+    let due_date = NaiveDate::parse_from_str(&receivable.due_date, "%Y-%m-%d").unwrap_or(today);
+    let interest_paid_until = receivable
+        .interest_paid_until
+        .as_deref()
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+    let charges = late_charges(
+        outstanding,
+        due_date,
+        interest_paid_until,
+        receivable.fine_charged,
+        today,
+        rules,
+    );
+    receivable.days_overdue = charges.days_overdue.max(0) as i32;
+    receivable.fine = charges.fine;
+    receivable.interest = charges.interest;
+    receivable.amount_due = round_cents(outstanding + charges.total());
+    receivable
+}
@@ -0,0 +1,213 @@
+//! Testes unitários para CreditAccountRepository
+
+#[cfg(test)]
+mod tests {
+    use super::super::credit_account_repository::CreditAccountRepository;
+    use crate::error::AppError;
+    use crate::models::{
+        CreateSale, CreateSaleItem, CreateSalePayment, PaymentMethod, ReceiveAccountPayment,
+        SaveCreditAccount,
+    };
+    use crate::repositories::test_support::{seed_pdv, setup_file_db};
+    use crate::repositories::{CashRepository, SaleRepository};
+    use chrono::Duration;
+    use sqlx::SqlitePool;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = setup_file_db("credit_account").await;
+        seed_pdv(&pool, 25.0, 50.0).await;
+        sqlx::query("INSERT INTO customers (id, name, is_active, created_at, updated_at) VALUES ('cus-001', 'Dona Maria', 1, datetime('now'), datetime('now'))").execute(&pool).await.unwrap();
+        pool
+    }
+
+    fn validation_message(result: Result<impl std::fmt::Debug, AppError>) -> String {
+        match result {
+            Err(AppError::Validation(message)) => message,
+            other => panic!("esperava erro de validação, veio {:?}", other),
+        }
+    }
+
+    fn account(credit_limit: f64) -> SaveCreditAccount {
+        SaveCreditAccount {
+            customer_id: "cus-001".to_string(),
+            credit_limit,
+            interest_rate: 3.0,
+            fine_percent: 2.0,
+            grace_days: 0,
+            default_installments: 1,
+            installment_interval_days: 30,
+            is_blocked: false,
+            notes: None,
+        }
+    }
+
+    fn credit_sale(quantity: f64, installments: Option<u32>) -> CreateSale {
+        CreateSale {
+            customer_id: Some("cus-001".to_string()),
+            employee_id: "emp-001".to_string(),
+            cash_session_id: "cs-001".to_string(),
+            items: vec![CreateSaleItem {
+                product_id: "prod-001".to_string(),
+                quantity,
+                unit_price: 25.0,
+                discount: Some(0.0),
+            }],
+            payments: vec![CreateSalePayment {
+                method: PaymentMethod::Account,
+                amount: quantity * 25.0,
+                installments,
+                store_credit_code: None,
+                card: None,
+            }],
+            amount_paid: quantity * 25.0,
+            discount_type: None,
+            discount_value: None,
+            discount_reason: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_credit_sale_requires_account_and_limit() {
+        let pool = setup_test_db().await;
+        let repo = CreditAccountRepository::new(&pool);
+        let sales = SaleRepository::new(&pool);
+
+        // Sem crediário cadastrado
+        let message = validation_message(sales.create(credit_sale(4.0, None)).await);
+        assert!(message.contains("não possui crediário"));
+
+        let mut invalid = account(200.0);
+        invalid.default_installments = 0;
+        let message = validation_message(repo.save_account(invalid).await);
+        assert!(message.starts_with("Parcelas devem estar entre 1 e"));
+        repo.save_account(account(200.0)).await.unwrap();
+
+        // Sem cliente identificado
+        let mut anonymous = credit_sale(4.0, None);
+        anonymous.customer_id = None;
+        let message = validation_message(sales.create(anonymous).await);
+        assert!(message.contains("exige cliente identificado"));
+
+        // Acima do limite (R$ 250 > R$ 200)
+        let message = validation_message(sales.create(credit_sale(10.0, None)).await);
+        assert!(message.starts_with("Limite de crédito excedido"));
+
+        let sale = sales.create(credit_sale(4.0, Some(3))).await.unwrap();
+        let installments = repo.find_by_sale(&sale.id).await.unwrap();
+        assert_eq!(installments.len(), 3);
+        assert_eq!(installments[0].amount, 33.34);
+        assert_eq!(installments[2].amount, 33.33);
+        assert_eq!(installments[2].installment_count, 3);
+        assert_eq!(repo.outstanding_balance("cus-001").await.unwrap(), 100.0);
+
+        let statement = repo.get_statement("cus-001").await.unwrap();
+        assert_eq!(statement.customer_name, "Dona Maria");
+        assert_eq!(statement.available_credit, 100.0);
+        assert_eq!(statement.open_receivables.len(), 3);
+
+        // Cancelar a venda cancela as parcelas
+        sales.cancel(&sale.id, "emp-001", "Teste").await.unwrap();
+        assert_eq!(repo.outstanding_balance("cus-001").await.unwrap(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_receive_payment_charges_overdue_and_enters_cash_session() {
+        let pool = setup_test_db().await;
+        let repo = CreditAccountRepository::new(&pool);
+        repo.save_account(account(500.0)).await.unwrap();
+        SaleRepository::new(&pool)
+            .create(credit_sale(4.0, Some(1)))
+            .await
+            .unwrap();
+
+        // Parcela vencida há 10 dias: multa 2% + juros 3% a.m. pro rata (R$ 1)
+        let due = chrono::Local::now().date_naive() - Duration::days(10);
+        sqlx::query("UPDATE receivables SET due_date = ?")
+            .bind(due.format("%Y-%m-%d").to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+        let overdue = repo.find_overdue().await.unwrap();
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].fine, 2.0);
+        assert_eq!(overdue[0].interest, 1.0);
+        assert_eq!(overdue[0].amount_due, 103.0);
+
+        // Cliente em atraso não compra fiado
+        sqlx::query("UPDATE sales SET created_at = '2000-01-01T00:00:00+00:00'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let message = validation_message(
+            SaleRepository::new(&pool)
+                .create(credit_sale(1.0, None))
+                .await,
+        );
+        assert!(message.contains("parcelas em atraso"));
+
+        let mut input = ReceiveAccountPayment {
+            customer_id: "cus-001".to_string(),
+            amount: 200.0,
+            method: PaymentMethod::Cash,
+            employee_id: "emp-001".to_string(),
+            cash_session_id: Some("cs-001".to_string()),
+        };
+        let message = validation_message(repo.receive_payment(input.clone()).await);
+        assert!(message.starts_with("Valor maior que o saldo devedor"));
+
+        // Parcial: encargos primeiro, R$ 50 de principal
+        input.amount = 53.0;
+        let payments = repo.receive_payment(input.clone()).await.unwrap();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].amount, 50.0);
+        assert_eq!(payments[0].fine, 2.0);
+        assert_eq!(payments[0].interest, 1.0);
+
+        // Multa e juros já quitados até hoje: resta só o principal
+        let statement = repo.get_statement("cus-001").await.unwrap();
+        assert_eq!(statement.balance, 50.0);
+        assert_eq!(statement.charges, 0.0);
+
+        input.amount = 50.0;
+        input.method = PaymentMethod::Pix;
+        repo.receive_payment(input).await.unwrap();
+        assert_eq!(repo.outstanding_balance("cus-001").await.unwrap(), 0.0);
+
+        let summary = CashRepository::new(&pool)
+            .get_session_summary("cs-001")
+            .await
+            .unwrap();
+        assert_eq!(summary.total_account_receipts, 103.0);
+        assert_eq!(summary.cash_in_drawer, 100.0 + 53.0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_rejected_after_installment_received() {
+        let pool = setup_test_db().await;
+        let repo = CreditAccountRepository::new(&pool);
+        repo.save_account(account(500.0)).await.unwrap();
+        let sales = SaleRepository::new(&pool);
+        let sale = sales.create(credit_sale(4.0, Some(2))).await.unwrap();
+
+        repo.receive_payment(ReceiveAccountPayment {
+            customer_id: "cus-001".to_string(),
+            amount: 30.0,
+            method: PaymentMethod::Cash,
+            employee_id: "emp-001".to_string(),
+            cash_session_id: Some("cs-001".to_string()),
+        })
+        .await
+        .unwrap();
+
+        let err = sales
+            .cancel(&sale.id, "emp-001", "Teste")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::SaleCannotBeCanceled(_)));
+
+        // Parcelas e saldo continuam como antes
+        assert_eq!(repo.outstanding_balance("cus-001").await.unwrap(), 70.0);
+        let installments = repo.find_by_sale(&sale.id).await.unwrap();
+        assert!(installments.iter().all(|r| r.status != "CANCELED"));
+    }
+}
@@ -0,0 +1,212 @@
+//! Repositório de Documentos Fiscais emitidos (XML autorizado)
+
+use crate::error::{AppError, AppResult};
+use crate::models::{CreateFiscalDocument, FiscalDocument};
+use crate::repositories::new_id;
+use chrono::Datelike;
+use sqlx::SqlitePool;
+
+pub struct FiscalDocumentRepository<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> FiscalDocumentRepository<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    const COLS: &'static str = "id, sale_id, service_order_id, access_key, model, serie, number, emission_type, environment, status, protocol, total, xml, issued_at, authorized_at, created_at, updated_at";
+
+    pub async fn find_by_access_key(&self, access_key: &str) -> AppResult<Option<FiscalDocument>> {
+        let query = format!(
+            "SELECT {} FROM fiscal_documents WHERE access_key = ?",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, FiscalDocument>(&query)
+            .bind(access_key)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_by_sale(&self, sale_id: &str) -> AppResult<Vec<FiscalDocument>> {
+        let query = format!(
+            "SELECT {} FROM fiscal_documents WHERE sale_id = ? ORDER BY issued_at ASC",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, FiscalDocument>(&query)
+            .bind(sale_id)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_by_service_order(&self, order_id: &str) -> AppResult<Vec<FiscalDocument>> {
+        let query = format!(
+            "SELECT {} FROM fiscal_documents WHERE service_order_id = ? ORDER BY issued_at ASC",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, FiscalDocument>(&query)
+            .bind(order_id)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Documentos emitidos no mês (formato YYYY-MM, horário de Brasília), em ordem de série/número
+    pub async fn find_by_month(&self, year_month: &str) -> AppResult<Vec<FiscalDocument>> {
+        let (start, end) = brasilia_month_utc_range(year_month)?;
+        let query = format!(
+            "SELECT {} FROM fiscal_documents WHERE datetime(issued_at) >= ? AND datetime(issued_at) < ? ORDER BY model, serie, number",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, FiscalDocument>(&query)
+            .bind(start)
+            .bind(end)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Insere o documento ou substitui o registro existente com a mesma chave
+    pub async fn upsert(&self, data: CreateFiscalDocument) -> AppResult<FiscalDocument> {
+        let id = new_id();
+        let now = chrono::Utc::now().to_rfc3339();
+        let authorized_at = data.protocol.as_ref().map(|_| now.clone());
+
+        sqlx::query(
+            r#"
+            INSERT INTO fiscal_documents (id, sale_id, service_order_id, access_key, model, serie, number, emission_type, environment, status, protocol, total, xml, issued_at, authorized_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(access_key) DO UPDATE SET
+                status = excluded.status,
+                protocol = excluded.protocol,
+                xml = excluded.xml,
+                authorized_at = excluded.authorized_at,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&id)
+        .bind(&data.sale_id)
+        .bind(&data.service_order_id)
+        .bind(&data.access_key)
+        .bind(data.model)
+        .bind(data.serie)
+        .bind(data.number)
+        .bind(data.emission_type)
+        .bind(data.environment)
+        .bind(&data.status)
+        .bind(&data.protocol)
+        .bind(data.total)
+        .bind(&data.xml)
+        .bind(&data.issued_at)
+        .bind(&authorized_at)
+        .bind(&now)
+        .bind(&now)
+        .execute(self.pool)
+        .await?;
+
+        self.find_by_access_key(&data.access_key)
+            .await?
+            .ok_or_else(|| crate::error::AppError::NotFound {
+                entity: "FiscalDocument".into(),
+                id: data.access_key.clone(),
+            })
+    }
+
+    /// Nota de contingência autorizada posteriormente: grava protocolo e nfeProc
+    pub async fn mark_authorized(
+        &self,
+        access_key: &str,
+        protocol: &str,
+        proc_xml: &str,
+    ) -> AppResult<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            "UPDATE fiscal_documents SET status = 'AUTHORIZED', protocol = ?, xml = ?, authorized_at = ?, updated_at = ? WHERE access_key = ?",
+        )
+        .bind(protocol)
+        .bind(proc_xml)
+        .bind(&now)
+        .bind(&now)
+        .bind(access_key)
+        .execute(self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_status(&self, access_key: &str, status: &str) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE fiscal_documents SET status = ?, updated_at = (datetime('now')) WHERE access_key = ?",
+        )
+        .bind(status)
+        .bind(access_key)
+        .execute(self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Converte um mês local (YYYY-MM, America/Sao_Paulo) no intervalo UTC [início, fim)
+/// usado em `issued_at`
+fn brasilia_month_utc_range(year_month: &str) -> AppResult<(String, String)> {
+    let start = chrono::NaiveDate::parse_from_str(&format!("{}-01", year_month), "%Y-%m-%d")
+        .map_err(|_| AppError::InvalidValue {
+            field: "year_month".into(),
+            message: format!("mês inválido: {} (use YYYY-MM)", year_month),
+        })?;
+    // Documentos do leiaute 4.00 não existem antes disso; regras de horário de
+    // verão anteriores a 2008 variavam ano a ano
+    if start.year() < 2008 {
+        return Err(AppError::Validation(format!(
+            "mês inválido: {} (exportação disponível a partir de 2008-01)",
+            year_month
+        )));
+    }
+    let end = start
+        .checked_add_months(chrono::Months::new(1))
+        .ok_or_else(|| AppError::Validation(format!("mês inválido: {}", year_month)))?;
+
+    let to_utc = |date: chrono::NaiveDate| {
+        (date.and_time(chrono::NaiveTime::MIN)
+            + chrono::Duration::hours(sao_paulo_month_start_offset_hours(date)))
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+    };
+    Ok((to_utc(start), to_utc(end)))
+}
+
+/// Horas atrás de UTC em America/Sao_Paulo à 0h do primeiro dia do mês.
+///
+/// Desde o Decreto 9.772/2019 o fuso é UTC-3 o ano todo. Até o verão 2018/2019
+/// valia o horário de verão (UTC-2) do Decreto 6.558/2008: do terceiro domingo de
+/// outubro (primeiro domingo de novembro a partir de 2018, Decreto 9.242/2017) ao
+/// terceiro domingo de fevereiro. O adiamento do fim no Carnaval só o move dentro
+/// de fevereiro, o que não muda a virada de nenhum mês.
+fn sao_paulo_month_start_offset_hours(month_start: chrono::NaiveDate) -> i64 {
+    use chrono::{NaiveDate, Weekday};
+
+    let season = if month_start.month() >= 7 {
+        month_start.year()
+    } else {
+        month_start.year() - 1
+    };
+    if !(2007..=2018).contains(&season) {
+        return 3;
+    }
+    let sunday = |year: i32, month: u32, n: u8| {
+        NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n)
+            .expect("domingo existe no mês")
+    };
+    let dst_start = if season >= 2018 {
+        sunday(season, 11, 1)
+    } else {
+        sunday(season, 10, 3)
+    };
+    let dst_end = sunday(season + 1, 2, 3);
+    if month_start >= dst_start && month_start < dst_end {
+        2
+    } else {
+        3
+    }
+}
@@ -0,0 +1,156 @@
+//! Testes unitários para FiscalDocumentRepository
+
+#[cfg(test)]
+mod tests {
+    use super::super::fiscal_document_repository::FiscalDocumentRepository;
+    use crate::models::CreateFiscalDocument;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        pool
+    }
+
+    fn document(access_key: &str, number: i32, issued_at: &str) -> CreateFiscalDocument {
+        CreateFiscalDocument {
+            sale_id: None,
+            service_order_id: None,
+            access_key: access_key.to_string(),
+            model: 65,
+            serie: 1,
+            number,
+            emission_type: 9,
+            environment: 2,
+            status: "CONTINGENCY".to_string(),
+            protocol: None,
+            total: 25.5,
+            xml: "<NFe/>".to_string(),
+            issued_at: issued_at.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upsert_and_mark_authorized() {
+        let pool = setup_test_db().await;
+        let repo = FiscalDocumentRepository::new(&pool);
+        let key = "35260112345678000190650010000000019123456789";
+
+        let created = repo
+            .upsert(document(key, 1, "2026-01-10T10:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(created.status, "CONTINGENCY");
+        assert!(created.protocol.is_none());
+
+        repo.mark_authorized(key, "135260000000001", "<nfeProc/>")
+            .await
+            .unwrap();
+
+        let doc = repo.find_by_access_key(key).await.unwrap().unwrap();
+        assert_eq!(doc.status, "AUTHORIZED");
+        assert_eq!(doc.protocol.as_deref(), Some("135260000000001"));
+        assert_eq!(doc.xml, "<nfeProc/>");
+        assert!(doc.authorized_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_find_by_month() {
+        let pool = setup_test_db().await;
+        let repo = FiscalDocumentRepository::new(&pool);
+
+        repo.upsert(document(
+            "35260112345678000190650010000000029123456789",
+            2,
+            "2026-01-31T23:00:00Z",
+        ))
+        .await
+        .unwrap();
+        repo.upsert(document(
+            "35260212345678000190650010000000039123456789",
+            3,
+            "2026-02-01T09:00:00Z",
+        ))
+        .await
+        .unwrap();
+
+        // 31/12 às 23h em Brasília: ainda é dezembro
+        repo.upsert(document(
+            "35260112345678000190650010000000019123456789",
+            1,
+            "2026-01-01T02:00:00Z",
+        ))
+        .await
+        .unwrap();
+        // 31/01 às 22h30 em Brasília: entra em janeiro
+        repo.upsert(document(
+            "35260212345678000190650010000000049123456789",
+            4,
+            "2026-02-01T01:30:00Z",
+        ))
+        .await
+        .unwrap();
+
+        let january = repo.find_by_month("2026-01").await.unwrap();
+        let numbers: Vec<i32> = january.iter().map(|d| d.number).collect();
+        assert_eq!(numbers, vec![2, 4]);
+
+        let december = repo.find_by_month("2025-12").await.unwrap();
+        assert_eq!(december.len(), 1);
+        assert_eq!(december[0].number, 1);
+
+        assert!(repo.find_by_month("2026-13").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_find_by_month_daylight_saving() {
+        let pool = setup_test_db().await;
+        let repo = FiscalDocumentRepository::new(&pool);
+
+        // 30/11/2018 às 23h30 em horário de verão (UTC-2): ainda é novembro
+        repo.upsert(document(
+            "35181112345678000190650010000000011123456789",
+            1,
+            "2018-12-01T01:30:00Z",
+        ))
+        .await
+        .unwrap();
+        // 01/12/2018 à 0h30 (UTC-2)
+        repo.upsert(document(
+            "35181212345678000190650010000000021123456789",
+            2,
+            "2018-12-01T02:30:00Z",
+        ))
+        .await
+        .unwrap();
+        // 28/02/2019 às 23h30, já sem horário de verão (UTC-3)
+        repo.upsert(document(
+            "35190212345678000190650010000000031123456789",
+            3,
+            "2019-03-01T02:30:00Z",
+        ))
+        .await
+        .unwrap();
+
+        let november = repo.find_by_month("2018-11").await.unwrap();
+        assert_eq!(november.len(), 1);
+        assert_eq!(november[0].number, 1);
+
+        let december = repo.find_by_month("2018-12").await.unwrap();
+        assert_eq!(december.len(), 1);
+        assert_eq!(december[0].number, 2);
+
+        let february = repo.find_by_month("2019-02").await.unwrap();
+        assert_eq!(february.len(), 1);
+        assert_eq!(february[0].number, 3);
+
+        assert!(repo.find_by_month("2007-12").await.is_err());
+    }
+}
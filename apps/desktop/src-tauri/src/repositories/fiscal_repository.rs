@@ -1,7 +1,11 @@
 //! Repositório de Configurações Fiscais (NFC-e)
 
 use crate::error::AppResult;
-use crate::models::{FiscalSettings, UpdateFiscalSettings};
+use crate::models::{
+    CreateFiscalEvent, CustomerFiscalData, FiscalEvent, FiscalSettings, ProductFiscalInfo,
+    SaleFiscalLink, UpdateFiscalSettings,
+};
+use crate::repositories::new_id;
 use sqlx::SqlitePool;
 
 pub struct FiscalRepository<'a> {
@@ -14,7 +18,8 @@ impl<'a> FiscalRepository<'a> {
     }
 
     const COLS: &'static str =
-        "enabled, uf, environment, serie, next_number, csc_id, csc, cert_path, cert_password, updated_at";
+        "enabled, uf, environment, serie, next_number, csc_id, csc, cert_path, cert_password, crt, nfe_serie, nfe_next_number, updated_at";
+    const EVENT_COLS: &'static str = "id, sale_id, access_key, event_type, sequence, serie, number_start, number_end, justification, status_code, status_message, protocol, xml, created_at";
 
     pub async fn get(&self) -> AppResult<FiscalSettings> {
         let query = format!("SELECT {} FROM fiscal_settings WHERE id = 1", Self::COLS);
@@ -39,6 +44,9 @@ impl<'a> FiscalRepository<'a> {
                 csc = ?, 
                 cert_path = ?, 
                 cert_password = ?, 
+                crt = ?, 
+                nfe_serie = ?, 
+                nfe_next_number = ?, 
                 updated_at = ? 
             WHERE id = 1",
         )
@@ -51,6 +59,9 @@ impl<'a> FiscalRepository<'a> {
         .bind(data.csc.or(current.csc))
         .bind(data.cert_path.or(current.cert_path))
         .bind(data.cert_password.or(current.cert_password))
+        .bind(data.crt.unwrap_or(current.crt))
+        .bind(data.nfe_serie.unwrap_or(current.nfe_serie))
+        .bind(data.nfe_next_number.unwrap_or(current.nfe_next_number))
         .bind(&now)
         .execute(self.pool)
         .await?;
@@ -69,4 +80,194 @@ impl<'a> FiscalRepository<'a> {
 
         Ok(next)
     }
+
+    /// Avança a numeração da NF-e (modelo 55)
+    pub async fn increment_nfe_number(&self) -> AppResult<i32> {
+        let current = self.get().await?;
+        let next = current.nfe_next_number + 1;
+
+        sqlx::query("UPDATE fiscal_settings SET nfe_next_number = ? WHERE id = 1")
+            .bind(next)
+            .execute(self.pool)
+            .await?;
+
+        Ok(next)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // DADOS FISCAIS DE CLIENTES E PRODUTOS (NF-e)
+    // ═══════════════════════════════════════════════════════════════════════
+
+    pub async fn find_customer_fiscal_data(
+        &self,
+        customer_id: &str,
+    ) -> AppResult<Option<CustomerFiscalData>> {
+        let result = sqlx::query_as::<_, CustomerFiscalData>(
+            "SELECT id AS customer_id, cnpj, state_registration, city_code FROM customers WHERE id = ?",
+        )
+        .bind(customer_id)
+        .fetch_optional(self.pool)
+        .await?;
+        Ok(result)
+    }
+
+    pub async fn update_customer_fiscal_data(&self, data: &CustomerFiscalData) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE customers SET cnpj = ?, state_registration = ?, city_code = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&data.cnpj)
+        .bind(&data.state_registration)
+        .bind(&data.city_code)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(&data.customer_id)
+        .execute(self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_product_fiscal_info(
+        &self,
+        product_id: &str,
+    ) -> AppResult<Option<ProductFiscalInfo>> {
+        let result = sqlx::query_as::<_, ProductFiscalInfo>(
+            "SELECT id, internal_code, barcode, ncm FROM products WHERE id = ?",
+        )
+        .bind(product_id)
+        .fetch_optional(self.pool)
+        .await?;
+        Ok(result)
+    }
+
+    pub async fn set_product_ncm(&self, product_id: &str, ncm: Option<&str>) -> AppResult<()> {
+        sqlx::query("UPDATE products SET ncm = ?, updated_at = ? WHERE id = ?")
+            .bind(ncm)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(product_id)
+            .execute(self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // VÍNCULO VENDA ↔ NFC-e
+    // ═══════════════════════════════════════════════════════════════════════
+
+    pub async fn link_sale(
+        &self,
+        sale_id: &str,
+        access_key: &str,
+        protocol: Option<&str>,
+    ) -> AppResult<()> {
+        let authorized_at = protocol.map(|_| chrono::Utc::now().to_rfc3339());
+        sqlx::query(
+            "UPDATE sales SET nfce_access_key = ?, nfce_protocol = ?, nfce_authorized_at = ? WHERE id = ?",
+        )
+        .bind(access_key)
+        .bind(protocol)
+        .bind(authorized_at)
+        .bind(sale_id)
+        .execute(self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_sale_link(&self, sale_id: &str) -> AppResult<Option<SaleFiscalLink>> {
+        let result = sqlx::query_as::<_, SaleFiscalLink>(
+            "SELECT id AS sale_id, nfce_access_key, nfce_protocol, nfce_authorized_at, nfce_cancel_protocol FROM sales WHERE id = ?",
+        )
+        .bind(sale_id)
+        .fetch_optional(self.pool)
+        .await?;
+        Ok(result)
+    }
+
+    pub async fn set_sale_cancel_protocol(&self, sale_id: &str, protocol: &str) -> AppResult<()> {
+        sqlx::query("UPDATE sales SET nfce_cancel_protocol = ? WHERE id = ?")
+            .bind(protocol)
+            .bind(sale_id)
+            .execute(self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // EVENTOS FISCAIS
+    // ═══════════════════════════════════════════════════════════════════════
+
+    pub async fn record_event(&self, data: CreateFiscalEvent) -> AppResult<FiscalEvent> {
+        let id = new_id();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO fiscal_events (id, sale_id, access_key, event_type, sequence, serie, number_start, number_end, justification, status_code, status_message, protocol, xml, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&data.sale_id)
+        .bind(&data.access_key)
+        .bind(&data.event_type)
+        .bind(data.sequence)
+        .bind(data.serie)
+        .bind(data.number_start)
+        .bind(data.number_end)
+        .bind(&data.justification)
+        .bind(&data.status_code)
+        .bind(&data.status_message)
+        .bind(&data.protocol)
+        .bind(&data.xml)
+        .bind(&now)
+        .execute(self.pool)
+        .await?;
+
+        let query = format!(
+            "SELECT {} FROM fiscal_events WHERE id = ?",
+            Self::EVENT_COLS
+        );
+        let event = sqlx::query_as::<_, FiscalEvent>(&query)
+            .bind(&id)
+            .fetch_one(self.pool)
+            .await?;
+        Ok(event)
+    }
+
+    /// Próximo nSeqEvento do evento para a nota (tentativas rejeitadas também contam)
+    pub async fn next_event_sequence(&self, access_key: &str, event_type: &str) -> AppResult<i32> {
+        let result: (i64,) = sqlx::query_as(
+            "SELECT COALESCE(MAX(sequence), 0) FROM fiscal_events WHERE access_key = ? AND event_type = ?",
+        )
+        .bind(access_key)
+        .bind(event_type)
+        .fetch_one(self.pool)
+        .await?;
+        Ok(result.0 as i32 + 1)
+    }
+
+    /// Próxima tentativa de inutilização da mesma faixa
+    pub async fn next_inutilization_sequence(
+        &self,
+        serie: i32,
+        number_start: i32,
+        number_end: i32,
+    ) -> AppResult<i32> {
+        let result: (i64,) = sqlx::query_as(
+            "SELECT COALESCE(MAX(sequence), 0) FROM fiscal_events WHERE event_type = 'INUTILIZATION' AND serie = ? AND number_start = ? AND number_end = ?",
+        )
+        .bind(serie)
+        .bind(number_start)
+        .bind(number_end)
+        .fetch_one(self.pool)
+        .await?;
+        Ok(result.0 as i32 + 1)
+    }
+
+    pub async fn find_events_by_access_key(&self, access_key: &str) -> AppResult<Vec<FiscalEvent>> {
+        let query = format!(
+            "SELECT {} FROM fiscal_events WHERE access_key = ? ORDER BY created_at ASC",
+            Self::EVENT_COLS
+        );
+        let result = sqlx::query_as::<_, FiscalEvent>(&query)
+            .bind(access_key)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
 }
@@ -0,0 +1,64 @@
+//! Repositório da tabela IBPT (alíquotas aproximadas - Lei 12.741/2012)
+
+use crate::error::AppResult;
+use crate::models::IbptRate;
+use sqlx::SqlitePool;
+
+pub struct IbptRepository<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> IbptRepository<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    const COLS: &'static str = "ncm, description, federal_rate, import_rate, state_rate, municipal_rate, valid_until, version, source";
+
+    pub async fn find_by_ncm(&self, ncm: &str) -> AppResult<Option<IbptRate>> {
+        let query = format!("SELECT {} FROM ibpt_rates WHERE ncm = ?", Self::COLS);
+        let result = sqlx::query_as::<_, IbptRate>(&query)
+            .bind(ncm)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Substitui as alíquotas importadas (uma linha por NCM)
+    pub async fn upsert_many(&self, rates: &[IbptRate]) -> AppResult<usize> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+
+        for rate in rates {
+            sqlx::query(
+                "INSERT INTO ibpt_rates (ncm, description, federal_rate, import_rate, state_rate, municipal_rate, valid_until, version, source, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT(ncm) DO UPDATE SET
+                    description = excluded.description,
+                    federal_rate = excluded.federal_rate,
+                    import_rate = excluded.import_rate,
+                    state_rate = excluded.state_rate,
+                    municipal_rate = excluded.municipal_rate,
+                    valid_until = excluded.valid_until,
+                    version = excluded.version,
+                    source = excluded.source,
+                    updated_at = excluded.updated_at",
+            )
+            .bind(&rate.ncm)
+            .bind(&rate.description)
+            .bind(rate.federal_rate)
+            .bind(rate.import_rate)
+            .bind(rate.state_rate)
+            .bind(rate.municipal_rate)
+            .bind(&rate.valid_until)
+            .bind(&rate.version)
+            .bind(&rate.source)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(rates.len())
+    }
+}
@@ -3,23 +3,32 @@
 pub mod alert_repository;
 pub mod cash_repository;
 pub mod category_repository;
+pub mod credit_account_repository;
 pub mod customer_repository;
 pub mod employee_repository;
+pub mod fiscal_document_repository;
 pub mod fiscal_repository;
 pub mod held_sale_repository;
+pub mod ibpt_repository;
 pub mod inventory_repository;
 
 #[cfg(test)]
 pub mod inventory_repository_test;
 
 pub mod price_history_repository;
+pub mod pricing_rule_repository;
+pub mod print_job_repository;
 pub mod product_lot_repository;
 pub mod product_repository;
+pub mod purchase_repository;
 pub mod sale_repository;
+pub mod sale_return_repository;
 pub mod service_order_repository;
 pub mod settings_repository;
 pub mod stock_repository;
+pub mod store_credit_repository;
 pub mod supplier_repository;
+pub mod tax_rule_repository;
 pub mod vehicle_repository;
 pub mod warranty_repository;
 
@@ -38,16 +47,30 @@ mod activity_repository_test;
 #[cfg(test)]
 mod contract_repository_test;
 #[cfg(test)]
+mod credit_account_repository_test;
+#[cfg(test)]
 mod customer_repository_test;
 #[cfg(test)]
 mod enterprise_inventory_repository_test;
 #[cfg(test)]
+mod fiscal_document_repository_test;
+#[cfg(test)]
 mod material_request_repository_test;
 #[cfg(test)]
+mod price_history_repository_test;
+#[cfg(test)]
+mod pricing_rule_repository_test;
+#[cfg(test)]
+mod print_job_repository_test;
+#[cfg(test)]
 mod product_lot_repository_test;
 #[cfg(test)]
+mod purchase_repository_test;
+#[cfg(test)]
 mod report_motoparts_repository_test;
 #[cfg(test)]
+mod sale_return_repository_test;
+#[cfg(test)]
 mod service_order_repository_test;
 #[cfg(test)]
 mod settings_repository_test;
@@ -56,26 +79,41 @@ mod stock_location_repository_test;
 #[cfg(test)]
 mod stock_repository_test;
 #[cfg(test)]
+mod store_credit_repository_test;
+#[cfg(test)]
 mod stock_transfer_repository_test;
 #[cfg(test)]
+mod tax_rule_repository_test;
+#[cfg(test)]
+pub(crate) mod test_support;
+#[cfg(test)]
 mod work_front_repository_test;
 
 pub use alert_repository::AlertRepository;
 pub use cash_repository::CashRepository;
 pub use category_repository::CategoryRepository;
+pub use credit_account_repository::CreditAccountRepository;
 pub use customer_repository::CustomerRepository;
 pub use employee_repository::EmployeeRepository;
+pub use fiscal_document_repository::FiscalDocumentRepository;
 pub use fiscal_repository::FiscalRepository;
 pub use held_sale_repository::HeldSaleRepository;
+pub use ibpt_repository::IbptRepository;
 pub use inventory_repository::InventoryRepository;
 pub use price_history_repository::PriceHistoryRepository;
+pub use pricing_rule_repository::PricingRuleRepository;
+pub use print_job_repository::PrintJobRepository;
 pub use product_lot_repository::ProductLotRepository;
 pub use product_repository::ProductRepository;
+pub use purchase_repository::PurchaseRepository;
 pub use sale_repository::SaleRepository;
+pub use sale_return_repository::SaleReturnRepository;
 pub use service_order_repository::ServiceOrderRepository;
 pub use settings_repository::SettingsRepository;
 pub use stock_repository::StockRepository;
+pub use store_credit_repository::StoreCreditRepository;
 pub use supplier_repository::SupplierRepository;
+pub use tax_rule_repository::TaxRuleRepository;
 pub use vehicle_repository::VehicleRepository;
 pub use warranty_repository::WarrantyRepository;
 
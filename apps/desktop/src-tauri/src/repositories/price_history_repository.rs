@@ -15,7 +15,7 @@ impl<'a> PriceHistoryRepository<'a> {
     }
 
     const COLS: &'static str =
-        "id, product_id, old_price, new_price, reason, employee_id, created_at";
+        "id, product_id, old_price, new_price, price_type, reason, employee_id, created_at";
 
     pub async fn find_by_id(&self, id: &str) -> AppResult<Option<PriceHistory>> {
         let query = format!("SELECT {} FROM price_history WHERE id = ?", Self::COLS);
@@ -47,6 +47,7 @@ impl<'a> PriceHistoryRepository<'a> {
                 ph.product_id, 
                 ph.old_price, 
                 ph.new_price, 
+                ph.price_type, 
                 ph.reason, 
                 ph.employee_id, 
                 ph.created_at,
@@ -65,6 +66,29 @@ impl<'a> PriceHistoryRepository<'a> {
         Ok(result)
     }
 
+    /// Produtos ativos com preço alterado a partir de `since` (data ou
+    /// data/hora ISO), em ordem alfabética — usado na reimpressão de etiquetas
+    pub async fn find_changed_product_ids(&self, since: &str) -> AppResult<Vec<String>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT p.id
+            FROM products p
+            WHERE p.is_active = 1
+              AND EXISTS (
+                  SELECT 1 FROM price_history ph
+                  WHERE ph.product_id = p.id
+                    AND ph.created_at >= ?
+                    AND ph.price_type = 'SALE'
+              )
+            ORDER BY p.name
+            "#,
+        )
+        .bind(since)
+        .fetch_all(self.pool)
+        .await?;
+        Ok(ids)
+    }
+
     pub async fn create(&self, data: CreatePriceHistory) -> AppResult<PriceHistory> {
         let id = new_id();
         let now = chrono::Utc::now().to_rfc3339();
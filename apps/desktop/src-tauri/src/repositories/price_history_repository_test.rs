@@ -0,0 +1,85 @@
+//! Testes unitários para PriceHistoryRepository
+
+#[cfg(test)]
+mod tests {
+    use super::super::price_history_repository::PriceHistoryRepository;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO categories (id, name, is_active, created_at, updated_at) 
+             VALUES ('cat-001', 'Test Cat', 1, datetime('now'), datetime('now'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (id, name, active) in [
+            ("prod-a", "Café 500g", 1),
+            ("prod-b", "Arroz 5kg", 1),
+            ("prod-c", "Feijão 1kg", 0),
+        ] {
+            sqlx::query(
+                "INSERT INTO products (id, name, internal_code, category_id, unit, cost_price, sale_price, current_stock, min_stock, is_weighted, is_active, created_at, updated_at) 
+                 VALUES (?, ?, ?, 'cat-001', 'UNIT', 5.0, 10.0, 10.0, 0.0, 0, ?, datetime('now'), datetime('now'))",
+            )
+            .bind(id)
+            .bind(name)
+            .bind(id)
+            .bind(active)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        pool
+    }
+
+    async fn insert_change(pool: &SqlitePool, product_id: &str, price_type: &str, at: &str) {
+        sqlx::query(
+            "INSERT INTO price_history (id, product_id, old_price, new_price, price_type, created_at) 
+             VALUES (?, ?, 9.0, 10.0, ?, ?)",
+        )
+        .bind(format!("{}-{}-{}", product_id, price_type, at))
+        .bind(product_id)
+        .bind(price_type)
+        .bind(at)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_find_changed_product_ids() {
+        let pool = setup_test_db().await;
+        let repo = PriceHistoryRepository::new(&pool);
+
+        insert_change(&pool, "prod-a", "SALE", "2026-10-10T12:00:00+00:00").await;
+        insert_change(&pool, "prod-a", "SALE", "2026-10-12T08:00:00+00:00").await;
+        insert_change(&pool, "prod-b", "SALE", "2026-10-11T09:30:00+00:00").await;
+        // Custo não muda a etiqueta; produto inativo não é reimpresso
+        insert_change(&pool, "prod-b", "COST", "2026-10-15T09:30:00+00:00").await;
+        insert_change(&pool, "prod-c", "SALE", "2026-10-12T09:30:00+00:00").await;
+
+        let ids = repo.find_changed_product_ids("2026-10-10").await.unwrap();
+        assert_eq!(ids, vec!["prod-b", "prod-a"]);
+
+        let ids = repo.find_changed_product_ids("2026-10-12").await.unwrap();
+        assert_eq!(ids, vec!["prod-a"]);
+
+        assert!(repo
+            .find_changed_product_ids("2026-10-13")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
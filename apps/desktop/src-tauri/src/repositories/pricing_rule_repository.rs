@@ -0,0 +1,308 @@
+//! Repositório de Regras de Preço (Promoções)
+
+use crate::error::AppResult;
+use crate::models::{CreateSaleItem, PricingRule, PricingRuleItem, SalePricing, SavePricingRule};
+use crate::repositories::new_id;
+use crate::services::pricing::{self, PricingLine};
+use sqlx::SqlitePool;
+
+pub struct PricingRuleRepository<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> PricingRuleRepository<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    const COLS: &'static str = "id, name, kind, product_id, category_id, customer_group, quantity, pay_quantity, percent, price, start_time, end_time, weekdays, valid_from, valid_until, priority, is_active, created_at, updated_at";
+
+    const KINDS: [&'static str; 7] = [
+        "BUY_X_PAY_Y",
+        "NTH_ITEM_PERCENT",
+        "COMBO_PRICE",
+        "HAPPY_HOUR",
+        "CATEGORY_PERCENT",
+        "CUSTOMER_GROUP_PRICE",
+        "WHOLESALE",
+    ];
+
+    pub async fn find_by_id(&self, id: &str) -> AppResult<Option<PricingRule>> {
+        let query = format!("SELECT {} FROM pricing_rules WHERE id = ?", Self::COLS);
+        let rule = sqlx::query_as::<_, PricingRule>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await?;
+        match rule {
+            Some(mut rule) => {
+                rule.items = self.find_items(&rule.id).await?;
+                Ok(Some(rule))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Regras ativas, da maior para a menor prioridade
+    pub async fn find_all(&self) -> AppResult<Vec<PricingRule>> {
+        let mut conn = self.pool.acquire().await?;
+        Self::find_all_conn(&mut conn).await
+    }
+
+    async fn find_all_conn(conn: &mut sqlx::SqliteConnection) -> AppResult<Vec<PricingRule>> {
+        let query = format!(
+            "SELECT {} FROM pricing_rules WHERE is_active = 1 ORDER BY priority DESC, name",
+            Self::COLS
+        );
+        let mut rules = sqlx::query_as::<_, PricingRule>(&query)
+            .fetch_all(&mut *conn)
+            .await?;
+        for rule in rules.iter_mut() {
+            rule.items = Self::find_items_conn(conn, &rule.id).await?;
+        }
+        Ok(rules)
+    }
+
+    async fn find_items(&self, rule_id: &str) -> AppResult<Vec<PricingRuleItem>> {
+        let mut conn = self.pool.acquire().await?;
+        Self::find_items_conn(&mut conn, rule_id).await
+    }
+
+    async fn find_items_conn(
+        conn: &mut sqlx::SqliteConnection,
+        rule_id: &str,
+    ) -> AppResult<Vec<PricingRuleItem>> {
+        let items = sqlx::query_as::<_, PricingRuleItem>(
+            "SELECT product_id, quantity FROM pricing_rule_items WHERE rule_id = ? ORDER BY product_id",
+        )
+        .bind(rule_id)
+        .fetch_all(conn)
+        .await?;
+        Ok(items)
+    }
+
+    /// Cria a regra (sem `id`) ou atualiza a existente, substituindo a composição do combo
+    pub async fn save(&self, data: SavePricingRule) -> AppResult<PricingRule> {
+        if !Self::KINDS.contains(&data.kind.as_str()) {
+            return Err(crate::error::AppError::Validation(format!(
+                "Tipo de promoção inválido: {}",
+                data.kind
+            )));
+        }
+        if data.percent < 0.0 || data.percent > 100.0 {
+            return Err(crate::error::AppError::Validation(
+                "Percentual deve estar entre 0 e 100".into(),
+            ));
+        }
+        if data.kind == "BUY_X_PAY_Y" && data.pay_quantity >= data.quantity {
+            return Err(crate::error::AppError::Validation(
+                "No \"leve X pague Y\", Y deve ser menor que X".into(),
+            ));
+        }
+        if data.kind == "COMBO_PRICE" && (data.items.is_empty() || data.price.is_none()) {
+            return Err(crate::error::AppError::Validation(
+                "Combo precisa de produtos e preço".into(),
+            ));
+        }
+        if data.kind == "CUSTOMER_GROUP_PRICE"
+            && data.customer_group.as_deref().unwrap_or("").is_empty()
+        {
+            return Err(crate::error::AppError::Validation(
+                "Informe o grupo de clientes da regra".into(),
+            ));
+        }
+
+        // Sem produto nem categoria a regra valeria para a loja inteira
+        let has_product = !data.product_id.as_deref().unwrap_or("").is_empty();
+        let has_category = !data.category_id.as_deref().unwrap_or("").is_empty();
+        if data.kind == "CATEGORY_PERCENT" && !has_category {
+            return Err(crate::error::AppError::Validation(
+                "Informe a categoria da promoção".into(),
+            ));
+        }
+        if matches!(
+            data.kind.as_str(),
+            "BUY_X_PAY_Y" | "NTH_ITEM_PERCENT" | "WHOLESALE"
+        ) && !has_product
+            && !has_category
+        {
+            return Err(crate::error::AppError::Validation(
+                "Informe o produto ou a categoria da promoção".into(),
+            ));
+        }
+        if data.price.is_some() && data.kind != "COMBO_PRICE" && !has_product {
+            return Err(crate::error::AppError::Validation(
+                "Preço especial precisa de um produto".into(),
+            ));
+        }
+
+        let id = data.id.clone().unwrap_or_else(new_id);
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "INSERT INTO pricing_rules (id, name, kind, product_id, category_id, customer_group, quantity, pay_quantity, percent, price, start_time, end_time, weekdays, valid_from, valid_until, priority, is_active, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                kind = excluded.kind,
+                product_id = excluded.product_id,
+                category_id = excluded.category_id,
+                customer_group = excluded.customer_group,
+                quantity = excluded.quantity,
+                pay_quantity = excluded.pay_quantity,
+                percent = excluded.percent,
+                price = excluded.price,
+                start_time = excluded.start_time,
+                end_time = excluded.end_time,
+                weekdays = excluded.weekdays,
+                valid_from = excluded.valid_from,
+                valid_until = excluded.valid_until,
+                priority = excluded.priority,
+                updated_at = excluded.updated_at",
+        )
+        .bind(&id)
+        .bind(&data.name)
+        .bind(&data.kind)
+        .bind(data.product_id.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.category_id.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.customer_group.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.quantity)
+        .bind(data.pay_quantity)
+        .bind(data.percent)
+        .bind(data.price)
+        .bind(data.start_time.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.end_time.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.weekdays.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.valid_from.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.valid_until.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.priority)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM pricing_rule_items WHERE rule_id = ?")
+            .bind(&id)
+            .execute(&mut *tx)
+            .await?;
+        for item in &data.items {
+            sqlx::query(
+                "INSERT INTO pricing_rule_items (rule_id, product_id, quantity) VALUES (?, ?, ?)",
+            )
+            .bind(&id)
+            .bind(&item.product_id)
+            .bind(item.quantity)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.find_by_id(&id)
+            .await?
+            .ok_or_else(|| crate::error::AppError::NotFound {
+                entity: "PricingRule".into(),
+                id,
+            })
+    }
+
+    /// Desativa a regra (vendas antigas continuam apontando para ela)
+    pub async fn delete(&self, id: &str) -> AppResult<()> {
+        sqlx::query("UPDATE pricing_rules SET is_active = 0, updated_at = ? WHERE id = ?")
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find_customer_price_group(&self, customer_id: &str) -> AppResult<Option<String>> {
+        let mut conn = self.pool.acquire().await?;
+        Self::find_customer_price_group_conn(&mut conn, customer_id).await
+    }
+
+    async fn find_customer_price_group_conn(
+        conn: &mut sqlx::SqliteConnection,
+        customer_id: &str,
+    ) -> AppResult<Option<String>> {
+        let group: Option<(Option<String>,)> =
+            sqlx::query_as("SELECT price_group FROM customers WHERE id = ?")
+                .bind(customer_id)
+                .fetch_optional(conn)
+                .await?;
+        Ok(group.and_then(|g| g.0))
+    }
+
+    /// Define (ou remove, com `None`) o grupo de preço do cliente
+    pub async fn set_customer_price_group(
+        &self,
+        customer_id: &str,
+        price_group: Option<&str>,
+    ) -> AppResult<()> {
+        sqlx::query("UPDATE customers SET price_group = ?, updated_at = ? WHERE id = ?")
+            .bind(price_group.map(str::trim).filter(|s| !s.is_empty()))
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(customer_id)
+            .execute(self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Avalia as promoções vigentes sobre os itens (horário local do PDV)
+    pub async fn evaluate(
+        &self,
+        items: &[CreateSaleItem],
+        customer_id: Option<&str>,
+    ) -> AppResult<SalePricing> {
+        let mut conn = self.pool.acquire().await?;
+        Self::evaluate_conn(&mut conn, items, customer_id).await
+    }
+
+    /// Mesma avaliação, lendo pela transação da venda
+    pub async fn evaluate_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        items: &[CreateSaleItem],
+        customer_id: Option<&str>,
+    ) -> AppResult<SalePricing> {
+        Self::evaluate_conn(tx, items, customer_id).await
+    }
+
+    async fn evaluate_conn(
+        conn: &mut sqlx::SqliteConnection,
+        items: &[CreateSaleItem],
+        customer_id: Option<&str>,
+    ) -> AppResult<SalePricing> {
+        let rules = Self::find_all_conn(conn).await?;
+        if rules.is_empty() || items.is_empty() {
+            return Ok(SalePricing::default());
+        }
+
+        let mut lines = Vec::with_capacity(items.len());
+        for item in items {
+            let category: Option<(Option<String>,)> =
+                sqlx::query_as("SELECT category_id FROM products WHERE id = ?")
+                    .bind(&item.product_id)
+                    .fetch_optional(&mut *conn)
+                    .await?;
+            lines.push(PricingLine {
+                product_id: item.product_id.clone(),
+                category_id: category.and_then(|c| c.0),
+                quantity: item.quantity,
+                unit_price: item.unit_price,
+                manual_discount: item.discount.unwrap_or(0.0),
+            });
+        }
+
+        let customer_group = match customer_id {
+            Some(id) => Self::find_customer_price_group_conn(conn, id).await?,
+            None => None,
+        };
+
+        Ok(pricing::evaluate(
+            &rules,
+            &lines,
+            customer_group.as_deref(),
+            chrono::Local::now().naive_local(),
+        ))
+    }
+}
@@ -0,0 +1,187 @@
+//! Testes unitários para PricingRuleRepository
+
+#[cfg(test)]
+mod tests {
+    use super::super::pricing_rule_repository::PricingRuleRepository;
+    use crate::models::{CreateSaleItem, PricingRuleItem, SavePricingRule};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO categories (id, name, is_active, created_at, updated_at)
+             VALUES ('cat-001', 'Bebidas', 1, datetime('now'), datetime('now')),
+                    ('cat-002', 'Lanches', 1, datetime('now'), datetime('now'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO products (id, name, barcode, internal_code, category_id, unit, cost_price, sale_price, current_stock, min_stock, is_weighted, is_active, created_at, updated_at)
+             VALUES ('prod-001', 'Refrigerante', '789123', 'REF01', 'cat-001', 'UN', 4.0, 8.0, 10.0, 1.0, 0, 1, datetime('now'), datetime('now')),
+                    ('prod-002', 'Salgado', '789456', 'SAL01', 'cat-002', 'UN', 2.0, 6.0, 10.0, 1.0, 0, 1, datetime('now'), datetime('now'))"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO customers (id, name, is_active, created_at, updated_at)
+             VALUES ('cus-001', 'Cliente Atacado', 1, datetime('now'), datetime('now'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    fn rule(name: &str, kind: &str) -> SavePricingRule {
+        SavePricingRule {
+            id: None,
+            name: name.to_string(),
+            kind: kind.to_string(),
+            product_id: None,
+            category_id: None,
+            customer_group: None,
+            quantity: 0.0,
+            pay_quantity: 0.0,
+            percent: 0.0,
+            price: None,
+            start_time: None,
+            end_time: None,
+            weekdays: None,
+            valid_from: None,
+            valid_until: None,
+            priority: 0,
+            items: Vec::new(),
+        }
+    }
+
+    fn item(product_id: &str, quantity: f64, unit_price: f64) -> CreateSaleItem {
+        CreateSaleItem {
+            product_id: product_id.to_string(),
+            quantity,
+            unit_price,
+            discount: Some(0.0),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_validates_and_replaces_combo_items() {
+        let pool = setup_test_db().await;
+        let repo = PricingRuleRepository::new(&pool);
+
+        assert!(repo.save(rule("Inválida", "MAGIC")).await.is_err());
+        let mut invalid = rule("Leve 2 pague 3", "BUY_X_PAY_Y");
+        invalid.quantity = 2.0;
+        invalid.pay_quantity = 3.0;
+        assert!(repo.save(invalid).await.is_err());
+
+        let mut combo = rule("Combo lanche", "COMBO_PRICE");
+        combo.price = Some(12.0);
+        combo.items = vec![
+            PricingRuleItem {
+                product_id: "prod-001".into(),
+                quantity: 1.0,
+            },
+            PricingRuleItem {
+                product_id: "prod-002".into(),
+                quantity: 1.0,
+            },
+        ];
+        let saved = repo.save(combo.clone()).await.unwrap();
+        assert_eq!(saved.items.len(), 2);
+
+        combo.id = Some(saved.id.clone());
+        combo.items.truncate(1);
+        let updated = repo.save(combo).await.unwrap();
+        assert_eq!(updated.id, saved.id);
+        assert_eq!(updated.items.len(), 1);
+
+        repo.delete(&saved.id).await.unwrap();
+        assert!(repo.find_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_save_rejects_rules_without_scope() {
+        let pool = setup_test_db().await;
+        let repo = PricingRuleRepository::new(&pool);
+
+        let mut category = rule("Bebidas 10%", "CATEGORY_PERCENT");
+        category.percent = 10.0;
+        category.product_id = Some("prod-001".into());
+        assert!(repo.save(category.clone()).await.is_err());
+
+        let mut take_three = rule("Leve 3 pague 2", "BUY_X_PAY_Y");
+        take_three.quantity = 3.0;
+        take_three.pay_quantity = 2.0;
+        assert!(repo.save(take_three.clone()).await.is_err());
+
+        let mut wholesale = rule("Atacado", "WHOLESALE");
+        wholesale.quantity = 10.0;
+        wholesale.price = Some(7.0);
+        wholesale.category_id = Some("cat-001".into());
+        assert!(repo.save(wholesale.clone()).await.is_err());
+        assert!(repo.find_all().await.unwrap().is_empty());
+
+        category.product_id = None;
+        category.category_id = Some("cat-001".into());
+        repo.save(category).await.unwrap();
+        take_three.category_id = Some("cat-002".into());
+        repo.save(take_three).await.unwrap();
+        wholesale.product_id = Some("prod-001".into());
+        repo.save(wholesale).await.unwrap();
+
+        // Happy hour continua valendo para a loja inteira
+        let mut happy_hour = rule("Happy hour", "HAPPY_HOUR");
+        happy_hour.percent = 15.0;
+        repo.save(happy_hour).await.unwrap();
+        assert_eq!(repo.find_all().await.unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_uses_category_and_customer_group() {
+        let pool = setup_test_db().await;
+        let repo = PricingRuleRepository::new(&pool);
+
+        let mut category = rule("Bebidas 10%", "CATEGORY_PERCENT");
+        category.category_id = Some("cat-001".into());
+        category.percent = 10.0;
+        repo.save(category).await.unwrap();
+
+        let mut group = rule("Preço atacado", "CUSTOMER_GROUP_PRICE");
+        group.customer_group = Some("ATACADO".into());
+        group.product_id = Some("prod-002".into());
+        group.price = Some(5.0);
+        repo.save(group).await.unwrap();
+
+        let items = vec![item("prod-001", 2.0, 8.0), item("prod-002", 2.0, 6.0)];
+
+        let pricing = repo.evaluate(&items, Some("cus-001")).await.unwrap();
+        assert_eq!(pricing.items.len(), 1);
+        assert_eq!(pricing.items[0].item_index, 0);
+        assert_eq!(pricing.items[0].reason, "Bebidas 10%");
+        assert_eq!(pricing.total_discount, 1.6);
+
+        repo.set_customer_price_group("cus-001", Some("atacado"))
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.find_customer_price_group("cus-001").await.unwrap(),
+            Some("atacado".to_string())
+        );
+        let pricing = repo.evaluate(&items, Some("cus-001")).await.unwrap();
+        assert_eq!(pricing.items.len(), 2);
+        assert_eq!(pricing.total_discount, 3.6);
+    }
+}
@@ -0,0 +1,210 @@
+//! Repositório da Fila de Impressão
+//!
+//! Spool persistente dos trabalhos enviados à impressora térmica. Os bytes
+//! ESC/POS ficam gravados até a impressão ser confirmada, de forma que um
+//! cupom não se perde se a impressora estiver sem papel ou o app reiniciar.
+
+use crate::error::AppResult;
+use crate::models::PrintJob;
+use crate::repositories::new_id;
+use sqlx::SqlitePool;
+
+/// Data/hora no formato gravado em `next_attempt_at` (comparável como texto)
+pub fn spool_timestamp(at: chrono::DateTime<chrono::Utc>) -> String {
+    at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+pub struct PrintJobRepository<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> PrintJobRepository<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    const COLS: &'static str = "id, kind, reference, status, attempts, max_attempts, last_error, next_attempt_at, printed_at, created_at, updated_at";
+
+    pub async fn find_by_id(&self, id: &str) -> AppResult<Option<PrintJob>> {
+        let query = format!("SELECT {} FROM print_jobs WHERE id = ?", Self::COLS);
+        let result = sqlx::query_as::<_, PrintJob>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Bytes ESC/POS do trabalho
+    pub async fn find_payload(&self, id: &str) -> AppResult<Option<Vec<u8>>> {
+        let payload: Option<(Vec<u8>,)> =
+            sqlx::query_as("SELECT payload FROM print_jobs WHERE id = ?")
+                .bind(id)
+                .fetch_optional(self.pool)
+                .await?;
+        Ok(payload.map(|(bytes,)| bytes))
+    }
+
+    /// Lista os trabalhos mais recentes, opcionalmente filtrando por status
+    pub async fn list(&self, status: Option<&str>, limit: i32) -> AppResult<Vec<PrintJob>> {
+        let query = format!(
+            "SELECT {} FROM print_jobs WHERE (? IS NULL OR status = ?) ORDER BY created_at DESC LIMIT ?",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, PrintJob>(&query)
+            .bind(status)
+            .bind(status)
+            .bind(limit)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Trabalho pendente mais antigo (a fila é FIFO para manter a ordem dos cupons)
+    pub async fn find_next_pending(&self) -> AppResult<Option<PrintJob>> {
+        let query = format!(
+            "SELECT {} FROM print_jobs WHERE status = 'PENDING' ORDER BY created_at, rowid LIMIT 1",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, PrintJob>(&query)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn create(
+        &self,
+        kind: &str,
+        reference: Option<&str>,
+        payload: &[u8],
+        max_attempts: i32,
+    ) -> AppResult<PrintJob> {
+        let id = new_id();
+        let now = spool_timestamp(chrono::Utc::now());
+
+        sqlx::query(
+            "INSERT INTO print_jobs (id, kind, reference, payload, status, attempts, max_attempts, next_attempt_at, created_at, updated_at)
+             VALUES (?, ?, ?, ?, 'PENDING', 0, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(kind)
+        .bind(reference)
+        .bind(payload)
+        .bind(max_attempts)
+        .bind(&now)
+        .bind(&now)
+        .bind(&now)
+        .execute(self.pool)
+        .await?;
+
+        self.get(&id).await
+    }
+
+    /// Reserva o trabalho para envio; falso se ele deixou de estar pendente
+    /// (ex.: cancelado pelo operador enquanto aguardava)
+    pub async fn mark_printing(&self, id: &str) -> AppResult<bool> {
+        let result = sqlx::query(
+            "UPDATE print_jobs SET status = 'PRINTING', updated_at = ? WHERE id = ? AND status = 'PENDING'",
+        )
+        .bind(spool_timestamp(chrono::Utc::now()))
+        .bind(id)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn mark_done(&self, id: &str) -> AppResult<PrintJob> {
+        let now = spool_timestamp(chrono::Utc::now());
+        sqlx::query(
+            "UPDATE print_jobs SET status = 'DONE', attempts = attempts + 1, last_error = NULL, printed_at = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&now)
+        .bind(&now)
+        .bind(id)
+        .execute(self.pool)
+        .await?;
+        self.get(id).await
+    }
+
+    /// Registra uma tentativa sem sucesso: volta para PENDING com nova data de
+    /// tentativa ou vai para FAILED ao esgotar `max_attempts`
+    pub async fn record_failure(
+        &self,
+        id: &str,
+        error: &str,
+        next_attempt_at: &str,
+    ) -> AppResult<PrintJob> {
+        sqlx::query(
+            "UPDATE print_jobs SET
+                attempts = attempts + 1,
+                status = CASE WHEN attempts + 1 >= max_attempts THEN 'FAILED' ELSE 'PENDING' END,
+                last_error = ?,
+                next_attempt_at = ?,
+                updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(error)
+        .bind(next_attempt_at)
+        .bind(spool_timestamp(chrono::Utc::now()))
+        .bind(id)
+        .execute(self.pool)
+        .await?;
+        self.get(id).await
+    }
+
+    /// Devolve à fila um trabalho que falhou ou foi cancelado
+    pub async fn requeue(&self, id: &str) -> AppResult<bool> {
+        let now = spool_timestamp(chrono::Utc::now());
+        let result = sqlx::query(
+            "UPDATE print_jobs SET status = 'PENDING', attempts = 0, last_error = NULL, next_attempt_at = ?, updated_at = ?
+             WHERE id = ? AND status IN ('FAILED', 'CANCELED')",
+        )
+        .bind(&now)
+        .bind(&now)
+        .bind(id)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn cancel(&self, id: &str) -> AppResult<bool> {
+        let result = sqlx::query(
+            "UPDATE print_jobs SET status = 'CANCELED', updated_at = ? WHERE id = ? AND status IN ('PENDING', 'FAILED')",
+        )
+        .bind(spool_timestamp(chrono::Utc::now()))
+        .bind(id)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Trabalhos interrompidos no meio do envio (app fechado) voltam para a fila
+    pub async fn recover_interrupted(&self) -> AppResult<u64> {
+        let result = sqlx::query(
+            "UPDATE print_jobs SET status = 'PENDING', updated_at = ? WHERE status = 'PRINTING'",
+        )
+        .bind(spool_timestamp(chrono::Utc::now()))
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Remove do spool os trabalhos concluídos ou cancelados antes de `before`
+    pub async fn purge_finished(&self, before: &str) -> AppResult<u64> {
+        let result = sqlx::query(
+            "DELETE FROM print_jobs WHERE status IN ('DONE', 'CANCELED') AND updated_at < ?",
+        )
+        .bind(before)
+        .execute(self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn get(&self, id: &str) -> AppResult<PrintJob> {
+        self.find_by_id(id)
+            .await?
+            .ok_or_else(|| crate::error::AppError::NotFound {
+                entity: "PrintJob".into(),
+                id: id.to_string(),
+            })
+    }
+}
@@ -0,0 +1,111 @@
+//! Testes unitários para PrintJobRepository
+
+#[cfg(test)]
+mod tests {
+    use super::super::print_job_repository::{spool_timestamp, PrintJobRepository};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_create_and_payload() {
+        let pool = setup_test_db().await;
+        let repo = PrintJobRepository::new(&pool);
+
+        let job = repo
+            .create("RECEIPT", Some("sale-1"), &[0x1B, b'@', b'A'], 8)
+            .await
+            .unwrap();
+        assert_eq!(job.status, "PENDING");
+        assert_eq!(job.attempts, 0);
+        assert_eq!(job.reference.as_deref(), Some("sale-1"));
+
+        let payload = repo.find_payload(&job.id).await.unwrap().unwrap();
+        assert_eq!(payload, vec![0x1B, b'@', b'A']);
+    }
+
+    #[tokio::test]
+    async fn test_next_pending_is_fifo() {
+        let pool = setup_test_db().await;
+        let repo = PrintJobRepository::new(&pool);
+
+        let first = repo.create("RECEIPT", None, b"1", 8).await.unwrap();
+        let second = repo.create("RECEIPT", None, b"2", 8).await.unwrap();
+
+        assert_eq!(
+            repo.find_next_pending().await.unwrap().unwrap().id,
+            first.id
+        );
+
+        assert!(repo.mark_printing(&first.id).await.unwrap());
+        repo.mark_done(&first.id).await.unwrap();
+        assert_eq!(
+            repo.find_next_pending().await.unwrap().unwrap().id,
+            second.id
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failure_until_max_attempts() {
+        let pool = setup_test_db().await;
+        let repo = PrintJobRepository::new(&pool);
+        let job = repo.create("DANFE", None, b"x", 2).await.unwrap();
+        let later = spool_timestamp(chrono::Utc::now() + chrono::Duration::seconds(5));
+
+        repo.mark_printing(&job.id).await.unwrap();
+        let job = repo
+            .record_failure(&job.id, "sem papel", &later)
+            .await
+            .unwrap();
+        assert_eq!(job.status, "PENDING");
+        assert_eq!(job.attempts, 1);
+        assert_eq!(job.next_attempt_at, later);
+
+        repo.mark_printing(&job.id).await.unwrap();
+        let job = repo
+            .record_failure(&job.id, "sem papel", &later)
+            .await
+            .unwrap();
+        assert_eq!(job.status, "FAILED");
+        assert_eq!(job.last_error.as_deref(), Some("sem papel"));
+
+        assert!(repo.requeue(&job.id).await.unwrap());
+        let job = repo.find_by_id(&job.id).await.unwrap().unwrap();
+        assert_eq!(job.status, "PENDING");
+        assert_eq!(job.attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_recover() {
+        let pool = setup_test_db().await;
+        let repo = PrintJobRepository::new(&pool);
+
+        let canceled = repo.create("RECEIPT", None, b"1", 8).await.unwrap();
+        assert!(repo.cancel(&canceled.id).await.unwrap());
+        assert!(!repo.mark_printing(&canceled.id).await.unwrap());
+        assert!(repo.find_next_pending().await.unwrap().is_none());
+
+        let interrupted = repo.create("RECEIPT", None, b"2", 8).await.unwrap();
+        repo.mark_printing(&interrupted.id).await.unwrap();
+        assert!(!repo.cancel(&interrupted.id).await.unwrap());
+        assert_eq!(repo.recover_interrupted().await.unwrap(), 1);
+        assert_eq!(
+            repo.find_next_pending().await.unwrap().unwrap().id,
+            interrupted.id
+        );
+
+        let future = spool_timestamp(chrono::Utc::now() + chrono::Duration::days(1));
+        assert_eq!(repo.purge_finished(&future).await.unwrap(), 1);
+        assert_eq!(repo.list(None, 10).await.unwrap().len(), 1);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::database::decimal_config;
 use crate::error::AppResult;
-use crate::models::{CreateProduct, Product, ProductFilters, StockSummary, UpdateProduct};
+use crate::models::{
+    CreateProduct, Product, ProductFilters, ProductUnit, StockSummary, UpdateProduct,
+};
 use crate::repositories::new_id;
 use sqlx::{QueryBuilder, Sqlite, SqlitePool};
 
@@ -112,6 +114,39 @@ impl<'a> ProductRepository<'a> {
         Ok(result)
     }
 
+    /// Produto pelo código da balança (PLU) impresso em códigos pesados.
+    /// O PLU é o código interno, comparado sem zeros à esquerda ("00123" = "123")
+    pub async fn find_by_scale_code(&self, code: &str) -> AppResult<Option<Product>> {
+        let trimmed = code.trim_start_matches('0');
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+        let query = format!(
+            "SELECT {} FROM products WHERE is_active = 1 AND (internal_code = ? OR ltrim(internal_code, '0') = ?) ORDER BY is_weighted DESC, internal_code = ? DESC LIMIT 1",
+            self.product_columns_string()
+        );
+        let result = sqlx::query_as::<_, Product>(&query)
+            .bind(code)
+            .bind(trimmed)
+            .bind(code)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Produtos ativos vendidos na unidade informada, por nome
+    pub async fn find_active_by_unit(&self, unit: ProductUnit) -> AppResult<Vec<Product>> {
+        let query = format!(
+            "SELECT {} FROM products WHERE is_active = 1 AND unit = ? ORDER BY name",
+            self.product_columns_string()
+        );
+        let result = sqlx::query_as::<_, Product>(&query)
+            .bind(unit.as_db_str())
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
     pub async fn find_all_active(&self, category_id: Option<String>) -> AppResult<Vec<Product>> {
         if let Some(cat_id) = category_id {
             let query = format!(
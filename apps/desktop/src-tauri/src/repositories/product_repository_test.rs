@@ -191,6 +191,54 @@ mod tests {
         assert_eq!(product.unwrap().id, created.id);
     }
 
+    #[tokio::test]
+    async fn test_find_by_scale_code() {
+        let pool = setup_test_db().await;
+        let repo = ProductRepository::new(&pool);
+
+        let input = CreateProduct {
+            name: "Queijo Prato".to_string(),
+            barcode: None,
+            internal_code: Some("123".to_string()),
+            category_id: "cat-test-001".to_string(),
+            sale_price: 49.90,
+            cost_price: Some(30.0),
+            min_stock: Some(0.0),
+            current_stock: Some(0.0),
+            description: None,
+            unit: Some(crate::models::ProductUnit::Kilogram),
+            is_weighted: Some(true),
+            max_stock: None,
+            notes: None,
+            oem_code: None,
+            aftermarket_code: None,
+            part_brand: None,
+            application: None,
+        };
+        let created = repo.create(input).await.unwrap();
+
+        // PLU com zeros à esquerda, como sai na etiqueta da balança
+        let found = repo.find_by_scale_code("00123").await.unwrap();
+        assert_eq!(found.map(|p| p.id), Some(created.id.clone()));
+        let found = repo.find_by_scale_code("0123").await.unwrap();
+        assert_eq!(found.map(|p| p.id), Some(created.id));
+
+        assert!(repo.find_by_scale_code("00124").await.unwrap().is_none());
+        assert!(repo.find_by_scale_code("00000").await.unwrap().is_none());
+
+        let by_kg = repo
+            .find_active_by_unit(crate::models::ProductUnit::Kilogram)
+            .await
+            .unwrap();
+        assert_eq!(by_kg.len(), 1);
+        assert_eq!(by_kg[0].name, "Queijo Prato");
+        assert!(repo
+            .find_active_by_unit(crate::models::ProductUnit::Liter)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_search_products() {
         let pool = setup_test_db().await;
@@ -0,0 +1,301 @@
+//! Repositório de Entrada por NF-e de Compra
+
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    CreatePurchaseInvoice, PurchaseImportResult, PurchaseInvoice, PurchaseLotInfo,
+    SupplierProductCode,
+};
+use crate::repositories::new_id;
+use sqlx::SqlitePool;
+
+pub struct PurchaseRepository<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> PurchaseRepository<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    const COLS: &'static str = "id, access_key, supplier_id, number, serie, issued_at, total, items_imported, items_skipped, employee_id, created_at";
+    const CODE_COLS: &'static str =
+        "product_id, supplier_id, supplier_code, created_at, updated_at";
+
+    pub async fn find_by_id(&self, id: &str) -> AppResult<Option<PurchaseInvoice>> {
+        let query = format!("SELECT {} FROM purchase_invoices WHERE id = ?", Self::COLS);
+        let result = sqlx::query_as::<_, PurchaseInvoice>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_by_access_key(&self, access_key: &str) -> AppResult<Option<PurchaseInvoice>> {
+        let query = format!(
+            "SELECT {} FROM purchase_invoices WHERE access_key = ?",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, PurchaseInvoice>(&query)
+            .bind(access_key)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_recent(&self, limit: i32) -> AppResult<Vec<PurchaseInvoice>> {
+        let query = format!(
+            "SELECT {} FROM purchase_invoices ORDER BY created_at DESC LIMIT ?",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, PurchaseInvoice>(&query)
+            .bind(limit)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // CÓDIGO DO FORNECEDOR ↔ PRODUTO
+    // ═══════════════════════════════════════════════════════════════════════
+
+    pub async fn find_codes_by_supplier(
+        &self,
+        supplier_id: &str,
+    ) -> AppResult<Vec<SupplierProductCode>> {
+        let query = format!(
+            "SELECT {} FROM product_suppliers WHERE supplier_id = ? ORDER BY supplier_code",
+            Self::CODE_COLS
+        );
+        let result = sqlx::query_as::<_, SupplierProductCode>(&query)
+            .bind(supplier_id)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Produto ativo vinculado ao código do fornecedor
+    pub async fn find_product_by_supplier_code(
+        &self,
+        supplier_id: &str,
+        supplier_code: &str,
+    ) -> AppResult<Option<(String, String)>> {
+        let result = sqlx::query_as::<_, (String, String)>(
+            "SELECT p.id, p.name FROM product_suppliers ps
+             JOIN products p ON p.id = ps.product_id
+             WHERE ps.supplier_id = ? AND ps.supplier_code = ? AND p.is_active = 1",
+        )
+        .bind(supplier_id)
+        .bind(supplier_code)
+        .fetch_optional(self.pool)
+        .await?;
+        Ok(result)
+    }
+
+    /// Produto ativo com o código de barras (EAN/GTIN) informado
+    pub async fn find_product_by_barcode(
+        &self,
+        barcode: &str,
+    ) -> AppResult<Option<(String, String)>> {
+        let result = sqlx::query_as::<_, (String, String)>(
+            "SELECT id, name FROM products WHERE barcode = ? AND is_active = 1 LIMIT 1",
+        )
+        .bind(barcode)
+        .fetch_optional(self.pool)
+        .await?;
+        Ok(result)
+    }
+
+    pub async fn save_supplier_code(
+        &self,
+        supplier_id: &str,
+        supplier_code: &str,
+        product_id: &str,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO product_suppliers (product_id, supplier_id, supplier_code, updated_at) VALUES (?, ?, ?, datetime('now'))",
+        )
+        .bind(product_id)
+        .bind(supplier_id)
+        .bind(supplier_code)
+        .execute(self.pool)
+        .await?;
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // ENTRADA NO ESTOQUE
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Lança a nota: lotes, movimentações de entrada, custo e histórico (transação única)
+    pub async fn import(&self, data: CreatePurchaseInvoice) -> AppResult<PurchaseImportResult> {
+        if self.find_by_access_key(&data.access_key).await?.is_some() {
+            return Err(AppError::Duplicate(format!(
+                "NF-e {} já foi importada",
+                data.access_key
+            )));
+        }
+        if data.entries.is_empty() {
+            return Err(AppError::Validation(
+                "Nenhum item vinculado a produto para dar entrada".into(),
+            ));
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let invoice_id = new_id();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO purchase_invoices (id, access_key, supplier_id, number, serie, issued_at, total, items_imported, items_skipped, xml, employee_id, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&invoice_id)
+        .bind(&data.access_key)
+        .bind(&data.supplier_id)
+        .bind(data.number)
+        .bind(data.serie)
+        .bind(&data.issued_at)
+        .bind(data.total)
+        .bind(data.entries.len() as i32)
+        .bind(data.items_skipped)
+        .bind(&data.xml)
+        .bind(&data.employee_id)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?;
+
+        let reason = format!("Entrada NF-e {} série {}", data.number, data.serie);
+        let mut lots_created = 0;
+        let mut cost_changes = 0;
+
+        for entry in &data.entries {
+            let (mut stock, old_cost): (f64, f64) =
+                sqlx::query_as("SELECT current_stock, cost_price FROM products WHERE id = ?")
+                    .bind(&entry.product_id)
+                    .fetch_optional(&mut *tx)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound {
+                        entity: "Product".into(),
+                        id: entry.product_id.clone(),
+                    })?;
+
+            for lot in split_lots(entry.quantity, &entry.lots) {
+                let lot_id = new_id();
+                sqlx::query(
+                    "INSERT INTO product_lots (id, product_id, supplier_id, lot_number, expiration_date, manufacturing_date, initial_quantity, current_quantity, cost_price, status, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'AVAILABLE', ?, ?)",
+                )
+                .bind(&lot_id)
+                .bind(&entry.product_id)
+                .bind(&data.supplier_id)
+                .bind(&lot.lot_number)
+                .bind(&lot.expiration_date)
+                .bind(&lot.manufacturing_date)
+                .bind(lot.quantity)
+                .bind(lot.quantity)
+                .bind(entry.unit_cost)
+                .bind(&now)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+                lots_created += 1;
+
+                let previous_stock = stock;
+                stock += lot.quantity;
+                sqlx::query(
+                    "INSERT INTO stock_movements (id, product_id, lot_id, type, quantity, previous_stock, new_stock, reason, reference_id, reference_type, employee_id, created_at) VALUES (?, ?, ?, 'ENTRY', ?, ?, ?, ?, ?, 'PURCHASE_NFE', ?, ?)",
+                )
+                .bind(new_id())
+                .bind(&entry.product_id)
+                .bind(&lot_id)
+                .bind(lot.quantity)
+                .bind(previous_stock)
+                .bind(stock)
+                .bind(&reason)
+                .bind(&invoice_id)
+                .bind(&data.employee_id)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            sqlx::query(
+                "UPDATE products SET current_stock = ?, cost_price = ?, updated_at = ? WHERE id = ?",
+            )
+            .bind(stock)
+            .bind(entry.unit_cost)
+            .bind(&now)
+            .bind(&entry.product_id)
+            .execute(&mut *tx)
+            .await?;
+
+            if crate::database::decimal_config::use_decimal_columns() {
+                sqlx::query("UPDATE products SET current_stock_decimal = ROUND(?,3) WHERE id = ?")
+                    .bind(stock)
+                    .bind(&entry.product_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            if (old_cost - entry.unit_cost).abs() >= 0.01 {
+                sqlx::query(
+                    "INSERT INTO price_history (id, product_id, old_price, new_price, price_type, reason, employee_id, created_at) VALUES (?, ?, ?, ?, 'COST', ?, ?, ?)",
+                )
+                .bind(new_id())
+                .bind(&entry.product_id)
+                .bind(old_cost)
+                .bind(entry.unit_cost)
+                .bind(&reason)
+                .bind(&data.employee_id)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+                cost_changes += 1;
+            }
+
+            if data.remember_codes {
+                sqlx::query(
+                    "INSERT OR REPLACE INTO product_suppliers (product_id, supplier_id, supplier_code, updated_at) VALUES (?, ?, ?, ?)",
+                )
+                .bind(&entry.product_id)
+                .bind(&data.supplier_id)
+                .bind(&entry.supplier_code)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(PurchaseImportResult {
+            invoice_id,
+            supplier_id: data.supplier_id,
+            supplier_created: false,
+            items_imported: data.entries.len() as i32,
+            items_skipped: data.items_skipped,
+            lots_created,
+            cost_changes,
+        })
+    }
+}
+
+/// Distribui a quantidade do item entre os lotes do `rastro`; o excedente
+/// (ou o item inteiro, sem rastreabilidade) entra em um lote sem número
+pub fn split_lots(quantity: f64, lots: &[PurchaseLotInfo]) -> Vec<PurchaseLotInfo> {
+    let mut result: Vec<PurchaseLotInfo> = lots
+        .iter()
+        .filter(|lot| lot.quantity > 0.0)
+        .cloned()
+        .collect();
+
+    let allocated: f64 = result.iter().map(|lot| lot.quantity).sum();
+    let remainder = ((quantity - allocated) * 1000.0).round() / 1000.0;
+    if remainder > 0.0 {
+        result.push(PurchaseLotInfo {
+            lot_number: None,
+            quantity: remainder,
+            manufacturing_date: None,
+            expiration_date: None,
+        });
+    }
+
+    result
+}
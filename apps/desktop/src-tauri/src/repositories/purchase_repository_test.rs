@@ -0,0 +1,155 @@
+//! Testes unitários para PurchaseRepository
+
+#[cfg(test)]
+mod tests {
+    use super::super::purchase_repository::{split_lots, PurchaseRepository};
+    use crate::error::AppError;
+    use crate::models::{CreatePurchaseInvoice, PurchaseEntry, PurchaseLotInfo};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO categories (id, name, is_active, created_at, updated_at)
+             VALUES ('cat-001', 'Peças', 1, datetime('now'), datetime('now'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO suppliers (id, name, cnpj, is_active, created_at, updated_at)
+             VALUES ('sup-001', 'Distribuidora', '98765432000110', 1, datetime('now'), datetime('now'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO products (id, name, barcode, internal_code, category_id, unit, cost_price, sale_price, current_stock, min_stock, is_weighted, is_active, created_at, updated_at)
+             VALUES ('prod-001', 'Kit Relação', '7891234567895', 'KIT01', 'cat-001', 'UNIT', 50.0, 90.0, 2.0, 1.0, 0, 1, datetime('now'), datetime('now'))"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    fn lot(number: &str, quantity: f64) -> PurchaseLotInfo {
+        PurchaseLotInfo {
+            lot_number: Some(number.to_string()),
+            quantity,
+            manufacturing_date: Some("2026-01-02".to_string()),
+            expiration_date: Some("2028-01-02".to_string()),
+        }
+    }
+
+    fn invoice(lots: Vec<PurchaseLotInfo>) -> CreatePurchaseInvoice {
+        CreatePurchaseInvoice {
+            access_key: "35260198765432000110550010000012341000012345".to_string(),
+            supplier_id: "sup-001".to_string(),
+            number: 1234,
+            serie: 1,
+            issued_at: "2026-01-15T10:00:00-03:00".to_string(),
+            total: 535.0,
+            xml: "<nfeProc/>".to_string(),
+            employee_id: None,
+            entries: vec![PurchaseEntry {
+                product_id: "prod-001".to_string(),
+                supplier_code: "KIT-0042".to_string(),
+                quantity: 10.0,
+                unit_cost: 53.5,
+                lots,
+            }],
+            items_skipped: 1,
+            remember_codes: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_creates_lots_movements_and_cost_history() {
+        let pool = setup_test_db().await;
+        let repo = PurchaseRepository::new(&pool);
+
+        let result = repo
+            .import(invoice(vec![lot("L2026A", 6.0), lot("L2026B", 4.0)]))
+            .await
+            .unwrap();
+        assert_eq!(result.items_imported, 1);
+        assert_eq!(result.items_skipped, 1);
+        assert_eq!(result.lots_created, 2);
+        assert_eq!(result.cost_changes, 1);
+
+        let (stock, cost): (f64, f64) =
+            sqlx::query_as("SELECT current_stock, cost_price FROM products WHERE id = 'prod-001'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(stock, 12.0);
+        assert_eq!(cost, 53.5);
+
+        let movements: Vec<(f64, f64, String)> = sqlx::query_as(
+            "SELECT previous_stock, new_stock, reference_type FROM stock_movements
+             WHERE reference_id = ? ORDER BY new_stock",
+        )
+        .bind(&result.invoice_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(movements.len(), 2);
+        assert_eq!(movements[0], (2.0, 8.0, "PURCHASE_NFE".to_string()));
+        assert_eq!(movements[1].1, 12.0);
+
+        let history: (f64, f64, String) = sqlx::query_as(
+            "SELECT old_price, new_price, price_type FROM price_history WHERE product_id = 'prod-001'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(history, (50.0, 53.5, "COST".to_string()));
+
+        let mapped = repo
+            .find_product_by_supplier_code("sup-001", "KIT-0042")
+            .await
+            .unwrap();
+        assert_eq!(mapped.map(|(id, _)| id).as_deref(), Some("prod-001"));
+    }
+
+    #[tokio::test]
+    async fn test_import_twice_is_rejected() {
+        let pool = setup_test_db().await;
+        let repo = PurchaseRepository::new(&pool);
+
+        repo.import(invoice(Vec::new())).await.unwrap();
+        let second = repo.import(invoice(Vec::new())).await;
+        assert!(matches!(second, Err(AppError::Duplicate(_))));
+
+        let stock: f64 =
+            sqlx::query_scalar("SELECT current_stock FROM products WHERE id = 'prod-001'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(stock, 12.0);
+    }
+
+    #[test]
+    fn test_split_lots_remainder() {
+        let lots = split_lots(10.0, &[lot("A", 6.0)]);
+        assert_eq!(lots.len(), 2);
+        assert_eq!(lots[1].lot_number, None);
+        assert_eq!(lots[1].quantity, 4.0);
+
+        let single = split_lots(5.0, &[]);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].quantity, 5.0);
+    }
+}
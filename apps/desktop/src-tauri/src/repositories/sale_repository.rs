@@ -2,11 +2,13 @@
 
 use crate::error::AppResult;
 use crate::models::{
-    CreateSale, CreateSaleItem, DailySalesSummary, MonthlySalesSummary, PaymentMethodSummary, Sale,
-    SaleItem, SaleWithDetails,
+    CreateSale, CreateSaleItem, DailySalesSummary, ItemPromotion, MonthlySalesSummary,
+    PaymentMethod, PaymentMethodSummary, Sale, SaleItem, SaleWithDetails,
 };
 use crate::repositories::new_id;
-use crate::repositories::SettingsRepository;
+use crate::repositories::{
+    CreditAccountRepository, PricingRuleRepository, SettingsRepository, StoreCreditRepository,
+};
 use sqlx::Row;
 use sqlx::SqlitePool;
 
@@ -34,7 +36,7 @@ impl<'a> SaleRepository<'a> {
     }
 
     const SALE_COLS: &'static str = "id, daily_number, subtotal, discount_type, discount_value, discount_reason, total, payment_method, amount_paid, change, status, canceled_at, canceled_by_id, cancel_reason, customer_id, employee_id, cash_session_id, created_at, updated_at";
-    const ITEM_COLS: &'static str = "id, sale_id, product_id, quantity, unit_price, discount, total, product_name, product_barcode, product_unit, lot_id, created_at";
+    const ITEM_COLS: &'static str = "id, sale_id, product_id, quantity, unit_price, discount, total, product_name, product_barcode, product_unit, lot_id, pricing_rule_id, discount_reason, created_at";
 
     pub async fn find_by_id(&self, id: &str) -> AppResult<Option<Sale>> {
         let query = format!("SELECT {} FROM sales WHERE id = ?", Self::SALE_COLS);
@@ -193,9 +195,40 @@ impl<'a> SaleRepository<'a> {
 
     pub async fn create(&self, data: CreateSale) -> AppResult<Sale> {
         let mut tx = self.pool.begin().await?;
+        let id = self.create_tx(&mut tx, data).await?;
+        tx.commit().await?;
+
+        let mut sale =
+            self.find_by_id(&id)
+                .await?
+                .ok_or_else(|| crate::error::AppError::NotFound {
+                    entity: "Sale".into(),
+                    id: id.clone(),
+                })?;
+
+        let payments = self.find_payments_by_sale(&id).await?;
+        sale.payments = Some(payments);
+
+        // Sincronização em tempo real (broadcast)
+        if let Some(service) = self.event_service {
+            let items = self.find_items_by_sale(&id).await.unwrap_or_default();
+            for item in items {
+                service.emit_stock_updated(&item.product_id, &item.product_name, 0.0, 0.0, "SALE");
+            }
+        }
+
+        Ok(sale)
+    }
+
+    /// Registra a venda dentro de uma transação existente e retorna o id
+    pub async fn create_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        data: CreateSale,
+    ) -> AppResult<String> {
         let id = new_id();
         let now = chrono::Utc::now().to_rfc3339();
-        let daily_number = self.get_next_daily_number_tx(&mut tx).await?;
+        let daily_number = self.get_next_daily_number_tx(tx).await?;
 
         // Rate limiting: prevent duplicate sales within 2 seconds by same employee
         // This catches accidental double-clicks or double-submissions
@@ -207,7 +240,7 @@ impl<'a> SaleRepository<'a> {
                  LIMIT 1",
             )
             .bind(&data.employee_id)
-            .fetch_optional(&mut *tx)
+            .fetch_optional(&mut **tx)
             .await?;
 
             if recent_sale.is_some() {
@@ -219,7 +252,9 @@ impl<'a> SaleRepository<'a> {
 
         // Read PDV setting: allow selling when stock is insufficient
         let settings_repo = SettingsRepository::new(self.pool);
-        let allow_sale_zero = settings_repo.get_bool("pdv.allow_sale_zero_stock").await?;
+        let allow_sale_zero = settings_repo
+            .get_bool_tx(tx, "pdv.allow_sale_zero_stock")
+            .await?;
 
         // Business validation: ensure sufficient stock for all items before mutating
         use std::collections::HashMap;
@@ -236,7 +271,7 @@ impl<'a> SaleRepository<'a> {
                 let available: Option<(f64,)> =
                     sqlx::query_as("SELECT current_stock FROM products WHERE id = ?")
                         .bind(product_id)
-                        .fetch_optional(&mut *tx)
+                        .fetch_optional(&mut **tx)
                         .await?;
                 let available_val = available.map(|t| t.0).unwrap_or(0.0);
                 if available_val < *requested {
@@ -248,15 +283,32 @@ impl<'a> SaleRepository<'a> {
             }
         }
 
-        // Calculate totals
-        let subtotal: f64 = data.items.iter().map(|i| i.quantity * i.unit_price).sum();
+        // Promoções automáticas: no máximo uma regra por item, sem sobrepor desconto manual
+        let pricing = PricingRuleRepository::new(self.pool)
+            .evaluate_tx(tx, &data.items, data.customer_id.as_deref())
+            .await?;
+        let promotion_for = |index: usize| {
+            pricing
+                .items
+                .iter()
+                .find(|p| p.item_index as usize == index)
+        };
+
+        // Calculate totals: subtotal é a soma dos itens já líquidos (desconto manual
+        // e promoção), como no carrinho do PDV; o desconto geral sai do subtotal
+        let subtotal: f64 = data
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, i)| item_net_total(i, promotion_for(index)))
+            .sum();
         let discount = data.discount_value.unwrap_or(0.0);
 
         // Validate discount limits
         if discount > 0.0 {
             // Get max discount percentage allowed from settings (default 100% = no limit)
             let max_discount_percent = settings_repo
-                .get_number("pdv.max_discount_percent")
+                .get_number_tx(tx, "pdv.max_discount_percent")
                 .await?
                 .unwrap_or(100.0);
 
@@ -278,11 +330,24 @@ impl<'a> SaleRepository<'a> {
         let total = subtotal - discount;
         let change = data.amount_paid - total;
 
+        // Vale-crédito não gera troco
+        let store_credit_paid: f64 = data
+            .payments
+            .iter()
+            .filter(|p| p.method == PaymentMethod::StoreCredit)
+            .map(|p| p.amount)
+            .sum();
+        if store_credit_paid > total + 0.005 {
+            return Err(crate::error::AppError::Validation(
+                "Valor do vale-crédito não pode exceder o total da venda".into(),
+            ));
+        }
+
         // Primary method (first piece or 'OTHER')
         let primary_method = data
             .payments
             .first()
-            .map(|p| format!("{:?}", p.method).to_uppercase())
+            .map(|p| p.method.as_str().to_string())
             .unwrap_or_else(|| "OTHER".to_string());
 
         let discount_type = data
@@ -307,57 +372,97 @@ impl<'a> SaleRepository<'a> {
         .bind(&data.cash_session_id)
         .bind(&now)
         .bind(&now)
-        .execute(&mut *tx)
+        .execute(&mut **tx)
         .await?;
 
         // Insert payments
         for payment in &data.payments {
             let pay_id = new_id();
-            let method_str = format!("{:?}", payment.method).to_uppercase();
+            let method_str = payment.method.as_str();
+            let card = payment.card.clone().unwrap_or_default();
             sqlx::query(
-                "INSERT INTO sale_payments (id, sale_id, method, amount, created_at) VALUES (?, ?, ?, ?, ?)"
+                "INSERT INTO sale_payments (id, sale_id, method, amount, card_integration, acquirer_cnpj, card_brand, authorization_code, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
             )
             .bind(pay_id)
             .bind(&id)
             .bind(method_str)
             .bind(payment.amount)
+            .bind(card.card_integration)
+            .bind(&card.acquirer_cnpj)
+            .bind(&card.card_brand)
+            .bind(&card.authorization_code)
             .bind(&now)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await?;
+
+            // Fiado: gera as parcelas no crediário do cliente
+            if payment.method == PaymentMethod::Account {
+                let customer_id = data.customer_id.as_deref().ok_or_else(|| {
+                    crate::error::AppError::Validation(
+                        "Venda fiado exige cliente identificado".into(),
+                    )
+                })?;
+                CreditAccountRepository::new(self.pool)
+                    .post_sale_tx(tx, customer_id, &id, payment.amount, payment.installments)
+                    .await?;
+            }
+
+            // Vale-crédito / vale-presente: debita o saldo do vale
+            if payment.method == PaymentMethod::StoreCredit {
+                let code = payment.store_credit_code.as_deref().ok_or_else(|| {
+                    crate::error::AppError::Validation("Informe o código do vale-crédito".into())
+                })?;
+                StoreCreditRepository::new(self.pool)
+                    .redeem_tx(
+                        tx,
+                        code,
+                        payment.amount,
+                        &id,
+                        &data.cash_session_id,
+                        &data.employee_id,
+                    )
+                    .await?;
+            }
         }
 
         // Insert items and update stock
-        for item in &data.items {
-            self.create_item_tx(&mut tx, &id, item, &data.employee_id, allow_sale_zero)
-                .await?;
+        for (index, item) in data.items.iter().enumerate() {
+            self.create_item_tx(
+                tx,
+                &id,
+                item,
+                promotion_for(index),
+                &data.employee_id,
+                allow_sale_zero,
+            )
+            .await?;
         }
 
         // Record commission if applicable
-        self.record_commission_tx(&mut tx, &id, &data.employee_id, total, &now)
+        self.record_commission_tx(tx, &id, &data.employee_id, total, &now)
             .await?;
 
-        tx.commit().await?;
-
-        let mut sale =
-            self.find_by_id(&id)
-                .await?
-                .ok_or_else(|| crate::error::AppError::NotFound {
-                    entity: "Sale".into(),
-                    id: id.clone(),
-                })?;
-
-        let payments = self.find_payments_by_sale(&id).await?;
-        sale.payments = Some(payments);
-
-        // Sincronização em tempo real (broadcast)
-        if let Some(service) = self.event_service {
-            let items = self.find_items_by_sale(&id).await.unwrap_or_default();
-            for item in items {
-                service.emit_stock_updated(&item.product_id, &item.product_name, 0.0, 0.0, "SALE");
-            }
-        }
+        Ok(id)
+    }
 
-        Ok(sale)
+    /// Total que `create` cobraria pela venda (promoções e desconto geral aplicados)
+    pub async fn estimate_total(&self, data: &CreateSale) -> AppResult<f64> {
+        let pricing = PricingRuleRepository::new(self.pool)
+            .evaluate(&data.items, data.customer_id.as_deref())
+            .await?;
+        let subtotal: f64 = data
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, i)| {
+                let promotion = pricing
+                    .items
+                    .iter()
+                    .find(|p| p.item_index as usize == index);
+                item_net_total(i, promotion)
+            })
+            .sum();
+        Ok(subtotal - data.discount_value.unwrap_or(0.0))
     }
 
     async fn get_next_daily_number_tx(
@@ -416,13 +521,14 @@ impl<'a> SaleRepository<'a> {
         tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
         sale_id: &str,
         item: &CreateSaleItem,
+        promotion: Option<&ItemPromotion>,
         employee_id: &str,
         allow_sale_zero: bool,
     ) -> AppResult<()> {
         let item_id = new_id();
         let now = chrono::Utc::now().to_rfc3339();
-        let discount = item.discount.unwrap_or(0.0);
-        let total = (item.quantity * item.unit_price) - discount;
+        let discount = item.discount.unwrap_or(0.0) + promotion.map(|p| p.discount).unwrap_or(0.0);
+        let total = item_net_total(item, promotion);
 
         // Get product info and current stock
         let product: Option<(String, Option<String>, String, f64)> =
@@ -501,7 +607,7 @@ impl<'a> SaleRepository<'a> {
 
         // Insert sale item
         sqlx::query(
-            "INSERT INTO sale_items (id, sale_id, product_id, lot_id, quantity, unit_price, discount, total, product_name, product_barcode, product_unit, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO sale_items (id, sale_id, product_id, lot_id, quantity, unit_price, discount, total, product_name, product_barcode, product_unit, pricing_rule_id, discount_reason, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&item_id)
         .bind(sale_id)
@@ -514,6 +620,8 @@ impl<'a> SaleRepository<'a> {
         .bind(&product_name)
         .bind(&product_barcode)
         .bind(&product_unit)
+        .bind(promotion.map(|p| &p.rule_id))
+        .bind(promotion.map(|p| &p.reason))
         .bind(&now)
         .execute(&mut **tx)
         .await?;
@@ -525,6 +633,31 @@ impl<'a> SaleRepository<'a> {
         let mut tx = self.pool.begin().await?;
         let now = chrono::Utc::now().to_rfc3339();
 
+        // Devolução já repôs estoque e reembolsou o cliente: cancelar devolveria de novo
+        let returns: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sale_returns WHERE sale_id = ?")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if returns.0 > 0 {
+            return Err(crate::error::AppError::SaleCannotBeCanceled(
+                "venda possui devoluções registradas".into(),
+            ));
+        }
+
+        // Parcela do crediário já recebida: o dinheiro entrou no caixa e não tem para onde voltar
+        let received: (f64,) = sqlx::query_as(
+            "SELECT COALESCE(SUM(paid_amount), 0.0) FROM receivables WHERE sale_id = ? AND status != 'CANCELED'",
+        )
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if received.0 > 0.0 {
+            return Err(crate::error::AppError::SaleCannotBeCanceled(format!(
+                "crediário da venda já recebeu R$ {:.2}",
+                received.0
+            )));
+        }
+
         // Get sale items to revert stock
         let items = self.find_items_by_sale_tx(&mut tx, id).await?;
 
@@ -584,6 +717,18 @@ impl<'a> SaleRepository<'a> {
             .execute(&mut *tx)
             .await?;
 
+        // Give back store credit used as payment
+        StoreCreditRepository::new(self.pool)
+            .reverse_sale_tx(&mut tx, id, canceled_by)
+            .await?;
+
+        // Cancel open installments of a credit (fiado) sale
+        sqlx::query("UPDATE receivables SET status = 'CANCELED', updated_at = ? WHERE sale_id = ? AND status = 'OPEN'")
+            .bind(&now)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
         tx.commit().await?;
 
         let sale = self
@@ -608,7 +753,7 @@ impl<'a> SaleRepository<'a> {
         sale_id: &str,
     ) -> AppResult<Vec<crate::models::SalePayment>> {
         let result = sqlx::query_as::<_, crate::models::SalePayment>(
-            "SELECT id, sale_id, method, amount, created_at FROM sale_payments WHERE sale_id = ?",
+            "SELECT id, sale_id, method, amount, card_integration, acquirer_cnpj, card_brand, authorization_code, created_at FROM sale_payments WHERE sale_id = ?",
         )
         .bind(sale_id)
         .fetch_all(self.pool)
@@ -655,6 +800,7 @@ impl<'a> SaleRepository<'a> {
             SELECT method as payment_method, SUM(amount) as total, COUNT(*) as count
             FROM sale_payments 
             WHERE sale_id IN (SELECT id FROM sales WHERE date(created_at) = ? AND status = 'COMPLETED')
+              AND method != 'EXCHANGE_CREDIT'
             GROUP BY method
             "#,
         )
@@ -702,27 +848,25 @@ impl<'a> SaleRepository<'a> {
     }
 }
 
+/// Total do item: bruto menos desconto manual e promoção automática
+fn item_net_total(item: &CreateSaleItem, promotion: Option<&ItemPromotion>) -> f64 {
+    item.quantity * item.unit_price
+        - item.discount.unwrap_or(0.0)
+        - promotion.map(|p| p.discount).unwrap_or(0.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{CreateSaleItem, CreateSalePayment, DiscountType, PaymentMethod};
+    use crate::models::{
+        CreateSaleItem, CreateSalePayment, DiscountType, PaymentMethod, SetSetting,
+    };
+    use crate::repositories::test_support::{seed_pdv, setup_file_db};
     use sqlx::SqlitePool;
 
     async fn setup_test_db() -> SqlitePool {
-        let pool = sqlx::sqlite::SqlitePoolOptions::new()
-            .max_connections(5)
-            .connect("sqlite::memory:")
-            .await
-            .unwrap();
-
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
-
-        // Seed basic data
-        sqlx::query("INSERT INTO employees (id, name, pin, role, is_active, created_at, updated_at) VALUES ('emp-001', 'Test Employee', '8899', 'OPERATOR', 1, datetime('now'), datetime('now'))").execute(&pool).await.unwrap();
-        sqlx::query("INSERT INTO categories (id, name, is_active, created_at, updated_at) VALUES ('cat-001', 'General', 1, datetime('now'), datetime('now'))").execute(&pool).await.unwrap();
-        sqlx::query("INSERT INTO products (id, barcode, internal_code, name, unit, sale_price, cost_price, current_stock, category_id, is_active, created_at, updated_at) VALUES ('prod-001', '123456', 'P001', 'Test Product', 'UNIT', 10.0, 5.0, 100.0, 'cat-001', 1, datetime('now'), datetime('now'))").execute(&pool).await.unwrap();
-        sqlx::query("INSERT INTO cash_sessions (id, employee_id, opening_balance, status, opened_at, created_at, updated_at) VALUES ('cs-001', 'emp-001', 100.0, 'OPEN', datetime('now'), datetime('now'), datetime('now'))").execute(&pool).await.unwrap();
-
+        let pool = setup_file_db("sales").await;
+        seed_pdv(&pool, 10.0, 100.0).await;
         pool
     }
 
@@ -744,6 +888,9 @@ mod tests {
             payments: vec![CreateSalePayment {
                 method: PaymentMethod::Cash,
                 amount: 20.0,
+                installments: None,
+                store_credit_code: None,
+                card: None,
             }],
             amount_paid: 25.0,
             discount_type: None,
@@ -778,6 +925,9 @@ mod tests {
             payments: vec![CreateSalePayment {
                 method: PaymentMethod::Cash,
                 amount: 2000.0,
+                installments: None,
+                store_credit_code: None,
+                card: None,
             }],
             amount_paid: 2000.0,
             discount_type: None,
@@ -807,6 +957,9 @@ mod tests {
             payments: vec![CreateSalePayment {
                 method: PaymentMethod::Cash,
                 amount: 45.0,
+                installments: None,
+                store_credit_code: None,
+                card: None,
             }],
             amount_paid: 45.0,
             discount_type: Some(DiscountType::Fixed),
@@ -839,6 +992,9 @@ mod tests {
             payments: vec![CreateSalePayment {
                 method: PaymentMethod::Debit,
                 amount: 10.0,
+                installments: None,
+                store_credit_code: None,
+                card: None,
             }],
             amount_paid: 10.0,
             discount_type: None,
@@ -871,6 +1027,9 @@ mod tests {
             payments: vec![CreateSalePayment {
                 method: PaymentMethod::Cash,
                 amount: 10.0,
+                installments: None,
+                store_credit_code: None,
+                card: None,
             }],
             amount_paid: 10.0,
             discount_type: None,
@@ -902,6 +1061,9 @@ mod tests {
             payments: vec![CreateSalePayment {
                 method: PaymentMethod::Cash,
                 amount: 10.0,
+                installments: None,
+                store_credit_code: None,
+                card: None,
             }],
             amount_paid: 10.0,
             discount_type: None,
@@ -949,6 +1111,9 @@ mod tests {
                 payments: vec![CreateSalePayment {
                     method: PaymentMethod::Cash,
                     amount: 10.0,
+                    installments: None,
+                    store_credit_code: None,
+                    card: None,
                 }],
                 amount_paid: 10.0,
                 discount_type: None,
@@ -963,6 +1128,67 @@ mod tests {
         assert_eq!(summary.total_sales, 3);
     }
 
+    #[tokio::test]
+    async fn test_subtotal_net_of_item_discounts() {
+        let pool = setup_test_db().await;
+        let repo = SaleRepository::new(&pool);
+        SettingsRepository::new(&pool)
+            .set(SetSetting {
+                key: "pdv.max_discount_percent".to_string(),
+                value: "10".to_string(),
+                value_type: Some("NUMBER".to_string()),
+                group_name: Some("pdv".to_string()),
+                description: None,
+            })
+            .await
+            .unwrap();
+
+        // R$ 50 bruto, R$ 10 de desconto no item: subtotal R$ 40
+        let input = |general: f64| CreateSale {
+            customer_id: None,
+            employee_id: "emp-001".to_string(),
+            cash_session_id: "cs-001".to_string(),
+            items: vec![CreateSaleItem {
+                product_id: "prod-001".to_string(),
+                quantity: 5.0,
+                unit_price: 10.0,
+                discount: Some(10.0),
+            }],
+            payments: vec![CreateSalePayment {
+                method: PaymentMethod::Cash,
+                amount: 40.0 - general,
+                installments: None,
+                store_credit_code: None,
+                card: None,
+            }],
+            amount_paid: 40.0 - general,
+            discount_type: Some(DiscountType::Fixed),
+            discount_value: Some(general),
+            discount_reason: Some("Cliente".to_string()),
+        };
+
+        // R$ 4,50 é 11,25% do subtotal líquido (seria 9% do bruto)
+        let err = repo.create(input(4.5)).await.unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::AppError::DiscountExceedsLimit { .. }
+        ));
+
+        assert_eq!(repo.estimate_total(&input(4.0)).await.unwrap(), 36.0);
+        let sale = repo.create(input(4.0)).await.unwrap();
+        assert_eq!(sale.subtotal, 40.0);
+        assert_eq!(sale.discount_value, 4.0);
+        assert_eq!(sale.total, 36.0);
+
+        let items = repo.find_items_by_sale(&sale.id).await.unwrap();
+        assert_eq!(items[0].total, sale.subtotal);
+
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let summary = repo.get_daily_summary(&today).await.unwrap();
+        assert_eq!(summary.total_amount, 36.0);
+        assert_eq!(summary.by_payment_method[0].amount, 36.0);
+    }
+
     #[tokio::test]
     async fn test_create_sale_generates_commission() {
         let pool = setup_test_db().await;
@@ -986,6 +1212,9 @@ mod tests {
             payments: vec![CreateSalePayment {
                 method: PaymentMethod::Cash,
                 amount: 100.0,
+                installments: None,
+                store_credit_code: None,
+                card: None,
             }],
             amount_paid: 100.0,
             discount_type: None,
@@ -1028,6 +1257,9 @@ mod tests {
             payments: vec![CreateSalePayment {
                 method: PaymentMethod::Cash,
                 amount: 100.0,
+                installments: None,
+                store_credit_code: None,
+                card: None,
             }],
             amount_paid: 100.0,
             discount_type: None,
@@ -1048,4 +1280,67 @@ mod tests {
             .unwrap();
         assert_eq!(count.0, 0);
     }
+
+    #[tokio::test]
+    async fn test_create_sale_applies_pricing_rule() {
+        let pool = setup_test_db().await;
+        let repo = SaleRepository::new(&pool);
+
+        let promotion = crate::models::SavePricingRule {
+            id: None,
+            name: "Leve 3 pague 2".to_string(),
+            kind: "BUY_X_PAY_Y".to_string(),
+            product_id: Some("prod-001".to_string()),
+            category_id: None,
+            customer_group: None,
+            quantity: 3.0,
+            pay_quantity: 2.0,
+            percent: 0.0,
+            price: None,
+            start_time: None,
+            end_time: None,
+            weekdays: None,
+            valid_from: None,
+            valid_until: None,
+            priority: 0,
+            items: Vec::new(),
+        };
+        let rule = PricingRuleRepository::new(&pool)
+            .save(promotion)
+            .await
+            .unwrap();
+
+        let input = CreateSale {
+            customer_id: None,
+            employee_id: "emp-001".to_string(),
+            cash_session_id: "cs-001".to_string(),
+            items: vec![CreateSaleItem {
+                product_id: "prod-001".to_string(),
+                quantity: 3.0,
+                unit_price: 10.0,
+                discount: Some(0.0),
+            }],
+            payments: vec![CreateSalePayment {
+                method: PaymentMethod::Cash,
+                amount: 20.0,
+                installments: None,
+                store_credit_code: None,
+                card: None,
+            }],
+            amount_paid: 20.0,
+            discount_type: None,
+            discount_value: None,
+            discount_reason: None,
+        };
+
+        let sale = repo.create(input).await.unwrap();
+        assert_eq!(sale.subtotal, 20.0);
+        assert_eq!(sale.total, 20.0);
+
+        let items = repo.find_items_by_sale(&sale.id).await.unwrap();
+        assert_eq!(items[0].discount, 10.0);
+        assert_eq!(items[0].total, 20.0);
+        assert_eq!(items[0].pricing_rule_id.as_deref(), Some(rule.id.as_str()));
+        assert_eq!(items[0].discount_reason.as_deref(), Some("Leve 3 pague 2"));
+    }
 }
@@ -0,0 +1,361 @@
+//! Repositório de Devoluções e Trocas
+
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    CreateSalePayment, CreateSaleReturn, PaymentMethod, ReturnableSaleItem, SaleReturn,
+    SaleReturnItem,
+};
+use crate::repositories::new_id;
+use crate::repositories::store_credit_repository::NewStoreCredit;
+use crate::repositories::{CashRepository, SaleRepository, StoreCreditRepository};
+use sqlx::SqlitePool;
+
+pub struct SaleReturnRepository<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> SaleReturnRepository<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    const COLS: &'static str = "id, sale_id, customer_id, employee_id, cash_session_id, total, refund_type, refund_method, refund_amount, exchange_sale_id, store_credit_id, reason, fiscal_access_key, created_at";
+    const ITEM_COLS: &'static str = "id, return_id, sale_item_id, product_id, product_name, lot_id, quantity, unit_price, total, restocked, created_at";
+
+    pub async fn find_by_id(&self, id: &str) -> AppResult<Option<SaleReturn>> {
+        let query = format!("SELECT {} FROM sale_returns WHERE id = ?", Self::COLS);
+        let result = sqlx::query_as::<_, SaleReturn>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await?;
+        match result {
+            Some(mut sale_return) => {
+                sale_return.items = self.find_items(&sale_return.id).await?;
+                Ok(Some(sale_return))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub async fn find_by_sale(&self, sale_id: &str) -> AppResult<Vec<SaleReturn>> {
+        let query = format!(
+            "SELECT {} FROM sale_returns WHERE sale_id = ? ORDER BY created_at",
+            Self::COLS
+        );
+        let mut returns = sqlx::query_as::<_, SaleReturn>(&query)
+            .bind(sale_id)
+            .fetch_all(self.pool)
+            .await?;
+        for sale_return in returns.iter_mut() {
+            sale_return.items = self.find_items(&sale_return.id).await?;
+        }
+        Ok(returns)
+    }
+
+    pub async fn find_items(&self, return_id: &str) -> AppResult<Vec<SaleReturnItem>> {
+        let query = format!(
+            "SELECT {} FROM sale_return_items WHERE return_id = ? ORDER BY created_at",
+            Self::ITEM_COLS
+        );
+        let items = sqlx::query_as::<_, SaleReturnItem>(&query)
+            .bind(return_id)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(items)
+    }
+
+    /// Itens da venda com a quantidade já devolvida e o preço unitário líquido
+    /// (descontos do item e rateio do desconto geral da venda)
+    pub async fn find_returnable_items(&self, sale_id: &str) -> AppResult<Vec<ReturnableSaleItem>> {
+        let mut tx = self.pool.begin().await?;
+        let items = self.find_returnable_items_tx(&mut tx, sale_id).await?;
+        tx.commit().await?;
+        Ok(items)
+    }
+
+    async fn find_returnable_items_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        sale_id: &str,
+    ) -> AppResult<Vec<ReturnableSaleItem>> {
+        let sale: Option<(f64, f64)> =
+            sqlx::query_as("SELECT subtotal, total FROM sales WHERE id = ?")
+                .bind(sale_id)
+                .fetch_optional(&mut **tx)
+                .await?;
+        let (subtotal, total) = sale.ok_or_else(|| AppError::NotFound {
+            entity: "Sale".into(),
+            id: sale_id.into(),
+        })?;
+        let factor = if subtotal > 0.0 {
+            total / subtotal
+        } else {
+            1.0
+        };
+
+        let items = sqlx::query_as::<_, ReturnableSaleItem>(
+            "SELECT si.id AS sale_item_id, si.product_id, si.product_name, si.lot_id,
+                    si.quantity AS sold_quantity,
+                    COALESCE((SELECT SUM(ri.quantity) FROM sale_return_items ri WHERE ri.sale_item_id = si.id), 0.0) AS returned_quantity,
+                    CASE WHEN si.quantity > 0 THEN si.total / si.quantity * ? ELSE 0.0 END AS unit_price
+             FROM sale_items si WHERE si.sale_id = ? ORDER BY si.created_at",
+        )
+        .bind(factor)
+        .bind(sale_id)
+        .fetch_all(&mut **tx)
+        .await?;
+        Ok(items)
+    }
+
+    /// Registra a devolução: volta os itens ao estoque, estorna o valor (forma de
+    /// pagamento ou vale-crédito) e, na troca, abate o valor da venda nova
+    pub async fn create(&self, data: CreateSaleReturn) -> AppResult<SaleReturn> {
+        if data.items.is_empty() {
+            return Err(AppError::Validation(
+                "Selecione ao menos um item para devolver".into(),
+            ));
+        }
+        if data.refund_type != "PAYMENT" && data.refund_type != "STORE_CREDIT" {
+            return Err(AppError::Validation(format!(
+                "Tipo de reembolso inválido: {}",
+                data.refund_type
+            )));
+        }
+
+        let sale_repo = SaleRepository::new(self.pool);
+        let sale =
+            sale_repo
+                .find_by_id(&data.sale_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound {
+                    entity: "Sale".into(),
+                    id: data.sale_id.clone(),
+                })?;
+        if sale.status != "COMPLETED" {
+            return Err(AppError::Validation(
+                "Venda cancelada não pode ter devolução".into(),
+            ));
+        }
+
+        // Quantidade devolvida não pode passar do vendido menos o já devolvido;
+        // conferida na mesma transação que grava a devolução
+        let mut tx = self.pool.begin().await?;
+        let mut returnable = self.find_returnable_items_tx(&mut tx, &sale.id).await?;
+        let mut lines = Vec::with_capacity(data.items.len());
+        for item in &data.items {
+            if item.quantity <= 0.0 {
+                return Err(AppError::Validation(
+                    "Quantidade a devolver deve ser maior que zero".into(),
+                ));
+            }
+            let Some(sold) = returnable
+                .iter_mut()
+                .find(|r| r.sale_item_id == item.sale_item_id)
+            else {
+                return Err(AppError::Validation(format!(
+                    "Item {} não pertence à venda",
+                    item.sale_item_id
+                )));
+            };
+            let available = sold.sold_quantity - sold.returned_quantity;
+            if item.quantity > available + 1e-9 {
+                return Err(AppError::Validation(format!(
+                    "Devolução de {} excede a quantidade disponível ({:.3})",
+                    sold.product_name, available
+                )));
+            }
+            sold.returned_quantity += item.quantity;
+            let total = round_cents(sold.unit_price * item.quantity);
+            lines.push((item, sold.clone(), total));
+        }
+        let total = round_cents(lines.iter().map(|(_, _, t)| t).sum());
+
+        // Troca: o valor devolvido paga (parte d)a venda nova
+        if data.exchange.is_some() && data.cash_session_id.is_none() {
+            return Err(AppError::CashSessionNotOpen);
+        }
+        let mut credit_used = 0.0;
+        let mut exchange = data.exchange.clone();
+        if let Some(new_sale) = exchange.as_mut() {
+            let new_total = sale_repo.estimate_total(new_sale).await?;
+            credit_used = round_cents(total.min(new_total).max(0.0));
+            if credit_used > 0.0 {
+                new_sale.payments.push(CreateSalePayment {
+                    method: PaymentMethod::ExchangeCredit,
+                    amount: credit_used,
+                    installments: None,
+                    store_credit_code: None,
+                    card: None,
+                });
+                new_sale.amount_paid += credit_used;
+            }
+            if new_sale.customer_id.is_none() {
+                new_sale.customer_id = sale.customer_id.clone();
+            }
+        }
+
+        let refund_amount = round_cents(total - credit_used);
+        let refund_method = match (data.refund_type.as_str(), data.refund_method) {
+            ("PAYMENT", Some(method)) => Some(method.as_str().to_string()),
+            ("PAYMENT", None) if refund_amount > 0.0 => {
+                return Err(AppError::Validation(
+                    "Informe a forma de pagamento do estorno".into(),
+                ))
+            }
+            _ => None,
+        };
+        if refund_method.is_some() && refund_amount > 0.0 {
+            let Some(session_id) = data.cash_session_id.as_deref() else {
+                return Err(AppError::CashSessionNotOpen);
+            };
+            if refund_method.as_deref() == Some("CASH") {
+                let summary = CashRepository::new(self.pool)
+                    .get_session_summary(session_id)
+                    .await?;
+                if refund_amount > summary.cash_in_drawer {
+                    return Err(AppError::Validation(format!(
+                        "Estorno de R$ {:.2} excede o saldo disponível em caixa de R$ {:.2}",
+                        refund_amount, summary.cash_in_drawer
+                    )));
+                }
+            }
+        }
+
+        let exchange_sale_id = match exchange {
+            Some(new_sale) => Some(sale_repo.create_tx(&mut tx, new_sale).await?),
+            None => None,
+        };
+
+        let id = new_id();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO sale_returns (id, sale_id, customer_id, employee_id, cash_session_id, total, refund_type, refund_method, refund_amount, exchange_sale_id, reason, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(&sale.id)
+        .bind(&sale.customer_id)
+        .bind(&data.employee_id)
+        .bind(&data.cash_session_id)
+        .bind(total)
+        .bind(&data.refund_type)
+        .bind(&refund_method)
+        .bind(refund_amount)
+        .bind(&exchange_sale_id)
+        .bind(&data.reason)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?;
+
+        for (item, sold, line_total) in &lines {
+            let restock = item.restock.unwrap_or(true);
+            let lot_id = item.lot_id.clone().or_else(|| sold.lot_id.clone());
+
+            if restock {
+                let current: (f64,) =
+                    sqlx::query_as("SELECT current_stock FROM products WHERE id = ?")
+                        .bind(&sold.product_id)
+                        .fetch_one(&mut *tx)
+                        .await?;
+                sqlx::query("UPDATE products SET current_stock = current_stock + ?, updated_at = (datetime('now')) WHERE id = ?")
+                    .bind(item.quantity)
+                    .bind(&sold.product_id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                if let Some(lot_id) = &lot_id {
+                    let updated = sqlx::query("UPDATE product_lots SET current_quantity = current_quantity + ?, updated_at = (datetime('now')) WHERE id = ? AND product_id = ?")
+                        .bind(item.quantity)
+                        .bind(lot_id)
+                        .bind(&sold.product_id)
+                        .execute(&mut *tx)
+                        .await?;
+                    if updated.rows_affected() == 0 {
+                        return Err(AppError::Validation(format!(
+                            "Lote {} não pertence ao produto {}",
+                            lot_id, sold.product_name
+                        )));
+                    }
+                }
+
+                sqlx::query(
+                    "INSERT INTO stock_movements (id, product_id, type, quantity, previous_stock, new_stock, reason, reference_id, reference_type, employee_id, created_at) VALUES (?, ?, 'RETURN', ?, ?, ?, ?, ?, 'RETURN', ?, ?)"
+                )
+                .bind(new_id())
+                .bind(&sold.product_id)
+                .bind(item.quantity)
+                .bind(current.0)
+                .bind(current.0 + item.quantity)
+                .bind(format!("Devolução venda #{}", sale.daily_number))
+                .bind(&id)
+                .bind(&data.employee_id)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            sqlx::query(
+                "INSERT INTO sale_return_items (id, return_id, sale_item_id, product_id, product_name, lot_id, quantity, unit_price, total, restocked, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(new_id())
+            .bind(&id)
+            .bind(&sold.sale_item_id)
+            .bind(&sold.product_id)
+            .bind(&sold.product_name)
+            .bind(restock.then_some(lot_id).flatten())
+            .bind(item.quantity)
+            .bind(sold.unit_price)
+            .bind(line_total)
+            .bind(restock)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if data.refund_type == "STORE_CREDIT" && refund_amount > 0.0 {
+            let credit_id = StoreCreditRepository::new(self.pool)
+                .issue_tx(
+                    &mut tx,
+                    NewStoreCredit {
+                        kind: "STORE_CREDIT",
+                        amount: refund_amount,
+                        customer_id: sale.customer_id.as_deref(),
+                        return_id: Some(&id),
+                        expires_at: None,
+                        payment_method: None,
+                        cash_session_id: data.cash_session_id.as_deref(),
+                        employee_id: Some(&data.employee_id),
+                    },
+                )
+                .await?;
+            sqlx::query("UPDATE sale_returns SET store_credit_id = ? WHERE id = ?")
+                .bind(&credit_id)
+                .bind(&id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        self.find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "SaleReturn".into(),
+                id,
+            })
+    }
+
+    /// Vincula a NF-e de devolução emitida
+    pub async fn set_fiscal_access_key(&self, id: &str, access_key: &str) -> AppResult<()> {
+        sqlx::query("UPDATE sale_returns SET fiscal_access_key = ? WHERE id = ?")
+            .bind(access_key)
+            .bind(id)
+            .execute(self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
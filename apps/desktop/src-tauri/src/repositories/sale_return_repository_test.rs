@@ -0,0 +1,256 @@
+//! Testes unitários para SaleReturnRepository
+
+#[cfg(test)]
+mod tests {
+    use super::super::sale_return_repository::SaleReturnRepository;
+    use crate::error::AppError;
+    use crate::models::{
+        CreateSale, CreateSaleItem, CreateSalePayment, CreateSaleReturn, CreateSaleReturnItem,
+        PaymentMethod, Sale,
+    };
+    use crate::repositories::test_support::{seed_pdv, setup_file_db};
+    use crate::repositories::{CashRepository, SaleRepository, StoreCreditRepository};
+    use sqlx::SqlitePool;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = setup_file_db("returns").await;
+        seed_pdv(&pool, 10.0, 20.0).await;
+        sqlx::query(
+            "INSERT INTO products (id, barcode, internal_code, name, unit, sale_price, cost_price, current_stock, category_id, is_active, created_at, updated_at)
+             VALUES ('prod-002', '654321', 'P002', 'Bermuda', 'UNIT', 30.0, 15.0, 20.0, 'cat-001', 1, datetime('now'), datetime('now'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    fn sale_input(product_id: &str, quantity: f64, unit_price: f64) -> CreateSale {
+        CreateSale {
+            customer_id: None,
+            employee_id: "emp-001".to_string(),
+            cash_session_id: "cs-001".to_string(),
+            items: vec![CreateSaleItem {
+                product_id: product_id.to_string(),
+                quantity,
+                unit_price,
+                discount: Some(0.0),
+            }],
+            payments: vec![CreateSalePayment {
+                method: PaymentMethod::Cash,
+                amount: quantity * unit_price,
+                installments: None,
+                store_credit_code: None,
+                card: None,
+            }],
+            amount_paid: quantity * unit_price,
+            discount_type: None,
+            discount_value: None,
+            discount_reason: None,
+        }
+    }
+
+    /// Venda original registrada "ontem" (fora da trava de venda duplicada)
+    async fn create_original_sale(pool: &SqlitePool) -> Sale {
+        let sale = SaleRepository::new(pool)
+            .create(sale_input("prod-001", 5.0, 10.0))
+            .await
+            .unwrap();
+        sqlx::query("UPDATE sales SET created_at = '2000-01-01T00:00:00+00:00' WHERE id = ?")
+            .bind(&sale.id)
+            .execute(pool)
+            .await
+            .unwrap();
+        sale
+    }
+
+    fn return_input(sale_id: &str, sale_item_id: &str, quantity: f64) -> CreateSaleReturn {
+        CreateSaleReturn {
+            sale_id: sale_id.to_string(),
+            items: vec![CreateSaleReturnItem {
+                sale_item_id: sale_item_id.to_string(),
+                quantity,
+                lot_id: None,
+                restock: None,
+            }],
+            refund_type: "STORE_CREDIT".to_string(),
+            refund_method: None,
+            reason: Some("Tamanho errado".to_string()),
+            employee_id: "emp-001".to_string(),
+            cash_session_id: Some("cs-001".to_string()),
+            exchange: None,
+        }
+    }
+
+    async fn current_stock(pool: &SqlitePool, product_id: &str) -> f64 {
+        let stock: (f64,) = sqlx::query_as("SELECT current_stock FROM products WHERE id = ?")
+            .bind(product_id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        stock.0
+    }
+
+    #[tokio::test]
+    async fn test_partial_return_restocks_and_issues_store_credit() {
+        let pool = setup_test_db().await;
+        let repo = SaleReturnRepository::new(&pool);
+        let sale = create_original_sale(&pool).await;
+        let items = SaleRepository::new(&pool)
+            .find_items_by_sale(&sale.id)
+            .await
+            .unwrap();
+        assert_eq!(current_stock(&pool, "prod-001").await, 15.0);
+
+        let result = repo
+            .create(return_input(&sale.id, &items[0].id, 2.0))
+            .await
+            .unwrap();
+        assert_eq!(result.total, 20.0);
+        assert_eq!(result.refund_amount, 20.0);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(current_stock(&pool, "prod-001").await, 17.0);
+
+        let movements: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM stock_movements WHERE type = 'RETURN' AND reference_type = 'RETURN' AND reference_id = ?",
+        )
+        .bind(&result.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(movements.0, 1);
+
+        let credit = StoreCreditRepository::new(&pool)
+            .find_by_id(result.store_credit_id.as_deref().unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(credit.balance, 20.0);
+        assert!(credit.code.starts_with("980"));
+        assert!(crate::hardware::barcode::validate_gs1_check_digit(
+            &credit.code
+        ));
+
+        // Restam 3 unidades devolvíveis
+        let returnable = repo.find_returnable_items(&sale.id).await.unwrap();
+        assert_eq!(returnable[0].returned_quantity, 2.0);
+        assert!(repo
+            .create(return_input(&sale.id, &items[0].id, 4.0))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_after_partial_return_is_rejected() {
+        let pool = setup_test_db().await;
+        let sale = create_original_sale(&pool).await;
+        let items = SaleRepository::new(&pool)
+            .find_items_by_sale(&sale.id)
+            .await
+            .unwrap();
+        let result = SaleReturnRepository::new(&pool)
+            .create(return_input(&sale.id, &items[0].id, 2.0))
+            .await
+            .unwrap();
+
+        let err = SaleRepository::new(&pool)
+            .cancel(&sale.id, "emp-001", "Desistência")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::SaleCannotBeCanceled(_)));
+
+        // Estoque e vale da devolução ficam como estavam
+        assert_eq!(current_stock(&pool, "prod-001").await, 17.0);
+        let credit = StoreCreditRepository::new(&pool)
+            .find_by_id(result.store_credit_id.as_deref().unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(credit.balance, 20.0);
+        let status: (String,) = sqlx::query_as("SELECT status FROM sales WHERE id = ?")
+            .bind(&sale.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(status.0, "COMPLETED");
+    }
+
+    #[tokio::test]
+    async fn test_cash_refund_reduces_drawer() {
+        let pool = setup_test_db().await;
+        let repo = SaleReturnRepository::new(&pool);
+        let sale = create_original_sale(&pool).await;
+        let items = SaleRepository::new(&pool)
+            .find_items_by_sale(&sale.id)
+            .await
+            .unwrap();
+
+        let mut input = return_input(&sale.id, &items[0].id, 1.0);
+        input.refund_type = "PAYMENT".to_string();
+        assert!(repo.create(input.clone()).await.is_err());
+
+        input.refund_method = Some(PaymentMethod::Cash);
+        let result = repo.create(input).await.unwrap();
+        assert_eq!(result.refund_method.as_deref(), Some("CASH"));
+        assert!(result.store_credit_id.is_none());
+
+        let summary = CashRepository::new(&pool)
+            .get_session_summary("cs-001")
+            .await
+            .unwrap();
+        assert_eq!(summary.total_refunds, 10.0);
+        assert_eq!(summary.cash_in_drawer, 100.0 + 50.0 - 10.0);
+    }
+
+    #[tokio::test]
+    async fn test_exchange_nets_return_against_new_sale() {
+        let pool = setup_test_db().await;
+        let repo = SaleReturnRepository::new(&pool);
+        let sale = create_original_sale(&pool).await;
+        let items = SaleRepository::new(&pool)
+            .find_items_by_sale(&sale.id)
+            .await
+            .unwrap();
+
+        // Devolve 2 camisetas (R$ 20) e leva uma bermuda (R$ 30): paga R$ 10
+        let mut input = return_input(&sale.id, &items[0].id, 2.0);
+        let mut exchange = sale_input("prod-002", 1.0, 30.0);
+        exchange.payments[0].amount = 10.0;
+        exchange.amount_paid = 10.0;
+        input.exchange = Some(exchange);
+
+        let result = repo.create(input).await.unwrap();
+        assert_eq!(result.total, 20.0);
+        assert_eq!(result.refund_amount, 0.0);
+        assert!(result.store_credit_id.is_none());
+
+        let exchange_id = result.exchange_sale_id.unwrap();
+        let exchange_sale = SaleRepository::new(&pool)
+            .find_by_id(&exchange_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(exchange_sale.total, 30.0);
+        assert_eq!(exchange_sale.change, 0.0);
+        let payments = SaleRepository::new(&pool)
+            .find_payments_by_sale(&exchange_id)
+            .await
+            .unwrap();
+        assert!(payments
+            .iter()
+            .any(|p| p.method == PaymentMethod::ExchangeCredit && p.amount == 20.0));
+        assert_eq!(current_stock(&pool, "prod-001").await, 17.0);
+        assert_eq!(current_stock(&pool, "prod-002").await, 19.0);
+
+        // Crédito de troca não conta como recebimento no caixa
+        let summary = CashRepository::new(&pool)
+            .get_session_summary("cs-001")
+            .await
+            .unwrap();
+        assert!(summary
+            .sales_by_method
+            .iter()
+            .all(|m| m.method != "EXCHANGE_CREDIT"));
+        assert_eq!(summary.cash_in_drawer, 100.0 + 50.0 + 10.0);
+    }
+}
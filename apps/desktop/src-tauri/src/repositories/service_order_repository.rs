@@ -10,7 +10,9 @@ use crate::models::{
     ServiceOrder, ServiceOrderFilters, ServiceOrderItem, ServiceOrderSummary,
     ServiceOrderWithDetails, UpdateService, UpdateServiceOrder, UpdateServiceOrderItem,
 };
-use crate::repositories::{new_id, PaginatedResult, Pagination, SaleRepository};
+use crate::repositories::{
+    new_id, PaginatedResult, Pagination, SaleRepository, StoreCreditRepository,
+};
 
 pub struct ServiceOrderRepository {
     pool: Pool<Sqlite>,
@@ -1600,7 +1602,7 @@ impl ServiceOrderRepository {
         // Primary method for backwards compatibility
         let primary_method = payments
             .first()
-            .map(|p| format!("{:?}", p.method).to_uppercase())
+            .map(|p| p.method.as_str().to_string())
             .unwrap_or_else(|| "OTHER".to_string());
 
         // 3. Criar Venda (Sale)
@@ -1625,17 +1627,38 @@ impl ServiceOrderRepository {
         // 3.1 Gravar pagamentos múltiplos
         for payment in &payments {
             let pay_id = new_id();
-            let method_str = format!("{:?}", payment.method).to_uppercase();
+            let method_str = payment.method.as_str();
+            let card = payment.card.clone().unwrap_or_default();
             sqlx::query(
-                "INSERT INTO sale_payments (id, sale_id, method, amount, created_at) VALUES (?, ?, ?, ?, ?)"
+                "INSERT INTO sale_payments (id, sale_id, method, amount, card_integration, acquirer_cnpj, card_brand, authorization_code, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
             )
             .bind(pay_id)
             .bind(&sale_id)
             .bind(method_str)
             .bind(payment.amount)
+            .bind(card.card_integration)
+            .bind(&card.acquirer_cnpj)
+            .bind(&card.card_brand)
+            .bind(&card.authorization_code)
             .bind(&now)
             .execute(&mut *tx)
             .await?;
+
+            if payment.method == crate::models::PaymentMethod::StoreCredit {
+                let code = payment.store_credit_code.as_deref().ok_or_else(|| {
+                    AppError::Validation("Informe o código do vale-crédito".into())
+                })?;
+                StoreCreditRepository::new(&self.pool)
+                    .redeem_tx(
+                        &mut tx,
+                        code,
+                        payment.amount,
+                        &sale_id,
+                        cash_session_id,
+                        employee_id,
+                    )
+                    .await?;
+            }
         }
 
         // 4. Criar Itens da Venda (SERVIÇOS + PRODUTOS)
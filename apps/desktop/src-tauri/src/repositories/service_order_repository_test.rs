@@ -394,6 +394,9 @@ async fn test_finish_order_generates_commission() {
     let payments = vec![crate::models::CreateSalePayment {
         method: crate::models::PaymentMethod::Cash,
         amount: 250.0,
+        installments: None,
+        store_credit_code: None,
+        card: None,
     }];
     let result = repo
         .finish_order_transaction(&order.id, payments, 250.0, cashier_id, session_id)
@@ -104,6 +104,24 @@ impl<'a> SettingsRepository<'a> {
         Ok(value.and_then(|v| v.parse().ok()))
     }
 
+    pub async fn get_bool_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        key: &str,
+    ) -> AppResult<bool> {
+        let value = self.find_by_key_tx(tx, key).await?.map(|s| s.value);
+        Ok(value.map(|v| v == "true" || v == "1").unwrap_or(false))
+    }
+
+    pub async fn get_number_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        key: &str,
+    ) -> AppResult<Option<f64>> {
+        let value = self.find_by_key_tx(tx, key).await?.map(|s| s.value);
+        Ok(value.and_then(|v| v.parse().ok()))
+    }
+
     pub async fn set(&self, data: SetSetting) -> AppResult<Setting> {
         let mut conn = self.pool.acquire().await?;
         self.set_conn(&mut conn, data).await
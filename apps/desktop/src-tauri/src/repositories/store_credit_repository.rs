@@ -0,0 +1,343 @@
+//! Repositório de Vale-Crédito e Vale-Presente
+
+use crate::error::{AppError, AppResult};
+use crate::models::{IssueGiftCard, PaymentMethod, StoreCredit, StoreCreditTransaction};
+use crate::repositories::new_id;
+use sqlx::SqlitePool;
+
+pub struct StoreCreditRepository<'a> {
+    pool: &'a SqlitePool,
+}
+
+/// Dados de emissão de um vale (dentro de uma transação)
+pub struct NewStoreCredit<'a> {
+    pub kind: &'a str,
+    pub amount: f64,
+    pub customer_id: Option<&'a str>,
+    pub return_id: Option<&'a str>,
+    pub expires_at: Option<&'a str>,
+    pub payment_method: Option<&'a str>,
+    pub cash_session_id: Option<&'a str>,
+    pub employee_id: Option<&'a str>,
+}
+
+impl<'a> StoreCreditRepository<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    const COLS: &'static str = "id, code, kind, customer_id, return_id, amount, balance, is_active, expires_at, created_at, updated_at";
+    const TX_COLS: &'static str = "id, store_credit_id, type, amount, balance_after, sale_id, return_id, payment_method, cash_session_id, employee_id, created_at";
+
+    pub async fn find_by_id(&self, id: &str) -> AppResult<Option<StoreCredit>> {
+        let query = format!("SELECT {} FROM store_credits WHERE id = ?", Self::COLS);
+        let result = sqlx::query_as::<_, StoreCredit>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_by_code(&self, code: &str) -> AppResult<Option<StoreCredit>> {
+        let query = format!("SELECT {} FROM store_credits WHERE code = ?", Self::COLS);
+        let result = sqlx::query_as::<_, StoreCredit>(&query)
+            .bind(code.trim())
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_by_customer(&self, customer_id: &str) -> AppResult<Vec<StoreCredit>> {
+        let query = format!(
+            "SELECT {} FROM store_credits WHERE customer_id = ? ORDER BY created_at DESC",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, StoreCredit>(&query)
+            .bind(customer_id)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Histórico do vale (mais antigas primeiro)
+    pub async fn find_transactions(
+        &self,
+        store_credit_id: &str,
+    ) -> AppResult<Vec<StoreCreditTransaction>> {
+        let query = format!(
+            "SELECT {} FROM store_credit_transactions WHERE store_credit_id = ? ORDER BY created_at, rowid",
+            Self::TX_COLS
+        );
+        let result = sqlx::query_as::<_, StoreCreditTransaction>(&query)
+            .bind(store_credit_id)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Vende um vale-presente; o valor entra no caixa pela forma de pagamento
+    pub async fn issue_gift_card(&self, data: IssueGiftCard) -> AppResult<StoreCredit> {
+        if data.amount <= 0.0 {
+            return Err(AppError::Validation("Valor deve ser maior que zero".into()));
+        }
+        if matches!(
+            data.payment_method,
+            PaymentMethod::StoreCredit | PaymentMethod::Account | PaymentMethod::ExchangeCredit
+        ) {
+            return Err(AppError::Validation(
+                "Vale-presente deve ser pago em dinheiro, PIX ou cartão".into(),
+            ));
+        }
+        if let Some(expires_at) = &data.expires_at {
+            let valid = chrono::NaiveDate::parse_from_str(expires_at, "%Y-%m-%d")
+                .map(|d| d > chrono::Local::now().date_naive())
+                .unwrap_or(false);
+            if !valid {
+                return Err(AppError::Validation(
+                    "Validade deve ser uma data futura (AAAA-MM-DD)".into(),
+                ));
+            }
+        }
+        let cash_session_id = data
+            .cash_session_id
+            .as_deref()
+            .ok_or(AppError::CashSessionNotOpen)?;
+
+        let mut tx = self.pool.begin().await?;
+        let id = self
+            .issue_tx(
+                &mut tx,
+                NewStoreCredit {
+                    kind: "GIFT_CARD",
+                    amount: data.amount,
+                    customer_id: data.customer_id.as_deref(),
+                    return_id: None,
+                    expires_at: data.expires_at.as_deref(),
+                    payment_method: Some(data.payment_method.as_str()),
+                    cash_session_id: Some(cash_session_id),
+                    employee_id: Some(&data.employee_id),
+                },
+            )
+            .await?;
+        tx.commit().await?;
+
+        self.find_by_id(&id)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "StoreCredit".into(),
+                id,
+            })
+    }
+
+    /// Emite o vale e registra a movimentação de emissão. Retorna o id.
+    pub async fn issue_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        credit: NewStoreCredit<'_>,
+    ) -> AppResult<String> {
+        let id = new_id();
+        let now = chrono::Utc::now().to_rfc3339();
+        let amount = round_cents(credit.amount);
+
+        // Código sorteado pode repetir um já emitido: sorteia de novo
+        let mut attempt = 1;
+        loop {
+            let result = sqlx::query(
+                "INSERT INTO store_credits (id, code, kind, customer_id, return_id, amount, balance, is_active, expires_at, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?)"
+            )
+            .bind(&id)
+            .bind(generate_credit_code())
+            .bind(credit.kind)
+            .bind(credit.customer_id)
+            .bind(credit.return_id)
+            .bind(amount)
+            .bind(amount)
+            .bind(credit.expires_at)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut **tx)
+            .await;
+            match result {
+                Err(sqlx::Error::Database(e))
+                    if e.is_unique_violation() && attempt < CODE_ATTEMPTS =>
+                {
+                    attempt += 1;
+                }
+                other => {
+                    other?;
+                    break;
+                }
+            }
+        }
+
+        sqlx::query(
+            "INSERT INTO store_credit_transactions (id, store_credit_id, type, amount, balance_after, return_id, payment_method, cash_session_id, employee_id, created_at) VALUES (?, ?, 'ISSUE', ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(new_id())
+        .bind(&id)
+        .bind(amount)
+        .bind(amount)
+        .bind(credit.return_id)
+        .bind(credit.payment_method)
+        .bind(credit.cash_session_id)
+        .bind(credit.employee_id)
+        .bind(&now)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Debita o vale usado como pagamento de uma venda (dentro da transação da venda)
+    pub async fn redeem_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        code: &str,
+        amount: f64,
+        sale_id: &str,
+        cash_session_id: &str,
+        employee_id: &str,
+    ) -> AppResult<()> {
+        let query = format!("SELECT {} FROM store_credits WHERE code = ?", Self::COLS);
+        let credit = sqlx::query_as::<_, StoreCredit>(&query)
+            .bind(code.trim())
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| AppError::Validation(format!("Vale {} não encontrado", code)))?;
+
+        if !credit.is_active {
+            return Err(AppError::Validation(format!(
+                "Vale {} está bloqueado",
+                credit.code
+            )));
+        }
+        if let Some(expires_at) = &credit.expires_at {
+            let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+            if expires_at.as_str() < today.as_str() {
+                return Err(AppError::Validation(format!(
+                    "Vale {} venceu em {}",
+                    credit.code, expires_at
+                )));
+            }
+        }
+        let amount = round_cents(amount);
+        if amount <= 0.0 {
+            return Err(AppError::Validation(
+                "Valor do vale deve ser maior que zero".into(),
+            ));
+        }
+        if amount > credit.balance + 0.005 {
+            return Err(AppError::Validation(format!(
+                "Saldo insuficiente no vale {} (disponível: R$ {:.2})",
+                credit.code, credit.balance
+            )));
+        }
+
+        let balance = round_cents(credit.balance - amount);
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query("UPDATE store_credits SET balance = ?, updated_at = ? WHERE id = ?")
+            .bind(balance)
+            .bind(&now)
+            .bind(&credit.id)
+            .execute(&mut **tx)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO store_credit_transactions (id, store_credit_id, type, amount, balance_after, sale_id, cash_session_id, employee_id, created_at) VALUES (?, ?, 'REDEEM', ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(new_id())
+        .bind(&credit.id)
+        .bind(-amount)
+        .bind(balance)
+        .bind(sale_id)
+        .bind(cash_session_id)
+        .bind(employee_id)
+        .bind(&now)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Devolve aos vales o que foi usado numa venda cancelada
+    pub async fn reverse_sale_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        sale_id: &str,
+        employee_id: &str,
+    ) -> AppResult<()> {
+        let redemptions: Vec<(String, f64)> = sqlx::query_as(
+            "SELECT store_credit_id, SUM(amount) FROM store_credit_transactions WHERE sale_id = ? AND type IN ('REDEEM', 'REVERSAL') GROUP BY store_credit_id",
+        )
+        .bind(sale_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        for (credit_id, net) in redemptions {
+            let restore = round_cents(-net);
+            if restore <= 0.0 {
+                continue;
+            }
+            let balance: (f64,) = sqlx::query_as("SELECT balance FROM store_credits WHERE id = ?")
+                .bind(&credit_id)
+                .fetch_one(&mut **tx)
+                .await?;
+            let balance = round_cents(balance.0 + restore);
+
+            sqlx::query("UPDATE store_credits SET balance = ?, updated_at = ? WHERE id = ?")
+                .bind(balance)
+                .bind(&now)
+                .bind(&credit_id)
+                .execute(&mut **tx)
+                .await?;
+
+            sqlx::query(
+                "INSERT INTO store_credit_transactions (id, store_credit_id, type, amount, balance_after, sale_id, employee_id, created_at) VALUES (?, ?, 'REVERSAL', ?, ?, ?, ?, ?)"
+            )
+            .bind(new_id())
+            .bind(&credit_id)
+            .bind(restore)
+            .bind(balance)
+            .bind(sale_id)
+            .bind(employee_id)
+            .bind(&now)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Bloqueia ou libera o uso do vale (ex.: extravio)
+    pub async fn set_active(&self, id: &str, is_active: bool) -> AppResult<StoreCredit> {
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query("UPDATE store_credits SET is_active = ?, updated_at = ? WHERE id = ?")
+            .bind(is_active)
+            .bind(&now)
+            .bind(id)
+            .execute(self.pool)
+            .await?;
+
+        self.find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                entity: "StoreCredit".into(),
+                id: id.into(),
+            })
+    }
+}
+
+/// Sorteios de código antes de desistir da emissão
+const CODE_ATTEMPTS: u32 = 5;
+
+/// Código EAN-13 do vale (prefixo GS1 980, reservado a recibos de reembolso)
+fn generate_credit_code() -> String {
+    let body = format!("980{:09}", rand::random_range(0..1_000_000_000u32));
+    let check = crate::hardware::barcode::gs1_check_digit(&body).unwrap_or(0);
+    format!("{}{}", body, check)
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
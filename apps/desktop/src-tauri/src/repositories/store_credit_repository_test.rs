@@ -0,0 +1,161 @@
+//! Testes unitários para StoreCreditRepository
+
+#[cfg(test)]
+mod tests {
+    use super::super::store_credit_repository::StoreCreditRepository;
+    use crate::models::{
+        CreateSale, CreateSaleItem, CreateSalePayment, IssueGiftCard, PaymentMethod,
+    };
+    use crate::repositories::test_support::{seed_pdv, setup_file_db};
+    use crate::repositories::{CashRepository, SaleRepository};
+    use sqlx::SqlitePool;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = setup_file_db("store_credit").await;
+        seed_pdv(&pool, 25.0, 50.0).await;
+        pool
+    }
+
+    fn gift_card(amount: f64) -> IssueGiftCard {
+        IssueGiftCard {
+            amount,
+            customer_id: None,
+            expires_at: None,
+            payment_method: PaymentMethod::Cash,
+            employee_id: "emp-001".to_string(),
+            cash_session_id: Some("cs-001".to_string()),
+        }
+    }
+
+    fn sale_with_credit(code: &str, credit: f64) -> CreateSale {
+        CreateSale {
+            customer_id: None,
+            employee_id: "emp-001".to_string(),
+            cash_session_id: "cs-001".to_string(),
+            items: vec![CreateSaleItem {
+                product_id: "prod-001".to_string(),
+                quantity: 2.0,
+                unit_price: 25.0,
+                discount: Some(0.0),
+            }],
+            payments: vec![
+                CreateSalePayment {
+                    method: PaymentMethod::StoreCredit,
+                    amount: credit,
+                    installments: None,
+                    store_credit_code: Some(code.to_string()),
+                    card: None,
+                },
+                CreateSalePayment {
+                    method: PaymentMethod::Cash,
+                    amount: 50.0 - credit,
+                    installments: None,
+                    store_credit_code: None,
+                    card: None,
+                },
+            ],
+            amount_paid: 50.0,
+            discount_type: None,
+            discount_value: None,
+            discount_reason: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_issue_gift_card_validations() {
+        let pool = setup_test_db().await;
+        let repo = StoreCreditRepository::new(&pool);
+
+        assert!(repo.issue_gift_card(gift_card(0.0)).await.is_err());
+
+        let mut paid_with_credit = gift_card(50.0);
+        paid_with_credit.payment_method = PaymentMethod::StoreCredit;
+        assert!(repo.issue_gift_card(paid_with_credit).await.is_err());
+
+        let mut past = gift_card(50.0);
+        past.expires_at = Some("2000-01-01".to_string());
+        assert!(repo.issue_gift_card(past).await.is_err());
+
+        let mut no_session = gift_card(50.0);
+        no_session.cash_session_id = None;
+        assert!(repo.issue_gift_card(no_session).await.is_err());
+
+        let card = repo.issue_gift_card(gift_card(50.0)).await.unwrap();
+        assert_eq!(card.kind, "GIFT_CARD");
+        assert_eq!(card.balance, 50.0);
+        assert_eq!(card.code.len(), 13);
+        assert!(card.code.starts_with("980"));
+
+        // Venda do vale em dinheiro entra na gaveta
+        let summary = CashRepository::new(&pool)
+            .get_session_summary("cs-001")
+            .await
+            .unwrap();
+        assert_eq!(summary.total_gift_card_sales, 50.0);
+        assert_eq!(summary.cash_in_drawer, 150.0);
+    }
+
+    #[tokio::test]
+    async fn test_redeem_in_sale_and_reverse_on_cancel() {
+        let pool = setup_test_db().await;
+        let repo = StoreCreditRepository::new(&pool);
+        let sales = SaleRepository::new(&pool);
+        let card = repo.issue_gift_card(gift_card(30.0)).await.unwrap();
+
+        // Acima do saldo
+        assert!(sales
+            .create(sale_with_credit(&card.code, 40.0))
+            .await
+            .is_err());
+
+        let sale = sales
+            .create(sale_with_credit(&card.code, 20.0))
+            .await
+            .unwrap();
+        let card = repo.find_by_id(&card.id).await.unwrap().unwrap();
+        assert_eq!(card.balance, 10.0);
+
+        // O vale conta uma vez, na venda do cartão; o resgate não é dinheiro novo
+        let summary = CashRepository::new(&pool)
+            .get_session_summary("cs-001")
+            .await
+            .unwrap();
+        assert!(summary.by_method.iter().all(|m| m.method != "STORE_CREDIT"));
+        assert_eq!(summary.by_method[0].sales, 30.0);
+        assert_eq!(summary.by_method[0].receipts, 30.0);
+        assert_eq!(summary.cash_in_drawer, 100.0 + 30.0 + 30.0);
+
+        sales.cancel(&sale.id, "emp-001", "Teste").await.unwrap();
+        let card = repo.find_by_id(&card.id).await.unwrap().unwrap();
+        assert_eq!(card.balance, 30.0);
+
+        let history = repo.find_transactions(&card.id).await.unwrap();
+        let types: Vec<&str> = history
+            .iter()
+            .map(|t| t.transaction_type.as_str())
+            .collect();
+        assert_eq!(types, vec!["ISSUE", "REDEEM", "REVERSAL"]);
+        assert_eq!(history[1].amount, -20.0);
+        assert_eq!(history[2].balance_after, 30.0);
+    }
+
+    #[tokio::test]
+    async fn test_blocked_card_is_rejected() {
+        let pool = setup_test_db().await;
+        let repo = StoreCreditRepository::new(&pool);
+        let card = repo.issue_gift_card(gift_card(30.0)).await.unwrap();
+
+        let blocked = repo.set_active(&card.id, false).await.unwrap();
+        assert!(!blocked.is_active);
+        assert!(SaleRepository::new(&pool)
+            .create(sale_with_credit(&card.code, 10.0))
+            .await
+            .is_err());
+
+        repo.set_active(&card.id, true).await.unwrap();
+        assert!(SaleRepository::new(&pool)
+            .create(sale_with_credit(&card.code, 10.0))
+            .await
+            .is_ok());
+    }
+}
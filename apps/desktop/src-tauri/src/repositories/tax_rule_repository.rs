@@ -0,0 +1,204 @@
+//! Repositório de Regras Tributárias (ICMS/PIS/COFINS)
+
+use crate::error::AppResult;
+use crate::models::{SaveTaxRule, TaxRule};
+use crate::repositories::new_id;
+use sqlx::SqlitePool;
+
+pub struct TaxRuleRepository<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> TaxRuleRepository<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    const COLS: &'static str = "id, name, crt, ncm, cfop, icms_origin, icms_code, icms_rate, icms_base_reduction, st_mva, st_rate, fcp_rate, pis_cst, pis_rate, cofins_cst, cofins_rate, is_active, created_at, updated_at";
+
+    pub async fn find_by_id(&self, id: &str) -> AppResult<Option<TaxRule>> {
+        let query = format!("SELECT {} FROM tax_rules WHERE id = ?", Self::COLS);
+        let result = sqlx::query_as::<_, TaxRule>(&query)
+            .bind(id)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    pub async fn find_all(&self) -> AppResult<Vec<TaxRule>> {
+        let query = format!(
+            "SELECT {} FROM tax_rules WHERE is_active = 1 ORDER BY name",
+            Self::COLS
+        );
+        let result = sqlx::query_as::<_, TaxRule>(&query)
+            .fetch_all(self.pool)
+            .await?;
+        Ok(result)
+    }
+
+    /// Cria a regra (sem `id`) ou atualiza a existente
+    pub async fn save(&self, data: SaveTaxRule) -> AppResult<TaxRule> {
+        let id = data.id.clone().unwrap_or_else(new_id);
+        let now = chrono::Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO tax_rules (id, name, crt, ncm, cfop, icms_origin, icms_code, icms_rate, icms_base_reduction, st_mva, st_rate, fcp_rate, pis_cst, pis_rate, cofins_cst, cofins_rate, is_active, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                crt = excluded.crt,
+                ncm = excluded.ncm,
+                cfop = excluded.cfop,
+                icms_origin = excluded.icms_origin,
+                icms_code = excluded.icms_code,
+                icms_rate = excluded.icms_rate,
+                icms_base_reduction = excluded.icms_base_reduction,
+                st_mva = excluded.st_mva,
+                st_rate = excluded.st_rate,
+                fcp_rate = excluded.fcp_rate,
+                pis_cst = excluded.pis_cst,
+                pis_rate = excluded.pis_rate,
+                cofins_cst = excluded.cofins_cst,
+                cofins_rate = excluded.cofins_rate,
+                updated_at = excluded.updated_at",
+        )
+        .bind(&id)
+        .bind(&data.name)
+        .bind(data.crt)
+        .bind(data.ncm.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.cfop.as_deref().filter(|s| !s.is_empty()))
+        .bind(data.icms_origin)
+        .bind(&data.icms_code)
+        .bind(data.icms_rate)
+        .bind(data.icms_base_reduction)
+        .bind(data.st_mva)
+        .bind(data.st_rate)
+        .bind(data.fcp_rate)
+        .bind(&data.pis_cst)
+        .bind(data.pis_rate)
+        .bind(&data.cofins_cst)
+        .bind(data.cofins_rate)
+        .bind(&now)
+        .bind(&now)
+        .execute(self.pool)
+        .await?;
+
+        self.find_by_id(&id)
+            .await?
+            .ok_or_else(|| crate::error::AppError::NotFound {
+                entity: "TaxRule".into(),
+                id,
+            })
+    }
+
+    /// Desativa a regra e remove os vínculos com produtos/categorias
+    pub async fn delete(&self, id: &str) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("UPDATE products SET tax_rule_id = NULL WHERE tax_rule_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE categories SET tax_rule_id = NULL WHERE tax_rule_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE tax_rules SET is_active = 0, updated_at = ? WHERE id = ?")
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn assign_to_product(
+        &self,
+        product_id: &str,
+        tax_rule_id: Option<&str>,
+    ) -> AppResult<()> {
+        sqlx::query("UPDATE products SET tax_rule_id = ? WHERE id = ?")
+            .bind(tax_rule_id)
+            .bind(product_id)
+            .execute(self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn assign_to_category(
+        &self,
+        category_id: &str,
+        tax_rule_id: Option<&str>,
+    ) -> AppResult<()> {
+        sqlx::query("UPDATE categories SET tax_rule_id = ? WHERE id = ?")
+            .bind(tax_rule_id)
+            .bind(category_id)
+            .execute(self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Resolve a regra do item: produto → categoria → NCM/CFOP (prefixo mais longo)
+    pub async fn resolve(
+        &self,
+        product_id: Option<&str>,
+        ncm: &str,
+        cfop: &str,
+        crt: i32,
+    ) -> AppResult<Option<TaxRule>> {
+        let prefixed = Self::COLS
+            .split(", ")
+            .map(|c| format!("tr.{}", c))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if let Some(product_id) = product_id {
+            let query = format!(
+                "SELECT {} FROM products p
+                 JOIN tax_rules tr ON tr.id = p.tax_rule_id
+                 WHERE p.id = ? AND tr.is_active = 1 AND tr.crt = ?",
+                prefixed
+            );
+            let rule = sqlx::query_as::<_, TaxRule>(&query)
+                .bind(product_id)
+                .bind(crt)
+                .fetch_optional(self.pool)
+                .await?;
+            if rule.is_some() {
+                return Ok(rule);
+            }
+
+            let query = format!(
+                "SELECT {} FROM products p
+                 JOIN categories c ON c.id = p.category_id
+                 JOIN tax_rules tr ON tr.id = c.tax_rule_id
+                 WHERE p.id = ? AND tr.is_active = 1 AND tr.crt = ?",
+                prefixed
+            );
+            let rule = sqlx::query_as::<_, TaxRule>(&query)
+                .bind(product_id)
+                .bind(crt)
+                .fetch_optional(self.pool)
+                .await?;
+            if rule.is_some() {
+                return Ok(rule);
+            }
+        }
+
+        let query = format!(
+            "SELECT {} FROM tax_rules
+             WHERE is_active = 1 AND crt = ?
+               AND (ncm IS NULL OR ? LIKE ncm || '%')
+               AND (cfop IS NULL OR cfop = ?)
+             ORDER BY LENGTH(COALESCE(ncm, '')) DESC, cfop IS NULL
+             LIMIT 1",
+            Self::COLS
+        );
+        let rule = sqlx::query_as::<_, TaxRule>(&query)
+            .bind(crt)
+            .bind(ncm)
+            .bind(cfop)
+            .fetch_optional(self.pool)
+            .await?;
+        Ok(rule)
+    }
+}
@@ -0,0 +1,130 @@
+//! Testes unitários para TaxRuleRepository
+
+#[cfg(test)]
+mod tests {
+    use super::super::tax_rule_repository::TaxRuleRepository;
+    use crate::models::SaveTaxRule;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::SqlitePool;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO categories (id, name, is_active, created_at, updated_at)
+             VALUES ('cat-001', 'Bebidas', 1, datetime('now'), datetime('now'))",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO products (id, name, barcode, internal_code, category_id, unit, cost_price, sale_price, current_stock, min_stock, is_weighted, is_active, created_at, updated_at)
+             VALUES ('prod-001', 'Refrigerante', '789123', 'REF01', 'cat-001', 'UN', 4.0, 8.0, 10.0, 1.0, 0, 1, datetime('now'), datetime('now'))"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    fn rule(name: &str, ncm: Option<&str>, icms_code: &str) -> SaveTaxRule {
+        SaveTaxRule {
+            id: None,
+            name: name.to_string(),
+            crt: 1,
+            ncm: ncm.map(|n| n.to_string()),
+            cfop: None,
+            icms_origin: 0,
+            icms_code: icms_code.to_string(),
+            icms_rate: 0.0,
+            icms_base_reduction: 0.0,
+            st_mva: 0.0,
+            st_rate: 0.0,
+            fcp_rate: 0.0,
+            pis_cst: "07".to_string(),
+            pis_rate: 0.0,
+            cofins_cst: "07".to_string(),
+            cofins_rate: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_by_ncm_prefix() {
+        let pool = setup_test_db().await;
+        let repo = TaxRuleRepository::new(&pool);
+
+        repo.save(rule("Padrão Simples", None, "102"))
+            .await
+            .unwrap();
+        repo.save(rule("Bebidas ST", Some("2202"), "500"))
+            .await
+            .unwrap();
+
+        let matched = repo
+            .resolve(None, "22021000", "5405", 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(matched.icms_code, "500");
+
+        let fallback = repo
+            .resolve(None, "85171231", "5102", 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fallback.icms_code, "102");
+
+        assert!(repo
+            .resolve(None, "22021000", "5405", 3)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_prefers_product_then_category() {
+        let pool = setup_test_db().await;
+        let repo = TaxRuleRepository::new(&pool);
+
+        let by_ncm = repo.save(rule("NCM", Some("2202"), "500")).await.unwrap();
+        let category = repo.save(rule("Categoria", None, "103")).await.unwrap();
+        let product = repo.save(rule("Produto", None, "400")).await.unwrap();
+
+        repo.assign_to_category("cat-001", Some(&category.id))
+            .await
+            .unwrap();
+        let resolved = repo
+            .resolve(Some("prod-001"), "22021000", "5102", 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.id, category.id);
+
+        repo.assign_to_product("prod-001", Some(&product.id))
+            .await
+            .unwrap();
+        let resolved = repo
+            .resolve(Some("prod-001"), "22021000", "5102", 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.id, product.id);
+
+        repo.delete(&product.id).await.unwrap();
+        repo.delete(&category.id).await.unwrap();
+        let resolved = repo
+            .resolve(Some("prod-001"), "22021000", "5102", 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.id, by_ncm.id);
+    }
+}
@@ -0,0 +1,33 @@
+//! Apoio aos testes de repositório: banco em arquivo e cadastro básico do PDV
+
+use crate::database::DatabaseManager;
+use sqlx::SqlitePool;
+
+/// Banco em arquivo com várias conexões (WAL e migrations aplicadas).
+/// A venda lê configurações e regras de preço pelo pool enquanto a própria
+/// transação está aberta; com `sqlite::memory:` de uma conexão isso trava
+pub async fn setup_file_db(name: &str) -> SqlitePool {
+    let ts = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+    let path = std::env::temp_dir().join(format!("giro_{}_test_{}.db", name, ts));
+    DatabaseManager::new(path.to_str().unwrap())
+        .await
+        .unwrap()
+        .into_pool()
+}
+
+/// Operador `emp-001`, categoria `cat-001`, produto `prod-001` e caixa `cs-001` aberto
+pub async fn seed_pdv(pool: &SqlitePool, sale_price: f64, stock: f64) {
+    sqlx::query("INSERT INTO employees (id, name, pin, role, is_active, created_at, updated_at) VALUES ('emp-001', 'Operador', '8899', 'OPERATOR', 1, datetime('now'), datetime('now'))").execute(pool).await.unwrap();
+    sqlx::query("INSERT INTO categories (id, name, is_active, created_at, updated_at) VALUES ('cat-001', 'Geral', 1, datetime('now'), datetime('now'))").execute(pool).await.unwrap();
+    sqlx::query(
+        "INSERT INTO products (id, barcode, internal_code, name, unit, sale_price, cost_price, current_stock, category_id, is_active, created_at, updated_at)
+         VALUES ('prod-001', '123456', 'P001', 'Produto Teste', 'UNIT', ?, ?, ?, 'cat-001', 1, datetime('now'), datetime('now'))",
+    )
+    .bind(sale_price)
+    .bind(sale_price / 2.0)
+    .bind(stock)
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO cash_sessions (id, employee_id, opening_balance, status, opened_at, created_at, updated_at) VALUES ('cs-001', 'emp-001', 100.0, 'OPEN', datetime('now'), datetime('now'), datetime('now'))").execute(pool).await.unwrap();
+}
@@ -0,0 +1,170 @@
+//! Crediário - parcelamento e encargos por atraso
+//!
+//! A parcela paga até o fim da carência não tem encargos. Depois disso a multa
+//! incide uma única vez sobre o principal em aberto e os juros correm pro rata
+//! dia desde o vencimento (ou desde o último recebimento que os quitou).
+
+use chrono::{Duration, NaiveDate};
+
+/// Regras de cobrança da conta do cliente
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LateFeeRules {
+    /// Juros em % ao mês (mês comercial de 30 dias)
+    pub interest_rate: f64,
+    pub fine_percent: f64,
+    pub grace_days: i64,
+}
+
+/// Encargos de uma parcela em uma data
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LateCharges {
+    pub days_overdue: i64,
+    pub fine: f64,
+    pub interest: f64,
+}
+
+impl LateCharges {
+    pub fn total(&self) -> f64 {
+        self.fine + self.interest
+    }
+}
+
+/// Divide o total em parcelas; os centavos que sobram ficam na primeira
+pub fn installment_schedule(
+    total: f64,
+    count: u32,
+    first_due: NaiveDate,
+    interval_days: i64,
+) -> Vec<(NaiveDate, f64)> {
+    let count = count.max(1);
+    let total_cents = (total * 100.0).round() as i64;
+    let base = total_cents / count as i64;
+    let remainder = total_cents - base * count as i64;
+
+    (0..count)
+        .map(|i| {
+            let cents = if i == 0 { base + remainder } else { base };
+            let due = first_due + Duration::days(interval_days * i as i64);
+            (due, cents as f64 / 100.0)
+        })
+        .collect()
+}
+
+/// Multa e juros sobre o principal em aberto de uma parcela
+pub fn late_charges(
+    outstanding: f64,
+    due_date: NaiveDate,
+    interest_paid_until: Option<NaiveDate>,
+    fine_charged: bool,
+    today: NaiveDate,
+    rules: &LateFeeRules,
+) -> LateCharges {
+    let days_overdue = (today - due_date).num_days();
+    if days_overdue <= 0 {
+        return LateCharges::default();
+    }
+    if days_overdue <= rules.grace_days || outstanding <= 0.0 {
+        return LateCharges {
+            days_overdue,
+            ..Default::default()
+        };
+    }
+
+    let fine = if fine_charged {
+        0.0
+    } else {
+        round_cents(outstanding * rules.fine_percent / 100.0)
+    };
+    let interest_from = interest_paid_until.map_or(due_date, |d| d.max(due_date));
+    let interest_days = (today - interest_from).num_days().max(0);
+    let interest =
+        round_cents(outstanding * rules.interest_rate / 100.0 / 30.0 * interest_days as f64);
+
+    LateCharges {
+        days_overdue,
+        fine,
+        interest,
+    }
+}
+
+pub fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    const RULES: LateFeeRules = LateFeeRules {
+        interest_rate: 3.0,
+        fine_percent: 2.0,
+        grace_days: 5,
+    };
+
+    #[test]
+    fn test_schedule_puts_remainder_on_first_installment() {
+        let schedule = installment_schedule(100.0, 3, date("2026-11-17"), 30);
+        assert_eq!(schedule.len(), 3);
+        assert_eq!(schedule[0], (date("2026-11-17"), 33.34));
+        assert_eq!(schedule[1], (date("2026-12-17"), 33.33));
+        assert_eq!(schedule[2], (date("2027-01-16"), 33.33));
+        let sum: f64 = schedule.iter().map(|(_, v)| v).sum();
+        assert!((sum - 100.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_no_charges_within_grace_period() {
+        let charges = late_charges(
+            100.0,
+            date("2026-10-01"),
+            None,
+            false,
+            date("2026-10-06"),
+            &RULES,
+        );
+        assert_eq!(charges.days_overdue, 5);
+        assert_eq!(charges.total(), 0.0);
+
+        let charges = late_charges(
+            100.0,
+            date("2026-10-10"),
+            None,
+            false,
+            date("2026-10-06"),
+            &RULES,
+        );
+        assert_eq!(charges, LateCharges::default());
+    }
+
+    #[test]
+    fn test_fine_once_and_interest_pro_rata() {
+        // 10 dias de atraso: multa 2% + juros 3% a.m. por 10 dias
+        let charges = late_charges(
+            100.0,
+            date("2026-10-01"),
+            None,
+            false,
+            date("2026-10-11"),
+            &RULES,
+        );
+        assert_eq!(charges.days_overdue, 10);
+        assert_eq!(charges.fine, 2.0);
+        assert_eq!(charges.interest, 1.0);
+
+        // Recebimento parcial já quitou multa e juros até dia 11
+        let charges = late_charges(
+            50.0,
+            date("2026-10-01"),
+            Some(date("2026-10-11")),
+            true,
+            date("2026-10-31"),
+            &RULES,
+        );
+        assert_eq!(charges.fine, 0.0);
+        assert_eq!(charges.interest, 1.0);
+    }
+}
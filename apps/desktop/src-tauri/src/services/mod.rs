@@ -3,6 +3,7 @@
 //! Este módulo contém a lógica de negócio da aplicação.
 
 pub mod backup_service;
+pub mod credit;
 pub mod enterprise;
 pub mod mdns_service;
 pub mod mobile_events;
@@ -12,6 +13,7 @@ pub mod mobile_server;
 pub mod mobile_session;
 pub mod network_client;
 pub mod notification_service;
+pub mod pricing;
 pub mod setup_checks;
 
 pub use backup_service::*;
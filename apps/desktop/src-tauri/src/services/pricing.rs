@@ -0,0 +1,512 @@
+//! Motor de Promoções - avalia as regras de preço sobre o carrinho
+//!
+//! Cada item recebe no máximo uma promoção. As regras vigentes são aplicadas em
+//! ordem de prioridade e, no empate, a que rende o maior desconto vence. Itens
+//! que já têm desconto manual ficam de fora.
+
+use crate::models::{ItemPromotion, PricingRule, SalePricing};
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
+use std::cmp::Ordering;
+
+/// Item do carrinho como o motor enxerga
+#[derive(Debug, Clone)]
+pub struct PricingLine {
+    pub product_id: String,
+    pub category_id: Option<String>,
+    pub quantity: f64,
+    pub unit_price: f64,
+    pub manual_discount: f64,
+}
+
+impl PricingLine {
+    fn gross(&self) -> f64 {
+        self.quantity * self.unit_price
+    }
+}
+
+/// Avalia as regras sobre os itens e devolve o desconto de cada um
+pub fn evaluate(
+    rules: &[PricingRule],
+    lines: &[PricingLine],
+    customer_group: Option<&str>,
+    now: NaiveDateTime,
+) -> SalePricing {
+    let mut claimed: Vec<bool> = lines.iter().map(|l| l.manual_discount > 0.0).collect();
+    let mut pending: Vec<&PricingRule> = rules
+        .iter()
+        .filter(|r| r.is_active && is_in_effect(r, now))
+        .collect();
+    let mut promotions = Vec::new();
+
+    loop {
+        let best = pending
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rule)| {
+                let discounts = apply_rule(rule, lines, &claimed, customer_group);
+                let total: f64 = discounts.iter().map(|(_, d)| d).sum();
+                (total > 0.0).then_some((i, discounts, total))
+            })
+            .max_by(|a, b| {
+                pending[a.0]
+                    .priority
+                    .cmp(&pending[b.0].priority)
+                    .then(a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal))
+            });
+
+        let Some((index, discounts, _)) = best else {
+            break;
+        };
+        let rule = pending.remove(index);
+        for (line, discount) in discounts {
+            claimed[line] = true;
+            promotions.push(ItemPromotion {
+                item_index: line as u32,
+                rule_id: rule.id.clone(),
+                reason: rule.name.clone(),
+                discount,
+            });
+        }
+    }
+
+    promotions.sort_by_key(|p| p.item_index);
+    let total_discount = round_cents(promotions.iter().map(|p| p.discount).sum());
+    SalePricing {
+        items: promotions,
+        total_discount,
+    }
+}
+
+/// Vigência, dias da semana e janela de horário
+fn is_in_effect(rule: &PricingRule, now: NaiveDateTime) -> bool {
+    let today = now.date().format("%Y-%m-%d").to_string();
+    if let Some(from) = rule.valid_from.as_deref().filter(|s| !s.is_empty()) {
+        if today.as_str() < from {
+            return false;
+        }
+    }
+    if let Some(until) = rule.valid_until.as_deref().filter(|s| !s.is_empty()) {
+        if today.as_str() > until {
+            return false;
+        }
+    }
+
+    if let Some(weekdays) = rule.weekdays.as_deref().filter(|s| !s.trim().is_empty()) {
+        let weekday = now.weekday().num_days_from_sunday();
+        let allowed = weekdays
+            .split(',')
+            .filter_map(|d| d.trim().parse::<u32>().ok())
+            .any(|d| d == weekday);
+        if !allowed {
+            return false;
+        }
+    }
+
+    let start = rule.start_time.as_deref().and_then(parse_time);
+    let end = rule.end_time.as_deref().and_then(parse_time);
+    if let (Some(start), Some(end)) = (start, end) {
+        let time = now.time();
+        // Janela que atravessa a meia-noite (ex.: 22:00 → 02:00)
+        let inside = if start <= end {
+            time >= start && time < end
+        } else {
+            time >= start || time < end
+        };
+        if !inside {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+fn in_scope(rule: &PricingRule, line: &PricingLine) -> bool {
+    if let Some(product_id) = &rule.product_id {
+        return &line.product_id == product_id;
+    }
+    if let Some(category_id) = &rule.category_id {
+        return line.category_id.as_ref() == Some(category_id);
+    }
+    true
+}
+
+/// Desconto que a regra daria a cada item ainda livre
+fn apply_rule(
+    rule: &PricingRule,
+    lines: &[PricingLine],
+    claimed: &[bool],
+    customer_group: Option<&str>,
+) -> Vec<(usize, f64)> {
+    let eligible: Vec<usize> = (0..lines.len())
+        .filter(|&i| !claimed[i] && lines[i].quantity > 0.0 && in_scope(rule, &lines[i]))
+        .collect();
+
+    let discounts = match rule.kind.as_str() {
+        "CATEGORY_PERCENT" | "HAPPY_HOUR" => eligible
+            .iter()
+            .map(|&i| (i, lines[i].gross() * rule.percent / 100.0))
+            .collect(),
+        "CUSTOMER_GROUP_PRICE" => {
+            let matches = match (customer_group, rule.customer_group.as_deref()) {
+                (Some(group), Some(rule_group)) => group.eq_ignore_ascii_case(rule_group),
+                _ => false,
+            };
+            if matches {
+                special_price(rule, lines, &eligible)
+            } else {
+                Vec::new()
+            }
+        }
+        "WHOLESALE" => {
+            let quantity: f64 = eligible.iter().map(|&i| lines[i].quantity).sum();
+            if rule.quantity > 0.0 && quantity >= rule.quantity {
+                special_price(rule, lines, &eligible)
+            } else {
+                Vec::new()
+            }
+        }
+        "BUY_X_PAY_Y" => {
+            let (buy, pay) = (rule.quantity.floor(), rule.pay_quantity.floor());
+            if buy >= 1.0 && pay >= 0.0 && pay < buy {
+                let units = whole_units(lines, &eligible);
+                let free = (units / buy).floor() * (buy - pay);
+                cheapest_units(lines, &eligible, free, 100.0)
+            } else {
+                Vec::new()
+            }
+        }
+        "NTH_ITEM_PERCENT" => {
+            let nth = rule.quantity.floor();
+            if nth >= 1.0 {
+                let units = whole_units(lines, &eligible);
+                cheapest_units(lines, &eligible, (units / nth).floor(), rule.percent)
+            } else {
+                Vec::new()
+            }
+        }
+        "COMBO_PRICE" => combo(rule, lines, claimed),
+        _ => Vec::new(),
+    };
+
+    discounts
+        .into_iter()
+        .map(|(i, d)| (i, round_cents(d.min(lines[i].gross()))))
+        .filter(|(_, d)| *d > 0.0)
+        .collect()
+}
+
+/// Preço especial unitário (`price`) ou percentual (`percent`)
+fn special_price(
+    rule: &PricingRule,
+    lines: &[PricingLine],
+    eligible: &[usize],
+) -> Vec<(usize, f64)> {
+    eligible
+        .iter()
+        .map(|&i| {
+            let line = &lines[i];
+            let discount = match rule.price {
+                Some(price) => (line.unit_price - price).max(0.0) * line.quantity,
+                None => line.gross() * rule.percent / 100.0,
+            };
+            (i, discount)
+        })
+        .collect()
+}
+
+fn whole_units(lines: &[PricingLine], eligible: &[usize]) -> f64 {
+    eligible.iter().map(|&i| lines[i].quantity.floor()).sum()
+}
+
+/// Aplica o percentual às `units` unidades mais baratas
+fn cheapest_units(
+    lines: &[PricingLine],
+    eligible: &[usize],
+    units: f64,
+    percent: f64,
+) -> Vec<(usize, f64)> {
+    let mut sorted = eligible.to_vec();
+    sorted.sort_by(|a, b| {
+        lines[*a]
+            .unit_price
+            .partial_cmp(&lines[*b].unit_price)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut remaining = units;
+    let mut discounts = Vec::new();
+    for i in sorted {
+        if remaining <= 0.0 {
+            break;
+        }
+        let taken = lines[i].quantity.floor().min(remaining);
+        if taken > 0.0 {
+            discounts.push((i, taken * lines[i].unit_price * percent / 100.0));
+            remaining -= taken;
+        }
+    }
+    discounts
+}
+
+/// Combo/kit: cada conjunto completo sai pelo preço fixo, com o desconto
+/// rateado entre os itens proporcionalmente ao valor consumido
+fn combo(rule: &PricingRule, lines: &[PricingLine], claimed: &[bool]) -> Vec<(usize, f64)> {
+    let Some(price) = rule.price else {
+        return Vec::new();
+    };
+    if rule.items.is_empty() {
+        return Vec::new();
+    }
+
+    let free_lines = |product_id: &str| -> Vec<usize> {
+        (0..lines.len())
+            .filter(|&i| !claimed[i] && lines[i].product_id == product_id)
+            .collect()
+    };
+
+    let sets = rule
+        .items
+        .iter()
+        .map(|item| {
+            let available: f64 = free_lines(&item.product_id)
+                .into_iter()
+                .map(|i| lines[i].quantity)
+                .sum();
+            if item.quantity > 0.0 {
+                (available / item.quantity).floor()
+            } else {
+                0.0
+            }
+        })
+        .fold(f64::INFINITY, f64::min);
+    if !sets.is_finite() || sets < 1.0 {
+        return Vec::new();
+    }
+
+    let mut consumed: Vec<(usize, f64)> = Vec::new();
+    for item in &rule.items {
+        let mut remaining = sets * item.quantity;
+        for i in free_lines(&item.product_id) {
+            if remaining <= 0.0 {
+                break;
+            }
+            let taken = lines[i].quantity.min(remaining);
+            consumed.push((i, taken * lines[i].unit_price));
+            remaining -= taken;
+        }
+    }
+
+    let regular: f64 = consumed.iter().map(|(_, value)| value).sum();
+    let discount = regular - sets * price;
+    if discount <= 0.0 || regular <= 0.0 {
+        return Vec::new();
+    }
+    consumed
+        .into_iter()
+        .map(|(i, value)| (i, discount * value / regular))
+        .collect()
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PricingRuleItem;
+    use chrono::NaiveDate;
+
+    fn rule(id: &str, kind: &str) -> PricingRule {
+        PricingRule {
+            id: id.to_string(),
+            name: format!("Promo {}", id),
+            kind: kind.to_string(),
+            product_id: None,
+            category_id: None,
+            customer_group: None,
+            quantity: 0.0,
+            pay_quantity: 0.0,
+            percent: 0.0,
+            price: None,
+            start_time: None,
+            end_time: None,
+            weekdays: None,
+            valid_from: None,
+            valid_until: None,
+            priority: 0,
+            is_active: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+            items: Vec::new(),
+        }
+    }
+
+    fn line(product_id: &str, category_id: &str, quantity: f64, unit_price: f64) -> PricingLine {
+        PricingLine {
+            product_id: product_id.to_string(),
+            category_id: Some(category_id.to_string()),
+            quantity,
+            unit_price,
+            manual_discount: 0.0,
+        }
+    }
+
+    /// Sábado, 17/10/2026
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 17)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_buy_x_pay_y_gives_cheapest_units() {
+        let mut r = rule("r1", "BUY_X_PAY_Y");
+        r.category_id = Some("cat-bebidas".into());
+        r.quantity = 3.0;
+        r.pay_quantity = 2.0;
+        let lines = vec![
+            line("coca", "cat-bebidas", 4.0, 8.0),
+            line("agua", "cat-bebidas", 2.0, 3.0),
+        ];
+
+        let pricing = evaluate(&[r], &lines, None, at(10, 0));
+        // 6 unidades → 2 grátis, ambas água (a mais barata)
+        assert_eq!(pricing.items.len(), 1);
+        assert_eq!(pricing.items[0].item_index, 1);
+        assert_eq!(pricing.items[0].discount, 6.0);
+        assert_eq!(pricing.total_discount, 6.0);
+    }
+
+    #[test]
+    fn test_nth_item_percent() {
+        let mut r = rule("r1", "NTH_ITEM_PERCENT");
+        r.product_id = Some("coca".into());
+        r.quantity = 2.0;
+        r.percent = 50.0;
+        let lines = vec![line("coca", "cat-bebidas", 5.0, 8.0)];
+
+        let pricing = evaluate(&[r], &lines, None, at(10, 0));
+        assert_eq!(pricing.total_discount, 8.0);
+    }
+
+    #[test]
+    fn test_combo_distributes_discount() {
+        let mut r = rule("r1", "COMBO_PRICE");
+        r.price = Some(20.0);
+        r.items = vec![
+            PricingRuleItem {
+                product_id: "lanche".into(),
+                quantity: 1.0,
+            },
+            PricingRuleItem {
+                product_id: "suco".into(),
+                quantity: 1.0,
+            },
+        ];
+        let lines = vec![
+            line("lanche", "cat-lanches", 1.0, 18.0),
+            line("suco", "cat-bebidas", 2.0, 6.0),
+        ];
+
+        let pricing = evaluate(&[r], &lines, None, at(10, 0));
+        // Um conjunto: 18 + 6 = 24 → 20, desconto 4 rateado 3/1
+        assert_eq!(pricing.total_discount, 4.0);
+        assert_eq!(pricing.items[0].discount, 3.0);
+        assert_eq!(pricing.items[1].discount, 1.0);
+    }
+
+    #[test]
+    fn test_happy_hour_window_and_weekdays() {
+        let mut r = rule("r1", "HAPPY_HOUR");
+        r.percent = 20.0;
+        r.start_time = Some("17:00".into());
+        r.end_time = Some("19:00".into());
+        r.weekdays = Some("5,6".into());
+        let lines = vec![line("chopp", "cat-bebidas", 2.0, 10.0)];
+
+        assert_eq!(
+            evaluate(std::slice::from_ref(&r), &lines, None, at(18, 0)).total_discount,
+            4.0
+        );
+        assert!(evaluate(std::slice::from_ref(&r), &lines, None, at(19, 0))
+            .items
+            .is_empty());
+
+        r.weekdays = Some("1,2,3".into());
+        assert!(evaluate(&[r], &lines, None, at(18, 0)).items.is_empty());
+    }
+
+    #[test]
+    fn test_validity_period() {
+        let mut r = rule("r1", "CATEGORY_PERCENT");
+        r.percent = 10.0;
+        r.valid_until = Some("2026-10-16".into());
+        let lines = vec![line("coca", "cat-bebidas", 1.0, 10.0)];
+
+        assert!(evaluate(&[r], &lines, None, at(10, 0)).items.is_empty());
+    }
+
+    #[test]
+    fn test_customer_group_price() {
+        let mut r = rule("r1", "CUSTOMER_GROUP_PRICE");
+        r.customer_group = Some("FUNCIONARIO".into());
+        r.product_id = Some("coca".into());
+        r.price = Some(6.5);
+        let lines = vec![line("coca", "cat-bebidas", 2.0, 8.0)];
+
+        assert!(evaluate(std::slice::from_ref(&r), &lines, None, at(10, 0))
+            .items
+            .is_empty());
+        let pricing = evaluate(&[r], &lines, Some("funcionario"), at(10, 0));
+        assert_eq!(pricing.total_discount, 3.0);
+    }
+
+    #[test]
+    fn test_wholesale_minimum_quantity() {
+        let mut r = rule("r1", "WHOLESALE");
+        r.product_id = Some("arroz".into());
+        r.quantity = 10.0;
+        r.price = Some(20.0);
+
+        let few = vec![line("arroz", "cat-mercearia", 9.0, 22.0)];
+        assert!(evaluate(std::slice::from_ref(&r), &few, None, at(10, 0))
+            .items
+            .is_empty());
+
+        let many = vec![line("arroz", "cat-mercearia", 10.0, 22.0)];
+        assert_eq!(evaluate(&[r], &many, None, at(10, 0)).total_discount, 20.0);
+    }
+
+    #[test]
+    fn test_one_rule_per_item_priority_and_manual_discount() {
+        let mut category = rule("cat", "CATEGORY_PERCENT");
+        category.percent = 10.0;
+        let mut wholesale = rule("atacado", "WHOLESALE");
+        wholesale.product_id = Some("coca".into());
+        wholesale.quantity = 2.0;
+        wholesale.percent = 5.0;
+        wholesale.priority = 1;
+
+        let mut lines = vec![
+            line("coca", "cat-bebidas", 2.0, 10.0),
+            line("agua", "cat-bebidas", 1.0, 10.0),
+            line("suco", "cat-bebidas", 1.0, 10.0),
+        ];
+        lines[2].manual_discount = 1.0;
+
+        let pricing = evaluate(&[category, wholesale], &lines, None, at(10, 0));
+        // Prioridade maior vence mesmo com desconto menor; o item com
+        // desconto manual não recebe promoção
+        assert_eq!(pricing.items.len(), 2);
+        assert_eq!(pricing.items[0].rule_id, "atacado");
+        assert_eq!(pricing.items[0].discount, 1.0);
+        assert_eq!(pricing.items[1].rule_id, "cat");
+        assert_eq!(pricing.items[1].discount, 1.0);
+    }
+}
@@ -0,0 +1,206 @@
+import { Button } from '@/components/ui/button';
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
+import { useToast } from '@/hooks/use-toast';
+import { useBlindCloseCashSession } from '@/hooks/usePDV';
+import { printCashClosingReport } from '@/lib/tauri';
+import { formatCurrency, getErrorMessage } from '@/lib/utils';
+import type { PaymentMethod } from '@/types';
+import { useMemo, useState, type FC } from 'react';
+
+// Cédulas e moedas em circulação (R$)
+const NOTES = [200, 100, 50, 20, 10, 5, 2];
+const COINS = [1, 0.5, 0.25, 0.1, 0.05];
+
+const COUNTED_METHODS: { method: PaymentMethod; label: string }[] = [
+  { method: 'PIX', label: 'PIX' },
+  { method: 'DEBIT', label: 'Cartão de Débito' },
+  { method: 'CREDIT', label: 'Cartão de Crédito' },
+  { method: 'VOUCHER', label: 'Vale' },
+];
+
+const parseAmount = (value: string) => parseFloat(value.replace(',', '.')) || 0;
+
+interface BlindCloseCashDialogProps {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+}
+
+/**
+ * Fechamento cego: o operador conta cédulas, moedas e comprovantes sem ver o valor
+ * esperado. A conferência fica com o backend, que pede aprovação do gerente se a
+ * diferença passar da tolerância.
+ */
+export const BlindCloseCashDialog: FC<BlindCloseCashDialogProps> = ({ open, onOpenChange }) => {
+  const { toast } = useToast();
+  const blindClose = useBlindCloseCashSession();
+
+  const [quantities, setQuantities] = useState<Record<string, string>>({});
+  const [methodAmounts, setMethodAmounts] = useState<Record<string, string>>({});
+  const [notes, setNotes] = useState('');
+
+  const cashTotal = useMemo(
+    () =>
+      [...NOTES, ...COINS].reduce(
+        (acc, d) => acc + d * (parseInt(quantities[String(d)] ?? '', 10) || 0),
+        0
+      ),
+    [quantities]
+  );
+
+  const reset = () => {
+    setQuantities({});
+    setMethodAmounts({});
+    setNotes('');
+  };
+
+  const handleSubmit = async () => {
+    const denominations = [...NOTES, ...COINS]
+      .map((denomination) => ({
+        denomination,
+        quantity: parseInt(quantities[String(denomination)] ?? '', 10) || 0,
+      }))
+      .filter((d) => d.quantity > 0);
+    const methodCounts = COUNTED_METHODS.map(({ method }) => ({
+      method,
+      amount: parseAmount(methodAmounts[method] ?? ''),
+    })).filter((m) => m.amount > 0);
+
+    try {
+      const closed = await blindClose.mutateAsync({
+        denominations,
+        methodCounts,
+        notes: notes.trim() || undefined,
+      });
+
+      if (closed.status === 'PENDING_APPROVAL') {
+        toast({
+          title: 'Fechamento aguardando aprovação',
+          description: 'A contagem divergiu acima da tolerância. Chame o gerente.',
+          variant: 'destructive',
+        });
+      } else {
+        toast({ title: 'Caixa fechado com sucesso!' });
+      }
+
+      try {
+        await printCashClosingReport(closed.id);
+      } catch (error) {
+        toast({
+          title: 'Erro ao imprimir fechamento',
+          description: getErrorMessage(error),
+          variant: 'destructive',
+        });
+      }
+
+      reset();
+      onOpenChange(false);
+    } catch (error) {
+      toast({
+        title: 'Erro ao fechar caixa',
+        description: getErrorMessage(error),
+        variant: 'destructive',
+      });
+    }
+  };
+
+  const renderDenomination = (denomination: number) => (
+    <div key={denomination} className="flex items-center gap-2">
+      <Label htmlFor={`denomination-${denomination}`} className="w-20 text-right">
+        {formatCurrency(denomination)}
+      </Label>
+      <Input
+        id={`denomination-${denomination}`}
+        data-testid={`denomination-${denomination}`}
+        type="number"
+        min={0}
+        step={1}
+        inputMode="numeric"
+        value={quantities[String(denomination)] ?? ''}
+        onChange={(e) =>
+          setQuantities((prev) => ({ ...prev, [String(denomination)]: e.target.value }))
+        }
+        placeholder="0"
+        className="h-9 text-center"
+      />
+    </div>
+  );
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-2xl">
+        <DialogHeader>
+          <DialogTitle>Fechar Caixa</DialogTitle>
+          <DialogDescription>
+            Conte as cédulas, moedas e comprovantes. O valor esperado não é exibido.
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="grid gap-6 py-4 sm:grid-cols-2">
+          <div className="space-y-2">
+            <p className="text-sm font-medium">Cédulas</p>
+            {NOTES.map(renderDenomination)}
+            <p className="pt-2 text-sm font-medium">Moedas</p>
+            {COINS.map(renderDenomination)}
+          </div>
+
+          <div className="space-y-4">
+            <div className="rounded-lg bg-muted p-4">
+              <p className="text-sm text-muted-foreground">Dinheiro contado</p>
+              <p className="text-2xl font-bold" data-testid="blind-cash-total">
+                {formatCurrency(cashTotal)}
+              </p>
+            </div>
+
+            {COUNTED_METHODS.map(({ method, label }) => (
+              <div key={method}>
+                <Label htmlFor={`method-${method}`}>{label} (R$)</Label>
+                <Input
+                  id={`method-${method}`}
+                  type="text"
+                  inputMode="decimal"
+                  value={methodAmounts[method] ?? ''}
+                  onChange={(e) =>
+                    setMethodAmounts((prev) => ({ ...prev, [method]: e.target.value }))
+                  }
+                  placeholder="0,00"
+                />
+              </div>
+            ))}
+
+            <div>
+              <Label htmlFor="blindClosingNotes">Observações (opcional)</Label>
+              <Input
+                id="blindClosingNotes"
+                value={notes}
+                onChange={(e) => setNotes(e.target.value)}
+              />
+            </div>
+          </div>
+        </div>
+
+        <DialogFooter>
+          <Button variant="outline" onClick={() => onOpenChange(false)}>
+            Cancelar
+          </Button>
+          <Button
+            variant="destructive"
+            data-testid="confirm-blind-close-cash"
+            onClick={handleSubmit}
+            disabled={blindClose.isPending}
+          >
+            Confirmar Fechamento
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+};
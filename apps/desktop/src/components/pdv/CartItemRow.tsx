@@ -30,7 +30,7 @@ export const CartItemRow: FC<CartItemRowProps> = ({ item, index }) => {
   const { updateQuantity, removeItem } = usePDVStore();
   const [showRemoveConfirm, setShowRemoveConfirm] = useState(false);
 
-  const itemTotal = item.quantity * item.unitPrice - item.discount;
+  const itemTotal = item.quantity * item.unitPrice - item.discount - (item.promotionDiscount ?? 0);
 
   const handleIncrement = () => {
     updateQuantity(item.id, item.quantity + 1);
@@ -90,6 +90,11 @@ export const CartItemRow: FC<CartItemRowProps> = ({ item, index }) => {
               (-{formatCurrency(item.discount)})
             </span>
           )}
+          {!!item.promotionDiscount && (
+            <span className="text-success truncate" role="status">
+              {item.promotionLabel} (-{formatCurrency(item.promotionDiscount)})
+            </span>
+          )}
         </div>
       </div>
 
@@ -17,17 +17,26 @@ import { Label } from '@/components/ui/label';
 import { Separator } from '@/components/ui/separator';
 import { useToast } from '@/hooks/use-toast';
 import { useCreateSale } from '@/hooks/useSales';
-import { emitNfce, printReceipt, type EmitNfceRequest, type NfceItem } from '@/lib/tauri';
+import {
+  emitNfce,
+  getStoreCreditByCode,
+  printReceipt,
+  type EmitNfceRequest,
+  type NfceItem,
+} from '@/lib/tauri';
 import { cn, formatCurrency, getErrorMessage } from '@/lib/utils';
 import { useAuthStore } from '@/stores/auth-store';
 import { usePDVStore, type PaymentMethod } from '@/stores/pdv-store';
 import { useSettingsStore } from '@/stores/settings-store';
+import type { StoreCredit } from '@/types';
 import {
   ArrowLeft,
   Banknote,
   Check,
   CreditCard,
+  Gift,
   Loader2,
+  NotebookPen,
   Plus,
   QrCode,
   Split,
@@ -40,6 +49,7 @@ export interface SplitPayment {
   id: string;
   method: PaymentMethod;
   amount: number;
+  storeCreditCode?: string;
 }
 
 interface PaymentModalProps {
@@ -59,6 +69,8 @@ const PAYMENT_METHODS: { method: PaymentMethod; label: string; icon: string }[]
   { method: 'CREDIT', label: 'Crédito', icon: 'card' },
   { method: 'DEBIT', label: 'Débito', icon: 'card' },
   { method: 'VOUCHER', label: 'Vale', icon: 'ticket' },
+  { method: 'ACCOUNT', label: 'Fiado', icon: 'notebook' },
+  { method: 'STORE_CREDIT', label: 'Vale-Crédito', icon: 'gift' },
 ];
 
 export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFinalize }) => {
@@ -69,6 +81,9 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
   const [splitPayments, setSplitPayments] = useState<SplitPayment[]>([]);
   const [splitMethod, setSplitMethod] = useState<PaymentMethod | null>(null);
   const [splitAmount, setSplitAmount] = useState('');
+  const [storeCreditCode, setStoreCreditCode] = useState('');
+  const [storeCredit, setStoreCredit] = useState<StoreCredit | null>(null);
+  const [isLookingUpCredit, setIsLookingUpCredit] = useState(false);
   const inputRef = useRef<HTMLInputElement>(null);
   const splitInputRef = useRef<HTMLInputElement>(null);
 
@@ -81,7 +96,9 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
   const amountPaidNum = parseFloat(amountPaid.replace(',', '.')) || 0;
   const change = amountPaidNum - total;
   const canFinalize =
-    paymentMethod !== null && (paymentMethod !== 'CASH' || amountPaidNum >= total);
+    paymentMethod !== null &&
+    (paymentMethod !== 'CASH' || amountPaidNum >= total) &&
+    (paymentMethod !== 'STORE_CREDIT' || (storeCredit !== null && storeCredit.balance >= total));
 
   // Split mode calculations
   const splitTotal = splitPayments.reduce((sum, p) => sum + p.amount, 0);
@@ -109,6 +126,8 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
       setSplitPayments([]);
       setSplitMethod(null);
       setSplitAmount('');
+      setStoreCreditCode('');
+      setStoreCredit(null);
     }
   }, [open]);
 
@@ -120,14 +139,73 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
     setAmountPaid(total.toFixed(2));
   };
 
+  // Vale-crédito: consulta saldo e validade pelo código lido no vale
+  const handleLookupStoreCredit = async () => {
+    const code = storeCreditCode.trim();
+    if (!code) return;
+
+    setIsLookingUpCredit(true);
+    setStoreCredit(null);
+    try {
+      const credit = await getStoreCreditByCode(code);
+      const today = new Date().toISOString().slice(0, 10);
+      if (!credit) {
+        toast.error('Vale não encontrado', `Nenhum vale com o código ${code}`);
+      } else if (!credit.isActive) {
+        toast.error('Vale bloqueado', 'Este vale foi bloqueado e não pode ser usado');
+      } else if (credit.expiresAt && credit.expiresAt < today) {
+        toast.error('Vale vencido', `Validade até ${credit.expiresAt}`);
+      } else if (credit.balance <= 0) {
+        toast.error('Vale sem saldo', 'O saldo deste vale já foi utilizado');
+      } else {
+        setStoreCredit(credit);
+        if (isSplitMode) {
+          const used = splitPayments
+            .filter((p) => p.storeCreditCode === credit.code)
+            .reduce((sum, p) => sum + p.amount, 0);
+          const available = Math.max(0, credit.balance - used);
+          setSplitAmount(Math.min(available, Math.max(0, splitRemaining)).toFixed(2));
+        }
+      }
+    } catch (error) {
+      toast.error('Falha ao consultar vale', getErrorMessage(error));
+    } finally {
+      setIsLookingUpCredit(false);
+    }
+  };
+
   const handleAddSplitPayment = () => {
     if (!splitMethod || !splitAmount) return;
     const amount = parseFloat(splitAmount.replace(',', '.')) || 0;
     if (amount <= 0) return;
 
-    setSplitPayments([...splitPayments, { id: crypto.randomUUID(), method: splitMethod, amount }]);
+    if (splitMethod === 'STORE_CREDIT') {
+      if (!storeCredit) return;
+      const used = splitPayments
+        .filter((p) => p.storeCreditCode === storeCredit.code)
+        .reduce((sum, p) => sum + p.amount, 0);
+      if (amount > storeCredit.balance - used + 0.005) {
+        toast.error(
+          'Saldo insuficiente',
+          `Disponível no vale: ${formatCurrency(storeCredit.balance - used)}`
+        );
+        return;
+      }
+    }
+
+    setSplitPayments([
+      ...splitPayments,
+      {
+        id: crypto.randomUUID(),
+        method: splitMethod,
+        amount,
+        storeCreditCode: splitMethod === 'STORE_CREDIT' ? storeCredit?.code : undefined,
+      },
+    ]);
     setSplitMethod(null);
     setSplitAmount('');
+    setStoreCreditCode('');
+    setStoreCredit(null);
   };
 
   const handleRemoveSplitPayment = (id: string) => {
@@ -148,6 +226,7 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
         const payments = splitPayments.map((p) => ({
           method: p.method,
           amount: p.amount,
+          storeCreditCode: p.storeCreditCode,
         }));
 
         if (onFinalize) {
@@ -180,6 +259,7 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
           {
             method: paymentMethod,
             amount: amountPaidNum || total,
+            storeCreditCode: paymentMethod === 'STORE_CREDIT' ? storeCredit?.code : undefined,
           },
         ];
 
@@ -227,6 +307,7 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
       if (fiscal.enabled && saleResult) {
         try {
           const nfceItems: NfceItem[] = items.map((item) => ({
+            productId: item.productId,
             code: item.productId.substring(0, 10),
             description: item.productName,
             ncm: '00000000',
@@ -242,11 +323,13 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
             cofinsCst: '07',
           }));
 
+          // vProd bruto; vDesc reúne descontos dos itens, promoções e desconto geral
+          const grossTotal = nfceItems.reduce((sum, item) => sum + item.totalValue, 0);
           const request: EmitNfceRequest = {
             saleId: saleResult.id,
             items: nfceItems,
-            total: saleResult.total,
-            discount: saleResult.discountValue,
+            total: grossTotal,
+            discount: Math.max(0, grossTotal - saleResult.total),
             paymentMethod: saleResult.paymentMethod,
             paymentValue: saleResult.amountPaid,
             emitterCnpj: company.cnpj || '',
@@ -270,6 +353,9 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
           const emission = await emitNfce(request);
           if (emission.success) {
             useSettingsStore.getState().setFiscalConfig({ nextNumber: fiscal.nextNumber + 1 });
+          } else if (emission.validationErrors?.length) {
+            const [first] = emission.validationErrors;
+            toast.error(emission.message, `${first?.path}: ${first?.message}`);
           }
         } catch (e) {
           console.error('Falha NFC-e', getErrorMessage(e));
@@ -389,6 +475,40 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
         </div>
       </Button>
 
+      {pdvStore.customerId && (
+        <Button
+          variant="outline"
+          className="h-20 text-lg justify-start gap-4"
+          onClick={() => {
+            setPaymentMethod('ACCOUNT');
+            setAmountPaid(total.toFixed(2));
+          }}
+          aria-label="Vender fiado no crediário do cliente"
+        >
+          <NotebookPen className="h-8 w-8 text-muted-foreground" aria-hidden="true" />
+          <div className="text-left">
+            <div className="font-semibold">Fiado</div>
+            <div className="text-sm text-muted-foreground">Lançar no crediário do cliente</div>
+          </div>
+        </Button>
+      )}
+
+      <Button
+        variant="outline"
+        className="h-20 text-lg justify-start gap-4"
+        onClick={() => {
+          setPaymentMethod('STORE_CREDIT');
+          setAmountPaid(total.toFixed(2));
+        }}
+        aria-label="Pagar com vale-crédito ou vale-presente"
+      >
+        <Gift className="h-8 w-8 text-primary" aria-hidden="true" />
+        <div className="text-left">
+          <div className="font-semibold">Vale-Crédito</div>
+          <div className="text-sm text-muted-foreground">Vale-troca ou vale-presente</div>
+        </div>
+      </Button>
+
       <Separator />
 
       <Button
@@ -541,11 +661,17 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
           {paymentMethod === 'VOUCHER' && (
             <Ticket className="h-5 w-5 text-warning" aria-hidden="true" />
           )}
+          {paymentMethod === 'ACCOUNT' && <NotebookPen className="h-5 w-5" aria-hidden="true" />}
+          {paymentMethod === 'STORE_CREDIT' && (
+            <Gift className="h-5 w-5 text-primary" aria-hidden="true" />
+          )}
           <span className="font-semibold">
             {paymentMethod === 'PIX' && 'Pagamento via PIX'}
             {paymentMethod === 'CREDIT' && 'Cartão de Crédito'}
             {paymentMethod === 'DEBIT' && 'Cartão de Débito'}
             {paymentMethod === 'VOUCHER' && 'Vale Alimentação/Refeição'}
+            {paymentMethod === 'ACCOUNT' && 'Fiado (Crediário)'}
+            {paymentMethod === 'STORE_CREDIT' && 'Vale-Crédito'}
           </span>
         </div>
       </div>
@@ -566,8 +692,54 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
           {(paymentMethod === 'CREDIT' || paymentMethod === 'DEBIT') &&
             'Processe o pagamento na maquininha'}
           {paymentMethod === 'VOUCHER' && 'Processe o pagamento na maquininha (VR, VA, etc.)'}
+          {paymentMethod === 'ACCOUNT' &&
+            'Parcelas geradas conforme o crediário do cliente (limite e vencimentos)'}
+          {paymentMethod === 'STORE_CREDIT' &&
+            (storeCredit && storeCredit.balance < total
+              ? 'Saldo do vale não cobre a venda. Use Pagamento Múltiplo para completar.'
+              : 'O valor é debitado do saldo do vale')}
         </p>
       </div>
+
+      {paymentMethod === 'STORE_CREDIT' && renderStoreCreditLookup()}
+    </div>
+  );
+
+  const renderStoreCreditLookup = () => (
+    <div className="space-y-2">
+      <Label htmlFor="store-credit-code">Código do Vale</Label>
+      <div className="flex gap-2">
+        <Input
+          id="store-credit-code"
+          type="text"
+          inputMode="numeric"
+          autoFocus
+          value={storeCreditCode}
+          onChange={(e) => {
+            setStoreCreditCode(e.target.value);
+            setStoreCredit(null);
+          }}
+          onKeyDown={(e) => {
+            if (e.key === 'Enter') handleLookupStoreCredit();
+          }}
+          placeholder="Leia o código de barras do vale"
+        />
+        <Button
+          variant="secondary"
+          onClick={handleLookupStoreCredit}
+          disabled={isLookingUpCredit || !storeCreditCode.trim()}
+        >
+          {isLookingUpCredit ? <Loader2 className="h-4 w-4 animate-spin" /> : 'Consultar'}
+        </Button>
+      </div>
+      {storeCredit && (
+        <div className="flex justify-between text-sm" role="status">
+          <span className="text-muted-foreground">Saldo disponível</span>
+          <span className="text-money font-semibold text-success">
+            {formatCurrency(storeCredit.balance)}
+          </span>
+        </div>
+      )}
     </div>
   );
 
@@ -603,6 +775,9 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
             >
               <div className="flex items-center gap-2">
                 <span className="font-medium">{getMethodLabel(payment.method)}</span>
+                {payment.storeCreditCode && (
+                  <span className="text-xs text-muted-foreground">{payment.storeCreditCode}</span>
+                )}
               </div>
               <div className="flex items-center gap-2">
                 <span className="text-money font-semibold">{formatCurrency(payment.amount)}</span>
@@ -648,11 +823,14 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
               onClick={() => {
                 setSplitMethod(null);
                 setSplitAmount('');
+                setStoreCreditCode('');
+                setStoreCredit(null);
               }}
             >
               Cancelar
             </Button>
           </div>
+          {splitMethod === 'STORE_CREDIT' && renderStoreCreditLookup()}
           <div className="relative">
             <span className="absolute left-3 top-1/2 -translate-y-1/2 text-muted-foreground">
               R$
@@ -670,7 +848,11 @@ export const PaymentModal: FC<PaymentModalProps> = ({ open, onClose, total, onFi
               placeholder="0,00"
             />
           </div>
-          <Button className="w-full" onClick={handleAddSplitPayment}>
+          <Button
+            className="w-full"
+            onClick={handleAddSplitPayment}
+            disabled={splitMethod === 'STORE_CREDIT' && !storeCredit}
+          >
             <Plus className="mr-2 h-4 w-4" />
             Adicionar
           </Button>
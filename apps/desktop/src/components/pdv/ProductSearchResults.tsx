@@ -5,15 +5,16 @@
 
 import { Badge } from '@/components/ui/badge';
 import { ScrollArea } from '@/components/ui/scroll-area';
-import { useProductSearch, useProductByBarcode } from '@/hooks/use-products';
+import { useProductSearch, useScannedProduct } from '@/hooks/use-products';
 import { cn, formatCurrency } from '@/lib/utils';
 import { type Product } from '@/types';
 import { AlertTriangle, Loader2, Package } from 'lucide-react';
-import { useEffect, useState, useMemo, type FC } from 'react';
+import { useCallback, useEffect, useState, useMemo, type FC } from 'react';
 
 interface ProductSearchResultsProps {
   query: string;
-  onSelect: (product: Product) => void;
+  /** `quantity` vem de códigos pesados (peso ou preço embutido) */
+  onSelect: (product: Product, quantity?: number) => void;
   onClose: () => void;
 }
 
@@ -29,7 +30,7 @@ function isLikelyBarcode(query: string): boolean {
 
 interface ProductSearchResultsProps {
   query: string;
-  onSelect: (product: Product) => void;
+  onSelect: (product: Product, quantity?: number) => void;
   onClose: () => void;
 }
 
@@ -43,10 +44,24 @@ export const ProductSearchResults: FC<ProductSearchResultsProps> = ({
   // Detectar se é busca por barcode ou texto
   const searchMode = useMemo(() => (isLikelyBarcode(query) ? 'barcode' : 'text'), [query]);
 
-  // Busca por barcode (se detectado)
-  const { data: barcodeProduct, isLoading: isLoadingBarcode } = useProductByBarcode(
+  // Busca por barcode (se detectado), incluindo códigos pesados da balança
+  const { data: scanned, isLoading: isLoadingBarcode } = useScannedProduct(
     searchMode === 'barcode' ? query.trim() : null
   );
+  const barcodeProduct = scanned?.product;
+  const scannedQuantity = scanned?.weighted ? scanned.quantity : undefined;
+
+  // Código pesado: a quantidade segue junto com o produto
+  const handleSelect = useCallback(
+    (product: Product) => {
+      if (scannedQuantity !== undefined && product === barcodeProduct) {
+        onSelect(product, scannedQuantity);
+      } else {
+        onSelect(product);
+      }
+    },
+    [onSelect, barcodeProduct, scannedQuantity]
+  );
 
   // Busca por texto usando search_products (funciona corretamente com LIKE)
   const { data: textProducts, isLoading: isLoadingText } = useProductSearch(
@@ -91,7 +106,7 @@ export const ProductSearchResults: FC<ProductSearchResultsProps> = ({
           e.preventDefault();
           const product = products[selectedIndex];
           if (product) {
-            onSelect(product);
+            handleSelect(product);
           }
           break;
         }
@@ -111,7 +126,7 @@ export const ProductSearchResults: FC<ProductSearchResultsProps> = ({
 
     window.addEventListener('keydown', handleKeyDown);
     return () => window.removeEventListener('keydown', handleKeyDown);
-  }, [products, selectedIndex, onSelect, onClose]);
+  }, [products, selectedIndex, handleSelect, onClose]);
 
   // Reset selected index quando query muda E quando produtos carregam
   useEffect(() => {
@@ -165,7 +180,7 @@ export const ProductSearchResults: FC<ProductSearchResultsProps> = ({
               key={product.id}
               data-product-index={index}
               type="button"
-              onClick={() => onSelect(product)}
+              onClick={() => handleSelect(product)}
               onMouseEnter={() => setSelectedIndex(index)}
               className={cn(
                 'flex w-full items-center gap-3 rounded-md p-3 text-left',
@@ -59,6 +59,10 @@ vi.mock('@/hooks/use-products', () => ({
     data: undefined,
     isLoading: false,
   }),
+  useScannedProduct: () => ({
+    data: undefined,
+    isLoading: false,
+  }),
 }));
 
 describe('ProductSearchResults', () => {
@@ -36,6 +36,7 @@ export {
   useInactiveProducts,
   useProduct,
   useProductByBarcode,
+  useScannedProduct,
   useProductSearch,
   useProducts,
   useReactivateProduct,
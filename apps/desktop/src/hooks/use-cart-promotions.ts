@@ -0,0 +1,45 @@
+import { logger } from '@/lib/logger';
+import { previewSalePricing } from '@/lib/tauri';
+import { usePDVStore } from '@/stores/pdv-store';
+import { useEffect } from 'react';
+
+/**
+ * Hook que mantém as promoções automáticas do carrinho atualizadas.
+ * Recalcula no backend (as mesmas regras aplicadas ao finalizar a venda)
+ * sempre que itens, quantidades, descontos manuais ou o cliente mudam.
+ */
+export function useCartPromotions() {
+  const applyPromotions = usePDVStore((state) => state.applyPromotions);
+  const signature = usePDVStore((state) =>
+    JSON.stringify([
+      state.customerId,
+      state.items.map((item) => [item.productId, item.quantity, item.unitPrice, item.discount]),
+    ])
+  );
+
+  useEffect(() => {
+    const { items, customerId } = usePDVStore.getState();
+    if (items.length === 0) return;
+    let cancelled = false;
+
+    previewSalePricing(
+      items.map((item) => ({
+        productId: item.productId,
+        quantity: item.quantity,
+        unitPrice: item.unitPrice,
+        discount: item.discount,
+      })),
+      customerId
+    )
+      .then((pricing) => {
+        if (!cancelled) applyPromotions(pricing);
+      })
+      .catch((error) => {
+        logger.warn('Não foi possível calcular as promoções do carrinho:', error);
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [signature, applyPromotions]);
+}
@@ -14,6 +14,7 @@ import {
   getProducts,
   getProductsPaginated,
   reactivateProduct,
+  scanProductBarcode,
   searchProducts,
   updateProduct,
 } from '@/lib/tauri';
@@ -94,6 +95,21 @@ export function useProductByBarcode(barcode: string | null) {
   });
 }
 
+/**
+ * Busca o produto do código lido no PDV, incluindo códigos pesados
+ * (quantidade vinda do peso ou do preço embutido na etiqueta)
+ */
+export function useScannedProduct(barcode: string | null) {
+  return useQuery({
+    queryKey: ['product', 'scan', barcode],
+    queryFn: () => scanProductBarcode(barcode!),
+    enabled: !!barcode && barcode.length > 0,
+    staleTime: 0,
+    gcTime: 1000 * 30,
+    refetchOnWindowFocus: false,
+  });
+}
+
 /**
  * Busca produtos por texto (nome, código)
  */
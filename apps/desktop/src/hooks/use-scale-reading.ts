@@ -0,0 +1,54 @@
+import { logger } from '@/lib/logger';
+import { startScaleStream, stopScaleStream } from '@/lib/tauri';
+import type { ScaleReading } from '@/types';
+import { listen } from '@tauri-apps/api/event';
+import { useEffect, useState } from 'react';
+
+interface HardwareEventPayload {
+  deviceType: string;
+  scaleReading?: ScaleReading | null;
+}
+
+/**
+ * Hook para acompanhar o peso da balança em tempo real.
+ * Liga a leitura contínua enquanto o componente está montado
+ * e devolve o último peso publicado no evento `hardware:event`.
+ */
+export function useScaleReading(enabled = true) {
+  const [reading, setReading] = useState<ScaleReading | null>(null);
+
+  useEffect(() => {
+    if (!enabled) return;
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+
+    async function setup() {
+      unlisten = await listen<HardwareEventPayload>('hardware:event', (event) => {
+        const { deviceType, scaleReading } = event.payload;
+        if (deviceType === 'scale' && scaleReading) {
+          setReading(scaleReading);
+        }
+      });
+      if (cancelled) {
+        unlisten();
+        return;
+      }
+      try {
+        await startScaleStream();
+      } catch (error) {
+        logger.warn('Leitura contínua da balança indisponível:', error);
+      }
+    }
+
+    setup();
+
+    return () => {
+      cancelled = true;
+      if (unlisten) unlisten();
+      stopScaleStream().catch(() => undefined);
+      setReading(null);
+    };
+  }, [enabled]);
+
+  return reading;
+}
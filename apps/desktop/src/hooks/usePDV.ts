@@ -1,13 +1,18 @@
 import {
+  approveCashClosing,
   closeCashSession,
+  closeCashSessionBlind,
   getCashSessionMovements,
   getCashSessionSummary,
   getCurrentCashSession,
+  getPendingCashClosings,
+  getSetting,
   openCashSession,
 } from '@/lib/tauri';
 import { useAuthStore } from '@/stores';
 import type { CashSession as AuthCashSession } from '@/stores/auth-store';
 import type {
+  BlindCashClosingInput,
   CashSession as BackendCashSession,
   CloseCashSessionInput,
   OpenCashSessionInput,
@@ -19,6 +24,8 @@ export const cashSessionKeys = {
   current: () => [...cashSessionKeys.all, 'current'] as const,
   summary: (id: string) => [...cashSessionKeys.all, 'summary', id] as const,
   movements: (id: string) => [...cashSessionKeys.all, 'movements', id] as const,
+  pendingApproval: () => [...cashSessionKeys.all, 'pendingApproval'] as const,
+  closingSettings: () => [...cashSessionKeys.all, 'closingSettings'] as const,
 };
 
 /**
@@ -131,3 +138,70 @@ export function useCloseCashSession() {
     },
   });
 }
+
+/**
+ * Configuração do fechamento cego (padrão: ligado)
+ */
+export function useCashClosingSettings() {
+  return useQuery({
+    queryKey: cashSessionKeys.closingSettings(),
+    queryFn: async () => {
+      const blind = await getSetting('cash.blind_closing');
+      return { blindClosing: blind === 'true' };
+    },
+    staleTime: 1000 * 60 * 5,
+  });
+}
+
+// Tipo simplificado para a UI (sessionId é injetado)
+export type UIBlindCloseCashSessionInput = Omit<BlindCashClosingInput, 'sessionId'>;
+
+/**
+ * Fecha sessão de caixa com contagem cega
+ */
+export function useBlindCloseCashSession() {
+  const queryClient = useQueryClient();
+  const { closeCashSession: clearCashSession, currentSession } = useAuthStore();
+
+  return useMutation({
+    mutationFn: (input: UIBlindCloseCashSessionInput) => {
+      if (!currentSession?.id) {
+        throw new Error('Nenhuma sessão aberta');
+      }
+      return closeCashSessionBlind({
+        ...input,
+        sessionId: currentSession.id,
+      });
+    },
+    onSuccess: () => {
+      clearCashSession();
+      queryClient.invalidateQueries({ queryKey: cashSessionKeys.all });
+    },
+  });
+}
+
+/**
+ * Fechamentos aguardando aprovação do gerente
+ */
+export function usePendingCashClosings(enabled = true) {
+  return useQuery({
+    queryKey: cashSessionKeys.pendingApproval(),
+    queryFn: getPendingCashClosings,
+    enabled,
+    refetchInterval: 1000 * 60,
+  });
+}
+
+/**
+ * Aprova fechamento com diferença acima da tolerância
+ */
+export function useApproveCashClosing() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, notes }: { id: string; notes?: string }) => approveCashClosing(id, notes),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: cashSessionKeys.pendingApproval() });
+    },
+  });
+}
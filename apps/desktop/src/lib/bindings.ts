@@ -29,6 +29,17 @@ export const commands = {
       else return { status: 'error', error: e as any };
     }
   },
+  /**
+   * Localiza o produto do código lido no PDV (inclui códigos pesados)
+   */
+  async scanProductBarcode(barcode: string): Promise<Result<ScannedProduct | null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('scan_product_barcode', { barcode }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
   async searchProducts(query: string): Promise<Result<Product[], AppError>> {
     try {
       return { status: 'ok', data: await TAURI_INVOKE('search_products', { query }) };
@@ -474,6 +485,41 @@ export const commands = {
       else return { status: 'error', error: e as any };
     }
   },
+  async closeCashSessionBlind(input: BlindCashClosing): Promise<Result<CashSession, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('close_cash_session_blind', { input }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  async approveCashClosing(
+    id: string,
+    notes: string | null
+  ): Promise<Result<CashSession, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('approve_cash_closing', { id, notes }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  async getPendingCashClosings(): Promise<Result<CashSession[], AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_pending_cash_closings') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  async getCashClosingReport(sessionId: string): Promise<Result<CashClosingReport, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_cash_closing_report', { sessionId }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
   async addCashMovement(input: CreateCashMovement): Promise<Result<CashMovement, AppError>> {
     try {
       return { status: 'ok', data: await TAURI_INVOKE('add_cash_movement', { input }) };
@@ -546,6 +592,32 @@ export const commands = {
       else return { status: 'error', error: e as any };
     }
   },
+  async previewPurchaseNfe(filePath: string): Promise<Result<PurchaseImportPreview, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('preview_purchase_nfe', { filePath }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  async importPurchaseNfe(
+    input: ImportPurchaseNfe
+  ): Promise<Result<PurchaseImportResult, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('import_purchase_nfe', { input }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  async getPurchaseInvoices(limit: number | null): Promise<Result<PurchaseInvoice[], AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_purchase_invoices', { limit }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
   async getSuppliers(): Promise<Result<Supplier[], AppError>> {
     try {
       return { status: 'ok', data: await TAURI_INVOKE('get_suppliers') };
@@ -1249,6 +1321,242 @@ export const commands = {
       else return { status: 'error', error: e as any };
     }
   },
+  /**
+   * Lista as regras de preço ativas (maior prioridade primeiro)
+   */
+  async getPricingRules(): Promise<Result<PricingRule[], AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_pricing_rules') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Cria ou atualiza uma regra de preço
+   */
+  async savePricingRule(input: SavePricingRule): Promise<Result<PricingRule, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('save_pricing_rule', { input }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Desativa uma regra de preço
+   */
+  async deletePricingRule(id: string): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('delete_pricing_rule', { id }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Calcula as promoções do carrinho, como serão aplicadas ao finalizar a venda
+   */
+  async previewSalePricing(items: CreateSaleItem[], customerId: string | null): Promise<Result<SalePricing, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('preview_sale_pricing', { items, customerId }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Busca o grupo de preço do cliente
+   */
+  async getCustomerPriceGroup(customerId: string): Promise<Result<string | null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_customer_price_group', { customerId }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Define (ou remove) o grupo de preço do cliente
+   */
+  async setCustomerPriceGroup(customerId: string, priceGroup: string | null): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('set_customer_price_group', { customerId, priceGroup }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Itens da venda com a quantidade que ainda pode ser devolvida
+   */
+  async getReturnableSaleItems(saleId: string): Promise<Result<ReturnableSaleItem[], AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_returnable_sale_items', { saleId }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Devoluções já registradas para a venda
+   */
+  async getSaleReturns(saleId: string): Promise<Result<SaleReturn[], AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_sale_returns', { saleId }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Registra devolução parcial ou troca (estorno no caixa aberto do operador)
+   */
+  async createSaleReturn(input: CreateSaleReturn): Promise<Result<SaleReturn, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('create_sale_return', { input }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Vale pelo id (ex.: emitido por uma devolução)
+   */
+  async getStoreCredit(id: string): Promise<Result<StoreCredit | null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_store_credit', { id }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Consulta o vale pelo código lido no recibo (saldo antes de usar como pagamento)
+   */
+  async getStoreCreditByCode(code: string): Promise<Result<StoreCredit | null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_store_credit_by_code', { code }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Vales do cliente (devoluções e vale-presentes)
+   */
+  async getCustomerStoreCredits(customerId: string): Promise<Result<StoreCredit[], AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_customer_store_credits', { customerId }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Histórico de movimentações do vale
+   */
+  async getStoreCreditTransactions(
+    storeCreditId: string
+  ): Promise<Result<StoreCreditTransaction[], AppError>> {
+    try {
+      return {
+        status: 'ok',
+        data: await TAURI_INVOKE('get_store_credit_transactions', { storeCreditId }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Vende um vale-presente no caixa aberto do operador
+   */
+  async issueGiftCard(input: IssueGiftCard): Promise<Result<StoreCredit, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('issue_gift_card', { input }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Bloqueia (extravio) ou libera o vale
+   */
+  async setStoreCreditActive(
+    id: string,
+    isActive: boolean
+  ): Promise<Result<StoreCredit, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('set_store_credit_active', { id, isActive }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Conta de crediário do cliente (limite e regras de cobrança)
+   */
+  async getCreditAccount(
+    customerId: string
+  ): Promise<Result<CustomerCreditAccount | null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_credit_account', { customerId }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Cria ou atualiza o crediário do cliente
+   */
+  async saveCreditAccount(
+    input: SaveCreditAccount
+  ): Promise<Result<CustomerCreditAccount, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('save_credit_account', { input }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Extrato do crediário: parcelas em aberto com encargos do dia e últimos recebimentos
+   */
+  async getCustomerStatement(
+    customerId: string
+  ): Promise<Result<CustomerStatement, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_customer_statement', { customerId }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Parcelas vencidas além da carência (todos os clientes)
+   */
+  async getOverdueReceivables(): Promise<Result<Receivable[], AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_overdue_receivables') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Recebe pagamento do crediário no caixa aberto do operador
+   */
+  async receiveAccountPayment(
+    input: ReceiveAccountPayment
+  ): Promise<Result<ReceivablePayment[], AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('receive_account_payment', { input }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
   /**
    * Retorna o caminho do diretório de dados do aplicativo
    */
@@ -1905,93 +2213,423 @@ export const commands = {
     }
   },
   /**
-   * Testa impressão
+   * Imprime extrato do crediário (fiado) do cliente
    */
-  async testPrinter(): Promise<Result<null, AppError>> {
+  async printCustomerStatement(customerId: string): Promise<Result<null, AppError>> {
     try {
-      return { status: 'ok', data: await TAURI_INVOKE('test_printer') };
+      return { status: 'ok', data: await TAURI_INVOKE('print_customer_statement', { customerId }) };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: 'error', error: e as any };
     }
   },
-  async testPrinterConnection(): Promise<Result<boolean, AppError>> {
+  /**
+   * Imprime vale-troca / vale-presente com o saldo atual
+   */
+  async printStoreCredit(id: string): Promise<Result<null, AppError>> {
     try {
-      return { status: 'ok', data: await TAURI_INVOKE('test_printer_connection') };
+      return { status: 'ok', data: await TAURI_INVOKE('print_store_credit', { id }) };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: 'error', error: e as any };
     }
   },
   /**
-   * Imprime múltiplos documentos de teste (nota, ordem de serviço, relatório)
+   * Imprime a leitura X (sessão aberta) ou a redução Z (sessão fechada)
    */
-  async printTestDocuments(): Promise<Result<null, AppError>> {
+  async printCashClosingReport(sessionId: string): Promise<Result<null, AppError>> {
     try {
-      return { status: 'ok', data: await TAURI_INVOKE('print_test_documents') };
+      return { status: 'ok', data: await TAURI_INVOKE('print_cash_closing_report', { sessionId }) };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: 'error', error: e as any };
     }
   },
   /**
-   * Retorna configuração atual da impressora
+   * Lista os trabalhos da fila de impressão (mais recentes primeiro)
    */
-  async getPrinterConfig(): Promise<Result<PrinterConfig, AppError>> {
+  async listPrintJobs(
+    status: string | null,
+    limit: number | null
+  ): Promise<Result<PrintJob[], AppError>> {
     try {
-      return { status: 'ok', data: await TAURI_INVOKE('get_printer_config') };
+      return { status: 'ok', data: await TAURI_INVOKE('list_print_jobs', { status, limit }) };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: 'error', error: e as any };
     }
   },
   /**
-   * Configura a balança
+   * Reimprime um trabalho da fila (falho, cancelado ou já impresso)
    */
-  async configureScale(config: ScaleConfig): Promise<Result<null, AppError>> {
+  async reprintPrintJob(id: string): Promise<Result<PrintJob, AppError>> {
     try {
-      return { status: 'ok', data: await TAURI_INVOKE('configure_scale', { config }) };
+      return { status: 'ok', data: await TAURI_INVOKE('reprint_print_job', { id }) };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: 'error', error: e as any };
     }
   },
   /**
-   * Carrega configurações de hardware do banco de dados
+   * Cancela um trabalho que ainda aguarda na fila
    */
-  async loadHardwareConfigs(): Promise<Result<null, AppError>> {
+  async cancelPrintJob(id: string): Promise<Result<PrintJob, AppError>> {
     try {
-      return { status: 'ok', data: await TAURI_INVOKE('load_hardware_configs') };
+      return { status: 'ok', data: await TAURI_INVOKE('cancel_print_job', { id }) };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: 'error', error: e as any };
     }
   },
   /**
-   * Health check agregado de hardware (impressora, balança, scanner)
+   * Testa impressão
    */
-  async hardwareHealthCheck(): Promise<Result<HardwareStatus[], AppError>> {
+  async testPrinter(): Promise<Result<null, AppError>> {
     try {
-      return { status: 'ok', data: await TAURI_INVOKE('hardware_health_check') };
+      return { status: 'ok', data: await TAURI_INVOKE('test_printer') };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: 'error', error: e as any };
     }
   },
-  /**
-   * Lê peso da balança
-   */
-  async readWeight(): Promise<Result<ScaleReading, AppError>> {
+  async testPrinterConnection(): Promise<Result<boolean, AppError>> {
     try {
-      return { status: 'ok', data: await TAURI_INVOKE('read_weight') };
+      return { status: 'ok', data: await TAURI_INVOKE('test_printer_connection') };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: 'error', error: e as any };
     }
   },
-  async readScaleWeight(): Promise<Result<number, AppError>> {
-    try {
-      return { status: 'ok', data: await TAURI_INVOKE('read_scale_weight') };
+  /**
+   * Imprime múltiplos documentos de teste (nota, ordem de serviço, relatório)
+   */
+  async printTestDocuments(): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('print_test_documents') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Lê os sensores da impressora (papel, tampa, erro e gaveta) via DLE EOT / GS r
+   */
+  async getPrinterSensors(): Promise<Result<PrinterSensorStatus, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_printer_sensors') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Modelo vigente do documento (personalizado ou o padrão embutido)
+   */
+  async getReceiptTemplate(
+    document: TemplateDocument
+  ): Promise<Result<ReceiptTemplateSetting, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_receipt_template', { document }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Grava o modelo do documento; `None` volta ao layout padrão
+   */
+  async saveReceiptTemplate(
+    document: TemplateDocument,
+    template: ReceiptTemplate | null
+  ): Promise<Result<ReceiptTemplateSetting, AppError>> {
+    try {
+      return {
+        status: 'ok',
+        data: await TAURI_INVOKE('save_receipt_template', { document, template }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Define o logo impresso no topo dos cupons (PNG em base64); `None` remove
+   */
+  async setPrinterLogo(logo: string | null): Promise<Result<PrinterLogoInfo | null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('set_printer_logo', { logo }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Pré-visualização em texto do modelo com dados de exemplo
+   */
+  async previewReceiptTemplate(
+    document: TemplateDocument,
+    template: ReceiptTemplate
+  ): Promise<Result<string[], AppError>> {
+    try {
+      return {
+        status: 'ok',
+        data: await TAURI_INVOKE('preview_receipt_template', { document, template }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Retorna configuração atual da impressora
+   */
+  async getPrinterConfig(): Promise<Result<PrinterConfig, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_printer_config') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Configura a impressora de etiquetas
+   */
+  async configureLabelPrinter(config: LabelPrinterConfig): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('configure_label_printer', { config }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Retorna configuração atual da impressora de etiquetas
+   */
+  async getLabelPrinterConfig(): Promise<Result<LabelPrinterConfig, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_label_printer_config') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Imprime uma etiqueta de exemplo
+   */
+  async testLabelPrinter(): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('test_label_printer') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Imprime etiquetas dos produtos informados (`copies` de cada).
+   * Retorna quantos produtos foram impressos.
+   */
+  async printProductLabels(productIds: string[], copies: number): Promise<Result<number, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('print_product_labels', { productIds, copies }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Imprime etiquetas de todos os produtos ativos cujo preço de venda mudou
+   * a partir de `since` (AAAA-MM-DD ou data/hora ISO), conforme o histórico
+   * de preços. Retorna quantos produtos foram impressos.
+   */
+  async printPriceChangeLabels(since: string, copies: number): Promise<Result<number, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('print_price_change_labels', { since, copies }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Configura a balança
+   */
+  async configureScale(config: ScaleConfig): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('configure_scale', { config }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Carrega configurações de hardware do banco de dados
+   */
+  async loadHardwareConfigs(): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('load_hardware_configs') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Health check agregado de hardware (impressora, balança, scanner)
+   */
+  async hardwareHealthCheck(): Promise<Result<HardwareStatus[], AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('hardware_health_check') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Lê peso da balança
+   */
+  async readWeight(): Promise<Result<ScaleReading, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('read_weight') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Inicia a leitura contínua da balança (pesos chegam via `hardware:event`)
+   */
+  async startScaleStream(): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('start_scale_stream') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Encerra a leitura contínua da balança
+   */
+  async stopScaleStream(): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('stop_scale_stream') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Envia o preço por kg à balança computadora
+   */
+  async setScaleUnitPrice(unitPrice: number): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('set_scale_unit_price', { unitPrice }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Layouts de código pesado (EAN-13 iniciado em 2)
+   */
+  async getWeightedBarcodeConfig(): Promise<Result<WeightedBarcodeConfig, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_weighted_barcode_config') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Grava os layouts de código pesado da loja
+   */
+  async saveWeightedBarcodeConfig(config: WeightedBarcodeConfig): Promise<Result<WeightedBarcodeConfig, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('save_weighted_barcode_config', { config }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Configuração do cadastro de itens das balanças de balcão
+   */
+  async getScaleCatalogConfig(): Promise<Result<ScaleCatalogConfig, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_scale_catalog_config') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Grava a configuração do cadastro de itens das balanças
+   */
+  async saveScaleCatalogConfig(config: ScaleCatalogConfig): Promise<Result<ScaleCatalogConfig, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('save_scale_catalog_config', { config }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Exporta os produtos por kg para as balanças de balcão: gera o arquivo do
+   * formato configurado, grava na pasta e/ou envia pela rede
+   */
+  async exportScaleCatalog(): Promise<Result<ScaleCatalogExport, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('export_scale_catalog') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Liga o modo de demonstração: balança e impressora simuladas no lugar das
+   * configuradas, sem alterar as configurações salvas
+   */
+  async startDemoHardware(): Promise<Result<DemoHardwareInfo, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('start_demo_hardware') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Desliga o modo de demonstração e volta às configurações anteriores
+   */
+  async stopDemoHardware(): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('stop_demo_hardware') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Situação do modo de demonstração (None quando desligado)
+   */
+  async getDemoHardware(): Promise<Result<DemoHardwareInfo | null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('get_demo_hardware') };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  /**
+   * Coloca um peso no prato da balança simulada
+   */
+  async setDemoScaleWeight(weightGrams: number): Promise<Result<null, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('set_demo_scale_weight', { weightGrams }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
+  async readScaleWeight(): Promise<Result<number, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('read_scale_weight') };
     } catch (e) {
       if (e instanceof Error) throw e;
       else return { status: 'error', error: e as any };
@@ -2147,6 +2785,17 @@ export const commands = {
       else return { status: 'error', error: e as any };
     }
   },
+  /**
+   * Interpreta um código lido (formato, código do produto e dados GS1)
+   */
+  async parseBarcode(code: string): Promise<Result<ParsedBarcode, AppError>> {
+    try {
+      return { status: 'ok', data: await TAURI_INVOKE('parse_barcode', { code }) };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: 'error', error: e as any };
+    }
+  },
   /**
    * Cria um backup do banco de dados
    */
@@ -3623,6 +4272,24 @@ export type AttendantOrderReceipt = {
 /**
  * Metadados do backup
  */
+/**
+ * Formato de código de barras
+ */
+export type BarcodeFormat =
+  | 'EAN13'
+  | 'EAN8'
+  | 'CODE128'
+  | 'GS1_128'
+  | 'CODE39'
+  | 'ITF'
+  | 'ITF14'
+  | 'DATA_MATRIX'
+  | 'QR'
+  | 'UNKNOWN';
+/**
+ * Simbologia para impressão
+ */
+export type BarcodeSymbology = 'EAN13' | 'EAN8' | 'CODE128' | 'GS1_128' | 'ITF' | 'CODE39';
 export type BackupMetadata = {
   id: string;
   filename: string;
@@ -3640,6 +4307,58 @@ export type BackupResult = {
   metadata: BackupMetadata | null;
   error: string | null;
 };
+/**
+ * Contagem informada pelo operador no fechamento cego
+ */
+export type BlindCashClosing = {
+  sessionId: string;
+  denominations: DenominationCount[];
+  methodCounts: MethodCount[];
+  notes: string | null;
+};
+/**
+ * Contagem registrada por forma de pagamento
+ */
+export type CashClosingCount = {
+  id: string;
+  sessionId: string;
+  method: string;
+  expectedAmount: number;
+  countedAmount: number;
+  difference: number;
+  createdAt: string;
+};
+/**
+ * Cédulas e moedas contadas na gaveta
+ */
+export type CashClosingDenomination = {
+  id: string;
+  sessionId: string;
+  denomination: number;
+  quantity: number;
+  amount: number;
+};
+/**
+ * Relatório de fechamento (leitura X / redução Z)
+ */
+export type CashClosingReport = {
+  summary: CashSessionSummary;
+  employeeName: string;
+  approvedByName: string | null;
+  counts: CashClosingCount[];
+  denominations: CashClosingDenomination[];
+  tolerance: number;
+};
+/**
+ * Esperado da sessão por forma de pagamento
+ */
+export type CashMethodBreakdown = {
+  method: string;
+  sales: number;
+  receipts: number;
+  refunds: number;
+  expected: number;
+};
 /**
  * Movimentação de caixa
  */
@@ -3665,6 +4384,11 @@ export type CashSession = {
   difference: number | null;
   status: string;
   notes: string | null;
+  /**
+   * Gerente que aprovou o fechamento com diferença acima da tolerância
+   */
+  approvedById: string | null;
+  approvedAt: string | null;
   createdAt: string;
   updatedAt: string;
 };
@@ -3677,9 +4401,25 @@ export type CashSessionSummary = {
   totalCanceled: number;
   totalWithdrawals: number;
   totalSupplies: number;
+  /**
+   * Estornos de devoluções feitos na sessão (todas as formas)
+   */
+  totalRefunds?: number;
+  /**
+   * Recebimentos de crediário (fiado) na sessão, com encargos
+   */
+  totalAccountReceipts?: number;
+  /**
+   * Vale-presentes vendidos na sessão
+   */
+  totalGiftCardSales?: number;
   movementCount: number;
   salesByMethod: PaymentMethodSummary[];
   cashInDrawer: number;
+  /**
+   * Esperado por forma de pagamento (vendas + recebimentos − estornos)
+   */
+  byMethod?: CashMethodBreakdown[];
 };
 /**
  * Categoria de produtos
@@ -3978,7 +4718,59 @@ export type CreateSaleItem = {
 /**
  * Dados para registro de pagamento
  */
-export type CreateSalePayment = { method: PaymentMethod; amount: number };
+export type CreateSalePayment = {
+  method: PaymentMethod;
+  amount: number;
+  /**
+   * Parcelas do fiado (padrão do crediário do cliente quando ausente)
+   */
+  installments?: number | null;
+  /**
+   * Código do vale (obrigatório para vale-crédito)
+   */
+  storeCreditCode?: string | null;
+  /**
+   * Cartão: integração, credenciadora, bandeira e autorização
+   */
+  card?: SalePaymentCard | null;
+};
+/**
+ * Para registrar devolução ou troca
+ */
+export type CreateSaleReturn = {
+  saleId: string;
+  items: CreateSaleReturnItem[];
+  /**
+   * PAYMENT ou STORE_CREDIT
+   */
+  refundType: string;
+  /**
+   * Forma do estorno quando PAYMENT
+   */
+  refundMethod: PaymentMethod | null;
+  reason: string | null;
+  employeeId: string;
+  cashSessionId: string | null;
+  /**
+   * Troca: venda nova abatida do valor devolvido (pagamentos cobrem só a diferença)
+   */
+  exchange: CreateSale | null;
+};
+/**
+ * Item a devolver
+ */
+export type CreateSaleReturnItem = {
+  saleItemId: string;
+  quantity: number;
+  /**
+   * Lote de destino (padrão: lote de onde o item saiu)
+   */
+  lotId: string | null;
+  /**
+   * `false` para item avariado, que não volta ao estoque
+   */
+  restock: boolean | null;
+};
 /**
  * Para criar serviço padrão
  */
@@ -4122,6 +4914,31 @@ export type Customer = {
   createdAt: string;
   updatedAt: string;
 };
+/**
+ * Conta de crediário do cliente: limite e regras de cobrança
+ */
+export type CustomerCreditAccount = {
+  customerId: string;
+  creditLimit: number;
+  /**
+   * Juros de mora em % ao mês (pro rata dia)
+   */
+  interestRate: number;
+  /**
+   * Multa em % sobre a parcela em atraso
+   */
+  finePercent: number;
+  /**
+   * Dias após o vencimento sem encargos
+   */
+  graceDays: number;
+  defaultInstallments: number;
+  installmentIntervalDays: number;
+  isBlocked: boolean;
+  notes: string | null;
+  createdAt: string;
+  updatedAt: string;
+};
 /**
  * Filtros para busca de clientes
  */
@@ -4132,6 +4949,28 @@ export type CustomerFilters = {
   isActive: boolean | null;
   hasVehicles: boolean | null;
 };
+/**
+ * Extrato do crediário do cliente
+ */
+export type CustomerStatement = {
+  customerId: string;
+  customerName: string;
+  account: CustomerCreditAccount | null;
+  /**
+   * Principal em aberto
+   */
+  balance: number;
+  /**
+   * Multa e juros calculados para hoje
+   */
+  charges: number;
+  totalDue: number;
+  overdueAmount: number;
+  availableCredit: number;
+  openReceivables: Receivable[];
+  recentPayments: ReceivablePayment[];
+  generatedAt: string;
+};
 /**
  * Veículo cadastrado para um cliente
  */
@@ -4219,6 +5058,25 @@ export type DashboardStats = {
   activeAlerts: number;
   revenueWeekly: DailyRevenue[];
 };
+/**
+ * Quantidade contada de uma cédula/moeda
+ */
+export type DenominationCount = { denomination: number; quantity: number };
+/**
+ * Situação do modo de demonstração exibida na tela de hardware
+ */
+export type DemoHardwareInfo = {
+  /**
+   * Porta da balança simulada (ausente no Windows, onde a balança fica em mock)
+   */
+  scalePort: string | null;
+  printerAddress: string;
+  printedJobs: number;
+  /**
+   * Último cupom recebido pela impressora simulada
+   */
+  lastTranscript: string | null;
+};
 /**
  * Tipo de desconto
  */
@@ -4246,6 +5104,10 @@ export type DrawerConfig = {
    */
   pulseDuration: number;
   mockMode: boolean;
+  /**
+   * Sensor com lógica invertida (pino 3 em nível alto = gaveta aberta)
+   */
+  sensorInverted?: boolean;
 };
 /**
  * Pino de acionamento da gaveta
@@ -4297,10 +5159,44 @@ export type FinancialReport = {
   netProfit: number;
   margin: number;
 };
+/**
+ * Dados GS1 lidos do código
+ */
+export type Gs1Data = {
+  /**
+   * AI 01 (GTIN-14)
+   */
+  gtin: string | null;
+  /**
+   * AI 10
+   */
+  lot: string | null;
+  /**
+   * AI 17 (AAAA-MM-DD)
+   */
+  expirationDate: string | null;
+  /**
+   * AI 21
+   */
+  serial: string | null;
+  elements: Gs1Element[];
+};
+/**
+ * Elemento GS1 (Identificador de Aplicação e valor)
+ */
+export type Gs1Element = { ai: string; value: string };
 /**
  * Resultado simples de verificação de saúde de um dispositivo de hardware
  */
 export type HardwareStatus = { name: string; ok: boolean; message: string | null };
+export type ImportPurchaseNfe = {
+  filePath: string;
+  matches: PurchaseItemMatch[];
+  /**
+   * Memoriza o vínculo código do fornecedor → produto para as próximas notas
+   */
+  rememberCodes?: boolean;
+};
 /**
  * Contagem de Inventário (Enterprise)
  */
@@ -4352,6 +5248,35 @@ export type InventoryCountItemWithProduct = {
   createdAt: string;
   updatedAt: string;
 } & { productName: string; productCode: string; productUnit: string };
+/**
+ * Para vender um vale-presente no caixa
+ */
+export type IssueGiftCard = {
+  amount: number;
+  customerId: string | null;
+  /**
+   * Validade (YYYY-MM-DD)
+   */
+  expiresAt: string | null;
+  paymentMethod: PaymentMethod;
+  employeeId: string;
+  cashSessionId: string | null;
+};
+/**
+ * Promoção aplicada a um item do carrinho
+ */
+export type ItemPromotion = {
+  /**
+   * Posição do item na lista enviada
+   */
+  itemIndex: number;
+  ruleId: string;
+  /**
+   * Motivo impresso no cupom (nome da regra)
+   */
+  reason: string;
+  discount: number;
+};
 export type JsonValue =
   | null
   | boolean
@@ -4359,6 +5284,42 @@ export type JsonValue =
   | string
   | JsonValue[]
   | { [key in string]: JsonValue };
+/**
+ * Linguagem de comandos da impressora de etiquetas
+ */
+export type LabelLanguage = 'zpl' | 'epl' | 'ppla';
+/**
+ * Configuração da impressora de etiquetas
+ */
+export type LabelPrinterConfig = {
+  enabled: boolean;
+  language: LabelLanguage;
+  connection: PrinterConnection;
+  port: string;
+  baudRate: number;
+  /**
+   * Resolução do cabeçote (203 ou 300 dpi)
+   */
+  dpi: number;
+  /**
+   * Largura da etiqueta em mm
+   */
+  labelWidthMm: number;
+  /**
+   * Altura da etiqueta em mm
+   */
+  labelHeightMm: number;
+  /**
+   * Espaço entre etiquetas em mm
+   */
+  gapMm: number;
+  /**
+   * Temperatura de impressão (0-30)
+   */
+  darkness: number;
+  timeoutMs: number;
+  mockMode: boolean;
+};
 /**
  * License information
  */
@@ -4457,6 +5418,10 @@ export type MaterialRequestItemWithProduct = {
   createdAt: string;
   updatedAt: string;
 } & { productName: string; productCode: string; productUnit: string };
+/**
+ * Valor contado para uma forma de pagamento que não é dinheiro
+ */
+export type MethodCount = { method: PaymentMethod; amount: number };
 /**
  * Metrics sync payload (as passed from frontend/service)
  */
@@ -4521,10 +5486,37 @@ export type PaginatedResult<T> = {
   limit: number;
   totalPages: number;
 };
+/**
+ * Código escaneado já interpretado
+ */
+export type ParsedBarcode = {
+  /**
+   * Código lido, sem identificador de simbologia
+   */
+  code: string;
+  format: BarcodeFormat;
+  /**
+   * Código do produto (EAN) quando o lido é GS1 ou caixa ITF-14
+   */
+  productCode: string | null;
+  /**
+   * Lote, validade e demais dados GS1
+   */
+  gs1: Gs1Data | null;
+};
 /**
  * Forma de pagamento
  */
-export type PaymentMethod = 'CASH' | 'PIX' | 'CREDIT' | 'DEBIT' | 'VOUCHER' | 'OTHER';
+export type PaymentMethod =
+  | 'CASH'
+  | 'PIX'
+  | 'CREDIT'
+  | 'DEBIT'
+  | 'VOUCHER'
+  | 'OTHER'
+  | 'ACCOUNT'
+  | 'STORE_CREDIT'
+  | 'EXCHANGE_CREDIT';
 /**
  * Resumo por forma de pagamento
  */
@@ -4554,6 +5546,7 @@ export type PriceHistory = {
   productId: string;
   oldPrice: number;
   newPrice: number;
+  priceType: string;
   reason: string | null;
   employeeId: string | null;
   createdAt: string;
@@ -4566,12 +5559,58 @@ export type PriceHistoryWithProduct = {
   productId: string;
   oldPrice: number;
   newPrice: number;
+  priceType: string;
   reason: string | null;
   employeeId: string | null;
   createdAt: string;
   productName: string | null;
   employeeName: string | null;
 };
+/**
+ * Regra de preço/promoção avaliada na criação da venda
+ */
+export type PricingRule = {
+  id: string;
+  name: string;
+  kind: string;
+  productId: string | null;
+  categoryId: string | null;
+  customerGroup: string | null;
+  quantity: number;
+  payQuantity: number;
+  percent: number;
+  price: number | null;
+  startTime: string | null;
+  endTime: string | null;
+  weekdays: string | null;
+  validFrom: string | null;
+  validUntil: string | null;
+  priority: number;
+  isActive: boolean;
+  createdAt: string;
+  updatedAt: string;
+  items?: PricingRuleItem[];
+};
+/**
+ * Produto que compõe um combo/kit
+ */
+export type PricingRuleItem = { productId: string; quantity: number };
+/**
+ * Trabalho da fila de impressão (sem os bytes ESC/POS)
+ */
+export type PrintJob = {
+  id: string;
+  kind: string;
+  reference: string | null;
+  status: string;
+  attempts: number;
+  maxAttempts: number;
+  lastError: string | null;
+  nextAttemptAt: string;
+  printedAt: string | null;
+  createdAt: string;
+  updatedAt: string;
+};
 /**
  * Configuração da impressora
  */
@@ -4593,10 +5632,31 @@ export type PrinterConfig = {
  * Tipo de conexão da impressora
  */
 export type PrinterConnection = 'usb' | 'serial' | 'network';
+/**
+ * Dimensões do logo convertido, em pontos
+ */
+export type PrinterLogoInfo = { widthDots: number; heightDots: number };
 /**
  * Modelo de impressora
  */
 export type PrinterModel = 'epson' | 'elgin' | 'bematech' | 'daruma' | 'c3tech' | 'generic';
+/**
+ * Sensores lidos da impressora
+ */
+export type PrinterSensorStatus = {
+  online: boolean;
+  paperNearEnd: boolean;
+  paperOut: boolean;
+  coverOpen: boolean;
+  /**
+   * Erro de guilhotina ou do mecanismo
+   */
+  error: boolean;
+  /**
+   * Nível do pino 3 do conector da gaveta (alto = true)
+   */
+  drawerPinHigh: boolean;
+};
 /**
  * Produto do catálogo
  */
@@ -4669,6 +5729,77 @@ export type ProductUnit =
   | 'DOZEN'
   | 'KIT'
   | 'PAIR';
+/**
+ * NF-e de compra já lançada no estoque
+ */
+export type PurchaseInvoice = {
+  id: string;
+  accessKey: string;
+  supplierId: string;
+  number: number;
+  serie: number;
+  issuedAt: string;
+  total: number;
+  itemsImported: number;
+  itemsSkipped: number;
+  employeeId: string | null;
+  createdAt: string;
+};
+/**
+ * Prévia da importação (não altera o banco)
+ */
+export type PurchaseImportPreview = {
+  accessKey: string;
+  number: number;
+  serie: number;
+  issuedAt: string;
+  total: number;
+  supplierCnpj: string;
+  supplierName: string;
+  supplierId: string | null;
+  alreadyImported: boolean;
+  items: PurchasePreviewItem[];
+};
+export type PurchaseImportResult = {
+  invoiceId: string;
+  supplierId: string;
+  supplierCreated: boolean;
+  itemsImported: number;
+  itemsSkipped: number;
+  lotsCreated: number;
+  costChanges: number;
+};
+/**
+ * Produto escolhido para cada item (ausente = item ignorado)
+ */
+export type PurchaseItemMatch = { itemNumber: number; productId: string | null };
+/**
+ * Lote do item (grupo `rastro`)
+ */
+export type PurchaseLotInfo = {
+  lotNumber: string | null;
+  quantity: number;
+  manufacturingDate: string | null;
+  expirationDate: string | null;
+};
+/**
+ * Item da nota com o produto sugerido
+ */
+export type PurchasePreviewItem = {
+  itemNumber: number;
+  supplierCode: string;
+  ean: string | null;
+  description: string;
+  ncm: string | null;
+  unit: string;
+  quantity: number;
+  unitCost: number;
+  totalCost: number;
+  lots: PurchaseLotInfo[];
+  productId: string | null;
+  productName: string | null;
+  matchSource: string | null;
+};
 /**
  * Dados do cupom
  */
@@ -4680,6 +5811,11 @@ export type Receipt = {
   saleNumber: number;
   operatorName: string;
   dateTime: string;
+  customerName?: string | null;
+  /**
+   * CPF/CNPJ do cliente identificado na venda
+   */
+  customerDocument?: string | null;
   items: ReceiptItem[];
   subtotal: number;
   discount: number;
@@ -4698,6 +5834,85 @@ export type ReceiptItem = {
   unit: string;
   unitPrice: number;
   total: number;
+  /**
+   * Desconto do item (manual ou promoção), já abatido do total
+   */
+  discount?: number;
+  /**
+   * Motivo impresso abaixo do item (nome da promoção)
+   */
+  discountReason?: string | null;
+};
+/**
+ * Modelo de impressão de um documento
+ */
+export type ReceiptTemplate = { blocks: TemplateBlock[] };
+/**
+ * Modelo vigente de um documento
+ */
+export type ReceiptTemplateSetting = {
+  document: TemplateDocument;
+  /**
+   * Falso quando a loja usa o layout padrão (o modelo devolvido é o
+   * equivalente embutido, ponto de partida para personalizar)
+   */
+  custom: boolean;
+  template: ReceiptTemplate;
+};
+/**
+ * Parcela a receber (gerada por venda fiado)
+ */
+export type Receivable = {
+  id: string;
+  customerId: string;
+  saleId: string | null;
+  installmentNumber: number;
+  installmentCount: number;
+  amount: number;
+  paidAmount: number;
+  dueDate: string;
+  status: string;
+  interestPaidUntil: string | null;
+  fineCharged: boolean;
+  paidAt: string | null;
+  createdAt: string;
+  updatedAt: string;
+  /**
+   * Encargos calculados para hoje (não persistidos)
+   */
+  daysOverdue?: number;
+  fine?: number;
+  interest?: number;
+  amountDue?: number;
+};
+/**
+ * Recebimento de parcela no caixa
+ */
+export type ReceivablePayment = {
+  id: string;
+  customerId: string;
+  receivableId: string;
+  /**
+   * Principal abatido
+   */
+  amount: number;
+  interest: number;
+  fine: number;
+  total: number;
+  method: string;
+  cashSessionId: string | null;
+  employeeId: string;
+  createdAt: string;
+};
+/**
+ * Para receber pagamento do crediário (quita as parcelas mais antigas primeiro)
+ */
+export type ReceiveAccountPayment = {
+  customerId: string;
+  amount: number;
+  method: PaymentMethod;
+  employeeId: string;
+  cashSessionId: string | null;
 };
 /**
  * DTO para item recebido
@@ -4717,6 +5932,18 @@ export type RequestFilters = {
   dateFrom: string | null;
   dateTo: string | null;
 };
+/**
+ * Quantidade ainda devolvível de cada item da venda
+ */
+export type ReturnableSaleItem = {
+  saleItemId: string;
+  productId: string;
+  productName: string;
+  lotId: string | null;
+  soldQuantity: number;
+  returnedQuantity: number;
+  unitPrice: number;
+};
 /**
  * Funcionário seguro (para retorno ao frontend, sem senhas)
  */
@@ -4788,6 +6015,8 @@ export type SaleItem = {
   productBarcode: string | null;
   productUnit: string;
   lotId: string | null;
+  pricingRuleId: string | null;
+  discountReason: string | null;
   createdAt: string;
 };
 /**
@@ -4798,6 +6027,65 @@ export type SalePayment = {
   saleId: string;
   method: PaymentMethod;
   amount: number;
+  /**
+   * 1=TEF, 2=POS (somente cartão)
+   */
+  cardIntegration: number | null;
+  acquirerCnpj: string | null;
+  cardBrand: string | null;
+  authorizationCode: string | null;
+  createdAt: string;
+};
+/**
+ * Dados do cartão informados pelo TEF/POS
+ */
+export type SalePaymentCard = {
+  /**
+   * 1=TEF, 2=POS (padrão)
+   */
+  cardIntegration: number | null;
+  acquirerCnpj: string | null;
+  cardBrand: string | null;
+  authorizationCode: string | null;
+};
+/**
+ * Resultado da avaliação das promoções de um carrinho
+ */
+export type SalePricing = { items: ItemPromotion[]; totalDiscount: number };
+/**
+ * Devolução (parcial ou total) de uma venda
+ */
+export type SaleReturn = {
+  id: string;
+  saleId: string;
+  customerId: string | null;
+  employeeId: string;
+  cashSessionId: string | null;
+  total: number;
+  refundType: string;
+  refundMethod: string | null;
+  refundAmount: number;
+  exchangeSaleId: string | null;
+  storeCreditId: string | null;
+  reason: string | null;
+  fiscalAccessKey: string | null;
+  createdAt: string;
+  items?: SaleReturnItem[];
+};
+/**
+ * Item devolvido, ligado ao item da venda original
+ */
+export type SaleReturnItem = {
+  id: string;
+  returnId: string;
+  saleItemId: string;
+  productId: string;
+  productName: string;
+  lotId: string | null;
+  quantity: number;
+  unitPrice: number;
+  total: number;
+  restocked: boolean;
   createdAt: string;
 };
 /**
@@ -4832,10 +6120,95 @@ export type SalesReport = {
   salesByPaymentMethod: { [key in string]: number };
   salesByHour: { [key in string]: number };
 };
+/**
+ * Para criar ou atualizar o crediário do cliente
+ */
+export type SaveCreditAccount = {
+  customerId: string;
+  creditLimit: number;
+  interestRate: number;
+  finePercent: number;
+  graceDays: number;
+  defaultInstallments: number;
+  installmentIntervalDays: number;
+  isBlocked: boolean;
+  notes: string | null;
+};
+/**
+ * Para criar ou atualizar regra de preço
+ */
+export type SavePricingRule = {
+  id: string | null;
+  name: string;
+  kind: string;
+  productId: string | null;
+  categoryId: string | null;
+  customerGroup: string | null;
+  quantity?: number;
+  payQuantity?: number;
+  percent?: number;
+  price: number | null;
+  startTime: string | null;
+  endTime: string | null;
+  weekdays: string | null;
+  validFrom: string | null;
+  validUntil: string | null;
+  priority?: number;
+  items?: PricingRuleItem[];
+};
 /**
  * Para salvar compatibilidades de um produto
  */
 export type SaveProductCompatibilities = { productId: string; vehicleYearIds: string[] };
+/**
+ * Configuração da exportação
+ */
+export type ScaleCatalogConfig = {
+  format: ScaleCatalogFormat;
+  /**
+   * Departamento dos itens na Toledo (1 a 99)
+   */
+  department: number;
+  /**
+   * Validade impressa na etiqueta (0 = não imprime)
+   */
+  validityDays: number;
+  /**
+   * Pasta onde gravar o arquivo (lida pelo MGV ou software da Filizola)
+   */
+  outputDir: string | null;
+  /**
+   * Endereço "ip:porta" da balança (ou do concentrador) para envio direto
+   */
+  networkAddress: string | null;
+};
+/**
+ * Resultado da exportação
+ */
+export type ScaleCatalogExport = {
+  fileName: string;
+  /**
+   * Conteúdo do arquivo (para conferência ou download)
+   */
+  content: string;
+  exported: number;
+  /**
+   * Produtos por kg que ficaram de fora, com o motivo
+   */
+  skipped: string[];
+  /**
+   * Caminho gravado, quando há pasta configurada
+   */
+  writtenPath: string | null;
+  /**
+   * Se o arquivo foi enviado pela rede
+   */
+  pushed: boolean;
+};
+/**
+ * Formato do arquivo de itens
+ */
+export type ScaleCatalogFormat = 'toledo' | 'filizola';
 export type ScaleAutoDetectInfo = { config: ScaleConfig | null; failures: string[] };
 export type ScaleConfig = {
   enabled: boolean;
@@ -4846,6 +6219,14 @@ export type ScaleConfig = {
   parity: string;
   stopBits: number;
   mockMode: boolean;
+  /**
+   * Balança transmite o peso sem consulta (ENQ)
+   */
+  continuous?: boolean;
+  /**
+   * Balança computadora: aceita preço por kg e devolve o total
+   */
+  priceComputing?: boolean;
 };
 /**
  * Protocolo da balança
@@ -4875,11 +6256,38 @@ export type ScaleReading = {
    * Se o peso é negativo (tara)
    */
   negative: boolean;
+  /**
+   * Se há tara ativa (peso líquido)
+   */
+  tare?: boolean;
+  /**
+   * Preço por kg informado pela balança computadora
+   */
+  unitPrice?: number | null;
+  /**
+   * Total calculado pela balança computadora
+   */
+  totalPrice?: number | null;
   /**
    * Protocolo usado
    */
   protocol: ScaleProtocol;
 };
+/**
+ * Produto localizado pelo código lido no PDV
+ */
+export type ScannedProduct = {
+  product: Product;
+  /**
+   * 1 para códigos comuns; peso em kg nos códigos pesados
+   */
+  quantity: number;
+  /**
+   * Preço total impresso na etiqueta da balança (código de preço)
+   */
+  totalPrice: number | null;
+  weighted: WeightedBarcode | null;
+};
 /**
  * Informações do servidor de scanner
  */
@@ -5203,6 +6611,41 @@ export type StockTransferItem = {
   createdAt: string;
   updatedAt: string;
 };
+/**
+ * Vale com saldo (código EAN-13 impresso no recibo)
+ */
+export type StoreCredit = {
+  id: string;
+  code: string;
+  kind: string;
+  customerId: string | null;
+  returnId: string | null;
+  amount: number;
+  balance: number;
+  isActive: boolean;
+  expiresAt: string | null;
+  createdAt: string;
+  updatedAt: string;
+};
+/**
+ * Movimentação do saldo do vale
+ */
+export type StoreCreditTransaction = {
+  id: string;
+  storeCreditId: string;
+  type: string;
+  /**
+   * Positivo credita, negativo debita
+   */
+  amount: number;
+  balanceAfter: number;
+  saleId: string | null;
+  returnId: string | null;
+  paymentMethod: string | null;
+  cashSessionId: string | null;
+  employeeId: string | null;
+  createdAt: string;
+};
 /**
  * Fornecedor
  */
@@ -5221,6 +6664,41 @@ export type Supplier = {
   createdAt: string;
   updatedAt: string;
 };
+/**
+ * Alinhamento de um bloco de texto
+ */
+export type TemplateAlign = 'left' | 'center' | 'right';
+/**
+ * Bloco do modelo
+ */
+export type TemplateBlock =
+  | {
+      type: 'text';
+      text: string;
+      right?: string | null;
+      align?: TemplateAlign;
+      style?: TemplateStyle;
+    }
+  | { type: 'separator'; char?: string }
+  | { type: 'feed'; lines?: number }
+  | { type: 'if'; field: string; blocks: TemplateBlock[] }
+  | { type: 'items'; blocks: TemplateBlock[] }
+  | { type: 'barcode'; value: string; symbology?: BarcodeSymbology }
+  | { type: 'qrCode'; value: string }
+  | { type: 'logo' };
+/**
+ * Documento impresso que aceita modelo próprio
+ */
+export type TemplateDocument = 'receipt' | 'service_order' | 'attendant_order';
+/**
+ * Estilo (negrito, sublinhado e tamanho da fonte) de um bloco de texto
+ */
+export type TemplateStyle = {
+  bold?: boolean;
+  underline?: boolean;
+  doubleHeight?: boolean;
+  doubleWidth?: boolean;
+};
 export type TopItem = { id: string; name: string; quantity: number; totalValue: number };
 export type TopProduct = { product: Product; quantity: number; revenue: number };
 /**
@@ -5518,6 +6996,57 @@ export type WarrantyStats = {
   totalReplacementCost: number;
   avgResolutionDays: number | null;
 };
+/**
+ * Código de barras pesado decodificado
+ */
+export type WeightedBarcode = {
+  productCode: string;
+  weightGrams: number;
+  priceCents: number | null;
+  isWeight: boolean;
+};
+/**
+ * Layouts de código pesado da loja
+ */
+export type WeightedBarcodeConfig = {
+  enabled: boolean;
+  /**
+   * O prefixo mais longo que casar com o código define o layout
+   */
+  layouts: WeightedBarcodeLayout[];
+};
+/**
+ * Layout de código pesado (EAN-13 iniciado em 2)
+ *
+ * Formato: prefixo + código do produto + valor + dígito verificador; o dígito
+ * que sobrar antes do verificador (verificador do preço em algumas balanças)
+ * é ignorado. Ex.: 2 CCCCC VVVVV X D (prefixo "2", código 5, valor 5) ou
+ * 20 CCCC VVVVVV D (prefixo "20", código 4, valor 6).
+ */
+export type WeightedBarcodeLayout = {
+  /**
+   * "2" (qualquer código pesado) ou "20" a "29"
+   */
+  prefix: string;
+  /**
+   * O segundo dígito do prefixo também é o primeiro do código do produto
+   * (2 CCCCC VVVVV X D com o prefixo "2C" escolhendo peso ou preço)
+   */
+  prefixInCode?: boolean;
+  /**
+   * Dígitos do código do produto na balança (4 a 6)
+   */
+  codeDigits: number;
+  /**
+   * Dígitos do valor: gramas ou centavos (4 a 6)
+   */
+  valueDigits: number;
+  valueKind: WeightedValueKind;
+};
+/**
+ * Valor embutido no código pesado
+ */
+export type WeightedValueKind = 'weight' | 'price';
 /**
  * Frente de Trabalho
  */
@@ -185,6 +185,9 @@ export function getPaymentMethodLabel(method: PaymentMethod): string {
     DEBIT: 'Débito',
     VOUCHER: 'Vale',
     OTHER: 'Outro',
+    ACCOUNT: 'Fiado',
+    STORE_CREDIT: 'Vale-Crédito',
+    EXCHANGE_CREDIT: 'Crédito de Troca',
   };
   return labels[method];
 }
@@ -200,6 +203,9 @@ export function getPaymentMethodIcon(method: PaymentMethod): string {
     DEBIT: 'credit-card',
     VOUCHER: 'ticket',
     OTHER: 'wallet',
+    ACCOUNT: 'notebook-pen',
+    STORE_CREDIT: 'gift',
+    EXCHANGE_CREDIT: 'repeat',
   };
   return icons[method];
 }
@@ -49,7 +49,16 @@ export const CreateSaleItem = z.object({
 
 export const CreateSale = z.object({
   items: z.array(CreateSaleItem),
-  paymentMethod: z.enum(['CASH', 'PIX', 'CREDIT', 'DEBIT', 'VOUCHER', 'OTHER']),
+  paymentMethod: z.enum([
+    'CASH',
+    'PIX',
+    'CREDIT',
+    'DEBIT',
+    'VOUCHER',
+    'OTHER',
+    'ACCOUNT',
+    'STORE_CREDIT',
+  ]),
   amountPaid: z.number(),
   discountType: z.string().optional(),
   discountValue: z.number().optional(),
@@ -83,6 +92,8 @@ export const Receipt = z.object({
   saleNumber: z.number(),
   operatorName: z.string(),
   dateTime: z.string(),
+  customerName: z.string().optional(),
+  customerDocument: z.string().optional(),
   items: z.array(ReceiptItem),
   subtotal: z.number(),
   discount: z.number(),
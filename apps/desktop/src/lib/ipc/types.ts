@@ -1,6 +1,15 @@
 // IPC Types mirroring Rust structs (serde)
 
-export type PaymentMethod = 'CASH' | 'PIX' | 'CREDIT' | 'DEBIT' | 'VOUCHER' | 'OTHER';
+export type PaymentMethod =
+  | 'CASH'
+  | 'PIX'
+  | 'CREDIT'
+  | 'DEBIT'
+  | 'VOUCHER'
+  | 'OTHER'
+  | 'ACCOUNT'
+  | 'STORE_CREDIT'
+  | 'EXCHANGE_CREDIT';
 
 export interface CreateSaleItem {
   productId: string;
@@ -44,6 +53,8 @@ export interface Receipt {
   saleNumber: number;
   operatorName: string;
   dateTime: string;
+  customerName?: string | null;
+  customerDocument?: string | null;
 
   items: ReceiptItem[];
   subtotal: number;
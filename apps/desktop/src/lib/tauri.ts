@@ -8,6 +8,8 @@ const log = createLogger('Tauri');
 // import { useAuthStore } from '@/stores/auth-store'; // Removed to break circular dependency
 import type {
   Alert,
+  BlindCashClosingInput,
+  CashClosingReport,
   CashMovement,
   CashMovementInput,
   CashMovementType,
@@ -17,17 +19,47 @@ import type {
   CloseCashSessionInput,
   CreateProductInput,
   CreateSaleInput,
+  CreateSaleReturnInput,
+  CustomerCreditAccount,
+  CustomerStatement,
+  DanfeReprintResponse,
   EmissionResponse,
   EmitNfceRequest,
   Employee,
+  LabelPrinterConfig,
   LicenseInfo,
   OfflineNote,
   OpenCashSessionInput,
   PaginatedResult,
+  ParsedBarcode,
+  PaymentMethod,
+  PricingRule,
+  PrintJob,
+  PrintJobStatus,
+  PrinterLogoInfo,
+  PrinterSensorStatus,
   Product,
+  Receivable,
+  ReceivablePayment,
+  ReceiptTemplate,
+  ReturnableSaleItem,
+  ScaleCatalogConfig,
+  ScaleCatalogExport,
+  DemoHardwareInfo,
+  ScannedProduct,
+  WeightedBarcodeConfig,
+  ReceiptTemplateSetting,
+  TemplateDocument,
   ProductFilter,
   ProductLot,
   Sale,
+  SalePricing,
+  SaleReturn,
+  SaveCreditAccountInput,
+  SavePricingRule,
+  StoreCredit,
+  StoreCreditTransaction,
+  IssueGiftCardInput,
   EnterpriseKPIs,
   SaleFilter,
   Setting,
@@ -264,6 +296,36 @@ const webMockInvoke = async <T>(command: string, args?: Record<string, unknown>)
 
       return closed as T;
     }
+    case 'close_cash_session_blind': {
+      const input = args?.input as BlindCashClosingInput | undefined;
+      const session = db.currentCashSession;
+      if (!session || !input || session.id !== input.sessionId || session.status !== 'OPEN') {
+        throw new Error('Nenhuma sessão aberta para fechar');
+      }
+
+      const counted = input.denominations.reduce(
+        (acc, d) => acc + d.denomination * d.quantity,
+        0
+      );
+      const closed: CashSession = {
+        ...session,
+        status: 'CLOSED',
+        closedAt: nowIso(),
+        actualBalance: counted,
+        notes: input.notes ?? session.notes,
+      };
+
+      db.currentCashSession = null;
+      db.cashSessionHistory = db.cashSessionHistory.map((s) =>
+        s.id === session.id ? closed : s
+      );
+      saveWebMockDb(db);
+
+      return closed as T;
+    }
+    case 'get_pending_cash_closings': {
+      return db.cashSessionHistory.filter((s) => s.status === 'PENDING_APPROVAL') as T;
+    }
     case 'get_cash_session_history': {
       return db.cashSessionHistory as T;
     }
@@ -331,6 +393,8 @@ const webMockInvoke = async <T>(command: string, args?: Record<string, unknown>)
         totalCanceled: 0,
         totalWithdrawals: withdrawals,
         totalSupplies: supplies,
+        totalRefunds: 0,
+        totalAccountReceipts: 0,
         movementCount: (session.movements ?? []).length,
         salesByMethod: [],
         cashInDrawer,
@@ -375,9 +439,50 @@ const webMockInvoke = async <T>(command: string, args?: Record<string, unknown>)
       // const id = (args?.id as string | undefined) ?? '';
       return null as unknown as T;
     }
-    case 'get_product_by_barcode': {
+    case 'get_product_by_barcode':
+    case 'scan_product_barcode': {
       return null as unknown as T;
     }
+    case 'get_weighted_barcode_config': {
+      return {
+        enabled: true,
+        layouts: [{ prefix: '2', codeDigits: 5, valueDigits: 5, valueKind: 'price' }],
+      } as unknown as T;
+    }
+    case 'save_weighted_barcode_config':
+    case 'save_scale_catalog_config': {
+      return args?.config as T;
+    }
+    case 'get_scale_catalog_config': {
+      return {
+        format: 'toledo',
+        department: 1,
+        validityDays: 0,
+        outputDir: null,
+        networkAddress: null,
+      } as unknown as T;
+    }
+    case 'export_scale_catalog': {
+      return {
+        fileName: 'ITENSMGV.TXT',
+        content: '',
+        exported: 0,
+        skipped: [],
+        writtenPath: null,
+        pushed: false,
+      } as unknown as T;
+    }
+    case 'start_demo_hardware': {
+      return {
+        scalePort: null,
+        printerAddress: '127.0.0.1:9100',
+        printedJobs: 0,
+        lastTranscript: null,
+      } as unknown as T;
+    }
+    case 'get_demo_hardware': {
+      return null as T;
+    }
     case 'search_products': {
       const query = ((args?.query as string) || '').toLowerCase();
 
@@ -938,6 +1043,96 @@ const webMockInvoke = async <T>(command: string, args?: Record<string, unknown>)
       const saleId = randomId('sale');
       return { id: saleId, status: 'COMPLETED' } as unknown as T;
     }
+    case 'preview_sale_pricing': {
+      return { items: [], totalDiscount: 0 } as unknown as T;
+    }
+    case 'get_pricing_rules': {
+      return [] as unknown as T;
+    }
+    case 'save_pricing_rule': {
+      const input = args?.input as SavePricingRule | undefined;
+      return { ...input, id: input?.id ?? randomId('rule'), isActive: true } as unknown as T;
+    }
+    case 'get_customer_price_group': {
+      return null as unknown as T;
+    }
+    case 'delete_pricing_rule':
+    case 'set_customer_price_group': {
+      return undefined as T;
+    }
+    case 'get_returnable_sale_items':
+    case 'get_sale_returns': {
+      return [] as unknown as T;
+    }
+    case 'create_sale_return': {
+      const input = args?.input as CreateSaleReturnInput | undefined;
+      if (!input?.items?.length) throw new Error('Selecione ao menos um item para devolver');
+      return {
+        id: randomId('ret'),
+        saleId: input.saleId,
+        employeeId: 'admin-1',
+        total: 0,
+        refundType: input.refundType,
+        refundMethod: input.refundMethod,
+        refundAmount: 0,
+        items: [],
+        createdAt: nowIso(),
+      } as unknown as T;
+    }
+    case 'get_store_credit':
+    case 'get_store_credit_by_code': {
+      return null as unknown as T;
+    }
+    case 'get_customer_store_credits':
+    case 'get_store_credit_transactions': {
+      return [] as unknown as T;
+    }
+    case 'issue_gift_card': {
+      const input = args?.input as IssueGiftCardInput | undefined;
+      return {
+        id: randomId(),
+        code: '9800000000001',
+        kind: 'GIFT_CARD',
+        customerId: input?.customerId,
+        amount: input?.amount ?? 0,
+        balance: input?.amount ?? 0,
+        isActive: true,
+        expiresAt: input?.expiresAt,
+        createdAt: nowIso(),
+        updatedAt: nowIso(),
+      } as unknown as T;
+    }
+    case 'set_store_credit_active': {
+      throw new Error('Vale não encontrado');
+    }
+    case 'get_credit_account': {
+      return null as unknown as T;
+    }
+    case 'save_credit_account': {
+      const input = args?.input as SaveCreditAccountInput | undefined;
+      return { ...input, createdAt: nowIso(), updatedAt: nowIso() } as unknown as T;
+    }
+    case 'get_customer_statement': {
+      const customerId = (args?.customerId as string | undefined) ?? '';
+      return {
+        customerId,
+        customerName: 'Cliente',
+        balance: 0,
+        charges: 0,
+        totalDue: 0,
+        overdueAmount: 0,
+        availableCredit: 0,
+        openReceivables: [],
+        recentPayments: [],
+        generatedAt: nowIso(),
+      } as unknown as T;
+    }
+    case 'get_overdue_receivables': {
+      return [] as unknown as T;
+    }
+    case 'receive_account_payment': {
+      throw new Error('Cliente não possui parcelas em aberto');
+    }
     case 'get_waiting_orders': {
       // Retorna vendas com status WAITING (cozinha/balcão)
       return (db.heldSales ?? []).filter((s) => s.status === 'WAITING') as T;
@@ -1058,16 +1253,36 @@ const webMockInvoke = async <T>(command: string, args?: Record<string, unknown>)
       return ['COM1', 'COM2', '/dev/ttyUSB0'] as unknown as T;
     }
     case 'configure_printer':
+    case 'configure_label_printer':
+    case 'test_label_printer':
     case 'configure_scale':
+    case 'start_scale_stream':
+    case 'stop_scale_stream':
+    case 'set_scale_unit_price':
+    case 'stop_demo_hardware':
+    case 'set_demo_scale_weight':
     case 'test_printer':
     case 'print_test_documents':
+    case 'print_customer_statement':
+    case 'print_store_credit':
+    case 'print_cash_closing_report':
     case 'start_serial_scanner':
     case 'stop_serial_scanner': {
       return undefined as T;
     }
+    case 'print_product_labels': {
+      return ((args?.productIds as string[] | undefined) ?? []).length as unknown as T;
+    }
+    case 'print_price_change_labels': {
+      return 0 as unknown as T;
+    }
     case 'read_weight': {
       return { weight: 0, stable: true } as unknown as T;
     }
+    case 'parse_barcode': {
+      const code = (args?.code as string | undefined) ?? '';
+      return { code, format: 'UNKNOWN', productCode: null, gs1: null } as unknown as T;
+    }
     case 'generate_qr_svg': {
       return '<svg></svg>' as unknown as T;
     }
@@ -1368,6 +1583,11 @@ export async function getProductByBarcode(barcode: string): Promise<Product | nu
   return tauriInvoke<Product | null>('get_product_by_barcode', { barcode });
 }
 
+/** Produto do código lido no PDV, com a quantidade de códigos pesados */
+export async function scanProductBarcode(barcode: string): Promise<ScannedProduct | null> {
+  return tauriInvoke<ScannedProduct | null>('scan_product_barcode', { barcode });
+}
+
 export async function searchProducts(query: string): Promise<Product[]> {
   return tauriInvoke<Product[]>('search_products', { query });
 }
@@ -1562,6 +1782,32 @@ export async function getCashSessionMovements(sessionId: string): Promise<CashMo
   return tauriInvoke<CashMovement[]>('get_session_movements', { sessionId });
 }
 
+/**
+ * Fechamento cego: o operador informa a contagem sem ver o esperado.
+ * Diferença acima da tolerância deixa a sessão em PENDING_APPROVAL.
+ */
+export async function closeCashSessionBlind(input: BlindCashClosingInput): Promise<CashSession> {
+  return tauriInvoke<CashSession>('close_cash_session_blind', {
+    input: { ...input, notes: input.notes ?? null },
+  });
+}
+
+export async function approveCashClosing(id: string, notes?: string): Promise<CashSession> {
+  return tauriInvoke<CashSession>('approve_cash_closing', { id, notes: notes ?? null });
+}
+
+export async function getPendingCashClosings(): Promise<CashSession[]> {
+  return tauriInvoke<CashSession[]>('get_pending_cash_closings');
+}
+
+export async function getCashClosingReport(sessionId: string): Promise<CashClosingReport> {
+  return tauriInvoke<CashClosingReport>('get_cash_closing_report', { sessionId });
+}
+
+export async function printCashClosingReport(sessionId: string): Promise<void> {
+  return tauriInvoke<void>('print_cash_closing_report', { sessionId });
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // EMPLOYEES
 // ────────────────────────────────────────────────────────────────────────────
@@ -1807,6 +2053,20 @@ export async function printReceipt(saleId: string): Promise<TauriResponse<void>>
   return tauriInvoke<TauriResponse<void>>('print_sale_by_id', { saleId });
 }
 
+/** Fila de impressão: trabalhos mais recentes, opcionalmente filtrados por status */
+export async function listPrintJobs(status?: PrintJobStatus, limit?: number): Promise<PrintJob[]> {
+  return tauriInvoke<PrintJob[]>('list_print_jobs', { status, limit });
+}
+
+/** Falhos/cancelados voltam para a fila; impressos geram uma nova cópia */
+export async function reprintPrintJob(id: string): Promise<PrintJob> {
+  return tauriInvoke<PrintJob>('reprint_print_job', { id });
+}
+
+export async function cancelPrintJob(id: string): Promise<PrintJob> {
+  return tauriInvoke<PrintJob>('cancel_print_job', { id });
+}
+
 export async function openCashDrawer(): Promise<TauriResponse<void>> {
   return tauriInvoke<TauriResponse<void>>('open_cash_drawer');
 }
@@ -1815,10 +2075,127 @@ export async function readScaleWeight(): Promise<TauriResponse<number>> {
   return tauriInvoke<TauriResponse<number>>('read_scale_weight');
 }
 
+/** Leitura contínua: os pesos chegam no evento `hardware:event` */
+export async function startScaleStream(): Promise<void> {
+  return tauriInvoke<void>('start_scale_stream');
+}
+
+export async function stopScaleStream(): Promise<void> {
+  return tauriInvoke<void>('stop_scale_stream');
+}
+
+/** Preço por kg para a balança computadora calcular o total */
+export async function setScaleUnitPrice(unitPrice: number): Promise<void> {
+  return tauriInvoke<void>('set_scale_unit_price', { unitPrice });
+}
+
+/** Layouts de código pesado (EAN-13 iniciado em 2) da loja */
+export async function getWeightedBarcodeConfig(): Promise<WeightedBarcodeConfig> {
+  return tauriInvoke<WeightedBarcodeConfig>('get_weighted_barcode_config');
+}
+
+export async function saveWeightedBarcodeConfig(
+  config: WeightedBarcodeConfig
+): Promise<WeightedBarcodeConfig> {
+  return tauriInvoke<WeightedBarcodeConfig>('save_weighted_barcode_config', { config });
+}
+
+export async function getScaleCatalogConfig(): Promise<ScaleCatalogConfig> {
+  return tauriInvoke<ScaleCatalogConfig>('get_scale_catalog_config');
+}
+
+export async function saveScaleCatalogConfig(
+  config: ScaleCatalogConfig
+): Promise<ScaleCatalogConfig> {
+  return tauriInvoke<ScaleCatalogConfig>('save_scale_catalog_config', { config });
+}
+
+/** Envia os produtos por kg (PLU, descrição, preço/kg) às balanças de balcão */
+export async function exportScaleCatalog(): Promise<ScaleCatalogExport> {
+  return tauriInvoke<ScaleCatalogExport>('export_scale_catalog');
+}
+
+/** Liga balança e impressora simuladas (demonstração sem equipamentos) */
+export async function startDemoHardware(): Promise<DemoHardwareInfo> {
+  return tauriInvoke<DemoHardwareInfo>('start_demo_hardware');
+}
+
+export async function stopDemoHardware(): Promise<void> {
+  return tauriInvoke<void>('stop_demo_hardware');
+}
+
+export async function getDemoHardware(): Promise<DemoHardwareInfo | null> {
+  return tauriInvoke<DemoHardwareInfo | null>('get_demo_hardware');
+}
+
+export async function setDemoScaleWeight(weightGrams: number): Promise<void> {
+  return tauriInvoke<void>('set_demo_scale_weight', { weightGrams });
+}
+
 export async function testPrinterConnection(): Promise<TauriResponse<boolean>> {
   return tauriInvoke<TauriResponse<boolean>>('test_printer_connection');
 }
 
+export async function configureLabelPrinter(config: LabelPrinterConfig): Promise<void> {
+  return tauriInvoke<void>('configure_label_printer', { config });
+}
+
+export async function getLabelPrinterConfig(): Promise<LabelPrinterConfig> {
+  return tauriInvoke<LabelPrinterConfig>('get_label_printer_config');
+}
+
+export async function testLabelPrinter(): Promise<void> {
+  return tauriInvoke<void>('test_label_printer');
+}
+
+/** Etiquetas de gôndola dos produtos; retorna quantos foram impressos */
+export async function printProductLabels(productIds: string[], copies = 1): Promise<number> {
+  return tauriInvoke<number>('print_product_labels', { productIds, copies });
+}
+
+/** Etiquetas dos produtos com preço alterado desde `since` (AAAA-MM-DD) */
+export async function printPriceChangeLabels(since: string, copies = 1): Promise<number> {
+  return tauriInvoke<number>('print_price_change_labels', { since, copies });
+}
+
+/** Papel, tampa, erro e gaveta lidos da impressora (serial/rede) */
+export async function getPrinterSensors(): Promise<PrinterSensorStatus> {
+  return tauriInvoke<PrinterSensorStatus>('get_printer_sensors');
+}
+
+/** Logo dos cupons (PNG em base64, aceita data URL); `null` remove */
+export async function setPrinterLogo(logo: string | null): Promise<PrinterLogoInfo | null> {
+  return tauriInvoke<PrinterLogoInfo | null>('set_printer_logo', { logo });
+}
+
+/** Formato, código do produto e dados GS1 (lote/validade) de um código lido */
+export async function parseBarcode(code: string): Promise<ParsedBarcode> {
+  return tauriInvoke<ParsedBarcode>('parse_barcode', { code });
+}
+
+/** Modelo de impressão do documento (personalizado ou o padrão embutido) */
+export async function getReceiptTemplate(
+  document: TemplateDocument
+): Promise<ReceiptTemplateSetting> {
+  return tauriInvoke<ReceiptTemplateSetting>('get_receipt_template', { document });
+}
+
+/** Grava o modelo; `null` volta ao layout padrão */
+export async function saveReceiptTemplate(
+  document: TemplateDocument,
+  template: ReceiptTemplate | null
+): Promise<ReceiptTemplateSetting> {
+  return tauriInvoke<ReceiptTemplateSetting>('save_receipt_template', { document, template });
+}
+
+/** Linhas de texto do modelo renderizado com dados de exemplo */
+export async function previewReceiptTemplate(
+  document: TemplateDocument,
+  template: ReceiptTemplate
+): Promise<string[]> {
+  return tauriInvoke<string[]>('preview_receipt_template', { document, template });
+}
+
 export async function testScaleConnection(): Promise<TauriResponse<boolean>> {
   return tauriInvoke<TauriResponse<boolean>>('test_scale_connection');
 }
@@ -2042,6 +2419,7 @@ export type PriceHistory = {
   productId: string;
   oldPrice: number;
   newPrice: number;
+  priceType?: 'SALE' | 'COST';
   reason?: string;
   employeeId?: string;
   createdAt: string;
@@ -2064,6 +2442,173 @@ export async function getPriceHistoryById(id: string): Promise<PriceHistory | nu
   return tauriInvoke<PriceHistory | null>('get_price_history_by_id', { id });
 }
 
+// ────────────────────────────────────────────────────────────────────────────
+// PROMOÇÕES
+// ────────────────────────────────────────────────────────────────────────────
+
+export async function getPricingRules(): Promise<PricingRule[]> {
+  return tauriInvoke<PricingRule[]>('get_pricing_rules');
+}
+
+export async function savePricingRule(input: SavePricingRule): Promise<PricingRule> {
+  return tauriInvoke<PricingRule>('save_pricing_rule', { input });
+}
+
+export async function deletePricingRule(id: string): Promise<void> {
+  return tauriInvoke<void>('delete_pricing_rule', { id });
+}
+
+/** Promoções que serão aplicadas ao carrinho ao finalizar a venda */
+export async function previewSalePricing(
+  items: CreateSaleInput['items'],
+  customerId?: string | null
+): Promise<SalePricing> {
+  return tauriInvoke<SalePricing>('preview_sale_pricing', {
+    items,
+    customerId: customerId ?? null,
+  });
+}
+
+export async function getCustomerPriceGroup(customerId: string): Promise<string | null> {
+  return tauriInvoke<string | null>('get_customer_price_group', { customerId });
+}
+
+export async function setCustomerPriceGroup(
+  customerId: string,
+  priceGroup: string | null
+): Promise<void> {
+  return tauriInvoke<void>('set_customer_price_group', { customerId, priceGroup });
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// DEVOLUÇÕES E TROCAS
+// ────────────────────────────────────────────────────────────────────────────
+
+export async function getReturnableSaleItems(saleId: string): Promise<ReturnableSaleItem[]> {
+  return tauriInvoke<ReturnableSaleItem[]>('get_returnable_sale_items', { saleId });
+}
+
+export async function getSaleReturns(saleId: string): Promise<SaleReturn[]> {
+  return tauriInvoke<SaleReturn[]>('get_sale_returns', { saleId });
+}
+
+/** Devolução parcial ou troca; o estorno cai no caixa aberto do operador */
+export async function createSaleReturn(input: CreateSaleReturnInput): Promise<SaleReturn> {
+  return tauriInvoke<SaleReturn>('create_sale_return', {
+    input: {
+      saleId: input.saleId,
+      items: input.items.map((item) => ({
+        saleItemId: item.saleItemId,
+        quantity: item.quantity,
+        lotId: item.lotId ?? null,
+        restock: item.restock ?? null,
+      })),
+      refundType: input.refundType,
+      refundMethod: input.refundMethod ?? null,
+      reason: input.reason ?? null,
+      // Operador e caixa são definidos pelo backend a partir da sessão
+      employeeId: '',
+      cashSessionId: null,
+      exchange: input.exchange ?? null,
+    },
+  });
+}
+
+export async function getStoreCredit(id: string): Promise<StoreCredit | null> {
+  return tauriInvoke<StoreCredit | null>('get_store_credit', { id });
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// VALE-CRÉDITO E VALE-PRESENTE
+// ────────────────────────────────────────────────────────────────────────────
+
+/** Consulta saldo e validade pelo código lido no vale */
+export async function getStoreCreditByCode(code: string): Promise<StoreCredit | null> {
+  return tauriInvoke<StoreCredit | null>('get_store_credit_by_code', { code });
+}
+
+export async function getCustomerStoreCredits(customerId: string): Promise<StoreCredit[]> {
+  return tauriInvoke<StoreCredit[]>('get_customer_store_credits', { customerId });
+}
+
+export async function getStoreCreditTransactions(
+  storeCreditId: string
+): Promise<StoreCreditTransaction[]> {
+  return tauriInvoke<StoreCreditTransaction[]>('get_store_credit_transactions', {
+    storeCreditId,
+  });
+}
+
+/** Venda de vale-presente; o valor entra no caixa aberto do operador */
+export async function issueGiftCard(input: IssueGiftCardInput): Promise<StoreCredit> {
+  return tauriInvoke<StoreCredit>('issue_gift_card', {
+    input: {
+      amount: input.amount,
+      paymentMethod: input.paymentMethod,
+      customerId: input.customerId ?? null,
+      expiresAt: input.expiresAt ?? null,
+      // Operador e caixa são definidos pelo backend a partir da sessão
+      employeeId: '',
+      cashSessionId: null,
+    },
+  });
+}
+
+export async function setStoreCreditActive(id: string, isActive: boolean): Promise<StoreCredit> {
+  return tauriInvoke<StoreCredit>('set_store_credit_active', { id, isActive });
+}
+
+export async function printStoreCredit(id: string): Promise<void> {
+  return tauriInvoke<void>('print_store_credit', { id });
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// CREDIÁRIO (FIADO)
+// ────────────────────────────────────────────────────────────────────────────
+
+export async function getCreditAccount(customerId: string): Promise<CustomerCreditAccount | null> {
+  return tauriInvoke<CustomerCreditAccount | null>('get_credit_account', { customerId });
+}
+
+export async function saveCreditAccount(
+  input: SaveCreditAccountInput
+): Promise<CustomerCreditAccount> {
+  return tauriInvoke<CustomerCreditAccount>('save_credit_account', {
+    input: { ...input, notes: input.notes ?? null },
+  });
+}
+
+/** Parcelas em aberto com multa e juros calculados para hoje */
+export async function getCustomerStatement(customerId: string): Promise<CustomerStatement> {
+  return tauriInvoke<CustomerStatement>('get_customer_statement', { customerId });
+}
+
+export async function getOverdueReceivables(): Promise<Receivable[]> {
+  return tauriInvoke<Receivable[]>('get_overdue_receivables');
+}
+
+/** Recebimento no caixa aberto do operador; quita as parcelas mais antigas primeiro */
+export async function receiveAccountPayment(
+  customerId: string,
+  amount: number,
+  method: PaymentMethod
+): Promise<ReceivablePayment[]> {
+  return tauriInvoke<ReceivablePayment[]>('receive_account_payment', {
+    input: {
+      customerId,
+      amount,
+      method,
+      // Operador e caixa são definidos pelo backend a partir da sessão
+      employeeId: '',
+      cashSessionId: null,
+    },
+  });
+}
+
+export async function printCustomerStatement(customerId: string): Promise<void> {
+  return tauriInvoke<void>('print_customer_statement', { customerId });
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // NFC-e
 // ────────────────────────────────────────────────────────────────────────────
@@ -2076,6 +2621,14 @@ export async function checkSefazStatus(uf: string, environment: number): Promise
   return tauriInvoke<StatusResponse>('check_sefaz_status', { uf, environment });
 }
 
+export async function reprintDanfe(
+  accessKey: string,
+  format: 'ESCPOS' | 'PDF',
+  pdfPath?: string
+): Promise<DanfeReprintResponse> {
+  return tauriInvoke<DanfeReprintResponse>('reprint_danfe', { accessKey, format, pdfPath });
+}
+
 export async function listOfflineNotes(): Promise<OfflineNote[]> {
   return tauriInvoke<OfflineNote[]>('list_offline_notes');
 }
@@ -1,5 +1,6 @@
 'use client';
 
+import { BlindCloseCashDialog } from '@/components/cash/BlindCloseCashDialog';
 import { Button } from '@/components/ui/button';
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '@/components/ui/card';
 import {
@@ -14,13 +15,17 @@ import { Input } from '@/components/ui/input';
 import { Label } from '@/components/ui/label';
 import { useToast } from '@/hooks/use-toast';
 import {
+  useApproveCashClosing,
+  useCashClosingSettings,
   useCashMovements,
   useCashSessionSummary,
   useCloseCashSession,
   useCurrentCashSession,
   useOpenCashSession,
+  usePendingCashClosings,
 } from '@/hooks/usePDV';
 import { useCashMovement } from '@/hooks/useSales';
+import { printCashClosingReport } from '@/lib/tauri';
 import { cn, formatCurrency, getErrorMessage } from '@/lib/utils';
 
 import { useAuthStore } from '@/stores/auth-store';
@@ -60,6 +65,8 @@ export const CashControlPage: FC = () => {
   const navigate = useNavigate();
 
   const addMovementMutation = useCashMovement();
+  const { data: closingSettings } = useCashClosingSettings();
+  const approveClosing = useApproveCashClosing();
 
   // Keyboard Shortcuts
   useKeyboard([
@@ -108,6 +115,12 @@ export const CashControlPage: FC = () => {
     closeCashSession: clearCashStore,
   } = useAuthStore();
 
+  // No fechamento cego só quem aprova o fechamento vê o valor esperado
+  const isBlindClosing = closingSettings?.blindClosing ?? false;
+  const canApproveClosing = hasPermission('cash.approve_closing');
+  const showExpected = !isBlindClosing || canApproveClosing;
+  const { data: pendingClosings } = usePendingCashClosings(canApproveClosing);
+
   // Sincronizar estado do caixa com a store global quando carregar do backend
   useEffect(() => {
     // Se o backend retorna sessão, verificar se precisa sincronizar
@@ -203,6 +216,33 @@ export const CashControlPage: FC = () => {
     }
   };
 
+  const handlePrintSummary = async () => {
+    if (!sessionData?.id) return;
+    try {
+      await printCashClosingReport(sessionData.id);
+      toast({ title: 'Leitura X enviada para impressão' });
+    } catch (error) {
+      toast({
+        title: 'Erro ao imprimir resumo',
+        description: getErrorMessage(error),
+        variant: 'destructive',
+      });
+    }
+  };
+
+  const handleApproveClosing = async (id: string) => {
+    try {
+      await approveClosing.mutateAsync({ id });
+      toast({ title: 'Fechamento aprovado' });
+    } catch (error) {
+      toast({
+        title: 'Erro ao aprovar fechamento',
+        description: getErrorMessage(error),
+        variant: 'destructive',
+      });
+    }
+  };
+
   const handleCloseSession = async () => {
     const value = parseFloat(closingBalance.replace(',', '.'));
     if (isNaN(value) || value < 0) {
@@ -296,7 +336,7 @@ export const CashControlPage: FC = () => {
             </Button>
           ) : (
             <>
-              <Button variant="outline">
+              <Button variant="outline" onClick={handlePrintSummary} disabled={!showExpected}>
                 <Printer className="mr-2 h-4 w-4" />
                 Imprimir Resumo
               </Button>
@@ -357,9 +397,11 @@ export const CashControlPage: FC = () => {
                 <p className="text-sm text-green-600">
                   Operador: {session?.employee?.name || 'Não identificado'}
                 </p>
-                <p className="text-sm text-muted-foreground mt-1" data-testid="cash-balance">
-                  {formatCurrency(summary.expectedBalance)}
-                </p>
+                {showExpected && (
+                  <p className="text-sm text-muted-foreground mt-1" data-testid="cash-balance">
+                    {formatCurrency(summary.expectedBalance)}
+                  </p>
+                )}
               </div>
             </>
           ) : (
@@ -374,6 +416,51 @@ export const CashControlPage: FC = () => {
         </CardContent>
       </Card>
 
+      {/* Fechamentos aguardando aprovação */}
+      {canApproveClosing && pendingClosings && pendingClosings.length > 0 && (
+        <Card className="border-orange-500 bg-orange-50/50">
+          <CardHeader>
+            <CardTitle>Fechamentos aguardando aprovação</CardTitle>
+            <CardDescription>Diferença de contagem acima da tolerância</CardDescription>
+          </CardHeader>
+          <CardContent className="space-y-2">
+            {pendingClosings.map((pending) => (
+              <div
+                key={pending.id}
+                className="flex items-center justify-between rounded-lg border bg-background p-3"
+              >
+                <div>
+                  <p className="font-medium">{pending.employee?.name || 'Operador'}</p>
+                  <p className="text-sm text-muted-foreground">
+                    Fechado em{' '}
+                    {pending.closedAt
+                      ? format(new Date(pending.closedAt), 'dd/MM/yy HH:mm')
+                      : '-'}
+                    {' · '}Diferença em dinheiro: {formatCurrency(pending.difference ?? 0)}
+                  </p>
+                </div>
+                <div className="flex gap-2">
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    onClick={() => printCashClosingReport(pending.id).catch(() => undefined)}
+                  >
+                    <Printer className="h-4 w-4" />
+                  </Button>
+                  <Button
+                    size="sm"
+                    onClick={() => handleApproveClosing(pending.id)}
+                    disabled={approveClosing.isPending || pending.employeeId === employee?.id}
+                  >
+                    Aprovar
+                  </Button>
+                </div>
+              </div>
+            ))}
+          </CardContent>
+        </Card>
+      )}
+
       {/* Movimentações */}
       {isOpen && (
         <Card className="border-none bg-card/50 backdrop-blur-sm shadow-md">
@@ -444,7 +531,9 @@ export const CashControlPage: FC = () => {
                 <ArrowUpRight className="h-4 w-4 text-green-600" />
               </CardHeader>
               <CardContent>
-                <div className="text-2xl font-bold">{formatCurrency(summary.sales)}</div>
+                <div className="text-2xl font-bold">
+                  {showExpected ? formatCurrency(summary.sales) : '—'}
+                </div>
                 <p className="text-xs text-muted-foreground">total do período</p>
               </CardContent>
             </Card>
@@ -475,18 +564,20 @@ export const CashControlPage: FC = () => {
               </CardContent>
             </Card>
 
-            <Card>
-              <CardHeader className="flex flex-row items-center justify-between pb-2">
-                <CardTitle className="text-sm font-medium">Saldo Esperado</CardTitle>
-                <Calculator className="h-4 w-4 text-blue-600" />
-              </CardHeader>
-              <CardContent>
-                <div className="text-2xl font-bold text-blue-600" data-testid="expected-balance">
-                  {formatCurrency(summary.expectedBalance)}
-                </div>
-                <p className="text-xs text-muted-foreground">em dinheiro no caixa</p>
-              </CardContent>
-            </Card>
+            {showExpected && (
+              <Card>
+                <CardHeader className="flex flex-row items-center justify-between pb-2">
+                  <CardTitle className="text-sm font-medium">Saldo Esperado</CardTitle>
+                  <Calculator className="h-4 w-4 text-blue-600" />
+                </CardHeader>
+                <CardContent>
+                  <div className="text-2xl font-bold text-blue-600" data-testid="expected-balance">
+                    {formatCurrency(summary.expectedBalance)}
+                  </div>
+                  <p className="text-xs text-muted-foreground">em dinheiro no caixa</p>
+                </CardContent>
+              </Card>
+            )}
           </div>
 
           {/* Formas de Pagamento */}
@@ -654,7 +745,10 @@ export const CashControlPage: FC = () => {
       </Dialog>
 
       {/* Dialog Fechar Caixa */}
-      <Dialog open={isCloseDialogOpen} onOpenChange={setIsCloseDialogOpen}>
+      {isBlindClosing && (
+        <BlindCloseCashDialog open={isCloseDialogOpen} onOpenChange={setIsCloseDialogOpen} />
+      )}
+      <Dialog open={!isBlindClosing && isCloseDialogOpen} onOpenChange={setIsCloseDialogOpen}>
         <DialogContent>
           <DialogHeader>
             <DialogTitle>Fechar Caixa</DialogTitle>
@@ -39,6 +39,10 @@ vi.mock('@/hooks/usePDV', () => {
     useOpenCashSession: () => mockUseOpenCashSession,
     useCloseCashSession: () => mockUseCloseCashSession,
     useCashSessionSummary: () => mockUseCashSessionSummary,
+    useCashClosingSettings: () => ({ data: { blindClosing: false } }),
+    usePendingCashClosings: () => ({ data: [] }),
+    useApproveCashClosing: () => ({ mutateAsync: vi.fn(), isPending: false }),
+    useBlindCloseCashSession: () => ({ mutateAsync: vi.fn(), isPending: false }),
   };
 });
 
@@ -51,6 +55,7 @@ vi.mock('@/hooks/useSales', () => ({
 vi.mock('@/lib/tauri', () => ({
   invoke: mockInvoke,
   addCashMovement: vi.fn((args) => mockInvoke('add_cash_movement', { input: args })),
+  printCashClosingReport: vi.fn(),
 }));
 
 // Mock Auth Store
@@ -46,6 +46,7 @@ import { LabeledShortcut, pdvShortcuts } from '@/components/pdv/KeyboardShortcut
 import { HelpCircle } from 'lucide-react';
 import { commands } from '@/lib/bindings';
 import { usePDVKeyboard } from '@/hooks/use-keyboard';
+import { useCartPromotions } from '@/hooks/use-cart-promotions';
 
 export const PDVPage: FC = () => {
   const navigate = useNavigate();
@@ -89,6 +90,9 @@ export const PDVPage: FC = () => {
   const { getCustomerById } = useCustomers();
   const [selectedCustomer, setSelectedCustomer] = useState<Customer | null>(null);
 
+  // Promoções automáticas (leve X pague Y, combos, happy hour...)
+  useCartPromotions();
+
   // Verificar se é atendente (não pode finalizar pagamento)
   const isAttendant = employee?.role === 'ATTENDANT';
 
@@ -293,12 +297,12 @@ export const PDVPage: FC = () => {
   }, []);
 
   const handleProductSelected = useCallback(
-    (product: Product) => {
+    (product: Product, quantity?: number) => {
       addItem({
         productId: product.id,
         productName: product.name,
         barcode: product.barcode,
-        quantity: 1,
+        quantity: quantity ?? 1,
         unitPrice: product.salePrice,
         unit: product.unit,
         isWeighted: product.isWeighted,
@@ -307,7 +311,8 @@ export const PDVPage: FC = () => {
       // Limpar busca completamente
       handleCloseSearch();
 
-      if (product.isWeighted) {
+      // Código pesado já traz o peso; senão pede a quantidade
+      if (product.isWeighted && quantity === undefined) {
         // Como o addItem no store gera seu próprio ID e é síncrono (zustand),
         // precisamos encontrar o item recém adicionado.
         setTimeout(() => {
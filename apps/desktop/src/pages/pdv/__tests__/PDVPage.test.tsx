@@ -52,6 +52,9 @@ vi.mock('@/hooks/useCustomers', () => ({
     searchCustomers: vi.fn(async () => []),
   })),
 }));
+vi.mock('@/hooks/use-cart-promotions', () => ({
+  useCartPromotions: vi.fn(),
+}));
 
 // Mock sub-components (necessários para testes de integração)
 vi.mock('@/components/pdv/CartItemRow', () => ({
@@ -18,11 +18,12 @@ import {
 import { useProductSearch, useSuppliers } from '@/hooks';
 import { useAddStockEntry } from '@/hooks/useStock';
 import { useToast } from '@/hooks/use-toast';
+import { parseBarcode } from '@/lib/tauri';
 import { formatCurrency, formatUserError } from '@/lib/utils';
 import type { Product } from '@/types';
 import { zodResolver } from '@hookform/resolvers/zod';
 import { ArrowLeft, PackagePlus, Save, Search, Truck } from 'lucide-react';
-import { useState, type FC } from 'react';
+import { useState, type FC, type KeyboardEvent } from 'react';
 import { useForm } from 'react-hook-form';
 import { useNavigate } from 'react-router-dom';
 import { z } from 'zod';
@@ -78,6 +79,22 @@ export const StockEntryPage: FC = () => {
     setValue('costPrice', product.costPrice);
   };
 
+  // Etiquetas GS1-128/DataMatrix trazem lote e validade junto do GTIN
+  const handleSearchKeyDown = async (e: KeyboardEvent<HTMLInputElement>) => {
+    if (e.key !== 'Enter' || !searchQuery.trim()) return;
+    e.preventDefault();
+    try {
+      const parsed = await parseBarcode(searchQuery);
+      if (parsed.gs1?.lot) setValue('lotNumber', parsed.gs1.lot);
+      if (parsed.gs1?.expirationDate) {
+        setValue('expirationDate', new Date(`${parsed.gs1.expirationDate}T00:00:00`));
+      }
+      setSearchQuery(parsed.productCode ?? parsed.code);
+    } catch {
+      // Mantém o texto digitado como busca comum
+    }
+  };
+
   const onSubmit = async (data: EntryFormData) => {
     if (!selectedProduct) {
       toast({
@@ -149,6 +166,7 @@ export const StockEntryPage: FC = () => {
                 placeholder="Buscar por nome ou código..."
                 value={searchQuery}
                 onChange={(e) => setSearchQuery(e.target.value)}
+                onKeyDown={handleSearchKeyDown}
                 data-tutorial="stock-entry-product"
                 aria-label="Buscar produto por nome ou código"
                 aria-expanded={searchQuery && searchResults.length > 0 ? 'true' : 'false'}
@@ -32,6 +32,7 @@ export const PERMISSIONS = {
   // Caixa
   'cash.open': ['ADMIN', 'MANAGER', 'CASHIER'],
   'cash.close': ['ADMIN', 'MANAGER', 'CASHIER'],
+  'cash.approve_closing': ['ADMIN', 'MANAGER'],
 
   // Clientes
   'customers.manage': ['ADMIN', 'MANAGER', 'CASHIER', 'ATTENDANT'],
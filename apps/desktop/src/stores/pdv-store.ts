@@ -1,6 +1,6 @@
 import { create } from 'zustand';
 import { getHeldSales, saveHeldSale, deleteHeldSale } from '@/lib/tauri';
-import type { HeldSale, PaymentMethod, CashSession, HeldSaleItem } from '@/types';
+import type { HeldSale, PaymentMethod, CashSession, HeldSaleItem, SalePricing } from '@/types';
 export type { PaymentMethod };
 
 /**
@@ -38,6 +38,8 @@ export interface CartItem {
   discount: number;
   unit: string;
   isWeighted: boolean;
+  promotionDiscount?: number; // Desconto automático calculado pelas regras de preço
+  promotionLabel?: string; // Nome da promoção aplicada
 }
 
 interface PDVState {
@@ -69,6 +71,7 @@ interface PDVState {
   updateItemQuantity: (itemId: string, quantity: number) => void;
   updateItemDiscount: (itemId: string, discount: number) => void;
   setDiscount: (discount: number, reason?: string) => void;
+  applyPromotions: (pricing: SalePricing) => void;
   clearCart: () => void;
 
   // Ações de sessão
@@ -185,6 +188,20 @@ export const usePDVStore = create<PDVState>()((set, get) => ({
     set({ discount: Math.max(0, discount), discountReason: reason });
   },
 
+  // Promoções devolvidas pelo backend (índice = posição do item no carrinho)
+  applyPromotions: (pricing) => {
+    set((state) => ({
+      items: state.items.map((item, index) => {
+        const promotion = pricing.items.find((p) => p.itemIndex === index);
+        return {
+          ...item,
+          promotionDiscount: promotion?.discount ?? 0,
+          promotionLabel: promotion?.reason,
+        };
+      }),
+    }));
+  },
+
   clearCart: () => {
     set({
       items: [],
@@ -335,7 +352,8 @@ export const usePDVStore = create<PDVState>()((set, get) => ({
   getSubtotal: () => {
     const { items } = get();
     return items.reduce((sum, item) => {
-      const itemTotal = item.quantity * item.unitPrice - item.discount;
+      const itemTotal =
+        item.quantity * item.unitPrice - item.discount - (item.promotionDiscount ?? 0);
       return sum + Math.max(0, itemTotal);
     }, 0);
   },
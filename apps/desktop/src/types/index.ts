@@ -42,11 +42,20 @@ export type EmployeeRole =
   | 'WAREHOUSE' // Almoxarife/Estoquista industrial
   | 'REQUESTER'; // Requisitante comum
 
-export type PaymentMethod = 'CASH' | 'PIX' | 'CREDIT' | 'DEBIT' | 'VOUCHER' | 'OTHER';
+export type PaymentMethod =
+  | 'CASH'
+  | 'PIX'
+  | 'CREDIT'
+  | 'DEBIT'
+  | 'VOUCHER'
+  | 'OTHER'
+  | 'ACCOUNT'
+  | 'STORE_CREDIT'
+  | 'EXCHANGE_CREDIT';
 
 export type SaleStatus = 'COMPLETED' | 'CANCELED';
 
-export type CashSessionStatus = 'OPEN' | 'CLOSED' | 'FORCED';
+export type CashSessionStatus = 'OPEN' | 'PENDING_APPROVAL' | 'CLOSED' | 'FORCED';
 
 export type CashMovementType =
   | 'OPENING'
@@ -211,9 +220,21 @@ export interface SalePayment {
   saleId: string;
   method: PaymentMethod;
   amount: number;
+  cardIntegration?: number; // 1=TEF, 2=POS
+  acquirerCnpj?: string;
+  cardBrand?: string;
+  authorizationCode?: string;
   createdAt: string;
 }
 
+/** Dados do cartão informados pelo TEF/POS */
+export interface SalePaymentCard {
+  cardIntegration?: number; // 1=TEF, 2=POS
+  acquirerCnpj?: string;
+  cardBrand?: string; // tBand
+  authorizationCode?: string; // cAut
+}
+
 export interface SaleItem {
   id: string;
   saleId: string;
@@ -225,7 +246,255 @@ export interface SaleItem {
   total: number;
   lotId?: string;
   lot?: ProductLot;
+  pricingRuleId?: string; // Promoção aplicada ao item
+  discountReason?: string; // Motivo impresso no cupom
+  createdAt: string;
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// PROMOÇÕES
+// ────────────────────────────────────────────────────────────────────────────
+
+export type PricingRuleKind =
+  | 'BUY_X_PAY_Y'
+  | 'NTH_ITEM_PERCENT'
+  | 'COMBO_PRICE'
+  | 'HAPPY_HOUR'
+  | 'CATEGORY_PERCENT'
+  | 'CUSTOMER_GROUP_PRICE'
+  | 'WHOLESALE';
+
+export interface PricingRuleItem {
+  productId: string;
+  quantity: number;
+}
+
+export interface PricingRule {
+  id: string;
+  name: string;
+  kind: PricingRuleKind;
+  productId?: string;
+  categoryId?: string;
+  customerGroup?: string;
+  quantity: number; // X do "leve X", N do "N-ésimo item" ou mínimo do atacado
+  payQuantity: number; // Y do "pague Y"
+  percent: number;
+  price?: number; // Preço do combo ou preço unitário especial
+  startTime?: string; // HH:MM
+  endTime?: string;
+  weekdays?: string; // "0,6" (0 = domingo)
+  validFrom?: string; // YYYY-MM-DD
+  validUntil?: string;
+  priority: number;
+  isActive: boolean;
+  items: PricingRuleItem[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface SavePricingRule {
+  id?: string;
+  name: string;
+  kind: PricingRuleKind;
+  productId?: string;
+  categoryId?: string;
+  customerGroup?: string;
+  quantity?: number;
+  payQuantity?: number;
+  percent?: number;
+  price?: number;
+  startTime?: string;
+  endTime?: string;
+  weekdays?: string;
+  validFrom?: string;
+  validUntil?: string;
+  priority?: number;
+  items?: PricingRuleItem[];
+}
+
+/** Promoção aplicada a um item do carrinho (índice na lista enviada) */
+export interface ItemPromotion {
+  itemIndex: number;
+  ruleId: string;
+  reason: string;
+  discount: number;
+}
+
+export interface SalePricing {
+  items: ItemPromotion[];
+  totalDiscount: number;
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// DEVOLUÇÕES E TROCAS
+// ────────────────────────────────────────────────────────────────────────────
+
+export type RefundType = 'PAYMENT' | 'STORE_CREDIT';
+
+/** Item da venda com o saldo que ainda pode ser devolvido */
+export interface ReturnableSaleItem {
+  saleItemId: string;
+  productId: string;
+  productName: string;
+  lotId?: string;
+  soldQuantity: number;
+  returnedQuantity: number;
+  unitPrice: number; // Líquido dos descontos da venda
+}
+
+export interface SaleReturnItem {
+  id: string;
+  returnId: string;
+  saleItemId: string;
+  productId: string;
+  productName: string;
+  lotId?: string;
+  quantity: number;
+  unitPrice: number;
+  total: number;
+  restocked: boolean;
+  createdAt: string;
+}
+
+export interface SaleReturn {
+  id: string;
+  saleId: string;
+  customerId?: string;
+  employeeId: string;
+  cashSessionId?: string;
+  total: number;
+  refundType: RefundType;
+  refundMethod?: PaymentMethod;
+  refundAmount: number; // Total menos o abatido na troca
+  exchangeSaleId?: string;
+  storeCreditId?: string;
+  reason?: string;
+  fiscalAccessKey?: string;
   createdAt: string;
+  items: SaleReturnItem[];
+}
+
+export interface CreateSaleReturnInput {
+  saleId: string;
+  items: {
+    saleItemId: string;
+    quantity: number;
+    lotId?: string;
+    restock?: boolean; // false = avariado, não volta ao estoque
+  }[];
+  refundType: RefundType;
+  refundMethod?: PaymentMethod;
+  reason?: string;
+  /** Troca: pagamentos cobrem só a diferença após abater a devolução */
+  exchange?: CreateSaleInput;
+}
+
+export type StoreCreditKind = 'STORE_CREDIT' | 'GIFT_CARD';
+
+export interface StoreCredit {
+  id: string;
+  code: string; // EAN-13 (prefixo 980)
+  kind: StoreCreditKind;
+  customerId?: string;
+  returnId?: string;
+  amount: number;
+  balance: number;
+  isActive: boolean;
+  expiresAt?: string; // YYYY-MM-DD
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface StoreCreditTransaction {
+  id: string;
+  storeCreditId: string;
+  type: 'ISSUE' | 'REDEEM' | 'REVERSAL';
+  amount: number; // positivo credita, negativo debita
+  balanceAfter: number;
+  saleId?: string;
+  returnId?: string;
+  paymentMethod?: PaymentMethod;
+  cashSessionId?: string;
+  employeeId?: string;
+  createdAt: string;
+}
+
+export interface IssueGiftCardInput {
+  amount: number;
+  paymentMethod: PaymentMethod;
+  customerId?: string;
+  expiresAt?: string;
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// CREDIÁRIO (FIADO)
+// ────────────────────────────────────────────────────────────────────────────
+
+export interface CustomerCreditAccount {
+  customerId: string;
+  creditLimit: number;
+  interestRate: number; // % ao mês, pro rata dia
+  finePercent: number; // Multa única sobre a parcela em atraso
+  graceDays: number; // Carência sem encargos após o vencimento
+  defaultInstallments: number;
+  installmentIntervalDays: number;
+  isBlocked: boolean;
+  notes?: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type SaveCreditAccountInput = Omit<CustomerCreditAccount, 'createdAt' | 'updatedAt'>;
+
+export type ReceivableStatus = 'OPEN' | 'PAID' | 'CANCELED';
+
+export interface Receivable {
+  id: string;
+  customerId: string;
+  saleId?: string;
+  installmentNumber: number;
+  installmentCount: number;
+  amount: number;
+  paidAmount: number; // Principal já pago
+  dueDate: string; // YYYY-MM-DD
+  status: ReceivableStatus;
+  interestPaidUntil?: string;
+  fineCharged: boolean;
+  paidAt?: string;
+  createdAt: string;
+  updatedAt: string;
+  daysOverdue: number;
+  fine: number;
+  interest: number;
+  amountDue: number; // Saldo + multa + juros calculados para hoje
+}
+
+export interface ReceivablePayment {
+  id: string;
+  customerId: string;
+  receivableId: string;
+  amount: number; // Principal abatido
+  interest: number;
+  fine: number;
+  total: number;
+  method: PaymentMethod;
+  cashSessionId?: string;
+  employeeId: string;
+  createdAt: string;
+}
+
+export interface CustomerStatement {
+  customerId: string;
+  customerName: string;
+  account?: CustomerCreditAccount;
+  balance: number;
+  charges: number;
+  totalDue: number;
+  overdueAmount: number;
+  availableCredit: number;
+  openReceivables: Receivable[];
+  recentPayments: ReceivablePayment[];
+  generatedAt: string;
 }
 
 // ────────────────────────────────────────────────────────────────────────────
@@ -285,6 +554,8 @@ export interface CashSession {
   closingBalance?: number; // Added for compatibility with auth-store
   status: CashSessionStatus;
   notes?: string;
+  approvedById?: string; // Gerente que aprovou a diferença do fechamento
+  approvedAt?: string;
   sales?: Sale[];
   movements?: CashMovement[];
 }
@@ -313,9 +584,69 @@ export interface CashSessionSummary {
   totalCanceled: number;
   totalWithdrawals: number;
   totalSupplies: number;
+  totalRefunds?: number; // Estornos de devoluções na sessão
+  totalAccountReceipts?: number; // Recebimentos de fiado na sessão
+  totalGiftCardSales?: number; // Vale-presentes vendidos na sessão
   movementCount: number;
   salesByMethod: PaymentMethodSummary[];
   cashInDrawer: number;
+  byMethod?: CashMethodBreakdown[]; // Esperado por forma de pagamento
+}
+
+export interface CashMethodBreakdown {
+  method: string;
+  sales: number;
+  receipts: number; // Recebimentos de fiado e vale-presentes vendidos
+  refunds: number;
+  expected: number; // Em dinheiro inclui abertura, suprimentos e sangrias
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// FECHAMENTO CEGO
+// ────────────────────────────────────────────────────────────────────────────
+
+export interface DenominationCount {
+  denomination: number;
+  quantity: number;
+}
+
+export interface MethodCount {
+  method: PaymentMethod;
+  amount: number;
+}
+
+export interface BlindCashClosingInput {
+  sessionId: string;
+  denominations: DenominationCount[];
+  methodCounts: MethodCount[]; // PIX, DEBIT, CREDIT, VOUCHER, OTHER
+  notes?: string;
+}
+
+export interface CashClosingCount {
+  id: string;
+  sessionId: string;
+  method: string;
+  expectedAmount: number;
+  countedAmount: number;
+  difference: number;
+  createdAt: string;
+}
+
+export interface CashClosingDenomination {
+  id: string;
+  sessionId: string;
+  denomination: number;
+  quantity: number;
+  amount: number;
+}
+
+export interface CashClosingReport {
+  summary: CashSessionSummary;
+  employeeName: string;
+  approvedByName?: string;
+  counts: CashClosingCount[];
+  denominations: CashClosingDenomination[];
+  tolerance: number;
 }
 
 // ────────────────────────────────────────────────────────────────────────────
@@ -402,6 +733,203 @@ export interface ScaleSettings {
   model: string;
 }
 
+/** Peso lido da balança (avulso ou pela leitura contínua) */
+export interface ScaleReading {
+  weightKg: number;
+  weightGrams: number;
+  stable: boolean;
+  overload: boolean;
+  negative: boolean;
+  tare: boolean;
+  unitPrice?: number | null; // Balança computadora
+  totalPrice?: number | null;
+  protocol: string;
+}
+
+/** Impressora de etiquetas (Zebra ZPL/EPL, Argox/Elgin PPLA) */
+export interface LabelPrinterConfig {
+  enabled: boolean;
+  language: 'zpl' | 'epl' | 'ppla';
+  connection: 'usb' | 'serial' | 'network';
+  port: string;
+  baudRate: number;
+  dpi: number; // 203 ou 300
+  labelWidthMm: number;
+  labelHeightMm: number;
+  gapMm: number; // Espaço entre etiquetas
+  darkness: number; // 0-30
+  timeoutMs: number;
+  mockMode: boolean;
+}
+
+/** Trabalho da fila de impressão persistente */
+export interface PrintJob {
+  id: string;
+  kind:
+    | 'RECEIPT'
+    | 'SERVICE_ORDER'
+    | 'ATTENDANT_ORDER'
+    | 'DANFE'
+    | 'STATEMENT'
+    | 'STORE_CREDIT'
+    | 'CASH_CLOSING'
+    | 'TEST';
+  reference?: string; // Ex: "Venda #12", "OS #40" ou chave de acesso
+  status: PrintJobStatus;
+  attempts: number;
+  maxAttempts: number;
+  lastError?: string;
+  nextAttemptAt: string;
+  printedAt?: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type PrintJobStatus = 'PENDING' | 'PRINTING' | 'DONE' | 'FAILED' | 'CANCELED';
+
+/** Sensores da impressora (DLE EOT / GS r), disponíveis em serial e rede */
+export interface PrinterSensorStatus {
+  online: boolean;
+  paperNearEnd: boolean;
+  paperOut: boolean;
+  coverOpen: boolean;
+  error: boolean; // Guilhotina ou mecanismo
+  drawerPinHigh: boolean;
+}
+
+/** Logo da loja convertido para a largura da bobina */
+export interface PrinterLogoInfo {
+  widthDots: number;
+  heightDots: number;
+}
+
+/** Simbologia de código de barras para impressão */
+export type BarcodeSymbology = 'EAN13' | 'EAN8' | 'CODE128' | 'GS1_128' | 'ITF' | 'CODE39';
+
+/** Formato detectado no código lido */
+export type BarcodeFormat =
+  | 'EAN13'
+  | 'EAN8'
+  | 'CODE128'
+  | 'GS1_128'
+  | 'CODE39'
+  | 'ITF'
+  | 'ITF14'
+  | 'DATA_MATRIX'
+  | 'QR'
+  | 'UNKNOWN';
+
+/** Identificadores de Aplicação GS1 lidos (GS1-128 / DataMatrix) */
+export interface Gs1Data {
+  gtin: string | null; // AI 01
+  lot: string | null; // AI 10
+  expirationDate: string | null; // AI 17 (AAAA-MM-DD)
+  serial: string | null; // AI 21
+  elements: { ai: string; value: string }[];
+}
+
+export interface ParsedBarcode {
+  code: string; // Sem identificador de simbologia
+  format: BarcodeFormat;
+  productCode: string | null; // EAN do produto quando GS1 ou ITF-14
+  gs1: Gs1Data | null;
+}
+
+/** Layout de código pesado: prefixo + código + valor (+ dígito ignorado) + DV */
+export interface WeightedBarcodeLayout {
+  prefix: string; // "2" ou "20" a "29"
+  prefixInCode?: boolean; // 2º dígito do prefixo é o 1º do código do produto
+  codeDigits: number; // 4 a 6
+  valueDigits: number; // 4 a 6
+  valueKind: 'weight' | 'price'; // Gramas ou centavos
+}
+
+export interface WeightedBarcodeConfig {
+  enabled: boolean;
+  layouts: WeightedBarcodeLayout[];
+}
+
+/** Exportação do cadastro de itens para balanças Toledo MGV / Filizola */
+export interface ScaleCatalogConfig {
+  format: 'toledo' | 'filizola';
+  department: number; // Toledo: 1 a 99
+  validityDays: number; // 0 = não imprime validade
+  outputDir: string | null; // Pasta lida pelo MGV / software Filizola
+  networkAddress: string | null; // ip:porta para envio direto
+}
+
+export interface ScaleCatalogExport {
+  fileName: string; // ITENSMGV.TXT ou CADTXT.TXT
+  content: string;
+  exported: number;
+  skipped: string[]; // "Produto: motivo"
+  writtenPath: string | null;
+  pushed: boolean;
+}
+
+/** Modo de demonstração: balança (pseudo-terminal) e impressora (rede) simuladas */
+export interface DemoHardwareInfo {
+  scalePort: string | null; // null no Windows: balança em modo mock
+  printerAddress: string; // 127.0.0.1:9100
+  printedJobs: number;
+  lastTranscript: string | null; // Último cupom transcrito ([INIT], [CUT]...)
+}
+
+export interface WeightedBarcode {
+  productCode: string;
+  weightGrams: number;
+  priceCents: number | null;
+  isWeight: boolean;
+}
+
+/** Produto do código lido no PDV; em códigos pesados, com a quantidade embutida */
+export interface ScannedProduct {
+  product: Product;
+  quantity: number;
+  totalPrice: number | null;
+  weighted: WeightedBarcode | null;
+}
+
+/** Documento impresso que aceita modelo próprio */
+export type TemplateDocument = 'receipt' | 'service_order' | 'attendant_order';
+
+export interface TemplateStyle {
+  bold?: boolean;
+  underline?: boolean;
+  doubleHeight?: boolean; // Fonte alta
+  doubleWidth?: boolean; // Fonte larga (metade das colunas)
+}
+
+/**
+ * Bloco do modelo de cupom. Textos aceitam `{{campo|filtro}}` com os campos
+ * em camelCase do documento; filtros: money, qty, upper, padN, maxN
+ */
+export type TemplateBlock =
+  | {
+      type: 'text';
+      text: string;
+      right?: string | null;
+      align?: 'left' | 'center' | 'right';
+      style?: TemplateStyle;
+    }
+  | { type: 'separator'; char?: string }
+  | { type: 'feed'; lines?: number }
+  | { type: 'if'; field: string; blocks: TemplateBlock[] }
+  | { type: 'items'; blocks: TemplateBlock[] }
+  | { type: 'barcode'; value: string; symbology?: BarcodeSymbology }
+  | { type: 'qrCode'; value: string }
+  | { type: 'logo' };
+
+export interface ReceiptTemplate {
+  blocks: TemplateBlock[];
+}
+
+export interface ReceiptTemplateSetting {
+  document: TemplateDocument;
+  custom: boolean; // Falso = layout padrão
+  template: ReceiptTemplate;
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // HISTÓRICO DE PREÇOS
 // ────────────────────────────────────────────────────────────────────────────
@@ -412,6 +940,7 @@ export interface PriceHistory {
   product?: Product;
   oldPrice: number;
   newPrice: number;
+  priceType?: 'SALE' | 'COST';
   reason?: string;
   employeeId: string;
   employee?: Employee;
@@ -528,6 +1057,9 @@ export interface CreateSaleInput {
 export interface CreateSalePayment {
   method: PaymentMethod;
   amount: number;
+  installments?: number; // Fiado: padrão do crediário do cliente
+  storeCreditCode?: string; // Vale-Crédito: código lido no vale
+  card?: SalePaymentCard; // Cartão: dados do TEF/POS para a NFC-e
 }
 
 export interface OpenCashSessionInput {
@@ -1,4 +1,6 @@
 export interface NfceItem {
+  /** Usado para resolver a regra tributária do produto/categoria */
+  productId?: string;
   code: string;
   description: string;
   ncm: string;
@@ -19,8 +21,11 @@ export interface EmitNfceRequest {
   items: NfceItem[];
   total: number;
   discount: number;
-  paymentMethod: string;
-  paymentValue: number;
+  /** Pagamento único (legado); ignorado quando `payments` ou `saleId` estão presentes */
+  paymentMethod?: string;
+  paymentValue?: number;
+  /** Um detPag por forma de pagamento */
+  payments?: EmissionPayment[];
 
   // Emitter
   emitterCnpj: string;
@@ -47,6 +52,15 @@ export interface EmitNfceRequest {
   certPassword: string;
 }
 
+export interface EmissionPayment {
+  method: string; // CASH, PIX, CREDIT, DEBIT, VOUCHER, OTHER
+  value: number;
+  cardIntegration?: number; // 1=TEF, 2=POS
+  acquirerCnpj?: string;
+  cardBrand?: string;
+  authorizationCode?: string;
+}
+
 export interface EmissionResponse {
   success: boolean;
   message: string;
@@ -55,6 +69,23 @@ export interface EmissionResponse {
   xml?: string; // XML Assinado
   danfeEscpos?: number[]; // Bytes para impressão direta (convertidos de Vec<u8>)
   qrcodeUrl?: string;
+  validationErrors: NfceValidationIssue[]; // Preenchido quando a validação local reprova o XML
+}
+
+/** Erro da validação local do XML (antes da assinatura) */
+export interface NfceValidationIssue {
+  source: 'SCHEMA' | 'RULE';
+  path: string; // Ex: NFe/infNFe/det[2]/prod/cEAN
+  message: string;
+}
+
+/** Reimpressão do DANFE NFC-e pela chave de acesso */
+export interface DanfeReprintResponse {
+  accessKey: string;
+  printed: boolean; // Enviado à fila da impressora térmica configurada
+  danfeEscpos?: number[];
+  danfePdf?: number[]; // PDF 80mm para e-mail/WhatsApp
+  pdfPath?: string;
 }
 
 export interface StatusResponse {
@@ -70,3 +101,104 @@ export interface OfflineNote {
   created_at: string;
   status: 'PENDING' | 'TRANSMITTED' | 'ERROR';
 }
+
+export interface FiscalExportSummary {
+  documents: number;
+  authorized: number;
+  canceled: number;
+  pending: number;
+  totalAuthorized: number;
+}
+
+export interface TaxRule {
+  id: string;
+  name: string;
+  crt: number; // 1=Simples Nacional, 3=Regime Normal
+  ncm?: string; // Prefixo de NCM
+  cfop?: string;
+  icmsOrigin: number;
+  icmsCode: string; // CSOSN (Simples) ou CST (Regime Normal)
+  icmsRate: number;
+  icmsBaseReduction: number;
+  stMva: number;
+  stRate: number;
+  fcpRate: number;
+  pisCst: string;
+  pisRate: number;
+  cofinsCst: string;
+  cofinsRate: number;
+  isActive: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type SaveTaxRule = Omit<TaxRule, 'id' | 'isActive' | 'createdAt' | 'updatedAt'> & {
+  id?: string;
+};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// NF-e (modelo 55)
+// ═══════════════════════════════════════════════════════════════════════════
+
+export interface EmitNfeRequest {
+  source: 'SALE' | 'SERVICE_ORDER' | 'RETURN';
+  sourceId: string;
+
+  // Emitter
+  emitterCnpj: string;
+  emitterIe: string;
+  emitterName: string;
+  emitterTradeName?: string;
+  emitterAddress: string;
+  emitterDistrict?: string;
+  emitterCity: string;
+  emitterCityCode: string; // IBGE
+  emitterState: string;
+  emitterCep: string;
+
+  natureOfOperation?: string; // Padrão: VENDA DE MERCADORIA
+  cfop?: string; // CFOP interno (5xxx); convertido para 6xxx se interestadual
+  additionalInfo?: string;
+
+  // Tributação padrão para itens sem regra cadastrada
+  icmsOrigin?: number;
+  icmsCst?: string;
+  pisCst?: string;
+  cofinsCst?: string;
+
+  freight?: EmissionFreight;
+  pdfPath?: string;
+}
+
+export interface EmissionFreight {
+  modality: number; // 0=CIF, 1=FOB, 2=Terceiros, 3/4=Próprio, 9=Sem frete
+  value?: number;
+  carrierDocument?: string;
+  carrierName?: string;
+  carrierIe?: string;
+  carrierAddress?: string;
+  carrierCity?: string;
+  carrierUf?: string;
+  volumes?: number;
+  species?: string;
+  netWeight?: number;
+  grossWeight?: number;
+}
+
+export interface NfeEmissionResponse {
+  success: boolean;
+  message: string;
+  accessKey?: string;
+  protocol?: string;
+  xml?: string; // nfeProc quando autorizada
+  danfePdf?: number[];
+  pdfPath?: string;
+  validationErrors: NfceValidationIssue[];
+}
+
+export interface CustomerFiscalData {
+  customerId: string;
+  cnpj?: string;
+  stateRegistration?: string; // IE ou "ISENTO"
+  cityCode?: string; // IBGE
+}